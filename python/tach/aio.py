@@ -0,0 +1,78 @@
+from __future__ import annotations
+
+import asyncio
+from functools import partial
+from typing import TYPE_CHECKING, Callable, TypeVar
+
+from tach import extension
+from tach.check_external import check_external as _check_external
+
+if TYPE_CHECKING:
+    from pathlib import Path
+
+    from tach.extension import DependencyGraph, Diagnostic, ProjectConfig
+
+T = TypeVar("T")
+
+
+async def _run_in_executor(func: Callable[[], T]) -> T:
+    # The extension releases the GIL while checking,
+    # so running in a worker thread does not block the event loop.
+    loop = asyncio.get_running_loop()
+    return await loop.run_in_executor(None, func)
+
+
+async def check(
+    project_root: Path,
+    project_config: ProjectConfig,
+    dependencies: bool = True,
+    interfaces: bool = True,
+    exclude_paths: list[str] | None = None,
+    modules: list[str] | None = None,
+    domains: list[str] | None = None,
+) -> list[Diagnostic]:
+    return await _run_in_executor(
+        partial(
+            extension.check,
+            project_root=project_root,
+            project_config=project_config,
+            dependencies=dependencies,
+            interfaces=interfaces,
+            exclude_paths=exclude_paths or [],
+            modules=modules or [],
+            domains=domains or [],
+        )
+    )
+
+
+async def check_external(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str] | None = None,
+) -> list[Diagnostic]:
+    return await _run_in_executor(
+        partial(
+            _check_external,
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=exclude_paths or [],
+        )
+    )
+
+
+async def get_dependency_graph(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str] | None = None,
+) -> DependencyGraph:
+    return await _run_in_executor(
+        partial(
+            extension.get_dependency_graph,
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=exclude_paths or [],
+        )
+    )
+
+
+__all__ = ["check", "check_external", "get_dependency_graph"]
@@ -0,0 +1,354 @@
+from __future__ import annotations
+
+import ast
+import json
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any, Callable
+
+from tach import __version__
+from tach.errors import TachError
+from tach.filesystem import module_to_pyfile_or_dir_path
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+API_SNAPSHOT_VERSION = 1
+DEFAULT_API_SNAPSHOT_PATH = Path("tach-api.json")
+
+
+@dataclass
+class ApiMember:
+    name: str
+    # 'function', 'class', 'method', 'variable' or 'import'
+    kind: str
+    # Parameters for functions and methods, bases for classes, and sources for imports
+    signature: str = ""
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"kind": self.kind, "signature": self.signature}
+
+    def describe(self) -> str:
+        return f"{self.kind} {self.name}{self.signature}"
+
+
+@dataclass
+class ApiSnapshot:
+    # The public members of each module, by name
+    modules: dict[str, dict[str, ApiMember]] = field(default_factory=dict)
+    tach_version: str = __version__
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "version": API_SNAPSHOT_VERSION,
+            "tach_version": self.tach_version,
+            "modules": {
+                module: {name: member.to_dict() for name, member in members.items()}
+                for module, members in self.modules.items()
+            },
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> ApiSnapshot:
+        version = data.get("version")
+        if version != API_SNAPSHOT_VERSION:
+            raise TachError(
+                f"Unsupported API snapshot version '{version}' "
+                f"(expected {API_SNAPSHOT_VERSION})."
+            )
+        return cls(
+            modules={
+                module: {
+                    name: ApiMember(name=name, **member)
+                    for name, member in members.items()
+                }
+                for module, members in data.get("modules", {}).items()
+            },
+            tach_version=data.get("tach_version", ""),
+        )
+
+
+@dataclass
+class ApiChange:
+    module: str
+    member: str
+    # 'removed', 'changed' or 'added'
+    change: str
+    old: ApiMember | None = None
+    new: ApiMember | None = None
+
+    @property
+    def is_breaking(self) -> bool:
+        return self.change != "added"
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "member": self.member,
+            "change": self.change,
+            "old": self.old.to_dict() if self.old else None,
+            "new": self.new.to_dict() if self.new else None,
+        }
+
+
+@dataclass
+class ApiDiff:
+    changes: list[ApiChange] = field(default_factory=list)
+
+    @property
+    def breaking_changes(self) -> list[ApiChange]:
+        return [change for change in self.changes if change.is_breaking]
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "breaking": len(self.breaking_changes),
+            "changes": [change.to_dict() for change in self.changes],
+        }
+
+
+def _dotted_name(expr: ast.expr) -> str:
+    if isinstance(expr, ast.Name):
+        return expr.id
+    if isinstance(expr, ast.Attribute):
+        return f"{_dotted_name(expr.value)}.{expr.attr}"
+    if isinstance(expr, ast.Subscript):
+        return _dotted_name(expr.value)
+    return "..."
+
+
+def _parameters(args: ast.arguments) -> str:
+    """Parameter names, kinds and whether they have defaults. Annotations are not
+    included, since they cannot be rendered consistently on every Python version."""
+    positional = list(getattr(args, "posonlyargs", [])) + list(args.args)
+    first_default = len(positional) - len(args.defaults)
+    parts: list[str] = []
+    for index, arg in enumerate(positional):
+        parts.append(arg.arg + ("=..." if index >= first_default else ""))
+        if index + 1 == len(getattr(args, "posonlyargs", [])):
+            parts.append("/")
+    if args.vararg:
+        parts.append(f"*{args.vararg.arg}")
+    elif args.kwonlyargs:
+        parts.append("*")
+    for arg, default in zip(args.kwonlyargs, args.kw_defaults):
+        parts.append(arg.arg + ("=..." if default is not None else ""))
+    if args.kwarg:
+        parts.append(f"**{args.kwarg.arg}")
+    return f"({', '.join(parts)})"
+
+
+def _function_kind(node: ast.FunctionDef | ast.AsyncFunctionDef, kind: str) -> str:
+    return f"async {kind}" if isinstance(node, ast.AsyncFunctionDef) else kind
+
+
+def _string_value(expr: ast.expr) -> str | None:
+    # Python 3.7 parses strings as 'ast.Str', which has 's' rather than 'value'
+    value = expr.value if hasattr(expr, "value") else getattr(expr, "s", None)
+    return value if isinstance(value, str) else None
+
+
+def _declared_all(tree: ast.Module) -> list[str] | None:
+    for node in tree.body:
+        if isinstance(node, ast.Assign) and any(
+            isinstance(target, ast.Name) and target.id == "__all__"
+            for target in node.targets
+        ):
+            if isinstance(node.value, (ast.List, ast.Tuple)):
+                values = [_string_value(element) for element in node.value.elts]
+                return [value for value in values if value is not None]
+    return None
+
+
+def _top_level_members(tree: ast.Module) -> tuple[list[ApiMember], set[str]]:
+    """Every member defined at the top level, and the names which are imported."""
+    members: list[ApiMember] = []
+    imported: set[str] = set()
+    for node in tree.body:
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
+            members.append(
+                ApiMember(
+                    node.name, _function_kind(node, "function"), _parameters(node.args)
+                )
+            )
+        elif isinstance(node, ast.ClassDef):
+            bases = ", ".join(_dotted_name(base) for base in node.bases)
+            members.append(ApiMember(node.name, "class", f"({bases})"))
+            members.extend(
+                ApiMember(
+                    f"{node.name}.{child.name}",
+                    _function_kind(child, "method"),
+                    _parameters(child.args),
+                )
+                for child in node.body
+                if isinstance(child, (ast.FunctionDef, ast.AsyncFunctionDef))
+                and (not child.name.startswith("_") or child.name == "__init__")
+            )
+        elif isinstance(node, (ast.Assign, ast.AnnAssign)):
+            targets = node.targets if isinstance(node, ast.Assign) else [node.target]
+            members.extend(
+                ApiMember(target.id, "variable")
+                for target in targets
+                if isinstance(target, ast.Name) and target.id != "__all__"
+            )
+        elif isinstance(node, (ast.Import, ast.ImportFrom)):
+            source = ""
+            if isinstance(node, ast.ImportFrom):
+                source = "." * node.level + (node.module or "")
+            for alias in node.names:
+                name = alias.asname or alias.name.split(".")[0]
+                imported.add(name)
+                origin = alias.name
+                if source:
+                    separator = "" if source.endswith(".") else "."
+                    origin = f"{source}{separator}{alias.name}"
+                members.append(ApiMember(name, "import", f" from {origin}"))
+    return members, imported
+
+
+def module_api(
+    source: str, is_exposed: Callable[[str], bool] | None = None
+) -> dict[str, ApiMember]:
+    """The public members of a module's source.
+
+    With 'is_exposed' (from the module's interfaces), members it matches are public.
+    Otherwise, the names in '__all__' are public, or every top-level definition
+    without a leading underscore if there is no '__all__'.
+    """
+    tree = ast.parse(source)
+    members, imported = _top_level_members(tree)
+    declared_all = _declared_all(tree)
+
+    def is_public(name: str) -> bool:
+        top_level = name.split(".")[0]
+        if is_exposed is not None:
+            return is_exposed(name) or is_exposed(top_level)
+        if declared_all is not None:
+            return top_level in declared_all
+        return not top_level.startswith("_") and top_level not in imported
+
+    return {
+        member.name: member
+        for member in sorted(members, key=lambda member: member.name)
+        if is_public(member.name)
+    }
+
+
+def _exposure_matcher(
+    project_config: ProjectConfig, module_path: str
+) -> Callable[[str], bool] | None:
+    patterns = [
+        re.compile(f"^{expose}$")
+        for interface in project_config.all_interfaces()
+        if any(
+            re.match(f"^{pattern}$", module_path) for pattern in interface.from_modules
+        )
+        for expose in interface.expose
+    ]
+    if not patterns:
+        return None
+    return lambda name: any(pattern.match(name) for pattern in patterns)
+
+
+def module_source_file(source_roots: tuple[Path, ...], module_path: str) -> Path | None:
+    path = module_to_pyfile_or_dir_path(source_roots, module_path)
+    if path is not None and path.is_dir():
+        path = path / "__init__.py"
+    return path if path is not None and path.is_file() else None
+
+
+def take_api_snapshot(project_root: Path, project_config: ProjectConfig) -> ApiSnapshot:
+    source_roots = tuple(
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    )
+    snapshot = ApiSnapshot()
+    for module in sorted(project_config.all_modules(), key=lambda module: module.path):
+        if module.path == "<root>":
+            continue
+        path = module_source_file(source_roots, module.path)
+        if path is None:
+            continue
+        try:
+            source = path.read_text()
+            snapshot.modules[module.path] = module_api(
+                source, _exposure_matcher(project_config, module.path)
+            )
+        except (SyntaxError, UnicodeDecodeError, OSError) as e:
+            raise TachError(f"Failed to read the public API of '{module.path}': {e}")
+    return snapshot
+
+
+def write_api_snapshot(snapshot: ApiSnapshot, path: Path) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(snapshot.to_dict(), indent=2) + "\n")
+
+
+def read_api_snapshot(path: Path) -> ApiSnapshot:
+    try:
+        data = json.loads(path.read_text())
+    except FileNotFoundError:
+        raise TachError(f"API snapshot file '{path}' does not exist.")
+    except json.JSONDecodeError as e:
+        raise TachError(f"API snapshot file '{path}' is not valid JSON: {e}")
+    return ApiSnapshot.from_dict(data)
+
+
+def compare_api_snapshots(baseline: ApiSnapshot, current: ApiSnapshot) -> ApiDiff:
+    changes: list[ApiChange] = []
+    for module in sorted(set(baseline.modules) | set(current.modules)):
+        old_members = baseline.modules.get(module, {})
+        new_members = current.modules.get(module, {})
+        for name in sorted(set(old_members) | set(new_members)):
+            old = old_members.get(name)
+            new = new_members.get(name)
+            if old == new:
+                continue
+            if new is None:
+                change = "removed"
+            elif old is None:
+                change = "added"
+            else:
+                change = "changed"
+            changes.append(ApiChange(module, name, change, old=old, new=new))
+    return ApiDiff(changes=changes)
+
+
+def render_api_diff(diff: ApiDiff) -> str:
+    if not diff.changes:
+        return "No public API changes since the snapshot."
+    lines: list[str] = []
+    for module in sorted({change.module for change in diff.changes}):
+        lines.append(f"{module}:")
+        for change in diff.changes:
+            if change.module != module:
+                continue
+            if change.change == "removed" and change.old:
+                lines.append(f"  - removed {change.old.describe()}")
+            elif change.change == "added" and change.new:
+                lines.append(f"  + added {change.new.describe()}")
+            elif change.old and change.new:
+                lines.append(
+                    f"  ~ changed {change.old.describe()} -> {change.new.describe()}"
+                )
+    breaking = len(diff.breaking_changes)
+    additions = len(diff.changes) - breaking
+    lines.append(f"\n{breaking} breaking change(s), {additions} addition(s).")
+    return "\n".join(lines)
+
+
+__all__ = [
+    "API_SNAPSHOT_VERSION",
+    "DEFAULT_API_SNAPSHOT_PATH",
+    "ApiMember",
+    "ApiSnapshot",
+    "ApiChange",
+    "ApiDiff",
+    "module_api",
+    "module_source_file",
+    "take_api_snapshot",
+    "write_api_snapshot",
+    "read_api_snapshot",
+    "compare_api_snapshots",
+    "render_api_diff",
+]
@@ -0,0 +1,202 @@
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach.extension import get_dependency_graph
+from tach.filesystem import module_to_pyfile_or_dir_path
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+BUILD_SYSTEMS = ["bazel", "pants"]
+BUILD_FILE_NAMES = {"bazel": "BUILD.bazel", "pants": "BUILD"}
+
+
+@dataclass
+class BuildTarget:
+    module: str
+    # The directory of the BUILD file, relative to the project root ('' for the root)
+    directory: str
+    name: str
+    sources: list[str]
+    excludes: list[str] = field(default_factory=list)
+    # The modules this module imports
+    dependencies: list[str] = field(default_factory=list)
+
+
+def _is_within(path: Path, directory: Path) -> bool:
+    try:
+        path.relative_to(directory)
+    except ValueError:
+        return False
+    return True
+
+
+def _relative(path: Path, project_root: Path) -> str:
+    relative = path.relative_to(project_root).as_posix()
+    return "" if relative == "." else relative
+
+
+def build_targets(
+    project_root: Path,
+    project_config: ProjectConfig,
+    modules: list[str],
+    edges: list[tuple[str, str]],
+) -> list[BuildTarget]:
+    """One target per module, with a dependency for each module it imports."""
+    project_root = project_root.resolve()
+    source_roots = tuple(
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    )
+    module_files: dict[str, Path] = {}
+    for module in modules:
+        path = module_to_pyfile_or_dir_path(source_roots, module)
+        if path is not None and _is_within(path, project_root):
+            module_files[module] = path
+
+    targets: list[BuildTarget] = []
+    for module, path in sorted(module_files.items()):
+        if path.is_dir():
+            directory, name, sources = path, path.name, ["**/*.py"]
+        else:
+            directory, name, sources = path.parent, path.stem, [path.name]
+        # Nested modules have their own targets
+        excludes = sorted(
+            other.relative_to(path).as_posix() + ("/**" if other.is_dir() else "")
+            for other in module_files.values()
+            if other != path and path.is_dir() and _is_within(other, path)
+        )
+        dependencies = sorted(
+            {
+                target
+                for source, target in edges
+                if source == module and target != module and target in module_files
+            }
+        )
+        targets.append(
+            BuildTarget(
+                module=module,
+                directory=_relative(directory, project_root),
+                name=name,
+                sources=sources,
+                excludes=excludes,
+                dependencies=dependencies,
+            )
+        )
+    return targets
+
+
+def compute_build_targets(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> list[BuildTarget]:
+    graph = get_dependency_graph(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+    )
+    return build_targets(
+        project_root,
+        project_config,
+        [node.path for node in graph.nodes],
+        [(edge.source, edge.target) for edge in graph.edges],
+    )
+
+
+def _label(target: BuildTarget, build_system: str) -> str:
+    if build_system == "bazel":
+        if target.directory.rsplit("/", 1)[-1] == target.name:
+            # '//a/b' is shorthand for '//a/b:b'
+            return f"//{target.directory}"
+        return f"//{target.directory}:{target.name}"
+    if not target.directory:
+        return f"//:{target.name}"
+    return f"{target.directory}:{target.name}"
+
+
+def _list(values: list[str]) -> str:
+    if len(values) < 2:
+        return "[" + "".join(f'"{value}"' for value in values) + "]"
+    items = "".join(f'        "{value}",\n' for value in values)
+    return f"[\n{items}    ]"
+
+
+def render_target(
+    target: BuildTarget, build_system: str, labels: dict[str, str]
+) -> str:
+    dependencies = [labels[dependency] for dependency in target.dependencies]
+    if build_system == "bazel":
+        if target.sources != ["**/*.py"]:
+            sources = _list(target.sources)
+        elif target.excludes:
+            sources = f'glob(["**/*.py"], exclude = {_list(target.excludes)})'
+        else:
+            sources = 'glob(["**/*.py"])'
+        return (
+            "py_library(\n"
+            f'    name = "{target.name}",\n'
+            f"    srcs = {sources},\n"
+            f"    deps = {_list(dependencies)},\n"
+            '    visibility = ["//visibility:public"],\n'
+            ")\n"
+        )
+    sources = target.sources + [f"!{exclude}" for exclude in target.excludes]
+    return (
+        "python_sources(\n"
+        f'    name="{target.name}",\n'
+        f"    sources={_list(sources)},\n"
+        f"    dependencies={_list(dependencies)},\n"
+        ")\n"
+    )
+
+
+def render_build_files(targets: list[BuildTarget], build_system: str) -> dict[str, str]:
+    """The contents of each BUILD file, by its path relative to the project root."""
+    labels = {target.module: _label(target, build_system) for target in targets}
+    file_name = BUILD_FILE_NAMES[build_system]
+    build_files: dict[str, list[str]] = {}
+    for target in targets:
+        path = f"{target.directory}/{file_name}" if target.directory else file_name
+        build_files.setdefault(path, []).append(
+            f"# Module '{target.module}'\n"
+            + render_target(target, build_system, labels)
+        )
+    return {path: "\n".join(contents) for path, contents in sorted(build_files.items())}
+
+
+def render_build_files_text(build_files: dict[str, str]) -> str:
+    return "\n".join(
+        f"# --- {path} ---\n{contents}" for path, contents in build_files.items()
+    )
+
+
+def write_build_files(
+    project_root: Path, build_files: dict[str, str], force: bool = False
+) -> tuple[list[Path], list[Path]]:
+    """Write each BUILD file, returning the (written, skipped) paths.
+    Existing files are skipped unless 'force' is set."""
+    written: list[Path] = []
+    skipped: list[Path] = []
+    for relative_path, contents in build_files.items():
+        path = project_root / relative_path
+        if path.exists() and not force:
+            skipped.append(path)
+            continue
+        path.write_text(contents)
+        written.append(path)
+    return written, skipped
+
+
+__all__ = [
+    "BUILD_SYSTEMS",
+    "BUILD_FILE_NAMES",
+    "BuildTarget",
+    "build_targets",
+    "compute_build_targets",
+    "render_target",
+    "render_build_files",
+    "render_build_files_text",
+    "write_build_files",
+]
@@ -0,0 +1,176 @@
+from __future__ import annotations
+
+import hashlib
+import sys
+import tarfile
+from pathlib import Path, PurePosixPath
+from typing import Iterable
+
+from tach import __version__
+from tach.constants import CONFIG_FILE_NAME
+from tach.errors import TachError
+
+# Relative to the '.tach' directory, which is the root of every archive
+CACHE_ARCHIVE_DIRS = ("computation-cache",)
+ARCHIVE_PREFIX = "tach-cache-"
+ARCHIVE_SUFFIX = ".tar.gz"
+
+LOCKFILE_NAMES = (
+    "uv.lock",
+    "poetry.lock",
+    "pdm.lock",
+    "Pipfile.lock",
+    "requirements.txt",
+    "pyproject.toml",
+)
+
+
+class CacheArchiveError(TachError): ...
+
+
+def _dot_tach(project_root: Path) -> Path:
+    return project_root / ".tach"
+
+
+def _key_inputs(project_root: Path, file_dependencies: Iterable[str]) -> list[Path]:
+    inputs: set[Path] = set()
+    config_path = project_root / f"{CONFIG_FILE_NAME}.toml"
+    if config_path.is_file():
+        inputs.add(config_path)
+    for name in LOCKFILE_NAMES:
+        path = project_root / name
+        if path.is_file():
+            inputs.add(path)
+    for pattern in file_dependencies:
+        inputs.update(path for path in project_root.glob(pattern) if path.is_file())
+    return sorted(inputs)
+
+
+def compute_cache_key(project_root: Path, file_dependencies: Iterable[str] = ()) -> str:
+    """A key which changes whenever cached results could be invalidated wholesale:
+    the tach version, the Python version, the project config, lockfiles,
+    and any configured cache file dependencies."""
+    digest = hashlib.sha256()
+    digest.update(f"tach={__version__}\n".encode())
+    digest.update(f"python={sys.version_info[0]}.{sys.version_info[1]}\n".encode())
+    for path in _key_inputs(project_root, file_dependencies):
+        digest.update(path.relative_to(project_root).as_posix().encode())
+        digest.update(b"\0")
+        digest.update(path.read_bytes())
+        digest.update(b"\0")
+    return f"tach-{__version__}-{digest.hexdigest()[:16]}"
+
+
+def _cache_files(project_root: Path) -> list[Path]:
+    dot_tach = _dot_tach(project_root)
+    files: list[Path] = []
+    for dirname in CACHE_ARCHIVE_DIRS:
+        cache_dir = dot_tach / dirname
+        if cache_dir.is_dir():
+            files.extend(path for path in cache_dir.rglob("*") if path.is_file())
+    return sorted(files)
+
+
+def export_cache(project_root: Path, output_dir: Path) -> Path | None:
+    """Write the cache to a content-addressed archive in output_dir.
+
+    Returns None when there is nothing cached. Since the archive name is derived
+    from its contents, exporting an unchanged cache reuses the existing archive.
+    Any other archives in output_dir are removed so CI caches do not grow unbounded.
+    """
+    dot_tach = _dot_tach(project_root)
+    files = _cache_files(project_root)
+    if not files:
+        return None
+
+    digest = hashlib.sha256()
+    for path in files:
+        digest.update(path.relative_to(dot_tach).as_posix().encode())
+        digest.update(b"\0")
+        digest.update(path.read_bytes())
+        digest.update(b"\0")
+
+    output_dir.mkdir(parents=True, exist_ok=True)
+    archive_path = (
+        output_dir / f"{ARCHIVE_PREFIX}{digest.hexdigest()[:32]}{ARCHIVE_SUFFIX}"
+    )
+    if not archive_path.exists():
+        partial_path = archive_path.with_name(archive_path.name + ".partial")
+        with tarfile.open(partial_path, "w:gz") as archive:
+            for path in files:
+                archive.add(
+                    str(path),
+                    arcname=path.relative_to(dot_tach).as_posix(),
+                    recursive=False,
+                )
+        partial_path.replace(archive_path)
+
+    for stale_path in output_dir.glob(f"{ARCHIVE_PREFIX}*{ARCHIVE_SUFFIX}"):
+        if stale_path != archive_path:
+            stale_path.unlink()
+    return archive_path
+
+
+def _validate_member(member: tarfile.TarInfo) -> None:
+    member_path = PurePosixPath(member.name)
+    if member_path.is_absolute() or ".." in member_path.parts:
+        raise CacheArchiveError(f"Refusing to extract unsafe path '{member.name}'.")
+    if not member_path.parts or member_path.parts[0] not in CACHE_ARCHIVE_DIRS:
+        raise CacheArchiveError(f"Unexpected path '{member.name}' in cache archive.")
+    if not (member.isfile() or member.isdir()):
+        raise CacheArchiveError(
+            f"Refusing to extract non-regular file '{member.name}'."
+        )
+
+
+def import_cache(project_root: Path, archive_path: Path) -> int:
+    """Restore the cache from an archive created by export_cache.
+
+    Returns the number of files restored. Existing cache entries are kept,
+    so a restored cache can only add results, never drop local ones.
+    """
+    if not archive_path.is_file():
+        raise CacheArchiveError(f"Cache archive '{archive_path}' does not exist.")
+    dot_tach = _dot_tach(project_root)
+    try:
+        with tarfile.open(archive_path, "r:gz") as archive:
+            members = archive.getmembers()
+            for member in members:
+                _validate_member(member)
+            restored = 0
+            for member in members:
+                target = dot_tach.joinpath(*PurePosixPath(member.name).parts)
+                if member.isdir():
+                    target.mkdir(parents=True, exist_ok=True)
+                    continue
+                source = archive.extractfile(member)
+                if source is None:
+                    continue
+                target.parent.mkdir(parents=True, exist_ok=True)
+                with source, open(target, "wb") as dest:
+                    dest.write(source.read())
+                restored += 1
+    except (tarfile.TarError, EOFError, OSError) as e:
+        raise CacheArchiveError(f"Failed to read cache archive: {e}") from e
+    return restored
+
+
+def find_cache_archive(directory: Path) -> Path | None:
+    """The most recently written archive in directory, if any."""
+    archives = [
+        path
+        for path in directory.glob(f"{ARCHIVE_PREFIX}*{ARCHIVE_SUFFIX}")
+        if path.is_file()
+    ]
+    if not archives:
+        return None
+    return max(archives, key=lambda path: path.stat().st_mtime)
+
+
+__all__ = [
+    "CacheArchiveError",
+    "compute_cache_key",
+    "export_cache",
+    "import_cache",
+    "find_cache_archive",
+]
@@ -0,0 +1,228 @@
+from __future__ import annotations
+
+import csv
+import io
+import json
+from dataclasses import dataclass, field
+from typing import Any
+
+from tach.colors import BCOLORS
+
+DEFAULT_TOP = 5
+
+
+@dataclass
+class ChainModule:
+    module: str
+    # The number of maximal dependency chains which pass through this module
+    chains: int = 0
+    # The length (in modules) of the longest chain which passes through this module
+    longest_chain: int = 0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "chains": self.chains,
+            "longest_chain": self.longest_chain,
+        }
+
+
+@dataclass
+class ChainReport:
+    # The longest chains, each starting from a module which nothing depends on
+    longest_chains: list[list[str]] = field(default_factory=list)
+    # The modules which appear in the most chains
+    bottlenecks: list[ChainModule] = field(default_factory=list)
+    # Groups of modules which depend on each other, whose internal edges are ignored
+    cycles: list[list[str]] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "longest_chains": self.longest_chains,
+            "bottlenecks": [module.to_dict() for module in self.bottlenecks],
+            "cycles": self.cycles,
+        }
+
+
+def _strongly_connected_components(graph: dict[str, list[str]]) -> list[list[str]]:
+    # Tarjan's algorithm, iterative to avoid recursion limits on deep graphs
+    index: dict[str, int] = {}
+    lowlink: dict[str, int] = {}
+    on_stack: set[str] = set()
+    stack: list[str] = []
+    components: list[list[str]] = []
+    for root in sorted(graph):
+        if root in index:
+            continue
+        work = [(root, iter(graph[root]))]
+        index[root] = lowlink[root] = len(index)
+        stack.append(root)
+        on_stack.add(root)
+        while work:
+            node, children = work[-1]
+            child = next(children, None)
+            if child is not None:
+                if child not in index:
+                    index[child] = lowlink[child] = len(index)
+                    stack.append(child)
+                    on_stack.add(child)
+                    work.append((child, iter(graph[child])))
+                elif child in on_stack:
+                    lowlink[node] = min(lowlink[node], index[child])
+                continue
+            work.pop()
+            if work:
+                parent = work[-1][0]
+                lowlink[parent] = min(lowlink[parent], lowlink[node])
+            if lowlink[node] == index[node]:
+                component: list[str] = []
+                while True:
+                    member = stack.pop()
+                    on_stack.discard(member)
+                    component.append(member)
+                    if member == node:
+                        break
+                components.append(sorted(component))
+    return components
+
+
+def _topological_order(graph: dict[str, list[str]]) -> list[str]:
+    # Dependents come before their dependencies
+    in_degree = {module: 0 for module in graph}
+    for dependencies in graph.values():
+        for dependency in dependencies:
+            in_degree[dependency] += 1
+    ready = sorted(module for module, degree in in_degree.items() if degree == 0)
+    order: list[str] = []
+    while ready:
+        module = ready.pop(0)
+        order.append(module)
+        for dependency in graph[module]:
+            in_degree[dependency] -= 1
+            if in_degree[dependency] == 0:
+                ready.append(dependency)
+    return order
+
+
+def analyze_chains(
+    module_dependencies: dict[str, list[str]], top: int = DEFAULT_TOP
+) -> ChainReport:
+    """Find the longest dependency chains, and the modules which appear in the most.
+
+    A chain follows 'depends_on' edges from a module which nothing depends on, to a
+    module without dependencies. Finding the longest chain through cycles is
+    intractable, so edges between modules in the same cycle are ignored.
+    """
+    graph: dict[str, list[str]] = {module: [] for module in module_dependencies}
+    for module, dependencies in module_dependencies.items():
+        for dependency in dependencies:
+            graph.setdefault(dependency, [])
+            if dependency != module and dependency not in graph[module]:
+                graph[module].append(dependency)
+
+    cycles = sorted(
+        component
+        for component in _strongly_connected_components(graph)
+        if len(component) > 1
+    )
+    cycle_of = {module: i for i, component in enumerate(cycles) for module in component}
+    for module, dependencies in graph.items():
+        graph[module] = sorted(
+            dependency
+            for dependency in dependencies
+            if module not in cycle_of or cycle_of.get(dependency) != cycle_of[module]
+        )
+
+    order = _topological_order(graph)
+    # The longest chain starting from each module, and the number of chains to a leaf
+    depth: dict[str, int] = {}
+    chains_from: dict[str, int] = {}
+    for module in reversed(order):
+        dependencies = graph[module]
+        depth[module] = 1 + max((depth[dep] for dep in dependencies), default=0)
+        chains_from[module] = sum(chains_from[dep] for dep in dependencies) or 1
+    # The longest chain ending at each module, and the number of chains from a root
+    height: dict[str, int] = {module: 1 for module in graph}
+    chains_to: dict[str, int] = {module: 0 for module in graph}
+    for module in order:
+        if chains_to[module] == 0:
+            chains_to[module] = 1
+        for dependency in graph[module]:
+            height[dependency] = max(height[dependency], height[module] + 1)
+            chains_to[dependency] += chains_to[module]
+
+    roots = set(graph) - {
+        dependency for dependencies in graph.values() for dependency in dependencies
+    }
+    longest_chains: list[list[str]] = []
+    for root in roots:
+        chain = [root]
+        while graph[chain[-1]]:
+            # Follow the deepest dependency, preferring module paths in sorted order
+            chain.append(min(graph[chain[-1]], key=lambda dep: (-depth[dep], dep)))
+        longest_chains.append(chain)
+    longest_chains.sort(key=lambda chain: (-len(chain), chain))
+
+    bottlenecks = sorted(
+        (
+            ChainModule(
+                module=module,
+                chains=chains_to[module] * chains_from[module],
+                longest_chain=height[module] + depth[module] - 1,
+            )
+            for module in graph
+        ),
+        key=lambda module: (-module.chains, -module.longest_chain, module.module),
+    )
+    return ChainReport(
+        longest_chains=longest_chains[:top],
+        bottlenecks=bottlenecks[:top],
+        cycles=cycles,
+    )
+
+
+def render_chains_text(report: ChainReport) -> str:
+    if not report.longest_chains:
+        return "No dependency chains found."
+    lines = [f"{BCOLORS.OKCYAN}Longest dependency chains:{BCOLORS.ENDC}"]
+    for chain in report.longest_chains:
+        lines.append(f"  ({len(chain)}) " + " -> ".join(chain))
+    lines.append(f"\n{BCOLORS.OKCYAN}Modules in the most chains:{BCOLORS.ENDC}")
+    width = max(len(module.module) for module in report.bottlenecks)
+    for module in report.bottlenecks:
+        lines.append(
+            f"  {module.module.ljust(width)}  {module.chains} chains, "
+            f"longest {module.longest_chain}"
+        )
+    if report.cycles:
+        lines.append(
+            f"\n{BCOLORS.WARNING}Dependencies within these cycles were ignored:"
+            f"{BCOLORS.ENDC}"
+        )
+        lines.extend(f"  {', '.join(cycle)}" for cycle in report.cycles)
+    return "\n".join(lines)
+
+
+def render_chains_json(report: ChainReport) -> str:
+    return json.dumps(report.to_dict(), indent=2)
+
+
+def render_chains_csv(report: ChainReport) -> str:
+    output = io.StringIO()
+    writer = csv.DictWriter(
+        output, fieldnames=["module", "chains", "longest_chain"], lineterminator="\n"
+    )
+    writer.writeheader()
+    writer.writerows(module.to_dict() for module in report.bottlenecks)
+    return output.getvalue()
+
+
+__all__ = [
+    "DEFAULT_TOP",
+    "ChainModule",
+    "ChainReport",
+    "analyze_chains",
+    "render_chains_text",
+    "render_chains_json",
+    "render_chains_csv",
+]
@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+from pathlib import Path
 from typing import TYPE_CHECKING
 
 from tach.errors import TachError
@@ -10,12 +11,12 @@ from tach.extension import (
 )
 from tach.utils.external import (
     get_module_mappings,
+    get_project_distributions,
     get_stdlib_modules,
 )
+from tach.utils.virtualenv import get_project_site_packages
 
 if TYPE_CHECKING:
-    from pathlib import Path
-
     from tach.extension import ProjectConfig
 
 
@@ -30,28 +31,39 @@ def extract_module_mappings(rename: list[str]) -> dict[str, list[str]]:
         ) from e
 
 
+def external_module_mappings(
+    project_config: ProjectConfig, site_packages: tuple[str, ...]
+) -> dict[str, list[str]]:
+    """The distributions providing each top-level module, including renames."""
+    module_mappings = dict(get_module_mappings(site_packages))
+    if project_config.external.rename:
+        module_mappings.update(extract_module_mappings(project_config.external.rename))
+    return module_mappings
+
+
 def check_external(
     project_root: Path,
     project_config: ProjectConfig,
     exclude_paths: list[str],
 ) -> list[Diagnostic]:
-    set_excluded_paths(
+    with set_excluded_paths(
         project_root=str(project_root),
         exclude_paths=exclude_paths,
         use_regex_matching=project_config.use_regex_matching,
-    )
-
-    metadata_module_mappings = get_module_mappings()
-    if project_config.external.rename:
-        metadata_module_mappings.update(
-            extract_module_mappings(project_config.external.rename)
+        include_paths=project_config.include,
+    ):
+        site_packages = get_project_site_packages(project_root, project_config)
+        return check_external_dependencies(
+            project_root=str(project_root),
+            project_config=project_config,
+            module_mappings=external_module_mappings(project_config, site_packages),
+            stdlib_modules=get_stdlib_modules(),
+            project_distributions=get_project_distributions(
+                project_root,
+                [Path(root) for root in project_config.source_roots],
+                site_packages,
+            ),
         )
-    return check_external_dependencies(
-        project_root=str(project_root),
-        project_config=project_config,
-        module_mappings=metadata_module_mappings,
-        stdlib_modules=get_stdlib_modules(),
-    )
 
 
-__all__ = ["check_external"]
+__all__ = ["check_external", "external_module_mappings"]
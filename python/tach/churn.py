@@ -0,0 +1,174 @@
+from __future__ import annotations
+
+import json
+from collections import Counter
+from dataclasses import dataclass
+from itertools import combinations
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+from tach.domains import get_module_dependencies
+from tach.filesystem.git_ops import get_commit_changes
+from tach.modules import module_for_file
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+DEFAULT_MAX_COMMITS = 500
+DEFAULT_MIN_CO_CHANGES = 5
+DEFAULT_MIN_CONFIDENCE = 0.5
+# Commits which change more modules than this (e.g. formatting or renames) are skipped
+MAX_MODULES_PER_COMMIT = 20
+
+
+@dataclass
+class CoChange:
+    # The two modules, in sorted order
+    first: str
+    second: str
+    # The number of commits which changed both modules
+    co_changes: int
+    # The share of commits changing the less frequently changed module,
+    # which also changed the other module
+    confidence: float
+    # Whether either module declares a dependency on the other
+    declared: bool
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "modules": [self.first, self.second],
+            "co_changes": self.co_changes,
+            "confidence": round(self.confidence, 3),
+            "declared": self.declared,
+        }
+
+
+def changed_modules(
+    commits: list[list[Path]], source_roots: list[Path], module_paths: list[str]
+) -> list[set[str]]:
+    """The modules changed by each commit, based on the Python files it changed."""
+    commit_modules: list[set[str]] = []
+    for files in commits:
+        modules = {
+            module_for_file(file_path, source_roots, module_paths)
+            for file_path in files
+            if file_path.suffix == ".py"
+        }
+        commit_modules.append({module for module in modules if module is not None})
+    return commit_modules
+
+
+def find_co_changes(
+    commit_modules: list[set[str]],
+    module_dependencies: dict[str, list[str]],
+    min_co_changes: int = DEFAULT_MIN_CO_CHANGES,
+    min_confidence: float = DEFAULT_MIN_CONFIDENCE,
+) -> list[CoChange]:
+    """Pairs of modules which are frequently changed in the same commits."""
+    changes: Counter[str] = Counter()
+    pairs: Counter[tuple[str, str]] = Counter()
+    for modules in commit_modules:
+        if len(modules) > MAX_MODULES_PER_COMMIT:
+            continue
+        changes.update(modules)
+        pairs.update(combinations(sorted(modules), 2))
+
+    co_changes = [
+        CoChange(
+            first=first,
+            second=second,
+            co_changes=count,
+            confidence=count / min(changes[first], changes[second]),
+            declared=second in module_dependencies.get(first, [])
+            or first in module_dependencies.get(second, []),
+        )
+        for (first, second), count in pairs.items()
+        if count >= min_co_changes
+        and count / min(changes[first], changes[second]) >= min_confidence
+    ]
+    return sorted(
+        co_changes,
+        key=lambda co_change: (
+            -co_change.co_changes,
+            -co_change.confidence,
+            co_change.first,
+            co_change.second,
+        ),
+    )
+
+
+def analyze_churn(
+    project_root: Path,
+    project_config: ProjectConfig,
+    max_commits: int = DEFAULT_MAX_COMMITS,
+    min_co_changes: int = DEFAULT_MIN_CO_CHANGES,
+    min_confidence: float = DEFAULT_MIN_CONFIDENCE,
+) -> list[CoChange]:
+    project_root = project_root.resolve()
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    module_dependencies = get_module_dependencies(project_config)
+    commit_modules = changed_modules(
+        get_commit_changes(project_root, max_commits=max_commits),
+        source_roots,
+        list(module_dependencies),
+    )
+    return find_co_changes(
+        commit_modules,
+        module_dependencies,
+        min_co_changes=min_co_changes,
+        min_confidence=min_confidence,
+    )
+
+
+def render_churn_text(co_changes: list[CoChange]) -> str:
+    hidden = [co_change for co_change in co_changes if not co_change.declared]
+    if not hidden:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}No hidden coupling found.{BCOLORS.ENDC}"
+        )
+    lines = [
+        f"{icons.WARNING} {BCOLORS.WARNING}'{co_change.first}' and "
+        f"'{co_change.second}' changed together in {co_change.co_changes} commits "
+        f"({co_change.confidence:.0%}), but neither depends on the other"
+        f"{BCOLORS.ENDC}"
+        for co_change in hidden
+    ]
+    lines.append(
+        f"\n{BCOLORS.WARNING}These modules may share an undeclared concern, "
+        f"such as duplicated logic or a data format.{BCOLORS.ENDC}"
+    )
+    return "\n".join(lines)
+
+
+def render_churn_json(co_changes: list[CoChange]) -> str:
+    return json.dumps(
+        {
+            "hidden_coupling": [
+                co_change.to_dict()
+                for co_change in co_changes
+                if not co_change.declared
+            ],
+            "declared": [
+                co_change.to_dict() for co_change in co_changes if co_change.declared
+            ],
+        },
+        indent=2,
+    )
+
+
+__all__ = [
+    "DEFAULT_MAX_COMMITS",
+    "DEFAULT_MIN_CO_CHANGES",
+    "DEFAULT_MIN_CONFIDENCE",
+    "CoChange",
+    "changed_modules",
+    "find_co_changes",
+    "analyze_churn",
+    "render_churn_text",
+    "render_churn_json",
+]
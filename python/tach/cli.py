@@ -2,17 +2,79 @@ from __future__ import annotations
 
 import argparse
 import json
+import os
 import sys
-from dataclasses import dataclass, field
+from dataclasses import dataclass, field, replace
 from enum import Enum
 from pathlib import Path
 from typing import TYPE_CHECKING, Any
 
 from tach import __version__, cache, icons
 from tach import filesystem as fs
+from tach.api_surface import (
+    DEFAULT_API_SNAPSHOT_PATH,
+    compare_api_snapshots,
+    read_api_snapshot,
+    render_api_diff,
+    take_api_snapshot,
+    write_api_snapshot,
+)
+from tach.build_targets import (
+    BUILD_SYSTEMS,
+    compute_build_targets,
+    render_build_files,
+    render_build_files_text,
+    write_build_files,
+)
+from tach.chains import (
+    DEFAULT_TOP,
+    analyze_chains,
+    render_chains_csv,
+    render_chains_json,
+    render_chains_text,
+)
+from tach.cache.ci import (
+    compute_cache_key,
+    export_cache,
+    find_cache_archive,
+    import_cache,
+)
 from tach.check_external import check_external
+from tach.churn import (
+    DEFAULT_MAX_COMMITS,
+    DEFAULT_MIN_CO_CHANGES,
+    DEFAULT_MIN_CONFIDENCE,
+    analyze_churn,
+    render_churn_json,
+    render_churn_text,
+)
+from tach.cohesion import (
+    DEFAULT_MAX_COUPLING,
+    compute_split_suggestions,
+    render_split_suggestions_json,
+    render_split_suggestions_text,
+)
 from tach.colors import BCOLORS
+from tach.config_diff import (
+    diff_configs,
+    read_config_source,
+    render_config_diff_json,
+    render_config_diff_text,
+)
 from tach.constants import CONFIG_FILE_NAME, TOOL_NAME
+from tach.deprecations import (
+    find_deprecated_modules,
+    render_deprecations_json,
+    render_deprecations_text,
+)
+from tach.django_apps import django_app_to_dict, render_django_apps
+from tach.duplicates import (
+    DEFAULT_SIMILARITY,
+    find_duplicate_clusters,
+    render_duplicates_csv,
+    render_duplicates_json,
+    render_duplicates_text,
+)
 from tach.errors import (
     TachCircularDependencyError,
     TachClosedBetaError,
@@ -21,32 +83,146 @@ from tach.errors import (
     TachSetupError,
     TachVisibilityError,
 )
+from tach.exit_codes import EXIT_SYNC_REQUIRED, ExitPolicy
+from tach.explain import render_explanation_json, render_explanation_text
 from tach.extension import (
     ProjectConfig,
+    cap_diagnostics,
     check,
     check_computation_cache,
+    check_streaming,
+    clear_cache,
     create_computation_cache_key,
+    create_new_domain,
+    create_new_module,
     detect_unused_dependencies,
+    explain_dependency,
+    explain_module,
     format_diagnostics,
+    get_cache_stats,
+    get_circular_dependencies,
+    get_dependency_graph,
+    get_django_apps,
+    get_module_owners,
+    prune_cache,
+    run_rpc_server,
     run_server,
     serialize_diagnostics_json,
+    serialize_diagnostics_sarif,
     update_computation_cache,
+    upgrade_project_config,
+)
+from tach.domains import (
+    NO_DOMAIN,
+    NO_MODULE,
+    NO_PACKAGE,
+    DomainReport,
+    get_module_dependencies,
+    group_by_domain,
 )
 from tach.filesystem import install_pre_commit
-from tach.logging import CallInfo, init_logging, logger
+from tach.god_modules import (
+    find_god_modules,
+    render_god_modules_csv,
+    render_god_modules_json,
+    render_god_modules_text,
+)
+from tach.graph_artifact import (
+    DEFAULT_GRAPH_ARTIFACT_PATH,
+    build_graph_artifact,
+    read_graph_artifact,
+    write_graph_artifact,
+)
+from tach.graph_formats import GRAPH_FORMATS, compute_graph_format
+from tach.graph_pruning import GraphPruning
+from tach.html_report import generate_html_report
+from tach.ignores import (
+    ignore_directive_usages,
+    render_ignores_json,
+    render_ignores_text,
+)
+from tach.import_linter import CONFIG_FILES as IMPORT_LINTER_CONFIG_FILES
+from tach.import_linter import (
+    find_import_linter_config,
+    import_contracts,
+    tach_to_contracts,
+)
+from tach.init import ask_yes_no, edit_proposal_interactive, propose_configuration
+from tach.lint import (
+    DEFAULT_MIN_UTILITY_CONSUMERS,
+    lint_architecture,
+    render_lint_json,
+    render_lint_text,
+)
+from tach.logging import (
+    CallInfo,
+    init_logging,
+    init_structured_logging,
+    log_cache_lookup,
+    log_phase,
+    logger,
+)
+from tach.markdown_report import generate_markdown_report
+from tach.metrics import (
+    build_metrics,
+    compute_metrics,
+    render_metrics_csv,
+    render_metrics_json,
+    render_metrics_table,
+)
 from tach.modularity import export_report, upload_report_to_gauge
+from tach.new_module import (
+    NewModuleOptions,
+    module_path_in_domain,
+    prompt_new_module_options,
+    register_new_module,
+)
+from tach.output_template import (
+    format_diagnostics_with_template,
+    validate_output_template,
+)
+from tach.owners import OwnerReport, group_by_owner
 from tach.parsing import extend_and_validate, parse_project_config
+from tach.progress import ProgressReporter, create_progress_reporter
+from tach.project_stats import compute_project_stats, render_project_stats
+from tach.ratchet import BudgetReport, check_budgets, ratchet_enabled
 from tach.report import external_dependency_report, report
 from tach.show import (
     generate_module_graph_dot_file,
     generate_module_graph_mermaid,
     generate_show_url,
 )
-from tach.sync import sync_project
+from tach.snapshot import (
+    DEFAULT_SNAPSHOT_PATH,
+    compare_snapshots,
+    read_snapshot,
+    render_snapshot_diff,
+    take_snapshot,
+    write_snapshot,
+)
+from tach.statistics import compute_statistics, format_statistics
+from tach.stubgen import STUB_PATH, generate_stubs, missing_stubs
+from tach.sync import (
+    check_sync,
+    render_sync_plan_json,
+    render_sync_plan_text,
+    render_unregistered_packages,
+    sync_project,
+)
 from tach.test import run_affected_tests
+from tach.trace import render_trace_json, render_trace_text, trace
+from tach.why import render_why_json, render_why_text
+from tach.workspace import WorkspaceReport, check_workspace
 
 if TYPE_CHECKING:
-    from tach.extension import UnusedDependencies
+    from tach.extension import (
+        CircularDependency,
+        DependencyGraph,
+        Diagnostic,
+        GraphArtifact,
+        NewPackagePolicy,
+        UnusedDependencies,
+    )
 
 
 def print_unused_dependencies(
@@ -67,6 +243,44 @@ def print_unused_dependencies(
     )
 
 
+def print_capped_diagnostics(
+    project_root: Path,
+    project_config: ProjectConfig,
+    diagnostics: list[Diagnostic],
+    output_template: str | None = None,
+    code_frames: bool = False,
+) -> None:
+    """Print diagnostics up to the configured caps, then summarize those left out."""
+    capped = cap_diagnostics(diagnostics, project_config)
+    if output_template is not None:
+        formatted = format_diagnostics_with_template(
+            output_template, capped.diagnostics
+        )
+    else:
+        formatted = format_diagnostics(
+            project_root=project_root,
+            diagnostics=capped.diagnostics,
+            code_frames=code_frames,
+        )
+    print(formatted, file=sys.stderr)
+    for omitted in capped.omitted:
+        print(f"{BCOLORS.WARNING}{omitted.message()}{BCOLORS.ENDC}", file=sys.stderr)
+
+
+def print_budget_report(report: BudgetReport, tightened: bool) -> None:
+    for code, count, budget in report.exceeded:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}'{code}' has {count} violation(s), over its budget of {budget}.{BCOLORS.ENDC}",
+            file=sys.stderr,
+        )
+    if tightened:
+        for code, budget in report.tightened.items():
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}Lowered the violation budget of '{code}' from {report.budgets[code]} to {budget}.{BCOLORS.ENDC}",
+                file=sys.stderr,
+            )
+
+
 def print_no_config_found(output_format: str = "text") -> None:
     if output_format == "json":
         json.dump({"error": "No config file found"}, sys.stdout)
@@ -115,17 +329,54 @@ def print_generated_module_graph_file(
         )
 
 
+def _format_cycle(cycle: CircularDependency) -> str:
+    modules = ", ".join(f"'{module_path}'" for module_path in cycle.modules)
+    lines = [
+        f"{icons.FAIL} {BCOLORS.FAIL}Circular dependency detected between modules {BCOLORS.ENDC}{modules}"
+    ]
+    for cycle_break in cycle.suggested_breaks:
+        imports = "import" if cycle_break.import_count == 1 else "imports"
+        lines.append(
+            f"    {BCOLORS.OKCYAN}Suggested fix: remove the dependency "
+            f"'{cycle_break.source}' -> '{cycle_break.target}' "
+            f"({cycle_break.import_count} {imports}){BCOLORS.ENDC}"
+        )
+    return "\n".join(lines)
+
+
 def print_circular_dependency_error(
-    module_paths: list[str], output_format: str = "text"
+    module_paths: list[str],
+    output_format: str = "text",
+    cycles: list[CircularDependency] | None = None,
 ) -> None:
     if output_format == "json":
         json.dump(
-            {"error": "Circular dependency", "dependencies": module_paths}, sys.stdout
+            {
+                "error": "Circular dependency",
+                "dependencies": module_paths,
+                "cycles": [
+                    {
+                        "modules": cycle.modules,
+                        "suggested_breaks": [
+                            {
+                                "source": cycle_break.source,
+                                "target": cycle_break.target,
+                                "import_count": cycle_break.import_count,
+                            }
+                            for cycle_break in cycle.suggested_breaks
+                        ],
+                    }
+                    for cycle in cycles or []
+                ],
+            },
+            sys.stdout,
         )
     else:
         print(
             "\n".join(
-                [
+                [_format_cycle(cycle) for cycle in cycles]
+                if cycles
+                else [
                     f"{icons.FAIL} {BCOLORS.FAIL}Circular dependency detected for module {BCOLORS.ENDC}'{module_path}'"
                     for module_path in module_paths
                 ]
@@ -156,6 +407,147 @@ def print_visibility_errors(
             )
 
 
+def print_owner_reports(
+    project_root: Path, owner_reports: list[OwnerReport], code_frames: bool = False
+) -> None:
+    for report in owner_reports:
+        print(
+            f"{BCOLORS.HEADER}{BCOLORS.BOLD}{report.owner}{BCOLORS.ENDC} "
+            f"({len(report.modules)} modules, {report.error_count} errors, {report.warning_count} warnings)",
+            file=sys.stderr,
+        )
+        if report.diagnostics:
+            print(
+                format_diagnostics(
+                    project_root=project_root,
+                    diagnostics=report.diagnostics,
+                    code_frames=code_frames,
+                ),
+                file=sys.stderr,
+            )
+
+
+def print_domain_reports(
+    project_root: Path, domain_reports: list[DomainReport], code_frames: bool = False
+) -> None:
+    for report in domain_reports:
+        print(
+            f"{BCOLORS.HEADER}{BCOLORS.BOLD}{report.domain}{BCOLORS.ENDC} "
+            f"({len(report.modules)} modules, fan-in {report.fan_in}, fan-out {report.fan_out}, "
+            f"instability {report.instability:.2f}, {report.error_count} errors, {report.warning_count} warnings)",
+            file=sys.stderr,
+        )
+        if report.diagnostics:
+            print(
+                format_diagnostics(
+                    project_root=project_root,
+                    diagnostics=report.diagnostics,
+                    code_frames=code_frames,
+                ),
+                file=sys.stderr,
+            )
+
+
+def print_workspace_report(workspace_root: Path, report: WorkspaceReport) -> None:
+    for project_report in report.projects:
+        print(
+            f"{BCOLORS.HEADER}{BCOLORS.BOLD}{project_report.root}{BCOLORS.ENDC} "
+            f"({project_report.error_count} errors, {project_report.warning_count} warnings)",
+            file=sys.stderr,
+        )
+        if project_report.diagnostics:
+            print(
+                format_diagnostics(
+                    project_root=workspace_root / project_report.root,
+                    diagnostics=project_report.diagnostics,
+                ),
+                file=sys.stderr,
+            )
+    if report.cross_project_imports:
+        print(
+            f"{BCOLORS.HEADER}{BCOLORS.BOLD}Cross-project imports{BCOLORS.ENDC} "
+            f"({len(report.cross_project_imports)})",
+            file=sys.stderr,
+        )
+        for cross_project_import in report.cross_project_imports:
+            print(
+                f"{BCOLORS.OKGREEN}{cross_project_import.file_path}:{cross_project_import.line_number}{BCOLORS.ENDC}: "
+                f"{BCOLORS.OKCYAN}'{cross_project_import.module_path}' is imported from "
+                f"'{cross_project_import.imported_project}'{BCOLORS.ENDC}",
+                file=sys.stderr,
+            )
+
+
+def add_exit_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--fail-on",
+        choices=["error", "warning", "never"],
+        default=None,
+        help="The lowest severity of violation which fails the check (default: error)",
+    )
+    parser.add_argument(
+        "--max-violations",
+        type=int,
+        default=None,
+        metavar="N",
+        help="Only fail when there are more than N failing violations.",
+    )
+    parser.add_argument(
+        "--category-exit-codes",
+        action="store_true",
+        help="Exit with distinct codes for dependency (2), interface (4), and external (8) failures, combined as bit flags.",
+    )
+
+
+def add_graph_pruning_arguments(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--omit-utilities",
+        action="store_true",
+        help="Leave utility modules out of the graph.",
+    )
+    parser.add_argument(
+        "--omit-root",
+        action="store_true",
+        help="Leave the root module out of the graph.",
+    )
+    parser.add_argument(
+        "--min-fan-in",
+        type=int,
+        default=0,
+        metavar="N",
+        help="Leave out modules which fewer than N other modules depend on.",
+    )
+
+
+def graph_pruning_from_args(args: argparse.Namespace) -> GraphPruning:
+    return GraphPruning(
+        utilities=args.omit_utilities,
+        root=args.omit_root,
+        min_fan_in=args.min_fan_in,
+    )
+
+
+def add_from_graph_argument(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--from-graph",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Read dependencies from a graph exported with 'tach export --format graph', "
+        "instead of analyzing the project.",
+    )
+
+
+def graph_artifact_from_args(args: argparse.Namespace) -> GraphArtifact | None:
+    if args.from_graph is None:
+        return None
+    try:
+        return read_graph_artifact(args.from_graph)
+    except TachError as e:
+        print(f"{icons.FAIL} {BCOLORS.FAIL}{e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+
 def add_base_arguments(parser: argparse.ArgumentParser) -> None:
     parser.add_argument(
         "-e",
@@ -175,9 +567,36 @@ def build_parser() -> argparse.ArgumentParser:
     parser.add_argument(
         "--version", action="version", version=f"{TOOL_NAME} {__version__}"
     )
+    parser.add_argument(
+        "--log-format",
+        choices=["text", "json"],
+        default="text",
+        help="With 'json', write structured logs with phase timings and cache hit rates to stderr.",
+    )
 
     subparsers = parser.add_subparsers(title="commands", dest="command")
 
+    ## tach init
+    init_parser = subparsers.add_parser(
+        "init",
+        prog=f"{TOOL_NAME} init",
+        help="Set up Tach in a new project",
+        description="Detect source roots, modules and layers, preview the generated "
+        f"{CONFIG_FILE_NAME}.toml, and optionally sync dependencies",
+    )
+    init_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Accept the proposed configuration and sync without prompting",
+    )
+    init_parser.add_argument(
+        "--force",
+        action="store_true",
+        help=f"Overwrite an existing {CONFIG_FILE_NAME}.toml",
+    )
+    add_base_arguments(init_parser)
+
     ## tach mod
     mod_parser = subparsers.add_parser(
         "mod",
@@ -195,6 +614,52 @@ def build_parser() -> argparse.ArgumentParser:
     )
     add_base_arguments(mod_parser)
 
+    ## tach upgrade
+    upgrade_parser = subparsers.add_parser(
+        "upgrade",
+        prog=f"{TOOL_NAME} upgrade",
+        help=f"Migrate {CONFIG_FILE_NAME}.toml to the latest config version",
+        description=f"Migrate deprecated keys in {CONFIG_FILE_NAME}.toml, "
+        "and set its version to the latest config version",
+    )
+    upgrade_parser.add_argument(
+        "--check",
+        action="store_true",
+        help="Report the migrations without writing them, and exit with an error "
+        "if any are needed",
+    )
+
+    ## tach diff-config
+    diff_config_parser = subparsers.add_parser(
+        "diff-config",
+        prog=f"{TOOL_NAME} diff-config",
+        help="Compare the modules, dependencies, and interfaces of two configs",
+        description="Compare the modules, dependencies, interfaces, and settings of "
+        "two project configs. Each config is a file, or a git ref at which to read "
+        f"{CONFIG_FILE_NAME}.toml.",
+    )
+    diff_config_parser.add_argument(
+        "old", help="The original config, as a file or git ref (e.g. 'main')"
+    )
+    diff_config_parser.add_argument(
+        "new",
+        nargs="?",
+        default=None,
+        help="The changed config, as a file or git ref "
+        f"(default: {CONFIG_FILE_NAME}.toml)",
+    )
+    diff_config_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    diff_config_parser.add_argument(
+        "--exit-code",
+        action="store_true",
+        help="Exit with an error if the configs differ",
+    )
+
     ## tach check
     check_parser = subparsers.add_parser(
         "check",
@@ -219,10 +684,53 @@ def build_parser() -> argparse.ArgumentParser:
     )
     check_parser.add_argument(
         "--output",
-        choices=["text", "json"],
-        default="text",
-        help="Output format (default: text)",
+        choices=["text", "json", "pretty", "sarif"],
+        default=None,
+        help="Output format (default: pretty when attached to a terminal, otherwise text). 'sarif' prints a SARIF 2.1.0 log for code scanning tools.",
+    )
+    check_parser.add_argument(
+        "--output-format",
+        metavar="TEMPLATE",
+        default=None,
+        help="Template for each diagnostic in text output, e.g. '{path}:{line}: {code} {message}'",
+    )
+    check_parser.add_argument(
+        "--group-by",
+        choices=["owner", "domain", "package"],
+        default=None,
+        help="Group diagnostics and per-group metrics by module owner, domain, or package.",
+    )
+    check_parser.add_argument(
+        "-q",
+        "--quiet",
+        action="store_true",
+        help="Only report errors, and print nothing when there are none.",
+    )
+    check_parser.add_argument(
+        "--statistics",
+        action="store_true",
+        help="Show counts of diagnostics per code and per module, instead of each diagnostic.",
     )
+    check_parser.add_argument(
+        "--module",
+        dest="modules",
+        action="append",
+        metavar="MODULE",
+        help="Only check files within this module. Can be given more than once.",
+    )
+    check_parser.add_argument(
+        "--domain",
+        dest="domains",
+        action="append",
+        metavar="DOMAIN",
+        help="Only check files within the modules of this domain. Can be given more than once.",
+    )
+    check_parser.add_argument(
+        "--progress",
+        action="store_true",
+        help="Report progress as JSON lines on stderr when not attached to a terminal.",
+    )
+    add_exit_arguments(check_parser)
     add_base_arguments(check_parser)
 
     ## tach check-external
@@ -232,8 +740,43 @@ def build_parser() -> argparse.ArgumentParser:
         help="Perform checks related to third-party dependencies",
         description="Perform checks related to third-party dependencies",
     )
+    check_parser_external.add_argument(
+        "--output-format",
+        metavar="TEMPLATE",
+        default=None,
+        help="Template for each diagnostic in text output, e.g. '{path}:{line}: {code} {message}'",
+    )
+    check_parser_external.add_argument(
+        "--group-by",
+        choices=["owner", "domain", "package", "module"],
+        default=None,
+        help="Group diagnostics by the owner, domain, package, or module of the importing file.",
+    )
+    add_exit_arguments(check_parser_external)
     add_base_arguments(check_parser_external)
 
+    ## tach workspace
+    workspace_parser = subparsers.add_parser(
+        "workspace",
+        prog=f"{TOOL_NAME} workspace",
+        help="Check several projects together and detect imports between them",
+        description="Check several projects, each with its own configuration, "
+        "and detect imports from one project into another",
+    )
+    workspace_parser.add_argument(
+        "project_roots",
+        type=Path,
+        nargs="+",
+        metavar="project_root",
+        help="The root directories of the projects to check",
+    )
+    workspace_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+
     ## tach sync
     sync_parser = subparsers.add_parser(
         "sync",
@@ -246,8 +789,161 @@ def build_parser() -> argparse.ArgumentParser:
         action="store_true",
         help="Add any missing dependencies, but do not remove unused dependencies.",
     )
+    sync_parser.add_argument(
+        "--check",
+        action="store_true",
+        help=f"Report the changes which would be made without making them, and exit with code {EXIT_SYNC_REQUIRED} if there are any.",
+    )
+    sync_parser.add_argument(
+        "--new-packages",
+        choices=["top-level", "nested", "warn"],
+        default=None,
+        help="How to register packages which do not belong to any module (default: 'new_packages' under [sync] in the project config, or 'warn')",
+    )
+    sync_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format for --check (default: text)",
+    )
     add_base_arguments(sync_parser)
 
+    ## tach rename-module
+    rename_module_parser = subparsers.add_parser(
+        "rename-module",
+        prog=f"{TOOL_NAME} rename-module",
+        help="Rename a module and update all references to it in your configuration.",
+        description="Rename a module and update all references to it in your configuration.",
+    )
+    rename_module_parser.add_argument(
+        "path", help="The current module path (e.g. 'core.utils')."
+    )
+    rename_module_parser.add_argument(
+        "new_path", help="The new module path (e.g. 'core.helpers')."
+    )
+
+    ## tach domain
+    domain_parser = subparsers.add_parser(
+        "domain",
+        prog=f"{TOOL_NAME} domain",
+        help="Create and delete domains, or move modules into them.",
+        description="Create and delete domains, or move modules into them.",
+    )
+    domain_subparsers = domain_parser.add_subparsers(
+        title="domain commands", dest="domain_command", required=True
+    )
+    domain_create_parser = domain_subparsers.add_parser(
+        "create",
+        prog=f"{TOOL_NAME} domain create",
+        help="Create a domain config file in the directory of a module",
+    )
+    domain_create_parser.add_argument(
+        "path", help="The module path of the domain (e.g. 'billing')."
+    )
+    domain_delete_parser = domain_subparsers.add_parser(
+        "delete",
+        prog=f"{TOOL_NAME} domain delete",
+        help=f"Delete a domain config file, moving its modules into {CONFIG_FILE_NAME}.toml",
+    )
+    domain_delete_parser.add_argument(
+        "path", help="The module path of the domain (e.g. 'billing')."
+    )
+    domain_move_parser = domain_subparsers.add_parser(
+        "move-module",
+        prog=f"{TOOL_NAME} domain move-module",
+        help="Move a module's configuration into a domain",
+    )
+    domain_move_parser.add_argument(
+        "path", metavar="module", help="The module path to move (e.g. 'billing.api')."
+    )
+    domain_move_parser.add_argument(
+        "domain", help="The module path of the domain (e.g. 'billing')."
+    )
+
+    ## tach new-domain
+    new_domain_parser = subparsers.add_parser(
+        "new-domain",
+        prog=f"{TOOL_NAME} new-domain",
+        help="Scaffold a new domain from the domain template in your configuration.",
+        description="Scaffold a new domain from the domain template in your configuration.",
+    )
+    new_domain_parser.add_argument(
+        "path", help="The module path of the new domain (e.g. 'billing')."
+    )
+
+    ## tach new-module
+    new_module_parser = subparsers.add_parser(
+        "new-module",
+        prog=f"{TOOL_NAME} new-module",
+        help="Create a new module's package and add it to your configuration.",
+        description="Create a new module's package and add it to your configuration. "
+        "Any layer, domain, or interface which is not given as a flag is prompted for.",
+    )
+    new_module_parser.add_argument(
+        "path", help="The module path of the new module (e.g. 'billing.invoices')."
+    )
+    new_module_parser.add_argument(
+        "--layer", help="The layer of the module, from the layers in your config."
+    )
+    new_module_parser.add_argument(
+        "--domain",
+        help="The domain of the module. The module path is resolved within the domain.",
+    )
+    new_module_parser.add_argument(
+        "--interface",
+        nargs="*",
+        metavar="MEMBER",
+        help="Add an interface for the module which exposes these members.",
+    )
+    new_module_parser.add_argument(
+        "-y",
+        "--yes",
+        action="store_true",
+        help="Do not prompt for anything which is not given as a flag",
+    )
+
+    ## tach import-linter
+    import_linter_parser = subparsers.add_parser(
+        "import-linter",
+        prog=f"{TOOL_NAME} import-linter",
+        help="Convert import-linter contracts to and from your configuration.",
+        description="Convert import-linter contracts ('layers', 'forbidden', and "
+        "'independence') to and from your configuration.",
+    )
+    import_linter_subparsers = import_linter_parser.add_subparsers(
+        title="import-linter commands", dest="import_linter_command", required=True
+    )
+    import_linter_import_parser = import_linter_subparsers.add_parser(
+        "import",
+        prog=f"{TOOL_NAME} import-linter import",
+        help=f"Write {CONFIG_FILE_NAME}.toml from import-linter contracts",
+    )
+    import_linter_import_parser.add_argument(
+        "path",
+        type=Path,
+        nargs="?",
+        default=None,
+        help="The import-linter config (default: the first of "
+        f"{', '.join(IMPORT_LINTER_CONFIG_FILES)} which configures import-linter)",
+    )
+    import_linter_import_parser.add_argument(
+        "--force",
+        action="store_true",
+        help=f"Overwrite an existing {CONFIG_FILE_NAME}.toml",
+    )
+    import_linter_export_parser = import_linter_subparsers.add_parser(
+        "export",
+        prog=f"{TOOL_NAME} import-linter export",
+        help="Print import-linter contracts for your configuration",
+    )
+    import_linter_export_parser.add_argument(
+        "-o",
+        "--output",
+        type=Path,
+        default=None,
+        help="Write the contracts to this file (e.g. .importlinter) instead",
+    )
+
     ## tach report
     report_parser = subparsers.add_parser(
         "report",
@@ -256,7 +952,10 @@ def build_parser() -> argparse.ArgumentParser:
         description="Create a report of dependencies and usages.",
     )
     report_parser.add_argument(
-        "path", help="The path or directory path used to generate the report."
+        "path",
+        nargs="?",
+        default=None,
+        help="The path or directory path used to generate the report. Not used with --html or --format markdown.",
     )
     # Report type flags
     report_parser.add_argument(
@@ -296,6 +995,26 @@ def build_parser() -> argparse.ArgumentParser:
         action="store_true",
         help="Group lines by module and print each without any formatting.",
     )
+    report_parser.add_argument(
+        "--html",
+        type=Path,
+        default=None,
+        metavar="OUTPUT_DIR",
+        help="Generate a static HTML report of the whole project in the given directory.",
+    )
+    report_parser.add_argument(
+        "--format",
+        choices=["text", "markdown"],
+        default="text",
+        help="Report format. Markdown summarizes changes since --base, e.g. for a pull request comment (default: text)",
+    )
+    report_parser.add_argument(
+        "--base",
+        type=str,
+        default="main",
+        help="The base commit to compare against in a markdown report. [default: 'main']",
+    )
+    add_from_graph_argument(report_parser)
     add_base_arguments(report_parser)
 
     ## tach show
@@ -321,6 +1040,16 @@ def build_parser() -> argparse.ArgumentParser:
         action="store_true",
         help="Generate a mermaid.js graph instead of a DOT file.",
     )
+    show_parser.add_argument(
+        "--collapse-domains",
+        action="store_true",
+        help="Show each domain as a single node, with the number of dependencies between domains.",
+    )
+    show_parser.add_argument(
+        "--collapse-packages",
+        action="store_true",
+        help="Show each package as a single node, with the number of dependencies between packages.",
+    )
     show_parser.add_argument(
         "-o",
         "--out",
@@ -329,8 +1058,341 @@ def build_parser() -> argparse.ArgumentParser:
         default=None,
         help="Specify an output path for a locally generated module graph file.",
     )
+    add_graph_pruning_arguments(show_parser)
+    add_from_graph_argument(show_parser)
 
-    ## tach install
+    ## tach metrics
+    metrics_parser = subparsers.add_parser(
+        "metrics",
+        prog=f"{TOOL_NAME} metrics",
+        help="Compute coupling, instability, and abstractness metrics for each module",
+        description="Compute coupling, instability, and abstractness metrics for each module",
+    )
+    metrics_parser.add_argument(
+        "--output",
+        choices=["table", "json", "csv"],
+        default="table",
+        help="Output format (default: table)",
+    )
+    metrics_analysis_group = metrics_parser.add_mutually_exclusive_group()
+    metrics_analysis_group.add_argument(
+        "--god-modules",
+        action="store_true",
+        help="Report modules whose fan-in and fan-out both exceed the thresholds in the [god_modules] config.",
+    )
+    metrics_analysis_group.add_argument(
+        "--chains",
+        action="store_true",
+        help="Report the longest dependency chains, and the modules which appear in the most chains.",
+    )
+    metrics_analysis_group.add_argument(
+        "--duplicates",
+        action="store_true",
+        help="Report groups of modules whose dependencies and imports are (nearly) identical.",
+    )
+    metrics_parser.add_argument(
+        "--similarity",
+        type=float,
+        default=DEFAULT_SIMILARITY,
+        metavar="RATIO",
+        help=f"The similarity at which modules are reported with --duplicates (default: {DEFAULT_SIMILARITY})",
+    )
+    metrics_parser.add_argument(
+        "--top",
+        type=int,
+        default=DEFAULT_TOP,
+        metavar="N",
+        help=f"The number of chains and modules to report with --chains (default: {DEFAULT_TOP})",
+    )
+    add_from_graph_argument(metrics_parser)
+    add_base_arguments(metrics_parser)
+
+    ## tach stats
+    stats_parser = subparsers.add_parser(
+        "stats",
+        prog=f"{TOOL_NAME} stats",
+        help="Print an overview of the project's modules, dependencies, and adoption",
+        description="Print an overview of the project's modules, dependencies, and adoption",
+    )
+    stats_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(stats_parser)
+
+    ## tach django-apps
+    django_apps_parser = subparsers.add_parser(
+        "django-apps",
+        prog=f"{TOOL_NAME} django-apps",
+        help="Show how each Django app label resolves to a module",
+        description="Show how each Django app label resolves to a module",
+    )
+    django_apps_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+
+    ## tach suggest-splits
+    splits_parser = subparsers.add_parser(
+        "suggest-splits",
+        prog=f"{TOOL_NAME} suggest-splits",
+        help="Suggest how to split modules whose files form weakly-connected groups",
+        description="Suggest how to split modules whose files form weakly-connected groups",
+    )
+    splits_parser.add_argument(
+        "modules",
+        nargs="*",
+        metavar="module",
+        help="The module paths to analyze (default: all modules)",
+    )
+    splits_parser.add_argument(
+        "--max-coupling",
+        type=float,
+        default=DEFAULT_MAX_COUPLING,
+        metavar="RATIO",
+        help=f"The largest share of imports between files which may cross a suggested boundary (default: {DEFAULT_MAX_COUPLING})",
+    )
+    splits_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(splits_parser)
+
+    ## tach why
+    why_parser = subparsers.add_parser(
+        "why",
+        prog=f"{TOOL_NAME} why",
+        help="Show the imports which make one module depend on another",
+        description="Show the imports which make one module depend on another, or the shortest chain of dependencies between them",
+    )
+    why_parser.add_argument(
+        "source",
+        help="The module path which depends on 'target'",
+    )
+    why_parser.add_argument(
+        "target",
+        help="The module path which 'source' depends on",
+    )
+    why_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_from_graph_argument(why_parser)
+    add_base_arguments(why_parser)
+
+    ## tach explain-module
+    explain_parser = subparsers.add_parser(
+        "explain-module",
+        prog=f"{TOOL_NAME} explain-module",
+        help="Show the effective configuration of a module, and where each part is declared",
+        description="Show the effective configuration of the module containing a file or module path: its entry, domain, layer, interface and suppressions, with the config file and line each is declared on",
+    )
+    explain_parser.add_argument(
+        "path",
+        help="A file, directory or module path",
+    )
+    explain_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(explain_parser)
+
+    ## tach trace
+    trace_parser = subparsers.add_parser(
+        "trace",
+        prog=f"{TOOL_NAME} trace",
+        help="Show every import and reference Tach finds in a file, and how each is treated",
+        description="Show every import and reference Tach finds in a file, how each is categorized, and why any are ignored",
+    )
+    trace_parser.add_argument(
+        "file_path",
+        type=Path,
+        help="The Python file to trace",
+    )
+    trace_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(trace_parser)
+
+    ## tach ignores
+    ignores_parser = subparsers.add_parser(
+        "ignores",
+        prog=f"{TOOL_NAME} ignores",
+        help="Show each 'tach-ignore' directive and the violations it suppressed",
+        description="Run 'tach check' and show each 'tach-ignore' directive along with the violations it suppressed, so stale directives can be told apart from ones which are still needed",
+    )
+    ignores_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(ignores_parser)
+
+    ## tach deprecations
+    deprecations_parser = subparsers.add_parser(
+        "deprecations",
+        prog=f"{TOOL_NAME} deprecations",
+        help="Show the remaining consumers of each deprecated module",
+        description="Show the modules which still use each deprecated module, to track progress towards removing it",
+    )
+    deprecations_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    add_base_arguments(deprecations_parser)
+
+    ## tach lint
+    lint_parser = subparsers.add_parser(
+        "lint",
+        prog=f"{TOOL_NAME} lint",
+        help="Report configuration which has no effect, such as unused interfaces",
+        description="Report configuration which has no effect, such as unused interfaces",
+    )
+    lint_parser.add_argument(
+        "--min-utility-consumers",
+        type=int,
+        default=DEFAULT_MIN_UTILITY_CONSUMERS,
+        metavar="N",
+        help=f"Report utility modules imported by fewer than this many modules (default: {DEFAULT_MIN_UTILITY_CONSUMERS})",
+    )
+    lint_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+
+    ## tach churn
+    churn_parser = subparsers.add_parser(
+        "churn",
+        prog=f"{TOOL_NAME} churn",
+        help="Find modules which change together in git history without depending on each other",
+        description="Find modules which change together in git history without depending on each other",
+    )
+    churn_parser.add_argument(
+        "--max-commits",
+        type=int,
+        default=DEFAULT_MAX_COMMITS,
+        metavar="N",
+        help=f"The number of recent commits to analyze (default: {DEFAULT_MAX_COMMITS})",
+    )
+    churn_parser.add_argument(
+        "--min-co-changes",
+        type=int,
+        default=DEFAULT_MIN_CO_CHANGES,
+        metavar="N",
+        help=f"Only report modules which changed together in at least this many commits (default: {DEFAULT_MIN_CO_CHANGES})",
+    )
+    churn_parser.add_argument(
+        "--min-confidence",
+        type=float,
+        default=DEFAULT_MIN_CONFIDENCE,
+        metavar="RATIO",
+        help=f"Only report modules where at least this share of the changes to either module changed both (default: {DEFAULT_MIN_CONFIDENCE})",
+    )
+    churn_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+
+    ## tach snapshot
+    snapshot_parser = subparsers.add_parser(
+        "snapshot",
+        prog=f"{TOOL_NAME} snapshot",
+        help="Record the dependency graph and violation counts, or compare against a recording",
+        description="Record the dependency graph and violation counts, or compare against a recording",
+    )
+    snapshot_subparsers = snapshot_parser.add_subparsers(
+        title="snapshot commands", dest="snapshot_command", required=True
+    )
+    snapshot_write_parser = snapshot_subparsers.add_parser(
+        "write",
+        prog=f"{TOOL_NAME} snapshot write",
+        help="Write a snapshot of the current architecture",
+    )
+    snapshot_compare_parser = snapshot_subparsers.add_parser(
+        "compare",
+        prog=f"{TOOL_NAME} snapshot compare",
+        help="Compare the current architecture against a snapshot and report drift",
+    )
+    for snapshot_subparser in (snapshot_write_parser, snapshot_compare_parser):
+        snapshot_subparser.add_argument(
+            "path",
+            type=Path,
+            nargs="?",
+            default=DEFAULT_SNAPSHOT_PATH,
+            help=f"Path to the snapshot file (default: {DEFAULT_SNAPSHOT_PATH})",
+        )
+        add_base_arguments(snapshot_subparser)
+    snapshot_compare_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+    snapshot_compare_parser.add_argument(
+        "--max-drift",
+        type=int,
+        default=None,
+        metavar="SCORE",
+        help="Exit with an error if the drift score is greater than this value.",
+    )
+
+    ## tach api
+    api_parser = subparsers.add_parser(
+        "api",
+        prog=f"{TOOL_NAME} api",
+        help="Record the public API of each module, or report changes since a recording",
+        description="Record the public API of each module, or report changes since a recording",
+    )
+    api_subparsers = api_parser.add_subparsers(
+        title="api commands", dest="api_command", required=True
+    )
+    api_snapshot_parser = api_subparsers.add_parser(
+        "snapshot",
+        prog=f"{TOOL_NAME} api snapshot",
+        help="Write a snapshot of the public API of each module",
+    )
+    api_diff_parser = api_subparsers.add_parser(
+        "diff",
+        prog=f"{TOOL_NAME} api diff",
+        help="Report public members which were removed or changed since the snapshot",
+    )
+    for api_subparser in (api_snapshot_parser, api_diff_parser):
+        api_subparser.add_argument(
+            "path",
+            type=Path,
+            nargs="?",
+            default=DEFAULT_API_SNAPSHOT_PATH,
+            help=f"Path to the API snapshot file (default: {DEFAULT_API_SNAPSHOT_PATH})",
+        )
+        add_base_arguments(api_subparser)
+    api_diff_parser.add_argument(
+        "--output",
+        choices=["text", "json"],
+        default="text",
+        help="Output format (default: text)",
+    )
+
+    ## tach install
     install_parser = subparsers.add_parser(
         "install",
         prog=f"{TOOL_NAME} install",
@@ -402,22 +1464,133 @@ def build_parser() -> argparse.ArgumentParser:
         type=Path,
         nargs="?",
         default=None,
-        help="Specify an output path for the modularity report [DEFAULT: 'modularity_report.json']",
+        help="Specify an output path for the modularity report [DEFAULT: 'modularity_report.json']. "
+        "With a pydeps or snakefood format, write the graph there instead of printing it. "
+        "With the graph format, write the graph artifact there [DEFAULT: 'tach-graph.json'].",
     )
     export_parser.add_argument(
         "-f",
         "--force",
         action="store_true",
-        help="Ignore warnings and force the report to be generated.",
+        help="Ignore warnings and force the report to be generated. With a build "
+        "system format, overwrite existing BUILD files.",
+    )
+    export_parser.add_argument(
+        "--format",
+        choices=["modularity", "graph", *BUILD_SYSTEMS, *GRAPH_FORMATS],
+        default="modularity",
+        help="Export a modularity report, the fully-resolved dependency graph for "
+        "use with --from-graph, a BUILD file target for each module with a "
+        "dependency on each module it imports, or the dependency graph in the "
+        "format of pydeps or snakefood (default: modularity)",
+    )
+    export_parser.add_argument(
+        "--write",
+        action="store_true",
+        help="Write the BUILD files into each module's directory, instead of printing "
+        "them",
+    )
+    add_graph_pruning_arguments(export_parser)
+    add_base_arguments(export_parser)
+
+    ## tach generate-stubs
+    generate_stubs_parser = subparsers.add_parser(
+        "generate-stubs",
+        prog=f"{TOOL_NAME} generate-stubs",
+        help=f"Generate type stubs for the {TOOL_NAME} extension module",
+        description=f"Generate type stubs for the {TOOL_NAME} extension module",
+    )
+    generate_stubs_parser.add_argument(
+        "-o",
+        "--output",
+        type=Path,
+        nargs="?",
+        default=None,
+        help="Specify an output path for the stubs [DEFAULT: the installed 'extension.pyi']",
+    )
+    generate_stubs_parser.add_argument(
+        "--check",
+        action="store_true",
+        help="Do not write stubs, and exit with an error if the existing stubs are out of date.",
+    )
+
+    ## tach cache
+    cache_parser = subparsers.add_parser(
+        "cache",
+        prog=f"{TOOL_NAME} cache",
+        help=f"Manage the {TOOL_NAME} computation cache",
+        description=f"Manage the {TOOL_NAME} computation cache",
+    )
+    cache_subparsers = cache_parser.add_subparsers(
+        title="cache commands", dest="cache_command", required=True
+    )
+    cache_subparsers.add_parser(
+        "clear",
+        prog=f"{TOOL_NAME} cache clear",
+        help="Remove all cached results",
+    )
+    cache_subparsers.add_parser(
+        "stats",
+        prog=f"{TOOL_NAME} cache stats",
+        help="Show the location, size, and number of entries in the cache",
+    )
+    cache_prune_parser = cache_subparsers.add_parser(
+        "prune",
+        prog=f"{TOOL_NAME} cache prune",
+        help="Remove cached results older than a given number of days",
+    )
+    cache_prune_parser.add_argument(
+        "--older-than",
+        type=int,
+        required=True,
+        metavar="DAYS",
+        help="Remove cached results created more than this many days ago.",
+    )
+    cache_subparsers.add_parser(
+        "backend",
+        prog=f"{TOOL_NAME} cache backend",
+        help="Show the configured cache backend",
+    )
+    cache_subparsers.add_parser(
+        "key",
+        prog=f"{TOOL_NAME} cache key",
+        help="Print a CI cache key derived from the project config and lockfiles",
+    )
+    cache_export_parser = cache_subparsers.add_parser(
+        "export",
+        prog=f"{TOOL_NAME} cache export",
+        help="Write the cache to a content-addressed archive in a directory",
+    )
+    cache_export_parser.add_argument(
+        "path",
+        type=Path,
+        metavar="DIR",
+        help="Directory to write the cache archive into.",
+    )
+    cache_import_parser = cache_subparsers.add_parser(
+        "import",
+        prog=f"{TOOL_NAME} cache import",
+        help="Restore the cache from an archive written by 'tach cache export'",
+    )
+    cache_import_parser.add_argument(
+        "path",
+        type=Path,
+        metavar="ARCHIVE",
+        help="Cache archive to restore, or a directory containing one.",
     )
 
     ## tach server
-    subparsers.add_parser(
+    server_parser = subparsers.add_parser(
         "server",
         prog=f"{TOOL_NAME} server",
         help="Start the Language Server Protocol (LSP) server",
         description="Start the Language Server Protocol (LSP) server",
     )
+    server_parser.add_argument(
+        "--rpc",
+        action="store_true",
+        help="Start a line-delimited JSON-RPC server over stdio instead of LSP.",
+    )
 
     return parser
 
@@ -466,6 +1639,7 @@ def check_cache_for_action(
     cache_result = check_computation_cache(
         project_root=str(project_root), cache_key=cache_key
     )
+    log_cache_lookup("computation", hit=bool(cache_result))
     if cache_result:
         return CachedOutput(
             key=cache_key,
@@ -483,55 +1657,197 @@ def tach_check(
     dependencies: bool = True,
     interfaces: bool = True,
     output_format: str = "text",
+    group_by: str | None = None,
+    quiet: bool = False,
+    statistics: bool = False,
+    output_template: str | None = None,
+    exit_policy: ExitPolicy | None = None,
+    progress: ProgressReporter | None = None,
+    modules: list[str] | None = None,
+    domains: list[str] | None = None,
 ):
+    exit_policy = exit_policy or ExitPolicy()
+    modules = modules or []
+    domains = domains or []
     logger.info(
         "tach check called",
         extra={
             "data": CallInfo(
                 function="tach_check",
-                parameters={"exact": exact, "output_format": output_format},
+                parameters={
+                    "exact": exact,
+                    "output_format": output_format,
+                    "group_by": group_by,
+                    "quiet": quiet,
+                    "statistics": statistics,
+                    "output_template": output_template is not None,
+                    "fail_on": exit_policy.fail_on,
+                    "category_exit_codes": exit_policy.category_exit_codes,
+                    "progress": progress is not None,
+                    "modules": modules,
+                    "domains": domains,
+                },
             ),
         },
     )
     try:
         exact |= project_config.exact
 
-        diagnostics = check(
-            project_root=project_root,
-            project_config=project_config,
-            dependencies=dependencies,
-            interfaces=interfaces,
-            exclude_paths=exclude_paths,
-        )
+        with log_phase("check"):
+            if progress is None:
+                diagnostics = check(
+                    project_root=project_root,
+                    project_config=project_config,
+                    dependencies=dependencies,
+                    interfaces=interfaces,
+                    exclude_paths=exclude_paths,
+                    modules=modules,
+                    domains=domains,
+                )
+            else:
+                # Streaming lets us report each file as it is checked
+                diagnostics = check_streaming(
+                    project_root=project_root,
+                    project_config=project_config,
+                    dependencies=dependencies,
+                    interfaces=interfaces,
+                    exclude_paths=exclude_paths,
+                    callback=lambda _file_path, _diagnostics: progress.advance(),
+                    total_callback=lambda total: progress.start_phase("check", total),
+                    modules=modules,
+                    domains=domains,
+                )
+                progress.finish()
         has_errors = any(diagnostic.is_error() for diagnostic in diagnostics)
+        budget_report = None
+        # Only a full check lowers budgets, as a partial check finds fewer violations
+        tighten_budgets = dependencies and interfaces and not (modules or domains)
+        if ratchet_enabled(project_config.exit):
+            budget_report = check_budgets(
+                project_root,
+                project_config,
+                exit_policy.violation_counts(diagnostics),
+                tighten=tighten_budgets,
+            )
+            exit_policy = replace(exit_policy, budgets=budget_report.budgets)
+        exit_code = exit_policy.exit_code(diagnostics)
+        checked_diagnostics = diagnostics
+        if quiet:
+            diagnostics = [
+                diagnostic for diagnostic in diagnostics if diagnostic.is_error()
+            ]
+        diagnostic_statistics = compute_statistics(diagnostics) if statistics else None
+
+        owner_reports = (
+            group_by_owner(
+                diagnostics,
+                get_module_owners(
+                    project_root=project_root, project_config=project_config
+                ),
+            )
+            if group_by == "owner"
+            else None
+        )
+        domain_reports = None
+        if group_by == "domain":
+            domain_reports = group_by_domain(
+                diagnostics,
+                get_module_dependencies(project_config),
+                project_config.domain_paths(),
+            )
+        elif group_by == "package":
+            domain_reports = group_by_domain(
+                diagnostics,
+                get_module_dependencies(project_config),
+                project_config.package_paths(),
+                ungrouped=NO_PACKAGE,
+            )
+
+        if output_format == "sarif":
+            print(
+                serialize_diagnostics_sarif(
+                    diagnostics, project_config, tach_version=__version__
+                )
+            )
+            sys.exit(exit_code)
 
         if output_format == "json":
             try:
-                print(serialize_diagnostics_json(diagnostics, pretty_print=True))
+                if diagnostic_statistics is not None:
+                    print(json.dumps(diagnostic_statistics.to_dict(), indent=2))
+                elif owner_reports is not None:
+                    print(
+                        json.dumps(
+                            {report.owner: report.to_dict() for report in owner_reports},
+                            indent=2,
+                        )
+                    )
+                elif domain_reports is not None:
+                    print(
+                        json.dumps(
+                            {
+                                report.domain: report.to_dict()
+                                for report in domain_reports
+                            },
+                            indent=2,
+                        )
+                    )
+                else:
+                    print(serialize_diagnostics_json(diagnostics, pretty_print=True))
             except ValueError as e:
                 json.dump({"error": str(e)}, sys.stdout)
-            sys.exit(1 if has_errors else 0)
+            sys.exit(exit_code)
 
-        if diagnostics:
-            print(
-                format_diagnostics(project_root=project_root, diagnostics=diagnostics),
-                file=sys.stderr,
+        # Pretty output shows the source line and governing config of each diagnostic
+        code_frames = output_format == "pretty"
+        if diagnostic_statistics is not None:
+            if diagnostics:
+                print(format_statistics(diagnostic_statistics), file=sys.stderr)
+        elif owner_reports is not None:
+            print_owner_reports(project_root, owner_reports, code_frames=code_frames)
+        elif domain_reports is not None:
+            print_domain_reports(project_root, domain_reports, code_frames=code_frames)
+        elif diagnostics:
+            print_capped_diagnostics(
+                project_root,
+                project_config,
+                diagnostics,
+                output_template=output_template,
+                code_frames=code_frames,
             )
-        exit_code = 1 if has_errors else 0
+        if budget_report is not None:
+            print_budget_report(budget_report, tightened=tighten_budgets)
 
         # If we're checking in exact mode, we want to verify that there are no unused dependencies
         if dependencies and exact:
-            unused_dependencies = detect_unused_dependencies(
-                project_root=project_root,
-                project_config=project_config,
-                exclude_paths=exclude_paths,
-            )
+            if progress is not None:
+                progress.start_phase("unused-dependencies")
+            with log_phase("unused-dependencies"):
+                unused_dependencies = detect_unused_dependencies(
+                    project_root=project_root,
+                    project_config=project_config,
+                    exclude_paths=exclude_paths,
+                )
+            if progress is not None:
+                progress.finish()
             if unused_dependencies:
                 print_unused_dependencies(unused_dependencies)
-                exit_code = 1
+                has_errors = True
+                exit_code = exit_policy.exit_code(
+                    checked_diagnostics, unused_dependencies=True
+                )
 
     except TachCircularDependencyError as e:
-        print_circular_dependency_error(e.dependencies, output_format)
+        try:
+            cycles = get_circular_dependencies(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=exclude_paths,
+            )
+        except Exception:
+            # Suggestions are best-effort, the modules involved are still reported
+            cycles = []
+        print_circular_dependency_error(e.dependencies, output_format, cycles=cycles)
         sys.exit(1)
     except TachVisibilityError as e:
         print_visibility_errors(e.visibility_errors, output_format)
@@ -543,7 +1859,8 @@ def tach_check(
             print(str(e))
         sys.exit(1)
 
-    if exit_code == 0 and output_format == "text":
+    # Errors below the failure threshold still don't count as validated
+    if not has_errors and exit_code == 0 and output_format != "json" and not quiet:
         print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}All modules validated!{BCOLORS.ENDC}")
     sys.exit(exit_code)
 
@@ -552,114 +1869,576 @@ def tach_check_external(
     project_config: ProjectConfig,
     project_root: Path,
     exclude_paths: list[str],
+    output_template: str | None = None,
+    exit_policy: ExitPolicy | None = None,
+    group_by: str | None = None,
 ):
+    exit_policy = exit_policy or ExitPolicy()
     logger.info(
         "tach check-external called",
         extra={
             "data": CallInfo(
                 function="tach_check_external",
+                parameters={
+                    "output_template": output_template is not None,
+                    "group_by": group_by,
+                    "fail_on": exit_policy.fail_on,
+                    "category_exit_codes": exit_policy.category_exit_codes,
+                },
             ),
         },
     )
     try:
-        diagnostics = check_external(
-            project_root=project_root,
-            project_config=project_config,
-            exclude_paths=exclude_paths,
-        )
+        with log_phase("check-external"):
+            diagnostics = check_external(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=exclude_paths,
+            )
 
-        if diagnostics:
-            print(
-                format_diagnostics(project_root=project_root, diagnostics=diagnostics),
-                file=sys.stderr,
+        # Diagnostics are attributed to the module which contains the importing file
+        if group_by == "owner":
+            print_owner_reports(
+                project_root,
+                group_by_owner(
+                    diagnostics,
+                    get_module_owners(
+                        project_root=project_root, project_config=project_config
+                    ),
+                ),
+            )
+        elif group_by is not None:
+            if group_by == "domain":
+                group_paths, ungrouped = project_config.domain_paths(), NO_DOMAIN
+            elif group_by == "package":
+                group_paths, ungrouped = project_config.package_paths(), NO_PACKAGE
+            else:
+                group_paths = [module.path for module in project_config.all_modules()]
+                ungrouped = NO_MODULE
+            print_domain_reports(
+                project_root,
+                group_by_domain(
+                    diagnostics,
+                    get_module_dependencies(project_config),
+                    group_paths,
+                    ungrouped=ungrouped,
+                ),
+            )
+        elif diagnostics:
+            print_capped_diagnostics(
+                project_root,
+                project_config,
+                diagnostics,
+                output_template=output_template,
             )
 
         has_errors = any(diagnostic.is_error() for diagnostic in diagnostics)
-        if has_errors:
-            sys.exit(1)
-        else:
+        exit_code = exit_policy.exit_code(diagnostics, external=True)
+        if not has_errors and exit_code == 0:
             print(
                 f"{icons.SUCCESS} {BCOLORS.OKGREEN}All external dependencies validated!{BCOLORS.ENDC}"
             )
-            sys.exit(0)
+        sys.exit(exit_code)
 
     except Exception as e:
         print(str(e))
         sys.exit(1)
 
 
-def tach_mod(
-    project_root: Path,
-    depth: int | None = 1,
-    exclude_paths: list[str] | None = None,
-):
+def tach_workspace(project_roots: list[Path], output_format: str = "text"):
     logger.info(
-        "tach mod called",
+        "tach workspace called",
         extra={
             "data": CallInfo(
-                function="tach_mod",
-                parameters={"depth": depth},
+                function="tach_workspace",
+                parameters={
+                    "project_count": len(project_roots),
+                    "output_format": output_format,
+                },
             ),
         },
     )
-    # Local import because prompt_toolkit takes about ~80ms to load
-    from tach.mod import mod_edit_interactive
-
+    # Projects are reported relative to the directory which contains all of them
+    workspace_root = Path(
+        os.path.commonpath([project_root.resolve() for project_root in project_roots])
+    )
     try:
-        project_config = parse_project_config(root=project_root) or ProjectConfig()
-        exclude_paths = extend_and_validate(
-            exclude_paths, project_config.exclude, project_config.use_regex_matching
-        )
-        saved_changes, warnings = mod_edit_interactive(
-            project_root=project_root,
-            project_config=project_config,
-            exclude_paths=exclude_paths,
-            depth=depth,
-        )
+        report = check_workspace(workspace_root, project_roots)
     except Exception as e:
-        print(str(e))
+        if output_format == "json":
+            json.dump({"error": str(e)}, sys.stdout)
+        else:
+            print(str(e))
         sys.exit(1)
 
-    if warnings:
-        print("\n".join(warnings))
-    if saved_changes:
-        print(
-            f"{icons.SUCCESS} {BCOLORS.OKGREEN}Set modules! You may want to run '{TOOL_NAME} sync' "
-            f"to automatically set boundaries.{BCOLORS.ENDC}"
-        )
-    sys.exit(0)
-
+    if output_format == "json":
+        print(json.dumps(report.to_dict(), indent=2))
+    else:
+        print_workspace_report(workspace_root, report)
+        if not report.has_errors:
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}All projects validated!{BCOLORS.ENDC}"
+            )
+    sys.exit(1 if report.has_errors else 0)
+
+
+def tach_init(
+    project_root: Path,
+    exclude_paths: list[str] | None = None,
+    yes: bool = False,
+    force: bool = False,
+):
+    logger.info(
+        "tach init called",
+        extra={
+            "data": CallInfo(
+                function="tach_init",
+                parameters={"yes": yes, "force": force},
+            ),
+        },
+    )
+    project_config_path = fs.build_project_config_path(project_root)
+    if project_config_path.exists() and not force:
+        print(
+            f"{BCOLORS.FAIL}{project_config_path.name} already exists. "
+            f"Use '{TOOL_NAME} mod' to edit modules, or --force to overwrite it."
+            f"{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    proposal = propose_configuration(project_root)
+    if not proposal.modules:
+        print(
+            f"{BCOLORS.WARNING}No Python packages found. Use '{TOOL_NAME} mod' to "
+            f"mark modules manually.{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+    if not yes:
+        proposal = edit_proposal_interactive(proposal)
+
+    print(f"\n{BCOLORS.OKCYAN}{project_config_path.name}:{BCOLORS.ENDC}")
+    print(proposal.to_toml())
+    if not yes and not ask_yes_no(f"Write {project_config_path.name}?"):
+        print(f"{BCOLORS.OKCYAN}No changes saved.{BCOLORS.ENDC}")
+        sys.exit(0)
+    project_config_path.write_text(proposal.to_toml())
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote {project_config_path.name}."
+        f"{BCOLORS.ENDC}"
+    )
+
+    if not yes and not ask_yes_no(
+        "Sync dependencies between modules now? This fills in 'depends_on'."
+    ):
+        print(f"Run '{TOOL_NAME} sync' to set dependencies between modules.")
+        sys.exit(0)
+    try:
+        project_config = parse_project_config(root=project_root)
+        if project_config is None:
+            raise TachError(f"Failed to read {project_config_path.name}.")
+        with log_phase("sync"):
+            sync_project(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=extend_and_validate(
+                    exclude_paths,
+                    project_config.exclude
+                    + project_config.source_root_exclude_paths(),
+                    project_config.use_regex_matching,
+                ),
+            )
+    except Exception as e:
+        print(str(e))
+        sys.exit(1)
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Synced dependencies. "
+        f"Run '{TOOL_NAME} check' to validate your modules.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_mod(
+    project_root: Path,
+    depth: int | None = 1,
+    exclude_paths: list[str] | None = None,
+):
+    logger.info(
+        "tach mod called",
+        extra={
+            "data": CallInfo(
+                function="tach_mod",
+                parameters={"depth": depth},
+            ),
+        },
+    )
+    # Local import because prompt_toolkit takes about ~80ms to load
+    from tach.mod import mod_edit_interactive
+
+    try:
+        project_config = parse_project_config(root=project_root) or ProjectConfig()
+        exclude_paths = extend_and_validate(
+            exclude_paths,
+            project_config.exclude + project_config.source_root_exclude_paths(),
+            project_config.use_regex_matching,
+        )
+        saved_changes, warnings = mod_edit_interactive(
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=exclude_paths,
+            depth=depth,
+        )
+    except Exception as e:
+        print(str(e))
+        sys.exit(1)
+
+    if warnings:
+        print("\n".join(warnings))
+    if saved_changes:
+        print(
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}Set modules! You may want to run '{TOOL_NAME} sync' "
+            f"to automatically set boundaries.{BCOLORS.ENDC}"
+        )
+    sys.exit(0)
+
+
+def tach_diff_config(
+    project_root: Path,
+    old: str,
+    new: str | None = None,
+    output_format: str = "text",
+    exit_code: bool = False,
+):
+    logger.info(
+        "tach diff-config called",
+        extra={
+            "data": CallInfo(
+                function="tach_diff_config",
+                parameters={"output_format": output_format, "exit_code": exit_code},
+            ),
+        },
+    )
+    new = new or str(fs.build_project_config_path(project_root))
+    try:
+        diff = diff_configs(
+            read_config_source(project_root, old),
+            read_config_source(project_root, new),
+        )
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to compare configs: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_config_diff_json(diff))
+    else:
+        print(render_config_diff_text(diff))
+    sys.exit(1 if exit_code and diff.has_changes() else 0)
+
+
+def tach_upgrade(project_root: Path, check: bool = False):
+    logger.info(
+        "tach upgrade called",
+        extra={
+            "data": CallInfo(
+                function="tach_upgrade",
+                parameters={"check": check},
+            ),
+        },
+    )
+    project_config_path = fs.get_project_config_path(project_root)
+    if project_config_path is None:
+        print(
+            f"{BCOLORS.FAIL}{CONFIG_FILE_NAME}.toml not found. Run '{TOOL_NAME} init' "
+            f"to create one.{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+    try:
+        migrations = upgrade_project_config(project_config_path, dry_run=check)
+    except Exception as e:
+        print(
+            f"{BCOLORS.FAIL}Failed to upgrade {project_config_path.name}: {e}"
+            f"{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    if not migrations:
+        print(
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}{project_config_path.name} is up to "
+            f"date.{BCOLORS.ENDC}"
+        )
+        sys.exit(0)
+    changes = "\n".join(f"  - {migration}" for migration in migrations)
+    if check:
+        print(
+            f"{icons.WARNING} {BCOLORS.WARNING}{project_config_path.name} needs to be "
+            f"upgraded:{BCOLORS.ENDC}\n{changes}\n"
+            f"Run '{TOOL_NAME} upgrade' to apply these changes."
+        )
+        sys.exit(1)
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Upgraded {project_config_path.name}:"
+        f"{BCOLORS.ENDC}\n{changes}"
+    )
+    sys.exit(0)
+
 
 def tach_sync(
     project_config: ProjectConfig,
     project_root: Path,
     exclude_paths: list[str],
     add: bool = False,
+    check_only: bool = False,
+    output_format: str = "text",
+    new_packages: NewPackagePolicy | None = None,
 ):
     logger.info(
         "tach sync called",
         extra={
             "data": CallInfo(
                 function="tach_sync",
-                parameters={"add": add},
+                parameters={
+                    "add": add,
+                    "check": check_only,
+                    "new_packages": new_packages,
+                    "output_format": output_format,
+                },
             ),
         },
     )
+    if check_only:
+        try:
+            with log_phase("sync"):
+                plan = check_sync(
+                    project_root=project_root,
+                    project_config=project_config,
+                    exclude_paths=exclude_paths,
+                    add=add,
+                    new_packages=new_packages,
+                )
+        except Exception as e:
+            if output_format == "json":
+                json.dump({"error": str(e)}, sys.stdout)
+            else:
+                print(str(e))
+            sys.exit(1)
+
+        if output_format == "json":
+            print(render_sync_plan_json(plan))
+        else:
+            print(render_sync_plan_text(plan))
+        sys.exit(0 if plan.is_empty() else EXIT_SYNC_REQUIRED)
+
     try:
-        sync_project(
-            project_root=project_root,
-            project_config=project_config,
-            exclude_paths=exclude_paths,
-            add=add,
-        )
+        with log_phase("sync"):
+            plan = sync_project(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=exclude_paths,
+                add=add,
+                new_packages=new_packages,
+            )
     except Exception as e:
         print(str(e))
         sys.exit(1)
 
+    for warning in render_unregistered_packages(plan):
+        print(
+            f"{icons.WARNING} {BCOLORS.WARNING}{warning}{BCOLORS.ENDC}", file=sys.stderr
+        )
+    for module_path in plan.new_modules:
+        print(
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}Registered module '{module_path}'.{BCOLORS.ENDC}"
+        )
     print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}Synced dependencies.{BCOLORS.ENDC}")
     sys.exit(0)
 
 
+def tach_rename_module(
+    project_config: ProjectConfig,
+    path: str,
+    new_path: str,
+):
+    logger.info(
+        "tach rename-module called",
+        extra={
+            "data": CallInfo(
+                function="tach_rename_module",
+            ),
+        },
+    )
+    try:
+        project_config.rename_module(path, new_path)
+        project_config.save_edits()
+    except Exception as e:
+        print(f"{BCOLORS.FAIL}Failed to rename module '{path}': {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Renamed module '{path}' to '{new_path}'.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_domain(
+    project_config: ProjectConfig,
+    command: str,
+    path: str,
+    domain: str | None = None,
+):
+    logger.info(
+        "tach domain called",
+        extra={
+            "data": CallInfo(
+                function="tach_domain", parameters={"command": command}
+            ),
+        },
+    )
+    try:
+        if command == "create":
+            project_config.create_domain(path)
+            message = f"Created domain '{path}'."
+        elif command == "delete":
+            project_config.delete_domain(path)
+            message = f"Deleted domain '{path}'."
+        elif command == "move-module":
+            assert domain is not None
+            project_config.move_module_to_domain(path, domain)
+            message = f"Moved module '{path}' to domain '{domain}'."
+        else:
+            raise NotImplementedError(f"Domain command {command} is not supported.")
+        project_config.save_edits()
+    except Exception as e:
+        print(f"{BCOLORS.FAIL}Failed to {command} '{path}': {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}{message}{BCOLORS.ENDC}")
+    sys.exit(0)
+
+
+def tach_new_domain(project_config: ProjectConfig, project_root: Path, path: str):
+    logger.info(
+        "tach new-domain called",
+        extra={
+            "data": CallInfo(
+                function="tach_new_domain",
+            ),
+        },
+    )
+    try:
+        config_path = create_new_domain(
+            project_root=project_root, project_config=project_config, path=path
+        )
+    except Exception as e:
+        print(f"{BCOLORS.FAIL}Failed to create domain '{path}': {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Created domain '{path}' at {config_path}.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_new_module(
+    project_config: ProjectConfig,
+    project_root: Path,
+    path: str,
+    layer: str | None = None,
+    domain: str | None = None,
+    interface: list[str] | None = None,
+    yes: bool = False,
+):
+    logger.info(
+        "tach new-module called",
+        extra={
+            "data": CallInfo(
+                function="tach_new_module",
+                parameters={"yes": yes},
+            ),
+        },
+    )
+    if domain is not None:
+        if domain not in project_config.domain_paths():
+            print(f"{BCOLORS.FAIL}Unknown domain: '{domain}'{BCOLORS.ENDC}")
+            sys.exit(1)
+        path = module_path_in_domain(path, domain)
+    options = NewModuleOptions(path=path, layer=layer, interface=interface)
+    if not yes:
+        options = prompt_new_module_options(
+            project_config, options, ask_domain=domain is None
+        )
+
+    try:
+        module_file = create_new_module(
+            project_root=project_root, project_config=project_config, path=options.path
+        )
+        register_new_module(project_config, options)
+    except Exception as e:
+        print(
+            f"{BCOLORS.FAIL}Failed to create module '{options.path}': {e}{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Created module '{options.path}' at "
+        f"{module_file}.{BCOLORS.ENDC}"
+    )
+    print(f"Run '{TOOL_NAME} sync' to add its dependencies once it has imports.")
+    sys.exit(0)
+
+
+def tach_import_linter(
+    project_root: Path,
+    project_config: ProjectConfig | None,
+    command: str,
+    path: Path | None = None,
+    force: bool = False,
+):
+    logger.info(
+        "tach import-linter called",
+        extra={
+            "data": CallInfo(
+                function="tach_import_linter",
+                parameters={"command": command, "force": force},
+            ),
+        },
+    )
+    try:
+        if command == "import":
+            config_path = fs.build_project_config_path(project_root)
+            if config_path.exists() and not force:
+                raise TachError(
+                    f"{config_path.name} already exists. Use --force to overwrite it."
+                )
+            path = path or find_import_linter_config(project_root)
+            if path is None:
+                raise TachError(
+                    "No import-linter config found in "
+                    f"{', '.join(IMPORT_LINTER_CONFIG_FILES)}."
+                )
+            conversion = import_contracts(project_root, path)
+            config_path.write_text(conversion.content)
+            message = f"Wrote {config_path.name} from {path}."
+        else:
+            assert project_config is not None
+            conversion = tach_to_contracts(project_config)
+            if path is None:
+                print(conversion.content, end="")
+                message = None
+            else:
+                path.write_text(conversion.content)
+                message = f"Wrote import-linter contracts to {path}."
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}{e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    for warning in conversion.warnings:
+        print(
+            f"{icons.WARNING} {BCOLORS.WARNING}{warning}{BCOLORS.ENDC}", file=sys.stderr
+        )
+    if message is not None:
+        print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}{message}{BCOLORS.ENDC}")
+    sys.exit(0)
+
+
 class InstallTarget(Enum):
     PRE_COMMIT = "pre-commit"
 
@@ -701,7 +2480,7 @@ def tach_install(project_root: Path, target: InstallTarget) -> None:
 def tach_report(
     project_config: ProjectConfig,
     project_root: Path,
-    path: str,
+    path: str | None,
     include_dependency_modules: list[str] | None = None,
     include_usage_modules: list[str] | None = None,
     dependencies: bool = False,
@@ -709,6 +2488,10 @@ def tach_report(
     external: bool = False,
     raw: bool = False,
     exclude_paths: list[str] | None = None,
+    html_output_dir: Path | None = None,
+    report_format: str = "text",
+    base: str = "main",
+    graph_artifact: GraphArtifact | None = None,
 ):
     logger.info(
         "tach report called",
@@ -719,16 +2502,45 @@ def tach_report(
                     "dependencies": dependencies,
                     "usages": usages,
                     "external": external,
+                    "html": html_output_dir is not None,
+                    "format": report_format,
+                    "from_graph": graph_artifact is not None,
                 },
             ),
         },
     )
     try:
-        # Generate reports based on flags
-        generate_all = not (dependencies or usages or external)
-        generate_dependencies = generate_all or dependencies
-        generate_usages = generate_all or usages
-        generate_external = generate_all or external
+        if html_output_dir is not None:
+            index_path = generate_html_report(
+                project_root,
+                project_config,
+                exclude_paths=exclude_paths or [],
+                output_dir=html_output_dir,
+            )
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}Generated an HTML report at '{index_path}'{BCOLORS.ENDC}"
+            )
+            sys.exit(0)
+
+        if report_format == "markdown":
+            print(
+                generate_markdown_report(
+                    project_root,
+                    project_config,
+                    exclude_paths=exclude_paths or [],
+                    base=base,
+                ),
+                end="",
+            )
+            sys.exit(0)
+
+        assert path is not None
+        # Generate reports based on flags
+        generate_all = not (dependencies or usages or external)
+        generate_dependencies = generate_all or dependencies
+        generate_usages = generate_all or usages
+        # External dependencies are not part of a graph artifact
+        generate_external = (generate_all and graph_artifact is None) or external
 
         reports: list[str] = []
         if generate_dependencies or generate_usages:
@@ -743,6 +2555,7 @@ def tach_report(
                     skip_usages=not generate_usages,
                     raw=raw,
                     exclude_paths=exclude_paths,
+                    graph_artifact=graph_artifact,
                 )
             )
 
@@ -771,13 +2584,25 @@ def tach_show(
     is_web: bool = False,
     is_mermaid: bool = False,
     output_filepath: Path | None = None,
+    collapse_domains: bool = False,
+    collapse_packages: bool = False,
+    pruning: GraphPruning | None = None,
+    graph_artifact: GraphArtifact | None = None,
 ):
+    pruning = pruning or GraphPruning()
     logger.info(
         "tach show called",
         extra={
             "data": CallInfo(
                 function="tach_show",
-                parameters={"is_web": is_web, "is_mermaid": is_mermaid},
+                parameters={
+                    "is_web": is_web,
+                    "is_mermaid": is_mermaid,
+                    "collapse_domains": collapse_domains,
+                    "collapse_packages": collapse_packages,
+                    "pruned": not pruning.is_empty(),
+                    "from_graph": graph_artifact is not None,
+                },
             ),
         },
     )
@@ -786,12 +2611,30 @@ def tach_show(
         print(
             f"{BCOLORS.WARNING}Passing --web generates a remote graph; ignoring '--mermaid' flag.{BCOLORS.ENDC}"
         )
+    if is_web and collapse_domains:
+        print(
+            f"{BCOLORS.WARNING}Passing --web generates a remote graph; ignoring '--collapse-domains' flag.{BCOLORS.ENDC}"
+        )
+    if is_web and collapse_packages:
+        print(
+            f"{BCOLORS.WARNING}Passing --web generates a remote graph; ignoring '--collapse-packages' flag.{BCOLORS.ENDC}"
+        )
+    if is_web and not pruning.is_empty():
+        print(
+            f"{BCOLORS.WARNING}Passing --web generates a remote graph; ignoring '--omit-utilities', '--omit-root' and '--min-fan-in' flags.{BCOLORS.ENDC}"
+        )
+    if is_web and graph_artifact is not None:
+        print(
+            f"{BCOLORS.WARNING}Passing --web generates a remote graph; ignoring '--from-graph' flag.{BCOLORS.ENDC}"
+        )
 
     if project_config.has_no_modules():
         print_no_modules_found()
         sys.exit(1)
 
-    if project_config.has_no_dependencies():
+    # A graph artifact shows the dependencies found in the project, not those declared
+    dependency_graph = graph_artifact.graph if graph_artifact is not None else None
+    if dependency_graph is None and project_config.has_no_dependencies():
         print_no_dependencies_found()
         sys.exit(1)
     try:
@@ -816,6 +2659,10 @@ def tach_show(
                     project_config,
                     included_paths=included_paths,
                     output_filepath=output_filepath,
+                    collapse_domains=collapse_domains,
+                    collapse_packages=collapse_packages,
+                    pruning=pruning,
+                    dependency_graph=dependency_graph,
                 )
                 print_generated_module_graph_file(output_filepath, is_mermaid=True)
                 sys.exit(0)
@@ -827,12 +2674,678 @@ def tach_show(
                     project_config,
                     included_paths=included_paths,
                     output_filepath=output_filepath,
+                    collapse_domains=collapse_domains,
+                    collapse_packages=collapse_packages,
+                    pruning=pruning,
+                    dependency_graph=dependency_graph,
                 )
                 print_generated_module_graph_file(output_filepath)
                 sys.exit(0)
     except TachError as e:
-        print(f"Failed to show module graph: {e}")
+        print(f"Failed to show module graph: {e}")
+        sys.exit(1)
+
+
+def load_dependency_graph(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    graph_artifact: GraphArtifact | None = None,
+) -> DependencyGraph:
+    if graph_artifact is not None:
+        return graph_artifact.graph
+    try:
+        with log_phase("metrics"):
+            return get_dependency_graph(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=exclude_paths,
+            )
+    except TachError as e:
+        print(f"Failed to compute metrics: {e}")
+        sys.exit(1)
+
+
+def tach_god_modules(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "table",
+    graph_artifact: GraphArtifact | None = None,
+):
+    graph = load_dependency_graph(
+        project_config, project_root, exclude_paths, graph_artifact
+    )
+
+    flagged, thresholds = find_god_modules(
+        [node.path for node in graph.nodes], graph.edges, project_config.god_modules
+    )
+    if output_format == "json":
+        print(render_god_modules_json(flagged, thresholds))
+    elif output_format == "csv":
+        print(render_god_modules_csv(flagged), end="")
+    else:
+        print(render_god_modules_text(flagged, thresholds))
+    sys.exit(0)
+
+
+def tach_duplicates(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "table",
+    similarity: float = DEFAULT_SIMILARITY,
+    graph_artifact: GraphArtifact | None = None,
+):
+    graph = load_dependency_graph(
+        project_config, project_root, exclude_paths, graph_artifact
+    )
+
+    module_imports: dict[str, set[str]] = {}
+    for edge in graph.edges:
+        module_imports.setdefault(edge.source, set()).add(edge.target)
+    clusters = find_duplicate_clusters(
+        get_module_dependencies(project_config), module_imports, threshold=similarity
+    )
+    if output_format == "json":
+        print(render_duplicates_json(clusters))
+    elif output_format == "csv":
+        print(render_duplicates_csv(clusters), end="")
+    else:
+        print(render_duplicates_text(clusters))
+    sys.exit(0)
+
+
+def tach_metrics(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "table",
+    god_modules: bool = False,
+    chains: bool = False,
+    top: int = DEFAULT_TOP,
+    duplicates: bool = False,
+    similarity: float = DEFAULT_SIMILARITY,
+    graph_artifact: GraphArtifact | None = None,
+):
+    logger.info(
+        "tach metrics called",
+        extra={
+            "data": CallInfo(
+                function="tach_metrics",
+                parameters={
+                    "output_format": output_format,
+                    "god_modules": god_modules,
+                    "chains": chains,
+                    "duplicates": duplicates,
+                    "from_graph": graph_artifact is not None,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    if god_modules:
+        tach_god_modules(
+            project_config, project_root, exclude_paths, output_format, graph_artifact
+        )
+    if duplicates:
+        tach_duplicates(
+            project_config,
+            project_root,
+            exclude_paths,
+            output_format,
+            similarity,
+            graph_artifact,
+        )
+    if chains:
+        report = analyze_chains(get_module_dependencies(project_config), top=top)
+        if output_format == "json":
+            print(render_chains_json(report))
+        elif output_format == "csv":
+            print(render_chains_csv(report), end="")
+        else:
+            print(render_chains_text(report))
+        sys.exit(0)
+
+    try:
+        with log_phase("metrics"):
+            if graph_artifact is not None:
+                metrics = build_metrics(
+                    project_config, graph_artifact.graph, graph_artifact.classes
+                )
+            else:
+                metrics = compute_metrics(project_root, project_config, exclude_paths)
+    except TachError as e:
+        print(f"Failed to compute metrics: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_metrics_json(metrics))
+    elif output_format == "csv":
+        print(render_metrics_csv(metrics), end="")
+    else:
+        print(render_metrics_table(metrics))
+    sys.exit(0)
+
+
+def tach_stats(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "text",
+):
+    logger.info(
+        "tach stats called",
+        extra={
+            "data": CallInfo(
+                function="tach_stats",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("stats"):
+            stats = compute_project_stats(project_root, project_config, exclude_paths)
+    except TachError as e:
+        print(f"Failed to compute project stats: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(json.dumps(stats.to_dict(), indent=2))
+    else:
+        print(render_project_stats(stats))
+    sys.exit(0)
+
+
+def tach_django_apps(
+    project_config: ProjectConfig,
+    project_root: Path,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach django-apps called",
+        extra={
+            "data": CallInfo(
+                function="tach_django_apps",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    try:
+        apps = get_django_apps(project_root=project_root, project_config=project_config)
+    except ValueError as e:
+        print(f"{BCOLORS.FAIL}{e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(json.dumps([django_app_to_dict(app) for app in apps], indent=2))
+    else:
+        print(render_django_apps(apps))
+    sys.exit(0)
+
+
+def tach_suggest_splits(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    modules: list[str] | None = None,
+    max_coupling: float = DEFAULT_MAX_COUPLING,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach suggest-splits called",
+        extra={
+            "data": CallInfo(
+                function="tach_suggest_splits",
+                parameters={
+                    "max_coupling": max_coupling,
+                    "output_format": output_format,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    unknown_modules = sorted(set(modules or []) - set(project_config.module_paths()))
+    if unknown_modules:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}Unknown module(s): {', '.join(unknown_modules)}{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    try:
+        with log_phase("suggest-splits"):
+            suggestions = compute_split_suggestions(
+                project_root,
+                project_config,
+                exclude_paths,
+                modules=modules,
+                max_coupling=max_coupling,
+            )
+    except TachError as e:
+        print(f"Failed to analyze modules: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_split_suggestions_json(suggestions))
+    else:
+        print(render_split_suggestions_text(suggestions))
+    sys.exit(0)
+
+
+def tach_lint(
+    project_config: ProjectConfig,
+    project_root: Path,
+    min_utility_consumers: int = DEFAULT_MIN_UTILITY_CONSUMERS,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach lint called",
+        extra={
+            "data": CallInfo(
+                function="tach_lint",
+                parameters={
+                    "min_utility_consumers": min_utility_consumers,
+                    "output_format": output_format,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("lint"):
+            issues = lint_architecture(
+                project_root,
+                project_config,
+                min_utility_consumers=min_utility_consumers,
+            )
+    except TachError as e:
+        print(f"Failed to lint configuration: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_lint_json(issues))
+    else:
+        print(render_lint_text(issues))
+    sys.exit(1 if issues else 0)
+
+
+def tach_why(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    source: str,
+    target: str,
+    output_format: str = "text",
+    graph_artifact: GraphArtifact | None = None,
+):
+    logger.info(
+        "tach why called",
+        extra={
+            "data": CallInfo(
+                function="tach_why",
+                parameters={
+                    "output_format": output_format,
+                    "from_graph": graph_artifact is not None,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    unknown_modules = sorted({source, target} - set(project_config.module_paths()))
+    if unknown_modules:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}Unknown module(s): {', '.join(unknown_modules)}{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    try:
+        with log_phase("why"):
+            if graph_artifact is not None:
+                hops = graph_artifact.explain_dependency(project_config, source, target)
+            else:
+                hops = explain_dependency(
+                    project_root, project_config, exclude_paths, source, target
+                )
+    except TachError as e:
+        print(f"Failed to analyze dependencies: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_why_json(source, target, hops))
+    else:
+        print(render_why_text(source, target, hops))
+    sys.exit(0)
+
+
+def tach_explain_module(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    path: str,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach explain-module called",
+        extra={
+            "data": CallInfo(
+                function="tach_explain_module",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    # Paths are given relative to the working directory, rather than the project root
+    if Path(path).exists():
+        path = str(Path(path).resolve())
+
+    try:
+        with log_phase("explain-module"):
+            explanation = explain_module(
+                project_root, project_config, exclude_paths, path
+            )
+    except (TachError, ValueError) as e:
+        print(f"{icons.FAIL} {BCOLORS.FAIL}{e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_explanation_json(explanation))
+    else:
+        print(render_explanation_text(explanation))
+    sys.exit(0)
+
+
+def tach_trace(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    file_path: Path,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach trace called",
+        extra={
+            "data": CallInfo(
+                function="tach_trace",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    file_path = file_path.resolve()
+    if not file_path.is_file():
+        print(f"{icons.FAIL} {BCOLORS.FAIL}File not found: {file_path}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    try:
+        with log_phase("trace"):
+            file_trace = trace(project_root, project_config, exclude_paths, file_path)
+    except (TachError, ValueError, OSError, SyntaxError) as e:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}Failed to trace {file_path}: {e}{BCOLORS.ENDC}"
+        )
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_trace_json(file_trace))
+    else:
+        print(render_trace_text(file_trace))
+    sys.exit(0)
+
+
+def tach_ignores(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "text",
+):
+    logger.info(
+        "tach ignores called",
+        extra={
+            "data": CallInfo(
+                function="tach_ignores",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("ignores"):
+            usages = ignore_directive_usages(
+                project_root, project_config, exclude_paths
+            )
+    except (TachError, ValueError) as e:
+        print(f"Failed to check ignore directives: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_ignores_json(usages))
+    else:
+        print(render_ignores_text(usages))
+    sys.exit(0)
+
+
+def tach_deprecations(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_format: str = "text",
+):
+    logger.info(
+        "tach deprecations called",
+        extra={
+            "data": CallInfo(
+                function="tach_deprecations",
+                parameters={"output_format": output_format},
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("deprecations"):
+            graph = get_dependency_graph(
+                project_root=project_root,
+                project_config=project_config,
+                exclude_paths=exclude_paths,
+            )
+    except TachError as e:
+        print(f"Failed to find deprecated module consumers: {e}")
+        sys.exit(1)
+
+    modules = find_deprecated_modules(project_config, graph)
+    if output_format == "json":
+        print(render_deprecations_json(modules))
+    else:
+        print(render_deprecations_text(modules))
+    sys.exit(0)
+
+
+def tach_churn(
+    project_config: ProjectConfig,
+    project_root: Path,
+    max_commits: int = DEFAULT_MAX_COMMITS,
+    min_co_changes: int = DEFAULT_MIN_CO_CHANGES,
+    min_confidence: float = DEFAULT_MIN_CONFIDENCE,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach churn called",
+        extra={
+            "data": CallInfo(
+                function="tach_churn",
+                parameters={
+                    "max_commits": max_commits,
+                    "min_co_changes": min_co_changes,
+                    "min_confidence": min_confidence,
+                    "output_format": output_format,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("churn"):
+            co_changes = analyze_churn(
+                project_root,
+                project_config,
+                max_commits=max_commits,
+                min_co_changes=min_co_changes,
+                min_confidence=min_confidence,
+            )
+    except TachError as e:
+        print(f"Failed to analyze git history: {e}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(render_churn_json(co_changes))
+    else:
+        print(render_churn_text(co_changes))
+    sys.exit(0)
+
+
+def tach_snapshot(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    command: str,
+    path: Path,
+    output_format: str = "text",
+    max_drift: int | None = None,
+):
+    logger.info(
+        "tach snapshot called",
+        extra={
+            "data": CallInfo(
+                function="tach_snapshot",
+                parameters={
+                    "command": command,
+                    "output_format": output_format,
+                    "max_drift": max_drift,
+                },
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("snapshot"):
+            current = take_snapshot(project_root, project_config, exclude_paths)
+        if command == "write":
+            write_snapshot(current, path)
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote snapshot of {len(current.modules)} module(s) to '{path}'.{BCOLORS.ENDC}"
+            )
+            sys.exit(0)
+        elif command == "compare":
+            diff = compare_snapshots(read_snapshot(path), current)
+        else:
+            raise NotImplementedError(f"Snapshot command {command} is not supported.")
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to {command} snapshot: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(json.dumps(diff.to_dict(), indent=2))
+    else:
+        print(render_snapshot_diff(diff))
+    if max_drift is not None and diff.drift_score > max_drift:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}Drift score {diff.drift_score} exceeds the maximum of {max_drift}.{BCOLORS.ENDC}",
+            file=sys.stderr,
+        )
+        sys.exit(1)
+    sys.exit(0)
+
+
+def tach_api(
+    project_config: ProjectConfig,
+    project_root: Path,
+    command: str,
+    path: Path,
+    output_format: str = "text",
+):
+    logger.info(
+        "tach api called",
+        extra={
+            "data": CallInfo(
+                function="tach_api",
+                parameters={"command": command, "output_format": output_format},
+            ),
+        },
+    )
+
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+
+    try:
+        with log_phase("api"):
+            current = take_api_snapshot(project_root, project_config)
+        if command == "snapshot":
+            write_api_snapshot(current, path)
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote the public API of {len(current.modules)} module(s) to '{path}'.{BCOLORS.ENDC}"
+            )
+            sys.exit(0)
+        elif command == "diff":
+            diff = compare_api_snapshots(read_api_snapshot(path), current)
+        else:
+            raise NotImplementedError(f"API command {command} is not supported.")
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to {command} the public API: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+
+    if output_format == "json":
+        print(json.dumps(diff.to_dict(), indent=2))
+    else:
+        print(render_api_diff(diff))
+    if diff.breaking_changes:
+        print(
+            f"{icons.FAIL} {BCOLORS.FAIL}{len(diff.breaking_changes)} public member(s) were removed or changed.{BCOLORS.ENDC}",
+            file=sys.stderr,
+        )
         sys.exit(1)
+    sys.exit(0)
 
 
 def tach_test(
@@ -865,13 +3378,14 @@ def tach_test(
     try:
         if disable_cache:
             # If cache disabled, just run affected tests and exit
-            results = run_affected_tests(
-                project_root=project_root,
-                project_config=project_config,
-                head=head,
-                base=base,
-                pytest_args=pytest_args[1:],  # Remove '--' pseudo-argument
-            )
+            with log_phase("test"):
+                results = run_affected_tests(
+                    project_root=project_root,
+                    project_config=project_config,
+                    head=head,
+                    base=base,
+                    pytest_args=pytest_args[1:],  # Remove '--' pseudo-argument
+                )
             sys.exit(results.exit_code)
 
         # Below this line caching is enabled
@@ -891,13 +3405,14 @@ def tach_test(
 
         # Cache missed, capture terminal output while tests run so we can update the cache
 
-        results = run_affected_tests(
-            project_root=project_root,
-            project_config=project_config,
-            head=head,
-            base=base,
-            pytest_args=pytest_args[1:],  # Remove '--' pseudo-argument
-        )
+        with log_phase("test"):
+            results = run_affected_tests(
+                project_root=project_root,
+                project_config=project_config,
+                head=head,
+                base=base,
+                pytest_args=pytest_args[1:],  # Remove '--' pseudo-argument
+            )
 
         if results.tests_ran_to_completion:
             update_computation_cache(
@@ -928,16 +3443,45 @@ def tach_export(
     project_root: Path,
     output_path: Path | None = None,
     force: bool = False,
+    export_format: str = "modularity",
+    exclude_paths: list[str] | None = None,
+    write: bool = False,
+    pruning: GraphPruning | None = None,
 ):
     logger.info(
         "tach export called",
         extra={
             "data": CallInfo(
                 function="tach_export",
-                parameters={"force": force},
+                parameters={"force": force, "format": export_format, "write": write},
             ),
         },
     )
+    if export_format in BUILD_SYSTEMS:
+        tach_export_build_files(
+            project_config=project_config,
+            project_root=project_root,
+            build_system=export_format,
+            exclude_paths=exclude_paths or [],
+            write=write,
+            force=force,
+        )
+    if export_format == "graph":
+        tach_export_graph_artifact(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths or [],
+            output_path=output_path,
+        )
+    if export_format in GRAPH_FORMATS:
+        tach_export_graph(
+            project_config=project_config,
+            project_root=project_root,
+            graph_format=export_format,
+            exclude_paths=exclude_paths or [],
+            output_path=output_path,
+            pruning=pruning,
+        )
 
     try:
         export_report(
@@ -951,6 +3495,96 @@ def tach_export(
         sys.exit(1)
 
 
+def tach_export_build_files(
+    project_config: ProjectConfig,
+    project_root: Path,
+    build_system: str,
+    exclude_paths: list[str],
+    write: bool = False,
+    force: bool = False,
+):
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+    try:
+        with log_phase("build-targets"):
+            targets = compute_build_targets(project_root, project_config, exclude_paths)
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to compute build targets: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+    build_files = render_build_files(targets, build_system)
+    if not write:
+        print(render_build_files_text(build_files), end="")
+        sys.exit(0)
+
+    written, skipped = write_build_files(project_root, build_files, force=force)
+    for path in skipped:
+        print(
+            f"{icons.WARNING} {BCOLORS.WARNING}Skipped {path}, which already exists. "
+            f"Use --force to overwrite it.{BCOLORS.ENDC}"
+        )
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote {len(written)} BUILD files for "
+        f"{len(targets)} modules.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_export_graph(
+    project_config: ProjectConfig,
+    project_root: Path,
+    graph_format: str,
+    exclude_paths: list[str],
+    output_path: Path | None = None,
+    pruning: GraphPruning | None = None,
+):
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+    try:
+        with log_phase("graph-format"):
+            content = compute_graph_format(
+                project_root, project_config, exclude_paths, graph_format, pruning
+            )
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to export dependency graph: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+    if output_path is None:
+        print(content, end="" if content.endswith("\n") else "\n")
+        sys.exit(0)
+
+    output_path.write_text(content)
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote the {graph_format} dependency graph "
+        f"to {output_path}.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_export_graph_artifact(
+    project_config: ProjectConfig,
+    project_root: Path,
+    exclude_paths: list[str],
+    output_path: Path | None = None,
+):
+    if project_config.has_no_modules():
+        print_no_modules_found()
+        sys.exit(1)
+    output_path = output_path or DEFAULT_GRAPH_ARTIFACT_PATH
+    try:
+        with log_phase("graph-artifact"):
+            artifact = build_graph_artifact(project_root, project_config, exclude_paths)
+        write_graph_artifact(artifact, output_path)
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to export dependency graph: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Wrote the dependency graph to "
+        f"{output_path}.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
 def tach_upload(
     project_config: ProjectConfig,
     project_root: Path,
@@ -980,15 +3614,118 @@ def tach_upload(
         sys.exit(1)
 
 
-def tach_server(project_config: ProjectConfig, project_root: Path):
+def tach_generate_stubs(output_path: Path | None = None, check: bool = False):
+    logger.info(
+        "tach generate-stubs called",
+        extra={
+            "data": CallInfo(
+                function="tach_generate_stubs", parameters={"check": check}
+            ),
+        },
+    )
+    output_path = output_path or STUB_PATH
+    if check:
+        missing = missing_stubs(existing_stub_path=output_path)
+        for name in missing:
+            print(f"{BCOLORS.FAIL}Missing stub for '{name}'.{BCOLORS.ENDC}")
+        if missing:
+            sys.exit(1)
+        print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}Stubs are up to date.{BCOLORS.ENDC}")
+        sys.exit(0)
+
+    try:
+        output_path.write_text(generate_stubs(existing_stub_path=output_path))
+    except TachError as e:
+        print(f"{BCOLORS.FAIL}Failed to generate stubs: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+    print(
+        f"{icons.SUCCESS} {BCOLORS.OKGREEN}Generated stubs at '{output_path}'.{BCOLORS.ENDC}"
+    )
+    sys.exit(0)
+
+
+def tach_cache(
+    project_config: ProjectConfig,
+    project_root: Path,
+    command: str,
+    older_than: int | None = None,
+    path: Path | None = None,
+):
+    logger.info(
+        "tach cache called",
+        extra={
+            "data": CallInfo(
+                function="tach_cache", parameters={"command": command}
+            ),
+        },
+    )
+    try:
+        if command == "clear":
+            clear_cache(project_root=str(project_root))
+            print(f"{icons.SUCCESS} {BCOLORS.OKGREEN}Cache cleared.{BCOLORS.ENDC}")
+        elif command == "stats":
+            stats = get_cache_stats(
+                project_root=str(project_root), project_config=project_config
+            )
+            print(f"Backend: {stats.backend}")
+            print(f"Location: {stats.location}")
+            print(f"Entries: {stats.entries}")
+            print(f"Size: {stats.size_bytes} bytes")
+        elif command == "prune":
+            assert older_than is not None
+            num_removed = prune_cache(
+                project_root=str(project_root), older_than_days=older_than
+            )
+            print(
+                f"{icons.SUCCESS} {BCOLORS.OKGREEN}Removed {num_removed} cached result(s) older than {older_than} day(s).{BCOLORS.ENDC}"
+            )
+        elif command == "backend":
+            print(project_config.cache.backend)
+        elif command == "key":
+            file_dependencies = project_config.cache.file_dependencies
+            print(compute_cache_key(project_root, file_dependencies=file_dependencies))
+        elif command == "export":
+            assert path is not None
+            archive_path = export_cache(project_root, output_dir=path)
+            if archive_path is None:
+                print(
+                    f"{BCOLORS.WARNING}Cache is empty, nothing to export.{BCOLORS.ENDC}"
+                )
+            else:
+                print(archive_path)
+        elif command == "import":
+            assert path is not None
+            archive_path = find_cache_archive(path) if path.is_dir() else path
+            if archive_path is None or not archive_path.exists():
+                # A cold CI cache is expected on the first run for a given key
+                print(
+                    f"{BCOLORS.WARNING}No cache archive found at '{path}'.{BCOLORS.ENDC}"
+                )
+            else:
+                num_restored = import_cache(project_root, archive_path=archive_path)
+                print(
+                    f"{icons.SUCCESS} {BCOLORS.OKGREEN}Restored {num_restored} cached file(s) from '{archive_path}'.{BCOLORS.ENDC}"
+                )
+        else:
+            raise NotImplementedError(f"Cache command {command} is not supported.")
+    except Exception as e:
+        print(f"{BCOLORS.FAIL}Failed to {command} cache: {e}{BCOLORS.ENDC}")
+        sys.exit(1)
+    sys.exit(0)
+
+
+def tach_server(project_config: ProjectConfig, project_root: Path, rpc: bool = False):
     logger.info(
         "tach server called",
         extra={
-            "data": CallInfo(function="tach_server"),
+            "data": CallInfo(function="tach_server", parameters={"rpc": rpc}),
         },
     )
     try:
-        run_server(project_root, project_config)
+        if rpc:
+            run_rpc_server(project_root, project_config)
+        else:
+            run_server(project_root, project_config)
     except TachSetupError as e:
         print(f"Failed to setup LSP server: {e}")
         sys.exit(1)
@@ -1013,8 +3750,25 @@ def try_parse_project_config(project_root: Path) -> ProjectConfig | None:
 
 def main() -> None:
     args, parser = parse_arguments(sys.argv[1:])
+    if args.log_format == "json":
+        init_structured_logging()
+
     project_root = fs.find_project_config_root() or Path.cwd()
-    project_config = try_parse_project_config(project_root)
+    # Upgrading runs before the config is parsed, since an outdated config may not parse
+    if args.command == "upgrade":
+        tach_upgrade(project_root=project_root, check=args.check)
+        return
+    if args.command == "diff-config":
+        tach_diff_config(
+            project_root=project_root,
+            old=args.old,
+            new=args.new,
+            output_format=args.output,
+            exit_code=args.exit_code,
+        )
+        return
+    with log_phase("parse-config"):
+        project_config = try_parse_project_config(project_root)
 
     if project_config is None or not project_config.disable_logging:
         init_logging(project_root)
@@ -1029,7 +3783,15 @@ def main() -> None:
     exclude_paths = args.exclude.split(",") if getattr(args, "exclude", None) else None
 
     # Some commands can run without project config
-    if args.command == "mod":
+    if args.command == "init":
+        tach_init(
+            project_root=Path.cwd(),
+            exclude_paths=exclude_paths,
+            yes=args.yes,
+            force=args.force,
+        )
+        return
+    elif args.command == "mod":
         tach_mod(
             project_root=project_root,
             depth=args.depth,
@@ -1044,6 +3806,23 @@ def main() -> None:
             sys.exit(1)
         tach_install(project_root=project_root, target=install_target)
         return
+    elif args.command == "generate-stubs":
+        tach_generate_stubs(output_path=args.output, check=args.check)
+        return
+    elif args.command == "import-linter" and args.import_linter_command == "import":
+        # Importing writes a new project config
+        tach_import_linter(
+            project_root=project_root,
+            project_config=project_config,
+            command=args.import_linter_command,
+            path=args.path,
+            force=args.force,
+        )
+        return
+    elif args.command == "workspace":
+        # Each project in the workspace has its own config
+        tach_workspace(project_roots=args.project_roots, output_format=args.output)
+        return
 
     # All other commands require project config
     if project_config is None:
@@ -1053,8 +3832,8 @@ def main() -> None:
     # Deprecation warnings
     if project_config.use_regex_matching:
         print(
-            f"{BCOLORS.WARNING}WARNING: regex matching for exclude paths is deprecated. "
-            + f"Update your exclude paths in {CONFIG_FILE_NAME}.toml to use glob patterns instead, and remove the 'use_regex_matching' setting.{BCOLORS.ENDC}"
+            f"{BCOLORS.WARNING}WARNING: the 'use_regex_matching' setting is deprecated. "
+            + f"Update your exclude paths in {CONFIG_FILE_NAME}.toml to use glob patterns, or prefix regex patterns with 're:', and remove the 'use_regex_matching' setting.{BCOLORS.ENDC}"
             + "\n"
         )
     if (
@@ -1072,20 +3851,80 @@ def main() -> None:
     # Exclude paths on the CLI extend those from the project config
     try:
         exclude_paths = extend_and_validate(
-            exclude_paths, project_config.exclude, project_config.use_regex_matching
+            exclude_paths,
+            project_config.exclude + project_config.source_root_exclude_paths(),
+            project_config.use_regex_matching,
         )
     except TachConfigError as e:
         print(f"{BCOLORS.FAIL}Failed to validate exclude paths: {e}{BCOLORS.ENDC}")
         sys.exit(1)
 
+    # An output format on the CLI overrides the default from the project config
+    output_template = (
+        getattr(args, "output_format", None) or project_config.output_format
+    )
+    if output_template is not None:
+        try:
+            validate_output_template(output_template)
+        except TachConfigError as e:
+            print(f"{BCOLORS.FAIL}{e}{BCOLORS.ENDC}")
+            sys.exit(1)
+
+    # Exit code flags on the CLI override the settings from the project config
+    if getattr(args, "max_violations", None) is not None and args.max_violations < 0:
+        parser.error("--max-violations must not be negative")
+    exit_policy = ExitPolicy.from_config(
+        project_config.exit,
+        fail_on=getattr(args, "fail_on", None),
+        max_violations=getattr(args, "max_violations", None),
+        category_exit_codes=getattr(args, "category_exit_codes", False),
+    )
+
     if args.command == "sync":
         tach_sync(
             project_config=project_config,
             project_root=project_root,
             add=args.add,
             exclude_paths=exclude_paths,
+            check_only=args.check,
+            output_format=args.output,
+            new_packages=args.new_packages,
+        )
+    elif args.command == "rename-module":
+        tach_rename_module(
+            project_config=project_config,
+            path=args.path,
+            new_path=args.new_path,
+        )
+    elif args.command == "new-domain":
+        tach_new_domain(
+            project_config=project_config,
+            project_root=project_root,
+            path=args.path,
+        )
+    elif args.command == "new-module":
+        tach_new_module(
+            project_config=project_config,
+            project_root=project_root,
+            path=args.path,
+            layer=args.layer,
+            domain=args.domain,
+            interface=args.interface,
+            yes=args.yes,
+        )
+    elif args.command == "domain":
+        tach_domain(
+            project_config=project_config,
+            command=args.domain_command,
+            path=args.path,
+            domain=getattr(args, "domain", None),
         )
     elif args.command == "check":
+        if args.statistics and args.group_by:
+            parser.error("--statistics cannot be combined with --group-by")
+        if args.output is None:
+            args.output = "pretty" if sys.stderr.isatty() else "text"
+        progress = create_progress_reporter(args.progress)
         if args.dependencies or args.interfaces:
             tach_check(
                 project_config=project_config,
@@ -1095,6 +3934,14 @@ def main() -> None:
                 exact=args.exact,
                 exclude_paths=exclude_paths,
                 output_format=args.output,
+                group_by=args.group_by,
+                quiet=args.quiet,
+                statistics=args.statistics,
+                output_template=output_template,
+                exit_policy=exit_policy,
+                progress=progress,
+                modules=args.modules,
+                domains=args.domains,
             )
         else:
             tach_check(
@@ -1103,14 +3950,35 @@ def main() -> None:
                 exact=args.exact,
                 exclude_paths=exclude_paths,
                 output_format=args.output,
+                group_by=args.group_by,
+                quiet=args.quiet,
+                statistics=args.statistics,
+                output_template=output_template,
+                exit_policy=exit_policy,
+                progress=progress,
+                modules=args.modules,
+                domains=args.domains,
             )
     elif args.command == "check-external":
         tach_check_external(
             project_config=project_config,
             project_root=project_root,
             exclude_paths=exclude_paths,
+            output_template=output_template,
+            exit_policy=exit_policy,
+            group_by=args.group_by,
         )
     elif args.command == "report":
+        if args.html is not None and args.format == "markdown":
+            parser.error("--html cannot be combined with --format markdown")
+        if args.path is None and args.html is None and args.format == "text":
+            parser.error("the following arguments are required: path")
+        if args.from_graph is not None and (
+            args.html is not None or args.format == "markdown" or args.external
+        ):
+            parser.error(
+                "--from-graph only supports dependency and usage reports in text format"
+            )
         include_dependency_modules = (
             args.dependency_modules.split(",") if args.dependency_modules else None
         )
@@ -1128,6 +3996,10 @@ def main() -> None:
             external=args.external,
             raw=args.raw,
             exclude_paths=exclude_paths,
+            html_output_dir=args.html,
+            report_format=args.format,
+            base=args.base,
+            graph_artifact=graph_artifact_from_args(args),
         )
     elif args.command == "show":
         tach_show(
@@ -1137,6 +4009,126 @@ def main() -> None:
             output_filepath=args.out,
             is_web=args.web,
             is_mermaid=args.mermaid,
+            collapse_domains=args.collapse_domains,
+            collapse_packages=args.collapse_packages,
+            pruning=graph_pruning_from_args(args),
+            graph_artifact=graph_artifact_from_args(args),
+        )
+    elif args.command == "metrics":
+        tach_metrics(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            output_format=args.output,
+            god_modules=args.god_modules,
+            chains=args.chains,
+            top=args.top,
+            duplicates=args.duplicates,
+            similarity=args.similarity,
+            graph_artifact=graph_artifact_from_args(args),
+        )
+    elif args.command == "stats":
+        tach_stats(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            output_format=args.output,
+        )
+    elif args.command == "django-apps":
+        tach_django_apps(
+            project_config=project_config,
+            project_root=project_root,
+            output_format=args.output,
+        )
+    elif args.command == "suggest-splits":
+        tach_suggest_splits(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            modules=args.modules,
+            max_coupling=args.max_coupling,
+            output_format=args.output,
+        )
+    elif args.command == "why":
+        tach_why(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            source=args.source,
+            target=args.target,
+            output_format=args.output,
+            graph_artifact=graph_artifact_from_args(args),
+        )
+    elif args.command == "explain-module":
+        tach_explain_module(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            path=args.path,
+            output_format=args.output,
+        )
+    elif args.command == "trace":
+        tach_trace(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            file_path=args.file_path,
+            output_format=args.output,
+        )
+    elif args.command == "ignores":
+        tach_ignores(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            output_format=args.output,
+        )
+    elif args.command == "deprecations":
+        tach_deprecations(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            output_format=args.output,
+        )
+    elif args.command == "lint":
+        tach_lint(
+            project_config=project_config,
+            project_root=project_root,
+            min_utility_consumers=args.min_utility_consumers,
+            output_format=args.output,
+        )
+    elif args.command == "churn":
+        tach_churn(
+            project_config=project_config,
+            project_root=project_root,
+            max_commits=args.max_commits,
+            min_co_changes=args.min_co_changes,
+            min_confidence=args.min_confidence,
+            output_format=args.output,
+        )
+    elif args.command == "import-linter":
+        tach_import_linter(
+            project_root=project_root,
+            project_config=project_config,
+            command=args.import_linter_command,
+            path=args.output,
+        )
+    elif args.command == "snapshot":
+        tach_snapshot(
+            project_config=project_config,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            command=args.snapshot_command,
+            path=args.path,
+            output_format=getattr(args, "output", "text"),
+            max_drift=getattr(args, "max_drift", None),
+        )
+    elif args.command == "api":
+        tach_api(
+            project_config=project_config,
+            project_root=project_root,
+            command=args.api_command,
+            path=args.path,
+            output_format=getattr(args, "output", "text"),
         )
     elif args.command == "test":
         tach_test(
@@ -1153,6 +4145,10 @@ def main() -> None:
             project_root=project_root,
             output_path=args.output,
             force=args.force,
+            export_format=args.format,
+            exclude_paths=exclude_paths,
+            write=args.write,
+            pruning=graph_pruning_from_args(args),
         )
     elif args.command == "upload":
         tach_upload(
@@ -1160,8 +4156,18 @@ def main() -> None:
             project_root=project_root,
             force=args.force,
         )
+    elif args.command == "cache":
+        tach_cache(
+            project_config=project_config,
+            project_root=project_root,
+            command=args.cache_command,
+            older_than=getattr(args, "older_than", None),
+            path=getattr(args, "path", None),
+        )
     elif args.command == "server":
-        tach_server(project_config=project_config, project_root=project_root)
+        tach_server(
+            project_config=project_config, project_root=project_root, rpc=args.rpc
+        )
     else:
         print("Unrecognized command")
         parser.print_help()
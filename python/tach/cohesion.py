@@ -0,0 +1,260 @@
+from __future__ import annotations
+
+import json
+from collections import defaultdict
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+from tach.extension import get_project_imports
+from tach.filesystem import walk_pyfiles
+from tach.modules import module_for_file
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+DEFAULT_MAX_COUPLING = 0.2
+# Each proposed sub-module must contain at least this many files
+MIN_CLUSTER_SIZE = 2
+
+
+@dataclass
+class SplitSuggestion:
+    module: str
+    # The proposed sub-modules, as lists of file paths relative to the project root
+    clusters: list[list[str]] = field(default_factory=list)
+    # The number of imports within the proposed sub-modules, and between them
+    internal_imports: int = 0
+    crossing_imports: int = 0
+    # Files which neither import nor are imported by other files in the module
+    unattached: list[str] = field(default_factory=list)
+
+    @property
+    def coupling(self) -> float:
+        total = self.internal_imports + self.crossing_imports
+        return self.crossing_imports / total if total else 0.0
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "clusters": self.clusters,
+            "internal_imports": self.internal_imports,
+            "crossing_imports": self.crossing_imports,
+            "coupling": round(self.coupling, 3),
+            "unattached": self.unattached,
+        }
+
+
+def _edge(a: str, b: str) -> tuple[str, str]:
+    return (a, b) if a < b else (b, a)
+
+
+def cluster_files(
+    files: list[str], edges: dict[tuple[str, str], int]
+) -> list[list[str]]:
+    """Cluster files into (at most) two groups by their mutual imports.
+
+    Uses average-linkage agglomerative clustering: the pair of clusters with the most
+    imports between them, relative to their sizes, is merged until two clusters remain
+    or no remaining clusters import each other. Only files with at least one edge
+    should be passed, since unconnected files would each remain as their own cluster.
+
+    'edges' are undirected: each key is a pair of files in sorted order, and each
+    value is the number of imports between them.
+    """
+    clusters: dict[int, list[str]] = {
+        index: [file] for index, file in enumerate(sorted(files))
+    }
+    cluster_of = {
+        file: index for index, cluster in clusters.items() for file in cluster
+    }
+    weights: dict[tuple[int, int], int] = defaultdict(int)
+    for (a, b), count in edges.items():
+        if a in cluster_of and b in cluster_of:
+            weights[_edge(cluster_of[a], cluster_of[b])] += count
+
+    while len(clusters) > 2:
+        linked = [
+            (count / (len(clusters[a]) * len(clusters[b])), -a, -b)
+            for (a, b), count in weights.items()
+            if count
+        ]
+        if not linked:
+            break
+        _, a, b = max(linked)
+        a, b = -a, -b
+        # Merge cluster 'b' into cluster 'a'
+        clusters[a].extend(clusters.pop(b))
+        merged: dict[tuple[int, int], int] = defaultdict(int)
+        for (x, y), count in weights.items():
+            x, y = (a if x == b else x), (a if y == b else y)
+            if x != y:
+                merged[_edge(x, y)] += count
+        weights = merged
+
+    return sorted(sorted(cluster) for cluster in clusters.values())
+
+
+def suggest_split(
+    module: str,
+    files: list[str],
+    edges: dict[tuple[str, str], int],
+    max_coupling: float = DEFAULT_MAX_COUPLING,
+) -> SplitSuggestion | None:
+    """Suggest splitting the module when its files form two weakly-connected clusters.
+
+    The split is suggested when each cluster has at least MIN_CLUSTER_SIZE files,
+    and at most 'max_coupling' of the imports between files cross the boundary.
+    """
+    connected = {file for edge in edges for file in edge}
+    clusters = cluster_files([file for file in files if file in connected], edges)
+    if len(clusters) < 2 or any(
+        len(cluster) < MIN_CLUSTER_SIZE for cluster in clusters
+    ):
+        return None
+
+    cluster_of = {
+        file: index for index, cluster in enumerate(clusters) for file in cluster
+    }
+    suggestion = SplitSuggestion(
+        module=module,
+        clusters=clusters,
+        unattached=sorted(file for file in files if file not in connected),
+    )
+    for (a, b), count in edges.items():
+        if cluster_of[a] == cluster_of[b]:
+            suggestion.internal_imports += count
+        else:
+            suggestion.crossing_imports += count
+    return suggestion if suggestion.coupling <= max_coupling else None
+
+
+def _resolve_file(import_path: str, file_paths: dict[str, str]) -> str | None:
+    # The file which defines the import, through the longest matching module path
+    parts = import_path.split(".")
+    for end in range(len(parts), 0, -1):
+        file = file_paths.get(".".join(parts[:end]))
+        if file is not None:
+            return file
+    return None
+
+
+def collect_module_files(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> dict[str, dict[str, Path]]:
+    """Map each module to its files, keyed by their dotted paths.
+
+    Each file belongs to the innermost module which contains it. The root
+    '__init__.py' of each module is left out, since it usually re-exports
+    the module's interface rather than belonging with any group of files.
+    """
+    project_root = project_root.resolve()
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    module_paths = [module.path for module in project_config.all_modules()]
+    module_files: dict[str, dict[str, Path]] = defaultdict(dict)
+    for source_root in source_roots:
+        for pyfile in walk_pyfiles(
+            source_root,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            use_regex_matching=project_config.use_regex_matching,
+        ):
+            file_path = source_root / pyfile
+            module_path = module_for_file(file_path, source_roots, module_paths)
+            if module_path is None:
+                continue
+            parts = list(pyfile.with_suffix("").parts)
+            if parts[-1] == "__init__":
+                parts.pop()
+            dotted_path = ".".join(parts)
+            if dotted_path != module_path:
+                module_files[module_path][dotted_path] = file_path
+    return module_files
+
+
+def compute_split_suggestions(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    modules: list[str] | None = None,
+    max_coupling: float = DEFAULT_MAX_COUPLING,
+) -> list[SplitSuggestion]:
+    project_root = project_root.resolve()
+    source_roots = [
+        str((project_root / source_root).resolve())
+        for source_root in project_config.source_roots
+    ]
+    suggestions: list[SplitSuggestion] = []
+    module_files = collect_module_files(project_root, project_config, exclude_paths)
+    for module, files in sorted(module_files.items()):
+        if modules and module not in modules:
+            continue
+        file_paths = {
+            dotted_path: file_path.relative_to(project_root).as_posix()
+            for dotted_path, file_path in files.items()
+        }
+        edges: dict[tuple[str, str], int] = defaultdict(int)
+        for dotted_path, file_path in files.items():
+            source = file_paths[dotted_path]
+            imports = get_project_imports(
+                source_roots=source_roots,
+                file_path=str(file_path),
+                ignore_type_checking_imports=project_config.ignore_type_checking_imports,
+                include_string_imports=project_config.include_string_imports,
+            )
+            for project_import in imports:
+                target = _resolve_file(project_import.module_path, file_paths)
+                if target is not None and target != source:
+                    edges[_edge(source, target)] += 1
+        suggestion = suggest_split(
+            module, sorted(file_paths.values()), edges, max_coupling=max_coupling
+        )
+        if suggestion is not None:
+            suggestions.append(suggestion)
+    return suggestions
+
+
+def render_split_suggestions_text(suggestions: list[SplitSuggestion]) -> str:
+    if not suggestions:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}No candidate splits found.{BCOLORS.ENDC}"
+        )
+    lines: list[str] = []
+    for suggestion in suggestions:
+        lines.append(
+            f"{icons.WARNING} {BCOLORS.WARNING}'{suggestion.module}' could be split "
+            f"into {len(suggestion.clusters)} sub-modules{BCOLORS.ENDC} "
+            f"({suggestion.crossing_imports} of "
+            f"{suggestion.internal_imports + suggestion.crossing_imports} imports "
+            "cross the boundary)"
+        )
+        for index, cluster in enumerate(suggestion.clusters, start=1):
+            lines.append(f"    Sub-module {index}:")
+            lines.extend(f"        {file}" for file in cluster)
+        if suggestion.unattached:
+            lines.append("    Not connected to either sub-module:")
+            lines.extend(f"        {file}" for file in suggestion.unattached)
+    return "\n".join(lines)
+
+
+def render_split_suggestions_json(suggestions: list[SplitSuggestion]) -> str:
+    return json.dumps([suggestion.to_dict() for suggestion in suggestions], indent=2)
+
+
+__all__ = [
+    "DEFAULT_MAX_COUPLING",
+    "SplitSuggestion",
+    "cluster_files",
+    "suggest_split",
+    "collect_module_files",
+    "compute_split_suggestions",
+    "render_split_suggestions_text",
+    "render_split_suggestions_json",
+]
@@ -0,0 +1,333 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any
+
+import tomli
+
+from tach.errors import TachError
+from tach.filesystem import build_project_config_path
+from tach.filesystem.git_ops import get_file_at_ref
+
+# Module settings which are compared between configs, with the value used when unset
+MODULE_DEFAULTS: dict[str, Any] = {
+    "layer": None,
+    "visibility": ["*"],
+    "utility": False,
+    "unrestricted_layer": False,
+    "depends_on_external": None,
+    "unchecked": False,
+    "strict": False,
+    "owner": None,
+    "deprecated": False,
+    "successor": None,
+}
+DEFAULT_INTERFACE_FROM = [".*"]
+
+
+@dataclass
+class SettingChange:
+    # A dotted key, e.g. 'layers' or 'rules.unused_ignore_directives'
+    key: str
+    old: Any
+    new: Any
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"key": self.key, "old": self.old, "new": self.new}
+
+
+@dataclass
+class ModuleChange:
+    path: str
+    added_dependencies: list[str] = field(default_factory=list)
+    removed_dependencies: list[str] = field(default_factory=list)
+    settings: list[SettingChange] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "path": self.path,
+            "added_dependencies": self.added_dependencies,
+            "removed_dependencies": self.removed_dependencies,
+            "settings": [setting.to_dict() for setting in self.settings],
+        }
+
+
+@dataclass
+class InterfaceChange:
+    from_modules: list[str]
+    data_types: str
+    # One of 'added', 'removed', or 'changed'
+    status: str
+    added_expose: list[str] = field(default_factory=list)
+    removed_expose: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "from": self.from_modules,
+            "data_types": self.data_types,
+            "status": self.status,
+            "added_expose": self.added_expose,
+            "removed_expose": self.removed_expose,
+        }
+
+
+@dataclass
+class ConfigDiff:
+    added_modules: list[str] = field(default_factory=list)
+    removed_modules: list[str] = field(default_factory=list)
+    changed_modules: list[ModuleChange] = field(default_factory=list)
+    interface_changes: list[InterfaceChange] = field(default_factory=list)
+    # Project settings other than modules and interfaces
+    settings: list[SettingChange] = field(default_factory=list)
+
+    def has_changes(self) -> bool:
+        return bool(
+            self.added_modules
+            or self.removed_modules
+            or self.changed_modules
+            or self.interface_changes
+            or self.settings
+        )
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "added_modules": self.added_modules,
+            "removed_modules": self.removed_modules,
+            "changed_modules": [module.to_dict() for module in self.changed_modules],
+            "interface_changes": [
+                interface.to_dict() for interface in self.interface_changes
+            ],
+            "settings": [setting.to_dict() for setting in self.settings],
+        }
+
+
+def read_config_source(project_root: Path, source: str) -> dict[str, Any]:
+    """Parse the config at 'source', which is a file or a git ref."""
+    path = Path(source)
+    if path.is_file():
+        content = path.read_text()
+    else:
+        content = get_file_at_ref(
+            project_root, source, build_project_config_path(project_root)
+        )
+    try:
+        return tomli.loads(content)
+    except tomli.TOMLDecodeError as e:
+        raise TachError(f"Failed to parse the config at '{source}': {e}")
+
+
+def _dependencies(module: dict[str, Any]) -> dict[str, bool] | None:
+    """Dependencies by path, with whether they are deprecated. None allows any."""
+    depends_on = module.get("depends_on")
+    if depends_on is None:
+        return None
+    dependencies: dict[str, bool] = {}
+    for dependency in depends_on:
+        if isinstance(dependency, str):
+            dependencies[dependency] = False
+        else:
+            dependencies[dependency["path"]] = bool(dependency.get("deprecated"))
+    return dependencies
+
+
+def _modules(config: dict[str, Any]) -> dict[str, dict[str, Any]]:
+    modules: dict[str, dict[str, Any]] = {}
+    for module in config.get("modules", []):
+        # Modules declared together with 'paths' share their settings
+        for path in module.get("paths", [module.get("path")]):
+            modules[path] = module
+    return modules
+
+
+def _flatten(table: dict[str, Any], prefix: str = "") -> dict[str, Any]:
+    values: dict[str, Any] = {}
+    for key, value in table.items():
+        if isinstance(value, dict):
+            values.update(_flatten(value, prefix=f"{prefix}{key}."))
+        else:
+            values[f"{prefix}{key}"] = value
+    return values
+
+
+def _setting_changes(old: dict[str, Any], new: dict[str, Any]) -> list[SettingChange]:
+    return [
+        SettingChange(key=key, old=old.get(key), new=new.get(key))
+        for key in sorted(set(old) | set(new))
+        if old.get(key) != new.get(key)
+    ]
+
+
+def _describe_dependencies(
+    old: dict[str, bool] | None, new: dict[str, bool] | None
+) -> tuple[list[str], list[str], list[SettingChange]]:
+    if old is None or new is None:
+        # Switching to or from unrestricted dependencies is a setting change
+        if old == new:
+            return [], [], []
+        return [], [], [SettingChange(key="depends_on", old=old, new=new)]
+    added = sorted(path for path in new if path not in old)
+    removed = sorted(path for path in old if path not in new)
+    settings = [
+        SettingChange(key=f"depends_on.{path}.deprecated", old=old[path], new=new[path])
+        for path in sorted(set(old) & set(new))
+        if old[path] != new[path]
+    ]
+    return added, removed, settings
+
+
+def _compare_modules(
+    old_modules: dict[str, dict[str, Any]], new_modules: dict[str, dict[str, Any]]
+) -> list[ModuleChange]:
+    changes: list[ModuleChange] = []
+    for path in sorted(set(old_modules) & set(new_modules)):
+        old, new = old_modules[path], new_modules[path]
+        added, removed, settings = _describe_dependencies(
+            _dependencies(old), _dependencies(new)
+        )
+        settings.extend(
+            SettingChange(key=key, old=old.get(key, default), new=new.get(key, default))
+            for key, default in MODULE_DEFAULTS.items()
+            if old.get(key, default) != new.get(key, default)
+        )
+        if added or removed or settings:
+            changes.append(
+                ModuleChange(
+                    path=path,
+                    added_dependencies=added,
+                    removed_dependencies=removed,
+                    settings=settings,
+                )
+            )
+    return changes
+
+
+def _interfaces(config: dict[str, Any]) -> dict[tuple[tuple[str, ...], str], set[str]]:
+    """The members exposed by each interface, by its modules and data types."""
+    interfaces: dict[tuple[tuple[str, ...], str], set[str]] = {}
+    for interface in config.get("interfaces", []):
+        key = (
+            tuple(interface.get("from", DEFAULT_INTERFACE_FROM)),
+            interface.get("data_types", "all"),
+        )
+        interfaces.setdefault(key, set()).update(interface.get("expose", []))
+    return interfaces
+
+
+def _compare_interfaces(
+    old_config: dict[str, Any], new_config: dict[str, Any]
+) -> list[InterfaceChange]:
+    old_interfaces = _interfaces(old_config)
+    new_interfaces = _interfaces(new_config)
+    changes: list[InterfaceChange] = []
+    for key in sorted(set(old_interfaces) | set(new_interfaces)):
+        old_expose = old_interfaces.get(key)
+        new_expose = new_interfaces.get(key)
+        if old_expose == new_expose:
+            continue
+        if old_expose is None:
+            status = "added"
+        elif new_expose is None:
+            status = "removed"
+        else:
+            status = "changed"
+        changes.append(
+            InterfaceChange(
+                from_modules=list(key[0]),
+                data_types=key[1],
+                status=status,
+                added_expose=sorted((new_expose or set()) - (old_expose or set())),
+                removed_expose=sorted((old_expose or set()) - (new_expose or set())),
+            )
+        )
+    return changes
+
+
+def diff_configs(old_config: dict[str, Any], new_config: dict[str, Any]) -> ConfigDiff:
+    old_modules = _modules(old_config)
+    new_modules = _modules(new_config)
+
+    def settings(config: dict[str, Any]) -> dict[str, Any]:
+        return _flatten(
+            {
+                key: value
+                for key, value in config.items()
+                if key not in ("modules", "interfaces")
+            }
+        )
+
+    return ConfigDiff(
+        added_modules=sorted(set(new_modules) - set(old_modules)),
+        removed_modules=sorted(set(old_modules) - set(new_modules)),
+        changed_modules=_compare_modules(old_modules, new_modules),
+        interface_changes=_compare_interfaces(old_config, new_config),
+        settings=_setting_changes(settings(old_config), settings(new_config)),
+    )
+
+
+def _format_setting(setting: SettingChange) -> str:
+    return f"{setting.key}: {json.dumps(setting.old)} -> {json.dumps(setting.new)}"
+
+
+def _format_interface(change: InterfaceChange) -> str:
+    data_types = "" if change.data_types == "all" else f" [{change.data_types}]"
+    members = [f"+{member}" for member in change.added_expose] + [
+        f"-{member}" for member in change.removed_expose
+    ]
+    return (
+        f"{change.status} interface for {', '.join(change.from_modules)}"
+        f"{data_types}: {', '.join(members)}"
+    )
+
+
+def render_config_diff_text(diff: ConfigDiff) -> str:
+    if not diff.has_changes():
+        return "No configuration changes."
+    lines: list[str] = []
+    if diff.added_modules:
+        lines.append("Added modules:")
+        lines.extend(f"  '{module}'" for module in diff.added_modules)
+    if diff.removed_modules:
+        lines.append("Removed modules:")
+        lines.extend(f"  '{module}'" for module in diff.removed_modules)
+    if diff.changed_modules:
+        lines.append("Changed modules:")
+        for module in diff.changed_modules:
+            lines.append(f"  '{module.path}'")
+            lines.extend(
+                f"    + depends on '{dependency}'"
+                for dependency in module.added_dependencies
+            )
+            lines.extend(
+                f"    - depends on '{dependency}'"
+                for dependency in module.removed_dependencies
+            )
+            lines.extend(
+                f"    {_format_setting(setting)}" for setting in module.settings
+            )
+    if diff.interface_changes:
+        lines.append("Interfaces:")
+        lines.extend(
+            f"  {_format_interface(change)}" for change in diff.interface_changes
+        )
+    if diff.settings:
+        lines.append("Settings:")
+        lines.extend(f"  {_format_setting(setting)}" for setting in diff.settings)
+    return "\n".join(lines)
+
+
+def render_config_diff_json(diff: ConfigDiff) -> str:
+    return json.dumps(diff.to_dict(), indent=2)
+
+
+__all__ = [
+    "SettingChange",
+    "ModuleChange",
+    "InterfaceChange",
+    "ConfigDiff",
+    "read_config_source",
+    "diff_configs",
+    "render_config_diff_text",
+    "render_config_diff_json",
+]
@@ -0,0 +1,111 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import DependencyGraph, ProjectConfig
+
+
+@dataclass
+class DeprecatedModuleConsumer:
+    module: str
+    # The number of imports and references onto the deprecated module
+    usages: int
+    # Consumers which do not declare the dependency are new, and fail 'tach check'
+    declared: bool
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "usages": self.usages,
+            "declared": self.declared,
+        }
+
+
+@dataclass
+class DeprecatedModule:
+    module: str
+    successor: str | None
+    # Sorted by module path
+    consumers: list[DeprecatedModuleConsumer] = field(default_factory=list)
+
+    @property
+    def usages(self) -> int:
+        return sum(consumer.usages for consumer in self.consumers)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "successor": self.successor,
+            "usages": self.usages,
+            "consumers": [consumer.to_dict() for consumer in self.consumers],
+        }
+
+
+def find_deprecated_modules(
+    project_config: ProjectConfig, graph: DependencyGraph
+) -> list[DeprecatedModule]:
+    deprecated = {
+        module.path: DeprecatedModule(module=module.path, successor=module.successor)
+        for module in project_config.all_modules()
+        if module.deprecated
+    }
+    for edge in graph.edges:
+        if edge.target in deprecated and edge.source != edge.target:
+            deprecated[edge.target].consumers.append(
+                DeprecatedModuleConsumer(
+                    module=edge.source, usages=edge.weight, declared=edge.declared
+                )
+            )
+    for module in deprecated.values():
+        module.consumers.sort(key=lambda consumer: consumer.module)
+    return sorted(deprecated.values(), key=lambda module: module.module)
+
+
+def render_deprecations_text(modules: list[DeprecatedModule]) -> str:
+    if not modules:
+        return "No deprecated modules found."
+
+    lines: list[str] = []
+    for module in modules:
+        header = f"{BCOLORS.BOLD}{module.module}{BCOLORS.ENDC}"
+        if module.successor:
+            header += f" -> {module.successor}"
+        lines.append(
+            f"{header}  ({len(module.consumers)} consumer(s), {module.usages} usage(s))"
+        )
+        for consumer in module.consumers:
+            line = f"    {consumer.module} ({consumer.usages} usage(s))"
+            if not consumer.declared:
+                line += f"  {BCOLORS.FAIL}new, not declared in depends_on{BCOLORS.ENDC}"
+            lines.append(line)
+
+    remaining = sum(1 for module in modules if module.consumers)
+    lines.append(
+        f"\n{icons.WARNING if remaining else icons.SUCCESS} {remaining} of "
+        f"{len(modules)} deprecated module(s) still have consumers."
+    )
+    return "\n".join(lines)
+
+
+def deprecations_to_dict(modules: list[DeprecatedModule]) -> list[dict[str, Any]]:
+    return [module.to_dict() for module in modules]
+
+
+def render_deprecations_json(modules: list[DeprecatedModule]) -> str:
+    return json.dumps(deprecations_to_dict(modules), indent=2)
+
+
+__all__ = [
+    "DeprecatedModuleConsumer",
+    "DeprecatedModule",
+    "find_deprecated_modules",
+    "render_deprecations_text",
+    "deprecations_to_dict",
+    "render_deprecations_json",
+]
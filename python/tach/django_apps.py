@@ -0,0 +1,36 @@
+from __future__ import annotations
+
+from typing import TYPE_CHECKING, Any
+
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import DjangoApp
+
+
+def django_app_to_dict(app: DjangoApp) -> dict[str, Any]:
+    return {
+        "entry": app.entry,
+        "label": app.label,
+        "module_path": app.module_path,
+        "app_config": app.app_config,
+        "in_project": app.in_project,
+    }
+
+
+def render_django_apps(apps: list[DjangoApp]) -> str:
+    if not apps:
+        return "No installed apps found."
+    width = max(len(app.label) for app in apps)
+    lines: list[str] = []
+    for app in apps:
+        line = f"{app.label:<{width}}  -> {app.module_path}"
+        if app.app_config is not None:
+            line += f" (via {app.app_config})"
+        if not app.in_project:
+            line += f" {BCOLORS.WARNING}[outside source roots]{BCOLORS.ENDC}"
+        lines.append(line)
+    return "\n".join(lines)
+
+
+__all__ = ["django_app_to_dict", "render_django_apps"]
@@ -0,0 +1,114 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from tach.extension import serialize_diagnostics_json
+from tach.show import domain_for_module
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ProjectConfig
+
+NO_DOMAIN = "(no domain)"
+NO_PACKAGE = "(no package)"
+NO_MODULE = "(no module)"
+
+
+@dataclass
+class DomainReport:
+    domain: str
+    modules: list[str] = field(default_factory=list)
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+    # The other domains which this domain depends on, and which depend on it
+    dependencies: set[str] = field(default_factory=set)
+    dependents: set[str] = field(default_factory=set)
+
+    @property
+    def fan_in(self) -> int:
+        return len(self.dependents)
+
+    @property
+    def fan_out(self) -> int:
+        return len(self.dependencies)
+
+    @property
+    def instability(self) -> float:
+        # Fan-out as a fraction of all coupling, from 0 (stable) to 1 (unstable)
+        coupling = self.fan_in + self.fan_out
+        return self.fan_out / coupling if coupling else 0.0
+
+    @property
+    def error_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_error())
+
+    @property
+    def warning_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_warning())
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "modules": self.modules,
+            "fan_in": self.fan_in,
+            "fan_out": self.fan_out,
+            "instability": round(self.instability, 2),
+            "errors": self.error_count,
+            "warnings": self.warning_count,
+            "diagnostics": json.loads(
+                serialize_diagnostics_json(self.diagnostics, pretty_print=False)
+            ),
+        }
+
+
+def group_by_domain(
+    diagnostics: list[Diagnostic],
+    module_dependencies: dict[str, list[str]],
+    domain_paths: list[str],
+    ungrouped: str = NO_DOMAIN,
+) -> list[DomainReport]:
+    """Group diagnostics and module dependencies by the domain of each module.
+
+    Modules outside of any domain, and diagnostics which are not attributed to a module,
+    are grouped under 'ungrouped'. Reports are sorted by domain, with 'ungrouped' last.
+    Packages and modules are grouped the same way, using their paths as 'domain_paths'.
+    """
+    reports: dict[str, DomainReport] = {}
+
+    def report_for(module_path: str | None) -> DomainReport:
+        domain = (
+            domain_for_module(domain_paths, module_path) if module_path else None
+        ) or ungrouped
+        return reports.setdefault(domain, DomainReport(domain=domain))
+
+    for domain in domain_paths:
+        report_for(domain)
+    for module_path, dependencies in module_dependencies.items():
+        source = report_for(module_path)
+        source.modules.append(module_path)
+        for dependency in dependencies:
+            target = report_for(dependency)
+            if target is not source:
+                source.dependencies.add(target.domain)
+                target.dependents.add(source.domain)
+    for diagnostic in diagnostics:
+        report_for(diagnostic.usage_module()).diagnostics.append(diagnostic)
+    return sorted(
+        reports.values(), key=lambda report: (report.domain == ungrouped, report.domain)
+    )
+
+
+def get_module_dependencies(project_config: ProjectConfig) -> dict[str, list[str]]:
+    return {
+        module.path: [dependency.path for dependency in module.depends_on or []]
+        for module in project_config.all_modules()
+    }
+
+
+__all__ = [
+    "DomainReport",
+    "group_by_domain",
+    "get_module_dependencies",
+    "NO_DOMAIN",
+    "NO_PACKAGE",
+    "NO_MODULE",
+]
@@ -0,0 +1,163 @@
+from __future__ import annotations
+
+import csv
+import io
+import json
+from collections import defaultdict
+from dataclasses import dataclass, field
+from itertools import combinations
+from typing import Any
+
+from tach import icons
+from tach.colors import BCOLORS
+
+DEFAULT_SIMILARITY = 0.8
+# Modules with fewer declared dependencies than this are never reported
+MIN_DEPENDENCIES = 2
+
+
+@dataclass
+class DuplicateCluster:
+    modules: list[str]
+    # The lowest similarity between any two modules in the cluster
+    similarity: float
+    shared_dependencies: list[str] = field(default_factory=list)
+    shared_imports: list[str] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "modules": self.modules,
+            "similarity": round(self.similarity, 3),
+            "shared_dependencies": self.shared_dependencies,
+            "shared_imports": self.shared_imports,
+        }
+
+
+def jaccard(a: set[str], b: set[str]) -> float:
+    if not a and not b:
+        return 1.0
+    return len(a & b) / len(a | b)
+
+
+def module_similarity(
+    a: str,
+    b: str,
+    declared: dict[str, set[str]],
+    imported: dict[str, set[str]],
+) -> float:
+    """The similarity of both the declared dependencies and the imports of two modules.
+
+    Each module is left out of the other's dependencies, so that two modules which
+    depend on each other along with the same set of other modules still match.
+    """
+    pair = {a, b}
+    return min(
+        jaccard(declared[a] - pair, declared[b] - pair),
+        jaccard(imported.get(a, set()) - pair, imported.get(b, set()) - pair),
+    )
+
+
+def find_duplicate_clusters(
+    module_dependencies: dict[str, list[str]],
+    module_imports: dict[str, set[str]],
+    threshold: float = DEFAULT_SIMILARITY,
+) -> list[DuplicateCluster]:
+    """Group modules whose declared dependencies and imports are (nearly) the same.
+
+    Modules are linked when their similarity is at least 'threshold', and clusters
+    are formed from the linked modules.
+    """
+    declared = {
+        module: set(dependencies)
+        for module, dependencies in module_dependencies.items()
+        if len(set(dependencies)) >= MIN_DEPENDENCIES
+    }
+    parent = {module: module for module in declared}
+
+    def find(module: str) -> str:
+        while parent[module] != module:
+            parent[module] = parent[parent[module]]
+            module = parent[module]
+        return module
+
+    similarities: dict[tuple[str, str], float] = {}
+    for a, b in combinations(sorted(declared), 2):
+        similarity = module_similarity(a, b, declared, module_imports)
+        similarities[(a, b)] = similarity
+        if similarity >= threshold:
+            parent[find(b)] = find(a)
+
+    groups: dict[str, list[str]] = defaultdict(list)
+    for module in sorted(declared):
+        groups[find(module)].append(module)
+
+    clusters = [
+        DuplicateCluster(
+            modules=modules,
+            similarity=min(similarities[pair] for pair in combinations(modules, 2)),
+            shared_dependencies=sorted(
+                set.intersection(*(declared[module] for module in modules))
+            ),
+            shared_imports=sorted(
+                set.intersection(
+                    *(module_imports.get(module, set()) for module in modules)
+                )
+            ),
+        )
+        for modules in groups.values()
+        if len(modules) > 1
+    ]
+    return sorted(clusters, key=lambda cluster: (-cluster.similarity, cluster.modules))
+
+
+def render_duplicates_text(clusters: list[DuplicateCluster]) -> str:
+    if not clusters:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}No modules with duplicate "
+            f"dependencies found.{BCOLORS.ENDC}"
+        )
+    lines: list[str] = []
+    for cluster in clusters:
+        modules = ", ".join(f"'{module}'" for module in cluster.modules)
+        lines.append(
+            f"{icons.WARNING} {BCOLORS.WARNING}{modules} have near-identical "
+            f"dependencies{BCOLORS.ENDC} (similarity {cluster.similarity:.2f})"
+        )
+        shared = ", ".join(f"'{module}'" for module in cluster.shared_dependencies)
+        lines.append(f"    Shared dependencies: {shared or 'none'}")
+    lines.append(
+        f"\n{BCOLORS.WARNING}Consider consolidating these modules, or extracting "
+        f"their shared dependencies behind a common abstraction.{BCOLORS.ENDC}"
+    )
+    return "\n".join(lines)
+
+
+def render_duplicates_json(clusters: list[DuplicateCluster]) -> str:
+    return json.dumps([cluster.to_dict() for cluster in clusters], indent=2)
+
+
+def render_duplicates_csv(clusters: list[DuplicateCluster]) -> str:
+    output = io.StringIO()
+    writer = csv.writer(output, lineterminator="\n")
+    writer.writerow(["modules", "similarity", "shared_dependencies"])
+    writer.writerows(
+        [
+            " ".join(cluster.modules),
+            round(cluster.similarity, 3),
+            " ".join(cluster.shared_dependencies),
+        ]
+        for cluster in clusters
+    )
+    return output.getvalue()
+
+
+__all__ = [
+    "DEFAULT_SIMILARITY",
+    "DuplicateCluster",
+    "jaccard",
+    "module_similarity",
+    "find_duplicate_clusters",
+    "render_duplicates_text",
+    "render_duplicates_json",
+    "render_duplicates_csv",
+]
@@ -0,0 +1,111 @@
+from __future__ import annotations
+
+from collections import Counter
+from dataclasses import dataclass
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ExitConfig
+
+EXIT_SUCCESS = 0
+# With category exit codes, each category is a bit flag in the exit code
+EXIT_FAILURE = 1
+EXIT_DEPENDENCY_FAILURE = 2
+EXIT_INTERFACE_FAILURE = 4
+EXIT_EXTERNAL_FAILURE = 8
+# 'tach sync --check' found changes to make, distinct from failures and usage errors (2)
+EXIT_SYNC_REQUIRED = 3
+
+
+@dataclass
+class ExitPolicy:
+    fail_on: str = "error"
+    max_violations: int | None = None
+    category_exit_codes: bool = False
+    # Allowed violations per diagnostic code, replacing 'max_violations' when set
+    budgets: dict[str, int] | None = None
+
+    @classmethod
+    def from_config(
+        cls,
+        config: ExitConfig,
+        fail_on: str | None = None,
+        max_violations: int | None = None,
+        category_exit_codes: bool = False,
+    ) -> ExitPolicy:
+        # Flags on the CLI override the project config
+        return cls(
+            fail_on=fail_on or config.fail_on,
+            max_violations=max_violations
+            if max_violations is not None
+            else config.max_violations,
+            category_exit_codes=category_exit_codes or config.category_exit_codes,
+        )
+
+    def failing_diagnostics(self, diagnostics: list[Diagnostic]) -> list[Diagnostic]:
+        if self.fail_on == "never":
+            return []
+        if self.fail_on == "warning":
+            return list(diagnostics)
+        return [diagnostic for diagnostic in diagnostics if diagnostic.is_error()]
+
+    def violation_counts(self, diagnostics: list[Diagnostic]) -> dict[str, int]:
+        """The number of failing diagnostics for each diagnostic code."""
+        failing = self.failing_diagnostics(diagnostics)
+        return dict(Counter(diagnostic.code() for diagnostic in failing))
+
+    def _over_budget(
+        self, diagnostics: list[Diagnostic], budgets: dict[str, int]
+    ) -> list[Diagnostic]:
+        counts = Counter(diagnostic.code() for diagnostic in diagnostics)
+        return [
+            diagnostic
+            for diagnostic in diagnostics
+            if counts[diagnostic.code()] > budgets.get(diagnostic.code(), 0)
+        ]
+
+    def exit_code(
+        self,
+        diagnostics: list[Diagnostic],
+        external: bool = False,
+        unused_dependencies: bool = False,
+    ) -> int:
+        """Determine the exit code of a check from its diagnostics.
+
+        Unused dependencies (found in exact mode) fail unless fail_on is 'never'.
+        Violations of external dependencies fall into the external category.
+        With budgets, only codes with more violations than their budget fail.
+        """
+        failing = self.failing_diagnostics(diagnostics)
+        unused_dependencies = unused_dependencies and self.fail_on != "never"
+        if self.budgets is not None:
+            failing = self._over_budget(failing, self.budgets)
+            within_threshold = not failing
+        else:
+            within_threshold = len(failing) <= (self.max_violations or 0)
+        if within_threshold and not unused_dependencies:
+            return EXIT_SUCCESS
+        if not self.category_exit_codes:
+            return EXIT_FAILURE
+
+        exit_code = EXIT_DEPENDENCY_FAILURE if unused_dependencies else EXIT_SUCCESS
+        for diagnostic in failing:
+            if external:
+                exit_code |= EXIT_EXTERNAL_FAILURE
+            elif diagnostic.is_dependency_error():
+                exit_code |= EXIT_DEPENDENCY_FAILURE
+            elif diagnostic.is_interface_error():
+                exit_code |= EXIT_INTERFACE_FAILURE
+            else:
+                exit_code |= EXIT_FAILURE
+        return exit_code
+
+
+__all__ = [
+    "ExitPolicy",
+    "EXIT_SUCCESS",
+    "EXIT_FAILURE",
+    "EXIT_DEPENDENCY_FAILURE",
+    "EXIT_INTERFACE_FAILURE",
+    "EXIT_EXTERNAL_FAILURE",
+]
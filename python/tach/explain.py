@@ -0,0 +1,145 @@
+from __future__ import annotations
+
+import json
+from typing import TYPE_CHECKING, Any
+
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import ConfigSource, ModuleExplanation
+
+
+def format_source(source: ConfigSource | None) -> str:
+    if source is None:
+        return "(not declared in a config file)"
+    if source.line_number is None:
+        return str(source.file_path)
+    return f"{source.file_path}:{source.line_number}"
+
+
+def source_to_dict(source: ConfigSource | None) -> dict[str, Any] | None:
+    if source is None:
+        return None
+    return {"file_path": str(source.file_path), "line_number": source.line_number}
+
+
+def _heading(title: str) -> str:
+    return f"\n{BCOLORS.BOLD}{title}{BCOLORS.ENDC}"
+
+
+def render_explanation_text(explanation: ModuleExplanation) -> str:
+    module = explanation.module
+    if module.depends_on is None:
+        depends_on = "(any module)"
+    else:
+        depends_on = ", ".join(
+            f"{dep.path} (deprecated)" if dep.deprecated else dep.path
+            for dep in module.depends_on
+        )
+    lines = [
+        f"{BCOLORS.BOLD}Module '{module.path}'{BCOLORS.ENDC}  "
+        f"{BCOLORS.OKCYAN}{format_source(explanation.source)}{BCOLORS.ENDC}",
+        f"  depends_on: {depends_on or '(none)'}",
+        f"  visibility: {', '.join(module.visibility)}",
+    ]
+    if module.owner:
+        lines.append(f"  owner: {module.owner}")
+
+    lines.append(_heading("Domain"))
+    if explanation.domain is None:
+        lines.append("  (none)")
+    else:
+        lines.append(
+            f"  {explanation.domain}  {BCOLORS.OKCYAN}"
+            f"{format_source(explanation.domain_source)}{BCOLORS.ENDC}"
+        )
+
+    lines.append(_heading("Layer"))
+    if module.layer is None:
+        lines.append("  (none)")
+    else:
+        lines.append(
+            f"  {module.layer} of {' > '.join(explanation.layers)}  {BCOLORS.OKCYAN}"
+            f"{format_source(explanation.layers_source)}{BCOLORS.ENDC}"
+        )
+
+    lines.append(_heading("Interface"))
+    if not explanation.interfaces:
+        lines.append("  (none, so every member may be imported)")
+    for interface in explanation.interfaces:
+        data_types = (
+            "" if interface.data_types == "all" else f" [{interface.data_types}]"
+        )
+        lines.append(
+            f"  {', '.join(interface.expose)}{data_types}  {BCOLORS.OKCYAN}"
+            f"{format_source(interface.source)}{BCOLORS.ENDC}"
+        )
+
+    lines.append(_heading("Suppressions"))
+    if not explanation.suppressions:
+        lines.append("  (none)")
+    for suppression in explanation.suppressions:
+        lines.append(
+            f"  {suppression.description}  {BCOLORS.OKCYAN}"
+            f"{format_source(suppression.source)}{BCOLORS.ENDC}"
+        )
+    return "\n".join(lines)
+
+
+def explanation_to_dict(explanation: ModuleExplanation) -> dict[str, Any]:
+    module = explanation.module
+    return {
+        "module": {
+            "path": module.path,
+            "depends_on": None
+            if module.depends_on is None
+            else [
+                {"path": dependency.path, "deprecated": dependency.deprecated}
+                for dependency in module.depends_on
+            ],
+            "layer": module.layer,
+            "visibility": module.visibility,
+            "utility": module.utility,
+            "unrestricted_layer": module.unrestricted_layer,
+            "unchecked": module.unchecked,
+            "owner": module.owner,
+            "source": source_to_dict(explanation.source),
+        },
+        "domain": None
+        if explanation.domain is None
+        else {
+            "path": explanation.domain,
+            "source": source_to_dict(explanation.domain_source),
+        },
+        "layers": {
+            "layers": explanation.layers,
+            "source": source_to_dict(explanation.layers_source),
+        },
+        "interfaces": [
+            {
+                "expose": interface.expose,
+                "data_types": interface.data_types,
+                "source": source_to_dict(interface.source),
+            }
+            for interface in explanation.interfaces
+        ],
+        "suppressions": [
+            {
+                "description": suppression.description,
+                "source": source_to_dict(suppression.source),
+            }
+            for suppression in explanation.suppressions
+        ],
+    }
+
+
+def render_explanation_json(explanation: ModuleExplanation) -> str:
+    return json.dumps(explanation_to_dict(explanation), indent=2)
+
+
+__all__ = [
+    "format_source",
+    "render_explanation_text",
+    "explanation_to_dict",
+    "render_explanation_json",
+]
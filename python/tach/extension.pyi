@@ -1,5 +1,5 @@
 from pathlib import Path
-from typing import Literal
+from typing import Callable, Literal
 
 class PythonImport:
     module_path: str
@@ -16,9 +16,35 @@ def get_external_imports(
     file_path: str,
     ignore_type_checking_imports: bool,
 ) -> list[PythonImport]: ...
+class ImportCategory:
+    Project: ImportCategory
+    External: ImportCategory
+    Stdlib: ImportCategory
+    Unresolved: ImportCategory
+
+class CategorizedImport:
+    module_path: str
+    line_number: int
+    category: ImportCategory
+    ignored: bool
+
+def get_categorized_imports(
+    source_roots: list[str],
+    file_path: str,
+    stdlib_modules: list[str],
+    ignore_type_checking_imports: bool = False,
+    include_string_imports: bool = False,
+) -> list[CategorizedImport]: ...
+class ExcludedPaths:
+    def __enter__(self) -> ExcludedPaths: ...
+    def __exit__(self, *args: object) -> bool: ...
+
 def set_excluded_paths(
-    project_root: str, exclude_paths: list[str], use_regex_matching: bool
-) -> None: ...
+    project_root: str,
+    exclude_paths: list[str],
+    use_regex_matching: bool,
+    include_paths: list[str] = [],
+) -> ExcludedPaths: ...
 def create_dependency_report(
     project_root: str,
     project_config: ProjectConfig,
@@ -46,23 +72,56 @@ def update_computation_cache(
 ) -> None: ...
 def parse_project_config(filepath: Path) -> tuple[ProjectConfig, bool]: ...
 def dump_project_config_to_toml(project_config: ProjectConfig) -> str: ...
+def upgrade_project_config(filepath: Path, dry_run: bool = False) -> list[str]: ...
+class CacheStats:
+    backend: Literal["disk"]
+    location: Path
+    entries: int
+    size_bytes: int
+
+def get_cache_stats(project_root: str, project_config: ProjectConfig) -> CacheStats: ...
+def clear_cache(project_root: str) -> None: ...
+def prune_cache(project_root: str, older_than_days: int) -> int: ...
 def check(
     project_root: Path,
     project_config: ProjectConfig,
     dependencies: bool,
     interfaces: bool,
     exclude_paths: list[str],
+    modules: list[str] = ...,
+    domains: list[str] = ...,
+) -> list[Diagnostic]: ...
+def check_streaming(
+    project_root: Path,
+    project_config: ProjectConfig,
+    dependencies: bool,
+    interfaces: bool,
+    exclude_paths: list[str],
+    callback: Callable[[str, list[Diagnostic]], bool | None],
+    total_callback: Callable[[int], None] | None = None,
+    modules: list[str] = ...,
+    domains: list[str] = ...,
 ) -> list[Diagnostic]: ...
+def get_ignore_directive_usages(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> list[IgnoreDirectiveUsage]: ...
 def check_external_dependencies(
     project_root: str,
     project_config: ProjectConfig,
     module_mappings: dict[str, list[str]],
     stdlib_modules: list[str],
+    project_distributions: list[str] = [],
 ) -> list[Diagnostic]: ...
 def format_diagnostics(
     project_root: Path,
     diagnostics: list[Diagnostic],
+    code_frames: bool = False,
 ) -> str: ...
+def cap_diagnostics(
+    diagnostics: list[Diagnostic], project_config: ProjectConfig
+) -> CappedDiagnostics: ...
 def detect_unused_dependencies(
     project_root: Path,
     project_config: ProjectConfig,
@@ -73,10 +132,95 @@ def sync_project(
     project_config: ProjectConfig,
     exclude_paths: list[str],
     add: bool = False,
-) -> None: ...
+    new_packages: NewPackagePolicy | None = None,
+) -> SyncPlan: ...
+def plan_sync(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    add: bool = False,
+    new_packages: NewPackagePolicy | None = None,
+) -> SyncPlan: ...
+def get_dependency_graph(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> DependencyGraph: ...
+def get_circular_dependencies(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> list[CircularDependency]: ...
+def explain_dependency(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    source: str,
+    target: str,
+) -> list[DependencyHop]: ...
+def get_resolved_dependencies(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> list[ResolvedDependency]: ...
+def explain_module(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    path: str,
+) -> ModuleExplanation: ...
+def trace_file(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    file_path: Path,
+    module_mappings: dict[str, list[str]],
+    stdlib_modules: list[str],
+) -> FileTrace: ...
+def get_module_owners(
+    project_root: Path, project_config: ProjectConfig
+) -> dict[str, str]: ...
+def get_django_apps(
+    project_root: Path, project_config: ProjectConfig
+) -> list[DjangoApp]: ...
+def create_new_domain(
+    project_root: Path, project_config: ProjectConfig, path: str
+) -> Path: ...
+def create_new_module(
+    project_root: Path, project_config: ProjectConfig, path: str
+) -> Path: ...
 def run_server(project_root: Path, project_config: ProjectConfig) -> None: ...
+def run_rpc_server(project_root: Path, project_config: ProjectConfig) -> None: ...
 def serialize_modules_json(modules: list[ModuleConfig]) -> str: ...
 
+class Severity:
+    Error: Severity
+    Warning: Severity
+
+class SuppressedViolation:
+    code: str
+    severity: Severity
+    dependency: str | None
+    target_module: str | None
+
+class IgnoreDirectiveUsage:
+    file_path: Path
+    line_number: int
+    reason: str
+    modules: list[str]
+    redundant: bool
+    suppressed: list[SuppressedViolation]
+
+class SourceSpan:
+    start_offset: int
+    end_offset: int
+    column: int
+
+class RelatedLocation:
+    file_path: Path
+    line_number: int | None
+    message: str
+
 class Diagnostic:
     def is_code(self) -> bool: ...
     def is_configuration(self) -> bool: ...
@@ -87,13 +231,165 @@ class Diagnostic:
     def is_deprecated(self) -> bool: ...
     def usage_module(self) -> str | None: ...
     def definition_module(self) -> str | None: ...
+    def dependency(self) -> str | None: ...
+    def code(self) -> str: ...
+    def message(self) -> str: ...
+    def severity(self) -> Severity: ...
+    def span(self) -> SourceSpan | None: ...
     def to_string(self) -> str: ...
     def pyfile_path(self) -> str | None: ...
     def pyline_number(self) -> int | None: ...
+    def related_locations(self) -> list[RelatedLocation]: ...
+    def suggestion(self) -> str | None: ...
+
+class OmittedDiagnostics:
+    file_path: str | None
+    usage_module: str | None
+    definition_module: str | None
+    count: int
+    def message(self) -> str: ...
+
+class CappedDiagnostics:
+    diagnostics: list[Diagnostic]
+    omitted: list[OmittedDiagnostics]
+
+class DependencyGraphNode:
+    path: str
+    layer: str | None
+    utility: bool
+    entry_points: list[str]
+    files: int
+    lines: int
+    public_symbols: int
+    imports: int
+
+class DependencyGraphEdge:
+    source: str
+    target: str
+    weight: int
+    declared: bool
+
+class DependencyGraph:
+    nodes: list[DependencyGraphNode]
+    edges: list[DependencyGraphEdge]
+    def to_networkx_dict(self) -> dict[str, dict[str, dict[str, int | bool]]]: ...
+    def node_attributes(self) -> dict[str, dict[str, str | int | bool | None]]: ...
+    def unreachable_modules(self) -> list[str]: ...
+    def without_modules(self, modules: list[str]) -> DependencyGraph: ...
+
+class CycleBreak:
+    source: str
+    target: str
+    import_count: int
+
+class DjangoApp:
+    entry: str
+    label: str
+    module_path: str
+    app_config: str | None
+    in_project: bool
+
+class CircularDependency:
+    modules: list[str]
+    suggested_breaks: list[CycleBreak]
+
+class ImportLocation:
+    file_path: Path
+    line_number: int
+    import_path: str
+
+class DependencyHop:
+    source: str
+    target: str
+    declared: bool
+    imports: list[ImportLocation]
+
+class ResolvedDependency:
+    file_path: Path
+    file_module_path: str
+    line_number: int
+    import_path: str
+    source: str
+    target: str
+    construct: str | None
+    ignored: bool
+
+class GraphArtifact:
+    tach_version: str
+    graph: DependencyGraph
+    # The number of classes in each module, and how many of them are abstract
+    classes: dict[str, tuple[int, int]]
+    def __new__(
+        cls,
+        graph: DependencyGraph,
+        dependencies: list[ResolvedDependency],
+        classes: dict[str, tuple[int, int]],
+        tach_version: str,
+    ) -> GraphArtifact: ...
+    def serialize_json(self) -> str: ...
+    @staticmethod
+    def deserialize_json(json: str) -> GraphArtifact: ...
+    def explain_dependency(
+        self, project_config: ProjectConfig, source: str, target: str
+    ) -> list[DependencyHop]: ...
+    def create_dependency_report(
+        self,
+        project_root: Path,
+        project_config: ProjectConfig,
+        path: Path,
+        include_dependency_modules: list[str] | None,
+        include_usage_modules: list[str] | None,
+        skip_dependencies: bool,
+        skip_usages: bool,
+        raw: bool,
+    ) -> str: ...
+
+class ConfigSource:
+    file_path: Path
+    line_number: int | None
+
+class ExplainedInterface:
+    expose: list[str]
+    data_types: str
+    source: ConfigSource | None
+
+class Suppression:
+    description: str
+    source: ConfigSource | None
+
+class ModuleExplanation:
+    module: ModuleConfig
+    source: ConfigSource | None
+    domain: str | None
+    domain_source: ConfigSource | None
+    layers: list[str]
+    layers_source: ConfigSource | None
+    interfaces: list[ExplainedInterface]
+    suppressions: list[Suppression]
+
+class TracedDependency:
+    line_number: int
+    module_path: str
+    source: str
+    category: ImportCategory
+    module: str | None
+    distributions: list[str]
+    ignored: str | None
+
+class FileTrace:
+    file_path: Path
+    module_path: str
+    module: str | None
+    skipped: str | None
+    dependencies: list[TracedDependency]
 
 def serialize_diagnostics_json(
     diagnostics: list[Diagnostic], pretty_print: bool
 ) -> str: ...
+def diagnostic_fingerprints(diagnostics: list[Diagnostic]) -> list[str]: ...
+def serialize_diagnostics_sarif(
+    diagnostics: list[Diagnostic], project_config: ProjectConfig, tach_version: str
+) -> str: ...
 
 ErrorKind = Literal["DEPENDENCY", "INTERFACE"]
 
@@ -116,13 +412,28 @@ class DependencyConfig:
 class ModuleConfig:
     path: str
     depends_on: list[DependencyConfig] | None
+    layer: str | None
     visibility: list[str]
+    utility: bool
+    unrestricted_layer: bool
+    depends_on_external: list[str] | None
     strict: bool
     unchecked: bool
+    owner: str | None
+    deprecated: bool
+    successor: str | None
 
     def __new__(cls, path: str, strict: bool) -> ModuleConfig: ...
     def mod_path(self) -> str: ...
 
+class ModuleTree:
+    def __new__(cls, project_root: Path, project_config: ProjectConfig) -> ModuleTree: ...
+    def get(self, module_path: str) -> ModuleConfig | None: ...
+    def find_nearest(self, module_path: str) -> ModuleConfig | None: ...
+    def children(self, module_path: str) -> list[ModuleConfig]: ...
+    def module_for_file(self, file_path: Path) -> ModuleConfig | None: ...
+    def module_paths(self) -> list[str]: ...
+
 InterfaceDataTypes = Literal["all", "primitive"]
 
 class InterfaceConfig:
@@ -131,6 +442,10 @@ class InterfaceConfig:
     from_modules: list[str]
     data_types: InterfaceDataTypes
 
+class PackageConfig:
+    path: str
+    depends_on: list[str] | None
+
 CacheBackend = Literal["disk"]
 
 class CacheConfig:
@@ -138,14 +453,71 @@ class CacheConfig:
     file_dependencies: list[str]
     env_dependencies: list[str]
 
+class SourceRootConfig:
+    path: Path
+    exclude: list[str]
+    include_string_imports: bool | None
+    ignore_type_checking_imports: bool | None
+    python_version: str | None
+
+class DomainTemplateConfig:
+    package: bool
+    public_module: str | None
+    layer: str | None
+    owner: str | None
+    depends_on: list[str]
+
 class ExternalDependencyConfig:
     exclude: list[str]
     rename: list[str]
+    virtualenv: str | None
+    layer_budgets: dict[str, int]
+
+class GeneratedCodeConfig:
+    markers: list[str]
+    max_lines: int
+
+FailOn = Literal["error", "warning", "never"]
+
+class ExitConfig:
+    fail_on: FailOn
+    max_violations: int | None
+    category_exit_codes: bool
+    budgets: dict[str, int]
+    budget_file: str | None
+
+class GodModuleConfig:
+    fan_in_percentile: int
+    fan_out_percentile: int
+    min_fan_in: int
+    min_fan_out: int
+
+NewPackagePolicy = Literal["top-level", "nested", "warn"]
+
+class DiagnosticsConfig:
+    max_per_file: int | None
+    max_per_module_pair: int | None
+
+class SyncConfig:
+    new_packages: NewPackagePolicy
 
 class UnusedDependencies:
     path: str
     dependencies: list[DependencyConfig]
 
+class ModuleSyncChanges:
+    path: str
+    added: list[str]
+    removed: list[str]
+
+class SyncPlan:
+    create_root_module: bool
+    modules: list[ModuleSyncChanges]
+    deleted_modules: list[str]
+    new_modules: list[str]
+    unregistered_packages: list[str]
+    def is_empty(self) -> bool: ...
+
 RuleSetting = Literal["error", "warn", "off"]
 
 RootModuleTreatment = Literal["allow", "ignore", "dependenciesonly", "forbid"]
@@ -153,28 +525,57 @@ RootModuleTreatment = Literal["allow", "ignore", "dependenciesonly", "forbid"]
 class RulesConfig:
     unused_ignore_directives: RuleSetting
     require_ignore_directive_reasons: RuleSetting
+    domain_public_api: RuleSetting
+    domain_isolation: RuleSetting
+    parse_errors: RuleSetting
+    utility_interfaces: RuleSetting
+    file_cycles: RuleSetting
 
 class ProjectConfig:
+    version: int | None
+    layers: list[str]
+    packages: list[PackageConfig]
     cache: CacheConfig
     external: ExternalDependencyConfig
+    generated_code: GeneratedCodeConfig
+    exit: ExitConfig
+    god_modules: GodModuleConfig
+    sync: SyncConfig
+    diagnostics: DiagnosticsConfig
     exclude: list[str]
+    include: list[str]
     source_roots: list[str]
+    source_root_configs: list[SourceRootConfig]
     exact: bool
     disable_logging: bool
     ignore_type_checking_imports: bool
     include_string_imports: bool
     forbid_circular_dependencies: bool
     use_regex_matching: bool
+    strict_exports: bool
     rules: RulesConfig
     root_module: RootModuleTreatment
+    python_version: str | None
+    use_codeowners: bool
+    output_format: str | None
+    domain_patterns: list[str]
+    domain_template: DomainTemplateConfig
 
     def __new__(cls) -> ProjectConfig: ...
     def serialize_json(self) -> str: ...
+    @staticmethod
+    def deserialize_json(json: str) -> ProjectConfig: ...
+    def __getstate__(self) -> str: ...
+    def __setstate__(self, state: str) -> None: ...
     def set_location(self, location: Path) -> None: ...
     def has_no_modules(self) -> bool: ...
     def has_no_dependencies(self) -> bool: ...
+    def source_root_exclude_paths(self) -> list[str]: ...
     def has_root_module_reference(self) -> bool: ...
     def module_paths(self) -> list[str]: ...
+    def domain_paths(self) -> list[str]: ...
+    def package_paths(self) -> list[str]: ...
+    def layers_for_module(self, module_path: str) -> list[str]: ...
     def utility_paths(self) -> list[str]: ...
     def create_module(self, path: str) -> None: ...
     def delete_module(self, path: str) -> None: ...
@@ -182,8 +583,15 @@ class ProjectConfig:
     def unmark_module_as_utility(self, path: str) -> None: ...
     def add_dependency(self, path: str, dependency: str) -> None: ...
     def remove_dependency(self, path: str, dependency: str) -> None: ...
+    def set_module_layer(self, path: str, layer: str) -> None: ...
+    def create_interface(self, path: str, expose: list[str]) -> None: ...
+    def set_violation_budget(self, code: str, budget: int) -> None: ...
+    def rename_module(self, path: str, new_path: str) -> None: ...
     def add_source_root(self, path: Path) -> None: ...
     def remove_source_root(self, path: Path) -> None: ...
+    def create_domain(self, path: str) -> None: ...
+    def delete_domain(self, path: str) -> None: ...
+    def move_module_to_domain(self, path: str, domain: str) -> None: ...
     def save_edits(self) -> None: ...
     def all_modules(self) -> list[ModuleConfig]: ...
     def all_interfaces(self) -> list[InterfaceConfig]: ...
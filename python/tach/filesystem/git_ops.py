@@ -182,4 +182,58 @@ def get_changed_files(
     return [(Path(git_root) / filepath).resolve() for filepath in changed_files]
 
 
-__all__ = ["get_changed_files", "get_current_branch_info"]
+def get_commit_changes(project_root: Path, max_commits: int = 500) -> list[list[Path]]:
+    """The files changed by each of the most recent commits, excluding merges."""
+    # Local import because git-python takes ~80ms to load
+    from git import GitCommandError, InvalidGitRepositoryError, NoSuchPathError, Repo
+
+    try:
+        repo = Repo(project_root, search_parent_directories=True)
+    except (InvalidGitRepositoryError, NoSuchPathError):
+        raise TachSetupError(
+            "The project does not appear to be a git repository, cannot read history!"
+        )
+
+    try:
+        # Each commit starts with a NUL byte, followed by its hash and changed files
+        log: str = repo.git.log(
+            "--name-only", "--no-merges", "--format=%x00%H", f"-n{max_commits}"
+        )
+    except GitCommandError:
+        raise TachError("Failed to read the git history!")
+
+    git_root = Path(repo.git.rev_parse("--show-toplevel"))
+    commits: list[list[Path]] = []
+    for entry in log.split("\0")[1:]:
+        _, *files = entry.strip().splitlines()
+        commits.append([(git_root / file).resolve() for file in files if file])
+    return commits
+
+
+def get_file_at_ref(project_root: Path, ref: str, file_path: Path) -> str:
+    """The contents of 'file_path' at the git ref 'ref' (e.g. 'main' or 'HEAD~1')."""
+    # Local import because git-python takes ~80ms to load
+    from git import GitCommandError, InvalidGitRepositoryError, NoSuchPathError, Repo
+
+    try:
+        repo = Repo(project_root, search_parent_directories=True)
+    except (InvalidGitRepositoryError, NoSuchPathError):
+        raise TachSetupError(
+            "The project does not appear to be a git repository, cannot read "
+            f"'{ref}'!"
+        )
+
+    git_root = Path(repo.git.rev_parse("--show-toplevel"))
+    relative_path = file_path.resolve().relative_to(git_root.resolve()).as_posix()
+    try:
+        return repo.git.show(f"{ref}:{relative_path}")
+    except GitCommandError:
+        raise TachError(f"Failed to read '{relative_path}' at '{ref}'!")
+
+
+__all__ = [
+    "get_changed_files",
+    "get_commit_changes",
+    "get_current_branch_info",
+    "get_file_at_ref",
+]
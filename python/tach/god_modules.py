@@ -0,0 +1,199 @@
+from __future__ import annotations
+
+import csv
+import io
+import json
+import math
+from collections import defaultdict
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import DependencyGraphEdge, GodModuleConfig
+
+# The number of edges shown in each direction in text output
+MAX_EDGES_SHOWN = 5
+
+
+@dataclass
+class GodModule:
+    module: str
+    fan_in: int
+    fan_out: int
+    # Neighbouring modules with their import counts, most imports first
+    incoming: list[tuple[str, int]] = field(default_factory=list)
+    outgoing: list[tuple[str, int]] = field(default_factory=list)
+
+    @property
+    def incoming_imports(self) -> int:
+        return sum(imports for _, imports in self.incoming)
+
+    @property
+    def outgoing_imports(self) -> int:
+        return sum(imports for _, imports in self.outgoing)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "fan_in": self.fan_in,
+            "fan_out": self.fan_out,
+            "incoming": [
+                {"module": module, "imports": imports}
+                for module, imports in self.incoming
+            ],
+            "outgoing": [
+                {"module": module, "imports": imports}
+                for module, imports in self.outgoing
+            ],
+        }
+
+
+@dataclass
+class GodModuleThresholds:
+    fan_in: int
+    fan_out: int
+
+
+def percentile(values: list[int], rank: int) -> int:
+    """The nearest-rank percentile of the values, with the rank clamped to 0-100."""
+    if not values:
+        return 0
+    ordered = sorted(values)
+    index = math.ceil(min(max(rank, 0), 100) / 100 * len(ordered))
+    return ordered[max(index - 1, 0)]
+
+
+def compute_thresholds(
+    fan_in: dict[str, int], fan_out: dict[str, int], config: GodModuleConfig
+) -> GodModuleThresholds:
+    return GodModuleThresholds(
+        fan_in=max(
+            percentile(list(fan_in.values()), config.fan_in_percentile),
+            config.min_fan_in,
+        ),
+        fan_out=max(
+            percentile(list(fan_out.values()), config.fan_out_percentile),
+            config.min_fan_out,
+        ),
+    )
+
+
+def find_god_modules(
+    modules: list[str],
+    edges: list[DependencyGraphEdge],
+    config: GodModuleConfig,
+) -> tuple[list[GodModule], GodModuleThresholds]:
+    """Flag modules whose fan-in and fan-out both meet the configured thresholds.
+
+    Fan-in and fan-out count distinct neighbouring modules in the import graph,
+    so a module imported many times from a single module has a fan-in of one.
+    """
+    incoming: dict[str, list[tuple[str, int]]] = defaultdict(list)
+    outgoing: dict[str, list[tuple[str, int]]] = defaultdict(list)
+    for edge in edges:
+        outgoing[edge.source].append((edge.target, edge.weight))
+        incoming[edge.target].append((edge.source, edge.weight))
+
+    fan_in = {module: len(incoming[module]) for module in modules}
+    fan_out = {module: len(outgoing[module]) for module in modules}
+    thresholds = compute_thresholds(fan_in, fan_out, config)
+
+    god_modules = [
+        GodModule(
+            module=module,
+            fan_in=fan_in[module],
+            fan_out=fan_out[module],
+            incoming=sorted(incoming[module], key=lambda edge: (-edge[1], edge[0])),
+            outgoing=sorted(outgoing[module], key=lambda edge: (-edge[1], edge[0])),
+        )
+        for module in sorted(modules)
+        if fan_in[module] >= thresholds.fan_in and fan_out[module] >= thresholds.fan_out
+    ]
+    return god_modules, thresholds
+
+
+def _format_distribution(edges: list[tuple[str, int]], total: int) -> str:
+    shown = [
+        f"'{module}' {imports} ({imports / total:.0%})" if total else f"'{module}'"
+        for module, imports in edges[:MAX_EDGES_SHOWN]
+    ]
+    if len(edges) > MAX_EDGES_SHOWN:
+        shown.append(f"and {len(edges) - MAX_EDGES_SHOWN} more")
+    return ", ".join(shown)
+
+
+def render_god_modules_text(
+    god_modules: list[GodModule], thresholds: GodModuleThresholds
+) -> str:
+    if not god_modules:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}No god modules found "
+            f"(fan-in >= {thresholds.fan_in} and fan-out >= {thresholds.fan_out})."
+            f"{BCOLORS.ENDC}"
+        )
+    lines: list[str] = []
+    for god_module in god_modules:
+        lines.append(
+            f"{icons.WARNING} {BCOLORS.WARNING}'{god_module.module}' is a god module: "
+            f"fan-in {god_module.fan_in} (>= {thresholds.fan_in}), "
+            f"fan-out {god_module.fan_out} (>= {thresholds.fan_out}){BCOLORS.ENDC}"
+        )
+        lines.append(
+            f"    Incoming ({god_module.incoming_imports} imports): "
+            + _format_distribution(god_module.incoming, god_module.incoming_imports)
+        )
+        lines.append(
+            f"    Outgoing ({god_module.outgoing_imports} imports): "
+            + _format_distribution(god_module.outgoing, god_module.outgoing_imports)
+        )
+    lines.append(
+        f"\n{BCOLORS.WARNING}Consider splitting these modules, so that code which is "
+        f"widely used does not also depend on many other modules.{BCOLORS.ENDC}"
+    )
+    return "\n".join(lines)
+
+
+def render_god_modules_json(
+    god_modules: list[GodModule], thresholds: GodModuleThresholds
+) -> str:
+    return json.dumps(
+        {
+            "thresholds": {"fan_in": thresholds.fan_in, "fan_out": thresholds.fan_out},
+            "god_modules": [god_module.to_dict() for god_module in god_modules],
+        },
+        indent=2,
+    )
+
+
+def render_god_modules_csv(god_modules: list[GodModule]) -> str:
+    output = io.StringIO()
+    writer = csv.writer(output, lineterminator="\n")
+    writer.writerow(
+        ["module", "fan_in", "fan_out", "incoming_imports", "outgoing_imports"]
+    )
+    writer.writerows(
+        [
+            god_module.module,
+            god_module.fan_in,
+            god_module.fan_out,
+            god_module.incoming_imports,
+            god_module.outgoing_imports,
+        ]
+        for god_module in god_modules
+    )
+    return output.getvalue()
+
+
+__all__ = [
+    "GodModule",
+    "GodModuleThresholds",
+    "percentile",
+    "compute_thresholds",
+    "find_god_modules",
+    "render_god_modules_text",
+    "render_god_modules_json",
+    "render_god_modules_csv",
+]
@@ -0,0 +1,56 @@
+from __future__ import annotations
+
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach import __version__
+from tach.errors import TachError
+from tach.extension import (
+    GraphArtifact,
+    get_dependency_graph,
+    get_resolved_dependencies,
+)
+from tach.metrics import count_module_classes
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+DEFAULT_GRAPH_ARTIFACT_PATH = Path("tach-graph.json")
+
+
+def build_graph_artifact(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> GraphArtifact:
+    """Resolve every dependency in the project, along with what 'tach metrics' needs."""
+    return GraphArtifact(
+        graph=get_dependency_graph(project_root, project_config, exclude_paths),
+        dependencies=get_resolved_dependencies(
+            project_root, project_config, exclude_paths
+        ),
+        classes=count_module_classes(project_root, project_config, exclude_paths),
+        tach_version=__version__,
+    )
+
+
+def write_graph_artifact(artifact: GraphArtifact, path: Path) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(artifact.serialize_json() + "\n")
+
+
+def read_graph_artifact(path: Path) -> GraphArtifact:
+    try:
+        data = path.read_text()
+    except FileNotFoundError:
+        raise TachError(f"Graph artifact '{path}' does not exist.")
+    try:
+        return GraphArtifact.deserialize_json(data)
+    except ValueError as e:
+        raise TachError(f"Graph artifact '{path}' could not be read: {e}")
+
+
+__all__ = [
+    "DEFAULT_GRAPH_ARTIFACT_PATH",
+    "build_graph_artifact",
+    "write_graph_artifact",
+    "read_graph_artifact",
+]
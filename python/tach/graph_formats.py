@@ -0,0 +1,165 @@
+from __future__ import annotations
+
+import json
+from collections import deque
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach.extension import get_dependency_graph
+from tach.filesystem import module_to_pyfile_or_dir_path
+from tach.graph_pruning import GraphPruning
+
+if TYPE_CHECKING:
+    from tach.extension import DependencyGraph, ProjectConfig
+
+# Formats read by tools which predate tach
+GRAPH_FORMATS = ["pydeps", "snakefood"]
+
+
+def module_sources(
+    project_root: Path, project_config: ProjectConfig, modules: list[str]
+) -> dict[str, tuple[Path, Path]]:
+    """The (source root, file) of each module, where a package is its '__init__.py'."""
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    sources: dict[str, tuple[Path, Path]] = {}
+    for module in modules:
+        for source_root in source_roots:
+            path = module_to_pyfile_or_dir_path((source_root,), module)
+            if path is None:
+                continue
+            if path.is_dir():
+                path = path / "__init__.py"
+            sources[module] = (source_root, path)
+            break
+    return sources
+
+
+def _bacon(imports: dict[str, list[str]], imported_by: dict[str, list[str]]):
+    """The distance of each module from a module which nothing imports."""
+    distances = {module: 1 for module in imports if not imported_by[module]}
+    queue = deque(distances)
+    while queue:
+        module = queue.popleft()
+        for target in imports[module]:
+            if target not in distances:
+                distances[target] = distances[module] + 1
+                queue.append(target)
+    # Modules within an import cycle which nothing else imports
+    return {module: distances.get(module, 0) for module in imports}
+
+
+def pydeps_dict(
+    graph: DependencyGraph, sources: dict[str, tuple[Path, Path]]
+) -> dict[str, dict[str, Any]]:
+    """Dependencies in the format of 'pydeps --show-deps'."""
+    modules = sorted(node.path for node in graph.nodes)
+    imports: dict[str, list[str]] = {module: [] for module in modules}
+    imported_by: dict[str, list[str]] = {module: [] for module in modules}
+    for edge in sorted(graph.edges, key=lambda edge: (edge.source, edge.target)):
+        if edge.source == edge.target:
+            continue
+        imports[edge.source].append(edge.target)
+        imported_by[edge.target].append(edge.source)
+    bacon = _bacon(imports, imported_by)
+    return {
+        module: {
+            "bacon": bacon[module],
+            "imported_by": imported_by[module],
+            "imports": imports[module],
+            "name": module,
+            "path": str(sources[module][1]) if module in sources else None,
+        }
+        for module in modules
+    }
+
+
+def render_pydeps(graph: DependencyGraph, sources: dict[str, tuple[Path, Path]]) -> str:
+    return json.dumps(pydeps_dict(graph, sources), indent=4, sort_keys=True)
+
+
+def _snakefood_file(source: tuple[Path, Path]) -> tuple[str, str]:
+    source_root, path = source
+    return str(source_root), path.relative_to(source_root).as_posix()
+
+
+def render_snakefood(
+    graph: DependencyGraph, sources: dict[str, tuple[Path, Path]]
+) -> str:
+    """Dependencies in the format of 'sfood', with one file per module.
+
+    Each line is a ((root, file), (root, file)) tuple. Every module also has a line
+    with (None, None) as its dependency, so that modules without dependencies appear.
+    """
+    dependencies = {
+        (_snakefood_file(sources[node.path]), (None, None))
+        for node in graph.nodes
+        if node.path in sources
+    }
+    for edge in graph.edges:
+        if edge.source == edge.target:
+            continue
+        if edge.source not in sources or edge.target not in sources:
+            continue
+        source = _snakefood_file(sources[edge.source])
+        dependencies.add((source, _snakefood_file(sources[edge.target])))
+    # Each file's (None, None) line comes before its dependencies
+    lines = sorted(
+        dependencies, key=lambda line: (line[0], line[1][0] is not None, line[1])
+    )
+    return "".join(f"{line!r}\n" for line in lines)
+
+
+def render_graph_format(
+    graph: DependencyGraph, sources: dict[str, tuple[Path, Path]], graph_format: str
+) -> str:
+    if graph_format == "pydeps":
+        return render_pydeps(graph, sources)
+    return render_snakefood(graph, sources)
+
+
+def prune_graph(
+    graph: DependencyGraph, pruning: GraphPruning, utility_paths: list[str]
+) -> DependencyGraph:
+    if pruning.is_empty():
+        return graph
+    pruned = pruning.pruned_modules(
+        [node.path for node in graph.nodes],
+        [(edge.source, edge.target) for edge in graph.edges],
+        utility_paths,
+    )
+    return graph.without_modules(sorted(pruned))
+
+
+def compute_graph_format(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    graph_format: str,
+    pruning: GraphPruning | None = None,
+) -> str:
+    graph = get_dependency_graph(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+    )
+    if pruning is not None:
+        graph = prune_graph(graph, pruning, project_config.utility_paths())
+    sources = module_sources(
+        project_root, project_config, [node.path for node in graph.nodes]
+    )
+    return render_graph_format(graph, sources, graph_format)
+
+
+__all__ = [
+    "GRAPH_FORMATS",
+    "compute_graph_format",
+    "module_sources",
+    "prune_graph",
+    "pydeps_dict",
+    "render_pydeps",
+    "render_snakefood",
+    "render_graph_format",
+]
@@ -0,0 +1,63 @@
+from __future__ import annotations
+
+from collections import defaultdict
+from dataclasses import dataclass
+
+ROOT_MODULE = "<root>"
+
+
+@dataclass
+class GraphPruning:
+    """Modules to leave out of a graph export, because they dominate its layout."""
+
+    utilities: bool = False
+    root: bool = False
+    # Modules which fewer than this many other modules depend on are left out
+    min_fan_in: int = 0
+
+    def is_empty(self) -> bool:
+        return not self.utilities and not self.root and self.min_fan_in <= 0
+
+    def pruned_modules(
+        self,
+        module_paths: list[str],
+        edges: list[tuple[str, str]],
+        utility_paths: list[str],
+    ) -> set[str]:
+        """The modules to leave out, with fan-in counted over the full graph."""
+        pruned: set[str] = set()
+        if self.utilities:
+            pruned.update(utility_paths)
+        if self.root:
+            pruned.add(ROOT_MODULE)
+        if self.min_fan_in > 0:
+            dependents: dict[str, set[str]] = defaultdict(set)
+            for source, target in edges:
+                if source != target:
+                    dependents[target].add(source)
+            modules = set(module_paths) | {module for edge in edges for module in edge}
+            pruned.update(
+                module
+                for module in modules
+                if len(dependents[module]) < self.min_fan_in
+            )
+        return pruned
+
+    def prune_edges(
+        self,
+        module_paths: list[str],
+        edges: list[tuple[str, str]],
+        utility_paths: list[str],
+    ) -> tuple[list[str], list[tuple[str, str]]]:
+        pruned = self.pruned_modules(module_paths, edges, utility_paths)
+        return (
+            [module for module in module_paths if module not in pruned],
+            [
+                (source, target)
+                for source, target in edges
+                if source not in pruned and target not in pruned
+            ],
+        )
+
+
+__all__ = ["GraphPruning", "ROOT_MODULE"]
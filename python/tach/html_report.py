@@ -0,0 +1,344 @@
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+from html import escape
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach.domains import (
+    NO_PACKAGE,
+    DomainReport,
+    get_module_dependencies,
+    group_by_domain,
+)
+from tach.extension import check
+from tach.show import render_module_graph_mermaid
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ProjectConfig
+
+MODULES_DIR = "modules"
+MERMAID_SCRIPT_URL = "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.min.js"
+
+STYLESHEET = """\
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 72rem; }
+h1, h2 { font-weight: 600; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.4rem; text-align: left; }
+th { background: #f5f5f5; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+.error { color: #b00020; }
+.warning { color: #9a6700; }
+.filters { display: flex; gap: 0.5rem; margin-bottom: 1rem; }
+pre.mermaid { overflow-x: auto; }
+"""
+
+FILTER_SCRIPT = """\
+const filters = document.querySelectorAll("[data-filter]");
+function applyFilters() {
+  document.querySelectorAll("#violations tbody tr").forEach((row) => {
+    row.hidden = !Array.from(filters).every((filter) => {
+      const value = filter.value.toLowerCase();
+      if (filter.dataset.filter === "text") {
+        return row.textContent.toLowerCase().includes(value);
+      }
+      return !value || row.dataset[filter.dataset.filter].toLowerCase() === value;
+    });
+  });
+}
+filters.forEach((filter) => filter.addEventListener("input", applyFilters));
+"""
+
+
+@dataclass
+class ModuleSummary:
+    path: str
+    # The modules which this module declares as dependencies, and which declare it
+    dependencies: list[str] = field(default_factory=list)
+    dependents: list[str] = field(default_factory=list)
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+
+    @property
+    def fan_in(self) -> int:
+        return len(self.dependents)
+
+    @property
+    def fan_out(self) -> int:
+        return len(self.dependencies)
+
+    @property
+    def instability(self) -> float:
+        coupling = self.fan_in + self.fan_out
+        return self.fan_out / coupling if coupling else 0.0
+
+    @property
+    def error_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_error())
+
+    @property
+    def warning_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_warning())
+
+
+def build_module_summaries(
+    diagnostics: list[Diagnostic], module_dependencies: dict[str, list[str]]
+) -> list[ModuleSummary]:
+    summaries = {
+        module_path: ModuleSummary(path=module_path, dependencies=sorted(dependencies))
+        for module_path, dependencies in module_dependencies.items()
+    }
+    for module_path, dependencies in module_dependencies.items():
+        for dependency in dependencies:
+            if dependency in summaries:
+                summaries[dependency].dependents.append(module_path)
+    for diagnostic in diagnostics:
+        module_path = diagnostic.usage_module()
+        if module_path in summaries:
+            summaries[module_path].diagnostics.append(diagnostic)
+    for summary in summaries.values():
+        summary.dependents.sort()
+    return sorted(summaries.values(), key=lambda summary: summary.path)
+
+
+def module_page_path(module_path: str) -> str:
+    # Module paths may contain characters like '<' (e.g. '<root>')
+    file_name = re.sub(r"[^\w.-]", "_", module_path)
+    return f"{MODULES_DIR}/{file_name}.html"
+
+
+def _page(title: str, body: str, stylesheet: str, scripts: str = "") -> str:
+    return (
+        "<!DOCTYPE html>\n"
+        '<html lang="en">\n<head>\n<meta charset="utf-8">\n'
+        f"<title>{escape(title)}</title>\n"
+        f'<link rel="stylesheet" href="{stylesheet}">\n'
+        f"</head>\n<body>\n{body}\n{scripts}</body>\n</html>\n"
+    )
+
+
+def _severity(diagnostic: Diagnostic) -> str:
+    return "error" if diagnostic.is_error() else "warning"
+
+
+def _location(diagnostic: Diagnostic) -> str:
+    file_path = diagnostic.pyfile_path()
+    if file_path is None:
+        return ""
+    line_number = diagnostic.pyline_number()
+    return file_path if line_number is None else f"{file_path}:{line_number}"
+
+
+def _module_link(module_path: str | None, known: set[str], prefix: str = "") -> str:
+    if not module_path:
+        return ""
+    if module_path not in known:
+        return escape(module_path)
+    href = prefix + module_page_path(module_path)
+    return f'<a href="{escape(href)}">{escape(module_path)}</a>'
+
+
+def _diagnostics_table(
+    diagnostics: list[Diagnostic], known: set[str], prefix: str = "", table_id: str = ""
+) -> str:
+    id_attribute = f' id="{table_id}"' if table_id else ""
+    rows = [
+        f'<tr data-severity="{_severity(diagnostic)}" '
+        f'data-code="{escape(diagnostic.code())}" '
+        f'data-module="{escape(diagnostic.usage_module() or "")}">'
+        f'<td class="{_severity(diagnostic)}">{_severity(diagnostic)}</td>'
+        f"<td>{escape(diagnostic.code())}</td>"
+        f"<td>{_module_link(diagnostic.usage_module(), known, prefix)}</td>"
+        f"<td>{escape(_location(diagnostic))}</td>"
+        f"<td>{escape(diagnostic.message())}</td></tr>"
+        for diagnostic in diagnostics
+    ]
+    return (
+        f"<table{id_attribute}>\n<thead><tr><th>Severity</th><th>Code</th>"
+        "<th>Module</th><th>Location</th><th>Message</th></tr></thead>\n"
+        "<tbody>\n" + "\n".join(rows) + "\n</tbody>\n</table>"
+    )
+
+
+def _select(name: str, label: str, values: list[str]) -> str:
+    options = "".join(
+        f'<option value="{escape(value)}">{escape(value)}</option>' for value in values
+    )
+    return (
+        f'<label>{label} <select data-filter="{name}">'
+        f'<option value="">All</option>{options}</select></label>'
+    )
+
+
+def _module_metrics_table(summaries: list[ModuleSummary], known: set[str]) -> str:
+    rows = [
+        f"<tr><td>{_module_link(summary.path, known)}</td>"
+        f'<td class="number">{summary.fan_in}</td>'
+        f'<td class="number">{summary.fan_out}</td>'
+        f'<td class="number">{summary.instability:.2f}</td>'
+        f'<td class="number">{summary.error_count}</td>'
+        f'<td class="number">{summary.warning_count}</td></tr>'
+        for summary in summaries
+    ]
+    return (
+        "<table>\n<thead><tr><th>Module</th><th>Fan-in</th><th>Fan-out</th>"
+        "<th>Instability</th><th>Errors</th><th>Warnings</th></tr></thead>\n"
+        "<tbody>\n" + "\n".join(rows) + "\n</tbody>\n</table>"
+    )
+
+
+def _domain_metrics_table(
+    domain_reports: list[DomainReport], heading: str = "Domain"
+) -> str:
+    rows = [
+        f"<tr><td>{escape(report.domain)}</td>"
+        f'<td class="number">{len(report.modules)}</td>'
+        f'<td class="number">{report.fan_in}</td>'
+        f'<td class="number">{report.fan_out}</td>'
+        f'<td class="number">{report.instability:.2f}</td>'
+        f'<td class="number">{report.error_count}</td>'
+        f'<td class="number">{report.warning_count}</td></tr>'
+        for report in domain_reports
+    ]
+    return (
+        f"<table>\n<thead><tr><th>{heading}</th><th>Modules</th><th>Fan-in</th>"
+        "<th>Fan-out</th><th>Instability</th><th>Errors</th><th>Warnings</th>"
+        "</tr></thead>\n<tbody>\n" + "\n".join(rows) + "\n</tbody>\n</table>"
+    )
+
+
+def _module_list(module_paths: list[str], known: set[str]) -> str:
+    if not module_paths:
+        return "<p>None</p>"
+    items = "".join(
+        f"<li>{_module_link(module_path, known, prefix='../')}</li>"
+        for module_path in module_paths
+    )
+    return f"<ul>{items}</ul>"
+
+
+def _module_page(summary: ModuleSummary, known: set[str]) -> str:
+    body = (
+        f'<p><a href="../index.html">Back to report</a></p>\n'
+        f"<h1>{escape(summary.path)}</h1>\n"
+        f"<p>Fan-in {summary.fan_in}, fan-out {summary.fan_out}, "
+        f"instability {summary.instability:.2f}, {summary.error_count} errors, "
+        f"{summary.warning_count} warnings</p>\n"
+        f"<h2>Dependencies</h2>\n{_module_list(summary.dependencies, known)}\n"
+        f"<h2>Dependents</h2>\n{_module_list(summary.dependents, known)}\n"
+        "<h2>Violations</h2>\n"
+        + (
+            _diagnostics_table(summary.diagnostics, known, prefix="../")
+            if summary.diagnostics
+            else "<p>None</p>"
+        )
+    )
+    return _page(summary.path, body, stylesheet="../style.css")
+
+
+def render_report_site(
+    title: str,
+    diagnostics: list[Diagnostic],
+    summaries: list[ModuleSummary],
+    mermaid_graph: str,
+    domain_reports: list[DomainReport] | None = None,
+    package_reports: list[DomainReport] | None = None,
+) -> dict[str, str]:
+    """Render the pages of the report, keyed by their path within the output directory.
+
+    The site is static: filtering violations happens in the browser, and the module
+    graph is rendered by Mermaid when it can be loaded (otherwise its source is shown).
+    """
+    known = {summary.path for summary in summaries}
+    error_count = sum(1 for diagnostic in diagnostics if diagnostic.is_error())
+    warning_count = sum(1 for diagnostic in diagnostics if diagnostic.is_warning())
+    filters = (
+        '<div class="filters">'
+        + _select("severity", "Severity", ["error", "warning"])
+        + _select("code", "Code", sorted({d.code() for d in diagnostics}))
+        + _select(
+            "module",
+            "Module",
+            sorted({d.usage_module() for d in diagnostics if d.usage_module()}),
+        )
+        + '<label>Search <input type="search" data-filter="text"></label></div>'
+    )
+    sections = [
+        f"<h1>{escape(title)}</h1>",
+        f"<p>{len(summaries)} modules, {error_count} errors, "
+        f"{warning_count} warnings</p>",
+        "<h2>Violations</h2>",
+        filters
+        + _diagnostics_table(diagnostics, known, table_id="violations")
+        if diagnostics
+        else "<p>None</p>",
+        "<h2>Modules</h2>",
+        _module_metrics_table(summaries, known),
+    ]
+    if domain_reports:
+        sections.extend(["<h2>Domains</h2>", _domain_metrics_table(domain_reports)])
+    if package_reports:
+        sections.extend(
+            ["<h2>Packages</h2>", _domain_metrics_table(package_reports, "Package")]
+        )
+    sections.extend(
+        ["<h2>Module graph</h2>", f'<pre class="mermaid">{escape(mermaid_graph)}</pre>']
+    )
+    scripts = (
+        f"<script>\n{FILTER_SCRIPT}</script>\n"
+        f'<script src="{MERMAID_SCRIPT_URL}"></script>\n'
+        "<script>if (window.mermaid) mermaid.initialize({ startOnLoad: true });"
+        "</script>\n"
+    )
+    pages = {
+        "index.html": _page(title, "\n".join(sections), "style.css", scripts),
+        "style.css": STYLESHEET,
+    }
+    for summary in summaries:
+        pages[module_page_path(summary.path)] = _module_page(summary, known)
+    return pages
+
+
+def generate_html_report(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    output_dir: Path,
+) -> Path:
+    diagnostics = check(
+        project_root=project_root,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=exclude_paths,
+    )
+    module_dependencies = get_module_dependencies(project_config)
+    domain_paths = project_config.domain_paths()
+    package_paths = project_config.package_paths()
+    pages = render_report_site(
+        title=f"Tach report for {project_root.name}",
+        diagnostics=diagnostics,
+        summaries=build_module_summaries(diagnostics, module_dependencies),
+        mermaid_graph=render_module_graph_mermaid(project_config, []),
+        domain_reports=group_by_domain(diagnostics, module_dependencies, domain_paths)
+        if domain_paths
+        else None,
+        package_reports=group_by_domain(
+            diagnostics, module_dependencies, package_paths, ungrouped=NO_PACKAGE
+        )
+        if package_paths
+        else None,
+    )
+    for relative_path, content in pages.items():
+        page_path = output_dir / relative_path
+        page_path.parent.mkdir(parents=True, exist_ok=True)
+        page_path.write_text(content, encoding="utf-8")
+    return output_dir / "index.html"
+
+
+__all__ = [
+    "ModuleSummary",
+    "build_module_summaries",
+    "render_report_site",
+    "generate_html_report",
+]
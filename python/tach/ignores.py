@@ -0,0 +1,127 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+from tach.extension import get_ignore_directive_usages
+
+if TYPE_CHECKING:
+    from tach.extension import (
+        IgnoreDirectiveUsage,
+        ProjectConfig,
+        Severity,
+        SuppressedViolation,
+    )
+
+
+def ignore_directive_usages(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+) -> list[IgnoreDirectiveUsage]:
+    return get_ignore_directive_usages(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+    )
+
+
+def severity_name(severity: Severity) -> str:
+    # Extension enums are displayed as 'Severity.Error'
+    return str(severity).rsplit(".", 1)[-1].lower()
+
+
+def is_stale(usage: IgnoreDirectiveUsage) -> bool:
+    return not usage.suppressed
+
+
+def _describe_directive(usage: IgnoreDirectiveUsage) -> str:
+    directive = "tach-ignore"
+    if usage.reason:
+        directive += f"({usage.reason})"
+    if usage.modules:
+        directive += " " + " ".join(usage.modules)
+    return directive
+
+
+def _describe_violation(violation: SuppressedViolation) -> str:
+    line = f"{severity_name(violation.severity)} [{violation.code}]"
+    if violation.dependency:
+        line += f" {violation.dependency}"
+    if violation.target_module:
+        line += f" -> {violation.target_module}"
+    return line
+
+
+def render_ignores_text(usages: list[IgnoreDirectiveUsage]) -> str:
+    if not usages:
+        return "No ignore directives found."
+
+    lines: list[str] = []
+    for usage in usages:
+        lines.append(
+            f"{BCOLORS.BOLD}{usage.file_path}:{usage.line_number}{BCOLORS.ENDC}  "
+            f"{_describe_directive(usage)}"
+        )
+        if usage.redundant:
+            lines.append(
+                f"    {BCOLORS.WARNING}stale, because another directive applies to the same line{BCOLORS.ENDC}"
+            )
+        elif is_stale(usage):
+            lines.append(
+                f"    {BCOLORS.WARNING}stale, because it suppressed nothing{BCOLORS.ENDC}"
+            )
+        for violation in usage.suppressed:
+            lines.append(f"    suppressed {_describe_violation(violation)}")
+
+    stale = sum(1 for usage in usages if is_stale(usage))
+    suppressed = [violation for usage in usages for violation in usage.suppressed]
+    errors = sum(
+        1 for violation in suppressed if severity_name(violation.severity) == "error"
+    )
+    lines.append(
+        f"\n{icons.WARNING if stale else icons.SUCCESS} {len(usages)} directive(s) "
+        f"suppressed {errors} error(s) and {len(suppressed) - errors} warning(s); "
+        f"{stale} stale."
+    )
+    return "\n".join(lines)
+
+
+def ignores_to_dict(usages: list[IgnoreDirectiveUsage]) -> list[dict[str, Any]]:
+    return [
+        {
+            "file_path": str(usage.file_path),
+            "line_number": usage.line_number,
+            "reason": usage.reason,
+            "modules": usage.modules,
+            "redundant": usage.redundant,
+            "stale": is_stale(usage),
+            "suppressed": [
+                {
+                    "code": violation.code,
+                    "severity": severity_name(violation.severity),
+                    "dependency": violation.dependency,
+                    "target_module": violation.target_module,
+                }
+                for violation in usage.suppressed
+            ],
+        }
+        for usage in usages
+    ]
+
+
+def render_ignores_json(usages: list[IgnoreDirectiveUsage]) -> str:
+    return json.dumps(ignores_to_dict(usages), indent=2)
+
+
+__all__ = [
+    "ignore_directive_usages",
+    "severity_name",
+    "is_stale",
+    "render_ignores_text",
+    "ignores_to_dict",
+    "render_ignores_json",
+]
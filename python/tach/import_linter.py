@@ -0,0 +1,373 @@
+from __future__ import annotations
+
+import configparser
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+import tomli
+import tomli_w
+
+from tach.errors import TachError
+from tach.init import detect_source_roots
+
+if TYPE_CHECKING:
+    from tach.extension import ModuleConfig, ProjectConfig
+
+# Files which import-linter reads its configuration from, in order of precedence
+CONFIG_FILES = [".importlinter", "setup.cfg", "pyproject.toml"]
+DEFAULT_EXPORT_FILE = ".importlinter"
+
+
+@dataclass
+class Contract:
+    id: str
+    name: str
+    type: str
+    # Multi-line INI values are split into lists
+    options: dict[str, Any] = field(default_factory=dict)
+
+    def values(self, key: str) -> list[str]:
+        value = self.options.get(key, [])
+        return [value] if isinstance(value, str) else list(value)
+
+    def flag(self, key: str) -> bool:
+        return str(self.options.get(key, "false")).lower() in ("true", "1", "yes")
+
+
+@dataclass
+class ImportLinterConfig:
+    root_packages: list[str]
+    contracts: list[Contract]
+
+
+@dataclass
+class Conversion:
+    content: str
+    # Rules which could not be converted exactly
+    warnings: list[str] = field(default_factory=list)
+
+
+def _split_lines(value: str) -> str | list[str]:
+    lines = [line.strip() for line in value.splitlines() if line.strip()]
+    if "\n" in value.strip():
+        return lines
+    return value.strip()
+
+
+def _read_ini(path: Path) -> ImportLinterConfig | None:
+    parser = configparser.ConfigParser(interpolation=None)
+    parser.read(path)
+    # setup.cfg namespaces the sections under 'tool:'
+    prefix = "tool:importlinter" if path.name == "setup.cfg" else "importlinter"
+    if not parser.has_section(prefix):
+        return None
+    options = {key: _split_lines(value) for key, value in parser[prefix].items()}
+    contracts: list[Contract] = []
+    for section in parser.sections():
+        if not section.startswith(f"{prefix}:contract:"):
+            continue
+        contract_options = {
+            key: _split_lines(value) for key, value in parser[section].items()
+        }
+        contract_id = section.rsplit(":", 1)[-1]
+        contracts.append(
+            Contract(
+                id=contract_id,
+                name=str(contract_options.pop("name", contract_id)),
+                type=str(contract_options.pop("type", "")),
+                options=contract_options,
+            )
+        )
+    return ImportLinterConfig(
+        root_packages=_root_packages(options), contracts=contracts
+    )
+
+
+def _read_toml(path: Path) -> ImportLinterConfig | None:
+    data = tomli.loads(path.read_text())
+    options = data.get("tool", {}).get("importlinter")
+    if options is None:
+        return None
+    contracts: list[Contract] = []
+    for index, contract in enumerate(options.get("contracts", [])):
+        contract = dict(contract)
+        contract_id = str(contract.pop("id", index + 1))
+        contracts.append(
+            Contract(
+                id=contract_id,
+                name=str(contract.pop("name", contract_id)),
+                type=str(contract.pop("type", "")),
+                options=contract,
+            )
+        )
+    return ImportLinterConfig(
+        root_packages=_root_packages(options), contracts=contracts
+    )
+
+
+def _root_packages(options: dict[str, Any]) -> list[str]:
+    root_packages = options.get("root_packages", options.get("root_package", []))
+    return [root_packages] if isinstance(root_packages, str) else list(root_packages)
+
+
+def read_import_linter_config(path: Path) -> ImportLinterConfig:
+    try:
+        if path.suffix == ".toml":
+            config = _read_toml(path)
+        else:
+            config = _read_ini(path)
+    except (configparser.Error, tomli.TOMLDecodeError) as e:
+        raise TachError(f"Failed to parse '{path}': {e}")
+    if config is None:
+        raise TachError(f"'{path}' does not contain an import-linter configuration.")
+    return config
+
+
+def find_import_linter_config(project_root: Path) -> Path | None:
+    for file_name in CONFIG_FILES:
+        path = project_root / file_name
+        if not path.is_file():
+            continue
+        try:
+            read_import_linter_config(path)
+        except TachError:
+            continue
+        return path
+    return None
+
+
+def _layer_names(entry: str) -> tuple[list[str], bool]:
+    """The modules in a layer, and whether they are independent of each other."""
+    # Optional layers are written in parentheses
+    names = [name.strip().strip("()").strip() for name in re.split(r"[|:]", entry)]
+    return [name for name in names if name], "|" in entry
+
+
+def _contains(package: str, module: str) -> bool:
+    return module == package or module.startswith(f"{package}.")
+
+
+class _Converter:
+    def __init__(self, root_packages: list[str]):
+        self.root_packages = root_packages
+        self.layers: list[str] = []
+        self.module_layers: dict[str, str] = {}
+        self.modules: list[str] = []
+        # Modules which each module may not import
+        self.disallowed: dict[str, set[str]] = {}
+        self.warnings: list[str] = []
+
+    def add_module(self, path: str) -> None:
+        if path not in self.modules:
+            self.modules.append(path)
+
+    def disallow(self, source: str, target: str) -> None:
+        self.add_module(source)
+        self.add_module(target)
+        self.disallowed.setdefault(source, set()).add(target)
+
+    def is_internal(self, path: str) -> bool:
+        return any(_contains(package, path) for package in self.root_packages)
+
+    def convert_layers(self, contract: Contract) -> None:
+        if self.layers:
+            self.warnings.append(
+                f"Contract '{contract.name}': tach supports a single list of layers, "
+                "so only the first layers contract is converted."
+            )
+            return
+        containers = contract.values("containers") or [""]
+        for entry in contract.values("layers"):
+            names, independent = _layer_names(entry)
+            if not names:
+                continue
+            layer = "_".join(name.rsplit(".", 1)[-1] for name in names)
+            self.layers.append(layer)
+            for container in containers:
+                paths = [f"{container}.{name}" if container else name for name in names]
+                for path in paths:
+                    self.add_module(path)
+                    self.module_layers[path] = layer
+                if independent:
+                    self.make_independent(paths)
+
+    def make_independent(self, paths: list[str]) -> None:
+        for source in paths:
+            for target in paths:
+                if source != target:
+                    self.disallow(source, target)
+
+    def convert_forbidden(self, contract: Contract) -> None:
+        for target in contract.values("forbidden_modules"):
+            if not self.is_internal(target):
+                self.warnings.append(
+                    f"Contract '{contract.name}': '{target}' is not in a root package. "
+                    "Use 'tach check-external' to restrict external dependencies."
+                )
+                continue
+            for source in contract.values("source_modules"):
+                self.disallow(source, target)
+        if not contract.flag("allow_indirect_imports"):
+            self.warnings.append(
+                f"Contract '{contract.name}': tach checks direct imports, so indirect "
+                "imports of the forbidden modules are not checked."
+            )
+
+    def convert(self, contract: Contract) -> None:
+        if contract.type == "layers":
+            self.convert_layers(contract)
+        elif contract.type == "forbidden":
+            self.convert_forbidden(contract)
+        elif contract.type == "independence":
+            self.make_independent(contract.values("modules"))
+        else:
+            self.warnings.append(
+                f"Contract '{contract.name}': '{contract.type}' contracts are not "
+                "supported, so it is not converted."
+            )
+            return
+        if contract.values("ignore_imports"):
+            self.warnings.append(
+                f"Contract '{contract.name}': 'ignore_imports' is not converted. "
+                "Use '# tach-ignore' comments on the ignored imports instead."
+            )
+
+    def module_table(self, path: str) -> dict[str, Any]:
+        module: dict[str, Any] = {"path": path}
+        if path in self.module_layers:
+            module["layer"] = self.module_layers[path]
+        disallowed = {
+            target
+            for source, targets in self.disallowed.items()
+            if _contains(source, path)
+            for target in targets
+        }
+        if disallowed:
+            # Restrictions apply to submodules too, so list every other allowed module
+            module["depends_on"] = [
+                other
+                for other in self.modules
+                if other != path
+                and not any(_contains(target, other) for target in disallowed)
+            ]
+        return module
+
+
+def contracts_to_tach(
+    config: ImportLinterConfig, source_roots: list[str] | None = None
+) -> Conversion:
+    """Translate import-linter contracts into an equivalent tach.toml."""
+    converter = _Converter(config.root_packages)
+    for contract in config.contracts:
+        converter.convert(contract)
+    data: dict[str, Any] = {"source_roots": source_roots or ["."]}
+    if converter.layers:
+        data["layers"] = converter.layers
+    data["modules"] = [converter.module_table(path) for path in converter.modules]
+    return Conversion(content=tomli_w.dumps(data), warnings=converter.warnings)
+
+
+def import_contracts(project_root: Path, path: Path) -> Conversion:
+    source_roots = [
+        source_root.relative_to(project_root).as_posix()
+        for source_root in detect_source_roots(project_root)
+    ]
+    return contracts_to_tach(read_import_linter_config(path), source_roots)
+
+
+def _ini_section(contract_id: str, options: dict[str, str | list[str]]) -> list[str]:
+    lines = [f"[importlinter:contract:{contract_id}]"]
+    for key, value in options.items():
+        if isinstance(value, list):
+            lines.append(f"{key} =")
+            lines.extend(f"    {item}" for item in value)
+        else:
+            lines.append(f"{key} = {value}")
+    return lines
+
+
+def _is_nested(module: ModuleConfig, other: ModuleConfig) -> bool:
+    return _contains(module.path, other.path) or _contains(other.path, module.path)
+
+
+def tach_to_contracts(project_config: ProjectConfig) -> Conversion:
+    """Translate tach layers and dependencies into import-linter contracts."""
+    modules = [
+        module for module in project_config.all_modules() if module.path != "<root>"
+    ]
+    warnings: list[str] = []
+    root_packages = sorted({module.path.split(".")[0] for module in modules})
+    lines = ["[importlinter]", "root_packages ="]
+    lines.extend(f"    {package}" for package in root_packages)
+
+    layers = [
+        " : ".join(sorted(module.path for module in modules if module.layer == layer))
+        for layer in project_config.layers
+    ]
+    layers = [layer for layer in layers if layer]
+    if len(layers) > 1:
+        lines.append("")
+        lines.extend(
+            _ini_section(
+                "layers", {"name": "Layers", "type": "layers", "layers": layers}
+            )
+        )
+
+    for module in modules:
+        if module.depends_on is None or module.unchecked:
+            continue
+        allowed = {dependency.path for dependency in module.depends_on}
+        forbidden = [
+            other.path
+            for other in modules
+            if other.path not in allowed
+            and not other.utility
+            and not _is_nested(module, other)
+        ]
+        if not forbidden:
+            continue
+        lines.append("")
+        lines.extend(
+            _ini_section(
+                f"{module.path.replace('.', '-')}-dependencies",
+                {
+                    "name": f"{module.path} dependencies",
+                    "type": "forbidden",
+                    "source_modules": [module.path],
+                    "forbidden_modules": forbidden,
+                    # tach checks direct imports only
+                    "allow_indirect_imports": "true",
+                },
+            )
+        )
+
+    if project_config.all_interfaces():
+        warnings.append(
+            "Interfaces have no import-linter equivalent, so they are not exported."
+        )
+    if any(module.visibility != ["*"] for module in modules):
+        warnings.append(
+            "Module visibility has no import-linter equivalent, so it is not exported."
+        )
+    if any(
+        project_config.layers_for_module(module.path) != project_config.layers
+        for module in modules
+    ):
+        warnings.append("Layers declared by domains are not exported.")
+    return Conversion(content="\n".join(lines) + "\n", warnings=warnings)
+
+
+__all__ = [
+    "CONFIG_FILES",
+    "DEFAULT_EXPORT_FILE",
+    "Contract",
+    "ImportLinterConfig",
+    "Conversion",
+    "read_import_linter_config",
+    "find_import_linter_config",
+    "contracts_to_tach",
+    "import_contracts",
+    "tach_to_contracts",
+]
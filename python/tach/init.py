@@ -0,0 +1,223 @@
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable
+
+import tomli_w
+
+from tach.colors import BCOLORS
+
+# Directories which are never proposed as source roots or modules
+IGNORED_DIRECTORIES = {
+    "build",
+    "dist",
+    "docs",
+    "node_modules",
+    "scripts",
+    "site-packages",
+    "test",
+    "tests",
+    "venv",
+}
+# Packages with these names are proposed as utilities, which any module may import
+UTILITY_NAMES = {"common", "helpers", "shared", "util", "utils"}
+# Packages with these names are proposed for a layer, highest layer first
+LAYER_NAMES = {
+    "interface": {"api", "cli", "commands", "handlers", "routes", "ui", "views", "web"},
+    "application": {"app", "application", "services", "usecases", "workflows"},
+    "domain": {"core", "domain", "entities", "models"},
+}
+
+
+@dataclass
+class InitProposal:
+    # Source roots relative to the project root, where '.' is the project root itself
+    source_roots: list[str]
+    modules: list[str]
+    utilities: list[str] = field(default_factory=list)
+    # The layer proposed for each module, by module path
+    module_layers: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def layers(self) -> list[str]:
+        used = set(self.module_layers.values())
+        return [layer for layer in LAYER_NAMES if layer in used]
+
+    def to_dict(self) -> dict[str, Any]:
+        modules: list[dict[str, Any]] = []
+        for path in self.modules:
+            module: dict[str, Any] = {"path": path, "depends_on": []}
+            if path in self.module_layers:
+                module["layer"] = self.module_layers[path]
+            if path in self.utilities:
+                module["utility"] = True
+            modules.append(module)
+        data: dict[str, Any] = {"source_roots": self.source_roots}
+        if self.layers:
+            data["layers"] = self.layers
+        data["modules"] = modules
+        return data
+
+    def to_toml(self) -> str:
+        return tomli_w.dumps(self.to_dict())
+
+
+def _is_candidate(directory: Path) -> bool:
+    return (
+        directory.is_dir()
+        and directory.name.isidentifier()
+        and directory.name not in IGNORED_DIRECTORIES
+    )
+
+
+def find_packages(directory: Path) -> list[str]:
+    """The names of the Python packages directly inside 'directory'."""
+    if not directory.is_dir():
+        return []
+    return sorted(
+        child.name
+        for child in directory.iterdir()
+        if _is_candidate(child) and (child / "__init__.py").is_file()
+    )
+
+
+def detect_source_roots(project_root: Path) -> list[Path]:
+    """Directories containing top-level Python packages.
+
+    A 'src' directory is preferred over the project root. When neither contains
+    packages, each child directory (or its 'src') is checked, as in a monorepo.
+    """
+    for candidate in [project_root / "src", project_root]:
+        if find_packages(candidate):
+            return [candidate]
+    source_roots: list[Path] = []
+    for child in sorted(project_root.iterdir()):
+        if not _is_candidate(child) or (child / "__init__.py").exists():
+            continue
+        for candidate in [child / "src", child]:
+            if find_packages(candidate):
+                source_roots.append(candidate)
+                break
+    return source_roots
+
+
+def propose_layer(module_path: str) -> str | None:
+    name = module_path.rsplit(".", 1)[-1].lower()
+    for layer, names in LAYER_NAMES.items():
+        if name in names:
+            return layer
+    return None
+
+
+def propose_configuration(project_root: Path) -> InitProposal:
+    """Propose source roots, modules, utilities and layers for a new project.
+
+    Each top-level package becomes a module. When there is a single top-level package,
+    its subpackages are proposed instead, since boundaries are more useful within it.
+    """
+    source_roots = detect_source_roots(project_root)
+    packages = [
+        (source_root, package)
+        for source_root in source_roots
+        for package in find_packages(source_root)
+    ]
+    modules = [package for _, package in packages]
+    if len(packages) == 1:
+        source_root, package = packages[0]
+        subpackages = find_packages(source_root / package)
+        if len(subpackages) > 1:
+            modules = [f"{package}.{subpackage}" for subpackage in subpackages]
+
+    utilities = [
+        module for module in modules if module.rsplit(".", 1)[-1] in UTILITY_NAMES
+    ]
+    module_layers: dict[str, str] = {}
+    for module in modules:
+        layer = propose_layer(module)
+        if layer is not None and module not in utilities:
+            module_layers[module] = layer
+    return InitProposal(
+        source_roots=[
+            source_root.relative_to(project_root).as_posix()
+            for source_root in source_roots
+        ],
+        modules=modules,
+        utilities=utilities,
+        module_layers=module_layers,
+    )
+
+
+def ask_yes_no(
+    question: str, default: bool = True, ask: Callable[[str], str] = input
+) -> bool:
+    options = "[Y/n]" if default else "[y/N]"
+    try:
+        answer = ask(f"{question} {options} ").strip().lower()
+    except EOFError:
+        return default
+    if not answer:
+        return default
+    return answer in ("y", "yes")
+
+
+def ask_list(
+    question: str, values: list[str], ask: Callable[[str], str] = input
+) -> list[str]:
+    """Show 'values' and return them, or the comma separated list the user enters."""
+    print(f"{BCOLORS.OKCYAN}{question}{BCOLORS.ENDC}")
+    for value in values:
+        print(f"  - {value}")
+    try:
+        answer = ask("Press Enter to accept, or enter a comma separated list: ").strip()
+    except EOFError:
+        return values
+    if not answer:
+        return values
+    return [value.strip() for value in answer.split(",") if value.strip()]
+
+
+def edit_proposal_interactive(
+    proposal: InitProposal, ask: Callable[[str], str] = input
+) -> InitProposal:
+    """Let the user adjust each part of the proposal in turn."""
+    source_roots = ask_list("Source roots:", proposal.source_roots, ask=ask)
+    modules = ask_list("Modules:", proposal.modules, ask=ask)
+    utilities = ask_list(
+        "Utilities (modules which any module may import):",
+        [module for module in proposal.utilities if module in modules],
+        ask=ask,
+    )
+    module_layers = {
+        module: layer
+        for module, layer in proposal.module_layers.items()
+        if module in modules and module not in utilities
+    }
+    if module_layers:
+        print(f"{BCOLORS.OKCYAN}Layers (highest first):{BCOLORS.ENDC}")
+        for layer in LAYER_NAMES:
+            members = [
+                module for module, value in module_layers.items() if value == layer
+            ]
+            if members:
+                print(f"  - {layer}: {', '.join(members)}")
+        if not ask_yes_no("Use these layers?", ask=ask):
+            module_layers = {}
+    return InitProposal(
+        source_roots=source_roots,
+        modules=modules,
+        utilities=[module for module in utilities if module in modules],
+        module_layers=module_layers,
+    )
+
+
+__all__ = [
+    "InitProposal",
+    "find_packages",
+    "detect_source_roots",
+    "propose_layer",
+    "propose_configuration",
+    "ask_yes_no",
+    "ask_list",
+    "edit_proposal_interactive",
+]
@@ -0,0 +1,197 @@
+from __future__ import annotations
+
+import json
+import re
+from collections import defaultdict
+from dataclasses import dataclass
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+from tach.modularity import build_usages
+
+if TYPE_CHECKING:
+    from tach.extension import InterfaceConfig, ModuleConfig, ProjectConfig
+    from tach.modularity import Usage
+
+# Utility modules imported by fewer modules than this are reported
+DEFAULT_MIN_UTILITY_CONSUMERS = 2
+
+
+@dataclass
+class LintIssue:
+    code: str
+    # The interface, module, or layer which the issue is about
+    subject: str
+    message: str
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"code": self.code, "subject": self.subject, "message": self.message}
+
+
+def _describe_interface(interface: InterfaceConfig) -> str:
+    expose = ", ".join(interface.expose)
+    from_modules = ", ".join(interface.from_modules)
+    return f"expose [{expose}] from [{from_modules}]"
+
+
+def _matches(patterns: list[str], value: str) -> bool:
+    return any(re.match(f"^{pattern}$", value) for pattern in patterns)
+
+
+def _member_matches(patterns: list[str], member: str) -> bool:
+    # Importing 'api.get_user' uses a member exposed as either 'api.get_user' or 'api'
+    parts = member.split(".")
+    return any(
+        _matches(patterns, ".".join(parts[:end])) for end in range(len(parts), 0, -1)
+    )
+
+
+def find_unused_interfaces(
+    interfaces: list[InterfaceConfig], module_paths: list[str], usages: list[Usage]
+) -> list[LintIssue]:
+    """Interfaces which match no modules, or whose members are never imported
+    from outside of the module which exposes them."""
+    issues: list[LintIssue] = []
+    for interface in interfaces:
+        subject = _describe_interface(interface)
+        modules = [
+            module_path
+            for module_path in module_paths
+            if _matches(interface.from_modules, module_path)
+        ]
+        if not modules:
+            issues.append(
+                LintIssue(
+                    code="unused-interface",
+                    subject=subject,
+                    message="The interface does not match any module.",
+                )
+            )
+            continue
+        consumed = any(
+            usage.module_path in modules
+            and usage.containing_module_path != usage.module_path
+            and _member_matches(
+                interface.expose, usage.full_path[len(usage.module_path) + 1 :]
+            )
+            for usage in usages
+        )
+        if not consumed:
+            issues.append(
+                LintIssue(
+                    code="unused-interface",
+                    subject=subject,
+                    message="No other module imports any member of the interface.",
+                )
+            )
+    return issues
+
+
+def find_narrow_utilities(
+    utility_paths: list[str],
+    usages: list[Usage],
+    min_consumers: int = DEFAULT_MIN_UTILITY_CONSUMERS,
+) -> list[LintIssue]:
+    """Utility modules which are imported by fewer than 'min_consumers' modules."""
+    consumers: dict[str, set[str]] = defaultdict(set)
+    for usage in usages:
+        if (
+            usage.containing_module_path is not None
+            and usage.containing_module_path != usage.module_path
+        ):
+            consumers[usage.module_path].add(usage.containing_module_path)
+    return [
+        LintIssue(
+            code="narrow-utility",
+            subject=utility_path,
+            message=(
+                f"The module is marked as a utility, but is only imported by "
+                f"{len(consumers[utility_path])} module(s)."
+            ),
+        )
+        for utility_path in sorted(utility_paths)
+        if len(consumers[utility_path]) < min_consumers
+    ]
+
+
+def find_sparse_layers(
+    layers: list[str], modules: list[ModuleConfig]
+) -> list[LintIssue]:
+    """Layers which contain at most one module."""
+    layer_modules: dict[str, list[str]] = defaultdict(list)
+    for module in modules:
+        if module.layer is not None:
+            layer_modules[module.layer].append(module.path)
+    issues: list[LintIssue] = []
+    for layer in layers:
+        members = layer_modules[layer]
+        if len(members) > 1:
+            continue
+        issues.append(
+            LintIssue(
+                code="sparse-layer",
+                subject=layer,
+                message=(
+                    f"The layer only contains the module '{members[0]}'."
+                    if members
+                    else "The layer does not contain any modules."
+                ),
+            )
+        )
+    return issues
+
+
+def lint_architecture(
+    project_root: Path,
+    project_config: ProjectConfig,
+    min_utility_consumers: int = DEFAULT_MIN_UTILITY_CONSUMERS,
+) -> list[LintIssue]:
+    project_root = project_root.resolve()
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    usages = build_usages(project_root, source_roots, project_config)
+    modules = project_config.all_modules()
+    return [
+        *find_unused_interfaces(
+            project_config.all_interfaces(),
+            [module.path for module in modules],
+            usages,
+        ),
+        *find_narrow_utilities(
+            project_config.utility_paths(), usages, min_consumers=min_utility_consumers
+        ),
+        *find_sparse_layers(project_config.layers, modules),
+    ]
+
+
+def render_lint_text(issues: list[LintIssue]) -> str:
+    if not issues:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}No issues found in the "
+            f"configuration.{BCOLORS.ENDC}"
+        )
+    return "\n".join(
+        f"{icons.WARNING} {BCOLORS.WARNING}[{issue.code}] {issue.subject}:"
+        f"{BCOLORS.ENDC} {issue.message}"
+        for issue in issues
+    )
+
+
+def render_lint_json(issues: list[LintIssue]) -> str:
+    return json.dumps([issue.to_dict() for issue in issues], indent=2)
+
+
+__all__ = [
+    "DEFAULT_MIN_UTILITY_CONSUMERS",
+    "LintIssue",
+    "find_unused_interfaces",
+    "find_narrow_utilities",
+    "find_sparse_layers",
+    "lint_architecture",
+    "render_lint_text",
+    "render_lint_json",
+]
@@ -1,5 +1,19 @@
 from __future__ import annotations
 
-from tach.logging.logger import CallInfo, init_logging, logger
+from tach.logging.logger import (
+    CallInfo,
+    init_logging,
+    init_structured_logging,
+    log_cache_lookup,
+    log_phase,
+    logger,
+)
 
-__all__ = ["logger", "CallInfo", "init_logging"]
+__all__ = [
+    "logger",
+    "CallInfo",
+    "init_logging",
+    "init_structured_logging",
+    "log_cache_lookup",
+    "log_phase",
+]
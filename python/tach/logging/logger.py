@@ -1,9 +1,13 @@
 from __future__ import annotations
 
+import atexit
 import json
 import logging
+import sys
+import time
+from contextlib import contextmanager
 from dataclasses import asdict, dataclass, field
-from typing import TYPE_CHECKING, Any, Dict
+from typing import TYPE_CHECKING, Any, Dict, Iterator, TextIO
 
 from tach import __version__
 from tach.cache import get_uid
@@ -14,6 +18,9 @@ if TYPE_CHECKING:
 
 logger = logging.getLogger("tach")
 logger.setLevel(logging.INFO)
+# Timings and cache lookups are only written to structured logs, never sent remotely
+events_logger = logging.getLogger("tach.events")
+events_logger.propagate = False
 
 
 def init_logging(project_root: Path) -> None:
@@ -21,12 +28,99 @@ def init_logging(project_root: Path) -> None:
     logger.addHandler(remote_handler)
 
 
+def init_structured_logging(stream: TextIO | None = None) -> None:
+    """Write all log records as JSON lines, followed by a summary when tach exits."""
+    handler = logging.StreamHandler(stream or sys.stderr)
+    handler.setFormatter(JsonLogFormatter())
+    logger.addHandler(handler)
+    events_logger.addHandler(handler)
+    atexit.register(log_run_summary)
+
+
 @dataclass
 class CallInfo:
     function: str
     parameters: Dict[str, Any] = field(default_factory=dict)
 
 
+@dataclass
+class CacheStats:
+    hits: int = 0
+    misses: int = 0
+
+    @property
+    def hit_rate(self) -> float | None:
+        lookups = self.hits + self.misses
+        return self.hits / lookups if lookups else None
+
+    def to_dict(self) -> dict[str, Any]:
+        return {"hits": self.hits, "misses": self.misses, "hit_rate": self.hit_rate}
+
+
+@dataclass
+class RunStats:
+    # Total duration of each phase, in milliseconds
+    phase_durations: Dict[str, float] = field(default_factory=dict)
+    caches: Dict[str, CacheStats] = field(default_factory=dict)
+
+
+run_stats = RunStats()
+
+
+def _log_event(message: str, **event: Any) -> None:
+    events_logger.info(message, extra={"event": event})
+
+
+@contextmanager
+def log_phase(phase: str) -> Iterator[None]:
+    start = time.perf_counter()
+    try:
+        yield
+    finally:
+        # Also runs when the phase exits early, e.g. through sys.exit
+        duration_ms = round((time.perf_counter() - start) * 1000, 3)
+        run_stats.phase_durations[phase] = (
+            run_stats.phase_durations.get(phase, 0.0) + duration_ms
+        )
+        _log_event(
+            "phase finished", event="phase", phase=phase, duration_ms=duration_ms
+        )
+
+
+def log_cache_lookup(cache: str, hit: bool) -> None:
+    stats = run_stats.caches.setdefault(cache, CacheStats())
+    if hit:
+        stats.hits += 1
+    else:
+        stats.misses += 1
+    _log_event("cache lookup", event="cache_lookup", cache=cache, hit=hit)
+
+
+def log_run_summary() -> None:
+    _log_event(
+        "run finished",
+        event="summary",
+        phases=run_stats.phase_durations,
+        caches={name: stats.to_dict() for name, stats in run_stats.caches.items()},
+    )
+
+
+class JsonLogFormatter(logging.Formatter):
+    def format(self, record: logging.LogRecord) -> str:
+        entry: dict[str, Any] = {
+            "timestamp": record.created,
+            "level": record.levelname,
+            "message": record.getMessage(),
+            "version": __version__,
+        }
+        if hasattr(record, "data"):
+            entry["call_info"] = asdict(getattr(record, "data"))
+        if hasattr(record, "event"):
+            entry.update(getattr(record, "event"))
+        # Parameters may hold values like paths, which are written as strings
+        return json.dumps(entry, default=str)
+
+
 class RemoteLoggingHandler(logging.Handler):
     def __init__(self, project_root: Path):
         super().__init__()
@@ -0,0 +1,260 @@
+from __future__ import annotations
+
+import tempfile
+from collections import Counter
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach.domains import get_module_dependencies
+from tach.errors import TachError, TachSetupError
+from tach.extension import check, diagnostic_fingerprints
+from tach.filesystem.git_ops import get_changed_files
+from tach.modules import module_for_file
+from tach.parsing import extend_and_validate, parse_project_config
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ProjectConfig
+
+# Keeps the comment well within the size limits of code review tools
+MAX_LISTED_ITEMS = 50
+
+
+@dataclass(frozen=True)
+class Violation:
+    code: str
+    module: str | None
+    file_path: str | None
+    line_number: int | None
+    message: str
+    is_error: bool
+    fingerprint: str
+
+    @classmethod
+    def from_diagnostic(cls, diagnostic: Diagnostic, fingerprint: str) -> Violation:
+        return cls(
+            code=diagnostic.code(),
+            module=diagnostic.usage_module(),
+            file_path=diagnostic.pyfile_path(),
+            line_number=diagnostic.pyline_number(),
+            message=diagnostic.message(),
+            is_error=diagnostic.is_error(),
+            fingerprint=fingerprint,
+        )
+
+
+@dataclass
+class ProjectSnapshot:
+    violations: list[Violation] = field(default_factory=list)
+    module_dependencies: dict[str, list[str]] = field(default_factory=dict)
+
+
+@dataclass
+class PullRequestReport:
+    new_violations: list[Violation] = field(default_factory=list)
+    resolved_violation_count: int = 0
+    affected_modules: list[str] = field(default_factory=list)
+    added_dependencies: list[tuple[str, str]] = field(default_factory=list)
+    removed_dependencies: list[tuple[str, str]] = field(default_factory=list)
+    added_modules: list[str] = field(default_factory=list)
+    removed_modules: list[str] = field(default_factory=list)
+
+
+def _edges(module_dependencies: dict[str, list[str]]) -> set[tuple[str, str]]:
+    return {
+        (module_path, dependency)
+        for module_path, dependencies in module_dependencies.items()
+        for dependency in dependencies
+    }
+
+
+def compare_snapshots(
+    base: ProjectSnapshot, head: ProjectSnapshot, changed_modules: set[str]
+) -> PullRequestReport:
+    """Compare the violations and module graph of a project before and after a change.
+
+    Violations are matched by fingerprint, which does not depend on line numbers.
+    Repeated occurrences of the same violation in a file have distinct fingerprints,
+    so only the additional occurrences are new.
+    """
+    remaining = Counter(violation.fingerprint for violation in base.violations)
+    new_violations: list[Violation] = []
+    for violation in head.violations:
+        if remaining[violation.fingerprint]:
+            remaining[violation.fingerprint] -= 1
+        else:
+            new_violations.append(violation)
+
+    base_edges = _edges(base.module_dependencies)
+    head_edges = _edges(head.module_dependencies)
+    affected_modules = changed_modules | {
+        violation.module for violation in new_violations if violation.module
+    }
+    return PullRequestReport(
+        new_violations=new_violations,
+        resolved_violation_count=sum(remaining.values()),
+        affected_modules=sorted(affected_modules),
+        added_dependencies=sorted(head_edges - base_edges),
+        removed_dependencies=sorted(base_edges - head_edges),
+        added_modules=sorted(
+            set(head.module_dependencies) - set(base.module_dependencies)
+        ),
+        removed_modules=sorted(
+            set(base.module_dependencies) - set(head.module_dependencies)
+        ),
+    )
+
+
+def _bullets(items: list[str]) -> list[str]:
+    lines = [f"- {item}" for item in items[:MAX_LISTED_ITEMS]]
+    if len(items) > MAX_LISTED_ITEMS:
+        lines.append(f"- ... and {len(items) - MAX_LISTED_ITEMS} more")
+    return lines
+
+
+def _violation_row(violation: Violation) -> str:
+    location = ""
+    if violation.file_path:
+        location = f"`{violation.file_path}`"
+        if violation.line_number is not None:
+            location = f"`{violation.file_path}:{violation.line_number}`"
+    severity = "error" if violation.is_error else "warning"
+    # Pipes would otherwise end the table cell
+    message = violation.message.replace("|", "\\|").replace("\n", " ")
+    return f"| {severity} | `{violation.code}` | {location} | {message} |"
+
+
+def render_markdown_report(report: PullRequestReport, base: str) -> str:
+    error_count = sum(1 for violation in report.new_violations if violation.is_error)
+    warning_count = len(report.new_violations) - error_count
+    lines = [
+        "## Tach report",
+        "",
+        f"Compared to `{base}`: **{error_count} new errors**, "
+        f"{warning_count} new warnings, "
+        f"{report.resolved_violation_count} resolved.",
+    ]
+
+    if report.new_violations:
+        lines.extend(
+            [
+                "",
+                "### New violations",
+                "",
+                "| Severity | Code | Location | Message |",
+                "| --- | --- | --- | --- |",
+            ]
+        )
+        lines.extend(
+            _violation_row(violation)
+            for violation in report.new_violations[:MAX_LISTED_ITEMS]
+        )
+        if len(report.new_violations) > MAX_LISTED_ITEMS:
+            lines.extend(
+                ["", f"... and {len(report.new_violations) - MAX_LISTED_ITEMS} more"]
+            )
+
+    if report.affected_modules:
+        lines.extend(["", "### Affected modules", ""])
+        lines.extend(_bullets([f"`{module}`" for module in report.affected_modules]))
+
+    graph_changes = (
+        [f"Added module `{module}`" for module in report.added_modules]
+        + [f"Removed module `{module}`" for module in report.removed_modules]
+        + [f"`{source}` → `{target}`" for source, target in report.added_dependencies]
+        + [
+            f"~~`{source}` → `{target}`~~"
+            for source, target in report.removed_dependencies
+        ]
+    )
+    lines.extend(["", "### Module graph changes", ""])
+    lines.extend(_bullets(graph_changes) if graph_changes else ["No changes."])
+    return "\n".join(lines) + "\n"
+
+
+def _snapshot(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> ProjectSnapshot:
+    diagnostics = check(
+        project_root=project_root,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=exclude_paths,
+    )
+    return ProjectSnapshot(
+        violations=[
+            Violation.from_diagnostic(diagnostic, fingerprint)
+            for diagnostic, fingerprint in zip(
+                diagnostics, diagnostic_fingerprints(diagnostics)
+            )
+        ],
+        module_dependencies=get_module_dependencies(project_config),
+    )
+
+
+def _base_snapshot(project_root: Path, base: str) -> ProjectSnapshot:
+    # Local import because git-python takes ~80ms to load
+    from git import GitCommandError, InvalidGitRepositoryError, NoSuchPathError, Repo
+
+    try:
+        repo = Repo(project_root, search_parent_directories=True)
+    except (InvalidGitRepositoryError, NoSuchPathError):
+        raise TachSetupError("The project does not appear to be a git repository!")
+
+    git_root = Path(repo.git.rev_parse("--show-toplevel")).resolve()
+    with tempfile.TemporaryDirectory() as tmp_dir:
+        worktree = Path(tmp_dir) / "base"
+        try:
+            repo.git.worktree("add", "--detach", str(worktree), base)
+        except GitCommandError:
+            raise TachError(f"Failed to check out '{base}'!")
+        try:
+            base_root = worktree / project_root.resolve().relative_to(git_root)
+            base_config = parse_project_config(base_root)
+            if base_config is None:
+                # The project did not use Tach before this change
+                return ProjectSnapshot()
+            exclude_paths = extend_and_validate(
+                None,
+                base_config.exclude + base_config.source_root_exclude_paths(),
+                base_config.use_regex_matching,
+            )
+            return _snapshot(base_root, base_config, exclude_paths)
+        finally:
+            repo.git.worktree("remove", "--force", str(worktree))
+
+
+def generate_markdown_report(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    base: str = "main",
+) -> str:
+    head = _snapshot(project_root, project_config, exclude_paths)
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    module_paths = list(head.module_dependencies)
+    changed_modules = {
+        module_path
+        for changed_file in get_changed_files(project_root, base=base)
+        if changed_file.suffix == ".py"
+        for module_path in [module_for_file(changed_file, source_roots, module_paths)]
+        if module_path is not None
+    }
+    report = compare_snapshots(
+        _base_snapshot(project_root, base), head, changed_modules
+    )
+    return render_markdown_report(report, base)
+
+
+__all__ = [
+    "Violation",
+    "ProjectSnapshot",
+    "PullRequestReport",
+    "compare_snapshots",
+    "render_markdown_report",
+    "generate_markdown_report",
+]
@@ -0,0 +1,282 @@
+from __future__ import annotations
+
+import ast
+import csv
+import io
+import json
+from dataclasses import dataclass
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach.domains import get_module_dependencies
+from tach.extension import get_dependency_graph
+from tach.filesystem import walk_pyfiles
+from tach.html_report import build_module_summaries
+from tach.modules import module_for_file
+
+if TYPE_CHECKING:
+    from tach.extension import DependencyGraph, ProjectConfig
+
+METRIC_FIELDS = [
+    "module",
+    "afferent_coupling",
+    "efferent_coupling",
+    "instability",
+    "classes",
+    "abstract_classes",
+    "abstractness",
+    "distance",
+    "files",
+    "lines",
+    "public_symbols",
+    "imports",
+]
+
+
+@dataclass
+class ModuleMetrics:
+    module: str
+    # The number of modules which depend on this module, and which it depends on
+    afferent_coupling: int = 0
+    efferent_coupling: int = 0
+    classes: int = 0
+    abstract_classes: int = 0
+    # Size totals over the files which belong to the module
+    files: int = 0
+    lines: int = 0
+    public_symbols: int = 0
+    imports: int = 0
+
+    @property
+    def instability(self) -> float:
+        coupling = self.afferent_coupling + self.efferent_coupling
+        return self.efferent_coupling / coupling if coupling else 0.0
+
+    @property
+    def abstractness(self) -> float:
+        return self.abstract_classes / self.classes if self.classes else 0.0
+
+    @property
+    def distance(self) -> float:
+        # Distance from the main sequence, where abstractness + instability = 1
+        return abs(self.abstractness + self.instability - 1)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "module": self.module,
+            "afferent_coupling": self.afferent_coupling,
+            "efferent_coupling": self.efferent_coupling,
+            "instability": round(self.instability, 3),
+            "classes": self.classes,
+            "abstract_classes": self.abstract_classes,
+            "abstractness": round(self.abstractness, 3),
+            "distance": round(self.distance, 3),
+            "files": self.files,
+            "lines": self.lines,
+            "public_symbols": self.public_symbols,
+            "imports": self.imports,
+        }
+
+
+def _is_abstract_decorator(decorator: ast.expr) -> bool:
+    # Matches 'abstractmethod' as well as 'abc.abstractmethod'
+    name = decorator.attr if isinstance(decorator, ast.Attribute) else None
+    if isinstance(decorator, ast.Name):
+        name = decorator.id
+    return name is not None and name.startswith("abstract")
+
+
+def _is_stub_statement(statement: ast.stmt) -> bool:
+    if isinstance(statement, ast.Pass):
+        return True
+    if isinstance(statement, ast.Expr):
+        # A docstring or '...'
+        try:
+            value = ast.literal_eval(statement.value)
+        except (ValueError, TypeError):
+            return False
+        return isinstance(value, str) or value is ...
+    if isinstance(statement, ast.Raise) and statement.exc is not None:
+        exc = statement.exc
+        if isinstance(exc, ast.Call):
+            exc = exc.func
+        return isinstance(exc, ast.Name) and exc.id == "NotImplementedError"
+    return False
+
+
+def is_abstract_class(node: ast.ClassDef) -> bool:
+    """Approximate whether a class is abstract: all of its methods must be abstract.
+
+    A method is abstract when it is decorated with 'abstractmethod' (or a variant),
+    or when its body is only a docstring, 'pass', '...', or 'raise NotImplementedError'.
+    Classes without methods are considered concrete.
+    """
+    methods = [
+        statement
+        for statement in node.body
+        if isinstance(statement, (ast.FunctionDef, ast.AsyncFunctionDef))
+    ]
+    return bool(methods) and all(
+        any(_is_abstract_decorator(decorator) for decorator in method.decorator_list)
+        or all(_is_stub_statement(statement) for statement in method.body)
+        for method in methods
+    )
+
+
+def count_classes(source: str) -> tuple[int, int]:
+    """Count the classes defined in the source, and how many of them are abstract."""
+    classes = [
+        node for node in ast.walk(ast.parse(source)) if isinstance(node, ast.ClassDef)
+    ]
+    return len(classes), sum(1 for node in classes if is_abstract_class(node))
+
+
+def count_module_classes(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> dict[str, tuple[int, int]]:
+    """Count the classes in each module, and how many of them are abstract."""
+    project_root = project_root.resolve()
+    module_paths = list(get_module_dependencies(project_config))
+    classes: dict[str, tuple[int, int]] = {}
+
+    source_roots = [
+        (project_root / source_root).resolve()
+        for source_root in project_config.source_roots
+    ]
+    for source_root in source_roots:
+        for pyfile in walk_pyfiles(
+            source_root,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            use_regex_matching=project_config.use_regex_matching,
+        ):
+            file_path = source_root / pyfile
+            module_path = module_for_file(file_path, source_roots, module_paths)
+            if module_path is None:
+                continue
+            try:
+                file_classes, file_abstract_classes = count_classes(
+                    file_path.read_text()
+                )
+            except (SyntaxError, UnicodeDecodeError, OSError):
+                # Files which can't be parsed are skipped, as they are by 'tach check'
+                continue
+            total, abstract = classes.get(module_path, (0, 0))
+            classes[module_path] = (
+                total + file_classes,
+                abstract + file_abstract_classes,
+            )
+    return classes
+
+
+def build_metrics(
+    project_config: ProjectConfig,
+    graph: DependencyGraph,
+    classes: dict[str, tuple[int, int]],
+) -> list[ModuleMetrics]:
+    """Combine declared coupling with the sizes in the graph and the class counts."""
+    summaries = build_module_summaries([], get_module_dependencies(project_config))
+    metrics = {
+        summary.path: ModuleMetrics(
+            module=summary.path,
+            afferent_coupling=summary.fan_in,
+            efferent_coupling=summary.fan_out,
+        )
+        for summary in summaries
+    }
+    for module_path, (total, abstract) in classes.items():
+        if module_path in metrics:
+            metrics[module_path].classes = total
+            metrics[module_path].abstract_classes = abstract
+    for node in graph.nodes:
+        if node.path in metrics:
+            metrics[node.path].files = node.files
+            metrics[node.path].lines = node.lines
+            metrics[node.path].public_symbols = node.public_symbols
+            metrics[node.path].imports = node.imports
+
+    return list(metrics.values())
+
+
+def compute_metrics(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> list[ModuleMetrics]:
+    project_root = project_root.resolve()
+    classes = count_module_classes(project_root, project_config, exclude_paths)
+    graph = get_dependency_graph(project_root, project_config, exclude_paths)
+    return build_metrics(project_config, graph, classes)
+
+
+def render_metrics_table(metrics: list[ModuleMetrics]) -> str:
+    headers = [
+        "Module",
+        "Ca",
+        "Ce",
+        "I",
+        "Classes",
+        "Abstract",
+        "A",
+        "D",
+        "Files",
+        "Lines",
+        "Symbols",
+        "Imports",
+    ]
+    rows = [
+        [
+            module_metrics.module,
+            str(module_metrics.afferent_coupling),
+            str(module_metrics.efferent_coupling),
+            f"{module_metrics.instability:.2f}",
+            str(module_metrics.classes),
+            str(module_metrics.abstract_classes),
+            f"{module_metrics.abstractness:.2f}",
+            f"{module_metrics.distance:.2f}",
+            str(module_metrics.files),
+            str(module_metrics.lines),
+            str(module_metrics.public_symbols),
+            str(module_metrics.imports),
+        ]
+        for module_metrics in metrics
+    ]
+    widths = [
+        max(len(row[column]) for row in [headers, *rows])
+        for column in range(len(headers))
+    ]
+    lines = [
+        # Module paths are left-aligned, and numbers right-aligned
+        "  ".join(
+            cell.ljust(width) if column == 0 else cell.rjust(width)
+            for column, (cell, width) in enumerate(zip(row, widths))
+        ).rstrip()
+        for row in [headers, *rows]
+    ]
+    lines.insert(1, "  ".join("-" * width for width in widths))
+    return "\n".join(lines)
+
+
+def render_metrics_json(metrics: list[ModuleMetrics]) -> str:
+    return json.dumps(
+        [module_metrics.to_dict() for module_metrics in metrics], indent=2
+    )
+
+
+def render_metrics_csv(metrics: list[ModuleMetrics]) -> str:
+    output = io.StringIO()
+    writer = csv.DictWriter(output, fieldnames=METRIC_FIELDS, lineterminator="\n")
+    writer.writeheader()
+    writer.writerows(module_metrics.to_dict() for module_metrics in metrics)
+    return output.getvalue()
+
+
+__all__ = [
+    "ModuleMetrics",
+    "is_abstract_class",
+    "count_classes",
+    "count_module_classes",
+    "build_metrics",
+    "compute_metrics",
+    "render_metrics_table",
+    "render_metrics_json",
+    "render_metrics_csv",
+]
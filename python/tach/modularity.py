@@ -297,7 +297,9 @@ def generate_modularity_report(
     report.modules = build_modules(project_config)
     report.usages = build_usages(project_root, source_roots, project_config)
     exclude_paths = extend_and_validate(
-        None, project_config.exclude, project_config.use_regex_matching
+        None,
+        project_config.exclude + project_config.source_root_exclude_paths(),
+        project_config.use_regex_matching,
     )
     check_diagnostics = check(
         project_root=project_root,
@@ -0,0 +1,29 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+
+def module_for_file(
+    file_path: Path, source_roots: list[Path], module_paths: list[str]
+) -> str | None:
+    # The innermost module which contains the file, if any
+    for source_root in source_roots:
+        try:
+            relative_path = file_path.relative_to(source_root)
+        except ValueError:
+            continue
+        parts = list(relative_path.with_suffix("").parts)
+        if parts and parts[-1] == "__init__":
+            parts.pop()
+        dotted_path = ".".join(parts)
+        containing = [
+            module_path
+            for module_path in module_paths
+            if dotted_path == module_path or dotted_path.startswith(f"{module_path}.")
+        ]
+        if containing:
+            return max(containing, key=len)
+    return None
+
+
+__all__ = ["module_for_file"]
@@ -0,0 +1,106 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Callable
+
+from tach.colors import BCOLORS
+from tach.init import ask_list, ask_yes_no
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+
+@dataclass
+class NewModuleOptions:
+    path: str
+    layer: str | None = None
+    # The members exposed by a stub interface for the module, if one is created
+    interface: list[str] | None = None
+
+
+def in_domain(path: str, domain: str) -> bool:
+    return path == domain or path.startswith(f"{domain}.")
+
+
+def module_path_in_domain(path: str, domain: str) -> str:
+    """Resolve 'path' against 'domain', unless it is already within the domain."""
+    if in_domain(path, domain):
+        return path
+    return f"{domain}.{path}"
+
+
+def ask_choice(
+    question: str, choices: list[str], ask: Callable[[str], str] = input
+) -> str | None:
+    """Show 'choices' and return the one the user picks, or None to skip."""
+    print(f"{BCOLORS.OKCYAN}{question}{BCOLORS.ENDC}")
+    for index, choice in enumerate(choices, start=1):
+        print(f"  {index}. {choice}")
+    while True:
+        try:
+            answer = ask("Enter a number or name, or press Enter to skip: ").strip()
+        except EOFError:
+            return None
+        if not answer:
+            return None
+        if answer.isdigit() and 1 <= int(answer) <= len(choices):
+            return choices[int(answer) - 1]
+        if answer in choices:
+            return answer
+        print(f"{BCOLORS.WARNING}'{answer}' is not one of the choices.{BCOLORS.ENDC}")
+
+
+def prompt_new_module_options(
+    project_config: ProjectConfig,
+    options: NewModuleOptions,
+    ask_domain: bool = True,
+    ask: Callable[[str], str] = input,
+) -> NewModuleOptions:
+    """Ask for each part of the module's configuration which was not given as a flag."""
+    path = options.path
+    domains = project_config.domain_paths()
+    if ask_domain and domains and not any(in_domain(path, d) for d in domains):
+        domain = ask_choice("Domain:", domains, ask=ask)
+        if domain is not None:
+            path = module_path_in_domain(path, domain)
+
+    layer = options.layer
+    layers = project_config.layers_for_module(path)
+    if layer is None and layers:
+        layer = ask_choice("Layer (highest first):", layers, ask=ask)
+
+    interface = options.interface
+    if interface is None and ask_yes_no(
+        f"Add an interface for '{path}'?", default=False, ask=ask
+    ):
+        interface = ask_list("Members to expose (e.g. 'get_invoice'):", [], ask=ask)
+    return NewModuleOptions(path=path, layer=layer, interface=interface)
+
+
+def register_new_module(
+    project_config: ProjectConfig, options: NewModuleOptions
+) -> None:
+    """Add the module to the configuration, along with its layer and interface."""
+    if options.layer is not None:
+        layers = project_config.layers_for_module(options.path)
+        if options.layer not in layers:
+            raise ValueError(
+                f"Unknown layer '{options.layer}'. "
+                f"Layers for '{options.path}': {', '.join(layers) or '(none)'}"
+            )
+    project_config.create_module(options.path)
+    if options.layer is not None:
+        project_config.set_module_layer(options.path, options.layer)
+    if options.interface is not None:
+        project_config.create_interface(options.path, options.interface)
+    project_config.save_edits()
+
+
+__all__ = [
+    "NewModuleOptions",
+    "in_domain",
+    "module_path_in_domain",
+    "ask_choice",
+    "prompt_new_module_options",
+    "register_new_module",
+]
@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+from string import Formatter
+from typing import TYPE_CHECKING
+
+from tach.errors import TachConfigError
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic
+
+TEMPLATE_FIELDS = ("path", "line", "column", "code", "severity", "message", "module")
+
+
+def validate_output_template(template: str) -> None:
+    try:
+        fields = [
+            field_name
+            for _, field_name, _, _ in Formatter().parse(template)
+            if field_name is not None
+        ]
+    except ValueError as e:
+        raise TachConfigError(f"Invalid output format '{template}': {e}") from e
+    for field_name in fields:
+        if field_name not in TEMPLATE_FIELDS:
+            raise TachConfigError(
+                f"Unknown field '{{{field_name}}}' in output format '{template}'. "
+                f"Available fields: {', '.join(TEMPLATE_FIELDS)}"
+            )
+
+
+def format_diagnostic(template: str, diagnostic: Diagnostic) -> str:
+    """Render a diagnostic with a user-provided template.
+
+    Location fields are empty for diagnostics which do not point into a file.
+    When a diagnostic has a line but no precise span, the column is 1.
+    """
+    line = diagnostic.pyline_number()
+    span = diagnostic.span()
+    if span is not None:
+        column = str(span.column)
+    elif line is not None:
+        column = "1"
+    else:
+        column = ""
+    return template.format(
+        path=diagnostic.pyfile_path() or "",
+        line="" if line is None else str(line),
+        column=column,
+        code=diagnostic.code(),
+        severity="error" if diagnostic.is_error() else "warning",
+        message=diagnostic.message(),
+        module=diagnostic.usage_module() or "",
+    )
+
+
+def format_diagnostics_with_template(
+    template: str, diagnostics: list[Diagnostic]
+) -> str:
+    return "\n".join(
+        format_diagnostic(template, diagnostic) for diagnostic in diagnostics
+    )
+
+
+__all__ = [
+    "TEMPLATE_FIELDS",
+    "validate_output_template",
+    "format_diagnostic",
+    "format_diagnostics_with_template",
+]
@@ -0,0 +1,62 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from tach.extension import serialize_diagnostics_json
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic
+
+UNOWNED = "(unowned)"
+
+
+@dataclass
+class OwnerReport:
+    owner: str
+    modules: list[str] = field(default_factory=list)
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+
+    @property
+    def error_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_error())
+
+    @property
+    def warning_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_warning())
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "modules": self.modules,
+            "errors": self.error_count,
+            "warnings": self.warning_count,
+            "diagnostics": json.loads(
+                serialize_diagnostics_json(self.diagnostics, pretty_print=False)
+            ),
+        }
+
+
+def group_by_owner(
+    diagnostics: list[Diagnostic], module_owners: dict[str, str]
+) -> list[OwnerReport]:
+    """Group diagnostics by the owner of the module in which they occur.
+
+    Diagnostics which are not attributed to a module, or whose module has no owner,
+    are grouped under UNOWNED. Reports are sorted by owner, with UNOWNED last.
+    """
+    reports: dict[str, OwnerReport] = {}
+    for module_path, owner in module_owners.items():
+        reports.setdefault(owner, OwnerReport(owner=owner)).modules.append(module_path)
+    for diagnostic in diagnostics:
+        usage_module = diagnostic.usage_module()
+        owner = module_owners.get(usage_module, UNOWNED) if usage_module else UNOWNED
+        reports.setdefault(owner, OwnerReport(owner=owner)).diagnostics.append(
+            diagnostic
+        )
+    return sorted(
+        reports.values(), key=lambda report: (report.owner == UNOWNED, report.owner)
+    )
+
+
+__all__ = ["OwnerReport", "group_by_owner", "UNOWNED"]
@@ -15,6 +15,11 @@ from tach.extension import (
 from tach.extension import (
     parse_project_config as ext_parse_project_config,
 )
+from tach.utils.exclude import (
+    REGEX_PATTERN_PREFIX,
+    is_regex_pattern,
+    strip_pattern_prefix,
+)
 
 if TYPE_CHECKING:
     from pathlib import Path
@@ -113,14 +118,13 @@ def extend_and_validate(
     else:
         exclude_paths = project_excludes
 
-    if not use_regex_matching:
-        return exclude_paths
-
     for exclude_path in exclude_paths:
+        if not is_regex_pattern(exclude_path, use_regex_matching):
+            continue
         try:
-            re.compile(exclude_path)
+            re.compile(strip_pattern_prefix(exclude_path))
         except re.error:
             raise TachConfigError(
-                f"Invalid regex pattern: '{exclude_path}'. To use glob matching, remove the 'use_regex_matching' setting from your {CONFIG_FILE_NAME}.toml file."
+                f"Invalid regex pattern: '{exclude_path}'. To use glob matching, remove the '{REGEX_PATTERN_PREFIX}' prefix or the 'use_regex_matching' setting from your {CONFIG_FILE_NAME}.toml file."
             )
     return exclude_paths
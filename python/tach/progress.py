@@ -0,0 +1,111 @@
+from __future__ import annotations
+
+import json
+import sys
+import time
+from typing import TextIO
+
+BAR_WIDTH = 30
+# Limits how often progress is redrawn or emitted, to keep output manageable
+MIN_UPDATE_INTERVAL = 0.1
+
+
+class ProgressReporter:
+    """Reports progress through the phases of a command, e.g. files checked."""
+
+    def __init__(self, stream: TextIO):
+        self.stream = stream
+        self.phase: str | None = None
+        self.total: int | None = None
+        self.processed = 0
+        self._last_update = 0.0
+
+    def start_phase(self, phase: str, total: int | None = None) -> None:
+        if self.phase is not None:
+            self._end_phase()
+        self.phase = phase
+        self.total = total
+        self.processed = 0
+        self._last_update = 0.0
+        self._update(force=True)
+
+    def advance(self, count: int = 1) -> None:
+        self.processed += count
+        self._update(force=self.processed == self.total)
+
+    def finish(self) -> None:
+        if self.phase is not None:
+            self._end_phase()
+        self.phase = None
+
+    def _update(self, force: bool = False) -> None:
+        now = time.monotonic()
+        if not force and now - self._last_update < MIN_UPDATE_INTERVAL:
+            return
+        self._last_update = now
+        self._emit()
+
+    def _emit(self) -> None:
+        raise NotImplementedError
+
+    def _end_phase(self) -> None:
+        raise NotImplementedError
+
+
+class TerminalProgressReporter(ProgressReporter):
+    """Draws a progress bar, redrawn in place on a single line."""
+
+    def _emit(self) -> None:
+        if self.total:
+            filled = BAR_WIDTH * min(self.processed, self.total) // self.total
+            bar = "#" * filled + "-" * (BAR_WIDTH - filled)
+            line = f"{self.phase} [{bar}] {self.processed}/{self.total}"
+        else:
+            line = f"{self.phase} ({self.processed})"
+        # Clear the rest of the line in case the previous one was longer
+        self.stream.write(f"\r{line}\x1b[K")
+        self.stream.flush()
+
+    def _end_phase(self) -> None:
+        self.stream.write("\r\x1b[K")
+        self.stream.flush()
+
+
+class JsonProgressReporter(ProgressReporter):
+    """Writes progress as one JSON event per line, for consumption by other tools."""
+
+    def _write_event(self, event: str) -> None:
+        data = {
+            "event": event,
+            "phase": self.phase,
+            "processed": self.processed,
+            "total": self.total,
+        }
+        self.stream.write(json.dumps(data) + "\n")
+        self.stream.flush()
+
+    def _emit(self) -> None:
+        self._write_event("progress")
+
+    def _end_phase(self) -> None:
+        self._write_event("phase_finished")
+
+
+def create_progress_reporter(
+    progress: bool = False, stream: TextIO | None = None
+) -> ProgressReporter | None:
+    """Draw a progress bar on a terminal, or emit JSON events when requested."""
+    stream = stream or sys.stderr
+    if stream.isatty():
+        return TerminalProgressReporter(stream)
+    if progress:
+        return JsonProgressReporter(stream)
+    return None
+
+
+__all__ = [
+    "ProgressReporter",
+    "TerminalProgressReporter",
+    "JsonProgressReporter",
+    "create_progress_reporter",
+]
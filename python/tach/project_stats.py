@@ -0,0 +1,193 @@
+from __future__ import annotations
+
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach.colors import BCOLORS
+from tach.extension import get_dependency_graph, set_excluded_paths
+from tach.filesystem import walk_pyfiles
+from tach.report import get_external_dependencies
+from tach.utils.virtualenv import get_project_site_packages
+
+if TYPE_CHECKING:
+    from tach.extension import InterfaceConfig, ModuleConfig, ProjectConfig
+
+NO_LAYER = "(no layer)"
+UNRESTRICTED_LAYER = "(unrestricted)"
+
+# Matches the directives which 'tach check' reads, e.g. '# tach-ignore(reason) module'
+IGNORE_DIRECTIVE_PATTERN = re.compile(r"# *tach-ignore\b")
+
+
+@dataclass
+class ProjectStats:
+    modules: int = 0
+    # Dependencies between modules found in the code, rather than declared
+    edges: int = 0
+    # The number of modules in each layer, in the order of the configured layers
+    layers: dict[str, int] = field(default_factory=dict)
+    modules_with_interfaces: int = 0
+    ignore_directives: int = 0
+    external_dependencies: int = 0
+
+    @property
+    def interface_coverage(self) -> float:
+        # The percentage of modules which have an interface
+        if not self.modules:
+            return 0.0
+        return 100 * self.modules_with_interfaces / self.modules
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "modules": self.modules,
+            "edges": self.edges,
+            "layers": self.layers,
+            "interface_coverage": round(self.interface_coverage, 1),
+            "modules_with_interfaces": self.modules_with_interfaces,
+            "ignore_directives": self.ignore_directives,
+            "external_dependencies": self.external_dependencies,
+        }
+
+
+def count_ignore_directives(source: str) -> int:
+    return sum(
+        1 for line in source.splitlines() if IGNORE_DIRECTIVE_PATTERN.search(line)
+    )
+
+
+def layer_distribution(
+    modules: list[ModuleConfig], layers: list[str]
+) -> dict[str, int]:
+    """The number of modules in each layer, including layers without modules.
+
+    Modules without a layer are counted under NO_LAYER, and modules which may be used
+    from any layer are counted under UNRESTRICTED_LAYER, if there are any.
+    Projects which don't use layers have no distribution.
+    """
+    if not layers and not any(module.layer for module in modules):
+        return {}
+    distribution = {layer: 0 for layer in layers}
+    for module in modules:
+        if module.unrestricted_layer:
+            layer = UNRESTRICTED_LAYER
+        else:
+            layer = module.layer or NO_LAYER
+        distribution[layer] = distribution.get(layer, 0) + 1
+    return distribution
+
+
+def count_modules_with_interfaces(
+    module_paths: list[str], interfaces: list[InterfaceConfig]
+) -> int:
+    patterns = [
+        re.compile(f"^{pattern}$")
+        for interface in interfaces
+        for pattern in interface.from_modules
+    ]
+    return sum(
+        1
+        for module_path in module_paths
+        if any(pattern.match(module_path) for pattern in patterns)
+    )
+
+
+def compute_project_stats(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> ProjectStats:
+    modules = [
+        module for module in project_config.all_modules() if module.path != "<root>"
+    ]
+    module_paths = [module.path for module in modules]
+    graph = get_dependency_graph(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+    )
+    stats = ProjectStats(
+        modules=len(modules),
+        edges=sum(1 for edge in graph.edges if edge.source != edge.target),
+        layers=layer_distribution(modules, project_config.layers),
+        modules_with_interfaces=count_modules_with_interfaces(
+            module_paths, list(project_config.all_interfaces())
+        ),
+    )
+
+    with set_excluded_paths(
+        project_root=str(project_root),
+        exclude_paths=exclude_paths,
+        use_regex_matching=project_config.use_regex_matching,
+        include_paths=project_config.include,
+    ):
+        source_roots = [
+            (project_root / source_root).resolve()
+            for source_root in project_config.source_roots
+        ]
+        site_packages = get_project_site_packages(project_root, project_config)
+        source_root_paths = [str(source_root) for source_root in source_roots]
+        excluded_modules = set(project_config.external.exclude)
+        ignore_type_checking_imports = project_config.ignore_type_checking_imports
+        external_packages: set[str] = set()
+        for source_root in source_roots:
+            for pyfile in walk_pyfiles(
+                source_root,
+                project_root=project_root,
+                exclude_paths=exclude_paths,
+                use_regex_matching=project_config.use_regex_matching,
+            ):
+                file_path = source_root / pyfile
+                try:
+                    stats.ignore_directives += count_ignore_directives(
+                        file_path.read_text()
+                    )
+                except (UnicodeDecodeError, OSError):
+                    continue
+                external_packages.update(
+                    dependency.package_name
+                    for dependency in get_external_dependencies(
+                        source_roots=source_root_paths,
+                        file_path=str(file_path),
+                        ignore_type_checking_imports=ignore_type_checking_imports,
+                        excluded_modules=excluded_modules,
+                        site_packages=site_packages,
+                    )
+                )
+    stats.external_dependencies = len(external_packages)
+    return stats
+
+
+def render_project_stats(stats: ProjectStats) -> str:
+    rows = [
+        ("Modules", str(stats.modules)),
+        ("Dependencies", str(stats.edges)),
+        (
+            "Interface coverage",
+            f"{stats.interface_coverage:.1f}% "
+            f"({stats.modules_with_interfaces}/{stats.modules} modules)",
+        ),
+        ("Ignore directives", str(stats.ignore_directives)),
+        ("External dependencies", str(stats.external_dependencies)),
+    ]
+    width = max(len(label) for label, _ in rows)
+    lines = [f"{label:<{width}}  {value}" for label, value in rows]
+    if stats.layers:
+        lines.append("")
+        lines.append(f"{BCOLORS.HEADER}{BCOLORS.BOLD}Modules by layer{BCOLORS.ENDC}")
+        layer_width = max(len(str(count)) for count in stats.layers.values())
+        lines.extend(
+            f"{count:>{layer_width}}  {layer}" for layer, count in stats.layers.items()
+        )
+    return "\n".join(lines)
+
+
+__all__ = [
+    "NO_LAYER",
+    "UNRESTRICTED_LAYER",
+    "ProjectStats",
+    "compute_project_stats",
+    "count_ignore_directives",
+    "count_modules_with_interfaces",
+    "layer_distribution",
+    "render_project_stats",
+]
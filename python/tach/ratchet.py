@@ -0,0 +1,133 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach.errors import TachError
+
+if TYPE_CHECKING:
+    from tach.extension import ExitConfig, ProjectConfig
+
+
+@dataclass
+class BudgetReport:
+    # The allowed number of failing violations for each diagnostic code
+    budgets: dict[str, int]
+    # The actual number of failing violations for each diagnostic code
+    counts: dict[str, int]
+
+    @property
+    def exceeded(self) -> list[tuple[str, int, int]]:
+        """The (code, count, budget) of each code with more violations than allowed.
+
+        Codes without a budget do not allow any violations.
+        """
+        return [
+            (code, count, self.budgets.get(code, 0))
+            for code, count in sorted(self.counts.items())
+            if count > self.budgets.get(code, 0)
+        ]
+
+    @property
+    def tightened(self) -> dict[str, int]:
+        """The lowered budget of each code which now has fewer violations."""
+        return {
+            code: self.counts.get(code, 0)
+            for code, budget in sorted(self.budgets.items())
+            if self.counts.get(code, 0) < budget
+        }
+
+
+def ratchet_enabled(exit_config: ExitConfig) -> bool:
+    return bool(exit_config.budgets) or exit_config.budget_file is not None
+
+
+def budget_file_path(project_root: Path, exit_config: ExitConfig) -> Path | None:
+    if exit_config.budget_file is None:
+        return None
+    return project_root / exit_config.budget_file
+
+
+def read_budget_file(path: Path) -> dict[str, int]:
+    try:
+        data = json.loads(path.read_text())
+    except json.JSONDecodeError as e:
+        raise TachError(f"Budget file '{path}' is not valid JSON: {e}")
+    if not isinstance(data, dict) or not all(
+        isinstance(budget, int) and budget >= 0 for budget in data.values()
+    ):
+        raise TachError(
+            f"Budget file '{path}' must map diagnostic codes to non-negative integers."
+        )
+    return data
+
+
+def write_budget_file(path: Path, budgets: dict[str, int]) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(budgets, indent=2, sort_keys=True) + "\n")
+
+
+def load_budgets(project_root: Path, exit_config: ExitConfig) -> dict[str, int] | None:
+    """The recorded violation budgets, or None if none have been recorded yet.
+
+    A budget file takes precedence over the budgets in the project config,
+    which seed the budget file when it does not exist.
+    """
+    path = budget_file_path(project_root, exit_config)
+    if path is not None and path.exists():
+        return read_budget_file(path)
+    if exit_config.budgets:
+        return dict(exit_config.budgets)
+    return None
+
+
+def save_budgets(
+    project_root: Path, project_config: ProjectConfig, budgets: dict[str, int]
+) -> None:
+    path = budget_file_path(project_root, project_config.exit)
+    if path is not None:
+        write_budget_file(path, budgets)
+        return
+    try:
+        for code, budget in budgets.items():
+            if project_config.exit.budgets.get(code) != budget:
+                project_config.set_violation_budget(code, budget)
+        project_config.save_edits()
+    except ValueError as e:
+        raise TachError(f"Failed to update the violation budgets in config: {e}")
+
+
+def check_budgets(
+    project_root: Path,
+    project_config: ProjectConfig,
+    counts: dict[str, int],
+    tighten: bool = True,
+) -> BudgetReport:
+    """Compare violation counts against their budgets, lowering any budget
+    whose count has dropped so that violations can never return.
+
+    Without any recorded budgets, the current counts become the budgets.
+    """
+    budgets = load_budgets(project_root, project_config.exit)
+    if budgets is None:
+        budgets = dict(counts)
+    report = BudgetReport(budgets=budgets, counts=counts)
+    path = budget_file_path(project_root, project_config.exit)
+    missing_file = path is not None and not path.exists()
+    if tighten and (report.tightened or missing_file):
+        save_budgets(project_root, project_config, {**budgets, **report.tightened})
+    return report
+
+
+__all__ = [
+    "BudgetReport",
+    "budget_file_path",
+    "check_budgets",
+    "load_budgets",
+    "ratchet_enabled",
+    "read_budget_file",
+    "save_budgets",
+    "write_budget_file",
+]
@@ -19,9 +19,10 @@ from tach.utils.external import (
     is_stdlib_module,
     normalize_package_name,
 )
+from tach.utils.virtualenv import get_project_site_packages
 
 if TYPE_CHECKING:
-    from tach.extension import ProjectConfig
+    from tach.extension import GraphArtifact, ProjectConfig
 
 
 def report(
@@ -34,6 +35,7 @@ def report(
     skip_usages: bool = False,
     raw: bool = False,
     exclude_paths: list[str] | None = None,
+    graph_artifact: GraphArtifact | None = None,
 ) -> str:
     if not project_root.is_dir():
         raise errors.TachSetupError(
@@ -43,30 +45,46 @@ def report(
     if not path.exists():
         raise errors.TachError(f"The path '{path}' does not exist.")
 
+    if graph_artifact is not None:
+        # The artifact already holds every resolved dependency, so nothing is read
+        try:
+            return graph_artifact.create_dependency_report(
+                project_root=project_root,
+                project_config=project_config,
+                path=path.resolve().relative_to(project_root),
+                include_dependency_modules=include_dependency_modules,
+                include_usage_modules=include_usage_modules,
+                skip_dependencies=skip_dependencies,
+                skip_usages=skip_usages,
+                raw=raw,
+            )
+        except ValueError as e:
+            raise errors.TachError(str(e))
+
     # This informs the Rust extension ahead-of-time which paths are excluded.
-    set_excluded_paths(
+    with set_excluded_paths(
         project_root=str(project_root),
         exclude_paths=exclude_paths or [],
         use_regex_matching=project_config.use_regex_matching,
-    )
-
-    # We prefer resolving symlinks and relative paths in Python
-    # because Rust's canonicalize adds an 'extended length path' prefix on Windows
-    # which breaks downstream code that compares to Python-resolved paths
-    path = path.resolve().relative_to(project_root)
-    try:
-        return create_dependency_report(
-            project_root=str(project_root),
-            project_config=project_config,
-            path=str(path),
-            include_dependency_modules=include_dependency_modules,
-            include_usage_modules=include_usage_modules,
-            skip_dependencies=skip_dependencies,
-            skip_usages=skip_usages,
-            raw=raw,
-        )
-    except ValueError as e:
-        raise errors.TachError(str(e))
+        include_paths=project_config.include,
+    ):
+        # We prefer resolving symlinks and relative paths in Python
+        # because Rust's canonicalize adds an 'extended length path' prefix on Windows
+        # which breaks downstream code that compares to Python-resolved paths
+        path = path.resolve().relative_to(project_root)
+        try:
+            return create_dependency_report(
+                project_root=str(project_root),
+                project_config=project_config,
+                path=str(path),
+                include_dependency_modules=include_dependency_modules,
+                include_usage_modules=include_usage_modules,
+                skip_dependencies=skip_dependencies,
+                skip_usages=skip_usages,
+                raw=raw,
+            )
+        except ValueError as e:
+            raise errors.TachError(str(e))
 
 
 @dataclass
@@ -91,6 +109,17 @@ def render_external_dependency(
     )
 
 
+def _external_dependency_sort_key(
+    dependency: ExternalDependency,
+) -> tuple[str, int, str]:
+    # Directory listings are not ordered consistently across platforms
+    return (
+        dependency.absolute_file_path.as_posix(),
+        dependency.import_line_number,
+        dependency.import_module_path,
+    )
+
+
 def render_external_dependency_report(
     path: Path, dependencies: list[ExternalDependency], raw: bool = False
 ) -> str:
@@ -112,7 +141,7 @@ def render_external_dependency_report(
         lines.append(f"{BCOLORS.OKGREEN}No external dependencies found.{BCOLORS.ENDC}")
         return "\n".join(lines)
 
-    for dependency in dependencies:
+    for dependency in sorted(dependencies, key=_external_dependency_sort_key):
         lines.append(
             render_external_dependency(
                 dependency=dependency,
@@ -128,6 +157,7 @@ def get_external_dependencies(
     file_path: str,
     ignore_type_checking_imports: bool,
     excluded_modules: set[str] | None = None,
+    site_packages: tuple[str, ...] = (),
 ) -> list[ExternalDependency]:
     external_imports = get_external_imports(
         source_roots=source_roots,
@@ -138,7 +168,9 @@ def get_external_dependencies(
     excluded_modules = excluded_modules or set()
     external_dependencies: list[ExternalDependency] = []
     for external_import in external_imports:
-        external_package = get_package_name(external_import.module_path)
+        external_package = get_package_name(
+            external_import.module_path, site_packages
+        )
         if external_package in excluded_modules:
             continue
 
@@ -150,7 +182,9 @@ def get_external_dependencies(
                 absolute_file_path=Path(file_path),
                 import_module_path=external_import.module_path,
                 import_line_number=external_import.line_number,
-                package_name=normalize_package_name(external_import.module_path),
+                package_name=normalize_package_name(
+                    external_import.module_path, site_packages
+                ),
             )
         )
     return external_dependencies
@@ -162,6 +196,7 @@ def external_dependency_report(
     project_config: ProjectConfig,
     raw: bool = False,
     exclude_paths: list[str] | None = None,
+    graph_artifact: GraphArtifact | None = None,
 ) -> str:
     if not project_root.is_dir():
         raise errors.TachSetupError(
@@ -171,6 +206,22 @@ def external_dependency_report(
     if not path.exists():
         raise errors.TachError(f"The path '{path}' does not exist.")
 
+    if graph_artifact is not None:
+        # The artifact already holds every resolved dependency, so nothing is read
+        try:
+            return graph_artifact.create_dependency_report(
+                project_root=project_root,
+                project_config=project_config,
+                path=path.resolve().relative_to(project_root),
+                include_dependency_modules=include_dependency_modules,
+                include_usage_modules=include_usage_modules,
+                skip_dependencies=skip_dependencies,
+                skip_usages=skip_usages,
+                raw=raw,
+            )
+        except ValueError as e:
+            raise errors.TachError(str(e))
+
     if exclude_paths and is_path_excluded(
         exclude_paths,
         path,
@@ -179,41 +230,50 @@ def external_dependency_report(
         raise errors.TachError(f"The path '{path}' is excluded.")
 
     # This informs the Rust extension ahead-of-time which paths are excluded.
-    set_excluded_paths(
+    with set_excluded_paths(
         project_root=str(project_root),
         exclude_paths=exclude_paths or [],
         use_regex_matching=project_config.use_regex_matching,
-    )
-    source_roots = [
-        str(project_root / source_root) for source_root in project_config.source_roots
-    ]
-
-    if path.is_file():
-        external_dependencies = get_external_dependencies(
-            source_roots=source_roots,
-            file_path=str(path.resolve()),
-            excluded_modules=set(project_config.external.exclude),
-            ignore_type_checking_imports=project_config.ignore_type_checking_imports,
-        )
-        return render_external_dependency_report(path, external_dependencies, raw=raw)
-
-    all_external_dependencies: list[ExternalDependency] = []
-    for pyfile in walk_pyfiles(
-        path,
-        project_root=project_root,
-        exclude_paths=exclude_paths,
-        use_regex_matching=project_config.use_regex_matching,
+        include_paths=project_config.include,
     ):
-        all_external_dependencies.extend(
-            get_external_dependencies(
+        source_roots = [
+            str(project_root / source_root)
+            for source_root in project_config.source_roots
+        ]
+        site_packages = get_project_site_packages(project_root, project_config)
+
+        if path.is_file():
+            external_dependencies = get_external_dependencies(
                 source_roots=source_roots,
-                file_path=str(path.resolve() / pyfile),
+                file_path=str(path.resolve()),
                 excluded_modules=set(project_config.external.exclude),
                 ignore_type_checking_imports=project_config.ignore_type_checking_imports,
+                site_packages=site_packages,
+            )
+            return render_external_dependency_report(
+                path, external_dependencies, raw=raw
             )
-        )
 
-    return render_external_dependency_report(path, all_external_dependencies, raw=raw)
+        all_external_dependencies: list[ExternalDependency] = []
+        for pyfile in walk_pyfiles(
+            path,
+            project_root=project_root,
+            exclude_paths=exclude_paths,
+            use_regex_matching=project_config.use_regex_matching,
+        ):
+            all_external_dependencies.extend(
+                get_external_dependencies(
+                    source_roots=source_roots,
+                    file_path=str(path.resolve() / pyfile),
+                    excluded_modules=set(project_config.external.exclude),
+                    ignore_type_checking_imports=project_config.ignore_type_checking_imports,
+                    site_packages=site_packages,
+                )
+            )
+
+        return render_external_dependency_report(
+            path, all_external_dependencies, raw=raw
+        )
 
 
 __all__ = ["report", "external_dependency_report"]
@@ -11,9 +11,8 @@ from tach.extension import serialize_modules_json
 if TYPE_CHECKING:
     from pathlib import Path
 
-    import pydot  # type: ignore
-
-    from tach.extension import ProjectConfig
+    from tach.extension import DependencyGraph, ProjectConfig
+    from tach.graph_pruning import GraphPruning
 
 
 def generate_show_url(
@@ -45,13 +44,80 @@ def generate_show_url(
         return None
 
 
+def domain_for_module(domain_paths: list[str], module_path: str) -> str | None:
+    # The innermost domain containing the module, if any
+    containing = [
+        domain
+        for domain in domain_paths
+        if module_path == domain or module_path.startswith(f"{domain}.")
+    ]
+    return max(containing, key=len, default=None)
+
+
+def collapse_domain_edges(
+    edges: list[tuple[str, str]], domain_paths: list[str]
+) -> dict[tuple[str, str], int]:
+    # Replace each module with its domain, counting the module edges between each pair.
+    # Modules outside of any domain are kept as-is, and edges within a domain are dropped.
+    weights: dict[tuple[str, str], int] = {}
+    for source, target in edges:
+        source = domain_for_module(domain_paths, source) or source
+        target = domain_for_module(domain_paths, target) or target
+        if source != target:
+            weights[(source, target)] = weights.get((source, target), 0) + 1
+    return weights
+
+
+def _collapsed_paths(
+    project_config: ProjectConfig, collapse_domains: bool, collapse_packages: bool
+) -> list[str] | None:
+    # The path prefixes which are each shown as a single node, if any
+    if collapse_packages:
+        return project_config.package_paths()
+    if collapse_domains:
+        return project_config.domain_paths()
+    return None
+
+
+def _module_edges(
+    project_config: ProjectConfig,
+    included_paths: list[Path],
+    pruning: GraphPruning | None = None,
+    dependency_graph: DependencyGraph | None = None,
+) -> tuple[list[str], list[tuple[str, str]]]:
+    modules = project_config.filtered_modules(included_paths)
+    module_paths = [module.path for module in modules]
+    if dependency_graph is None:
+        edges = [
+            (module.path, dependency.path)
+            for module in modules
+            for dependency in module.depends_on or []
+        ]
+    else:
+        # The dependencies found in the project, rather than those in the config
+        included = set(module_paths)
+        edges = [
+            (edge.source, edge.target)
+            for edge in dependency_graph.edges
+            if edge.source in included and edge.source != edge.target
+        ]
+    if pruning is None or pruning.is_empty():
+        return module_paths, edges
+    return pruning.prune_edges(module_paths, edges, project_config.utility_paths())
+
+
 def generate_module_graph_dot_file(
     project_config: ProjectConfig,
     output_filepath: Path,
     included_paths: list[Path],
+    collapse_domains: bool = False,
+    collapse_packages: bool = False,
+    pruning: GraphPruning | None = None,
+    dependency_graph: DependencyGraph | None = None,
 ) -> None:
     # Local import because networkx takes about ~100ms to load
     import networkx as nx
+    import pydot  # type: ignore
 
     graph = nx.DiGraph()  # type: ignore
 
@@ -62,41 +128,147 @@ def generate_module_graph_dot_file(
             graph.add_node(dependency)  # type: ignore
         graph.add_edge(module, dependency)  # type: ignore
 
-    modules = project_config.filtered_modules(included_paths)
+    domain_paths = project_config.domain_paths()
+    collapsed_paths = _collapsed_paths(
+        project_config, collapse_domains, collapse_packages
+    )
+    _, edges = _module_edges(
+        project_config, included_paths, pruning, dependency_graph
+    )
 
-    for module in modules:
-        for dependency in module.depends_on or []:
-            upsert_edge(graph, module.path, dependency.path)  # type: ignore
+    if collapsed_paths is not None:
+        for (source, target), weight in collapse_domain_edges(
+            edges, collapsed_paths
+        ).items():
+            upsert_edge(graph, source, target)  # type: ignore
+            graph.edges[source, target]["label"] = str(weight)  # type: ignore
+    else:
+        for module, dependency in edges:
+            upsert_edge(graph, module, dependency)  # type: ignore
 
     pydot_graph: pydot.Dot = nx.nx_pydot.to_pydot(graph)  # type: ignore
+
+    if collapsed_paths is None:
+        # Nest each module in the cluster of its domain, and each domain in its parent
+        clusters: dict[str, pydot.Cluster] = {}  # type: ignore
+        for domain in sorted(domain_paths, key=len):
+            cluster = pydot.Cluster(domain.replace(".", "_"), label=domain)  # type: ignore
+            parent = domain_for_module(list(clusters), domain)
+            (clusters[parent] if parent else pydot_graph).add_subgraph(cluster)  # type: ignore
+            clusters[domain] = cluster
+        for node in graph.nodes:  # type: ignore
+            domain = domain_for_module(domain_paths, node)  # type: ignore
+            if domain is not None:
+                clusters[domain].add_node(pydot.Node(node))  # type: ignore
+
     dot_data: str = pydot_graph.to_string()  # type: ignore
 
     output_filepath.write_text(dot_data)  # type: ignore
 
 
-def generate_module_graph_mermaid(
+def _mermaid_id(path: str) -> str:
+    return path.strip("<>")
+
+
+def render_module_graph_mermaid(
     project_config: ProjectConfig,
-    output_filepath: Path,
     included_paths: list[Path],
-) -> None:
-    modules = project_config.filtered_modules(included_paths)
+    collapse_domains: bool = False,
+    collapse_packages: bool = False,
+    pruning: GraphPruning | None = None,
+    dependency_graph: DependencyGraph | None = None,
+) -> str:
+    domain_paths = project_config.domain_paths()
+    collapsed_paths = _collapsed_paths(
+        project_config, collapse_domains, collapse_packages
+    )
+    module_paths, module_edges = _module_edges(
+        project_config, included_paths, pruning, dependency_graph
+    )
+
     edges: list[str] = []
-    isolated: list[str] = []
-    for module in modules:
-        for dependency in module.depends_on or []:
+    if collapsed_paths is not None:
+        weights = collapse_domain_edges(module_edges, collapsed_paths)
+        for (source, target), weight in weights.items():
             edges.append(
-                f"    {module.path.strip('<>')} --> {dependency.path.strip('<>')}"
+                f"    {_mermaid_id(source)} -->|{weight}| {_mermaid_id(target)}"
             )
-        if not module.depends_on:
-            isolated.append(f"    {module.path.strip('<>')}")
+        nodes = {
+            domain_for_module(collapsed_paths, module_path) or module_path
+            for module_path in module_paths
+        }
+        connected = {node for edge in weights for node in edge}
+        isolated = [f"    {_mermaid_id(node)}" for node in sorted(nodes - connected)]
+        subgraphs: list[str] = []
+    else:
+        for module, dependency in module_edges:
+            edges.append(f"    {_mermaid_id(module)} --> {_mermaid_id(dependency)}")
+        sources = {module for module, _ in module_edges}
+        isolated = [
+            f"    {_mermaid_id(module_path)}"
+            for module_path in module_paths
+            if module_path not in sources
+        ]
+        subgraphs = _mermaid_domain_subgraphs(
+            domain_paths, module_paths + [target for _, target in module_edges]
+        )
+
+    return "graph TD\n" + "\n".join(edges) + "\n" + "\n".join(isolated + subgraphs)
+
+
+def generate_module_graph_mermaid(
+    project_config: ProjectConfig,
+    output_filepath: Path,
+    included_paths: list[Path],
+    collapse_domains: bool = False,
+    collapse_packages: bool = False,
+    pruning: GraphPruning | None = None,
+    dependency_graph: DependencyGraph | None = None,
+) -> None:
+    output_filepath.write_text(
+        render_module_graph_mermaid(
+            project_config,
+            included_paths,
+            collapse_domains=collapse_domains,
+            collapse_packages=collapse_packages,
+            pruning=pruning,
+            dependency_graph=dependency_graph,
+        )
+    )
+
+
+def _mermaid_domain_subgraphs(
+    domain_paths: list[str], module_paths: list[str]
+) -> list[str]:
+    # Group modules (and nested domains) under the innermost domain which contains them
+    children: dict[str | None, list[str]] = {}
+    for domain in sorted(domain_paths):
+        parent = domain_for_module([d for d in domain_paths if d != domain], domain)
+        children.setdefault(parent, []).append(domain)
+    members: dict[str, list[str]] = {}
+    for module_path in sorted(set(module_paths)):
+        domain = domain_for_module(domain_paths, module_path)
+        if domain is not None:
+            members.setdefault(domain, []).append(module_path)
 
-    mermaid_graph = "graph TD\n" + "\n".join(edges) + "\n" + "\n".join(isolated)
+    def render(domain: str, depth: int) -> list[str]:
+        indent = "    " * depth
+        lines = [f"{indent}subgraph {_mermaid_id(domain)}_domain [{domain}]"]
+        for module_path in members.get(domain, []):
+            lines.append(f"{indent}    {_mermaid_id(module_path)}")
+        for child in children.get(domain, []):
+            lines.extend(render(child, depth + 1))
+        lines.append(f"{indent}end")
+        return lines
 
-    output_filepath.write_text(mermaid_graph)
+    return [line for domain in children.get(None, []) for line in render(domain, 1)]
 
 
 __all__ = [
+    "collapse_domain_edges",
+    "domain_for_module",
     "generate_show_url",
     "generate_module_graph_dot_file",
     "generate_module_graph_mermaid",
+    "render_module_graph_mermaid",
 ]
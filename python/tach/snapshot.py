@@ -0,0 +1,242 @@
+from __future__ import annotations
+
+import json
+from collections import Counter
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import __version__
+from tach.errors import TachError
+from tach.extension import check, get_dependency_graph
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ProjectConfig
+
+SNAPSHOT_VERSION = 1
+DEFAULT_SNAPSHOT_PATH = Path("tach-snapshot.json")
+
+
+@dataclass
+class SnapshotEdge:
+    source: str
+    target: str
+    # The number of imports along this edge
+    imports: int = 0
+    declared: bool = True
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "source": self.source,
+            "target": self.target,
+            "imports": self.imports,
+            "declared": self.declared,
+        }
+
+
+@dataclass
+class ArchitectureSnapshot:
+    modules: list[str] = field(default_factory=list)
+    edges: list[SnapshotEdge] = field(default_factory=list)
+    # The number of diagnostics for each diagnostic code
+    violations: dict[str, int] = field(default_factory=dict)
+    tach_version: str = __version__
+
+    @property
+    def violation_count(self) -> int:
+        return sum(self.violations.values())
+
+    def edge_keys(self) -> dict[tuple[str, str], SnapshotEdge]:
+        return {(edge.source, edge.target): edge for edge in self.edges}
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "version": SNAPSHOT_VERSION,
+            "tach_version": self.tach_version,
+            "modules": self.modules,
+            "edges": [edge.to_dict() for edge in self.edges],
+            "violations": self.violations,
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> ArchitectureSnapshot:
+        version = data.get("version")
+        if version != SNAPSHOT_VERSION:
+            raise TachError(
+                f"Unsupported snapshot version '{version}' "
+                f"(expected {SNAPSHOT_VERSION})."
+            )
+        return cls(
+            modules=list(data.get("modules", [])),
+            edges=[SnapshotEdge(**edge) for edge in data.get("edges", [])],
+            violations=dict(data.get("violations", {})),
+            tach_version=data.get("tach_version", ""),
+        )
+
+
+@dataclass
+class SnapshotDiff:
+    added_modules: list[str] = field(default_factory=list)
+    removed_modules: list[str] = field(default_factory=list)
+    added_edges: list[SnapshotEdge] = field(default_factory=list)
+    removed_edges: list[SnapshotEdge] = field(default_factory=list)
+    # Changes in the number of violations for each code, omitting unchanged codes
+    violation_changes: dict[str, int] = field(default_factory=dict)
+
+    @property
+    def drift_score(self) -> int:
+        """One point per structural change, two for each new undeclared edge,
+        and one per new violation. Fixed violations do not lower the score."""
+        return (
+            len(self.added_modules)
+            + len(self.removed_modules)
+            + sum(1 if edge.declared else 2 for edge in self.added_edges)
+            + len(self.removed_edges)
+            + sum(change for change in self.violation_changes.values() if change > 0)
+        )
+
+    def has_changes(self) -> bool:
+        return bool(
+            self.added_modules
+            or self.removed_modules
+            or self.added_edges
+            or self.removed_edges
+            or self.violation_changes
+        )
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "drift_score": self.drift_score,
+            "added_modules": self.added_modules,
+            "removed_modules": self.removed_modules,
+            "added_edges": [edge.to_dict() for edge in self.added_edges],
+            "removed_edges": [edge.to_dict() for edge in self.removed_edges],
+            "violation_changes": self.violation_changes,
+        }
+
+
+def build_snapshot(
+    diagnostics: list[Diagnostic], graph_edges: list[Any], modules: list[str]
+) -> ArchitectureSnapshot:
+    edges = sorted(
+        (
+            SnapshotEdge(
+                source=edge.source,
+                target=edge.target,
+                imports=edge.weight,
+                declared=edge.declared,
+            )
+            for edge in graph_edges
+        ),
+        key=lambda edge: (edge.source, edge.target),
+    )
+    violations = Counter(diagnostic.code() for diagnostic in diagnostics)
+    return ArchitectureSnapshot(
+        modules=sorted(modules),
+        edges=edges,
+        violations=dict(sorted(violations.items())),
+    )
+
+
+def take_snapshot(
+    project_root: Path, project_config: ProjectConfig, exclude_paths: list[str]
+) -> ArchitectureSnapshot:
+    diagnostics = check(
+        project_root=project_root,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=exclude_paths,
+    )
+    graph = get_dependency_graph(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+    )
+    return build_snapshot(diagnostics, graph.edges, [node.path for node in graph.nodes])
+
+
+def write_snapshot(snapshot: ArchitectureSnapshot, path: Path) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(snapshot.to_dict(), indent=2) + "\n")
+
+
+def read_snapshot(path: Path) -> ArchitectureSnapshot:
+    try:
+        data = json.loads(path.read_text())
+    except FileNotFoundError:
+        raise TachError(f"Snapshot file '{path}' does not exist.")
+    except json.JSONDecodeError as e:
+        raise TachError(f"Snapshot file '{path}' is not valid JSON: {e}")
+    return ArchitectureSnapshot.from_dict(data)
+
+
+def compare_snapshots(
+    baseline: ArchitectureSnapshot, current: ArchitectureSnapshot
+) -> SnapshotDiff:
+    baseline_modules = set(baseline.modules)
+    current_modules = set(current.modules)
+    baseline_edges = baseline.edge_keys()
+    current_edges = current.edge_keys()
+    violation_changes = {
+        code: current.violations.get(code, 0) - baseline.violations.get(code, 0)
+        for code in sorted(set(baseline.violations) | set(current.violations))
+    }
+    return SnapshotDiff(
+        added_modules=sorted(current_modules - baseline_modules),
+        removed_modules=sorted(baseline_modules - current_modules),
+        added_edges=[
+            edge
+            for key, edge in sorted(current_edges.items())
+            if key not in baseline_edges
+        ],
+        removed_edges=[
+            edge
+            for key, edge in sorted(baseline_edges.items())
+            if key not in current_edges
+        ],
+        violation_changes={
+            code: change for code, change in violation_changes.items() if change
+        },
+    )
+
+
+def _format_edge(edge: SnapshotEdge) -> str:
+    declared = "" if edge.declared else ", undeclared"
+    return f"'{edge.source}' -> '{edge.target}' ({edge.imports} imports{declared})"
+
+
+def render_snapshot_diff(diff: SnapshotDiff) -> str:
+    lines = [f"Drift score: {diff.drift_score}"]
+    if not diff.has_changes():
+        lines.append("No structural changes since the snapshot.")
+        return "\n".join(lines)
+    sections: list[tuple[str, list[str]]] = [
+        ("Added modules", [f"'{module}'" for module in diff.added_modules]),
+        ("Removed modules", [f"'{module}'" for module in diff.removed_modules]),
+        ("Added dependencies", [_format_edge(edge) for edge in diff.added_edges]),
+        ("Removed dependencies", [_format_edge(edge) for edge in diff.removed_edges]),
+        (
+            "Violation changes",
+            [f"{code}: {change:+d}" for code, change in diff.violation_changes.items()],
+        ),
+    ]
+    for title, items in sections:
+        if items:
+            lines.append(f"\n{title}:")
+            lines.extend(f"  {item}" for item in items)
+    return "\n".join(lines)
+
+
+__all__ = [
+    "DEFAULT_SNAPSHOT_PATH",
+    "SnapshotEdge",
+    "ArchitectureSnapshot",
+    "SnapshotDiff",
+    "build_snapshot",
+    "take_snapshot",
+    "write_snapshot",
+    "read_snapshot",
+    "compare_snapshots",
+    "render_snapshot_diff",
+]
@@ -0,0 +1,68 @@
+from __future__ import annotations
+
+from collections import Counter
+from dataclasses import dataclass, field
+from typing import TYPE_CHECKING, Any
+
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic
+
+NO_MODULE = "(no module)"
+
+
+@dataclass
+class DiagnosticStatistics:
+    # Counts are sorted from most to least frequent, then by name
+    by_code: list[tuple[str, int]] = field(default_factory=list)
+    by_module: list[tuple[str, int]] = field(default_factory=list)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "codes": dict(self.by_code),
+            "modules": dict(self.by_module),
+        }
+
+
+def _sorted_counts(counter: Counter[str]) -> list[tuple[str, int]]:
+    return sorted(counter.items(), key=lambda item: (-item[1], item[0]))
+
+
+def compute_statistics(diagnostics: list[Diagnostic]) -> DiagnosticStatistics:
+    """Count diagnostics per code, and per module in which they occur.
+
+    Diagnostics which are not attributed to a module are counted under NO_MODULE.
+    """
+    by_code: Counter[str] = Counter()
+    by_module: Counter[str] = Counter()
+    for diagnostic in diagnostics:
+        by_code[diagnostic.code()] += 1
+        by_module[diagnostic.usage_module() or NO_MODULE] += 1
+    return DiagnosticStatistics(
+        by_code=_sorted_counts(by_code), by_module=_sorted_counts(by_module)
+    )
+
+
+def format_statistics(statistics: DiagnosticStatistics) -> str:
+    lines: list[str] = []
+    for title, counts in (
+        ("Violations by code", statistics.by_code),
+        ("Violations by module", statistics.by_module),
+    ):
+        if not counts:
+            continue
+        width = len(str(counts[0][1]))
+        if lines:
+            lines.append("")
+        lines.append(f"{BCOLORS.HEADER}{BCOLORS.BOLD}{title}{BCOLORS.ENDC}")
+        lines.extend(f"{count:>{width}}  {name}" for name, count in counts)
+    return "\n".join(lines)
+
+
+__all__ = [
+    "DiagnosticStatistics",
+    "compute_statistics",
+    "format_statistics",
+    "NO_MODULE",
+]
@@ -0,0 +1,155 @@
+from __future__ import annotations
+
+import ast
+import inspect
+import sys
+from pathlib import Path
+from types import ModuleType
+from typing import Any
+
+from tach import extension
+from tach.errors import TachError
+
+STUB_PATH = Path(__file__).parent / "extension.pyi"
+
+
+def _parse_existing_stub(stub_path: Path) -> ast.Module:
+    if not stub_path.exists():
+        return ast.Module(body=[], type_ignores=[])
+    return ast.parse(stub_path.read_text())
+
+
+def _stub_name(node: ast.stmt) -> str | None:
+    if isinstance(node, (ast.FunctionDef, ast.ClassDef)):
+        return node.name
+    if isinstance(node, ast.AnnAssign) and isinstance(node.target, ast.Name):
+        return node.target.id
+    if isinstance(node, ast.Assign) and isinstance(node.targets[0], ast.Name):
+        return node.targets[0].id
+    return None
+
+
+def _format_signature(name: str, obj: Any, is_method: bool = False) -> str:
+    try:
+        signature = inspect.signature(obj)
+        params = [str(param) for param in signature.parameters.values()]
+    except (TypeError, ValueError):
+        params = ["*args: Any", "**kwargs: Any"]
+    if is_method and (not params or params[0] not in ("self", "cls")):
+        params.insert(0, "self")
+    return f"def {name}({', '.join(params)}) -> Any: ..."
+
+
+def _generate_class(cls: type, existing: ast.ClassDef | None) -> str:
+    existing_members: dict[str, ast.stmt] = {}
+    if existing is not None:
+        for node in existing.body:
+            member_name = _stub_name(node)
+            if member_name is not None:
+                existing_members[member_name] = node
+
+    lines = [f"class {cls.__name__}:"]
+    for member_name, member in vars(cls).items():
+        if member_name.startswith("_") and member_name not in (
+            "__new__",
+            "__init__",
+            "__getstate__",
+            "__setstate__",
+            "__str__",
+            "__repr__",
+            "__eq__",
+            "__hash__",
+        ):
+            continue
+        if member_name in existing_members:
+            lines.append(
+                "    " + ast.unparse(existing_members[member_name]).replace("\n", "\n    ")
+            )
+        elif inspect.isdatadescriptor(member):
+            lines.append(f"    {member_name}: Any")
+        elif callable(member) or isinstance(member, (staticmethod, classmethod)):
+            if isinstance(member, staticmethod):
+                lines.append("    @staticmethod")
+                lines.append(
+                    "    " + _format_signature(member_name, member.__func__)  # type: ignore
+                )
+            else:
+                lines.append(
+                    "    " + _format_signature(member_name, member, is_method=True)
+                )
+        else:
+            # Class attributes, such as enum variants
+            lines.append(f"    {member_name}: {cls.__name__}")
+    if len(lines) == 1:
+        lines.append("    ...")
+    return "\n".join(lines)
+
+
+def generate_stubs(
+    module: ModuleType = extension, existing_stub_path: Path = STUB_PATH
+) -> str:
+    """Generate stubs for every public member of the extension module.
+
+    Annotations are carried over from the existing stub where a member is already
+    declared there, so only new members fall back to 'Any'.
+    """
+    if sys.version_info < (3, 9):
+        raise TachError("Generating stubs requires Python 3.9 or later.")
+    existing_stub = _parse_existing_stub(existing_stub_path)
+    existing_nodes: dict[str, ast.stmt] = {}
+    header: list[str] = []
+    aliases: list[str] = []
+    for node in existing_stub.body:
+        if isinstance(node, (ast.Import, ast.ImportFrom)):
+            header.append(ast.unparse(node))
+            continue
+        name = _stub_name(node)
+        if name is None:
+            continue
+        if name not in vars(module) and isinstance(node, (ast.Assign, ast.AnnAssign)):
+            # Type aliases only exist in the stub
+            aliases.append(ast.unparse(node))
+        existing_nodes[name] = node
+
+    if "from typing import Any" not in header:
+        header.append("from typing import Any")
+
+    sections: list[str] = []
+    for name, member in vars(module).items():
+        if name.startswith("_"):
+            continue
+        existing = existing_nodes.get(name)
+        if isinstance(member, type):
+            sections.append(
+                _generate_class(
+                    member, existing if isinstance(existing, ast.ClassDef) else None
+                )
+            )
+        elif existing is not None:
+            sections.append(ast.unparse(existing))
+        elif callable(member):
+            sections.append(_format_signature(name, member))
+        else:
+            sections.append(f"{name}: Any")
+
+    return "\n".join(header) + "\n\n" + "\n".join(aliases + sections) + "\n"
+
+
+def missing_stubs(
+    module: ModuleType = extension, existing_stub_path: Path = STUB_PATH
+) -> list[str]:
+    """Names exported by the extension module which are not declared in its stub.
+
+    The stub may also declare classes which are only returned from the extension,
+    so declarations without a matching export are not considered stale.
+    """
+    existing_stub = _parse_existing_stub(existing_stub_path)
+    stubbed = {_stub_name(node) for node in existing_stub.body}
+    return sorted(
+        name
+        for name in vars(module)
+        if not name.startswith("_") and name not in stubbed
+    )
+
+
+__all__ = ["generate_stubs", "missing_stubs", "STUB_PATH"]
@@ -1,8 +1,11 @@
 from __future__ import annotations
 
-from typing import TYPE_CHECKING
+import json
+from typing import TYPE_CHECKING, Any
 
 from tach import errors
+from tach.colors import BCOLORS
+from tach.extension import plan_sync
 from tach.extension import (
     sync_project as sync_project_ext,
 )
@@ -11,7 +14,7 @@ from tach.filesystem import get_project_config_path
 if TYPE_CHECKING:
     from pathlib import Path
 
-    from tach.extension import ProjectConfig
+    from tach.extension import NewPackagePolicy, ProjectConfig, SyncPlan
 
 
 def sync_project(
@@ -19,19 +22,103 @@ def sync_project(
     project_config: ProjectConfig,
     exclude_paths: list[str],
     add: bool = False,
-) -> None:
+    new_packages: NewPackagePolicy | None = None,
+) -> SyncPlan:
     config_path = get_project_config_path(project_root)
     if config_path is None:
         raise errors.TachError(
             "Unexpected error. Could not find configuration file during 'sync'."
         )
 
-    sync_project_ext(
+    return sync_project_ext(
         project_root=project_root,
         project_config=project_config,
         exclude_paths=exclude_paths,
         add=add,
+        new_packages=new_packages,
     )
 
 
-__all__ = ["sync_project"]
+def check_sync(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    add: bool = False,
+    new_packages: NewPackagePolicy | None = None,
+) -> SyncPlan:
+    """The changes which 'sync_project' would make, without making them."""
+    return plan_sync(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+        add=add,
+        new_packages=new_packages,
+    )
+
+
+def render_unregistered_packages(plan: SyncPlan) -> list[str]:
+    return [
+        f"Package '{package}' does not belong to any module. "
+        "Register it with 'tach mod', or sync with '--new-packages top-level'."
+        for package in plan.unregistered_packages
+    ]
+
+
+def render_sync_plan_text(plan: SyncPlan) -> str:
+    warnings = [
+        f"{BCOLORS.WARNING}{warning}{BCOLORS.ENDC}"
+        for warning in render_unregistered_packages(plan)
+    ]
+    if plan.is_empty():
+        return "\n".join(["Dependencies are in sync.", *warnings])
+
+    lines = ["Running 'tach sync' would make the following changes:"]
+    if plan.create_root_module:
+        lines.append(f"{BCOLORS.OKGREEN}+ module '<root>'{BCOLORS.ENDC}")
+    lines.extend(
+        f"{BCOLORS.OKGREEN}+ module '{module_path}'{BCOLORS.ENDC}"
+        for module_path in plan.new_modules
+    )
+    for module in plan.modules:
+        lines.append(f"{BCOLORS.BOLD}{module.path}{BCOLORS.ENDC}")
+        lines.extend(
+            f"  {BCOLORS.OKGREEN}+ depends on '{dependency}'{BCOLORS.ENDC}"
+            for dependency in module.added
+        )
+        lines.extend(
+            f"  {BCOLORS.FAIL}- depends on '{dependency}'{BCOLORS.ENDC}"
+            for dependency in module.removed
+        )
+    lines.extend(
+        f"{BCOLORS.FAIL}- module '{module_path}'{BCOLORS.ENDC}"
+        for module_path in plan.deleted_modules
+    )
+    return "\n".join([*lines, *warnings])
+
+
+def sync_plan_to_dict(plan: SyncPlan) -> dict[str, Any]:
+    return {
+        "in_sync": plan.is_empty(),
+        "create_root_module": plan.create_root_module,
+        "create_modules": plan.new_modules,
+        "modules": [
+            {"path": module.path, "add": module.added, "remove": module.removed}
+            for module in plan.modules
+        ],
+        "delete_modules": plan.deleted_modules,
+        "unregistered_packages": plan.unregistered_packages,
+    }
+
+
+def render_sync_plan_json(plan: SyncPlan) -> str:
+    return json.dumps(sync_plan_to_dict(plan), indent=2)
+
+
+__all__ = [
+    "sync_project",
+    "check_sync",
+    "render_unregistered_packages",
+    "render_sync_plan_text",
+    "sync_plan_to_dict",
+    "render_sync_plan_json",
+]
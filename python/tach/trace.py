@@ -0,0 +1,107 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.check_external import external_module_mappings
+from tach.colors import BCOLORS
+from tach.extension import trace_file
+from tach.utils.external import get_stdlib_modules
+from tach.utils.virtualenv import get_project_site_packages
+
+if TYPE_CHECKING:
+    from tach.extension import FileTrace, ImportCategory, ProjectConfig
+
+
+def trace(
+    project_root: Path,
+    project_config: ProjectConfig,
+    exclude_paths: list[str],
+    file_path: Path,
+) -> FileTrace:
+    site_packages = get_project_site_packages(project_root, project_config)
+    return trace_file(
+        project_root=project_root,
+        project_config=project_config,
+        exclude_paths=exclude_paths,
+        file_path=file_path,
+        module_mappings=external_module_mappings(project_config, site_packages),
+        stdlib_modules=get_stdlib_modules(),
+    )
+
+
+def category_name(category: ImportCategory) -> str:
+    # Extension enums are displayed as 'ImportCategory.Project'
+    return str(category).rsplit(".", 1)[-1].lower()
+
+
+def render_trace_text(file_trace: FileTrace) -> str:
+    module = file_trace.module or "(none)"
+    lines = [
+        f"{BCOLORS.BOLD}{file_trace.file_path}{BCOLORS.ENDC}",
+        f"  Module path: {file_trace.module_path}",
+        f"  Module: {module}",
+    ]
+    if file_trace.skipped:
+        lines.append(
+            f"{icons.WARNING} {BCOLORS.WARNING}Skipped by 'tach check', "
+            f"because {file_trace.skipped}.{BCOLORS.ENDC}"
+        )
+    if not file_trace.dependencies:
+        lines.append("\nNo imports or references found.")
+        return "\n".join(lines)
+
+    lines.append("")
+    for dependency in file_trace.dependencies:
+        category = category_name(dependency.category)
+        line = (
+            f"{dependency.line_number:>5}  {dependency.module_path}  "
+            f"[{dependency.source}, {category}]"
+        )
+        if dependency.module:
+            line += f" -> {dependency.module}"
+        if dependency.distributions:
+            line += f" ({', '.join(dependency.distributions)})"
+        if dependency.ignored:
+            line = (
+                f"{BCOLORS.WARNING}{line}\n"
+                f"       ignored, because {dependency.ignored}{BCOLORS.ENDC}"
+            )
+        lines.append(line)
+    return "\n".join(lines)
+
+
+def trace_to_dict(file_trace: FileTrace) -> dict[str, Any]:
+    return {
+        "file_path": str(file_trace.file_path),
+        "module_path": file_trace.module_path,
+        "module": file_trace.module,
+        "skipped": file_trace.skipped,
+        "dependencies": [
+            {
+                "line_number": dependency.line_number,
+                "module_path": dependency.module_path,
+                "source": dependency.source,
+                "category": category_name(dependency.category),
+                "module": dependency.module,
+                "distributions": dependency.distributions,
+                "ignored": dependency.ignored,
+            }
+            for dependency in file_trace.dependencies
+        ],
+    }
+
+
+def render_trace_json(file_trace: FileTrace) -> str:
+    return json.dumps(trace_to_dict(file_trace), indent=2)
+
+
+__all__ = [
+    "trace",
+    "category_name",
+    "render_trace_text",
+    "trace_to_dict",
+    "render_trace_json",
+]
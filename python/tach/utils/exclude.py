@@ -8,6 +8,26 @@ if TYPE_CHECKING:
     from pathlib import Path
 
 
+REGEX_PATTERN_PREFIX = "re:"
+GLOB_PATTERN_PREFIX = "glob:"
+
+
+def is_regex_pattern(pattern: str, use_regex_matching: bool) -> bool:
+    # Prefixed patterns ignore 'use_regex_matching', which only applies to the rest
+    if pattern.startswith(REGEX_PATTERN_PREFIX):
+        return True
+    if pattern.startswith(GLOB_PATTERN_PREFIX):
+        return False
+    return use_regex_matching
+
+
+def strip_pattern_prefix(pattern: str) -> str:
+    for prefix in (REGEX_PATTERN_PREFIX, GLOB_PATTERN_PREFIX):
+        if pattern.startswith(prefix):
+            return pattern[len(prefix) :]
+    return pattern
+
+
 # Assumes 'relative_path' is a path relative to the project root
 def is_path_excluded(
     exclude_paths: list[str], relative_path: Path, use_regex_matching: bool
@@ -15,13 +35,16 @@ def is_path_excluded(
     if not exclude_paths:
         return False
 
-    # TODO: deprecate regex excludes
-    path_for_regex = f"{relative_path}/"
+    # Patterns are written with forward slashes on every platform
+    path_for_glob = relative_path.as_posix()
+    path_for_regex = f"{path_for_glob}/"
     return any(
         (
-            re.match(exclude_path, path_for_regex)
-            if use_regex_matching
-            else fnmatch.fnmatch(str(relative_path), exclude_path)
+            re.match(strip_pattern_prefix(exclude_path), path_for_regex)
+            if is_regex_pattern(exclude_path, use_regex_matching)
+            else fnmatch.fnmatchcase(
+                path_for_glob, strip_pattern_prefix(exclude_path).replace("\\", "/")
+            )
         )
         for exclude_path in exclude_paths
     )
@@ -1,9 +1,12 @@
 from __future__ import annotations
 
+import ast
 import re
 import sys
+from dataclasses import dataclass
 from functools import lru_cache
-from typing import Any
+from pathlib import Path
+from typing import Any, Iterable
 
 KNOWN_MODULE_SPECIAL_CASES = {
     "__future__",
@@ -41,8 +44,20 @@ def get_stdlib_modules() -> list[str]:
         return list(sorted(modules))
 
 
+def _get_distributions(site_packages: tuple[str, ...] = ()) -> Iterable[Any]:
+    if sys.version_info >= (3, 8):  # noqa: UP036
+        from importlib.metadata import distributions
+    else:
+        from importlib_metadata import distributions  # type: ignore
+
+    if site_packages:
+        return distributions(path=list(site_packages))
+    return distributions()
+
+
 def _get_installed_modules(dist: Any) -> list[str]:
-    # This method is best-effort, and is only used for Python < 3.10
+    # This method is best-effort, and is only used when 'packages_distributions'
+    # is unavailable (Python < 3.10) or does not apply (another virtualenv)
     module_names: set[str] = set()
 
     # Method 1: Check top_level.txt
@@ -86,47 +101,188 @@ def _get_installed_modules(dist: Any) -> list[str]:
     return list(module_names)
 
 
+STUB_PACKAGE_SUFFIX = "-stubs"
+
+
+def with_stub_package_mappings(
+    module_mappings: dict[str, list[str]],
+) -> dict[str, list[str]]:
+    # PEP 561 stub-only packages install a 'foo-stubs' directory for the module 'foo',
+    # so the distributions providing 'foo-stubs' also satisfy imports of 'foo'
+    result = {module: list(names) for module, names in module_mappings.items()}
+    for module, names in module_mappings.items():
+        if not module.endswith(STUB_PACKAGE_SUFFIX):
+            continue
+        base_module = module[: -len(STUB_PACKAGE_SUFFIX)]
+        base_names = result.setdefault(base_module, [])
+        base_names.extend(name for name in names if name not in base_names)
+    return result
+
+
+@dataclass(frozen=True)
+class EditableModule:
+    module: str
+    distribution: str
+    # The source of the module, outside of site-packages
+    path: Path
+
+
+# Modules which commonly sit next to packages in a project directory
+EDITABLE_IGNORED_MODULES = {"setup", "conftest", "tests"}
+# setuptools writes a 'MAPPING' from module names to source paths in its finders
+EDITABLE_FINDER_MAPPING_REGEX = re.compile(r"^MAPPING\b[^=]*=\s*(\{.*\})\s*$", re.M)
+
+
+def _get_pth_modules(pth_path: Path) -> dict[str, Path]:
+    # Each line of a .pth file is either an import statement or a directory
+    # which is added to sys.path, making its top level modules importable
+    modules: dict[str, Path] = {}
+    for line in pth_path.read_text().splitlines():
+        line = line.strip()
+        if not line or line.startswith("#") or line.startswith(("import ", "import\t")):
+            continue
+        directory = pth_path.parent / line
+        if not directory.is_dir():
+            continue
+        for entry in sorted(directory.iterdir()):
+            if entry.stem.startswith("_") or entry.stem in EDITABLE_IGNORED_MODULES:
+                continue
+            if entry.is_dir() and (entry / "__init__.py").exists():
+                modules.setdefault(entry.name, entry.resolve())
+            elif entry.suffix == ".py" and entry.stem.isidentifier():
+                modules.setdefault(entry.stem, entry.resolve())
+    return modules
+
+
+def _get_finder_modules(finder_path: Path) -> dict[str, Path]:
+    match = EDITABLE_FINDER_MAPPING_REGEX.search(finder_path.read_text())
+    if match is None:
+        return {}
+    try:
+        mapping = ast.literal_eval(match.group(1))
+    except (ValueError, SyntaxError):
+        return {}
+    return {
+        module: Path(path).resolve()
+        for module, path in mapping.items()
+        if "." not in module
+    }
+
+
 @lru_cache(maxsize=None)
-def get_module_mappings() -> dict[str, list[str]]:
-    if sys.version_info >= (3, 10):
+def get_editable_modules(site_packages: tuple[str, ...] = ()) -> list[EditableModule]:
+    editable_modules: list[EditableModule] = []
+    for dist in _get_distributions(site_packages):
+        modules: dict[str, Path] = {}
+        for file in dist.files or []:
+            is_pth = file.suffix == ".pth"
+            is_finder = file.name.startswith("__editable__") and file.name.endswith(
+                "_finder.py"
+            )
+            if not (is_pth or is_finder):
+                continue
+            try:
+                file_path = Path(str(dist.locate_file(file)))
+                if is_pth:
+                    modules.update(_get_pth_modules(file_path))
+                else:
+                    modules.update(_get_finder_modules(file_path))
+            except OSError:
+                continue
+        editable_modules.extend(
+            EditableModule(module=module, distribution=dist.metadata["Name"], path=path)
+            for module, path in sorted(modules.items())
+        )
+    return editable_modules
+
+
+def with_editable_mappings(
+    module_mappings: dict[str, list[str]],
+    editable_modules: list[EditableModule],
+) -> dict[str, list[str]]:
+    # Editable installs only record a .pth file or finder in their metadata,
+    # so their modules are missing from the mappings
+    result = {module: list(names) for module, names in module_mappings.items()}
+    for editable_module in editable_modules:
+        names = result.setdefault(editable_module.module, [])
+        if editable_module.distribution not in names:
+            names.append(editable_module.distribution)
+    return result
+
+
+def get_project_distributions(
+    project_root: Path, source_roots: list[Path], site_packages: tuple[str, ...] = ()
+) -> list[str]:
+    # An editable distribution belongs to the project when all of its modules
+    # have their source within one of the source roots
+    roots = [(project_root / source_root).resolve() for source_root in source_roots]
+    modules_by_distribution: dict[str, list[EditableModule]] = {}
+    for editable_module in get_editable_modules(site_packages):
+        modules_by_distribution.setdefault(editable_module.distribution, []).append(
+            editable_module
+        )
+    return sorted(
+        distribution
+        for distribution, modules in modules_by_distribution.items()
+        if all(
+            any(
+                root == editable_module.path or root in editable_module.path.parents
+                for root in roots
+            )
+            for editable_module in modules
+        )
+    )
+
+
+@lru_cache(maxsize=None)
+def get_module_mappings(site_packages: tuple[str, ...] = ()) -> dict[str, list[str]]:
+    # 'site_packages' selects the distributions of another virtualenv
+    module_mappings: dict[str, list[str]] = {}
+    if sys.version_info >= (3, 10) and not site_packages:
         from importlib.metadata import packages_distributions
 
-        return packages_distributions()  # type: ignore
+        module_mappings = packages_distributions()  # type: ignore
     else:
-        if sys.version_info >= (3, 8):  # noqa: UP036
-            from importlib.metadata import distributions
-        else:
-            from importlib_metadata import distributions  # type: ignore
-
-        result: dict[str, list[str]] = {}
-        for dist in distributions():
+        for dist in _get_distributions(site_packages):
             modules = _get_installed_modules(dist)
             name = dist.metadata["Name"]
             for module in modules:
-                if module not in result:
-                    result[module] = []
-                result[module].append(name)
-        return result
+                if module not in module_mappings:
+                    module_mappings[module] = []
+                module_mappings[module].append(name)
+    return with_stub_package_mappings(
+        with_editable_mappings(module_mappings, get_editable_modules(site_packages))
+    )
 
 
 PYPI_PACKAGE_REGEX = re.compile(r"[-_.]+")
 
 
-def get_package_name(import_module_path: str) -> str:
+def get_package_name(
+    import_module_path: str, site_packages: tuple[str, ...] = ()
+) -> str:
     top_level_name = import_module_path.split(".")[0]
-    module_mappings = get_module_mappings()
+    module_mappings = get_module_mappings(site_packages)
     # Ignoring the case of multiple packages providing this module,
     # using the first one in the mapping
     return module_mappings.get(top_level_name, [top_level_name])[0]
 
 
-def normalize_package_name(import_module_path: str) -> str:
-    return PYPI_PACKAGE_REGEX.sub("-", get_package_name(import_module_path)).lower()
+def normalize_package_name(
+    import_module_path: str, site_packages: tuple[str, ...] = ()
+) -> str:
+    package_name = get_package_name(import_module_path, site_packages)
+    return PYPI_PACKAGE_REGEX.sub("-", package_name).lower()
 
 
 __all__ = [
     "is_stdlib_module",
     "get_module_mappings",
+    "with_stub_package_mappings",
+    "EditableModule",
+    "get_editable_modules",
+    "with_editable_mappings",
+    "get_project_distributions",
     "get_package_name",
     "normalize_package_name",
 ]
@@ -0,0 +1,120 @@
+from __future__ import annotations
+
+import base64
+import hashlib
+import os
+import re
+import sys
+from pathlib import Path
+from typing import TYPE_CHECKING
+
+from tach import errors
+
+if TYPE_CHECKING:
+    from tach.extension import ProjectConfig
+
+
+PROJECT_VIRTUALENV_NAMES = (".venv", "venv")
+
+
+def is_virtualenv(path: Path) -> bool:
+    return (path / "pyvenv.cfg").is_file()
+
+
+def _poetry_virtualenvs_dir() -> Path:
+    if "POETRY_VIRTUALENVS_PATH" in os.environ:
+        return Path(os.environ["POETRY_VIRTUALENVS_PATH"])
+    if "POETRY_CACHE_DIR" in os.environ:
+        cache_dir = Path(os.environ["POETRY_CACHE_DIR"])
+    elif sys.platform == "win32":
+        local_app_data = os.environ.get("LOCALAPPDATA", Path.home() / "AppData/Local")
+        cache_dir = Path(local_app_data) / "pypoetry" / "Cache"
+    elif sys.platform == "darwin":
+        cache_dir = Path.home() / "Library" / "Caches" / "pypoetry"
+    else:
+        xdg_cache_home = os.environ.get("XDG_CACHE_HOME", Path.home() / ".cache")
+        cache_dir = Path(xdg_cache_home) / "pypoetry"
+    return cache_dir / "virtualenvs"
+
+
+def _poetry_project_name(project_root: Path) -> str | None:
+    pyproject = project_root / "pyproject.toml"
+    if not pyproject.is_file():
+        return None
+    # Avoid depending on a TOML parser for a single key
+    in_poetry_table = False
+    for line in pyproject.read_text().splitlines():
+        line = line.strip()
+        if line.startswith("["):
+            in_poetry_table = line in ("[tool.poetry]", "[project]")
+            continue
+        match = re.match(r"""name\s*=\s*["']([^"']+)["']""", line)
+        if in_poetry_table and match:
+            return match.group(1)
+    return None
+
+
+def find_poetry_virtualenv(project_root: Path) -> Path | None:
+    # Poetry names its environments after the project name and a hash of its directory
+    name = _poetry_project_name(project_root)
+    if name is None:
+        return None
+    sanitized_name = re.sub(r'[ $`!*@"\\\r\n\t]', "_", name.lower())[:42]
+    normalized_root = os.path.normcase(os.path.realpath(project_root))
+    digest = hashlib.sha256(normalized_root.encode()).digest()
+    prefix = f"{sanitized_name}-{base64.urlsafe_b64encode(digest).decode()[:8]}"
+    virtualenvs_dir = _poetry_virtualenvs_dir()
+    if not virtualenvs_dir.is_dir():
+        return None
+    candidates = sorted(
+        path
+        for path in virtualenvs_dir.glob(f"{prefix}-py*")
+        if is_virtualenv(path)
+    )
+    # Prefer the environment for the most recent Python version
+    return candidates[-1] if candidates else None
+
+
+def find_virtualenv(project_root: Path, configured: str | None = None) -> Path | None:
+    if configured is not None:
+        return (project_root / configured).resolve()
+
+    if "VIRTUAL_ENV" in os.environ:
+        return Path(os.environ["VIRTUAL_ENV"]).resolve()
+
+    for name in PROJECT_VIRTUALENV_NAMES:
+        if is_virtualenv(project_root / name):
+            return (project_root / name).resolve()
+
+    return find_poetry_virtualenv(project_root)
+
+
+def get_site_packages(virtualenv: Path) -> list[Path]:
+    return sorted(
+        path
+        for pattern in ("lib/python*/site-packages", "Lib/site-packages")
+        for path in virtualenv.glob(pattern)
+        if path.is_dir()
+    )
+
+
+def get_project_site_packages(
+    project_root: Path, project_config: ProjectConfig
+) -> tuple[str, ...]:
+    # An empty result means the distributions of the running interpreter are used
+    configured = project_config.external.virtualenv
+    virtualenv = find_virtualenv(project_root, configured)
+    if configured is not None and (virtualenv is None or not is_virtualenv(virtualenv)):
+        raise errors.TachSetupError(
+            f"The configured virtualenv '{configured}' is not a virtual environment."
+        )
+    if virtualenv is None or virtualenv == Path(sys.prefix).resolve():
+        return ()
+    return tuple(str(path) for path in get_site_packages(virtualenv))
+
+
+__all__ = [
+    "find_virtualenv",
+    "get_site_packages",
+    "get_project_site_packages",
+]
@@ -0,0 +1,71 @@
+from __future__ import annotations
+
+import json
+from typing import TYPE_CHECKING, Any
+
+from tach import icons
+from tach.colors import BCOLORS
+
+if TYPE_CHECKING:
+    from tach.extension import DependencyHop
+
+
+def _hop_to_dict(hop: DependencyHop) -> dict[str, Any]:
+    return {
+        "source": hop.source,
+        "target": hop.target,
+        "declared": hop.declared,
+        "imports": [
+            {
+                "file_path": str(location.file_path),
+                "line_number": location.line_number,
+                "import_path": location.import_path,
+            }
+            for location in hop.imports
+        ],
+    }
+
+
+def render_why_text(source: str, target: str, hops: list[DependencyHop]) -> str:
+    if not hops:
+        return (
+            f"{icons.SUCCESS} {BCOLORS.OKGREEN}'{source}' does not depend on "
+            f"'{target}'.{BCOLORS.ENDC}"
+        )
+    if len(hops) == 1:
+        lines = [f"'{source}' depends on '{target}' directly."]
+    else:
+        chain = " -> ".join([hop.source for hop in hops] + [target])
+        lines = [
+            f"'{source}' depends on '{target}' transitively through {len(hops)} "
+            f"dependencies: {BCOLORS.OKCYAN}{chain}{BCOLORS.ENDC}"
+        ]
+    for hop in hops:
+        status = (
+            "" if hop.declared else f" {BCOLORS.WARNING}(undeclared){BCOLORS.ENDC}"
+        )
+        lines.append(
+            f"\n{BCOLORS.BOLD}{hop.source} -> {hop.target}{BCOLORS.ENDC}{status}"
+        )
+        for location in hop.imports:
+            lines.append(
+                f"  {location.file_path}:{location.line_number}  "
+                f"{location.import_path}"
+            )
+    return "\n".join(lines)
+
+
+def render_why_json(source: str, target: str, hops: list[DependencyHop]) -> str:
+    return json.dumps(
+        {
+            "source": source,
+            "target": target,
+            "direct": len(hops) == 1,
+            "path": [hop.source for hop in hops] + [target] if hops else [],
+            "hops": [_hop_to_dict(hop) for hop in hops],
+        },
+        indent=2,
+    )
+
+
+__all__ = ["render_why_text", "render_why_json"]
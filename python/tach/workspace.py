@@ -0,0 +1,221 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import TYPE_CHECKING, Any
+
+from tach.errors import TachError
+from tach.extension import (
+    check,
+    get_external_imports,
+    serialize_diagnostics_json,
+    set_excluded_paths,
+)
+from tach.filesystem import walk_pyfiles
+from tach.parsing import extend_and_validate, parse_project_config
+
+if TYPE_CHECKING:
+    from tach.extension import Diagnostic, ProjectConfig
+
+
+@dataclass
+class WorkspaceProject:
+    root: Path
+    config: ProjectConfig
+    exclude_paths: list[str]
+
+    @property
+    def source_roots(self) -> list[Path]:
+        return [
+            (self.root / source_root).resolve()
+            for source_root in self.config.source_roots
+        ]
+
+    def top_level_modules(self) -> set[str]:
+        # The first-party modules this project provides to other projects
+        modules: set[str] = set()
+        for source_root in self.source_roots:
+            if not source_root.is_dir():
+                continue
+            for entry in source_root.iterdir():
+                if entry.name.startswith((".", "_")):
+                    continue
+                if entry.is_dir() and (entry / "__init__.py").exists():
+                    modules.add(entry.name)
+                elif entry.suffix == ".py" and entry.stem.isidentifier():
+                    modules.add(entry.stem)
+        return modules
+
+
+@dataclass
+class CrossProjectImport:
+    # The file (relative to the workspace) which imports from another project
+    file_path: Path
+    line_number: int
+    module_path: str
+    importing_project: Path
+    imported_project: Path
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "file": str(self.file_path),
+            "line": self.line_number,
+            "module": self.module_path,
+            "importing_project": str(self.importing_project),
+            "imported_project": str(self.imported_project),
+        }
+
+
+@dataclass
+class ProjectReport:
+    root: Path
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+
+    @property
+    def error_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_error())
+
+    @property
+    def warning_count(self) -> int:
+        return sum(1 for diagnostic in self.diagnostics if diagnostic.is_warning())
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "errors": self.error_count,
+            "warnings": self.warning_count,
+            "diagnostics": json.loads(
+                serialize_diagnostics_json(self.diagnostics, pretty_print=False)
+            ),
+        }
+
+
+@dataclass
+class WorkspaceReport:
+    # Paths are relative to the workspace root
+    projects: list[ProjectReport] = field(default_factory=list)
+    cross_project_imports: list[CrossProjectImport] = field(default_factory=list)
+
+    @property
+    def has_errors(self) -> bool:
+        return any(project.error_count for project in self.projects)
+
+    def to_dict(self) -> dict[str, Any]:
+        return {
+            "projects": {
+                str(project.root): project.to_dict() for project in self.projects
+            },
+            "cross_project_imports": [
+                cross_project_import.to_dict()
+                for cross_project_import in self.cross_project_imports
+            ],
+        }
+
+
+def load_workspace_project(project_root: Path) -> WorkspaceProject:
+    project_root = project_root.resolve()
+    if not project_root.is_dir():
+        raise TachError(f"The path '{project_root}' is not a valid directory.")
+    project_config = parse_project_config(project_root)
+    if project_config is None:
+        raise TachError(f"No configuration found in '{project_root}'.")
+    exclude_paths = extend_and_validate(
+        None,
+        project_config.exclude + project_config.source_root_exclude_paths(),
+        project_config.use_regex_matching,
+    )
+    return WorkspaceProject(
+        root=project_root, config=project_config, exclude_paths=exclude_paths
+    )
+
+
+def find_cross_project_imports(
+    project: WorkspaceProject,
+    providers: dict[str, Path],
+    workspace_root: Path,
+) -> list[CrossProjectImport]:
+    source_roots = [str(source_root) for source_root in project.source_roots]
+    cross_project_imports: list[CrossProjectImport] = []
+    for pyfile in walk_pyfiles(
+        project.root,
+        project_root=project.root,
+        exclude_paths=project.exclude_paths,
+        use_regex_matching=project.config.use_regex_matching,
+    ):
+        file_path = project.root / pyfile
+        # Imports which resolve within this project are not external to it
+        for external_import in get_external_imports(
+            source_roots=source_roots,
+            file_path=str(file_path),
+            ignore_type_checking_imports=project.config.ignore_type_checking_imports,
+        ):
+            top_level_module = external_import.module_path.split(".")[0]
+            provider = providers.get(top_level_module)
+            if provider is None or provider == project.root:
+                continue
+            cross_project_imports.append(
+                CrossProjectImport(
+                    file_path=file_path.relative_to(workspace_root),
+                    line_number=external_import.line_number,
+                    module_path=external_import.module_path,
+                    importing_project=project.root.relative_to(workspace_root),
+                    imported_project=provider.relative_to(workspace_root),
+                )
+            )
+    return cross_project_imports
+
+
+def check_workspace(workspace_root: Path, project_roots: list[Path]) -> WorkspaceReport:
+    """Check each project with its own configuration, and find imports between them.
+
+    An import crosses projects when it is external to the importing project,
+    and its top level module is provided by a source root of another project.
+    """
+    workspace_root = workspace_root.resolve()
+    projects = [load_workspace_project(project_root) for project_root in project_roots]
+    for project in projects:
+        if workspace_root not in (project.root, *project.root.parents):
+            raise TachError(
+                f"The project '{project.root}' is not within the workspace "
+                f"'{workspace_root}'."
+            )
+
+    # When several projects provide the same module, the first one listed wins
+    providers: dict[str, Path] = {}
+    for project in projects:
+        for module in sorted(project.top_level_modules()):
+            providers.setdefault(module, project.root)
+
+    report = WorkspaceReport()
+    for project in projects:
+        diagnostics = check(
+            project_root=project.root,
+            project_config=project.config,
+            dependencies=True,
+            interfaces=True,
+            exclude_paths=project.exclude_paths,
+        )
+        report.projects.append(
+            ProjectReport(
+                root=project.root.relative_to(workspace_root), diagnostics=diagnostics
+            )
+        )
+        # This informs the Rust extension ahead-of-time which paths are excluded.
+        with set_excluded_paths(
+            project_root=str(project.root),
+            exclude_paths=project.exclude_paths,
+            use_regex_matching=project.config.use_regex_matching,
+            include_paths=project.config.include,
+        ):
+            report.cross_project_imports.extend(
+                find_cross_project_imports(project, providers, workspace_root)
+            )
+    return report
+
+
+__all__ = [
+    "CrossProjectImport",
+    "ProjectReport",
+    "WorkspaceReport",
+    "check_workspace",
+]
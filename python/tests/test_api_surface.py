@@ -0,0 +1,151 @@
+from __future__ import annotations
+
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tach.api_surface import (
+    ApiMember,
+    ApiSnapshot,
+    compare_api_snapshots,
+    module_api,
+    read_api_snapshot,
+    render_api_diff,
+    take_api_snapshot,
+    write_api_snapshot,
+)
+from tach.errors import TachError
+
+SOURCE = """
+import os
+from .models import User
+
+VERSION = "1.0"
+_cache = {}
+
+
+def create_user(name, email=None, *, admin=False, **extra):
+    ...
+
+
+async def fetch(url, /, timeout=10):
+    ...
+
+
+def _helper():
+    ...
+
+
+class Service(Base, mixins.Logging):
+    def __init__(self, client):
+        ...
+
+    def run(self, *args):
+        ...
+
+    def _internal(self):
+        ...
+"""
+
+
+def test_module_api_top_level():
+    api = module_api(SOURCE)
+
+    assert list(api) == [
+        "Service",
+        "Service.__init__",
+        "Service.run",
+        "VERSION",
+        "create_user",
+        "fetch",
+    ]
+    assert api["create_user"] == ApiMember(
+        "create_user", "function", "(name, email=..., *, admin=..., **extra)"
+    )
+    assert api["fetch"].kind == "async function"
+    assert api["fetch"].signature == "(url, /, timeout=...)"
+    assert api["Service"].signature == "(Base, mixins.Logging)"
+    assert api["Service.run"] == ApiMember("Service.run", "method", "(self, *args)")
+
+
+def test_module_api_declared_all():
+    api = module_api(SOURCE + '\n__all__ = ["create_user", "User"]\n')
+
+    assert list(api) == ["User", "create_user"]
+    assert api["User"] == ApiMember("User", "import", " from .models.User")
+
+
+def test_module_api_interfaces():
+    api = module_api(SOURCE, is_exposed=lambda name: name in ("Service", "fetch"))
+
+    assert list(api) == ["Service", "Service.__init__", "Service.run", "fetch"]
+
+
+def test_compare_api_snapshots():
+    baseline = ApiSnapshot(
+        modules={
+            "myapp.api": {
+                "create_user": ApiMember("create_user", "function", "(name)"),
+                "delete_user": ApiMember("delete_user", "function", "(user)"),
+            },
+            "myapp.old": {"run": ApiMember("run", "function", "()")},
+        }
+    )
+    current = ApiSnapshot(
+        modules={
+            "myapp.api": {
+                "create_user": ApiMember("create_user", "function", "(name, email)"),
+                "list_users": ApiMember("list_users", "function", "()"),
+            },
+        }
+    )
+
+    diff = compare_api_snapshots(baseline, current)
+
+    changes = [(change.module, change.member, change.change) for change in diff.changes]
+    assert changes == [
+        ("myapp.api", "create_user", "changed"),
+        ("myapp.api", "delete_user", "removed"),
+        ("myapp.api", "list_users", "added"),
+        ("myapp.old", "run", "removed"),
+    ]
+    assert len(diff.breaking_changes) == 3
+    text = render_api_diff(diff)
+    assert (
+        "~ changed function create_user(name) -> function create_user(name, email)"
+        in text
+    )
+    assert "- removed function delete_user(user)" in text
+    assert "3 breaking change(s), 1 addition(s)." in text
+
+
+def test_snapshot_round_trip(tmp_path: Path):
+    package = tmp_path / "src" / "myapp" / "api"
+    package.mkdir(parents=True)
+    (package / "__init__.py").write_text(SOURCE)
+    (tmp_path / "src" / "myapp" / "cli.py").write_text("def main(): ...\n")
+    project_config = SimpleNamespace(
+        source_roots=["src"],
+        all_modules=lambda: [
+            SimpleNamespace(path="myapp.api"),
+            SimpleNamespace(path="myapp.cli"),
+            SimpleNamespace(path="<root>"),
+        ],
+        all_interfaces=lambda: [
+            SimpleNamespace(expose=["main"], from_modules=["myapp.cli"]),
+        ],
+    )
+
+    snapshot = take_api_snapshot(tmp_path, project_config)
+    path = tmp_path / "tach-api.json"
+    write_api_snapshot(snapshot, path)
+
+    assert list(snapshot.modules) == ["myapp.api", "myapp.cli"]
+    assert read_api_snapshot(path).modules == snapshot.modules
+    assert compare_api_snapshots(read_api_snapshot(path), snapshot).changes == []
+
+
+def test_read_missing_snapshot(tmp_path: Path):
+    with pytest.raises(TachError, match="does not exist"):
+        read_api_snapshot(tmp_path / "missing.json")
@@ -0,0 +1,151 @@
+from __future__ import annotations
+
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.build_targets import (
+    BuildTarget,
+    build_targets,
+    render_build_files,
+    write_build_files,
+)
+
+
+def make_project(root: Path) -> SimpleNamespace:
+    for package in ["src/myapp/api", "src/myapp/core", "src/myapp/core/models"]:
+        (root / package).mkdir(parents=True, exist_ok=True)
+        (root / package / "__init__.py").write_text("")
+    (root / "src/myapp/cli.py").write_text("")
+    return SimpleNamespace(source_roots=["src"])
+
+
+def test_build_targets(tmp_path: Path):
+    project_config = make_project(tmp_path)
+
+    targets = build_targets(
+        tmp_path,
+        project_config,
+        ["myapp.api", "myapp.core", "myapp.core.models", "myapp.cli", "missing"],
+        [
+            ("myapp.api", "myapp.core"),
+            ("myapp.api", "myapp.core.models"),
+            ("myapp.cli", "myapp.api"),
+            ("myapp.core", "myapp.core"),
+        ],
+    )
+
+    assert targets == [
+        BuildTarget(
+            module="myapp.api",
+            directory="src/myapp/api",
+            name="api",
+            sources=["**/*.py"],
+            dependencies=["myapp.core", "myapp.core.models"],
+        ),
+        BuildTarget(
+            module="myapp.cli",
+            directory="src/myapp",
+            name="cli",
+            sources=["cli.py"],
+            dependencies=["myapp.api"],
+        ),
+        BuildTarget(
+            module="myapp.core",
+            directory="src/myapp/core",
+            name="core",
+            sources=["**/*.py"],
+            excludes=["models/**"],
+        ),
+        BuildTarget(
+            module="myapp.core.models",
+            directory="src/myapp/core/models",
+            name="models",
+            sources=["**/*.py"],
+        ),
+    ]
+
+
+TARGETS = [
+    BuildTarget(
+        module="myapp.api",
+        directory="src/myapp/api",
+        name="api",
+        sources=["**/*.py"],
+        dependencies=["myapp.cli", "myapp.core"],
+    ),
+    BuildTarget(
+        module="myapp.cli",
+        directory="src/myapp",
+        name="cli",
+        sources=["cli.py"],
+    ),
+    BuildTarget(
+        module="myapp.core",
+        directory="src/myapp/core",
+        name="core",
+        sources=["**/*.py"],
+        excludes=["models/**"],
+    ),
+]
+
+
+def test_render_bazel():
+    build_files = render_build_files(TARGETS, "bazel")
+
+    assert list(build_files) == [
+        "src/myapp/BUILD.bazel",
+        "src/myapp/api/BUILD.bazel",
+        "src/myapp/core/BUILD.bazel",
+    ]
+    assert build_files["src/myapp/api/BUILD.bazel"] == (
+        "# Module 'myapp.api'\n"
+        "py_library(\n"
+        '    name = "api",\n'
+        '    srcs = glob(["**/*.py"]),\n'
+        "    deps = [\n"
+        '        "//src/myapp:cli",\n'
+        '        "//src/myapp/core",\n'
+        "    ],\n"
+        '    visibility = ["//visibility:public"],\n'
+        ")\n"
+    )
+    assert 'srcs = ["cli.py"]' in build_files["src/myapp/BUILD.bazel"]
+    assert 'exclude = ["models/**"]' in build_files["src/myapp/core/BUILD.bazel"]
+
+
+def test_render_pants():
+    build_files = render_build_files(TARGETS, "pants")
+
+    assert build_files["src/myapp/core/BUILD"] == (
+        "# Module 'myapp.core'\n"
+        "python_sources(\n"
+        '    name="core",\n'
+        "    sources=[\n"
+        '        "**/*.py",\n'
+        '        "!models/**",\n'
+        "    ],\n"
+        "    dependencies=[],\n"
+        ")\n"
+    )
+    assert '"src/myapp:cli"' in build_files["src/myapp/api/BUILD"]
+
+
+def test_write_build_files(tmp_path: Path):
+    (tmp_path / "existing").mkdir()
+    (tmp_path / "existing" / "BUILD").write_text("keep")
+    (tmp_path / "new").mkdir()
+
+    written, skipped = write_build_files(
+        tmp_path, {"existing/BUILD": "replaced", "new/BUILD": "created"}
+    )
+
+    assert written == [tmp_path / "new" / "BUILD"]
+    assert skipped == [tmp_path / "existing" / "BUILD"]
+    assert (tmp_path / "existing" / "BUILD").read_text() == "keep"
+    assert (tmp_path / "new" / "BUILD").read_text() == "created"
+
+    written, skipped = write_build_files(
+        tmp_path, {"existing/BUILD": "replaced"}, force=True
+    )
+    assert written == [tmp_path / "existing" / "BUILD"]
+    assert (tmp_path / "existing" / "BUILD").read_text() == "replaced"
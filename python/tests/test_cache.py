@@ -5,6 +5,13 @@ from unittest.mock import patch
 
 from tach.cache.access import get_latest_version, get_uid
 from tach.cache.setup import resolve_dot_tach
+from tach.extension import (
+    ProjectConfig,
+    clear_cache,
+    get_cache_stats,
+    prune_cache,
+    update_computation_cache,
+)
 
 
 @patch("tach.cache.setup.find_project_config_root")
@@ -61,3 +68,25 @@ def test_get_latest_version(mock_find_project_config_root, tmp_path):
 
     result = get_latest_version()
     assert result == version
+
+
+def test_cache_management(tmp_path):
+    project_root = str(tmp_path)
+    project_config = ProjectConfig()
+
+    stats = get_cache_stats(project_root=project_root, project_config=project_config)
+    assert stats.entries == 0
+    assert stats.backend == "disk"
+
+    update_computation_cache(
+        project_root=project_root, cache_key="key", value=([(1, "output")], 0)
+    )
+    stats = get_cache_stats(project_root=project_root, project_config=project_config)
+    assert stats.entries == 1
+    assert stats.size_bytes > 0
+
+    assert prune_cache(project_root=project_root, older_than_days=1) == 0
+
+    clear_cache(project_root=project_root)
+    stats = get_cache_stats(project_root=project_root, project_config=project_config)
+    assert stats.entries == 0
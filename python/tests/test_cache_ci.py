@@ -0,0 +1,113 @@
+from __future__ import annotations
+
+import io
+import tarfile
+
+import pytest
+
+from tach.cache.ci import (
+    CacheArchiveError,
+    compute_cache_key,
+    export_cache,
+    find_cache_archive,
+    import_cache,
+)
+
+
+def _write_cache_entry(project_root, name, content):
+    path = project_root / ".tach" / "computation-cache" / name
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_bytes(content)
+    return path
+
+
+def test_cache_key_is_stable(tmp_path):
+    (tmp_path / "tach.toml").write_text('source_roots = ["."]')
+    (tmp_path / "uv.lock").write_text("lock")
+    assert compute_cache_key(tmp_path) == compute_cache_key(tmp_path)
+    assert compute_cache_key(tmp_path).startswith("tach-")
+
+
+def test_cache_key_changes_with_config_and_lockfiles(tmp_path):
+    (tmp_path / "tach.toml").write_text('source_roots = ["."]')
+    initial = compute_cache_key(tmp_path)
+
+    (tmp_path / "poetry.lock").write_text("lock")
+    with_lockfile = compute_cache_key(tmp_path)
+    assert with_lockfile != initial
+
+    (tmp_path / "tach.toml").write_text('source_roots = ["src"]')
+    assert compute_cache_key(tmp_path) != with_lockfile
+
+
+def test_cache_key_includes_file_dependencies(tmp_path):
+    (tmp_path / "tach.toml").write_text("")
+    (tmp_path / "data").mkdir()
+    (tmp_path / "data" / "schema.json").write_text("{}")
+    without = compute_cache_key(tmp_path)
+    with_deps = compute_cache_key(tmp_path, file_dependencies=["data/*.json"])
+    assert without != with_deps
+
+    (tmp_path / "data" / "schema.json").write_text('{"a": 1}')
+    assert compute_cache_key(tmp_path, file_dependencies=["data/*.json"]) != with_deps
+
+
+def test_export_empty_cache(tmp_path):
+    assert export_cache(tmp_path, output_dir=tmp_path / "out") is None
+
+
+def test_export_is_content_addressed(tmp_path):
+    _write_cache_entry(tmp_path, "entry", b"result")
+    first = export_cache(tmp_path, output_dir=tmp_path / "out")
+    second = export_cache(tmp_path, output_dir=tmp_path / "out")
+    assert first is not None
+    assert first == second
+    assert first.name.startswith("tach-cache-")
+
+    _write_cache_entry(tmp_path, "other", b"result")
+    third = export_cache(tmp_path, output_dir=tmp_path / "out")
+    assert third != first
+    assert not first.exists()
+    assert list((tmp_path / "out").iterdir()) == [third]
+
+
+def test_export_then_import_round_trip(tmp_path):
+    source = tmp_path / "source"
+    _write_cache_entry(source, "entry", b"result")
+    _write_cache_entry(source, "nested/entry", b"nested")
+    archive = export_cache(source, output_dir=tmp_path / "out")
+    assert archive is not None
+
+    target = tmp_path / "target"
+    _write_cache_entry(target, "local", b"local")
+    assert import_cache(target, archive_path=archive) == 2
+
+    cache_dir = target / ".tach" / "computation-cache"
+    assert (cache_dir / "entry").read_bytes() == b"result"
+    assert (cache_dir / "nested" / "entry").read_bytes() == b"nested"
+    assert (cache_dir / "local").read_bytes() == b"local"
+
+
+def test_import_missing_archive(tmp_path):
+    with pytest.raises(CacheArchiveError):
+        import_cache(tmp_path, archive_path=tmp_path / "missing.tar.gz")
+
+
+def test_import_rejects_unsafe_paths(tmp_path):
+    archive_path = tmp_path / "tach-cache-bad.tar.gz"
+    with tarfile.open(archive_path, "w:gz") as archive:
+        info = tarfile.TarInfo("../escape")
+        info.size = 4
+        archive.addfile(info, io.BytesIO(b"evil"))
+
+    with pytest.raises(CacheArchiveError):
+        import_cache(tmp_path / "project", archive_path=archive_path)
+    assert not (tmp_path / "project" / "escape").exists()
+    assert not (tmp_path / "escape").exists()
+
+
+def test_find_cache_archive(tmp_path):
+    assert find_cache_archive(tmp_path) is None
+    _write_cache_entry(tmp_path, "entry", b"result")
+    archive = export_cache(tmp_path, output_dir=tmp_path / "out")
+    assert find_cache_archive(tmp_path / "out") == archive
@@ -0,0 +1,66 @@
+from __future__ import annotations
+
+import json
+
+from tach.chains import (
+    analyze_chains,
+    render_chains_csv,
+    render_chains_json,
+    render_chains_text,
+)
+
+# 'api' and 'jobs' both reach 'db' through 'core'
+MODULE_DEPENDENCIES = {
+    "api": ["core", "auth"],
+    "jobs": ["core"],
+    "auth": ["db"],
+    "core": ["models", "utils"],
+    "models": ["db"],
+    "db": [],
+    "utils": [],
+}
+
+
+def test_longest_chains():
+    report = analyze_chains(MODULE_DEPENDENCIES)
+
+    assert report.longest_chains == [
+        ["api", "core", "models", "db"],
+        ["jobs", "core", "models", "db"],
+    ]
+    assert report.cycles == []
+
+
+def test_bottlenecks():
+    report = analyze_chains(MODULE_DEPENDENCIES, top=3)
+
+    assert [
+        (module.module, module.chains, module.longest_chain)
+        for module in report.bottlenecks
+    ] == [("core", 4, 4), ("api", 3, 4), ("db", 3, 4)]
+
+
+def test_cycles_are_ignored():
+    report = analyze_chains({"a": ["b"], "b": ["c"], "c": ["b", "d"], "d": []})
+
+    assert report.cycles == [["b", "c"]]
+    assert report.longest_chains == [["a", "b"], ["c", "d"]]
+
+
+def test_render_chains():
+    report = analyze_chains(MODULE_DEPENDENCIES, top=2)
+
+    text = render_chains_text(report)
+    assert "(4) api -> core -> models -> db" in text
+    assert "core  4 chains, longest 4" in text
+    assert json.loads(render_chains_json(report))["bottlenecks"][0] == {
+        "module": "core",
+        "chains": 4,
+        "longest_chain": 4,
+    }
+    assert render_chains_csv(report).splitlines() == [
+        "module,chains,longest_chain",
+        "core,4,4",
+        "api,3,4",
+    ]
+    assert render_chains_text(analyze_chains({})) == "No dependency chains found."
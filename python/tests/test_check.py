@@ -1,13 +1,17 @@
 from __future__ import annotations
 
+import asyncio
 import json
+from concurrent.futures import ThreadPoolExecutor
+from types import SimpleNamespace
 from unittest.mock import NonCallableMagicMock
 
 import pytest
 
+from tach import aio
 from tach.cli import tach_check
 from tach.errors import TachCircularDependencyError, TachVisibilityError
-from tach.extension import Diagnostic
+from tach.extension import Diagnostic, check
 from tach.icons import FAIL, SUCCESS, WARNING
 from tach.parsing.config import parse_project_config
 
@@ -145,6 +149,51 @@ def test_check_circular_dependency_json(example_dir, capfd, mocker):
     assert result["dependencies"] == ["mod1", "mod2", "mod1"]
 
 
+def test_check_circular_dependency_suggested_breaks(example_dir, capfd, mocker):
+    project_root = example_dir / "valid"
+    project_config = parse_project_config(root=project_root)
+    assert project_config is not None
+
+    mocker.patch(
+        "tach.cli.check",
+        side_effect=TachCircularDependencyError(["mod1", "mod2", "mod1"]),
+    )
+    cycle_break = SimpleNamespace(source="mod2", target="mod1", import_count=3)
+    mocker.patch(
+        "tach.cli.get_circular_dependencies",
+        return_value=[
+            SimpleNamespace(modules=["mod1", "mod2"], suggested_breaks=[cycle_break])
+        ],
+    )
+
+    with pytest.raises(SystemExit):
+        tach_check(
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=project_config.exclude,
+        )
+    captured = capfd.readouterr()
+    assert "Circular dependency detected between modules" in captured.err
+    assert "remove the dependency 'mod2' -> 'mod1' (3 imports)" in captured.err
+
+    with pytest.raises(SystemExit):
+        tach_check(
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=project_config.exclude,
+            output_format="json",
+        )
+    result = json.loads(capfd.readouterr().out)
+    assert result["cycles"] == [
+        {
+            "modules": ["mod1", "mod2"],
+            "suggested_breaks": [
+                {"source": "mod2", "target": "mod1", "import_count": 3}
+            ],
+        }
+    ]
+
+
 def test_check_visibility_error_text(example_dir, capfd, mocker):
     project_root = example_dir / "valid"
     project_config = parse_project_config(root=project_root)
@@ -211,3 +260,211 @@ def test_distributed_config_example_dir(example_dir, capfd):
         in captured.err
     )
     assert "project/top_level.py" in captured.err
+
+
+def test_check_scoped_to_module(example_dir):
+    project_root = example_dir / "valid"
+    project_config = parse_project_config(root=project_root)
+    assert project_config is not None
+
+    diagnostics = check(
+        project_root=project_root,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=project_config.exclude,
+        modules=["domain_two"],
+    )
+    file_paths = {
+        diagnostic.pyfile_path()
+        for diagnostic in diagnostics
+        if diagnostic.pyfile_path() is not None
+    }
+    assert all(file_path.startswith("domain_two") for file_path in file_paths)
+
+
+def test_check_scoped_to_unknown_module(example_dir):
+    project_root = example_dir / "valid"
+    project_config = parse_project_config(root=project_root)
+    assert project_config is not None
+
+    with pytest.raises(ValueError, match="Module 'missing' is not defined"):
+        check(
+            project_root=project_root,
+            project_config=project_config,
+            dependencies=True,
+            interfaces=True,
+            exclude_paths=project_config.exclude,
+            modules=["missing"],
+        )
+
+
+def test_async_check_matches_sync(example_dir):
+    project_root = example_dir / "valid"
+    project_config = parse_project_config(root=project_root)
+    assert project_config is not None
+
+    expected = check(
+        project_root=project_root,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=project_config.exclude,
+    )
+    result = asyncio.run(
+        aio.check(
+            project_root=project_root,
+            project_config=project_config,
+            exclude_paths=project_config.exclude,
+        )
+    )
+    assert [d.to_string() for d in result] == [d.to_string() for d in expected]
+
+
+def test_concurrent_checks_on_different_roots(example_dir):
+    def run_check(project_root):
+        project_config = parse_project_config(root=project_root)
+        assert project_config is not None
+        return [
+            d.to_string()
+            for d in check(
+                project_root=project_root,
+                project_config=project_config,
+                dependencies=True,
+                interfaces=True,
+                exclude_paths=project_config.exclude,
+            )
+        ]
+
+    project_roots = [example_dir / "valid", example_dir / "monorepo"] * 4
+    expected = [run_check(project_root) for project_root in project_roots]
+    with ThreadPoolExecutor(max_workers=4) as executor:
+        results = list(executor.map(run_check, project_roots))
+    assert results == expected
+
+
+def test_check_deprecated_module(tmp_path):
+    (tmp_path / "tach.toml").write_text(
+        """
+[[modules]]
+path = "legacy"
+depends_on = []
+deprecated = true
+successor = "core"
+
+[[modules]]
+path = "core"
+depends_on = []
+
+[[modules]]
+path = "billing"
+depends_on = ["legacy"]
+
+[[modules]]
+path = "api"
+depends_on = []
+"""
+    )
+    for module in ["legacy", "core", "billing", "api"]:
+        (tmp_path / module).mkdir()
+    (tmp_path / "legacy" / "__init__.py").write_text("")
+    (tmp_path / "core" / "__init__.py").write_text("")
+    (tmp_path / "billing" / "__init__.py").write_text("import legacy\n")
+    (tmp_path / "api" / "__init__.py").write_text("import legacy\n")
+    project_config = parse_project_config(root=tmp_path)
+    assert project_config is not None
+
+    diagnostics = check(
+        project_root=tmp_path,
+        project_config=project_config,
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=[],
+    )
+
+    codes = {diagnostic.usage_module(): diagnostic.code() for diagnostic in diagnostics}
+    assert codes == {
+        "billing": "deprecated-module-usage",
+        "api": "new-deprecated-module-dependency",
+    }
+    errors = [diagnostic for diagnostic in diagnostics if diagnostic.is_error()]
+    assert [diagnostic.usage_module() for diagnostic in errors] == ["api"]
+    assert "Use 'core' instead." in errors[0].message()
+
+
+def test_check_caps_reported_diagnostics(tmp_path, capfd):
+    (tmp_path / "tach.toml").write_text(
+        """
+[[modules]]
+path = "core"
+depends_on = []
+
+[[modules]]
+path = "api"
+depends_on = []
+
+[diagnostics]
+max_per_file = 1
+"""
+    )
+    for module in ["core", "api"]:
+        (tmp_path / module).mkdir()
+    for name in ["a", "b", "c"]:
+        (tmp_path / "core" / f"{name}.py").write_text("")
+    (tmp_path / "core" / "__init__.py").write_text("")
+    (tmp_path / "api" / "__init__.py").write_text(
+        "import core.a\nimport core.b\nimport core.c\n"
+    )
+    project_config = parse_project_config(root=tmp_path)
+    assert project_config is not None
+
+    with pytest.raises(SystemExit) as exc_info:
+        tach_check(
+            project_root=tmp_path,
+            project_config=project_config,
+            exclude_paths=[],
+        )
+
+    # Every violation still fails the check, but only the first is printed
+    assert exc_info.value.code == 1
+    captured = capfd.readouterr()
+    assert captured.err.count("Cannot use") == 1
+    assert "... and 2 more in 'api/__init__.py'" in captured.err
+
+
+def test_check_sarif_output(tmp_path, capfd):
+    (tmp_path / "tach.toml").write_text(
+        """
+[[modules]]
+path = "core"
+depends_on = []
+
+[[modules]]
+path = "api"
+depends_on = []
+"""
+    )
+    for module in ["core", "api"]:
+        (tmp_path / module).mkdir()
+    (tmp_path / "core" / "__init__.py").write_text("")
+    (tmp_path / "api" / "__init__.py").write_text("import core\n")
+    project_config = parse_project_config(root=tmp_path)
+    assert project_config is not None
+
+    with pytest.raises(SystemExit) as exc_info:
+        tach_check(
+            project_root=tmp_path,
+            project_config=project_config,
+            exclude_paths=[],
+            output_format="sarif",
+        )
+
+    assert exc_info.value.code == 1
+    sarif = json.loads(capfd.readouterr().out)
+    assert sarif["version"] == "2.1.0"
+    [result] = sarif["runs"][0]["results"]
+    assert result["ruleId"] == "undeclared-dependency"
+    assert result["level"] == "error"
+    location = result["locations"][0]["physicalLocation"]
+    assert location["artifactLocation"]["uri"] == "api/__init__.py"
+    assert location["region"]["startLine"] == 1
@@ -0,0 +1,68 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tach.extension import check_streaming
+from tach.parsing.config import parse_project_config
+
+FILE_COUNT = 200
+
+
+def make_project(root: Path) -> Path:
+    """A project where each of many files in 'a' has an undeclared dependency on 'b'."""
+    for module in ["a", "b"]:
+        (root / module).mkdir(parents=True)
+        (root / module / "__init__.py").write_text("")
+    for i in range(FILE_COUNT):
+        (root / "a" / f"file_{i}.py").write_text("import b\n")
+    (root / "tach.toml").write_text(
+        '[[modules]]\npath = "a"\ndepends_on = []\n'
+        '[[modules]]\npath = "b"\ndepends_on = []\n'
+    )
+    return root
+
+
+def run_check(project_root: Path, callback):
+    totals: list[int] = []
+    diagnostics = check_streaming(
+        project_root=project_root,
+        project_config=parse_project_config(project_root),
+        dependencies=True,
+        interfaces=True,
+        exclude_paths=[],
+        callback=callback,
+        total_callback=totals.append,
+    )
+    return diagnostics, totals
+
+
+def test_check_streaming_stops_when_callback_returns_false(tmp_path: Path):
+    project_root = make_project(tmp_path / "project")
+    checked_files: list[Path] = []
+
+    def stop_after_first_file(file_path: str, _diagnostics) -> bool:
+        checked_files.append(Path(file_path).resolve())
+        return False
+
+    diagnostics, totals = run_check(project_root, stop_after_first_file)
+
+    # Files already being checked in parallel may finish, but no others are started
+    assert totals == [FILE_COUNT + 2]
+    assert 0 < len(checked_files) < FILE_COUNT
+    assert len(diagnostics) <= len(checked_files)
+    for diagnostic in diagnostics:
+        assert diagnostic.code() == "undeclared-dependency"
+        assert (project_root / diagnostic.pyfile_path()).resolve() in checked_files
+
+
+def test_check_streaming_continues_when_callback_returns_none(tmp_path: Path):
+    project_root = make_project(tmp_path / "project")
+    checked_files: list[str] = []
+
+    diagnostics, totals = run_check(
+        project_root, lambda file_path, _diagnostics: checked_files.append(file_path)
+    )
+
+    assert totals == [FILE_COUNT + 2]
+    assert len(checked_files) == FILE_COUNT + 2
+    assert len(diagnostics) == FILE_COUNT
@@ -0,0 +1,68 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+from tach.churn import (
+    changed_modules,
+    find_co_changes,
+    render_churn_json,
+    render_churn_text,
+)
+
+MODULE_DEPENDENCIES = {"api": ["core"], "core": [], "billing": [], "orders": []}
+
+
+def test_changed_modules(tmp_path: Path):
+    commits = [
+        [tmp_path / "src/api/views.py", tmp_path / "src/core/__init__.py"],
+        [tmp_path / "src/api/README.md", tmp_path / "scripts/deploy.py"],
+    ]
+
+    assert changed_modules(commits, [tmp_path / "src"], ["api", "core"]) == [
+        {"api", "core"},
+        set(),
+    ]
+
+
+def test_find_co_changes():
+    commit_modules = [
+        *[{"billing", "orders"}] * 4,
+        *[{"api", "core"}] * 3,
+        {"billing"},
+        {"orders", "api"},
+        # Commits which change many modules at once are ignored
+        {f"module{i}" for i in range(30)} | {"api", "billing"},
+    ]
+
+    co_changes = find_co_changes(
+        commit_modules, MODULE_DEPENDENCIES, min_co_changes=3, min_confidence=0.5
+    )
+
+    assert [
+        (co_change.first, co_change.second, co_change.co_changes, co_change.declared)
+        for co_change in co_changes
+    ] == [("billing", "orders", 4, False), ("api", "core", 3, True)]
+    assert co_changes[0].confidence == 0.8
+
+
+def test_min_confidence():
+    commit_modules = [{"billing", "orders"}] * 3 + [{"billing"}, {"orders"}] * 4
+
+    assert find_co_changes(commit_modules, {}, min_co_changes=3) == []
+
+
+def test_render_churn():
+    co_changes = find_co_changes(
+        [{"billing", "orders"}] * 5 + [{"api", "core"}] * 5, MODULE_DEPENDENCIES
+    )
+
+    text = render_churn_text(co_changes)
+    assert "'billing' and 'orders' changed together in 5 commits (100%)" in text
+    assert "'api'" not in text
+    result = json.loads(render_churn_json(co_changes))
+    assert [entry["modules"] for entry in result["hidden_coupling"]] == [
+        ["billing", "orders"]
+    ]
+    assert [entry["modules"] for entry in result["declared"]] == [["api", "core"]]
+    assert "No hidden coupling found" in render_churn_text([])
@@ -0,0 +1,88 @@
+from __future__ import annotations
+
+import json
+
+from tach.cohesion import (
+    SplitSuggestion,
+    cluster_files,
+    render_split_suggestions_json,
+    render_split_suggestions_text,
+    suggest_split,
+)
+
+# Two tightly-knit groups of files, joined by a single import
+EDGES = {
+    ("orders/api.py", "orders/models.py"): 4,
+    ("orders/api.py", "orders/views.py"): 3,
+    ("orders/models.py", "orders/views.py"): 2,
+    ("orders/invoices.py", "orders/tax.py"): 5,
+    ("orders/invoices.py", "orders/pdf.py"): 2,
+    ("orders/models.py", "orders/tax.py"): 1,
+}
+FILES = sorted({file for edge in EDGES for file in edge} | {"orders/constants.py"})
+
+
+def test_cluster_files():
+    connected = [file for file in FILES if file != "orders/constants.py"]
+
+    assert cluster_files(connected, EDGES) == [
+        ["orders/api.py", "orders/models.py", "orders/views.py"],
+        ["orders/invoices.py", "orders/pdf.py", "orders/tax.py"],
+    ]
+
+
+def test_cluster_files_keeps_disconnected_groups_apart():
+    edges = {("a.py", "b.py"): 1, ("c.py", "d.py"): 1, ("d.py", "e.py"): 1}
+
+    assert cluster_files(["a.py", "b.py", "c.py", "d.py", "e.py"], edges) == [
+        ["a.py", "b.py"],
+        ["c.py", "d.py", "e.py"],
+    ]
+
+
+def test_suggest_split():
+    suggestion = suggest_split("orders", FILES, EDGES)
+
+    assert suggestion is not None
+    assert suggestion.clusters == [
+        ["orders/api.py", "orders/models.py", "orders/views.py"],
+        ["orders/invoices.py", "orders/pdf.py", "orders/tax.py"],
+    ]
+    assert suggestion.internal_imports == 16
+    assert suggestion.crossing_imports == 1
+    assert suggestion.unattached == ["orders/constants.py"]
+
+
+def test_no_split_for_cohesive_module():
+    edges = dict(EDGES)
+    edges[("orders/api.py", "orders/tax.py")] = 4
+
+    assert suggest_split("orders", FILES, edges) is None
+    # A looser limit accepts the same clusters
+    assert suggest_split("orders", FILES, edges, max_coupling=0.5) is not None
+
+
+def test_no_split_for_small_clusters():
+    edges = {("a.py", "b.py"): 3, ("b.py", "c.py"): 3, ("c.py", "d.py"): 1}
+
+    assert suggest_split("m", ["a.py", "b.py", "c.py", "d.py"], edges) is None
+
+
+def test_render_split_suggestions():
+    suggestion = SplitSuggestion(
+        module="orders",
+        clusters=[["orders/api.py", "orders/views.py"], ["orders/tax.py", "x.py"]],
+        internal_imports=9,
+        crossing_imports=1,
+        unattached=["orders/constants.py"],
+    )
+
+    text = render_split_suggestions_text([suggestion])
+    assert "'orders' could be split into 2 sub-modules" in text
+    assert "(1 of 10 imports cross the boundary)" in text
+    assert "    Sub-module 2:\n        orders/tax.py" in text
+    assert "Not connected to either sub-module:\n        orders/constants.py" in text
+
+    [result] = json.loads(render_split_suggestions_json([suggestion]))
+    assert result["coupling"] == 0.1
+    assert "No candidate splits found" in render_split_suggestions_text([])
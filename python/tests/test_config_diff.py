@@ -0,0 +1,155 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+
+import tomli
+
+from tach.config_diff import (
+    InterfaceChange,
+    ModuleChange,
+    SettingChange,
+    diff_configs,
+    read_config_source,
+    render_config_diff_json,
+    render_config_diff_text,
+)
+
+OLD_CONFIG = """
+layers = ["ui", "core"]
+exclude = ["tests"]
+
+[[modules]]
+path = "api"
+depends_on = ["core", "legacy"]
+layer = "ui"
+
+[[modules]]
+paths = ["core", "legacy"]
+depends_on = []
+
+[[interfaces]]
+expose = ["get_user"]
+from = ["core"]
+
+[rules]
+unused_ignore_directives = "warn"
+"""
+
+NEW_CONFIG = """
+layers = ["ui", "core"]
+exclude = ["tests"]
+
+[[modules]]
+path = "api"
+depends_on = ["core", { path = "billing", deprecated = true }]
+layer = "ui"
+utility = false
+
+[[modules]]
+path = "core"
+depends_on = []
+visibility = ["api"]
+
+[[modules]]
+path = "billing"
+
+[[interfaces]]
+expose = ["get_user", "User"]
+from = ["core"]
+
+[[interfaces]]
+expose = ["charge"]
+from = ["billing"]
+
+[rules]
+unused_ignore_directives = "error"
+"""
+
+
+def test_diff_configs():
+    diff = diff_configs(tomli.loads(OLD_CONFIG), tomli.loads(NEW_CONFIG))
+
+    assert diff.added_modules == ["billing"]
+    assert diff.removed_modules == ["legacy"]
+    assert diff.changed_modules == [
+        ModuleChange(
+            path="api", added_dependencies=["billing"], removed_dependencies=["legacy"]
+        ),
+        ModuleChange(
+            path="core",
+            settings=[SettingChange(key="visibility", old=["*"], new=["api"])],
+        ),
+    ]
+    assert diff.interface_changes == [
+        InterfaceChange(
+            from_modules=["billing"],
+            data_types="all",
+            status="added",
+            added_expose=["charge"],
+        ),
+        InterfaceChange(
+            from_modules=["core"],
+            data_types="all",
+            status="changed",
+            added_expose=["User"],
+        ),
+    ]
+    assert diff.settings == [
+        SettingChange(key="rules.unused_ignore_directives", old="warn", new="error")
+    ]
+
+
+def test_diff_unrestricted_dependencies():
+    old = {"modules": [{"path": "api"}]}
+    new = {"modules": [{"path": "api", "depends_on": ["core"]}]}
+
+    diff = diff_configs(old, new)
+
+    assert diff.changed_modules == [
+        ModuleChange(
+            path="api",
+            settings=[SettingChange(key="depends_on", old=None, new={"core": False})],
+        )
+    ]
+
+
+def test_diff_identical_configs():
+    diff = diff_configs(tomli.loads(OLD_CONFIG), tomli.loads(OLD_CONFIG))
+
+    assert not diff.has_changes()
+    assert render_config_diff_text(diff) == "No configuration changes."
+
+
+def test_render_config_diff():
+    diff = diff_configs(tomli.loads(OLD_CONFIG), tomli.loads(NEW_CONFIG))
+
+    text = render_config_diff_text(diff)
+    assert "Added modules:\n  'billing'" in text
+    assert "    + depends on 'billing'" in text
+    assert "    - depends on 'legacy'" in text
+    assert "  changed interface for core: +User" in text
+    assert '  rules.unused_ignore_directives: "warn" -> "error"' in text
+
+    data = json.loads(render_config_diff_json(diff))
+    assert data["removed_modules"] == ["legacy"]
+    assert data["interface_changes"][0]["status"] == "added"
+
+
+def test_read_config_source(tmp_path: Path, mocker):
+    config_path = tmp_path / "old.toml"
+    config_path.write_text(OLD_CONFIG)
+    get_file_at_ref = mocker.patch(
+        "tach.config_diff.get_file_at_ref", return_value=NEW_CONFIG
+    )
+
+    assert read_config_source(tmp_path, str(config_path))["layers"] == ["ui", "core"]
+    get_file_at_ref.assert_not_called()
+
+    config = read_config_source(tmp_path, "main")
+    assert [module.get("path") for module in config["modules"]] == [
+        "api",
+        "core",
+        "billing",
+    ]
+    get_file_at_ref.assert_called_once_with(tmp_path, "main", tmp_path / "tach.toml")
@@ -0,0 +1,41 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tach.extension import get_dependency_graph
+from tach.parsing.config import parse_project_config
+
+
+def make_project(root: Path) -> Path:
+    for module in ["a", "b", "c"]:
+        (root / module).mkdir(parents=True)
+        (root / module / "__init__.py").write_text("")
+    (root / "a/service.py").write_text("import b\nfrom b import api\nimport c\n")
+    (root / "b/api.py").write_text("import c\n")
+    (root / "tach.toml").write_text(
+        '[[modules]]\npath = "a"\ndepends_on = ["b"]\n'
+        '[[modules]]\npath = "b"\ndepends_on = []\n'
+        '[[modules]]\npath = "c"\ndepends_on = []\n'
+    )
+    return root
+
+
+def test_to_networkx_dict(tmp_path: Path):
+    project_root = make_project(tmp_path / "project")
+    project_config = parse_project_config(project_root)
+
+    graph = get_dependency_graph(project_root, project_config, [])
+
+    assert graph.to_networkx_dict() == {
+        "a": {
+            "b": {"weight": 2, "declared": True},
+            "c": {"weight": 1, "declared": False},
+        },
+        "b": {"c": {"weight": 1, "declared": False}},
+        "c": {},
+    }
+    attributes = graph.node_attributes()
+    assert sorted(attributes) == ["a", "b", "c"]
+    assert attributes["a"]["files"] == 2
+    assert attributes["a"]["imports"] == 3
+    assert attributes["c"]["lines"] == 0
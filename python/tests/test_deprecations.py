@@ -0,0 +1,87 @@
+from __future__ import annotations
+
+import json
+from types import SimpleNamespace
+
+from tach.colors import BCOLORS
+from tach.deprecations import (
+    find_deprecated_modules,
+    render_deprecations_json,
+    render_deprecations_text,
+)
+
+
+def make_config() -> SimpleNamespace:
+    modules = [
+        SimpleNamespace(path="legacy", deprecated=True, successor="core"),
+        SimpleNamespace(path="old_utils", deprecated=True, successor=None),
+        SimpleNamespace(path="core", deprecated=False, successor=None),
+        SimpleNamespace(path="api", deprecated=False, successor=None),
+        SimpleNamespace(path="billing", deprecated=False, successor=None),
+    ]
+    return SimpleNamespace(all_modules=lambda: modules)
+
+
+def make_graph() -> SimpleNamespace:
+    edges = [
+        ("billing", "legacy", 1, False),
+        ("api", "legacy", 3, True),
+        ("legacy", "legacy", 2, False),
+        ("api", "core", 5, True),
+    ]
+    return SimpleNamespace(
+        edges=[
+            SimpleNamespace(
+                source=source, target=target, weight=weight, declared=declared
+            )
+            for source, target, weight, declared in edges
+        ]
+    )
+
+
+def test_find_deprecated_modules():
+    modules = find_deprecated_modules(make_config(), make_graph())
+
+    assert [module.module for module in modules] == ["legacy", "old_utils"]
+    legacy, old_utils = modules
+    assert legacy.successor == "core"
+    assert [consumer.module for consumer in legacy.consumers] == ["api", "billing"]
+    assert legacy.usages == 4
+    assert old_utils.consumers == []
+
+
+def test_render_deprecations_text():
+    modules = find_deprecated_modules(make_config(), make_graph())
+
+    text = render_deprecations_text(modules)
+
+    assert f"legacy{BCOLORS.ENDC} -> core  (2 consumer(s), 4 usage(s))" in text
+    assert "    api (3 usage(s))" in text
+    assert "billing (1 usage(s))" in text
+    assert "new, not declared in depends_on" in text
+    assert "1 of 2 deprecated module(s) still have consumers." in text
+
+
+def test_render_no_deprecations():
+    config = SimpleNamespace(all_modules=lambda: [])
+
+    text = render_deprecations_text(find_deprecated_modules(config, make_graph()))
+
+    assert text == "No deprecated modules found."
+
+
+def test_render_deprecations_json():
+    result = json.loads(
+        render_deprecations_json(find_deprecated_modules(make_config(), make_graph()))
+    )
+
+    assert result[0] == {
+        "module": "legacy",
+        "successor": "core",
+        "usages": 4,
+        "consumers": [
+            {"module": "api", "usages": 3, "declared": True},
+            {"module": "billing", "usages": 1, "declared": False},
+        ],
+    }
+    assert result[1]["consumers"] == []
@@ -0,0 +1,41 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+from tach.django_apps import django_app_to_dict, render_django_apps
+
+
+def _app(label, module_path, app_config=None, in_project=True):
+    return SimpleNamespace(
+        entry=app_config or module_path,
+        label=label,
+        module_path=module_path,
+        app_config=app_config,
+        in_project=in_project,
+    )
+
+
+def test_render_no_apps():
+    assert render_django_apps([]) == "No installed apps found."
+
+
+def test_render_django_apps():
+    apps = [
+        _app("invoices", "project.billing", app_config="project.billing.apps.Cfg"),
+        _app("auth", "django.contrib.auth", in_project=False),
+    ]
+    lines = render_django_apps(apps).splitlines()
+    assert lines[0] == "invoices  -> project.billing (via project.billing.apps.Cfg)"
+    assert lines[1].startswith("auth      -> django.contrib.auth ")
+    assert "outside source roots" in lines[1]
+
+
+def test_django_app_to_dict():
+    app = _app("users", "project.users")
+    assert django_app_to_dict(app) == {
+        "entry": "project.users",
+        "label": "users",
+        "module_path": "project.users",
+        "app_config": None,
+        "in_project": True,
+    }
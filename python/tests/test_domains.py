@@ -0,0 +1,114 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+from tach.domains import NO_DOMAIN, NO_MODULE, NO_PACKAGE, group_by_domain
+
+
+@dataclass
+class FakeDiagnostic:
+    module: str | None
+    error: bool = True
+
+    def usage_module(self) -> str | None:
+        return self.module
+
+    def is_error(self) -> bool:
+        return self.error
+
+    def is_warning(self) -> bool:
+        return not self.error
+
+
+def test_group_by_domain():
+    module_dependencies = {
+        "billing": ["billing.api", "auth"],
+        "billing.api": ["core"],
+        "billing.invoices": ["billing", "auth.tokens"],
+        "auth": [],
+        "auth.tokens": [],
+        "core": [],
+    }
+    diagnostics = [
+        FakeDiagnostic("billing.api"),
+        FakeDiagnostic("billing.invoices.models", error=False),
+        FakeDiagnostic(None),
+    ]
+
+    reports = group_by_domain(
+        diagnostics,  # type: ignore
+        module_dependencies,
+        ["auth", "billing", "billing.invoices"],
+    )
+
+    assert [report.domain for report in reports] == [
+        "auth",
+        "billing",
+        "billing.invoices",
+        NO_DOMAIN,
+    ]
+    auth, billing, invoices, no_domain = reports
+    assert auth.modules == ["auth", "auth.tokens"]
+    assert (auth.fan_in, auth.fan_out, auth.instability) == (2, 0, 0.0)
+    assert billing.modules == ["billing", "billing.api"]
+    assert billing.dependencies == {"auth", NO_DOMAIN}
+    assert billing.dependents == {"billing.invoices"}
+    assert billing.instability == 2 / 3
+    assert (billing.error_count, billing.warning_count) == (1, 0)
+    assert invoices.modules == ["billing.invoices"]
+    assert (invoices.fan_in, invoices.fan_out, invoices.instability) == (0, 2, 1.0)
+    assert (invoices.error_count, invoices.warning_count) == (0, 1)
+    assert no_domain.modules == ["core"]
+    assert no_domain.error_count == 1
+
+
+def test_group_by_package():
+    module_dependencies = {
+        "payments.stripe": ["payments.models", "core.db"],
+        "payments.models": ["core.db"],
+        "core.db": [],
+        "scripts": ["payments.stripe"],
+    }
+
+    reports = group_by_domain(
+        [FakeDiagnostic("payments.stripe")],  # type: ignore
+        module_dependencies,
+        ["payments", "core"],
+        ungrouped=NO_PACKAGE,
+    )
+
+    assert [report.domain for report in reports] == ["core", "payments", NO_PACKAGE]
+    core, payments, no_package = reports
+    assert payments.modules == ["payments.stripe", "payments.models"]
+    assert payments.dependencies == {"core"}
+    assert payments.dependents == {NO_PACKAGE}
+    assert payments.error_count == 1
+    assert core.fan_in == 1
+    assert no_package.modules == ["scripts"]
+
+
+def test_group_by_module():
+    module_dependencies = {"billing": ["core"], "billing.api": [], "core": []}
+    diagnostics = [
+        FakeDiagnostic("billing.api"),
+        FakeDiagnostic("billing"),
+        FakeDiagnostic(None, error=False),
+    ]
+
+    reports = group_by_domain(
+        diagnostics,  # type: ignore
+        module_dependencies,
+        list(module_dependencies),
+        ungrouped=NO_MODULE,
+    )
+
+    assert [report.domain for report in reports] == [
+        "billing",
+        "billing.api",
+        "core",
+        NO_MODULE,
+    ]
+    billing, api, core, no_module = reports
+    assert (billing.error_count, api.error_count, core.error_count) == (1, 1, 0)
+    assert billing.dependencies == {"core"}
+    assert no_module.warning_count == 1
@@ -0,0 +1,87 @@
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from tach.duplicates import (
+    find_duplicate_clusters,
+    jaccard,
+    module_similarity,
+    render_duplicates_csv,
+    render_duplicates_json,
+    render_duplicates_text,
+)
+
+MODULE_DEPENDENCIES = {
+    "billing.api": ["core", "db", "auth", "utils"],
+    "orders.api": ["core", "db", "auth", "utils"],
+    "shipping.api": ["core", "db", "auth", "utils", "maps"],
+    "reports": ["db", "utils"],
+    "core": ["utils"],
+    "utils": [],
+}
+MODULE_IMPORTS = {
+    "billing.api": {"core", "db", "auth", "utils"},
+    "orders.api": {"core", "db", "auth", "utils"},
+    "shipping.api": {"core", "db", "auth", "utils", "maps"},
+    "reports": {"db"},
+}
+
+
+@pytest.mark.parametrize(
+    "a,b,expected",
+    [
+        (set(), set(), 1.0),
+        ({"x"}, set(), 0.0),
+        ({"x", "y"}, {"y", "z"}, 1 / 3),
+    ],
+)
+def test_jaccard(a: set[str], b: set[str], expected: float):
+    assert jaccard(a, b) == expected
+
+
+def test_modules_are_left_out_of_each_others_dependencies():
+    declared = {"a": {"b", "x", "y"}, "b": {"a", "x", "y"}}
+
+    assert module_similarity("a", "b", declared, {}) == 1.0
+
+
+def test_find_duplicate_clusters():
+    [cluster] = find_duplicate_clusters(MODULE_DEPENDENCIES, MODULE_IMPORTS)
+
+    assert cluster.modules == ["billing.api", "orders.api", "shipping.api"]
+    assert cluster.similarity == 0.8
+    assert cluster.shared_dependencies == ["auth", "core", "db", "utils"]
+    assert cluster.shared_imports == ["auth", "core", "db", "utils"]
+
+
+def test_imports_must_also_match():
+    module_imports = {**MODULE_IMPORTS, "orders.api": {"core"}}
+
+    [cluster] = find_duplicate_clusters(
+        MODULE_DEPENDENCIES, module_imports, threshold=0.8
+    )
+
+    assert cluster.modules == ["billing.api", "shipping.api"]
+
+
+def test_threshold():
+    [cluster] = find_duplicate_clusters(
+        MODULE_DEPENDENCIES, MODULE_IMPORTS, threshold=1.0
+    )
+
+    assert cluster.modules == ["billing.api", "orders.api"]
+
+
+def test_render_duplicates():
+    clusters = find_duplicate_clusters(MODULE_DEPENDENCIES, MODULE_IMPORTS)
+
+    text = render_duplicates_text(clusters)
+    assert "'billing.api', 'orders.api', 'shipping.api' have near-identical" in text
+    assert "Shared dependencies: 'auth', 'core', 'db', 'utils'" in text
+    assert json.loads(render_duplicates_json(clusters))[0]["similarity"] == 0.8
+    assert render_duplicates_csv(clusters).splitlines()[1] == (
+        "billing.api orders.api shipping.api,0.8,auth core db utils"
+    )
+    assert "No modules with duplicate" in render_duplicates_text([])
@@ -0,0 +1,105 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+from unittest.mock import Mock
+
+import pytest
+
+from tach.exit_codes import (
+    EXIT_DEPENDENCY_FAILURE,
+    EXIT_EXTERNAL_FAILURE,
+    EXIT_FAILURE,
+    EXIT_INTERFACE_FAILURE,
+    EXIT_SUCCESS,
+    ExitPolicy,
+)
+
+
+def make_diagnostic(
+    is_error: bool = True, dependency: bool = False, interface: bool = False
+) -> Mock:
+    diagnostic = Mock()
+    diagnostic.is_error.return_value = is_error
+    diagnostic.is_dependency_error.return_value = dependency
+    diagnostic.is_interface_error.return_value = interface
+    return diagnostic
+
+
+ERROR = make_diagnostic()
+WARNING = make_diagnostic(is_error=False)
+
+
+@pytest.mark.parametrize(
+    "policy,diagnostics,expected",
+    [
+        (ExitPolicy(), [], EXIT_SUCCESS),
+        (ExitPolicy(), [WARNING], EXIT_SUCCESS),
+        (ExitPolicy(), [ERROR, WARNING], EXIT_FAILURE),
+        (ExitPolicy(fail_on="warning"), [WARNING], EXIT_FAILURE),
+        (ExitPolicy(fail_on="never"), [ERROR], EXIT_SUCCESS),
+        (ExitPolicy(max_violations=2), [ERROR, ERROR], EXIT_SUCCESS),
+        (ExitPolicy(max_violations=2), [ERROR, ERROR, ERROR], EXIT_FAILURE),
+    ],
+)
+def test_exit_code(policy: ExitPolicy, diagnostics: list[Mock], expected: int):
+    assert policy.exit_code(diagnostics) == expected
+
+
+def test_category_exit_codes():
+    policy = ExitPolicy(category_exit_codes=True)
+    dependency_error = make_diagnostic(dependency=True)
+    interface_error = make_diagnostic(interface=True)
+
+    assert policy.exit_code([dependency_error]) == EXIT_DEPENDENCY_FAILURE
+    assert policy.exit_code([interface_error, WARNING]) == EXIT_INTERFACE_FAILURE
+    assert (
+        policy.exit_code([dependency_error, interface_error])
+        == EXIT_DEPENDENCY_FAILURE | EXIT_INTERFACE_FAILURE
+    )
+    assert policy.exit_code([ERROR]) == EXIT_FAILURE
+    assert policy.exit_code([ERROR], external=True) == EXIT_EXTERNAL_FAILURE
+
+
+def test_unused_dependencies_fail_unless_never():
+    assert ExitPolicy().exit_code([], unused_dependencies=True) == EXIT_FAILURE
+    assert (
+        ExitPolicy(category_exit_codes=True).exit_code([], unused_dependencies=True)
+        == EXIT_DEPENDENCY_FAILURE
+    )
+    assert (
+        ExitPolicy(fail_on="never").exit_code([], unused_dependencies=True)
+        == EXIT_SUCCESS
+    )
+
+
+def test_cli_flags_override_config():
+    config = SimpleNamespace(
+        fail_on="warning", max_violations=5, category_exit_codes=True
+    )
+
+    assert ExitPolicy.from_config(config) == ExitPolicy(  # type: ignore
+        fail_on="warning", max_violations=5, category_exit_codes=True
+    )
+    assert ExitPolicy.from_config(  # type: ignore
+        config, fail_on="never", max_violations=0
+    ) == ExitPolicy(fail_on="never", max_violations=0, category_exit_codes=True)
+
+
+def make_coded_diagnostic(code: str, is_error: bool = True) -> Mock:
+    diagnostic = make_diagnostic(is_error=is_error)
+    diagnostic.code.return_value = code
+    return diagnostic
+
+
+def test_violation_budgets():
+    undeclared = make_coded_diagnostic("undeclared-dependency")
+    private = make_coded_diagnostic("private-import")
+    policy = ExitPolicy(budgets={"undeclared-dependency": 2})
+
+    assert policy.exit_code([undeclared, undeclared]) == EXIT_SUCCESS
+    assert policy.exit_code([undeclared, undeclared, undeclared]) == EXIT_FAILURE
+    # Codes without a budget do not allow any violations
+    assert policy.exit_code([private]) == EXIT_FAILURE
+    assert policy.violation_counts(
+        [undeclared, undeclared, private, make_coded_diagnostic("x", is_error=False)]
+    ) == {"undeclared-dependency": 2, "private-import": 1}
@@ -0,0 +1,94 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.explain import format_source, render_explanation_json, render_explanation_text
+
+
+def source(file_path: str, line_number: int | None = None):
+    return SimpleNamespace(file_path=Path(file_path), line_number=line_number)
+
+
+def explanation(**kwargs):
+    module = SimpleNamespace(
+        path="billing.invoices",
+        depends_on=[
+            SimpleNamespace(path="core", deprecated=False),
+            SimpleNamespace(path="orders", deprecated=True),
+        ],
+        layer="domain",
+        visibility=["*"],
+        utility=False,
+        unrestricted_layer=False,
+        unchecked=False,
+        owner=None,
+    )
+    values = dict(
+        module=module,
+        source=source("billing/tach.domain.toml", 4),
+        domain="billing",
+        domain_source=source("billing/tach.domain.toml"),
+        layers=["interface", "domain"],
+        layers_source=source("tach.toml", 2),
+        interfaces=[
+            SimpleNamespace(
+                expose=["api.*"], data_types="all", source=source("tach.toml", 20)
+            )
+        ],
+        suppressions=[],
+    )
+    values.update(kwargs)
+    return SimpleNamespace(**values)
+
+
+def test_format_source():
+    assert format_source(source("tach.toml", 3)) == "tach.toml:3"
+    assert format_source(source("tach.toml")) == "tach.toml"
+    assert format_source(None) == "(not declared in a config file)"
+
+
+def test_render_explanation_text():
+    text = render_explanation_text(
+        explanation(
+            suppressions=[
+                SimpleNamespace(
+                    description="'tach-ignore' comment (legacy)",
+                    source=source("billing/invoices/pdf.py", 7),
+                )
+            ]
+        )
+    )
+
+    assert "Module 'billing.invoices'" in text
+    assert "billing/tach.domain.toml:4" in text
+    assert "depends_on: core, orders (deprecated)" in text
+    assert "domain of interface > domain" in text
+    assert "tach.toml:2" in text
+    assert "api.*" in text and "tach.toml:20" in text
+    assert "'tach-ignore' comment (legacy)" in text
+    assert "billing/invoices/pdf.py:7" in text
+
+
+def test_render_explanation_without_config():
+    text = render_explanation_text(
+        explanation(domain=None, interfaces=[], suppressions=[])
+    )
+
+    assert "(none, so every member may be imported)" in text
+    assert text.count("(none)") == 2
+
+
+def test_render_explanation_json():
+    result = json.loads(render_explanation_json(explanation()))
+
+    assert result["module"]["source"] == {
+        "file_path": "billing/tach.domain.toml",
+        "line_number": 4,
+    }
+    assert result["module"]["depends_on"][1] == {"path": "orders", "deprecated": True}
+    assert result["domain"]["path"] == "billing"
+    assert result["layers"]["layers"] == ["interface", "domain"]
+    assert result["interfaces"][0]["source"]["line_number"] == 20
+    assert result["suppressions"] == []
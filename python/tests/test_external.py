@@ -0,0 +1,74 @@
+from __future__ import annotations
+
+from pathlib import Path
+from unittest.mock import Mock
+
+from tach.utils.external import (
+    EditableModule,
+    _get_finder_modules,
+    _get_pth_modules,
+    get_project_distributions,
+    with_editable_mappings,
+    with_stub_package_mappings,
+)
+
+
+def test_stub_package_mappings():
+    module_mappings = {
+        "requests": ["requests"],
+        "requests-stubs": ["types-requests"],
+        "django-stubs": ["django-stubs"],
+    }
+    assert with_stub_package_mappings(module_mappings) == {
+        "requests": ["requests", "types-requests"],
+        "requests-stubs": ["types-requests"],
+        "django": ["django-stubs"],
+        "django-stubs": ["django-stubs"],
+    }
+    # The original mappings are left untouched
+    assert module_mappings["requests"] == ["requests"]
+
+
+def test_editable_pth_and_finder_modules(tmp_path: Path):
+    site_packages = tmp_path / "site-packages"
+    site_packages.mkdir()
+    source = tmp_path / "libs" / "utils" / "src"
+    (source / "utils").mkdir(parents=True)
+    (source / "utils" / "__init__.py").touch()
+    (source / "setup.py").touch()
+    (source / "helpers.py").touch()
+
+    pth = site_packages / "__editable__.utils-0.1.pth"
+    pth.write_text(f"{source}\nimport os\n")
+    assert _get_pth_modules(pth) == {
+        "helpers": (source / "helpers.py").resolve(),
+        "utils": (source / "utils").resolve(),
+    }
+
+    finder = site_packages / "__editable___billing_0_1_finder.py"
+    finder.write_text(
+        "import sys\n"
+        f"MAPPING: dict[str, str] = {{'billing': '{tmp_path / 'billing'}'}}\n"
+        "NAMESPACES: dict[str, list[str]] = {}\n"
+    )
+    assert _get_finder_modules(finder) == {"billing": (tmp_path / "billing").resolve()}
+
+
+def test_editable_mappings_and_project_distributions(tmp_path: Path, mocker):
+    editable_modules = [
+        EditableModule("utils", "my-utils", (tmp_path / "libs" / "utils").resolve()),
+        EditableModule("billing", "billing", (tmp_path.parent / "billing").resolve()),
+    ]
+    mocker.patch(
+        "tach.utils.external.get_editable_modules",
+        Mock(return_value=editable_modules),
+    )
+
+    assert with_editable_mappings({"git": ["gitpython"]}, editable_modules) == {
+        "git": ["gitpython"],
+        "utils": ["my-utils"],
+        "billing": ["billing"],
+    }
+    # Only 'utils' has its source under a source root, so 'billing' stays external
+    assert get_project_distributions(tmp_path, [Path("libs")]) == ["my-utils"]
+    assert get_project_distributions(tmp_path, [Path("src")]) == []
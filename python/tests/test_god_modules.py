@@ -0,0 +1,91 @@
+from __future__ import annotations
+
+import json
+from types import SimpleNamespace
+
+import pytest
+
+from tach.god_modules import (
+    find_god_modules,
+    percentile,
+    render_god_modules_csv,
+    render_god_modules_json,
+    render_god_modules_text,
+)
+
+
+def god_module_config(rank: int = 90, min_edges: int = 2) -> SimpleNamespace:
+    return SimpleNamespace(
+        fan_in_percentile=rank,
+        fan_out_percentile=rank,
+        min_fan_in=min_edges,
+        min_fan_out=min_edges,
+    )
+
+
+def edge(source: str, target: str, weight: int = 1) -> SimpleNamespace:
+    return SimpleNamespace(source=source, target=target, weight=weight)
+
+
+# 'core' is imported by three modules, and imports two others
+EDGES = [
+    edge("api", "core", 6),
+    edge("billing", "core", 3),
+    edge("jobs", "core", 1),
+    edge("core", "db", 2),
+    edge("core", "utils", 2),
+    edge("api", "utils"),
+]
+MODULES = ["api", "billing", "core", "db", "jobs", "utils"]
+
+
+@pytest.mark.parametrize(
+    "values,rank,expected",
+    [
+        ([], 90, 0),
+        ([1, 2, 3, 4], 50, 2),
+        ([4, 3, 2, 1], 90, 4),
+        ([5, 1], 0, 1),
+        ([5, 1], 150, 5),
+    ],
+)
+def test_percentile(values: list[int], rank: int, expected: int):
+    assert percentile(values, rank) == expected
+
+
+def test_find_god_modules():
+    god_modules, thresholds = find_god_modules(MODULES, EDGES, god_module_config())
+
+    assert (thresholds.fan_in, thresholds.fan_out) == (3, 2)
+    [core] = god_modules
+    assert (core.module, core.fan_in, core.fan_out) == ("core", 3, 2)
+    assert core.incoming == [("api", 6), ("billing", 3), ("jobs", 1)]
+    assert core.outgoing == [("db", 2), ("utils", 2)]
+    assert core.incoming_imports == 10
+
+
+def test_min_thresholds_apply():
+    god_modules, thresholds = find_god_modules(
+        MODULES, EDGES, god_module_config(min_edges=4)
+    )
+
+    assert god_modules == []
+    assert (thresholds.fan_in, thresholds.fan_out) == (4, 4)
+    assert "No god modules found" in render_god_modules_text(god_modules, thresholds)
+
+
+def test_render_god_modules():
+    god_modules, thresholds = find_god_modules(MODULES, EDGES, god_module_config())
+
+    text = render_god_modules_text(god_modules, thresholds)
+    assert "'core' is a god module" in text
+    assert "Incoming (10 imports): 'api' 6 (60%), 'billing' 3 (30%)" in text
+
+    result = json.loads(render_god_modules_json(god_modules, thresholds))
+    assert result["thresholds"] == {"fan_in": 3, "fan_out": 2}
+    assert result["god_modules"][0]["outgoing"] == [
+        {"module": "db", "imports": 2},
+        {"module": "utils", "imports": 2},
+    ]
+
+    assert render_god_modules_csv(god_modules).splitlines()[1] == "core,3,2,10,4"
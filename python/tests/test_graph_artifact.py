@@ -0,0 +1,88 @@
+from __future__ import annotations
+
+import json
+import os
+from pathlib import Path
+
+import pytest
+
+from tach.errors import TachError
+from tach.extension import GraphArtifact, explain_dependency, get_dependency_graph
+from tach.graph_artifact import build_graph_artifact, read_graph_artifact
+from tach.parsing.config import parse_project_config
+from tach.report import report
+
+
+def test_read_graph_artifact_errors(tmp_path: Path):
+    with pytest.raises(TachError):
+        read_graph_artifact(tmp_path / "missing.json")
+    path = tmp_path / "tach-graph.json"
+    path.write_text("not json")
+    with pytest.raises(TachError):
+        read_graph_artifact(path)
+    path.write_text(json.dumps({"version": 99}))
+    with pytest.raises(TachError, match="version 99"):
+        read_graph_artifact(path)
+
+
+@pytest.fixture
+def example_valid_dir(example_dir):
+    original_cwd = os.getcwd()
+    os.chdir(example_dir / "valid")
+    yield example_dir / "valid"
+    os.chdir(original_cwd)
+
+
+def hop_summary(hops):
+    return [
+        (
+            hop.source,
+            hop.target,
+            hop.declared,
+            [(str(i.file_path), i.line_number, i.import_path) for i in hop.imports],
+        )
+        for hop in hops
+    ]
+
+
+def test_graph_artifact_matches_live_analysis(example_valid_dir):
+    project_root = example_valid_dir
+    project_config = parse_project_config(project_root)
+    exclude_paths = [
+        *project_config.exclude,
+        *project_config.source_root_exclude_paths(),
+    ]
+    artifact = GraphArtifact.deserialize_json(
+        build_graph_artifact(
+            project_root, project_config, exclude_paths
+        ).serialize_json()
+    )
+
+    for path in ["domain_one", "domain_two", "domain_three"]:
+        for raw in [False, True]:
+            live = report(
+                project_root,
+                Path(path),
+                project_config,
+                raw=raw,
+                exclude_paths=exclude_paths,
+            )
+            assert live == report(
+                project_root,
+                Path(path),
+                project_config,
+                raw=raw,
+                graph_artifact=artifact,
+            )
+
+    for source, target in [("domain_one", "domain_two"), ("<root>", "domain_three")]:
+        live_hops = explain_dependency(
+            project_root, project_config, exclude_paths, source, target
+        )
+        assert live_hops
+        assert hop_summary(live_hops) == hop_summary(
+            artifact.explain_dependency(project_config, source, target)
+        )
+
+    live_graph = get_dependency_graph(project_root, project_config, exclude_paths)
+    assert live_graph.to_networkx_dict() == artifact.graph.to_networkx_dict()
@@ -0,0 +1,97 @@
+from __future__ import annotations
+
+import ast
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.graph_formats import (
+    module_sources,
+    pydeps_dict,
+    render_pydeps,
+    render_snakefood,
+)
+
+
+def make_project(root: Path) -> SimpleNamespace:
+    for package in ["src/myapp/api", "src/myapp/core"]:
+        (root / package).mkdir(parents=True, exist_ok=True)
+        (root / package / "__init__.py").write_text("")
+    (root / "src/myapp/cli.py").write_text("")
+    return SimpleNamespace(source_roots=["src"])
+
+
+def make_graph(modules: list[str], edges: list[tuple[str, str]]) -> SimpleNamespace:
+    return SimpleNamespace(
+        nodes=[SimpleNamespace(path=module) for module in modules],
+        edges=[
+            SimpleNamespace(source=source, target=target) for source, target in edges
+        ],
+    )
+
+
+MODULES = ["myapp.api", "myapp.cli", "myapp.core"]
+EDGES = [
+    ("myapp.cli", "myapp.api"),
+    ("myapp.api", "myapp.core"),
+    ("myapp.cli", "myapp.core"),
+    ("myapp.core", "myapp.core"),
+]
+
+
+def test_module_sources(tmp_path: Path):
+    project_config = make_project(tmp_path)
+
+    sources = module_sources(tmp_path, project_config, MODULES + ["missing"])
+
+    source_root = (tmp_path / "src").resolve()
+    assert sources == {
+        "myapp.api": (source_root, source_root / "myapp/api/__init__.py"),
+        "myapp.cli": (source_root, source_root / "myapp/cli.py"),
+        "myapp.core": (source_root, source_root / "myapp/core/__init__.py"),
+    }
+
+
+def test_pydeps(tmp_path: Path):
+    project_config = make_project(tmp_path)
+    sources = module_sources(tmp_path, project_config, MODULES)
+
+    deps = pydeps_dict(make_graph(MODULES, EDGES), sources)
+
+    assert deps["myapp.cli"] == {
+        "bacon": 1,
+        "imported_by": [],
+        "imports": ["myapp.api", "myapp.core"],
+        "name": "myapp.cli",
+        "path": str(sources["myapp.cli"][1]),
+    }
+    assert deps["myapp.api"]["bacon"] == 2
+    assert deps["myapp.core"]["imported_by"] == ["myapp.api", "myapp.cli"]
+    assert deps["myapp.core"]["imports"] == []
+    assert json.loads(render_pydeps(make_graph(MODULES, EDGES), sources)) == deps
+
+
+def test_pydeps_cycle():
+    graph = make_graph(["a", "b"], [("a", "b"), ("b", "a")])
+
+    deps = pydeps_dict(graph, {})
+
+    assert deps["a"]["bacon"] == 0
+    assert deps["a"]["path"] is None
+
+
+def test_snakefood(tmp_path: Path):
+    project_config = make_project(tmp_path)
+    sources = module_sources(tmp_path, project_config, MODULES)
+    root = str((tmp_path / "src").resolve())
+
+    lines = render_snakefood(make_graph(MODULES, EDGES), sources).splitlines()
+
+    assert [ast.literal_eval(line) for line in lines] == [
+        ((root, "myapp/api/__init__.py"), (None, None)),
+        ((root, "myapp/api/__init__.py"), (root, "myapp/core/__init__.py")),
+        ((root, "myapp/cli.py"), (None, None)),
+        ((root, "myapp/cli.py"), (root, "myapp/api/__init__.py")),
+        ((root, "myapp/cli.py"), (root, "myapp/core/__init__.py")),
+        ((root, "myapp/core/__init__.py"), (None, None)),
+    ]
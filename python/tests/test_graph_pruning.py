@@ -0,0 +1,41 @@
+from __future__ import annotations
+
+from tach.graph_pruning import GraphPruning
+
+MODULES = ["api", "billing", "core", "logging", "<root>"]
+EDGES = [
+    ("api", "core"),
+    ("api", "logging"),
+    ("billing", "core"),
+    ("billing", "logging"),
+    ("core", "logging"),
+    ("core", "core"),
+    ("<root>", "api"),
+]
+
+
+def test_empty_pruning():
+    assert GraphPruning().is_empty()
+    assert GraphPruning().pruned_modules(MODULES, EDGES, ["logging"]) == set()
+
+
+def test_prune_utilities_and_root():
+    pruning = GraphPruning(utilities=True, root=True)
+
+    assert pruning.pruned_modules(MODULES, EDGES, ["logging"]) == {"logging", "<root>"}
+
+
+def test_prune_min_fan_in():
+    pruning = GraphPruning(min_fan_in=2)
+
+    # Self-dependencies do not count towards fan-in
+    assert pruning.pruned_modules(MODULES, EDGES, []) == {"api", "billing", "<root>"}
+
+
+def test_prune_edges():
+    pruning = GraphPruning(utilities=True, min_fan_in=1)
+
+    modules, edges = pruning.prune_edges(MODULES, EDGES, ["logging"])
+
+    assert modules == ["api", "core"]
+    assert edges == [("api", "core"), ("core", "core")]
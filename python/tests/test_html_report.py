@@ -0,0 +1,79 @@
+from __future__ import annotations
+
+from unittest.mock import Mock
+
+from tach.html_report import (
+    build_module_summaries,
+    module_page_path,
+    render_report_site,
+)
+
+
+def make_diagnostic(code: str, usage_module: str | None, is_error: bool = True) -> Mock:
+    diagnostic = Mock()
+    diagnostic.code.return_value = code
+    diagnostic.usage_module.return_value = usage_module
+    diagnostic.is_error.return_value = is_error
+    diagnostic.is_warning.return_value = not is_error
+    diagnostic.message.return_value = "Cannot use 'b.<internal>'."
+    diagnostic.pyfile_path.return_value = "src/a/x.py"
+    diagnostic.pyline_number.return_value = 3
+    return diagnostic
+
+
+MODULE_DEPENDENCIES = {"a": ["b", "c"], "b": ["c"], "c": [], "<root>": ["a"]}
+
+
+def test_build_module_summaries():
+    diagnostics = [
+        make_diagnostic("undeclared-dependency", "a"),
+        make_diagnostic("unused-ignore-directive", "a", is_error=False),
+        make_diagnostic("unused-ignore-directive", None, is_error=False),
+    ]
+
+    summaries = {
+        summary.path: summary
+        for summary in build_module_summaries(diagnostics, MODULE_DEPENDENCIES)
+    }
+
+    assert summaries["c"].dependents == ["a", "b"]
+    assert summaries["c"].fan_in == 2
+    assert summaries["c"].instability == 0.0
+    assert summaries["a"].fan_out == 2
+    assert summaries["a"].error_count == 1
+    assert summaries["a"].warning_count == 1
+    assert summaries["b"].diagnostics == []
+
+
+def test_module_page_path_is_a_safe_file_name():
+    assert module_page_path("a.b") == "modules/a.b.html"
+    assert module_page_path("<root>") == "modules/_root_.html"
+
+
+def test_render_report_site():
+    diagnostics = [make_diagnostic("undeclared-dependency", "a")]
+    summaries = build_module_summaries(diagnostics, MODULE_DEPENDENCIES)
+
+    pages = render_report_site(
+        title="Report",
+        diagnostics=diagnostics,
+        summaries=summaries,
+        mermaid_graph="graph TD\n    a --> b",
+    )
+
+    assert set(pages) == {
+        "index.html",
+        "style.css",
+        "modules/a.html",
+        "modules/b.html",
+        "modules/c.html",
+        "modules/_root_.html",
+    }
+    index = pages["index.html"]
+    assert 'id="violations"' in index
+    assert '<option value="undeclared-dependency">' in index
+    assert "Cannot use &#x27;b.&lt;internal&gt;&#x27;." in index
+    assert "a --&gt; b" in index
+    module_page = pages["modules/c.html"]
+    assert '<a href="../modules/a.html">a</a>' in module_page
+    assert '<a href="../modules/b.html">b</a>' in module_page
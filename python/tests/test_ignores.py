@@ -0,0 +1,80 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.ignores import render_ignores_json, render_ignores_text
+
+
+def violation(code: str, severity: str, dependency=None, target_module=None):
+    return SimpleNamespace(
+        code=code,
+        # Extension enums are displayed with their class name
+        severity=f"Severity.{severity}",
+        dependency=dependency,
+        target_module=target_module,
+    )
+
+
+def usage(line_number: int, suppressed, reason="", modules=None, redundant=False):
+    return SimpleNamespace(
+        file_path=Path("src/api/views.py"),
+        line_number=line_number,
+        reason=reason,
+        modules=modules or [],
+        redundant=redundant,
+        suppressed=suppressed,
+    )
+
+
+def test_render_ignores_text():
+    usages = [
+        usage(
+            3,
+            [
+                violation("undeclared-dependency", "Error", "core.models.User", "core"),
+                violation("deprecated-dependency", "Warning", "core.models", "core"),
+            ],
+            reason="legacy",
+            modules=["core.models"],
+        ),
+        usage(7, []),
+        usage(9, [], redundant=True),
+    ]
+
+    text = render_ignores_text(usages)
+
+    assert "src/api/views.py:3" in text
+    assert "tach-ignore(legacy) core.models" in text
+    assert "suppressed error [undeclared-dependency] core.models.User -> core" in text
+    assert "suppressed warning [deprecated-dependency] core.models -> core" in text
+    assert "stale, because it suppressed nothing" in text
+    assert "stale, because another directive applies to the same line" in text
+    assert "3 directive(s) suppressed 1 error(s) and 1 warning(s); 2 stale." in text
+
+
+def test_render_no_ignores():
+    assert render_ignores_text([]) == "No ignore directives found."
+
+
+def test_render_ignores_json():
+    usages = [
+        usage(3, [violation("layer-violation", "Error", "core.models", "core")]),
+        usage(7, []),
+    ]
+
+    result = json.loads(render_ignores_json(usages))
+
+    assert result[0]["file_path"] == "src/api/views.py"
+    assert result[0]["stale"] is False
+    assert result[0]["suppressed"] == [
+        {
+            "code": "layer-violation",
+            "severity": "error",
+            "dependency": "core.models",
+            "target_module": "core",
+        }
+    ]
+    assert result[1]["stale"] is True
+    assert result[1]["suppressed"] == []
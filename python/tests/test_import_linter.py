@@ -0,0 +1,153 @@
+from __future__ import annotations
+
+import configparser
+from pathlib import Path
+from types import SimpleNamespace
+
+import tomli
+
+from tach.import_linter import (
+    contracts_to_tach,
+    find_import_linter_config,
+    read_import_linter_config,
+    tach_to_contracts,
+)
+
+IMPORTLINTER = """
+[importlinter]
+root_packages =
+    myapp
+
+[importlinter:contract:layers]
+name = Layered architecture
+type = layers
+layers =
+    myapp.api
+    myapp.billing | myapp.orders
+    myapp.core
+
+[importlinter:contract:no-legacy]
+name = No legacy
+type = forbidden
+source_modules =
+    myapp.api
+forbidden_modules =
+    myapp.legacy
+    requests
+allow_indirect_imports = true
+ignore_imports =
+    myapp.api.old -> myapp.legacy
+
+[importlinter:contract:plugins]
+name = Independent plugins
+type = independence
+modules =
+    myapp.plugins.a
+    myapp.plugins.b
+"""
+
+
+def test_read_ini_config(tmp_path: Path):
+    path = tmp_path / ".importlinter"
+    path.write_text(IMPORTLINTER)
+
+    config = read_import_linter_config(path)
+
+    assert config.root_packages == ["myapp"]
+    assert [contract.type for contract in config.contracts] == [
+        "layers",
+        "forbidden",
+        "independence",
+    ]
+    assert config.contracts[1].values("source_modules") == ["myapp.api"]
+    assert config.contracts[1].flag("allow_indirect_imports")
+
+
+def test_read_pyproject_config(tmp_path: Path):
+    (tmp_path / "pyproject.toml").write_text(
+        """
+[tool.importlinter]
+root_package = "myapp"
+
+[[tool.importlinter.contracts]]
+name = "Core is independent"
+type = "independence"
+modules = ["myapp.a", "myapp.b"]
+"""
+    )
+
+    path = find_import_linter_config(tmp_path)
+    assert path == tmp_path / "pyproject.toml"
+    config = read_import_linter_config(path)
+    assert config.root_packages == ["myapp"]
+    assert config.contracts[0].name == "Core is independent"
+    assert config.contracts[0].values("modules") == ["myapp.a", "myapp.b"]
+
+
+def test_contracts_to_tach(tmp_path: Path):
+    path = tmp_path / ".importlinter"
+    path.write_text(IMPORTLINTER)
+
+    conversion = contracts_to_tach(read_import_linter_config(path), ["src"])
+    config = tomli.loads(conversion.content)
+
+    assert config["source_roots"] == ["src"]
+    assert config["layers"] == ["api", "billing_orders", "core"]
+    modules = {module["path"]: module for module in config["modules"]}
+    assert modules["myapp.core"] == {"path": "myapp.core", "layer": "core"}
+    # Independent modules in the same layer may not import each other
+    assert "myapp.orders" not in modules["myapp.billing"]["depends_on"]
+    assert "myapp.billing" not in modules["myapp.orders"]["depends_on"]
+    assert "myapp.legacy" not in modules["myapp.api"]["depends_on"]
+    assert "myapp.core" in modules["myapp.api"]["depends_on"]
+    assert "myapp.plugins.b" not in modules["myapp.plugins.a"]["depends_on"]
+    assert "myapp.plugins.a" not in modules["myapp.plugins.b"]["depends_on"]
+
+    assert len(conversion.warnings) == 2
+    assert "'requests' is not in a root package" in conversion.warnings[0]
+    assert "'ignore_imports' is not converted" in conversion.warnings[1]
+
+
+def module(path, depends_on=(), layer=None, utility=False):
+    return SimpleNamespace(
+        path=path,
+        depends_on=None
+        if depends_on is None
+        else [SimpleNamespace(path=dependency) for dependency in depends_on],
+        layer=layer,
+        utility=utility,
+        unchecked=False,
+        visibility=["*"],
+    )
+
+
+def test_tach_to_contracts():
+    layers = ["api", "core"]
+    project_config = SimpleNamespace(
+        layers=layers,
+        all_modules=lambda: [
+            module("myapp.api", depends_on=["myapp.core"], layer="api"),
+            module("myapp.core", layer="core"),
+            module("myapp.utils", utility=True),
+            module("myapp.scripts", depends_on=None),
+        ],
+        all_interfaces=lambda: [],
+        layers_for_module=lambda _: layers,
+    )
+
+    conversion = tach_to_contracts(project_config)
+    parser = configparser.ConfigParser(interpolation=None)
+    parser.read_string(conversion.content)
+
+    assert parser["importlinter"]["root_packages"].split() == ["myapp"]
+    assert parser["importlinter:contract:layers"]["layers"].split() == [
+        "myapp.api",
+        "myapp.core",
+    ]
+    api = parser["importlinter:contract:myapp-api-dependencies"]
+    assert api["type"] == "forbidden"
+    assert api["forbidden_modules"].split() == ["myapp.scripts"]
+    core = parser["importlinter:contract:myapp-core-dependencies"]
+    assert core["forbidden_modules"].split() == ["myapp.api", "myapp.scripts"]
+    assert not parser.has_section("importlinter:contract:myapp-scripts-dependencies")
+    assert conversion.warnings == []
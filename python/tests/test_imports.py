@@ -28,7 +28,7 @@ def temp_project():
     with tempfile.TemporaryDirectory() as project_root:
         project_root = Path(project_root)
         # This is tech debt!!
-        set_excluded_paths(
+        excluded_paths = set_excluded_paths(
             str(project_root), DEFAULT_EXCLUDE_PATHS, use_regex_matching=True
         )
 
@@ -82,7 +82,8 @@ import file3
         create_temp_file(project_root, "file3.py", file3_content)
         create_temp_file(project_root, "file4.py", file4_content)
 
-        yield project_root
+        with excluded_paths:
+            yield project_root
 
 
 def test_regular_imports(temp_project):
@@ -0,0 +1,108 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+import tomli
+
+from tach.init import (
+    InitProposal,
+    ask_yes_no,
+    detect_source_roots,
+    edit_proposal_interactive,
+    propose_configuration,
+)
+
+
+def make_packages(root: Path, *packages: str):
+    for package in packages:
+        (root / package).mkdir(parents=True, exist_ok=True)
+        (root / package / "__init__.py").write_text("")
+
+
+def answers(*responses: str):
+    remaining = list(responses)
+    return lambda _: remaining.pop(0)
+
+
+def test_detect_src_layout(tmp_path: Path):
+    make_packages(tmp_path, "src/myapp", "tests")
+
+    assert detect_source_roots(tmp_path) == [tmp_path / "src"]
+
+
+def test_detect_monorepo(tmp_path: Path):
+    make_packages(tmp_path, "billing/src/billing", "orders/orders", "docs/api")
+
+    assert detect_source_roots(tmp_path) == [
+        tmp_path / "billing" / "src",
+        tmp_path / "orders",
+    ]
+
+
+def test_propose_subpackages_of_single_package(tmp_path: Path):
+    make_packages(
+        tmp_path, "src/myapp", "src/myapp/api", "src/myapp/core", "src/myapp/utils"
+    )
+
+    proposal = propose_configuration(tmp_path)
+
+    assert proposal.source_roots == ["src"]
+    assert proposal.modules == ["myapp.api", "myapp.core", "myapp.utils"]
+    assert proposal.utilities == ["myapp.utils"]
+    assert proposal.module_layers == {"myapp.api": "interface", "myapp.core": "domain"}
+    assert proposal.layers == ["interface", "domain"]
+
+
+def test_propose_top_level_packages(tmp_path: Path):
+    make_packages(tmp_path, "billing", "orders", "tests")
+
+    proposal = propose_configuration(tmp_path)
+
+    assert proposal.source_roots == ["."]
+    assert proposal.modules == ["billing", "orders"]
+    assert proposal.layers == []
+
+
+def test_to_toml():
+    proposal = InitProposal(
+        source_roots=["src"],
+        modules=["api", "core", "utils"],
+        utilities=["utils"],
+        module_layers={"api": "interface", "core": "domain"},
+    )
+
+    assert tomli.loads(proposal.to_toml()) == {
+        "source_roots": ["src"],
+        "layers": ["interface", "domain"],
+        "modules": [
+            {"path": "api", "depends_on": [], "layer": "interface"},
+            {"path": "core", "depends_on": [], "layer": "domain"},
+            {"path": "utils", "depends_on": [], "utility": True},
+        ],
+    }
+
+
+def test_edit_proposal_interactive():
+    proposal = InitProposal(
+        source_roots=["src"],
+        modules=["api", "core", "utils"],
+        utilities=["utils"],
+        module_layers={"api": "interface", "core": "domain"},
+    )
+
+    # Accept the source roots, drop 'api', accept the utilities, reject the layers
+    edited = edit_proposal_interactive(
+        proposal, ask=answers("", "core, utils", "", "n")
+    )
+
+    assert edited.source_roots == ["src"]
+    assert edited.modules == ["core", "utils"]
+    assert edited.utilities == ["utils"]
+    assert edited.module_layers == {}
+
+
+def test_ask_yes_no():
+    assert ask_yes_no("Continue?", ask=answers(""))
+    assert not ask_yes_no("Continue?", default=False, ask=answers(""))
+    assert ask_yes_no("Continue?", default=False, ask=answers("Y"))
+    assert not ask_yes_no("Continue?", ask=answers("no"))
@@ -0,0 +1,88 @@
+from __future__ import annotations
+
+import json
+from types import SimpleNamespace
+
+from tach.lint import (
+    LintIssue,
+    find_narrow_utilities,
+    find_sparse_layers,
+    find_unused_interfaces,
+    render_lint_json,
+    render_lint_text,
+)
+
+
+def usage(module_path: str, full_path: str, containing_module_path: str | None):
+    return SimpleNamespace(
+        module_path=module_path,
+        full_path=full_path,
+        containing_module_path=containing_module_path,
+    )
+
+
+USAGES = [
+    usage("core", "core.api.get_user", "billing"),
+    usage("core", "core.models.User", "orders"),
+    usage("utils", "utils.slugify", "billing"),
+    usage("utils", "utils.slugify", "orders"),
+    usage("dates", "dates.parse", "billing"),
+    usage("dates", "dates.parse", None),
+]
+
+
+def test_find_unused_interfaces():
+    interfaces = [
+        SimpleNamespace(expose=["api"], from_modules=["core"]),
+        SimpleNamespace(expose=["internal.*"], from_modules=["core"]),
+        SimpleNamespace(expose=[".*"], from_modules=["legacy.*"]),
+    ]
+
+    issues = find_unused_interfaces(interfaces, ["core", "utils"], USAGES)
+
+    assert [(issue.code, issue.subject, issue.message) for issue in issues] == [
+        (
+            "unused-interface",
+            "expose [internal.*] from [core]",
+            "No other module imports any member of the interface.",
+        ),
+        (
+            "unused-interface",
+            "expose [.*] from [legacy.*]",
+            "The interface does not match any module.",
+        ),
+    ]
+
+
+def test_find_narrow_utilities():
+    issues = find_narrow_utilities(["utils", "dates", "unused"], USAGES)
+
+    assert [issue.subject for issue in issues] == ["dates", "unused"]
+    assert "only imported by 1 module(s)" in issues[0].message
+    assert find_narrow_utilities(["dates"], USAGES, min_consumers=1) == []
+
+
+def test_find_sparse_layers():
+    modules = [
+        SimpleNamespace(path="api", layer="ui"),
+        SimpleNamespace(path="web", layer="ui"),
+        SimpleNamespace(path="core", layer="domain"),
+        SimpleNamespace(path="utils", layer=None),
+    ]
+
+    issues = find_sparse_layers(["ui", "domain", "data"], modules)
+
+    assert [(issue.subject, issue.message) for issue in issues] == [
+        ("domain", "The layer only contains the module 'core'."),
+        ("data", "The layer does not contain any modules."),
+    ]
+
+
+def test_render_lint():
+    issues = [LintIssue(code="sparse-layer", subject="data", message="Empty.")]
+
+    assert "[sparse-layer] data:" in render_lint_text(issues)
+    assert json.loads(render_lint_json(issues)) == [
+        {"code": "sparse-layer", "subject": "data", "message": "Empty."}
+    ]
+    assert "No issues found" in render_lint_text([])
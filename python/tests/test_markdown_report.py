@@ -0,0 +1,81 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tach.markdown_report import (
+    MAX_LISTED_ITEMS,
+    ProjectSnapshot,
+    PullRequestReport,
+    Violation,
+    compare_snapshots,
+    render_markdown_report,
+)
+
+
+def make_violation(
+    module: str, line_number: int = 1, message: str = "Cannot use 'b'."
+) -> Violation:
+    return Violation(
+        code="undeclared-dependency",
+        module=module,
+        file_path=f"src/{module}/x.py",
+        line_number=line_number,
+        message=message,
+        is_error=True,
+        fingerprint=f"{module}:{message}",
+    )
+
+
+def test_compare_snapshots():
+    base = ProjectSnapshot(
+        violations=[make_violation("a"), make_violation("c")],
+        module_dependencies={"a": ["b"], "b": [], "c": []},
+    )
+    head = ProjectSnapshot(
+        violations=[
+            # Moved by an unrelated edit
+            make_violation("a", line_number=10),
+            make_violation("a", line_number=20),
+        ],
+        module_dependencies={"a": ["b", "d"], "b": [], "d": []},
+    )
+
+    report = compare_snapshots(base, head, changed_modules={"b"})
+
+    assert report.new_violations == [make_violation("a", line_number=20)]
+    assert report.resolved_violation_count == 1
+    assert report.affected_modules == ["a", "b"]
+    assert report.added_dependencies == [("a", "d")]
+    assert report.removed_dependencies == []
+    assert report.added_modules == ["d"]
+    assert report.removed_modules == ["c"]
+
+
+def test_render_markdown_report():
+    report = PullRequestReport(
+        new_violations=[make_violation("a", message="Cannot use 'b|c'.")],
+        resolved_violation_count=2,
+        affected_modules=["a"],
+        removed_dependencies=[("a", "b")],
+    )
+
+    markdown = render_markdown_report(report, base="main")
+
+    assert "**1 new errors**, 0 new warnings, 2 resolved." in markdown
+    assert (
+        "| error | `undeclared-dependency` | `src/a/x.py:1` | Cannot use 'b\\|c'. |"
+        in markdown
+    )
+    assert "- `a`" in markdown
+    assert "- ~~`a` → `b`~~" in markdown
+
+
+def test_render_markdown_report_truncates_long_lists():
+    report = PullRequestReport(
+        affected_modules=[f"module_{i}" for i in range(MAX_LISTED_ITEMS + 5)]
+    )
+
+    markdown = render_markdown_report(report, base="main")
+
+    assert "- ... and 5 more" in markdown
+    assert "No changes." in markdown
@@ -0,0 +1,167 @@
+from __future__ import annotations
+
+import ast
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tach.metrics import (
+    ModuleMetrics,
+    compute_metrics,
+    count_classes,
+    is_abstract_class,
+    render_metrics_csv,
+    render_metrics_json,
+    render_metrics_table,
+)
+
+
+def parse_class(source: str) -> ast.ClassDef:
+    node = ast.parse(source).body[0]
+    assert isinstance(node, ast.ClassDef)
+    return node
+
+
+@pytest.mark.parametrize(
+    "source,expected",
+    [
+        (
+            "class A(ABC):\n"
+            "    @abstractmethod\n"
+            "    def run(self): ...\n"
+            "    @abc.abstractmethod\n"
+            "    def stop(self):\n"
+            "        return 1\n",
+            True,
+        ),
+        (
+            "class A(Protocol):\n"
+            "    def run(self) -> None:\n"
+            "        '''Run it.'''\n"
+            "    def stop(self):\n"
+            "        raise NotImplementedError\n",
+            True,
+        ),
+        ("class A:\n    def run(self):\n        pass\n", True),
+        ("class A(ABC):\n    def run(self):\n        return 1\n", False),
+        ("class A:\n    x: int = 1\n", False),
+    ],
+)
+def test_is_abstract_class(source: str, expected: bool):
+    assert is_abstract_class(parse_class(source)) is expected
+
+
+def test_count_classes():
+    source = (
+        "class Base:\n"
+        "    def run(self): ...\n"
+        "class Impl(Base):\n"
+        "    def run(self):\n"
+        "        class Helper: ...\n"
+        "        return Helper()\n"
+    )
+    assert count_classes(source) == (3, 1)
+
+
+def test_module_metrics():
+    metrics = ModuleMetrics(
+        module="a",
+        afferent_coupling=1,
+        efferent_coupling=3,
+        classes=4,
+        abstract_classes=1,
+    )
+    assert metrics.instability == 0.75
+    assert metrics.abstractness == 0.25
+    assert metrics.distance == 0.0
+    assert ModuleMetrics(module="b").distance == 1.0
+
+
+def test_compute_metrics(tmp_path: Path, mocker):
+    (tmp_path / "a").mkdir()
+    (tmp_path / "a" / "__init__.py").write_text(
+        "class Port:\n    def send(self): ...\n"
+    )
+    (tmp_path / "b.py").write_text(
+        "class Adapter:\n    def send(self):\n        return 1\n"
+    )
+    project_config = SimpleNamespace(
+        source_roots=["."],
+        use_regex_matching=False,
+        all_modules=lambda: [
+            SimpleNamespace(path="a", depends_on=[]),
+            SimpleNamespace(path="b", depends_on=[SimpleNamespace(path="a")]),
+        ],
+    )
+
+    mocker.patch(
+        "tach.metrics.get_dependency_graph",
+        return_value=SimpleNamespace(
+            nodes=[
+                SimpleNamespace(
+                    path="a", files=1, lines=2, public_symbols=1, imports=0
+                ),
+                SimpleNamespace(
+                    path="b", files=1, lines=3, public_symbols=1, imports=1
+                ),
+            ]
+        ),
+    )
+
+    metrics = compute_metrics(
+        tmp_path,
+        project_config,  # type: ignore
+        exclude_paths=[],
+    )
+
+    assert [module_metrics.to_dict() for module_metrics in metrics] == [
+        {
+            "module": "a",
+            "afferent_coupling": 1,
+            "efferent_coupling": 0,
+            "instability": 0.0,
+            "classes": 1,
+            "abstract_classes": 1,
+            "abstractness": 1.0,
+            "distance": 0.0,
+            "files": 1,
+            "lines": 2,
+            "public_symbols": 1,
+            "imports": 0,
+        },
+        {
+            "module": "b",
+            "afferent_coupling": 0,
+            "efferent_coupling": 1,
+            "instability": 1.0,
+            "classes": 1,
+            "abstract_classes": 0,
+            "abstractness": 0.0,
+            "distance": 0.0,
+            "files": 1,
+            "lines": 3,
+            "public_symbols": 1,
+            "imports": 1,
+        },
+    ]
+
+
+def test_render_metrics():
+    metrics = [
+        ModuleMetrics(
+            module="a.long_name", afferent_coupling=2, classes=1, files=2, lines=40
+        ),
+        ModuleMetrics(module="b", efferent_coupling=1),
+    ]
+
+    table = render_metrics_table(metrics).splitlines()
+    assert table[0].split() == (
+        "Module Ca Ce I Classes Abstract A D Files Lines Symbols Imports".split()
+    )
+    assert table[2].split() == "a.long_name 2 0 0.00 1 0 0.00 1.00 2 40 0 0".split()
+    assert json.loads(render_metrics_json(metrics))[1]["module"] == "b"
+    assert render_metrics_csv(metrics).splitlines()[2] == (
+        "b,0,1,1.0,0,0,0.0,0.0,0,0,0,0"
+    )
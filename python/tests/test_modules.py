@@ -0,0 +1,17 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tach.modules import module_for_file
+
+
+def test_module_for_file(tmp_path: Path):
+    def module_for(relative_path: str) -> str | None:
+        return module_for_file(
+            tmp_path / relative_path, [tmp_path / "src"], ["a", "a.b"]
+        )
+
+    assert module_for("src/a/b/x.py") == "a.b"
+    assert module_for("src/a/__init__.py") == "a"
+    assert module_for("src/c.py") is None
+    assert module_for("other/a.py") is None
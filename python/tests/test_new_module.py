@@ -0,0 +1,106 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+import pytest
+
+from tach.new_module import (
+    NewModuleOptions,
+    ask_choice,
+    module_path_in_domain,
+    prompt_new_module_options,
+    register_new_module,
+)
+
+
+def answers(*responses: str):
+    remaining = list(responses)
+    return lambda _: remaining.pop(0)
+
+
+class FakeProjectConfig(SimpleNamespace):
+    def __init__(self, domains=(), layers=()):
+        super().__init__(domains=list(domains), layers=list(layers), edits=[])
+
+    def domain_paths(self):
+        return self.domains
+
+    def layers_for_module(self, module_path):
+        return self.layers
+
+    def create_module(self, path):
+        self.edits.append(("create_module", path))
+
+    def set_module_layer(self, path, layer):
+        self.edits.append(("set_module_layer", path, layer))
+
+    def create_interface(self, path, expose):
+        self.edits.append(("create_interface", path, expose))
+
+    def save_edits(self):
+        self.edits.append(("save_edits",))
+
+
+def test_module_path_in_domain():
+    assert module_path_in_domain("invoices", "billing") == "billing.invoices"
+    assert module_path_in_domain("billing.invoices", "billing") == "billing.invoices"
+    assert module_path_in_domain("billing_v2", "billing") == "billing.billing_v2"
+
+
+def test_ask_choice():
+    choices = ["ui", "services", "core"]
+    assert ask_choice("Layer:", choices, ask=answers("2")) == "services"
+    assert ask_choice("Layer:", choices, ask=answers("nope", "core")) == "core"
+    assert ask_choice("Layer:", choices, ask=answers("")) is None
+
+
+def test_prompt_new_module_options():
+    project_config = FakeProjectConfig(domains=["billing"], layers=["ui", "core"])
+
+    options = prompt_new_module_options(
+        project_config,
+        NewModuleOptions(path="invoices"),
+        ask=answers("1", "core", "y", "get_invoice, Invoice"),
+    )
+
+    assert options == NewModuleOptions(
+        path="billing.invoices", layer="core", interface=["get_invoice", "Invoice"]
+    )
+
+
+def test_prompt_skips_options_given_as_flags():
+    project_config = FakeProjectConfig(domains=["billing"], layers=["ui", "core"])
+
+    options = prompt_new_module_options(
+        project_config,
+        NewModuleOptions(path="billing.invoices", layer="ui", interface=[]),
+        ask=answers(),
+    )
+
+    assert options == NewModuleOptions(
+        path="billing.invoices", layer="ui", interface=[]
+    )
+
+
+def test_register_new_module():
+    project_config = FakeProjectConfig(layers=["ui", "core"])
+
+    register_new_module(
+        project_config, NewModuleOptions(path="core", layer="core", interface=["api"])
+    )
+
+    assert project_config.edits == [
+        ("create_module", "core"),
+        ("set_module_layer", "core", "core"),
+        ("create_interface", "core", ["api"]),
+        ("save_edits",),
+    ]
+
+
+def test_register_new_module_unknown_layer():
+    project_config = FakeProjectConfig(layers=["ui", "core"])
+
+    with pytest.raises(ValueError, match="Unknown layer 'data'"):
+        register_new_module(project_config, NewModuleOptions(path="db", layer="data"))
+
+    assert project_config.edits == []
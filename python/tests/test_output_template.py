@@ -0,0 +1,63 @@
+from __future__ import annotations
+
+from unittest.mock import Mock
+
+import pytest
+
+from tach.errors import TachConfigError
+from tach.output_template import format_diagnostic, validate_output_template
+
+
+def make_diagnostic(
+    path: str | None, line: int | None, column: int | None, is_error: bool = True
+) -> Mock:
+    diagnostic = Mock()
+    diagnostic.pyfile_path.return_value = path
+    diagnostic.pyline_number.return_value = line
+    diagnostic.span.return_value = None if column is None else Mock(column=column)
+    diagnostic.code.return_value = "undeclared-dependency"
+    diagnostic.is_error.return_value = is_error
+    diagnostic.message.return_value = "Cannot use 'b'."
+    diagnostic.usage_module.return_value = "a"
+    return diagnostic
+
+
+def test_format_located_diagnostic():
+    diagnostic = make_diagnostic("src/a/x.py", 3, 8)
+
+    assert (
+        format_diagnostic("{path}:{line}:{column}: {code} {message}", diagnostic)
+        == "src/a/x.py:3:8: undeclared-dependency Cannot use 'b'."
+    )
+    assert (
+        format_diagnostic("{severity}[{module}] {path}", diagnostic)
+        == "error[a] src/a/x.py"
+    )
+
+
+def test_format_diagnostic_without_span():
+    diagnostic = make_diagnostic("src/a/x.py", 3, None, is_error=False)
+
+    assert format_diagnostic("{line}:{column} {severity}", diagnostic) == "3:1 warning"
+
+
+def test_format_global_diagnostic():
+    diagnostic = make_diagnostic(None, None, None)
+
+    assert (
+        format_diagnostic("{path}:{line}:{column}: {code}", diagnostic)
+        == ":: undeclared-dependency"
+    )
+
+
+@pytest.mark.parametrize(
+    "template",
+    ["{path}:{lineno}", "{}", "{path.name}", "{path", "{0}"],
+)
+def test_validate_output_template_rejects_unknown_fields(template: str):
+    with pytest.raises(TachConfigError):
+        validate_output_template(template)
+
+
+def test_validate_output_template_accepts_format_specs():
+    validate_output_template("{path}:{line:>4}: {{literal}} {message}")
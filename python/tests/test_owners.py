@@ -0,0 +1,48 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+from tach.owners import UNOWNED, group_by_owner
+
+
+@dataclass
+class FakeDiagnostic:
+    module: str | None
+    error: bool = True
+
+    def usage_module(self) -> str | None:
+        return self.module
+
+    def is_error(self) -> bool:
+        return self.error
+
+    def is_warning(self) -> bool:
+        return not self.error
+
+
+def test_group_by_owner():
+    module_owners = {
+        "payments": "@payments-team",
+        "payments.legacy": "@platform-team",
+        "auth": "@platform-team",
+    }
+    diagnostics = [
+        FakeDiagnostic("payments"),
+        FakeDiagnostic("payments.legacy", error=False),
+        FakeDiagnostic("unowned"),
+        FakeDiagnostic(None),
+    ]
+
+    reports = group_by_owner(diagnostics, module_owners)  # type: ignore
+
+    assert [report.owner for report in reports] == [
+        "@payments-team",
+        "@platform-team",
+        UNOWNED,
+    ]
+    assert reports[0].modules == ["payments"]
+    assert (reports[0].error_count, reports[0].warning_count) == (1, 0)
+    assert reports[1].modules == ["payments.legacy", "auth"]
+    assert (reports[1].error_count, reports[1].warning_count) == (0, 1)
+    assert reports[2].modules == []
+    assert reports[2].error_count == 2
@@ -0,0 +1,77 @@
+from __future__ import annotations
+
+import io
+import json
+
+from tach.progress import (
+    JsonProgressReporter,
+    TerminalProgressReporter,
+    create_progress_reporter,
+)
+
+
+class TtyStream(io.StringIO):
+    def isatty(self) -> bool:
+        return True
+
+
+def test_create_progress_reporter():
+    assert isinstance(
+        create_progress_reporter(stream=TtyStream()), TerminalProgressReporter
+    )
+    assert isinstance(
+        create_progress_reporter(progress=True, stream=TtyStream()),
+        TerminalProgressReporter,
+    )
+    assert isinstance(
+        create_progress_reporter(progress=True, stream=io.StringIO()),
+        JsonProgressReporter,
+    )
+    assert create_progress_reporter(stream=io.StringIO()) is None
+
+
+def test_json_progress_reporter():
+    stream = io.StringIO()
+    reporter = JsonProgressReporter(stream)
+
+    reporter.start_phase("check", total=2)
+    reporter.advance()
+    reporter.advance()
+    reporter.start_phase("unused-dependencies")
+    reporter.finish()
+
+    events = [json.loads(line) for line in stream.getvalue().splitlines()]
+    assert events[0] == {
+        "event": "progress",
+        "phase": "check",
+        "processed": 0,
+        "total": 2,
+    }
+    # Intermediate updates are throttled, but the last one is always emitted
+    assert events[-4] == {
+        "event": "progress",
+        "phase": "check",
+        "processed": 2,
+        "total": 2,
+    }
+    assert [event["event"] for event in events[-3:]] == [
+        "phase_finished",
+        "progress",
+        "phase_finished",
+    ]
+    assert events[-1]["phase"] == "unused-dependencies"
+
+
+def test_terminal_progress_reporter():
+    stream = TtyStream()
+    reporter = TerminalProgressReporter(stream)
+
+    reporter.start_phase("check", total=4)
+    reporter.advance(4)
+    reporter.finish()
+
+    output = stream.getvalue()
+    assert "check [" + "-" * 30 + "] 0/4" in output
+    assert "check [" + "#" * 30 + "] 4/4" in output
+    # The bar is cleared once the phase is finished
+    assert output.endswith("\r\x1b[K")
@@ -0,0 +1,139 @@
+from __future__ import annotations
+
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.project_stats import (
+    NO_LAYER,
+    UNRESTRICTED_LAYER,
+    ProjectStats,
+    compute_project_stats,
+    count_ignore_directives,
+    count_modules_with_interfaces,
+    layer_distribution,
+    render_project_stats,
+)
+
+
+def _module(path, layer=None, unrestricted_layer=False):
+    return SimpleNamespace(
+        path=path, layer=layer, unrestricted_layer=unrestricted_layer
+    )
+
+
+def test_count_ignore_directives():
+    source = """
+import os  # tach-ignore
+# tach-ignore(legacy) myapp.core
+from myapp import api
+#tach-ignore
+x = "tach-ignore"
+"""
+    assert count_ignore_directives(source) == 3
+
+
+def test_layer_distribution():
+    modules = [
+        _module("api", layer="ui"),
+        _module("core", layer="core"),
+        _module("models", layer="core"),
+        _module("scripts"),
+    ]
+
+    assert layer_distribution(modules, ["ui", "commands", "core"]) == {  # type: ignore
+        "ui": 1,
+        "commands": 0,
+        "core": 2,
+        NO_LAYER: 1,
+    }
+    assert layer_distribution([_module("scripts")], []) == {}  # type: ignore
+
+
+def test_layer_distribution_unrestricted():
+    modules = [
+        _module("api", layer="ui"),
+        _module("logging", layer="core", unrestricted_layer=True),
+        _module("flags", unrestricted_layer=True),
+    ]
+
+    assert layer_distribution(modules, ["ui", "core"]) == {  # type: ignore
+        "ui": 1,
+        "core": 0,
+        UNRESTRICTED_LAYER: 2,
+    }
+
+
+def test_count_modules_with_interfaces():
+    interfaces = [
+        SimpleNamespace(from_modules=["myapp.api"]),
+        SimpleNamespace(from_modules=["myapp.services.*"]),
+    ]
+    module_paths = ["myapp.api", "myapp.services.billing", "myapp.core", "myapp"]
+
+    assert count_modules_with_interfaces(module_paths, interfaces) == 2  # type: ignore
+
+
+def test_compute_project_stats(tmp_path: Path, mocker):
+    package = tmp_path / "src" / "myapp"
+    package.mkdir(parents=True)
+    (package / "api.py").write_text("import requests  # tach-ignore\n")
+    (package / "core.py").write_text("import attrs\nimport requests\n")
+    project_config = SimpleNamespace(
+        source_roots=["src"],
+        layers=[],
+        use_regex_matching=False,
+        include=[],
+        ignore_type_checking_imports=True,
+        external=SimpleNamespace(exclude=[]),
+        all_modules=lambda: [
+            _module("<root>"),
+            _module("myapp.api"),
+            _module("myapp.core"),
+        ],
+        all_interfaces=lambda: [SimpleNamespace(from_modules=["myapp.core"])],
+    )
+    graph = SimpleNamespace(
+        edges=[
+            SimpleNamespace(source="myapp.api", target="myapp.core"),
+            SimpleNamespace(source="myapp.api", target="myapp.api"),
+        ]
+    )
+    mocker.patch("tach.project_stats.get_dependency_graph", return_value=graph)
+    mocker.patch("tach.project_stats.set_excluded_paths")
+    mocker.patch("tach.project_stats.get_project_site_packages", return_value=())
+    mocker.patch(
+        "tach.project_stats.get_external_dependencies",
+        side_effect=lambda file_path, **_kwargs: [
+            SimpleNamespace(package_name=line.split()[1])
+            for line in Path(file_path).read_text().splitlines()
+        ],
+    )
+
+    stats = compute_project_stats(tmp_path, project_config, [])  # type: ignore
+
+    assert stats == ProjectStats(
+        modules=2,
+        edges=1,
+        layers={},
+        modules_with_interfaces=1,
+        ignore_directives=1,
+        external_dependencies=2,
+    )
+    assert stats.to_dict()["interface_coverage"] == 50.0
+
+
+def test_render_project_stats():
+    stats = ProjectStats(
+        modules=4,
+        edges=3,
+        layers={"ui": 1, "core": 3},
+        modules_with_interfaces=1,
+        ignore_directives=2,
+        external_dependencies=5,
+    )
+
+    rendered = render_project_stats(stats)
+
+    assert "Modules                4" in rendered
+    assert "Interface coverage     25.0% (1/4 modules)" in rendered
+    assert rendered.endswith("1  ui\n3  core")
@@ -0,0 +1,113 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tach.errors import TachError
+from tach.ratchet import BudgetReport, check_budgets, ratchet_enabled
+
+
+def make_project_config(budgets=None, budget_file=None):
+    project_config = SimpleNamespace(
+        exit=SimpleNamespace(budgets=budgets or {}, budget_file=budget_file),
+        edits=[],
+        saved=False,
+    )
+    project_config.set_violation_budget = lambda code, budget: (
+        project_config.edits.append((code, budget))
+    )
+    project_config.save_edits = lambda: setattr(project_config, "saved", True)
+    return project_config
+
+
+def test_budget_report():
+    report = BudgetReport(
+        budgets={"undeclared-dependency": 5, "private-import": 2, "layer-violation": 1},
+        counts={"undeclared-dependency": 7, "private-import": 1, "unused-ignore": 1},
+    )
+
+    assert report.exceeded == [
+        ("undeclared-dependency", 7, 5),
+        ("unused-ignore", 1, 0),
+    ]
+    assert report.tightened == {"layer-violation": 0, "private-import": 1}
+
+
+def test_ratchet_enabled():
+    assert not ratchet_enabled(make_project_config().exit)
+    assert ratchet_enabled(make_project_config(budgets={"private-import": 1}).exit)
+    assert ratchet_enabled(make_project_config(budget_file="budgets.json").exit)
+
+
+def test_first_check_records_budget_file(tmp_path: Path):
+    project_config = make_project_config(budget_file="budgets.json")
+
+    report = check_budgets(tmp_path, project_config, {"private-import": 3})
+
+    assert report.exceeded == []
+    assert json.loads((tmp_path / "budgets.json").read_text()) == {
+        "private-import": 3
+    }
+
+
+def test_budget_file_tightens(tmp_path: Path):
+    (tmp_path / "budgets.json").write_text(
+        json.dumps({"private-import": 3, "undeclared-dependency": 4})
+    )
+    project_config = make_project_config(
+        budgets={"private-import": 10}, budget_file="budgets.json"
+    )
+
+    report = check_budgets(tmp_path, project_config, {"private-import": 2})
+
+    assert report.budgets == {"private-import": 3, "undeclared-dependency": 4}
+    assert json.loads((tmp_path / "budgets.json").read_text()) == {
+        "private-import": 2,
+        "undeclared-dependency": 0,
+    }
+    assert project_config.edits == []
+
+
+def test_config_budgets_tighten(tmp_path: Path):
+    project_config = make_project_config(
+        budgets={"private-import": 3, "undeclared-dependency": 4}
+    )
+
+    counts = {"private-import": 1, "undeclared-dependency": 4}
+    check_budgets(tmp_path, project_config, counts)
+
+    assert project_config.edits == [("private-import", 1)]
+    assert project_config.saved
+
+
+def test_partial_check_does_not_tighten(tmp_path: Path):
+    project_config = make_project_config(budgets={"private-import": 3})
+
+    report = check_budgets(tmp_path, project_config, {}, tighten=False)
+
+    assert report.tightened == {"private-import": 0}
+    assert project_config.edits == []
+    assert not project_config.saved
+
+
+def test_invalid_budget_file(tmp_path: Path):
+    (tmp_path / "budgets.json").write_text(json.dumps({"private-import": -1}))
+    project_config = make_project_config(budget_file="budgets.json")
+
+    with pytest.raises(TachError, match="non-negative integers"):
+        check_budgets(tmp_path, project_config, {})
+
+
+def test_config_budgets_seed_budget_file(tmp_path: Path):
+    project_config = make_project_config(
+        budgets={"private-import": 3}, budget_file="budgets.json"
+    )
+
+    check_budgets(tmp_path, project_config, {"private-import": 3})
+
+    assert json.loads((tmp_path / "budgets.json").read_text()) == {
+        "private-import": 3
+    }
@@ -113,6 +113,39 @@ def test_report_valid_domain_two(example_valid_dir):
     assert "domain_two.x" in usages
 
 
+def test_report_plugin_references(tmp_project):
+    (tmp_project / "tach.toml").write_text(
+        """
+[[modules]]
+path = "api"
+depends_on = ["billing"]
+
+[[modules]]
+path = "billing"
+depends_on = []
+
+[plugins.celery]
+"""
+    )
+    for package in ("api", "billing"):
+        (tmp_project / package).mkdir()
+        (tmp_project / package / "__init__.py").touch()
+    (tmp_project / "billing" / "tasks.py").write_text("def charge():\n    pass\n")
+    (tmp_project / "api" / "jobs.py").write_text(
+        "from celery import current_app\n\ncurrent_app.send_task('billing.tasks.charge')\n"
+    )
+
+    result = report(
+        project_root=tmp_project,
+        path=Path("api"),
+        project_config=parse_project_config(tmp_project),
+    )
+
+    dependencies, usages = result.split("Usages of 'api'")
+    assert "Reference 'billing.tasks.charge' (celery task name)" in dependencies
+    assert "billing.tasks.charge" not in usages
+
+
 def test_report_raw_output(example_valid_dir):
     project_config = parse_project_config(example_valid_dir)
     result = report(
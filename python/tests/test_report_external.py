@@ -23,6 +23,7 @@ def project_config():
     p.ignore_type_checking_imports = True
     p.use_regex_matching = False
     p.include_string_imports = False
+    p.external.virtualenv = None
     return p
 
 
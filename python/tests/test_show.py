@@ -0,0 +1,108 @@
+from __future__ import annotations
+
+from types import SimpleNamespace
+
+from tach.graph_pruning import GraphPruning
+from tach.show import (
+    collapse_domain_edges,
+    domain_for_module,
+    render_module_graph_mermaid,
+)
+
+
+def test_domain_for_module():
+    domain_paths = ["billing", "billing.invoices", "auth"]
+
+    assert domain_for_module(domain_paths, "billing") == "billing"
+    assert domain_for_module(domain_paths, "billing.api") == "billing"
+    assert domain_for_module(domain_paths, "billing.invoices.pdf") == "billing.invoices"
+    assert domain_for_module(domain_paths, "billing_legacy") is None
+    assert domain_for_module(domain_paths, "core") is None
+
+
+def test_collapse_domain_edges():
+    edges = [
+        ("billing.api", "auth.tokens"),
+        ("billing.invoices", "auth"),
+        ("billing.api", "billing.models"),
+        ("billing.api", "core"),
+        ("core", "auth.tokens"),
+    ]
+
+    assert collapse_domain_edges(edges, ["billing", "auth"]) == {
+        ("billing", "auth"): 2,
+        ("billing", "core"): 1,
+        ("core", "auth"): 1,
+    }
+
+
+def test_render_mermaid_collapse_packages():
+    core_db = SimpleNamespace(path="core.db")
+    modules = [
+        SimpleNamespace(path="payments.stripe", depends_on=[core_db]),
+        SimpleNamespace(path="payments.models", depends_on=[core_db]),
+        SimpleNamespace(path="core.db", depends_on=[]),
+        SimpleNamespace(path="scripts", depends_on=None),
+    ]
+    project_config = SimpleNamespace(
+        filtered_modules=lambda _included_paths: modules,
+        domain_paths=lambda: [],
+        package_paths=lambda: ["payments", "core"],
+    )
+
+    graph = render_module_graph_mermaid(
+        project_config, [], collapse_packages=True  # type: ignore
+    )
+
+    assert graph == "graph TD\n    payments -->|2| core\n    scripts"
+
+
+def test_render_mermaid_pruned():
+    core = SimpleNamespace(path="core")
+    logging = SimpleNamespace(path="logging")
+    modules = [
+        SimpleNamespace(path="api", depends_on=[core, logging]),
+        SimpleNamespace(path="billing", depends_on=[core, logging]),
+        SimpleNamespace(path="core", depends_on=[logging]),
+        SimpleNamespace(path="logging", depends_on=[]),
+        SimpleNamespace(path="<root>", depends_on=[core]),
+    ]
+    project_config = SimpleNamespace(
+        filtered_modules=lambda _included_paths: modules,
+        domain_paths=lambda: [],
+        utility_paths=lambda: ["logging"],
+    )
+
+    graph = render_module_graph_mermaid(
+        project_config,  # type: ignore
+        [],
+        pruning=GraphPruning(utilities=True, root=True),
+    )
+
+    assert graph == "graph TD\n    api --> core\n    billing --> core\n    core"
+
+
+def test_render_mermaid_from_dependency_graph():
+    modules = [
+        SimpleNamespace(path="api", depends_on=[]),
+        SimpleNamespace(path="core", depends_on=[]),
+    ]
+    project_config = SimpleNamespace(
+        filtered_modules=lambda _included_paths: modules,
+        domain_paths=lambda: [],
+    )
+    dependency_graph = SimpleNamespace(
+        edges=[
+            SimpleNamespace(source="api", target="core"),
+            SimpleNamespace(source="api", target="api"),
+            SimpleNamespace(source="scripts", target="core"),
+        ]
+    )
+
+    graph = render_module_graph_mermaid(
+        project_config,  # type: ignore
+        [],
+        dependency_graph=dependency_graph,  # type: ignore
+    )
+
+    assert graph == "graph TD\n    api --> core\n    core"
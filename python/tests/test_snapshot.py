@@ -0,0 +1,118 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+import pytest
+
+from tach.errors import TachError
+from tach.snapshot import (
+    ArchitectureSnapshot,
+    SnapshotEdge,
+    build_snapshot,
+    compare_snapshots,
+    read_snapshot,
+    render_snapshot_diff,
+    write_snapshot,
+)
+
+
+def diagnostic(code: str) -> SimpleNamespace:
+    return SimpleNamespace(code=lambda: code)
+
+
+def graph_edge(source: str, target: str, weight: int, declared: bool = True):
+    return SimpleNamespace(
+        source=source, target=target, weight=weight, declared=declared
+    )
+
+
+def test_build_snapshot():
+    snapshot = build_snapshot(
+        [diagnostic("undeclared-dependency"), diagnostic("undeclared-dependency")],
+        [graph_edge("b", "a", 2), graph_edge("a", "c", 1, declared=False)],
+        ["c", "a", "b"],
+    )
+
+    assert snapshot.modules == ["a", "b", "c"]
+    assert [(edge.source, edge.target) for edge in snapshot.edges] == [
+        ("a", "c"),
+        ("b", "a"),
+    ]
+    assert snapshot.violations == {"undeclared-dependency": 2}
+    assert snapshot.violation_count == 2
+
+
+def test_snapshot_round_trip(tmp_path: Path):
+    snapshot = ArchitectureSnapshot(
+        modules=["a", "b"],
+        edges=[SnapshotEdge(source="b", target="a", imports=3)],
+        violations={"interface-violation": 1},
+    )
+    path = tmp_path / "snapshots" / "tach-snapshot.json"
+
+    write_snapshot(snapshot, path)
+
+    assert json.loads(path.read_text())["version"] == 1
+    assert read_snapshot(path) == snapshot
+
+
+def test_read_snapshot_errors(tmp_path: Path):
+    with pytest.raises(TachError):
+        read_snapshot(tmp_path / "missing.json")
+    path = tmp_path / "tach-snapshot.json"
+    path.write_text(json.dumps({"version": 99}))
+    with pytest.raises(TachError):
+        read_snapshot(path)
+
+
+def test_compare_snapshots():
+    baseline = ArchitectureSnapshot(
+        modules=["a", "b", "old"],
+        edges=[
+            SnapshotEdge(source="b", target="a", imports=3),
+            SnapshotEdge(source="old", target="a", imports=1),
+        ],
+        violations={"interface-violation": 2, "undeclared-dependency": 1},
+    )
+    current = ArchitectureSnapshot(
+        modules=["a", "b", "new"],
+        edges=[
+            SnapshotEdge(source="a", target="b", imports=1, declared=False),
+            SnapshotEdge(source="b", target="a", imports=5),
+            SnapshotEdge(source="new", target="a", imports=2),
+        ],
+        violations={"interface-violation": 2, "undeclared-dependency": 4},
+    )
+
+    diff = compare_snapshots(baseline, current)
+
+    assert diff.added_modules == ["new"]
+    assert diff.removed_modules == ["old"]
+    assert [(edge.source, edge.target) for edge in diff.added_edges] == [
+        ("a", "b"),
+        ("new", "a"),
+    ]
+    assert [(edge.source, edge.target) for edge in diff.removed_edges] == [
+        ("old", "a")
+    ]
+    assert diff.violation_changes == {"undeclared-dependency": 3}
+    # 2 module changes, an undeclared (2) and declared (1) edge added,
+    # 1 edge removed, and 3 new violations
+    assert diff.drift_score == 9
+
+    output = render_snapshot_diff(diff)
+    assert output.startswith("Drift score: 9")
+    assert "'a' -> 'b' (1 imports, undeclared)" in output
+    assert "undeclared-dependency: +3" in output
+
+
+def test_compare_identical_snapshots():
+    snapshot = ArchitectureSnapshot(modules=["a"], violations={"x": 1})
+
+    diff = compare_snapshots(snapshot, snapshot)
+
+    assert diff.drift_score == 0
+    assert not diff.has_changes()
+    assert "No structural changes" in render_snapshot_diff(diff)
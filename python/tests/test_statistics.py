@@ -0,0 +1,36 @@
+from __future__ import annotations
+
+from unittest.mock import Mock
+
+from tach.statistics import NO_MODULE, compute_statistics
+
+
+def make_diagnostic(code: str, usage_module: str | None) -> Mock:
+    diagnostic = Mock()
+    diagnostic.code.return_value = code
+    diagnostic.usage_module.return_value = usage_module
+    return diagnostic
+
+
+def test_compute_statistics():
+    diagnostics = [
+        make_diagnostic("undeclared-dependency", "domain_one"),
+        make_diagnostic("undeclared-dependency", "domain_two"),
+        make_diagnostic("private-dependency", "domain_one"),
+        make_diagnostic("undeclared-dependency", "domain_one"),
+        make_diagnostic("unused-ignore-directive", None),
+    ]
+
+    statistics = compute_statistics(diagnostics)
+
+    assert statistics.by_code == [
+        ("undeclared-dependency", 3),
+        ("private-dependency", 1),
+        ("unused-ignore-directive", 1),
+    ]
+    assert statistics.by_module == [
+        ("domain_one", 3),
+        (NO_MODULE, 1),
+        ("domain_two", 1),
+    ]
+    assert statistics.to_dict()["codes"]["undeclared-dependency"] == 3
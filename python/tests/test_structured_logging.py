@@ -0,0 +1,81 @@
+from __future__ import annotations
+
+import io
+import json
+import logging
+
+import pytest
+
+from tach.logging.logger import (
+    CallInfo,
+    JsonLogFormatter,
+    events_logger,
+    log_cache_lookup,
+    log_phase,
+    log_run_summary,
+    logger,
+    run_stats,
+)
+
+
+@pytest.fixture
+def log_stream():
+    stream = io.StringIO()
+    handler = logging.StreamHandler(stream)
+    handler.setFormatter(JsonLogFormatter())
+    logger.addHandler(handler)
+    events_logger.addHandler(handler)
+    run_stats.phase_durations.clear()
+    run_stats.caches.clear()
+    yield stream
+    logger.removeHandler(handler)
+    events_logger.removeHandler(handler)
+
+
+def read_entries(stream: io.StringIO) -> list[dict]:
+    return [json.loads(line) for line in stream.getvalue().splitlines()]
+
+
+def test_call_info_is_structured(log_stream: io.StringIO):
+    logger.info(
+        "tach check called",
+        extra={"data": CallInfo(function="tach_check", parameters={"exact": True})},
+    )
+
+    [entry] = read_entries(log_stream)
+    assert entry["level"] == "INFO"
+    assert entry["message"] == "tach check called"
+    assert entry["call_info"] == {
+        "function": "tach_check",
+        "parameters": {"exact": True},
+    }
+
+
+def test_phase_timings(log_stream: io.StringIO):
+    with log_phase("check"):
+        pass
+    with pytest.raises(SystemExit):
+        with log_phase("sync"):
+            raise SystemExit(1)
+
+    entries = read_entries(log_stream)
+    assert [(entry["event"], entry["phase"]) for entry in entries] == [
+        ("phase", "check"),
+        ("phase", "sync"),
+    ]
+    assert all(entry["duration_ms"] >= 0 for entry in entries)
+    assert set(run_stats.phase_durations) == {"check", "sync"}
+
+
+def test_run_summary_includes_cache_hit_rate(log_stream: io.StringIO):
+    log_cache_lookup("computation", hit=True)
+    log_cache_lookup("computation", hit=False)
+    log_cache_lookup("computation", hit=True)
+    log_cache_lookup("computation", hit=True)
+    log_run_summary()
+
+    summary = read_entries(log_stream)[-1]
+    assert summary["event"] == "summary"
+    assert summary["caches"] == {
+        "computation": {"hits": 3, "misses": 1, "hit_rate": 0.75}
+    }
@@ -0,0 +1,22 @@
+from __future__ import annotations
+
+import sys
+
+import pytest
+
+from tach.stubgen import generate_stubs, missing_stubs
+
+
+def test_extension_stub_is_complete():
+    assert missing_stubs() == []
+
+
+@pytest.mark.skipif(sys.version_info < (3, 9), reason="requires ast.unparse")
+def test_generate_stubs_preserves_annotations(tmp_path):
+    stub_path = tmp_path / "extension.pyi"
+    stub_path.write_text("def check(project_root: str) -> list[str]: ...\n")
+
+    stubs = generate_stubs(existing_stub_path=stub_path)
+
+    assert "def check(project_root: str) -> list[str]: ..." in stubs
+    assert "class ProjectConfig:" in stubs
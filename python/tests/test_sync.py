@@ -1,13 +1,17 @@
 from __future__ import annotations
 
+import json
 import shutil
 import tempfile
 from pathlib import Path
+from types import SimpleNamespace
 
 import pytest
 
 from tach.cli import tach_sync
+from tach.exit_codes import EXIT_SYNC_REQUIRED
 from tach.parsing.config import parse_project_config
+from tach.sync import render_sync_plan_json, render_sync_plan_text
 
 
 def test_valid_example_dir(example_dir, capfd):
@@ -77,3 +81,118 @@ def test_distributed_config_dir(example_dir, capfd):
             "project.module_two",
             "project.module_one",
         }
+
+
+def test_sync_check_does_not_write(example_dir, capfd):
+    project_root = example_dir / "valid"
+
+    with tempfile.TemporaryDirectory() as temp_dir:
+        temp_project_root = Path(temp_dir) / "valid"
+        shutil.copytree(project_root, temp_project_root)
+        config_path = temp_project_root / "tach.toml"
+        original_config = config_path.read_text()
+
+        project_config = parse_project_config(root=temp_project_root)
+        assert project_config is not None
+
+        with pytest.raises(SystemExit) as exc_info:
+            tach_sync(
+                project_root=temp_project_root,
+                project_config=project_config,
+                exclude_paths=project_config.exclude,
+                check_only=True,
+                output_format="json",
+            )
+
+        result = json.loads(capfd.readouterr().out)
+        assert exc_info.value.code == (0 if result["in_sync"] else EXIT_SYNC_REQUIRED)
+        assert config_path.read_text() == original_config
+
+        with pytest.raises(SystemExit):
+            tach_sync(
+                project_root=temp_project_root,
+                project_config=project_config,
+                exclude_paths=project_config.exclude,
+            )
+        capfd.readouterr()
+
+        project_config = parse_project_config(root=temp_project_root)
+        assert project_config is not None
+        with pytest.raises(SystemExit) as exc_info:
+            tach_sync(
+                project_root=temp_project_root,
+                project_config=project_config,
+                exclude_paths=project_config.exclude,
+                check_only=True,
+            )
+
+        assert exc_info.value.code == 0
+        assert "Dependencies are in sync." in capfd.readouterr().out
+
+
+def test_render_sync_plan():
+    plan = SimpleNamespace(
+        create_root_module=False,
+        modules=[
+            SimpleNamespace(path="api", added=["core"], removed=["billing"]),
+        ],
+        deleted_modules=["legacy"],
+        new_modules=["payments"],
+        unregistered_packages=["scripts"],
+        is_empty=lambda: False,
+    )
+
+    text = render_sync_plan_text(plan)
+    assert "+ depends on 'core'" in text
+    assert "- depends on 'billing'" in text
+    assert "- module 'legacy'" in text
+    assert "+ module 'payments'" in text
+    assert "Package 'scripts' does not belong to any module." in text
+
+    assert json.loads(render_sync_plan_json(plan)) == {
+        "in_sync": False,
+        "create_root_module": False,
+        "create_modules": ["payments"],
+        "modules": [{"path": "api", "add": ["core"], "remove": ["billing"]}],
+        "delete_modules": ["legacy"],
+        "unregistered_packages": ["scripts"],
+    }
+
+
+@pytest.mark.parametrize(
+    "new_packages,expected_modules",
+    [
+        ("warn", {"billing"}),
+        ("top-level", {"billing", "payments"}),
+        ("nested", {"billing", "billing.api", "payments"}),
+    ],
+)
+def test_sync_new_packages(tmp_path, capfd, new_packages, expected_modules):
+    for package in ["billing", "billing/api", "payments"]:
+        (tmp_path / package).mkdir(parents=True)
+        (tmp_path / package / "__init__.py").write_text("")
+    (tmp_path / "payments" / "charge.py").write_text("import billing\n")
+    (tmp_path / "tach.toml").write_text(
+        '[[modules]]\npath = "billing"\ndepends_on = []\n'
+    )
+
+    project_config = parse_project_config(root=tmp_path)
+    assert project_config is not None
+    with pytest.raises(SystemExit) as exc_info:
+        tach_sync(
+            project_root=tmp_path,
+            project_config=project_config,
+            exclude_paths=project_config.exclude,
+            new_packages=new_packages,
+        )
+
+    assert exc_info.value.code == 0
+    captured = capfd.readouterr()
+    assert ("Package 'payments'" in captured.err) == (new_packages == "warn")
+
+    project_config = parse_project_config(root=tmp_path)
+    assert project_config is not None
+    modules = {module.path: module for module in project_config.filtered_modules([])}
+    assert set(modules) == expected_modules
+    if "payments" in modules:
+        assert [dep.path for dep in modules["payments"].depends_on] == ["billing"]
@@ -0,0 +1,90 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.trace import render_trace_json, render_trace_text
+
+
+def dependency(line_number: int, module_path: str, category: str, **kwargs):
+    return SimpleNamespace(
+        line_number=line_number,
+        module_path=module_path,
+        source=kwargs.get("source", "import"),
+        # Extension enums are displayed with their class name
+        category=f"ImportCategory.{category}",
+        module=kwargs.get("module"),
+        distributions=kwargs.get("distributions", []),
+        ignored=kwargs.get("ignored"),
+    )
+
+
+def file_trace(dependencies, skipped=None):
+    return SimpleNamespace(
+        file_path=Path("src/api/views.py"),
+        module_path="api.views",
+        module="api",
+        skipped=skipped,
+        dependencies=dependencies,
+    )
+
+
+def test_render_trace_text():
+    trace = file_trace(
+        [
+            dependency(1, "os.path", "Stdlib"),
+            dependency(2, "requests", "External", distributions=["requests"]),
+            dependency(3, "core.models", "Project", module="core"),
+            dependency(
+                4,
+                "api.helpers",
+                "Project",
+                module="api",
+                ignored="it is within the same module",
+            ),
+            dependency(
+                9, "billing.Invoice", "Project", source="django", module="billing"
+            ),
+        ]
+    )
+
+    text = render_trace_text(trace)
+
+    assert "Module path: api.views" in text
+    assert "1  os.path  [import, stdlib]" in text
+    assert "2  requests  [import, external] (requests)" in text
+    assert "3  core.models  [import, project] -> core" in text
+    assert "ignored, because it is within the same module" in text
+    assert "9  billing.Invoice  [django, project] -> billing" in text
+    assert "Skipped" not in text
+
+
+def test_render_skipped_file():
+    text = render_trace_text(
+        file_trace([], skipped="the file is marked as generated code")
+    )
+
+    assert "Skipped by 'tach check', because the file is marked as generated" in text
+    assert "No imports or references found." in text
+
+
+def test_render_trace_json():
+    trace = file_trace(
+        [dependency(3, "core.models", "Project", module="core", ignored="reason")]
+    )
+
+    result = json.loads(render_trace_json(trace))
+
+    assert result["file_path"] == "src/api/views.py"
+    assert result["dependencies"] == [
+        {
+            "line_number": 3,
+            "module_path": "core.models",
+            "source": "import",
+            "category": "project",
+            "module": "core",
+            "distributions": [],
+            "ignored": "reason",
+        }
+    ]
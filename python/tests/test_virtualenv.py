@@ -0,0 +1,63 @@
+from __future__ import annotations
+
+import base64
+import hashlib
+import os
+from pathlib import Path
+
+import pytest
+
+from tach.utils.virtualenv import find_virtualenv, get_site_packages
+
+
+def make_virtualenv(path: Path) -> Path:
+    (path / "lib" / "python3.12" / "site-packages").mkdir(parents=True)
+    (path / "pyvenv.cfg").touch()
+    return path
+
+
+@pytest.fixture
+def clean_environ(monkeypatch):
+    monkeypatch.delenv("VIRTUAL_ENV", raising=False)
+    monkeypatch.delenv("POETRY_CACHE_DIR", raising=False)
+
+
+def test_configured_virtualenv_takes_precedence(tmp_path, monkeypatch):
+    monkeypatch.setenv("VIRTUAL_ENV", str(make_virtualenv(tmp_path / "active")))
+    configured = make_virtualenv(tmp_path / "envs" / "tach")
+    assert find_virtualenv(tmp_path, "envs/tach") == configured.resolve()
+
+
+def test_active_virtualenv(tmp_path, monkeypatch):
+    active = make_virtualenv(tmp_path / "active")
+    make_virtualenv(tmp_path / ".venv")
+    monkeypatch.setenv("VIRTUAL_ENV", str(active))
+    assert find_virtualenv(tmp_path) == active.resolve()
+
+
+def test_project_virtualenv(tmp_path, clean_environ):
+    venv = make_virtualenv(tmp_path / ".venv")
+    assert find_virtualenv(tmp_path) == venv.resolve()
+    assert get_site_packages(venv) == [venv / "lib" / "python3.12" / "site-packages"]
+
+
+def test_poetry_virtualenv(tmp_path, clean_environ, monkeypatch):
+    project_root = tmp_path / "project"
+    project_root.mkdir()
+    (project_root / "pyproject.toml").write_text(
+        '[tool.poetry]\nname = "My Project"\nversion = "0.1.0"\n'
+    )
+    normalized_root = os.path.normcase(os.path.realpath(project_root))
+    digest = hashlib.sha256(normalized_root.encode()).digest()
+    env_hash = base64.urlsafe_b64encode(digest).decode()[:8]
+    virtualenvs = tmp_path / "virtualenvs"
+    make_virtualenv(virtualenvs / f"my_project-{env_hash}-py3.11")
+    newest = make_virtualenv(virtualenvs / f"my_project-{env_hash}-py3.12")
+    monkeypatch.setenv("POETRY_VIRTUALENVS_PATH", str(virtualenvs))
+
+    assert find_virtualenv(project_root) == newest
+
+
+def test_no_virtualenv(tmp_path, clean_environ, monkeypatch):
+    monkeypatch.setenv("POETRY_VIRTUALENVS_PATH", str(tmp_path / "virtualenvs"))
+    assert find_virtualenv(tmp_path) is None
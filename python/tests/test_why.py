@@ -0,0 +1,67 @@
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from types import SimpleNamespace
+
+from tach.why import render_why_json, render_why_text
+
+
+def hop(source: str, target: str, declared: bool = True, imports=()):
+    return SimpleNamespace(
+        source=source,
+        target=target,
+        declared=declared,
+        imports=[
+            SimpleNamespace(
+                file_path=Path(file_path),
+                line_number=line_number,
+                import_path=import_path,
+            )
+            for file_path, line_number, import_path in imports
+        ],
+    )
+
+
+def test_render_direct_dependency():
+    hops = [
+        hop(
+            "api",
+            "core",
+            imports=[
+                ("src/api/views.py", 3, "core.models.User"),
+                ("src/api/routes.py", 10, "core.service"),
+            ],
+        )
+    ]
+
+    text = render_why_text("api", "core", hops)
+
+    assert "'api' depends on 'core' directly." in text
+    assert "  src/api/views.py:3  core.models.User" in text
+    assert "  src/api/routes.py:10  core.service" in text
+    assert "undeclared" not in text
+
+
+def test_render_transitive_dependency():
+    hops = [
+        hop("api", "services", imports=[("src/api/views.py", 1, "services.orders")]),
+        hop("services", "db", declared=False, imports=[("src/services/x.py", 2, "db")]),
+    ]
+
+    text = render_why_text("api", "db", hops)
+    assert "transitively through 2 dependencies" in text
+    assert "api -> services -> db" in text
+    assert "(undeclared)" in text
+
+    result = json.loads(render_why_json("api", "db", hops))
+    assert result["direct"] is False
+    assert result["path"] == ["api", "services", "db"]
+    assert result["hops"][1]["imports"] == [
+        {"file_path": "src/services/x.py", "line_number": 2, "import_path": "db"}
+    ]
+
+
+def test_render_no_dependency():
+    assert "'core' does not depend on 'api'" in render_why_text("core", "api", [])
+    assert json.loads(render_why_json("core", "api", []))["path"] == []
@@ -0,0 +1,39 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from tach.workspace import check_workspace
+
+
+def write_project(root: Path, package: str, source: str) -> None:
+    (root / "src" / package).mkdir(parents=True)
+    (root / "src" / package / "__init__.py").write_text(source)
+    (root / "tach.toml").write_text(
+        f'source_roots = ["src"]\n\n[[modules]]\npath = "{package}"\ndepends_on = []\n'
+    )
+
+
+def test_cross_project_imports(tmp_path):
+    write_project(tmp_path / "billing", "billing", "import json\nimport auth.tokens\n")
+    write_project(tmp_path / "auth", "auth", "")
+    (tmp_path / "auth" / "src" / "auth" / "tokens.py").touch()
+
+    report = check_workspace(tmp_path, [tmp_path / "billing", tmp_path / "auth"])
+
+    assert [project.root for project in report.projects] == [
+        Path("billing"),
+        Path("auth"),
+    ]
+    assert not report.has_errors
+    assert [
+        cross_project_import.to_dict()
+        for cross_project_import in report.cross_project_imports
+    ] == [
+        {
+            "file": str(Path("billing/src/billing/__init__.py")),
+            "line": 2,
+            "module": "auth.tokens",
+            "importing_project": "billing",
+            "imported_project": "auth",
+        }
+    ]
@@ -1,5 +1,6 @@
 use cached::stores::DiskCacheBuildError;
 use cached::{DiskCache, DiskCacheError, IOCached};
+use pyo3::prelude::*;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
@@ -7,6 +8,7 @@ use std::{env, fs};
 use thiserror::Error;
 use toml::Value;
 
+use crate::config::CacheBackend;
 use crate::filesystem::{self, walk_pyfiles};
 
 #[derive(Error, Debug)]
@@ -15,6 +17,8 @@ pub enum CacheError {
     DiskCache(#[from] DiskCacheError),
     #[error("Disk cache build error: {0}")]
     DiskCacheBuild(#[from] DiskCacheBuildError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, CacheError>;
@@ -39,17 +43,19 @@ static CACHE_DIR: &str = ".tach";
 
 pub type ComputationCacheValue = (Vec<(u8, String)>, u8);
 
+fn computation_cache_dir<P: AsRef<Path>>(project_root: P) -> PathBuf {
+    project_root
+        .as_ref()
+        .join(CACHE_DIR)
+        .join("computation-cache")
+}
+
 fn build_computation_cache<P: AsRef<Path>>(
     project_root: P,
 ) -> Result<DiskCache<String, ComputationCacheValue>> {
     Ok(
         DiskCache::<String, ComputationCacheValue>::new("computation-cache")
-            .set_disk_directory(
-                project_root
-                    .as_ref()
-                    .join(CACHE_DIR)
-                    .join("computation-cache"),
-            )
+            .set_disk_directory(computation_cache_dir(project_root))
             .build()?,
     )
 }
@@ -172,3 +178,75 @@ pub fn update_computation_cache(
 
     Ok(cache.cache_set(cache_key, value)?)
 }
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct CacheStats {
+    pub backend: CacheBackend,
+    pub location: PathBuf,
+    pub entries: usize,
+    pub size_bytes: u64,
+}
+
+fn directory_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            size += directory_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+pub fn get_computation_cache_stats<P: AsRef<Path>>(
+    project_root: P,
+    backend: &CacheBackend,
+) -> Result<CacheStats> {
+    let location = computation_cache_dir(&project_root);
+    // Avoid creating the cache on disk just to report that it is empty
+    if !location.is_dir() {
+        return Ok(CacheStats {
+            backend: backend.clone(),
+            location,
+            entries: 0,
+            size_bytes: 0,
+        });
+    }
+    let entries = build_computation_cache(&project_root)?.connection().len();
+    Ok(CacheStats {
+        backend: backend.clone(),
+        size_bytes: directory_size(&location)?,
+        location,
+        entries,
+    })
+}
+
+pub fn clear_computation_cache<P: AsRef<Path>>(project_root: P) -> Result<()> {
+    let location = computation_cache_dir(project_root);
+    if location.is_dir() {
+        fs::remove_dir_all(location)?;
+    }
+    Ok(())
+}
+
+/// Remove cache entries created more than 'older_than_days' days ago,
+/// returning the number of entries removed.
+pub fn prune_computation_cache<P: AsRef<Path>>(
+    project_root: P,
+    older_than_days: u64,
+) -> Result<usize> {
+    if !computation_cache_dir(&project_root).is_dir() {
+        return Ok(0);
+    }
+    let cache = DiskCache::<String, ComputationCacheValue>::new("computation-cache")
+        .set_disk_directory(computation_cache_dir(&project_root))
+        .set_lifespan(older_than_days * 24 * 60 * 60)
+        .build()?;
+    let entries_before = cache.connection().len();
+    cache.remove_expired_entries()?;
+    Ok(entries_before.saturating_sub(cache.connection().len()))
+}
@@ -0,0 +1,107 @@
+use crate::{
+    config::{LocatedDomainConfig, ProjectConfig},
+    diagnostics::{ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, Severity},
+};
+
+/// Whether a domain config may depend on the module at 'module_path'.
+/// Modules outside of the domain are only allowed when their domain is declared in 'depends_on_domains'.
+fn is_isolated_dependency(
+    project_config: &ProjectConfig,
+    domain: &LocatedDomainConfig,
+    module_path: &str,
+) -> bool {
+    match project_config.domain_for_module(module_path) {
+        Some(target_domain) => {
+            target_domain.location.mod_path == domain.location.mod_path
+                || (domain.config.depends_on_domains.is_some()
+                    && domain.allows_domain_dependency(&target_domain.location.mod_path))
+        }
+        None => false,
+    }
+}
+
+fn isolation_violation(
+    domain: &LocatedDomainConfig,
+    module_path: &str,
+    severity: &Severity,
+) -> Diagnostic {
+    Diagnostic::new_global(
+        severity.clone(),
+        DiagnosticDetails::Configuration(ConfigurationDiagnostic::DomainIsolationViolation {
+            domain: domain.location.mod_path.clone(),
+            module_path: module_path.to_string(),
+        }),
+    )
+}
+
+/// Find modules referenced by each domain config which are outside of that domain's subtree,
+/// such as modules which now belong to a nested domain after files have moved.
+pub fn check_domain_isolation(
+    project_config: &ProjectConfig,
+    severity: Severity,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for domain in &project_config.domains {
+        for module in domain.modules() {
+            let owning_domain = project_config.domain_for_module(&module.path);
+            if owning_domain.map(|owner| &owner.location.mod_path)
+                != Some(&domain.location.mod_path)
+            {
+                diagnostics.push(isolation_violation(domain, &module.path, &severity));
+            }
+            for dependency in module.dependencies_iter() {
+                if !is_isolated_dependency(project_config, domain, &dependency.path) {
+                    diagnostics.push(isolation_violation(domain, &dependency.path, &severity));
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ConfigLocation, DomainConfig};
+    use std::path::PathBuf;
+
+    fn domain(mod_path: &str, config: &str) -> LocatedDomainConfig {
+        let config: DomainConfig = toml::from_str(config).unwrap();
+        config.with_location(ConfigLocation {
+            path: PathBuf::from(format!("{}/tach.domain.toml", mod_path.replace('.', "/"))),
+            mod_path: mod_path.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_check_domain_isolation() {
+        let mut project_config = ProjectConfig::default();
+        project_config.add_domain(domain(
+            "billing",
+            r#"
+depends_on_domains = ["//auth"]
+
+[root]
+depends_on = ["api", "//auth.tokens", "//core", "//billing.invoices.pdf"]
+
+[[modules]]
+path = "api"
+
+[[modules]]
+path = "invoices.models"
+"#,
+        ));
+        project_config.add_domain(domain("billing.invoices", "[root]"));
+        project_config.add_domain(domain("auth", "[root]"));
+
+        let violations: Vec<String> = check_domain_isolation(&project_config, Severity::Error)
+            .iter()
+            .map(|diagnostic| diagnostic.message())
+            .collect();
+
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].contains("'billing.invoices.models'"));
+        assert!(violations[1].contains("'core'"));
+        assert!(violations[2].contains("'billing.invoices.pdf'"));
+    }
+}
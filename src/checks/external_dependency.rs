@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails};
 use crate::diagnostics::{FileChecker, Result as DiagnosticResult};
-use crate::external::parsing::ProjectInfo;
+use crate::external::parsing::{normalize_package_name, ProjectInfo};
 use crate::processors::file_module::FileModule;
 use crate::processors::import::{with_distribution_names, ExternalImportWithDistributionNames};
 
@@ -28,19 +28,32 @@ impl<'a> ExternalDependencyChecker<'a> {
         }
     }
 
-    fn check_import(
-        &'a self,
-        import: ExternalImportWithDistributionNames<'a>,
-        processed_file: &FileModule<'a>,
-    ) -> Option<Diagnostic> {
-        if import
+    /// Whether an import is never checked, because it is excluded or part of the standard library.
+    fn is_exempt(&self, import: &ExternalImportWithDistributionNames<'a>) -> bool {
+        import
             .distribution_names
             .iter()
             .any(|dist_name| self.excluded_external_modules.contains(dist_name))
             || self
                 .stdlib_modules
                 .contains(&import.import.top_level_module_name().to_string())
-        {
+    }
+
+    /// The top level names of the external packages which a file imports.
+    pub fn external_packages(&'a self, processed_file: &FileModule<'a>) -> Vec<String> {
+        with_distribution_names(processed_file.imports(), self.module_mappings)
+            .into_iter()
+            .filter(|import| !self.is_exempt(import))
+            .map(|import| import.import.top_level_module_name().to_string())
+            .collect()
+    }
+
+    fn check_import(
+        &'a self,
+        import: ExternalImportWithDistributionNames<'a>,
+        processed_file: &FileModule<'a>,
+    ) -> Option<Diagnostic> {
+        if self.is_exempt(&import) {
             return None;
         }
 
@@ -48,18 +61,44 @@ impl<'a> ExternalDependencyChecker<'a> {
             .distribution_names
             .iter()
             .any(|dist_name| self.project_info.dependencies.contains(dist_name));
+        let module_config = processed_file.module.config.as_ref();
+
+        let details = if !is_declared {
+            CodeDiagnostic::UndeclaredExternalDependency {
+                dependency: import.import.top_level_module_name().to_string(),
+                usage_module: module_config
+                    .filter(|config| !config.is_root())
+                    .map(|config| config.path.clone()),
+            }
+        } else {
+            // A module which declares 'depends_on_external' may only use the packages it lists
+            let module_config = module_config?;
+            let allowed = module_config.depends_on_external.as_ref()?;
+            let top_level_module_name =
+                normalize_package_name(import.import.top_level_module_name());
+            let is_allowed = allowed
+                .iter()
+                .map(|name| normalize_package_name(name))
+                .any(|name| {
+                    name == top_level_module_name || import.distribution_names.contains(&name)
+                });
+            if is_allowed {
+                return None;
+            }
+            CodeDiagnostic::UndeclaredModuleExternalDependency {
+                dependency: import.import.top_level_module_name().to_string(),
+                usage_module: module_config.path.clone(),
+            }
+        };
 
-        if !is_declared {
-            Some(Diagnostic::new_located_error(
+        Some(
+            Diagnostic::new_located_error(
                 processed_file.relative_file_path().to_path_buf(),
                 processed_file.line_number(import.import.import_offset),
-                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
-                    dependency: import.import.top_level_module_name().to_string(),
-                }),
-            ))
-        } else {
-            None
-        }
+                DiagnosticDetails::Code(details),
+            )
+            .with_span(processed_file.span(import.import.import_offset)),
+        )
     }
 }
 
@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use petgraph::algo::kosaraju_scc;
+use petgraph::graphmap::DiGraphMap;
+
+use crate::{
+    diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, RelatedLocation, Severity},
+    filesystem::{self as fs, to_forward_slashes},
+    processors::FileModule,
+};
+
+/// Each file's imports of other project files, with the line of the first import of each file.
+type FileImports = BTreeMap<PathBuf, BTreeMap<PathBuf, usize>>;
+
+/// The imports between project files which run as soon as a file is imported.
+/// Imports within functions, TYPE_CHECKING blocks and strings are left out,
+/// since they do not run while a cycle of files is still being imported.
+pub struct FileImportGraph<'a> {
+    source_roots: &'a [PathBuf],
+    imports: Mutex<FileImports>,
+}
+
+impl<'a> FileImportGraph<'a> {
+    pub fn new(source_roots: &'a [PathBuf]) -> Self {
+        Self {
+            source_roots,
+            imports: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The file which runs when 'module_path' is imported, relative to the project root.
+    fn resolve_file(&self, project_root: &Path, module_path: &str) -> Option<PathBuf> {
+        let resolved = fs::module_to_file_path(self.source_roots, module_path, true)?;
+        let file_path = resolved.file_path.strip_prefix(project_root).ok()?;
+        // Stub files are preferred when resolving imports, but the source file runs instead
+        match file_path.extension() {
+            Some(extension) if extension == "pyi" => Some(file_path.with_extension("py")),
+            _ => Some(file_path.to_path_buf()),
+        }
+    }
+
+    pub fn record(&self, file_module: &FileModule) {
+        let mut file_imports: BTreeMap<PathBuf, usize> = BTreeMap::new();
+        for import in file_module.imports().filter(|import| import.runs_at_import) {
+            let Some(target) =
+                self.resolve_file(file_module.file.project_root, &import.module_path)
+            else {
+                continue;
+            };
+            if target == file_module.relative_file_path() {
+                continue;
+            }
+            let line_number = file_module.line_number(import.import_offset);
+            file_imports
+                .entry(target)
+                .and_modify(|first_line| *first_line = (*first_line).min(line_number))
+                .or_insert(line_number);
+        }
+        self.imports
+            .lock()
+            .unwrap()
+            .insert(file_module.relative_file_path().to_path_buf(), file_imports);
+    }
+
+    /// Report a concrete chain of imports for each group of files which import each other in a cycle.
+    /// Each chain is the shortest cycle through the first file of its group.
+    pub fn check(&self, severity: Severity) -> Vec<Diagnostic> {
+        let imports = self.imports.lock().unwrap();
+        let mut graph = DiGraphMap::new();
+        for (file, targets) in imports.iter() {
+            graph.add_node(file.as_path());
+            for target in targets
+                .keys()
+                .filter(|target| imports.contains_key(*target))
+            {
+                graph.add_edge(file.as_path(), target.as_path(), ());
+            }
+        }
+
+        let mut cycles: Vec<Vec<&Path>> = kosaraju_scc(&graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .filter_map(|scc| shortest_cycle(&graph, &scc))
+            .collect();
+        cycles.sort();
+        cycles
+            .iter()
+            .map(|cycle| cycle_diagnostic(&imports, cycle, &severity))
+            .collect()
+    }
+}
+
+/// The shortest cycle from the first file in 'scc' back to itself, without leaving 'scc'.
+fn shortest_cycle<'a>(graph: &DiGraphMap<&'a Path, ()>, scc: &[&'a Path]) -> Option<Vec<&'a Path>> {
+    let start = *scc.iter().min()?;
+    let members: HashSet<&Path> = scc.iter().copied().collect();
+    let mut previous: HashMap<&Path, &Path> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(file) = queue.pop_front() {
+        let mut targets: Vec<&Path> = graph.neighbors(file).collect();
+        targets.sort();
+        for target in targets {
+            if target == start {
+                let mut cycle = vec![file];
+                while let Some(&importer) = previous.get(cycle.last().unwrap()) {
+                    cycle.push(importer);
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if members.contains(target) && !previous.contains_key(target) {
+                previous.insert(target, file);
+                queue.push_back(target);
+            }
+        }
+    }
+    None
+}
+
+fn cycle_diagnostic(imports: &FileImports, cycle: &[&Path], severity: &Severity) -> Diagnostic {
+    let next_files = cycle.iter().cycle().skip(1);
+    let links: Vec<(&Path, &Path, usize)> = cycle
+        .iter()
+        .zip(next_files)
+        .map(|(&file, &next)| (file, next, imports[file][next]))
+        .collect();
+
+    let (first_file, _, first_line) = links[0];
+    let diagnostic = Diagnostic::new_located(
+        severity.clone(),
+        DiagnosticDetails::Code(CodeDiagnostic::FileImportCycle {
+            files: cycle.iter().map(|file| to_forward_slashes(file)).collect(),
+        }),
+        first_file.to_path_buf(),
+        first_line,
+    );
+    links
+        .iter()
+        .skip(1)
+        .fold(diagnostic, |diagnostic, &(file, next, line_number)| {
+            diagnostic.with_related_location(RelatedLocation::new(
+                file.to_path_buf(),
+                Some(line_number),
+                format!(
+                    "'{}' imports '{}'",
+                    to_forward_slashes(file),
+                    to_forward_slashes(next)
+                ),
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_imports(edges: &[(&str, &str, usize)]) -> Vec<Diagnostic> {
+        let source_roots: [PathBuf; 0] = [];
+        let graph = FileImportGraph::new(&source_roots);
+        {
+            let mut imports = graph.imports.lock().unwrap();
+            for &(file, target, line_number) in edges {
+                imports
+                    .entry(PathBuf::from(file))
+                    .or_default()
+                    .insert(PathBuf::from(target), line_number);
+                imports.entry(PathBuf::from(target)).or_default();
+            }
+        }
+        graph.check(Severity::Error)
+    }
+
+    #[test]
+    fn test_no_cycles() {
+        assert!(check_imports(&[("a.py", "b.py", 1), ("b.py", "c.py", 1)]).is_empty());
+    }
+
+    #[test]
+    fn test_cycle_within_and_across_modules() {
+        let diagnostics = check_imports(&[
+            ("billing/api.py", "billing/models.py", 3),
+            ("billing/models.py", "billing/api.py", 5),
+            ("core/a.py", "core/b.py", 1),
+            ("core/b.py", "orders/c.py", 2),
+            ("core/b.py", "core/a.py", 9),
+            ("orders/c.py", "core/a.py", 4),
+        ]);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].file_path(),
+            Some(&PathBuf::from("billing/api.py"))
+        );
+        assert_eq!(diagnostics[0].line_number(), Some(3));
+        assert!(diagnostics[0]
+            .message()
+            .contains("billing/api.py -> billing/models.py -> billing/api.py"));
+        assert_eq!(diagnostics[0].related_locations()[0].line_number, Some(5));
+
+        // The shortest cycle through the first file is reported
+        assert!(diagnostics[1]
+            .message()
+            .contains("core/a.py -> core/b.py -> core/a.py"));
+    }
+}
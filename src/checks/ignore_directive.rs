@@ -1,9 +1,84 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+use serde::Serialize;
 
 use crate::config::{ProjectConfig, RuleSetting};
-use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails};
+use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, Severity};
 use crate::processors::ignore_directive::{IgnoreDirective, IgnoreDirectives};
 
+/// A violation which an ignore directive suppressed.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct SuppressedViolation {
+    // The diagnostic code, e.g. 'undeclared-dependency'
+    pub code: String,
+    pub severity: Severity,
+    pub dependency: Option<String>,
+    // The module which the suppressed import depends on, if it is part of the project
+    pub target_module: Option<String>,
+}
+
+impl From<&Diagnostic> for SuppressedViolation {
+    fn from(diagnostic: &Diagnostic) -> Self {
+        Self {
+            code: diagnostic.code().to_string(),
+            severity: diagnostic.severity(),
+            dependency: diagnostic.dependency().map(str::to_string),
+            target_module: diagnostic.definition_module().map(str::to_string),
+        }
+    }
+}
+
+/// An ignore directive, and the violations it suppressed during a check.
+/// A directive which suppressed nothing is stale.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct IgnoreDirectiveUsage {
+    // Relative to the project root
+    pub file_path: PathBuf,
+    pub line_number: usize,
+    pub reason: String,
+    pub modules: Vec<String>,
+    // Whether another directive already applies to the same line
+    pub redundant: bool,
+    pub suppressed: Vec<SuppressedViolation>,
+}
+
+/// Match each ignore directive in a file against the diagnostics found before they are applied.
+pub fn get_ignore_directive_usages(
+    ignore_directives: &IgnoreDirectives,
+    diagnostics: &[Diagnostic],
+    relative_file_path: &Path,
+) -> Vec<IgnoreDirectiveUsage> {
+    let usage = |directive: &IgnoreDirective, redundant: bool| IgnoreDirectiveUsage {
+        file_path: relative_file_path.to_path_buf(),
+        line_number: directive.line_no,
+        reason: directive.reason.clone(),
+        modules: directive.modules.clone(),
+        redundant,
+        suppressed: if redundant {
+            vec![]
+        } else {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| directive.matches_diagnostic(diagnostic))
+                .map(SuppressedViolation::from)
+                .collect()
+        },
+    };
+
+    ignore_directives
+        .active_directives()
+        .map(|directive| usage(directive, false))
+        .chain(
+            ignore_directives
+                .redundant_directives()
+                .map(|directive| usage(directive, true)),
+        )
+        .collect()
+}
+
 pub struct IgnoreDirectivePostProcessor<'a> {
     project_config: &'a ProjectConfig,
 }
@@ -136,3 +211,49 @@ impl<'a> IgnoreDirectivePostProcessor<'a> {
         diagnostics.extend(ignore_directive_diagnostics);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::ignore_directive::get_ignore_directives;
+
+    fn undeclared_dependency(line_number: usize) -> Diagnostic {
+        Diagnostic::new_located_error(
+            PathBuf::from("src/api/views.py"),
+            line_number,
+            DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                dependency: "core.models.User".to_string(),
+                usage_module: "api".to_string(),
+                definition_module: "core".to_string(),
+                suggestion: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_ignore_directive_usages() {
+        let directives = get_ignore_directives(
+            "from core.models import User  # tach-ignore(legacy)\nimport os  # tach-ignore",
+        );
+        let mut usages = get_ignore_directive_usages(
+            &directives,
+            &[undeclared_dependency(1)],
+            Path::new("src/api/views.py"),
+        );
+        usages.sort_by_key(|usage| usage.line_number);
+
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].reason, "legacy");
+        assert_eq!(
+            usages[0].suppressed,
+            vec![SuppressedViolation {
+                code: "undeclared-dependency".to_string(),
+                severity: Severity::Error,
+                dependency: Some("core.models.User".to_string()),
+                target_module: Some("core".to_string()),
+            }]
+        );
+        assert!(usages[1].suppressed.is_empty());
+        assert!(!usages[1].redundant);
+    }
+}
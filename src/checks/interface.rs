@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::config::root_module::RootModuleTreatment;
 use crate::config::{ModuleConfig, ProjectConfig};
 use crate::diagnostics::{
     CodeDiagnostic, ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, FileChecker,
-    Result as DiagnosticResult,
+    RelatedLocation, Result as DiagnosticResult,
 };
 use crate::interfaces::compiled::CompiledInterfaces;
 use crate::interfaces::data_types::{TypeCheckCache, TypeCheckResult};
@@ -12,6 +13,7 @@ use crate::interfaces::error::InterfaceError;
 use crate::modules::ModuleTree;
 use crate::processors::file_module::FileModule;
 use crate::processors::Dependency;
+use crate::python::parsing::parse_interface_members;
 
 #[derive(Debug)]
 pub enum InterfaceCheckResult {
@@ -26,6 +28,14 @@ pub struct InterfaceChecker<'a> {
     module_tree: &'a ModuleTree,
     interfaces: CompiledInterfaces,
     type_check_cache: Option<TypeCheckCache>,
+    // The '__all__' of each module, when 'strict_exports' is enabled
+    declared_exports: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Whether a member (e.g. 'service.create') is exposed by a module's '__all__'.
+fn is_declared_export(exports: &[String], member: &str) -> bool {
+    let name = member.split('.').next().unwrap_or(member);
+    exports.iter().any(|export| export == name)
 }
 
 impl<'a> InterfaceChecker<'a> {
@@ -37,9 +47,33 @@ impl<'a> InterfaceChecker<'a> {
             module_tree,
             interfaces: compiled,
             type_check_cache: None,
+            declared_exports: None,
         }
     }
 
+    /// Treat modules without interfaces as exposing only the members in their '__all__'.
+    /// Modules without '__all__' (or which cannot be parsed) are fully private.
+    /// The root module has no source file of its own, so it is not restricted.
+    pub fn with_declared_exports(
+        mut self,
+        modules: &[ModuleConfig],
+        source_roots: &[PathBuf],
+    ) -> Self {
+        self.declared_exports = Some(
+            modules
+                .iter()
+                .filter(|module| !module.is_root())
+                .map(|module| {
+                    (
+                        module.path.clone(),
+                        parse_interface_members(source_roots, &module.path).unwrap_or_default(),
+                    )
+                })
+                .collect(),
+        );
+        self
+    }
+
     pub fn with_type_check_cache(
         mut self,
         modules: &[ModuleConfig],
@@ -58,7 +92,22 @@ impl<'a> InterfaceChecker<'a> {
         let matching_interfaces = self.interfaces.get_interfaces(module_path);
 
         if matching_interfaces.is_empty() {
-            return InterfaceCheckResult::NoInterfaces;
+            return match self
+                .declared_exports
+                .as_ref()
+                .and_then(|declared_exports| declared_exports.get(module_path))
+            {
+                Some(exports) => {
+                    if is_declared_export(exports, member) {
+                        InterfaceCheckResult::Exposed {
+                            type_check_result: TypeCheckResult::Unknown,
+                        }
+                    } else {
+                        InterfaceCheckResult::NotExposed
+                    }
+                }
+                None => InterfaceCheckResult::NoInterfaces,
+            };
         }
 
         let mut is_exposed = false;
@@ -81,6 +130,22 @@ impl<'a> InterfaceChecker<'a> {
         }
     }
 
+    /// Point a diagnostic at the definition of each interface of the given module.
+    fn with_interface_locations(&self, diagnostic: Diagnostic, module_path: &str) -> Diagnostic {
+        self.interfaces
+            .get_interfaces(module_path)
+            .into_iter()
+            .filter_map(|interface| {
+                let config_path = interface.span.file_path()?;
+                Some(RelatedLocation::new(
+                    self.project_config.relative_config_path(config_path),
+                    interface.span.line_number(),
+                    format!("Interface for '{}' is defined here.", module_path),
+                ))
+            })
+            .fold(diagnostic, Diagnostic::with_related_location)
+    }
+
     fn check_interfaces(
         &self,
         dependency: &Dependency,
@@ -109,26 +174,38 @@ impl<'a> InterfaceChecker<'a> {
                 .unwrap_or("");
             let check_result = self.check_member(import_member, &dependency_module_config.path);
             match check_result {
-                InterfaceCheckResult::NotExposed => Ok(vec![Diagnostic::new_located_error(
-                    file_module.relative_file_path().to_path_buf(),
-                    file_module.line_number(dependency.offset()),
-                    DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency {
-                        dependency: dependency.module_path().to_string(),
-                        usage_module: file_module.module_config().path.to_string(),
-                        definition_module: dependency_module_config.path.to_string(),
-                    }),
+                InterfaceCheckResult::NotExposed if dependency_module_config.utility => Ok(self
+                    .check_utility_interface(dependency, file_module, dependency_module_config)
+                    .into_iter()
+                    .collect()),
+                InterfaceCheckResult::NotExposed => Ok(vec![self.with_interface_locations(
+                    Diagnostic::new_located_error(
+                        file_module.relative_file_path().to_path_buf(),
+                        file_module.line_number(dependency.offset()),
+                        DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency {
+                            dependency: dependency.module_path().to_string(),
+                            usage_module: file_module.module_config().path.to_string(),
+                            definition_module: dependency_module_config.path.to_string(),
+                        }),
+                    )
+                    .with_span(file_module.span(dependency.offset())),
+                    &dependency_module_config.path,
                 )]),
                 InterfaceCheckResult::Exposed {
                     type_check_result: TypeCheckResult::DidNotMatchInterface { expected },
-                } => Ok(vec![Diagnostic::new_located_error(
-                    file_module.relative_file_path().to_path_buf(),
-                    file_module.line_number(dependency.offset()),
-                    DiagnosticDetails::Code(CodeDiagnostic::InvalidDataTypeExport {
-                        dependency: dependency.module_path().to_string(),
-                        usage_module: file_module.module_config().path.to_string(),
-                        definition_module: dependency_module_config.path.to_string(),
-                        expected_data_type: expected.to_string(),
-                    }),
+                } => Ok(vec![self.with_interface_locations(
+                    Diagnostic::new_located_error(
+                        file_module.relative_file_path().to_path_buf(),
+                        file_module.line_number(dependency.offset()),
+                        DiagnosticDetails::Code(CodeDiagnostic::InvalidDataTypeExport {
+                            dependency: dependency.module_path().to_string(),
+                            usage_module: file_module.module_config().path.to_string(),
+                            definition_module: dependency_module_config.path.to_string(),
+                            expected_data_type: expected.to_string(),
+                        }),
+                    )
+                    .with_span(file_module.span(dependency.offset())),
+                    &dependency_module_config.path,
                 )]),
                 InterfaceCheckResult::Exposed {
                     type_check_result: TypeCheckResult::MatchedInterface { .. },
@@ -149,6 +226,74 @@ impl<'a> InterfaceChecker<'a> {
     }
 }
 
+impl InterfaceChecker<'_> {
+    /// Utility modules may be used without declaring a dependency,
+    /// but their interfaces are enforced according to the 'utility_interfaces' rule.
+    fn check_utility_interface(
+        &self,
+        dependency: &Dependency,
+        file_module: &FileModule,
+        dependency_module_config: &ModuleConfig,
+    ) -> Option<Diagnostic> {
+        let severity = (&self.project_config.rules.utility_interfaces)
+            .try_into()
+            .ok()?;
+        Some(
+            self.with_interface_locations(
+                Diagnostic::new_located(
+                    severity,
+                    DiagnosticDetails::Code(CodeDiagnostic::UtilityMisuse {
+                        dependency: dependency.module_path().to_string(),
+                        usage_module: file_module.module_config().path.to_string(),
+                        definition_module: dependency_module_config.path.to_string(),
+                    }),
+                    file_module.relative_file_path().to_path_buf(),
+                    file_module.line_number(dependency.offset()),
+                )
+                .with_span(file_module.span(dependency.offset())),
+                &dependency_module_config.path,
+            ),
+        )
+    }
+
+    fn check_domain_public_api(
+        &self,
+        dependency: &Dependency,
+        file_module: &FileModule,
+    ) -> Option<Diagnostic> {
+        let severity = (&self.project_config.rules.domain_public_api)
+            .try_into()
+            .ok()?;
+        let dependency_module = self.module_tree.find_nearest(dependency.module_path())?;
+        let dependency_module_config = dependency_module.config.as_ref()?;
+        let domain = self
+            .project_config
+            .domain_for_module(&dependency_module_config.path)?;
+
+        // Modules within the domain may use any of its modules
+        if domain.contains_module(&file_module.module_config().path)
+            || domain.is_public_module(&dependency_module_config.path)
+        {
+            return None;
+        }
+
+        Some(
+            Diagnostic::new_located(
+                severity,
+                DiagnosticDetails::Code(CodeDiagnostic::DomainPrivateDependency {
+                    dependency: dependency.module_path().to_string(),
+                    usage_module: file_module.module_config().path.to_string(),
+                    definition_module: dependency_module_config.path.to_string(),
+                    definition_domain: domain.location.mod_path.clone(),
+                }),
+                file_module.relative_file_path().to_path_buf(),
+                file_module.line_number(dependency.offset()),
+            )
+            .with_span(file_module.span(dependency.offset())),
+        )
+    }
+}
+
 impl<'a> FileChecker<'a> for InterfaceChecker<'a> {
     type ProcessedFile = FileModule<'a>;
     type Output = Vec<Diagnostic>;
@@ -157,8 +302,140 @@ impl<'a> FileChecker<'a> for InterfaceChecker<'a> {
         let mut diagnostics = vec![];
         for dependency in input.dependencies.iter() {
             diagnostics.extend(self.check_interfaces(dependency, input)?);
+            diagnostics.extend(self.check_domain_public_api(dependency, input));
         }
 
         Ok(diagnostics)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::commands::check::check_internal;
+    use crate::diagnostics::Severity;
+    use crate::parsing::config::parse_project_config;
+
+    #[test]
+    fn test_is_declared_export() {
+        let exports = vec!["create_user".to_string(), "service".to_string()];
+        assert!(is_declared_export(&exports, "create_user"));
+        assert!(is_declared_export(&exports, "service.helpers"));
+        assert!(!is_declared_export(&exports, "_internal"));
+        assert!(!is_declared_export(&exports, "create"));
+        assert!(!is_declared_export(&[], "create_user"));
+    }
+
+    /// The code, severity and line of each interface diagnostic for a project where 'app'
+    /// uses a private member of the utility module 'utils', and of the declared dependency 'core'.
+    fn check_utility_project(utility_interfaces: &str) -> Vec<(&'static str, Severity, usize)> {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for Python files
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["app", "core", "utils"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(
+            root.join("app/service.py"),
+            "from utils import public\nfrom utils import private\nfrom core import hidden\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            format!(
+                concat!(
+                    "[[modules]]\npath = \"app\"\ndepends_on = [\"core\"]\n",
+                    "[[modules]]\npath = \"core\"\ndepends_on = []\n",
+                    "[[modules]]\npath = \"utils\"\nutility = true\n",
+                    "[[interfaces]]\nexpose = [\"public\"]\nfrom = [\"utils\"]\n",
+                    "[[interfaces]]\nexpose = [\"api\"]\nfrom = [\"core\"]\n",
+                    "[rules]\nutility_interfaces = \"{}\"\n",
+                ),
+                utility_interfaces
+            ),
+        )
+        .unwrap();
+
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let mut diagnostics: Vec<_> =
+            check_internal(root.to_path_buf(), &project_config, true, true, vec![])
+                .unwrap()
+                .iter()
+                .map(|diagnostic| {
+                    (
+                        diagnostic.code(),
+                        diagnostic.severity(),
+                        diagnostic.line_number().unwrap(),
+                    )
+                })
+                .collect();
+        diagnostics.sort_by_key(|(_, _, line_number)| *line_number);
+        diagnostics
+    }
+
+    #[rstest]
+    #[case("error", Some(Severity::Error))]
+    #[case("warn", Some(Severity::Warning))]
+    #[case("off", None)]
+    fn test_utility_interfaces_rule(
+        #[case] utility_interfaces: &str,
+        #[case] utility_severity: Option<Severity>,
+    ) {
+        let mut expected = vec![];
+        if let Some(severity) = utility_severity {
+            expected.push(("utility-misuse", severity, 2));
+        }
+        // Interfaces of other modules are enforced regardless of the rule
+        expected.push(("private-dependency", Severity::Error, 3));
+
+        assert_eq!(check_utility_project(utility_interfaces), expected);
+    }
+
+    #[test]
+    fn test_private_dependency_points_at_interface() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["app", "core"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(root.join("app/service.py"), "from core import hidden\n").unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"app\"\ndepends_on = [\"core\"]\n",
+                "\n",
+                "[[modules]]\npath = \"core\"\ndepends_on = []\n",
+                "\n",
+                "[[interfaces]]\nfrom = [\"core\"]\nexpose = [\"api\"]\n",
+            ),
+        )
+        .unwrap();
+
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let diagnostics =
+            check_internal(root.to_path_buf(), &project_config, true, true, vec![]).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), "private-dependency");
+        assert_eq!(
+            diagnostics[0].related_locations(),
+            &[RelatedLocation::new(
+                PathBuf::from("tach.toml"),
+                Some(11),
+                "Interface for 'core' is defined here.",
+            )]
+        );
+    }
+}
@@ -1,23 +1,98 @@
 use crate::{
-    config::{root_module::RootModuleTreatment, DependencyConfig, ModuleConfig, ProjectConfig},
+    config::{
+        root_module::RootModuleTreatment, DependencyConfig, LocatedDomainConfig, ModuleConfig,
+        PackageConfig, ProjectConfig,
+    },
     diagnostics::{
-        CodeDiagnostic, ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, FileChecker,
-        Result as DiagnosticResult,
+        suggestion::closest_match, CodeDiagnostic, ConfigurationDiagnostic, Diagnostic,
+        DiagnosticDetails, FileChecker, RelatedLocation, Result as DiagnosticResult,
     },
     modules::ModuleTree,
     processors::{file_module::FileModule, Dependency},
 };
 use std::path::Path;
 
+/// The layers which apply between two modules, along with the layer of each module.
+struct LayerScope<'a> {
+    layers: &'a [String],
+    source_layer: Option<&'a String>,
+    target_layer: Option<&'a String>,
+}
+
 #[derive(Debug)]
 enum LayerCheckResult {
     Ok,
     SameLayer,
     LayerNotSpecified,
+    UnrestrictedLayer,
     LayerViolation(Diagnostic),
     UnknownLayer(Diagnostic),
 }
 
+/// A group of modules, such as a domain or a package, which may only depend on the groups
+/// it allows.
+trait ModuleGroup {
+    fn group_path(&self) -> &String;
+
+    fn allows_group_dependency(&self, group_path: &str) -> bool;
+
+    /// The diagnostic for 'dependency' from a module in one group on a module in another,
+    /// given as each module's path along with its group.
+    fn violation(
+        dependency: String,
+        usage: (String, &Self),
+        definition: (String, &Self),
+    ) -> CodeDiagnostic;
+}
+
+impl ModuleGroup for LocatedDomainConfig {
+    fn group_path(&self) -> &String {
+        &self.location.mod_path
+    }
+
+    fn allows_group_dependency(&self, group_path: &str) -> bool {
+        self.allows_domain_dependency(group_path)
+    }
+
+    fn violation(
+        dependency: String,
+        (usage_module, usage_domain): (String, &Self),
+        (definition_module, definition_domain): (String, &Self),
+    ) -> CodeDiagnostic {
+        CodeDiagnostic::DomainViolation {
+            dependency,
+            usage_module,
+            usage_domain: usage_domain.group_path().clone(),
+            definition_module,
+            definition_domain: definition_domain.group_path().clone(),
+        }
+    }
+}
+
+impl ModuleGroup for PackageConfig {
+    fn group_path(&self) -> &String {
+        &self.path
+    }
+
+    fn allows_group_dependency(&self, group_path: &str) -> bool {
+        self.allows_package_dependency(group_path)
+    }
+
+    fn violation(
+        dependency: String,
+        (usage_module, usage_package): (String, &Self),
+        (definition_module, definition_package): (String, &Self),
+    ) -> CodeDiagnostic {
+        CodeDiagnostic::PackageViolation {
+            dependency,
+            usage_module,
+            usage_package: usage_package.group_path().clone(),
+            definition_module,
+            definition_package: definition_package.group_path().clone(),
+        }
+    }
+}
+
 pub struct InternalDependencyChecker<'a> {
     project_config: &'a ProjectConfig,
     module_tree: &'a ModuleTree,
@@ -31,16 +106,77 @@ impl<'a> InternalDependencyChecker<'a> {
         }
     }
 
+    fn module_config_related_location(&self, module_path: &str) -> Option<RelatedLocation> {
+        let (config_path, line_number) = self.project_config.module_config_location(module_path)?;
+        Some(RelatedLocation::new(
+            self.project_config.relative_config_path(&config_path),
+            line_number,
+            format!("Module '{}' is configured here.", module_path),
+        ))
+    }
+
+    fn with_usage_module_location(&self, diagnostic: Diagnostic, usage_module: &str) -> Diagnostic {
+        match self.module_config_related_location(usage_module) {
+            Some(location) => diagnostic.with_related_location(location),
+            None => diagnostic,
+        }
+    }
+
+    /// Modules within a domain which declares its own layers are checked against those layers,
+    /// and are otherwise checked against the project layers using the layer of the domain root.
+    fn layer_scope<'b>(
+        &'b self,
+        project_layers: &'b [String],
+        source_module_config: &'b ModuleConfig,
+        target_module_config: &'b ModuleConfig,
+    ) -> LayerScope<'b> {
+        let layered_domain = |module: &ModuleConfig| {
+            self.project_config
+                .domain_for_module(&module.path)
+                .filter(|domain| domain.has_layers())
+        };
+        let source_domain = layered_domain(source_module_config);
+        let target_domain = layered_domain(target_module_config);
+
+        if let (Some(source_domain), Some(target_domain)) = (source_domain, target_domain) {
+            if source_domain.location.mod_path == target_domain.location.mod_path {
+                // The domain root's layer is a project layer
+                let domain_layer = |module: &'b ModuleConfig| {
+                    module
+                        .layer
+                        .as_ref()
+                        .filter(|_| module.path != source_domain.location.mod_path)
+                };
+                return LayerScope {
+                    layers: &source_domain.config.layers,
+                    source_layer: domain_layer(source_module_config),
+                    target_layer: domain_layer(target_module_config),
+                };
+            }
+        }
+
+        LayerScope {
+            layers: project_layers,
+            source_layer: source_domain.map_or(source_module_config.layer.as_ref(), |domain| {
+                domain.root_layer()
+            }),
+            target_layer: target_domain.map_or(target_module_config.layer.as_ref(), |domain| {
+                domain.root_layer()
+            }),
+        }
+    }
+
     fn check_layers(
         &self,
         file_module: &FileModule,
         dependency: &Dependency,
-        layers: &[String],
+        scope: LayerScope,
         source_module_config: &ModuleConfig,
         target_module_config: &ModuleConfig,
         relative_file_path: &Path,
     ) -> LayerCheckResult {
-        match (&source_module_config.layer, &target_module_config.layer) {
+        let layers = scope.layers;
+        match (scope.source_layer, scope.target_layer) {
             (Some(source_layer), Some(target_layer)) => {
                 let source_index = layers.iter().position(|layer| layer == source_layer);
                 let target_index = layers.iter().position(|layer| layer == target_layer);
@@ -51,18 +187,27 @@ impl<'a> InternalDependencyChecker<'a> {
                             LayerCheckResult::SameLayer
                         } else if source_index < target_index {
                             LayerCheckResult::Ok
+                        } else if target_module_config.unrestricted_layer {
+                            // Modules such as logging or feature flags may be used from any layer
+                            LayerCheckResult::UnrestrictedLayer
                         } else {
-                            LayerCheckResult::LayerViolation(Diagnostic::new_located_error(
-                                relative_file_path.to_path_buf(),
-                                file_module.line_number(dependency.offset()),
-                                DiagnosticDetails::Code(CodeDiagnostic::LayerViolation {
-                                    dependency: dependency.module_path().to_string(),
-                                    usage_module: source_module_config.path.clone(),
-                                    usage_layer: source_layer.clone(),
-                                    definition_module: target_module_config.path.clone(),
-                                    definition_layer: target_layer.clone(),
-                                }),
-                            ))
+                            LayerCheckResult::LayerViolation(
+                                self.with_usage_module_location(
+                                    Diagnostic::new_located_error(
+                                        relative_file_path.to_path_buf(),
+                                        file_module.line_number(dependency.offset()),
+                                        DiagnosticDetails::Code(CodeDiagnostic::LayerViolation {
+                                            dependency: dependency.module_path().to_string(),
+                                            usage_module: source_module_config.path.clone(),
+                                            usage_layer: source_layer.clone(),
+                                            definition_module: target_module_config.path.clone(),
+                                            definition_layer: target_layer.clone(),
+                                        }),
+                                    )
+                                    .with_span(file_module.span(dependency.offset())),
+                                    &source_module_config.path,
+                                ),
+                            )
                         }
                     }
                     // If either index is not found, the layer is unknown
@@ -91,6 +236,126 @@ impl<'a> InternalDependencyChecker<'a> {
         }
     }
 
+    /// Dependencies between groups of modules, such as domains or packages,
+    /// must be allowed by the group which contains the source module.
+    fn check_group_boundary<G: ModuleGroup>(
+        &self,
+        file_module: &FileModule,
+        dependency: &Dependency,
+        source_module_config: &ModuleConfig,
+        target_module_config: &ModuleConfig,
+        group_for_module: for<'c> fn(&'c ProjectConfig, &str) -> Option<&'c G>,
+    ) -> Option<Diagnostic> {
+        let source_group = group_for_module(self.project_config, &source_module_config.path)?;
+        let target_group = group_for_module(self.project_config, &target_module_config.path)?;
+        if source_group.group_path() == target_group.group_path()
+            || source_group.allows_group_dependency(target_group.group_path())
+        {
+            return None;
+        }
+
+        Some(
+            self.with_usage_module_location(
+                Diagnostic::new_located_error(
+                    file_module.relative_file_path().to_path_buf(),
+                    file_module.line_number(dependency.offset()),
+                    DiagnosticDetails::Code(G::violation(
+                        dependency.module_path().to_string(),
+                        (source_module_config.path.clone(), source_group),
+                        (target_module_config.path.clone(), target_group),
+                    )),
+                )
+                .with_span(file_module.span(dependency.offset())),
+                &source_module_config.path,
+            ),
+        )
+    }
+
+    fn suggest_for_undeclared_dependency(
+        &self,
+        file_module_config: &ModuleConfig,
+        dependency_module_config: &ModuleConfig,
+    ) -> Option<String> {
+        // The dependency may already be declared in the opposite direction
+        if dependency_module_config
+            .dependencies_iter()
+            .any(|dep| dep.path == file_module_config.path)
+        {
+            return Some(format!(
+                "Note: '{}' already depends on '{}'; the dependency may be declared in the wrong direction.",
+                dependency_module_config.path, file_module_config.path
+            ));
+        }
+
+        // The declared dependency may be a near-miss of the module path
+        closest_match(
+            &dependency_module_config.path,
+            file_module_config
+                .dependencies_iter()
+                .map(|dep| dep.path.as_str()),
+        )
+        .map(|declared| {
+            format!(
+                "Did you mean '{}'? '{}' declares a dependency on '{}'.",
+                dependency_module_config.path, file_module_config.path, declared
+            )
+        })
+    }
+
+    /// Existing usages of a deprecated module, which are declared in 'depends_on', are warnings.
+    /// Any other usage is a new dependency on the deprecated module, and is an error.
+    /// Modules without 'depends_on' have no declared dependencies, so their usages are warnings.
+    fn check_deprecated_module(
+        &self,
+        file_module: &FileModule,
+        dependency: &Dependency,
+        dependency_module_config: &ModuleConfig,
+    ) -> Option<Diagnostic> {
+        if !dependency_module_config.deprecated {
+            return None;
+        }
+
+        let file_module_config = file_module.module_config();
+        let declared = file_module_config.depends_on.is_none()
+            || file_module_config
+                .dependencies_iter()
+                .any(|dep| dep.path == dependency_module_config.path);
+        let relative_file_path = file_module.relative_file_path().to_path_buf();
+        let line_number = file_module.line_number(dependency.offset());
+        let dependency_path = dependency.module_path().to_string();
+        let usage_module = file_module_config.path.clone();
+        let definition_module = dependency_module_config.path.clone();
+        let successor = dependency_module_config.successor.clone();
+
+        let diagnostic = if declared {
+            Diagnostic::new_located_warning(
+                relative_file_path,
+                line_number,
+                DiagnosticDetails::Code(CodeDiagnostic::DeprecatedModuleUsage {
+                    dependency: dependency_path,
+                    usage_module,
+                    definition_module,
+                    successor,
+                }),
+            )
+        } else {
+            Diagnostic::new_located_error(
+                relative_file_path,
+                line_number,
+                DiagnosticDetails::Code(CodeDiagnostic::NewDeprecatedModuleDependency {
+                    dependency: dependency_path,
+                    usage_module,
+                    definition_module,
+                    successor,
+                }),
+            )
+        };
+        Some(self.with_usage_module_location(
+            diagnostic.with_span(file_module.span(dependency.offset())),
+            &file_module_config.path,
+        ))
+    }
+
     fn check_dependency_rules(
         &self,
         file_module: &FileModule,
@@ -103,23 +368,49 @@ impl<'a> InternalDependencyChecker<'a> {
             return Ok(vec![]);
         }
 
+        // Domain rules apply to every module within the domain, regardless of layers
+        if let Some(diagnostic) = self.check_group_boundary(
+            file_module,
+            dependency,
+            file_module_config,
+            dependency_module_config,
+            ProjectConfig::domain_for_module,
+        ) {
+            return Ok(vec![diagnostic]);
+        }
+
         let relative_file_path = file_module.relative_file_path();
         // Layer check should take precedence over other depends_on checks
         match self.check_layers(
             file_module,
             dependency,
-            layers,
+            self.layer_scope(layers, file_module_config, dependency_module_config),
             file_module_config,
             dependency_module_config,
             relative_file_path,
         ) {
-            LayerCheckResult::Ok => return Ok(vec![]), // Higher layers can unconditionally import lower layers
+            LayerCheckResult::Ok => {
+                // Higher layers can unconditionally import lower layers, unless the lower module is deprecated
+                return Ok(self
+                    .check_deprecated_module(file_module, dependency, dependency_module_config)
+                    .into_iter()
+                    .collect());
+            }
             LayerCheckResult::LayerViolation(e) | LayerCheckResult::UnknownLayer(e) => {
                 return Ok(vec![e]);
             }
-            LayerCheckResult::SameLayer | LayerCheckResult::LayerNotSpecified => (), // We need to do further processing to determine if the dependency is allowed
+            LayerCheckResult::SameLayer
+            | LayerCheckResult::LayerNotSpecified
+            | LayerCheckResult::UnrestrictedLayer => (), // We need to do further processing to determine if the dependency is allowed
         };
 
+        // Deprecation applies even to modules without dependency rules, and to utilities
+        if let Some(diagnostic) =
+            self.check_deprecated_module(file_module, dependency, dependency_module_config)
+        {
+            return Ok(vec![diagnostic]);
+        }
+
         if file_module_config.depends_on.is_none() {
             return Ok(vec![]);
         }
@@ -137,24 +428,36 @@ impl<'a> InternalDependencyChecker<'a> {
         {
             Some(DependencyConfig {
                 deprecated: true, ..
-            }) => Ok(vec![Diagnostic::new_located_warning(
-                relative_file_path.to_path_buf(),
-                file_module.line_number(dependency.offset()),
-                DiagnosticDetails::Code(CodeDiagnostic::DeprecatedDependency {
-                    dependency: dependency.module_path().to_string(),
-                    usage_module: file_nearest_module_path.to_string(),
-                    definition_module: dependency_nearest_module_path.to_string(),
-                }),
+            }) => Ok(vec![self.with_usage_module_location(
+                Diagnostic::new_located_warning(
+                    relative_file_path.to_path_buf(),
+                    file_module.line_number(dependency.offset()),
+                    DiagnosticDetails::Code(CodeDiagnostic::DeprecatedDependency {
+                        dependency: dependency.module_path().to_string(),
+                        usage_module: file_nearest_module_path.to_string(),
+                        definition_module: dependency_nearest_module_path.to_string(),
+                    }),
+                )
+                .with_span(file_module.span(dependency.offset())),
+                file_nearest_module_path,
             )]),
             Some(_) => Ok(vec![]),
-            None => Ok(vec![Diagnostic::new_located_error(
-                relative_file_path.to_path_buf(),
-                file_module.line_number(dependency.offset()),
-                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
-                    dependency: dependency.module_path().to_string(),
-                    usage_module: file_nearest_module_path.to_string(),
-                    definition_module: dependency_nearest_module_path.to_string(),
-                }),
+            None => Ok(vec![self.with_usage_module_location(
+                Diagnostic::new_located_error(
+                    relative_file_path.to_path_buf(),
+                    file_module.line_number(dependency.offset()),
+                    DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                        dependency: dependency.module_path().to_string(),
+                        usage_module: file_nearest_module_path.to_string(),
+                        definition_module: dependency_nearest_module_path.to_string(),
+                        suggestion: self.suggest_for_undeclared_dependency(
+                            file_module_config,
+                            dependency_module_config,
+                        ),
+                    }),
+                )
+                .with_span(file_module.span(dependency.offset())),
+                file_nearest_module_path,
             )]),
         }
     }
@@ -176,12 +479,21 @@ impl<'a> InternalDependencyChecker<'a> {
                 return Ok(vec![]);
             }
 
-            self.check_dependency_rules(
+            let mut diagnostics = self.check_dependency_rules(
                 file_module,
                 dependency,
                 dependency_module_config,
                 &self.project_config.layers,
-            )
+            )?;
+            // Package rules are coarse, and apply in addition to the rules of each module
+            diagnostics.extend(self.check_group_boundary(
+                file_module,
+                dependency,
+                file_module.module_config(),
+                dependency_module_config,
+                ProjectConfig::package_for_module,
+            ));
+            Ok(diagnostics)
         } else {
             Ok(vec![Diagnostic::new_global_error(
                 DiagnosticDetails::Configuration(ConfigurationDiagnostic::ModuleConfigNotFound {
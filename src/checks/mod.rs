@@ -1,9 +1,15 @@
+pub mod domain_isolation;
 pub mod external_dependency;
+pub mod file_cycles;
 pub mod ignore_directive;
 pub mod interface;
 pub mod internal_dependency;
+pub mod parse_error;
 
+pub use domain_isolation::check_domain_isolation;
 pub use external_dependency::ExternalDependencyChecker;
+pub use file_cycles::FileImportGraph;
 pub use ignore_directive::IgnoreDirectivePostProcessor;
 pub use interface::InterfaceChecker;
 pub use internal_dependency::InternalDependencyChecker;
+pub use parse_error::{check_parse_error, check_unsupported_syntax};
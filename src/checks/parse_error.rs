@@ -0,0 +1,49 @@
+use crate::config::{ProjectConfig, RuleSetting};
+use crate::diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, Severity};
+use crate::processors::FileModule;
+
+/// Report the syntax error in a file which could only be partially parsed,
+/// with the severity configured by the 'parse_errors' rule.
+pub fn check_parse_error(file_module: &FileModule, setting: &RuleSetting) -> Option<Diagnostic> {
+    let syntax_error = file_module.syntax_error.as_ref()?;
+    let severity: Severity = setting.try_into().ok()?;
+    Some(
+        Diagnostic::new_located(
+            severity,
+            DiagnosticDetails::Code(CodeDiagnostic::ParseError {
+                message: syntax_error.message.clone(),
+            }),
+            file_module.relative_file_path().to_path_buf(),
+            file_module.line_number(syntax_error.offset),
+        )
+        .with_span(file_module.span(syntax_error.offset)),
+    )
+}
+
+/// Warn about syntax which is newer than the 'python_version' of the file's source root.
+pub fn check_unsupported_syntax(
+    file_module: &FileModule,
+    project_config: &ProjectConfig,
+) -> Vec<Diagnostic> {
+    let Some(python_version) = project_config
+        .python_version_in(file_module.file.project_root, file_module.file.source_root)
+    else {
+        return vec![];
+    };
+    file_module
+        .unsupported_syntax
+        .iter()
+        .map(|unsupported| {
+            Diagnostic::new_located_warning(
+                file_module.relative_file_path().to_path_buf(),
+                file_module.line_number(unsupported.offset),
+                DiagnosticDetails::Code(CodeDiagnostic::UnsupportedSyntax {
+                    feature: unsupported.feature.to_string(),
+                    minimum_version: unsupported.minimum_version.to_string(),
+                    python_version: python_version.to_string(),
+                }),
+            )
+            .with_span(file_module.span(unsupported.offset))
+        })
+        .collect()
+}
@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::Path;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = ["CODEOWNERS", ".github/CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug)]
+struct CodeownersRule {
+    matcher: GlobSet,
+    owners: Vec<String>,
+}
+
+/// Ownership rules parsed from a GitHub-style CODEOWNERS file.
+#[derive(Debug, Default)]
+pub struct Codeowners {
+    rules: Vec<CodeownersRule>,
+}
+
+/// Translate a CODEOWNERS pattern (gitignore syntax) into globs relative to the project root.
+fn pattern_to_globs(pattern: &str) -> Vec<String> {
+    // Patterns are anchored to the root if they begin with, or contain, a separator
+    let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+    let mut glob = pattern.trim_start_matches('/').to_string();
+    if !anchored {
+        glob = format!("**/{}", glob);
+    }
+    if let Some(directory) = glob.strip_suffix('/') {
+        // Trailing separators only match directories (and their contents)
+        vec![format!("{}/**", directory)]
+    } else {
+        vec![format!("{}/**", glob), glob]
+    }
+}
+
+fn build_matcher(pattern: &str) -> Option<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in pattern_to_globs(pattern) {
+        builder.add(
+            GlobBuilder::new(&glob)
+                .literal_separator(true)
+                .build()
+                .ok()?,
+        );
+    }
+    builder.build().ok()
+}
+
+impl Codeowners {
+    /// Find and parse the CODEOWNERS file in any of the locations supported by GitHub.
+    pub fn find(project_root: &Path) -> Option<Self> {
+        CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|location| project_root.join(location))
+            .find(|path| path.is_file())
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| Self::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .filter_map(|line| {
+                let line = line.split('#').next().unwrap_or_default().trim();
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                // Invalid patterns are skipped, as they are by GitHub
+                let matcher = build_matcher(pattern)?;
+                Some(CodeownersRule {
+                    matcher,
+                    owners: parts.map(|owner| owner.to_string()).collect(),
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// The owners of a path relative to the project root.
+    /// The last matching rule takes precedence, and may leave the path without owners.
+    pub fn owners(&self, relative_path: &Path) -> Option<&[String]> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matcher.is_match(relative_path))
+            .map(|rule| rule.owners.as_slice())
+            .filter(|owners| !owners.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const CODEOWNERS: &str = r#"
+# Default owners
+*       @platform-team
+
+/payments/          @payments-team  # Payments
+docs/*.md           @docs-team
+utils.py            @utils-team
+/payments/legacy/
+"#;
+
+    #[rstest]
+    #[case("setup.py", Some("@platform-team"))]
+    #[case("payments/__init__.py", Some("@payments-team"))]
+    #[case("payments/api/views.py", Some("@payments-team"))]
+    #[case("src/payments/views.py", Some("@platform-team"))]
+    #[case("docs/index.md", Some("@docs-team"))]
+    #[case("docs/guides/index.md", Some("@platform-team"))]
+    #[case("payments/utils.py", Some("@utils-team"))]
+    #[case("payments/legacy/old.py", None)]
+    fn test_codeowners(#[case] path: &str, #[case] expected: Option<&str>) {
+        let codeowners = Codeowners::parse(CODEOWNERS);
+        assert_eq!(
+            codeowners
+                .owners(Path::new(path))
+                .map(|owners| owners.join(" ")),
+            expected.map(|owner| owner.to_string())
+        );
+    }
+}
@@ -1,29 +1,38 @@
-use crate::checks::{ExternalDependencyChecker, IgnoreDirectivePostProcessor};
+use crate::checks::{check_parse_error, ExternalDependencyChecker, IgnoreDirectivePostProcessor};
 use crate::config::ProjectConfig;
 use crate::diagnostics::{
-    CodeDiagnostic, ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, DiagnosticError,
-    DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult,
+    dedup_diagnostics, sort_diagnostics, CodeDiagnostic, ConfigurationDiagnostic, Diagnostic,
+    DiagnosticDetails, DiagnosticError, DiagnosticPipeline, FileChecker, FileProcessor,
+    Result as DiagnosticResult,
 };
-use crate::external::parsing::{parse_pyproject_toml, ProjectInfo};
-use crate::filesystem::{walk_pyfiles, walk_pyprojects, ProjectFile};
+use crate::external::parsing::{normalize_package_name, parse_pyproject_toml, ProjectInfo};
+use crate::filesystem::{self as fs, walk_pyfiles, walk_pyprojects, ProjectFile};
 use crate::interrupt::check_interrupt;
+use crate::modules::{build_module_tree, ModuleTree};
 use crate::processors::file_module::FileModule;
 use crate::processors::import::with_distribution_names;
 use crate::processors::ExternalDependencyExtractor;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use dashmap::DashSet;
+use itertools::Itertools;
 use rayon::prelude::*;
 
 use super::error::CheckError;
 
 pub type Result<T> = std::result::Result<T, CheckError>;
 
+// For each layer with a budget, the external packages its modules import, and which modules import each one
+type LayerUsages = BTreeMap<String, BTreeMap<String, BTreeSet<String>>>;
+
 struct CheckExternalPipeline<'a> {
+    project_config: &'a ProjectConfig,
     module_mappings: &'a HashMap<String, Vec<String>>,
     excluded_external_modules: &'a HashSet<String>,
     seen_dependencies: DashSet<String>,
+    layer_usages: &'a Mutex<LayerUsages>,
     dependency_extractor: ExternalDependencyExtractor<'a>,
     dependency_checker: ExternalDependencyChecker<'a>,
     ignore_directive_post_processor: IgnoreDirectivePostProcessor<'a>,
@@ -32,17 +41,25 @@ struct CheckExternalPipeline<'a> {
 impl<'a> CheckExternalPipeline<'a> {
     pub fn new(
         source_roots: &'a [PathBuf],
+        module_tree: &'a ModuleTree,
         project_config: &'a ProjectConfig,
         project_info: &'a ProjectInfo,
         module_mappings: &'a HashMap<String, Vec<String>>,
         stdlib_modules: &'a HashSet<String>,
         excluded_external_modules: &'a HashSet<String>,
+        layer_usages: &'a Mutex<LayerUsages>,
     ) -> Self {
         Self {
+            project_config,
             module_mappings,
             excluded_external_modules,
             seen_dependencies: DashSet::new(),
-            dependency_extractor: ExternalDependencyExtractor::new(source_roots, project_config),
+            layer_usages,
+            dependency_extractor: ExternalDependencyExtractor::new(
+                source_roots,
+                module_tree,
+                project_config,
+            ),
             dependency_checker: ExternalDependencyChecker::new(
                 project_info,
                 module_mappings,
@@ -52,6 +69,66 @@ impl<'a> CheckExternalPipeline<'a> {
             ignore_directive_post_processor: IgnoreDirectivePostProcessor::new(project_config),
         }
     }
+
+    fn record_layer_usages(&'a self, processed_file: &FileModule<'a>) {
+        let Some(module_config) = processed_file.module.config.as_ref() else {
+            return;
+        };
+        // Modules exempt from layer restrictions don't count towards their layer's budget
+        let Some(layer) = module_config
+            .layer
+            .as_ref()
+            .filter(|_| !module_config.unrestricted_layer)
+            .filter(|layer| {
+                self.project_config
+                    .external
+                    .layer_budgets
+                    .contains_key(*layer)
+            })
+        else {
+            return;
+        };
+
+        let packages = self.dependency_checker.external_packages(processed_file);
+        if packages.is_empty() {
+            return;
+        }
+        let mut layer_usages = self.layer_usages.lock().unwrap();
+        let usages = layer_usages.entry(layer.clone()).or_default();
+        for package in packages {
+            usages
+                .entry(package)
+                .or_default()
+                .insert(module_config.path.clone());
+        }
+    }
+}
+
+fn check_layer_budgets(
+    project_config: &ProjectConfig,
+    layer_usages: LayerUsages,
+) -> Vec<Diagnostic> {
+    layer_usages
+        .into_iter()
+        .filter_map(|(layer, packages)| {
+            let budget = *project_config.external.layer_budgets.get(&layer)?;
+            if packages.len() <= budget {
+                return None;
+            }
+            Some(Diagnostic::new_global_error(DiagnosticDetails::Code(
+                CodeDiagnostic::LayerExternalBudgetExceeded {
+                    layer,
+                    budget,
+                    usages: packages
+                        .into_iter()
+                        .map(|(package, modules)| {
+                            format!("'{}' ({})", package, modules.into_iter().join(", "))
+                        })
+                        .collect(),
+                },
+            )))
+        })
+        .collect()
 }
 
 impl<'a> FileProcessor<'a, ProjectFile<'a>> for CheckExternalPipeline<'a> {
@@ -82,7 +159,12 @@ impl<'a> FileChecker<'a> for CheckExternalPipeline<'a> {
 
     fn check(&'a self, processed_file: &Self::ProcessedFile) -> DiagnosticResult<Self::Output> {
         let mut diagnostics = Vec::new();
+        diagnostics.extend(check_parse_error(
+            processed_file,
+            &self.project_config.rules.parse_errors,
+        ));
         diagnostics.extend(self.dependency_checker.check(processed_file)?);
+        self.record_layer_usages(processed_file);
 
         self.ignore_directive_post_processor.process_diagnostics(
             &processed_file.ignore_directives,
@@ -99,12 +181,37 @@ pub fn check(
     project_config: &ProjectConfig,
     module_mappings: &HashMap<String, Vec<String>>,
     stdlib_modules: &[String],
+    project_distributions: &[String],
 ) -> Result<Vec<Diagnostic>> {
     let stdlib_modules: HashSet<String> = stdlib_modules.iter().cloned().collect();
-    let excluded_external_modules: HashSet<String> =
-        project_config.external.exclude.iter().cloned().collect();
+    // Distributions installed in editable mode from within a source root are part of the project,
+    // so they are excluded just like the configured external modules
+    let excluded_external_modules: HashSet<String> = project_config
+        .external
+        .exclude
+        .iter()
+        .cloned()
+        .chain(
+            project_distributions
+                .iter()
+                .map(|dist_name| normalize_package_name(dist_name)),
+        )
+        .collect();
     let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    // Invalid modules are reported by 'tach check', here they only lose their per-module settings
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    // Circular dependencies are irrelevant to external imports
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )?;
 
+    let layer_usages = Mutex::new(LayerUsages::new());
     let diagnostics = walk_pyprojects(project_root.to_string_lossy().as_ref())
         .par_bridge()
         .flat_map(|pyproject| {
@@ -122,11 +229,13 @@ pub fn check(
             };
             let pipeline = CheckExternalPipeline::new(
                 &source_roots,
+                &module_tree,
                 project_config,
                 &project_info,
                 module_mappings,
                 &stdlib_modules,
                 &excluded_external_modules,
+                &layer_usages,
             );
             let mut project_diagnostics: Vec<Diagnostic> = project_info
                 .source_paths
@@ -216,13 +325,20 @@ pub fn check(
         return Err(CheckError::Interrupt);
     }
 
-    Ok(diagnostics.collect())
+    let mut diagnostics: Vec<Diagnostic> = diagnostics.collect();
+    diagnostics.extend(check_layer_budgets(
+        project_config,
+        layer_usages.into_inner().unwrap(),
+    ));
+    sort_diagnostics(&mut diagnostics);
+    dedup_diagnostics(&mut diagnostics);
+    Ok(diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::ProjectConfig;
+    use crate::config::{ExternalDependencyConfig, ModuleConfig, ProjectConfig, SourceRootConfig};
     use crate::diagnostics::Severity;
     use crate::tests::fixtures::example_dir;
     use rstest::*;
@@ -240,7 +356,7 @@ mod tests {
                 "src/pack-g/src",
             ]
             .iter()
-            .map(PathBuf::from)
+            .map(SourceRootConfig::new)
             .collect(),
             ignore_type_checking_imports: true,
             ..Default::default()
@@ -259,7 +375,7 @@ mod tests {
         module_mapping: HashMap<String, Vec<String>>,
     ) {
         let project_root = example_dir.join("multi_package");
-        let result = check(&project_root, &project_config, &module_mapping, &[]).unwrap();
+        let result = check(&project_root, &project_config, &module_mapping, &[], &[]).unwrap();
         assert_eq!(result.len(), 1);
         assert!(matches!(
             result[0],
@@ -282,11 +398,12 @@ mod tests {
         project_config: ProjectConfig,
     ) {
         let project_root = example_dir.join("multi_package");
-        let result = check(&project_root, &project_config, &HashMap::new(), &[]).unwrap();
+        let result = check(&project_root, &project_config, &HashMap::new(), &[], &[]).unwrap();
         assert_eq!(result.len(), 3);
         assert!(result.iter().any(|d| d.details()
             == &DiagnosticDetails::Code(CodeDiagnostic::UndeclaredExternalDependency {
-                dependency: "git".to_string()
+                dependency: "git".to_string(),
+                usage_module: None,
             })));
         assert!(result.iter().any(|d| d.details()
             == &DiagnosticDetails::Code(CodeDiagnostic::UnusedExternalDependency {
@@ -297,4 +414,145 @@ mod tests {
                 package_module_name: "unused".to_string()
             })));
     }
+
+    #[rstest]
+    fn check_external_dependencies_stub_only_package() {
+        // Hidden directories (like the default '.tmp' prefix) are not searched
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let project_root = project.path();
+        std::fs::create_dir_all(project_root.join("src/app")).unwrap();
+        std::fs::write(
+            project_root.join("pyproject.toml"),
+            "[project]\nname = \"app\"\ndependencies = [\"requests\", \"foo-stubs\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project_root.join("src/app/__init__.py"),
+            "import foo\nimport requests\n",
+        )
+        .unwrap();
+        let module_mapping = HashMap::from([(
+            "requests".to_string(),
+            vec!["requests".to_string(), "types-requests".to_string()],
+        )]);
+        let project_config = ProjectConfig {
+            source_roots: vec![SourceRootConfig::new("src")],
+            ..Default::default()
+        };
+
+        let result = check(project_root, &project_config, &module_mapping, &[], &[]).unwrap();
+        assert!(result.is_empty(), "{:?}", result);
+    }
+
+    #[rstest]
+    fn check_external_dependencies_module_allowlist() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let project_root = project.path();
+        std::fs::create_dir_all(project_root.join("src/app/billing")).unwrap();
+        std::fs::write(
+            project_root.join("pyproject.toml"),
+            "[project]\nname = \"app\"\ndependencies = [\"requests\", \"httpx\"]\n",
+        )
+        .unwrap();
+        std::fs::write(project_root.join("src/app/__init__.py"), "import httpx\n").unwrap();
+        std::fs::write(
+            project_root.join("src/app/billing/__init__.py"),
+            "import requests\nimport httpx\nimport foo\n",
+        )
+        .unwrap();
+        let project_config = ProjectConfig {
+            source_roots: vec![SourceRootConfig::new("src")],
+            modules: vec![ModuleConfig {
+                path: "app.billing".to_string(),
+                depends_on_external: Some(vec!["Requests".to_string()]),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let result = check(project_root, &project_config, &HashMap::new(), &[], &[]).unwrap();
+        let details: Vec<&DiagnosticDetails> = result.iter().map(|d| d.details()).collect();
+        assert_eq!(details.len(), 2, "{:?}", details);
+        assert!(details.contains(&&DiagnosticDetails::Code(
+            CodeDiagnostic::UndeclaredModuleExternalDependency {
+                dependency: "httpx".to_string(),
+                usage_module: "app.billing".to_string(),
+            }
+        )));
+        assert!(details.contains(&&DiagnosticDetails::Code(
+            CodeDiagnostic::UndeclaredExternalDependency {
+                dependency: "foo".to_string(),
+                usage_module: Some("app.billing".to_string()),
+            }
+        )));
+    }
+
+    #[test]
+    fn check_external_dependencies_layer_budget() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let project_root = project.path();
+        std::fs::create_dir_all(project_root.join("src/app/domain")).unwrap();
+        std::fs::create_dir_all(project_root.join("src/app/infra")).unwrap();
+        std::fs::write(
+            project_root.join("pyproject.toml"),
+            "[project]\nname = \"app\"\ndependencies = [\"requests\", \"httpx\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project_root.join("src/app/domain/__init__.py"),
+            "import os\nimport requests\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project_root.join("src/app/infra/__init__.py"),
+            "import requests\nimport httpx\n",
+        )
+        .unwrap();
+        let project_config = ProjectConfig {
+            source_roots: vec![SourceRootConfig::new("src")],
+            layers: vec!["infra".to_string(), "domain".to_string()],
+            modules: vec![
+                ModuleConfig {
+                    path: "app.domain".to_string(),
+                    layer: Some("domain".to_string()),
+                    ..Default::default()
+                },
+                ModuleConfig {
+                    path: "app.infra".to_string(),
+                    layer: Some("infra".to_string()),
+                    ..Default::default()
+                },
+            ],
+            external: ExternalDependencyConfig {
+                layer_budgets: BTreeMap::from([
+                    ("domain".to_string(), 0),
+                    ("infra".to_string(), 2),
+                ]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = check(project_root, &project_config, &HashMap::new(), &[], &[]).unwrap();
+        let details: Vec<&DiagnosticDetails> = result.iter().map(|d| d.details()).collect();
+        assert_eq!(
+            details,
+            vec![&DiagnosticDetails::Code(
+                CodeDiagnostic::LayerExternalBudgetExceeded {
+                    layer: "domain".to_string(),
+                    budget: 0,
+                    usages: vec!["'requests' (app.domain)".to_string()],
+                }
+            )]
+        );
+    }
 }
@@ -1,17 +1,25 @@
 use std::{
     path::{Path, PathBuf},
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
 };
 
 use rayon::prelude::*;
 
 use super::error::CheckError;
 use crate::{
-    checks::{IgnoreDirectivePostProcessor, InterfaceChecker, InternalDependencyChecker},
+    checks::{
+        check_domain_isolation, check_parse_error, check_unsupported_syntax,
+        ignore_directive::{get_ignore_directive_usages, IgnoreDirectiveUsage},
+        FileImportGraph, IgnoreDirectivePostProcessor, InterfaceChecker, InternalDependencyChecker,
+    },
     config::ProjectConfig,
     diagnostics::{
-        ConfigurationDiagnostic, Diagnostic, DiagnosticDetails, DiagnosticError,
-        DiagnosticPipeline, FileChecker, FileProcessor, Result as DiagnosticResult,
+        dedup_diagnostics, sort_diagnostics, ConfigurationDiagnostic, Diagnostic,
+        DiagnosticDetails, DiagnosticError, DiagnosticPipeline, FileChecker, FileProcessor,
+        Result as DiagnosticResult, Severity,
     },
     exclusion::set_excluded_paths,
     filesystem::{self as fs, ProjectFile},
@@ -23,11 +31,14 @@ use crate::{
 pub type Result<T> = std::result::Result<T, CheckError>;
 
 struct CheckInternalPipeline<'a> {
+    project_config: &'a ProjectConfig,
     found_imports: &'a AtomicBool,
     dependency_extractor: InternalDependencyExtractor<'a>,
     dependency_checker: Option<InternalDependencyChecker<'a>>,
     interface_checker: Option<InterfaceChecker<'a>>,
     ignore_directive_post_processor: IgnoreDirectivePostProcessor<'a>,
+    ignore_usages: Option<&'a Mutex<Vec<IgnoreDirectiveUsage>>>,
+    file_import_graph: Option<&'a FileImportGraph<'a>>,
 }
 
 impl<'a> CheckInternalPipeline<'a> {
@@ -38,6 +49,7 @@ impl<'a> CheckInternalPipeline<'a> {
         found_imports: &'a AtomicBool,
     ) -> Self {
         Self {
+            project_config,
             found_imports,
             dependency_extractor: InternalDependencyExtractor::new(
                 source_roots,
@@ -47,6 +59,8 @@ impl<'a> CheckInternalPipeline<'a> {
             dependency_checker: None,
             interface_checker: None,
             ignore_directive_post_processor: IgnoreDirectivePostProcessor::new(project_config),
+            ignore_usages: None,
+            file_import_graph: None,
         }
     }
 
@@ -65,6 +79,22 @@ impl<'a> CheckInternalPipeline<'a> {
         self.interface_checker = interface_checker;
         self
     }
+
+    pub fn with_ignore_usages(
+        mut self,
+        ignore_usages: Option<&'a Mutex<Vec<IgnoreDirectiveUsage>>>,
+    ) -> Self {
+        self.ignore_usages = ignore_usages;
+        self
+    }
+
+    pub fn with_file_import_graph(
+        mut self,
+        file_import_graph: Option<&'a FileImportGraph<'a>>,
+    ) -> Self {
+        self.file_import_graph = file_import_graph;
+        self
+    }
 }
 
 impl<'a> FileProcessor<'a, ProjectFile<'a>> for CheckInternalPipeline<'a> {
@@ -91,6 +121,14 @@ impl<'a> FileChecker<'a> for CheckInternalPipeline<'a> {
 
     fn check(&'a self, processed_file: &Self::ProcessedFile) -> DiagnosticResult<Self::Output> {
         let mut diagnostics = Vec::new();
+        diagnostics.extend(check_parse_error(
+            processed_file,
+            &self.project_config.rules.parse_errors,
+        ));
+        diagnostics.extend(check_unsupported_syntax(
+            processed_file,
+            self.project_config,
+        ));
         diagnostics.extend(
             self.dependency_checker
                 .as_ref()
@@ -103,6 +141,21 @@ impl<'a> FileChecker<'a> for CheckInternalPipeline<'a> {
                 .map_or(Ok(vec![]), |checker| checker.check(processed_file))?,
         );
 
+        if let Some(file_import_graph) = self.file_import_graph {
+            file_import_graph.record(processed_file);
+        }
+
+        if let Some(ignore_usages) = self.ignore_usages {
+            ignore_usages
+                .lock()
+                .unwrap()
+                .extend(get_ignore_directive_usages(
+                    &processed_file.ignore_directives,
+                    &diagnostics,
+                    processed_file.relative_file_path(),
+                ));
+        }
+
         self.ignore_directive_post_processor.process_diagnostics(
             &processed_file.ignore_directives,
             &mut diagnostics,
@@ -113,6 +166,81 @@ impl<'a> FileChecker<'a> for CheckInternalPipeline<'a> {
     }
 }
 
+/// Restricts a check to the files of some modules, for fast feedback within one area of a project.
+/// The whole module tree is still built, so dependencies on modules outside of the scope are checked as usual.
+#[derive(Debug, Default, Clone)]
+pub struct CheckScope {
+    pub modules: Vec<String>,
+    pub domains: Vec<String>,
+    // Absolute paths of the only files to check, such as those open in an editor
+    pub files: Vec<PathBuf>,
+}
+
+impl CheckScope {
+    pub fn new(modules: Vec<String>, domains: Vec<String>) -> Self {
+        Self {
+            modules,
+            domains,
+            files: vec![],
+        }
+    }
+
+    pub fn with_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.files = files;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.domains.is_empty() && self.files.is_empty()
+    }
+
+    fn validate(&self, project_config: &ProjectConfig) -> Result<()> {
+        if let Some(module) = self
+            .modules
+            .iter()
+            .find(|module| !project_config.all_modules().any(|m| &m.path == *module))
+        {
+            return Err(CheckError::UnknownScopeModule(module.clone()));
+        }
+        if let Some(domain) = self.domains.iter().find(|domain| {
+            !project_config
+                .domains
+                .iter()
+                .any(|d| &d.location.mod_path == *domain)
+        }) {
+            return Err(CheckError::UnknownScopeDomain(domain.clone()));
+        }
+        Ok(())
+    }
+
+    fn contains_module(&self, project_config: &ProjectConfig, module_path: &str) -> bool {
+        self.modules.iter().any(|module| module == module_path)
+            || project_config.domains.iter().any(|domain| {
+                self.domains.contains(&domain.location.mod_path)
+                    && domain.contains_module(module_path)
+            })
+    }
+
+    fn contains_file(
+        &self,
+        project_config: &ProjectConfig,
+        source_roots: &[PathBuf],
+        module_tree: &ModuleTree,
+        file_path: &Path,
+    ) -> bool {
+        if !self.files.is_empty() && !self.files.iter().any(|file| file == file_path) {
+            return false;
+        }
+        if self.modules.is_empty() && self.domains.is_empty() {
+            return true;
+        }
+        fs::file_to_module_path(source_roots, file_path)
+            .ok()
+            .and_then(|mod_path| module_tree.find_nearest(&mod_path))
+            .is_some_and(|module| self.contains_module(project_config, &module.full_path))
+    }
+}
+
 pub fn check(
     project_root: PathBuf,
     project_config: &ProjectConfig,
@@ -120,6 +248,106 @@ pub fn check(
     interfaces: bool,
     exclude_paths: Vec<String>,
 ) -> Result<Vec<Diagnostic>> {
+    check_with_callback(
+        project_root,
+        project_config,
+        dependencies,
+        interfaces,
+        exclude_paths,
+        &CheckScope::default(),
+        None::<fn(usize)>,
+        |_, _| true,
+    )
+}
+
+/// Run the check, calling 'on_file_diagnostics' with the diagnostics for each file as soon as they are produced.
+/// If the callback returns false, no further files are checked and the diagnostics found so far are returned.
+/// If given, 'on_total' is called with the number of files to check before any file is checked.
+/// Counting requires walking the project up front, so files are otherwise checked as they are found.
+/// Only files within 'scope' are checked, unless it is empty.
+#[allow(clippy::too_many_arguments)]
+pub fn check_with_callback<T, F>(
+    project_root: PathBuf,
+    project_config: &ProjectConfig,
+    dependencies: bool,
+    interfaces: bool,
+    exclude_paths: Vec<String>,
+    scope: &CheckScope,
+    on_total: Option<T>,
+    on_file_diagnostics: F,
+) -> Result<Vec<Diagnostic>>
+where
+    T: FnOnce(usize),
+    F: Fn(&Path, &[Diagnostic]) -> bool + Sync,
+{
+    check_files(
+        project_root,
+        project_config,
+        dependencies,
+        interfaces,
+        exclude_paths,
+        scope,
+        on_total,
+        on_file_diagnostics,
+        None,
+    )
+}
+
+/// Run the check, and report each ignore directive along with the violations it suppressed.
+pub fn check_ignore_directives(
+    project_root: PathBuf,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<IgnoreDirectiveUsage>> {
+    let ignore_usages = Mutex::new(Vec::new());
+    check_files(
+        project_root,
+        project_config,
+        true,
+        true,
+        exclude_paths,
+        &CheckScope::default(),
+        None::<fn(usize)>,
+        |_, _| true,
+        Some(&ignore_usages),
+    )?;
+
+    let mut ignore_usages = ignore_usages.into_inner().unwrap();
+    // Files are checked in parallel, so their directives arrive in no particular order
+    ignore_usages.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then(a.line_number.cmp(&b.line_number))
+            .then(a.redundant.cmp(&b.redundant))
+    });
+    Ok(ignore_usages)
+}
+
+/// Walk the Python files in 'source_root', stopping as soon as the check is interrupted or stopped early.
+fn walk_pyfiles_until_stopped<'a>(
+    source_root: &Path,
+    stopped_early: &'a AtomicBool,
+) -> impl Iterator<Item = PathBuf> + 'a {
+    fs::walk_pyfiles(&source_root.display().to_string())
+        .take_while(move |_| check_interrupt().is_ok() && !stopped_early.load(Ordering::Relaxed))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_files<T, F>(
+    project_root: PathBuf,
+    project_config: &ProjectConfig,
+    dependencies: bool,
+    interfaces: bool,
+    exclude_paths: Vec<String>,
+    scope: &CheckScope,
+    on_total: Option<T>,
+    on_file_diagnostics: F,
+    ignore_usages: Option<&Mutex<Vec<IgnoreDirectiveUsage>>>,
+) -> Result<Vec<Diagnostic>>
+where
+    T: FnOnce(usize),
+    F: Fn(&Path, &[Diagnostic]) -> bool + Sync,
+{
     if !dependencies && !interfaces {
         return Err(CheckError::NoChecksEnabled());
     }
@@ -129,9 +357,11 @@ pub fn check(
             project_root.display().to_string(),
         ));
     }
+    scope.validate(project_config)?;
 
     let mut warnings = Vec::new();
     let found_imports = AtomicBool::new(false);
+    let stopped_early = AtomicBool::new(false);
     let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
     let source_roots: Vec<PathBuf> = project_config.prepend_roots(&project_root);
     let (valid_modules, invalid_modules) = fs::validate_project_modules(
@@ -147,6 +377,10 @@ pub fn check(
         ));
     }
 
+    if let Ok(severity) = (&project_config.rules.domain_isolation).try_into() {
+        warnings.extend(check_domain_isolation(project_config, severity));
+    }
+
     check_interrupt().map_err(|_| CheckError::Interrupt)?;
     let module_tree = build_module_tree(
         &source_roots,
@@ -155,9 +389,10 @@ pub fn check(
         project_config.root_module.clone(),
     )?;
 
-    set_excluded_paths(
+    let _excluded_paths = set_excluded_paths(
         Path::new(&project_root),
         &exclude_paths,
+        &project_config.include,
         project_config.use_regex_matching,
     )?;
 
@@ -168,84 +403,434 @@ pub fn check(
     };
 
     let interface_checker = if interfaces {
-        let interface_checker = InterfaceChecker::new(project_config, &module_tree);
+        let mut interface_checker = InterfaceChecker::new(project_config, &module_tree);
+        if project_config.strict_exports {
+            interface_checker =
+                interface_checker.with_declared_exports(&valid_modules, &source_roots);
+        }
         // This is expensive
         Some(interface_checker.with_type_check_cache(&valid_modules, &source_roots)?)
     } else {
         None
     };
 
+    // File cycles can only be found once every file has been checked
+    let file_cycles: Option<Severity> = (&project_config.rules.file_cycles).try_into().ok();
+    let file_import_graph = FileImportGraph::new(&source_roots);
+
     let pipeline =
         CheckInternalPipeline::new(project_config, &source_roots, &module_tree, &found_imports)
             .with_dependency_checker(dependency_checker)
-            .with_interface_checker(interface_checker);
-
-    let diagnostics = source_roots.par_iter().flat_map(|source_root| {
-        fs::walk_pyfiles(&source_root.display().to_string())
-            .par_bridge()
-            .flat_map(|file_path| {
-                if check_interrupt().is_err() {
-                    // Since files are being processed in parallel,
-                    // this will essentially short-circuit all remaining files.
-                    // Then, we check for an interrupt right after, and return the Err if it is set
-                    return vec![];
-                }
-
-                let project_file =
-                    match ProjectFile::try_new(&project_root, source_root, &file_path) {
-                        Ok(project_file) => project_file,
-                        Err(_) => {
-                            return vec![Diagnostic::new_global_warning(
-                                DiagnosticDetails::Configuration(
-                                    ConfigurationDiagnostic::SkippedFileIoError {
-                                        file_path: file_path.display().to_string(),
-                                    },
-                                ),
-                            )]
-                        }
-                    };
-
-                match pipeline.diagnostics(project_file) {
-                    Ok(diagnostics) => diagnostics,
-                    Err(DiagnosticError::Io(_)) | Err(DiagnosticError::Filesystem(_)) => {
-                        vec![Diagnostic::new_global_warning(
-                            DiagnosticDetails::Configuration(
-                                ConfigurationDiagnostic::SkippedFileIoError {
-                                    file_path: file_path.display().to_string(),
-                                },
-                            ),
-                        )]
-                    }
-                    Err(DiagnosticError::ImportParse(_)) => {
-                        vec![Diagnostic::new_global_warning(
-                            DiagnosticDetails::Configuration(
-                                ConfigurationDiagnostic::SkippedFileSyntaxError {
-                                    file_path: file_path.display().to_string(),
-                                },
-                            ),
-                        )]
-                    }
-                    Err(_) => vec![Diagnostic::new_global_warning(
-                        DiagnosticDetails::Configuration(
-                            ConfigurationDiagnostic::SkippedUnknownError {
-                                file_path: file_path.display().to_string(),
-                            },
-                        ),
-                    )],
-                }
+            .with_interface_checker(interface_checker)
+            .with_ignore_usages(ignore_usages)
+            .with_file_import_graph(file_cycles.is_some().then_some(&file_import_graph));
+
+    let is_in_scope = |source_root: &PathBuf, file_path: &PathBuf| {
+        scope.contains_file(
+            project_config,
+            &source_roots,
+            &module_tree,
+            &source_root.join(file_path),
+        )
+    };
+    let check_file = |source_root: &PathBuf, file_path: PathBuf| -> Vec<Diagnostic> {
+        if check_interrupt().is_err() || stopped_early.load(Ordering::Relaxed) {
+            // Since files are being processed in parallel,
+            // this will essentially short-circuit all remaining files.
+            // Then, we check for an interrupt right after, and return the Err if it is set
+            return vec![];
+        }
+
+        let project_file = match ProjectFile::try_new(&project_root, source_root, &file_path) {
+            Ok(project_file) => project_file,
+            Err(_) => {
+                return vec![Diagnostic::new_global_warning(
+                    DiagnosticDetails::Configuration(ConfigurationDiagnostic::SkippedFileIoError {
+                        file_path: file_path.display().to_string(),
+                    }),
+                )]
+            }
+        };
+
+        let file_diagnostics = match pipeline.diagnostics(project_file) {
+            Ok(diagnostics) => diagnostics,
+            Err(DiagnosticError::Io(_)) | Err(DiagnosticError::Filesystem(_)) => {
+                vec![Diagnostic::new_global_warning(
+                    DiagnosticDetails::Configuration(ConfigurationDiagnostic::SkippedFileIoError {
+                        file_path: file_path.display().to_string(),
+                    }),
+                )]
+            }
+            Err(DiagnosticError::ImportParse(_)) => {
+                vec![Diagnostic::new_global_warning(
+                    DiagnosticDetails::Configuration(
+                        ConfigurationDiagnostic::SkippedFileSyntaxError {
+                            file_path: file_path.display().to_string(),
+                        },
+                    ),
+                )]
+            }
+            Err(_) => vec![Diagnostic::new_global_warning(
+                DiagnosticDetails::Configuration(ConfigurationDiagnostic::SkippedUnknownError {
+                    file_path: file_path.display().to_string(),
+                }),
+            )],
+        };
+
+        if !on_file_diagnostics(&source_root.join(&file_path), &file_diagnostics) {
+            stopped_early.store(true, Ordering::Relaxed);
+        }
+        file_diagnostics
+    };
+
+    let mut final_diagnostics: Vec<Diagnostic> = match on_total {
+        Some(on_total) => {
+            // Collecting the files up front allows reporting how many will be checked
+            let files: Vec<(&PathBuf, PathBuf)> = source_roots
+                .iter()
+                .flat_map(|source_root| {
+                    walk_pyfiles_until_stopped(source_root, &stopped_early)
+                        .map(move |file_path| (source_root, file_path))
+                })
+                .filter(|(source_root, file_path)| is_in_scope(source_root, file_path))
+                .collect();
+            check_interrupt().map_err(|_| CheckError::Interrupt)?;
+            on_total(files.len());
+            files
+                .into_par_iter()
+                .flat_map(|(source_root, file_path)| check_file(source_root, file_path))
+                .collect()
+        }
+        None => source_roots
+            .par_iter()
+            .flat_map(|source_root| {
+                walk_pyfiles_until_stopped(source_root, &stopped_early)
+                    .filter(|file_path| is_in_scope(source_root, file_path))
+                    .par_bridge()
+                    .flat_map(|file_path| check_file(source_root, file_path))
             })
-    });
+            .collect(),
+    };
 
     if check_interrupt().is_err() {
         return Err(CheckError::Interrupt);
     }
 
-    let mut final_diagnostics: Vec<Diagnostic> = diagnostics.collect();
-    if !found_imports.load(Ordering::Relaxed) {
+    if let Some(severity) = file_cycles {
+        if !stopped_early.load(Ordering::Relaxed) {
+            final_diagnostics.extend(file_import_graph.check(severity));
+        }
+    }
+    if !found_imports.load(Ordering::Relaxed) && !stopped_early.load(Ordering::Relaxed) {
         final_diagnostics.push(Diagnostic::new_global_warning(
             DiagnosticDetails::Configuration(ConfigurationDiagnostic::NoFirstPartyImportsFound()),
         ));
     }
 
+    // Files are checked in parallel, so their diagnostics arrive in no particular order
+    sort_diagnostics(&mut final_diagnostics);
+    dedup_diagnostics(&mut final_diagnostics);
     Ok(final_diagnostics)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::atomic::AtomicUsize;
+
+    use serial_test::serial;
+
+    use super::*;
+    use crate::interrupt::set_interrupt_signal;
+    use crate::parsing::config::parse_project_config;
+
+    const FILE_COUNT: usize = 200;
+
+    /// A project where each of many files in 'a' has an undeclared dependency on 'b'.
+    fn create_project() -> tempfile::TempDir {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for Python files
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["a", "b"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        for i in 0..FILE_COUNT {
+            fs::write(root.join(format!("a/file_{i}.py")), "import b\n").unwrap();
+        }
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"a\"\ndepends_on = []\n",
+                "[[modules]]\npath = \"b\"\ndepends_on = []\n",
+            ),
+        )
+        .unwrap();
+        project
+    }
+
+    /// Run 'f' with files checked one at a time, so that no callback can be racing
+    /// with the one which stops the check.
+    fn check_sequentially<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(f)
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_with_callback_stops_early() {
+        let project = create_project();
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let total = AtomicUsize::new(0);
+        let checked_files = Mutex::new(Vec::new());
+
+        let diagnostics = check_sequentially(|| {
+            check_with_callback(
+                root.to_path_buf(),
+                &project_config,
+                true,
+                true,
+                vec![],
+                &CheckScope::default(),
+                Some(|count| total.store(count, Ordering::Relaxed)),
+                |file_path, _| {
+                    checked_files.lock().unwrap().push(file_path.to_path_buf());
+                    false
+                },
+            )
+        })
+        .unwrap();
+
+        // No file is checked after the first callback stops the check
+        let checked_files = checked_files.into_inner().unwrap();
+        assert_eq!(total.load(Ordering::Relaxed), FILE_COUNT + 2);
+        assert_eq!(checked_files.len(), 1);
+        assert!(diagnostics.len() <= 1);
+        for diagnostic in &diagnostics {
+            assert_eq!(diagnostic.code(), "undeclared-dependency");
+            assert!(checked_files.contains(&root.join(diagnostic.file_path().unwrap())));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_without_total_stops_early() {
+        let project = create_project();
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let calls = AtomicUsize::new(0);
+
+        // Without a total, files are checked while the project is still being walked
+        let diagnostics = check_sequentially(|| {
+            check_with_callback(
+                root.to_path_buf(),
+                &project_config,
+                true,
+                true,
+                vec![],
+                &CheckScope::default(),
+                None::<fn(usize)>,
+                |_, _| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    false
+                },
+            )
+        })
+        .unwrap();
+
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert!(diagnostics.len() <= 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_without_total_stops_on_interrupt() {
+        let project = create_project();
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let calls = AtomicUsize::new(0);
+
+        set_interrupt_signal(false);
+        let result = check_sequentially(|| {
+            check_with_callback(
+                root.to_path_buf(),
+                &project_config,
+                true,
+                true,
+                vec![],
+                &CheckScope::default(),
+                None::<fn(usize)>,
+                |_, _| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    set_interrupt_signal(true);
+                    true
+                },
+            )
+        });
+        set_interrupt_signal(false);
+
+        assert!(matches!(result, Err(CheckError::Interrupt)));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_with_callback_continues() {
+        let project = create_project();
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        let calls = AtomicUsize::new(0);
+
+        let diagnostics = check_with_callback(
+            root.to_path_buf(),
+            &project_config,
+            true,
+            true,
+            vec![],
+            &CheckScope::default(),
+            None::<fn(usize)>,
+            |_, _| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                true
+            },
+        )
+        .unwrap();
+
+        assert_eq!(calls.load(Ordering::Relaxed), FILE_COUNT + 2);
+        assert_eq!(diagnostics.len(), FILE_COUNT);
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_reports_each_import_of_a_module_once() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["api", "core"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+        }
+        // Both names are defined in 'core.impl' and re-exported by the 'core' package,
+        // so each one is an undeclared dependency on 'core' from the same import
+        fs::write(
+            root.join("core/impl.py"),
+            "def first(): ...\ndef second(): ...\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("core/__init__.py"),
+            "from core.impl import first, second\n__all__ = [\"first\", \"second\"]\n",
+        )
+        .unwrap();
+        fs::write(root.join("api/__init__.py"), "").unwrap();
+        fs::write(
+            root.join("api/views.py"),
+            "import os\nfrom core import first, second\n\n\ndef handler():\n    return first(), second()\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"api\"\ndepends_on = []\n",
+                "[[modules]]\npath = \"core\"\ndepends_on = []\n",
+            ),
+        )
+        .unwrap();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+
+        let diagnostics = check(root.to_path_buf(), &project_config, true, true, vec![]).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), "undeclared-dependency");
+        assert_eq!(diagnostics[0].definition_module(), Some("core"));
+        assert_eq!(
+            diagnostics[0].file_path(),
+            Some(&PathBuf::from("api/views.py"))
+        );
+        assert_eq!(diagnostics[0].line_number(), Some(2));
+    }
+
+    /// A project with the given files, relative to its root.
+    fn create_project_with_files(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        for (path, contents) in files {
+            let path = project.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        project
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_domain_boundary() {
+        let project = create_project_with_files(&[
+            ("tach.toml", ""),
+            (
+                "a/tach.domain.toml",
+                "depends_on_domains = [\"c\"]\n\n[root]\ndepends_on = [\"//b\", \"//c\"]\n",
+            ),
+            ("b/tach.domain.toml", "[root]\ndepends_on = []\n"),
+            ("c/tach.domain.toml", "[root]\ndepends_on = []\n"),
+            ("a/__init__.py", ""),
+            ("a/service.py", "import c\nimport b\n"),
+            ("b/__init__.py", ""),
+            ("c/__init__.py", ""),
+        ]);
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+
+        let diagnostics = check(root.to_path_buf(), &project_config, true, true, vec![]).unwrap();
+
+        // The module dependency is declared, but domain 'a' may only depend on domain 'c'
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), "domain-violation");
+        assert_eq!(diagnostics[0].definition_module(), Some("b"));
+        assert_eq!(diagnostics[0].line_number(), Some(2));
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_package_boundary() {
+        let project = create_project_with_files(&[
+            (
+                "tach.toml",
+                concat!(
+                    "[[modules]]\npath = \"a.x\"\ndepends_on = [\"b.y\", \"c.z\"]\n",
+                    "[[modules]]\npath = \"b.y\"\ndepends_on = []\n",
+                    "[[modules]]\npath = \"c.z\"\ndepends_on = []\n",
+                    "[[packages]]\npath = \"a\"\ndepends_on = [\"c\"]\n",
+                    "[[packages]]\npath = \"b\"\n",
+                    "[[packages]]\npath = \"c\"\n",
+                ),
+            ),
+            ("a/__init__.py", ""),
+            ("a/x.py", "import c.z\nimport b.y\n"),
+            ("b/__init__.py", ""),
+            ("b/y.py", ""),
+            ("c/__init__.py", ""),
+            ("c/z.py", ""),
+        ]);
+        let root = project.path();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+
+        let diagnostics = check(root.to_path_buf(), &project_config, true, true, vec![]).unwrap();
+
+        // The module dependency is declared, but package 'a' may only depend on package 'c'
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code(), "package-violation");
+        assert_eq!(diagnostics[0].definition_module(), Some("b.y"));
+        assert_eq!(diagnostics[0].line_number(), Some(2));
+    }
+}
@@ -12,6 +12,10 @@ pub enum CheckError {
     InvalidDirectory(String),
     #[error("No checks enabled.")]
     NoChecksEnabled(),
+    #[error("Module '{0}' is not defined in the project.")]
+    UnknownScopeModule(String),
+    #[error("Domain '{0}' is not defined in the project.")]
+    UnknownScopeDomain(String),
     #[error("Filesystem error: {0}")]
     Filesystem(#[from] fs::FileSystemError),
     #[error("Module tree error: {0}")]
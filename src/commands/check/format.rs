@@ -1,8 +1,11 @@
 use crate::{
     cli::{create_clickable_link, fail, warning},
-    diagnostics::{CodeDiagnostic, Diagnostic, DiagnosticDetails, Severity},
+    diagnostics::{
+        CodeDiagnostic, Diagnostic, DiagnosticDetails, RelatedLocation, Severity, SourceSpan,
+    },
+    filesystem::read_file_content,
 };
-use std::{collections::HashMap, path::PathBuf};
+use std::{cmp::Reverse, collections::HashMap, fs, path::PathBuf};
 
 use console::style;
 use itertools::Itertools;
@@ -23,14 +26,27 @@ impl From<&DiagnosticDetails> for DiagnosticGroupKind {
             DiagnosticDetails::Code(code_diagnostic_details) => match code_diagnostic_details {
                 CodeDiagnostic::UndeclaredDependency { .. } => Self::InternalDependency,
                 CodeDiagnostic::DeprecatedDependency { .. } => Self::InternalDependency,
+                CodeDiagnostic::DeprecatedModuleUsage { .. } => Self::InternalDependency,
+                CodeDiagnostic::NewDeprecatedModuleDependency { .. } => Self::InternalDependency,
                 CodeDiagnostic::LayerViolation { .. } => Self::InternalDependency,
+                CodeDiagnostic::DomainViolation { .. } => Self::InternalDependency,
+                CodeDiagnostic::PackageViolation { .. } => Self::InternalDependency,
                 CodeDiagnostic::PrivateDependency { .. } => Self::Interface,
+                CodeDiagnostic::UtilityMisuse { .. } => Self::Interface,
                 CodeDiagnostic::InvalidDataTypeExport { .. } => Self::Interface,
+                CodeDiagnostic::DomainPrivateDependency { .. } => Self::Interface,
                 CodeDiagnostic::UndeclaredExternalDependency { .. } => Self::ExternalDependency,
+                CodeDiagnostic::UndeclaredModuleExternalDependency { .. } => {
+                    Self::ExternalDependency
+                }
                 CodeDiagnostic::UnusedExternalDependency { .. } => Self::ExternalDependency,
+                CodeDiagnostic::LayerExternalBudgetExceeded { .. } => Self::ExternalDependency,
                 CodeDiagnostic::UnnecessarilyIgnoredDependency { .. } => Self::Other,
                 CodeDiagnostic::UnusedIgnoreDirective() => Self::Other,
                 CodeDiagnostic::MissingIgnoreDirectiveReason() => Self::Other,
+                CodeDiagnostic::FileImportCycle { .. } => Self::Other,
+                CodeDiagnostic::ParseError { .. } => Self::Other,
+                CodeDiagnostic::UnsupportedSyntax { .. } => Self::Other,
             },
         }
     }
@@ -87,34 +103,129 @@ impl<'a> DiagnosticGroup<'a> {
     }
 
     fn sort_diagnostics(&mut self) {
-        self.diagnostics.sort_by(|a, b| {
-            // First sort by severity (warnings first)
-            let severity_order = b.severity().cmp(&a.severity());
-            if severity_order != std::cmp::Ordering::Equal {
-                return severity_order;
-            }
-
-            // Then sort by file path (None first)
-            match (a.file_path(), b.file_path()) {
-                (None, None) => std::cmp::Ordering::Equal,
-                (None, Some(_)) => std::cmp::Ordering::Less,
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (Some(a_path), Some(b_path)) => a_path.cmp(b_path),
-            }
+        // First sort by severity (warnings first), then by location (global diagnostics first)
+        self.diagnostics.sort_by_cached_key(|diagnostic| {
+            (Reverse(diagnostic.severity()), diagnostic.sort_key())
         });
     }
 }
 
+const PROJECT_CONFIG_FILE: &str = "tach.toml";
+// The most lines of configuration shown beneath a diagnostic
+const MAX_CONFIG_SNIPPET_LINES: usize = 8;
+
+/// Render the source line containing 'span', underlining the span with carets.
+fn format_code_frame(
+    contents: &str,
+    line_number: usize,
+    span: &SourceSpan,
+    severity: &Severity,
+) -> String {
+    let start_offset = span.start_offset.min(contents.len());
+    let line_start = contents[..start_offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = contents[line_start..].lines().next().unwrap_or_default();
+    // Spans over several lines (such as multi-line imports) only underline their first line
+    let span_length = contents
+        .get(start_offset..span.end_offset)
+        .and_then(|text| text.lines().next())
+        .map_or(1, |text| text.chars().count().max(1));
+    let carets = "^".repeat(span_length);
+    let underline = match severity {
+        Severity::Error => style(carets).red().bold(),
+        Severity::Warning => style(carets).yellow().bold(),
+    };
+    format!(
+        "{gutter} {bar}\n{number} {bar} {line}\n{gutter} {bar} {padding}{underline}",
+        gutter = " ".repeat(line_number.to_string().len()),
+        bar = style("|").blue().bold(),
+        number = style(line_number).blue().bold(),
+        padding = " ".repeat(span.column.saturating_sub(1)),
+    )
+}
+
+/// Render the '[[modules]]' entry of 'module_path' in 'config', if there is one.
+fn format_config_snippet(config: &str, module_path: &str) -> Option<String> {
+    let lines: Vec<&str> = config.lines().collect();
+    let quoted_path = format!("\"{}\"", module_path);
+    let path_index = lines.iter().position(|line| {
+        let line = line.trim_start();
+        // Matches both 'path' and 'paths'
+        line.starts_with("path") && line.contains(&quoted_path)
+    })?;
+    let start = lines[..path_index]
+        .iter()
+        .rposition(|line| line.trim() == "[[modules]]")
+        .unwrap_or(path_index);
+    let end = lines[path_index + 1..]
+        .iter()
+        .position(|line| line.trim().is_empty() || line.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| path_index + 1 + i)
+        .min(start + MAX_CONFIG_SNIPPET_LINES);
+    let gutter_width = end.to_string().len();
+    let snippet = (start..end)
+        .map(|index| {
+            format!(
+                "{} {} {}",
+                style(format!("{:>width$}", index + 1, width = gutter_width))
+                    .blue()
+                    .bold(),
+                style("|").blue().bold(),
+                style(lines[index]).dim(),
+            )
+        })
+        .join("\n");
+    Some(format!(
+        "{} {} {}:{}\n{}",
+        " ".repeat(gutter_width),
+        style("=").blue().bold(),
+        PROJECT_CONFIG_FILE,
+        path_index + 1,
+        snippet
+    ))
+}
+
 pub struct DiagnosticFormatter {
     project_root: PathBuf,
+    code_frames: bool,
 }
 
 impl DiagnosticFormatter {
     pub fn new(project_root: PathBuf) -> Self {
-        Self { project_root }
+        Self {
+            project_root,
+            code_frames: false,
+        }
+    }
+
+    /// Show the source line of each located diagnostic, and the configuration which governs it.
+    pub fn with_code_frames(mut self, code_frames: bool) -> Self {
+        self.code_frames = code_frames;
+        self
     }
 
-    fn format_diagnostic(&self, diagnostic: &Diagnostic) -> String {
+    fn format_frames(&self, diagnostic: &Diagnostic, config: Option<&str>) -> Vec<String> {
+        let mut frames = Vec::new();
+        if let (Some(file_path), Some(line_number), Some(span)) = (
+            diagnostic.file_path(),
+            diagnostic.line_number(),
+            diagnostic.span(),
+        ) {
+            if let Ok(contents) = read_file_content(self.project_root.join(file_path)) {
+                frames.push(format_code_frame(
+                    &contents,
+                    line_number,
+                    span,
+                    &diagnostic.severity(),
+                ));
+            }
+        }
+        if let (Some(config), Some(usage_module)) = (config, diagnostic.usage_module()) {
+            frames.extend(format_config_snippet(config, usage_module));
+        }
+        frames
+    }
+
+    fn format_diagnostic(&self, diagnostic: &Diagnostic, config: Option<&str>) -> String {
         let local_error_path = diagnostic.file_path();
 
         let error_location = match local_error_path {
@@ -129,7 +240,7 @@ impl DiagnosticFormatter {
             None => diagnostic.severity().to_string(),
         };
 
-        match diagnostic.severity() {
+        let formatted = match diagnostic.severity() {
             Severity::Error => format!(
                 "{} {}{} {}",
                 fail(),
@@ -144,10 +255,44 @@ impl DiagnosticFormatter {
                 style(":").yellow().bold(),
                 style(diagnostic.message()).yellow(),
             ),
+        };
+
+        let formatted =
+            diagnostic
+                .related_locations()
+                .iter()
+                .fold(formatted, |formatted, related| {
+                    format!("{}\n{}", formatted, self.format_related_location(related))
+                });
+        if !self.code_frames {
+            return formatted;
         }
+        self.format_frames(diagnostic, config)
+            .into_iter()
+            .fold(formatted, |formatted, frame| {
+                format!("{}\n{}", formatted, frame)
+            })
+    }
+
+    fn format_related_location(&self, related: &RelatedLocation) -> String {
+        let location = match related.line_number {
+            Some(line_number) => create_clickable_link(
+                &related.file_path,
+                &self.project_root.join(&related.file_path),
+                &line_number,
+            ),
+            None => related.file_path.display().to_string(),
+        };
+        format!(
+            "    {} {}{} {}",
+            style("-->").dim(),
+            style(location).dim(),
+            style(":").dim(),
+            style(&related.message).dim(),
+        )
     }
 
-    fn format_diagnostic_group(&self, group: &mut DiagnosticGroup) -> String {
+    fn format_diagnostic_group(&self, group: &mut DiagnosticGroup, config: Option<&str>) -> String {
         group.sort_diagnostics();
         let header = match group.severity {
             Severity::Error => style(&group.header).red().bold(),
@@ -156,9 +301,9 @@ impl DiagnosticFormatter {
         let diagnostics = group
             .diagnostics
             .iter()
-            .map(|d| self.format_diagnostic(d))
+            .map(|d| self.format_diagnostic(d, config))
             .collect::<Vec<String>>()
-            .join("\n");
+            .join(if self.code_frames { "\n\n" } else { "\n" });
 
         match &group.footer {
             Some(footer) => format!("{}\n{}\n\n{}", header, diagnostics, footer),
@@ -177,14 +322,50 @@ impl DiagnosticFormatter {
             group.add_diagnostic(diagnostic);
         }
 
+        let config = if self.code_frames {
+            fs::read_to_string(self.project_root.join(PROJECT_CONFIG_FILE)).ok()
+        } else {
+            None
+        };
         let mut formatted_diagnostics = Vec::new();
         for group in groups
             .values_mut()
             .sorted_by_key(|group| group.kind.clone())
         {
-            formatted_diagnostics.push(self.format_diagnostic_group(group));
+            formatted_diagnostics.push(self.format_diagnostic_group(group, config.as_deref()));
         }
 
         formatted_diagnostics.join("\n\n")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_code_frame() {
+        console::set_colors_enabled(false);
+        let contents = "import os\nfrom domain_one.core import api\n";
+        let span = SourceSpan {
+            start_offset: 15,
+            end_offset: 30,
+            column: 6,
+        };
+        assert_eq!(
+            format_code_frame(contents, 2, &span, &Severity::Error),
+            "  |\n2 | from domain_one.core import api\n  |      ^^^^^^^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn test_format_config_snippet() {
+        console::set_colors_enabled(false);
+        let config = "source_roots = [\".\"]\n\n[[modules]]\npath = \"domain_one\"\ndepends_on = []\n\n[[modules]]\npath = \"domain_two\"\n";
+        assert_eq!(
+            format_config_snippet(config, "domain_one").unwrap(),
+            "  = tach.toml:4\n3 | [[modules]]\n4 | path = \"domain_one\"\n5 | depends_on = []"
+        );
+        assert!(format_config_snippet(config, "domain_three").is_none());
+    }
+}
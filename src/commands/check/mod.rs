@@ -5,4 +5,7 @@ pub mod format;
 
 pub use check_external::check as check_external;
 pub use check_internal::check as check_internal;
+pub use check_internal::check_ignore_directives;
+pub use check_internal::check_with_callback as check_internal_with_callback;
+pub use check_internal::CheckScope;
 pub use error::CheckError;
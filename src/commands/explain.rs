@@ -0,0 +1,356 @@
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::commands::check::CheckError;
+use crate::config::root_module::RootModuleTreatment;
+use crate::config::{InterfaceConfig, ModuleConfig, ProjectConfig};
+use crate::exclusion::set_excluded_paths;
+use crate::filesystem::{self as fs, ProjectFile};
+use crate::interfaces::compiled::CompiledInterfaces;
+use crate::interrupt::check_interrupt;
+use crate::modules::{build_module_tree, ModuleTree};
+use crate::processors::ignore_directive::{get_ignore_directives, IgnoreDirective};
+
+#[derive(Error, Debug)]
+pub enum ExplainError {
+    #[error("'{0}' does not belong to any module.")]
+    ModuleNotFound(String),
+    #[error(transparent)]
+    Check(#[from] CheckError),
+}
+
+/// Where a piece of configuration is declared.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ConfigSource {
+    // Relative to the project root
+    pub file_path: PathBuf,
+    pub line_number: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ExplainedInterface {
+    pub expose: Vec<String>,
+    pub data_types: String,
+    pub source: Option<ConfigSource>,
+}
+
+/// Configuration or a comment which relaxes the checks for a module.
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct Suppression {
+    pub description: String,
+    pub source: Option<ConfigSource>,
+}
+
+/// The effective configuration of a module, and where each piece of it is declared.
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ModuleExplanation {
+    pub module: ModuleConfig,
+    pub source: Option<ConfigSource>,
+    pub domain: Option<String>,
+    pub domain_source: Option<ConfigSource>,
+    // The layers which 'module.layer' is ordered against, highest first
+    pub layers: Vec<String>,
+    pub layers_source: Option<ConfigSource>,
+    pub interfaces: Vec<ExplainedInterface>,
+    pub suppressions: Vec<Suppression>,
+}
+
+/// The (1-indexed) line of the 'n'th line in 'path' which matches 'predicate'.
+fn find_nth_line(path: &Path, n: usize, predicate: impl Fn(&str) -> bool) -> Option<usize> {
+    let content = fs::read_file_content(path).ok()?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| predicate(line.trim_start()))
+        .nth(n)
+        .map(|(index, _)| index + 1)
+}
+
+fn is_key(line: &str, key: &str) -> bool {
+    line.strip_prefix(key)
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+struct SourceFinder<'a> {
+    project_root: &'a Path,
+}
+
+impl SourceFinder<'_> {
+    fn source(&self, path: &Path, line_number: Option<usize>) -> Option<ConfigSource> {
+        if !path.is_file() {
+            return None;
+        }
+        let file_path = path
+            .strip_prefix(self.project_root)
+            .unwrap_or(path)
+            .to_path_buf();
+        Some(ConfigSource {
+            file_path,
+            line_number,
+        })
+    }
+
+    fn key(&self, path: &Path, key: &str) -> Option<ConfigSource> {
+        self.source(path, find_nth_line(path, 0, |line| is_key(line, key)))
+    }
+
+    /// Interfaces are located by the position of their '[[interfaces]]' header in the file.
+    fn interfaces<'b>(
+        &self,
+        path: &Path,
+        interfaces: impl Iterator<Item = &'b InterfaceConfig>,
+    ) -> Vec<(InterfaceConfig, Option<ConfigSource>)> {
+        interfaces
+            .enumerate()
+            .map(|(index, interface)| {
+                let line_number =
+                    find_nth_line(path, index, |line| line.starts_with("[[interfaces]]"));
+                (interface.clone(), self.source(path, line_number))
+            })
+            .collect()
+    }
+}
+
+fn resolve_module_path(
+    project_root: &Path,
+    source_roots: &[PathBuf],
+    path: &str,
+) -> Result<String, ExplainError> {
+    let file_path = project_root.join(path);
+    if !file_path.exists() {
+        // Not a file, so treat it as a module path
+        return Ok(path.to_string());
+    }
+    fs::file_to_module_path(source_roots, &file_path)
+        .map_err(|_| ExplainError::ModuleNotFound(path.to_string()))
+}
+
+fn directive_description(directive: &IgnoreDirective) -> String {
+    let mut description = "'tach-ignore' comment".to_string();
+    if !directive.modules.is_empty() {
+        description.push_str(&format!(" for {}", directive.modules.join(", ")));
+    }
+    if !directive.reason.is_empty() {
+        description.push_str(&format!(" ({})", directive.reason));
+    }
+    description
+}
+
+/// Find every 'tach-ignore' comment in the files which belong to 'module_path'.
+fn ignore_directives(
+    project_root: &Path,
+    source_roots: &[PathBuf],
+    module_tree: &ModuleTree,
+    module_path: &str,
+) -> Result<Vec<Suppression>, CheckError> {
+    let mut suppressions: Vec<Suppression> = source_roots
+        .par_iter()
+        .flat_map(|source_root| {
+            fs::walk_pyfiles(&source_root.display().to_string())
+                .par_bridge()
+                .flat_map(|file_path| {
+                    if check_interrupt().is_err() {
+                        return vec![];
+                    }
+                    let Ok(project_file) =
+                        ProjectFile::try_new(project_root, source_root, &file_path)
+                    else {
+                        return vec![];
+                    };
+                    let file_module =
+                        fs::file_to_module_path(source_roots, &project_file.file_path)
+                            .ok()
+                            .and_then(|mod_path| module_tree.find_nearest(&mod_path))
+                            .and_then(|module| module.config.as_ref().map(|c| c.path.clone()));
+                    if file_module.as_deref() != Some(module_path) {
+                        return vec![];
+                    }
+                    let directives = get_ignore_directives(&project_file.contents);
+                    directives
+                        .active_directives()
+                        .chain(directives.redundant_directives())
+                        .map(|directive| Suppression {
+                            description: directive_description(directive),
+                            source: Some(ConfigSource {
+                                file_path: project_file.relative_file_path.clone(),
+                                line_number: Some(directive.line_no),
+                            }),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    check_interrupt().map_err(|_| CheckError::Interrupt)?;
+
+    suppressions.sort_by(|a, b| a.source.cmp(&b.source));
+    Ok(suppressions)
+}
+
+/// Explain the effective configuration of the module containing 'path',
+/// which is either a file or directory (relative to the project root) or a module path.
+pub fn explain_module(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+    path: &str,
+) -> Result<ModuleExplanation, ExplainError> {
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )
+    .map_err(CheckError::from)?;
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )
+    .map_err(CheckError::from)?;
+
+    let module_path = resolve_module_path(project_root, &source_roots, path)?;
+    let module = module_tree
+        .find_nearest(&module_path)
+        .and_then(|node| node.config.clone())
+        .ok_or_else(|| ExplainError::ModuleNotFound(path.to_string()))?;
+
+    let finder = SourceFinder { project_root };
+    let source = project_config
+        .module_config_location(&module.path)
+        .and_then(|(config_path, line_number)| finder.source(&config_path, line_number));
+    let domain = project_config.domain_for_module(&module.path);
+
+    // Modules within a domain which declares its own layers are ordered against those layers
+    let (layers, layers_source) = match domain.filter(|domain| domain.has_layers()) {
+        Some(domain) => (
+            domain.config.layers.clone(),
+            finder.key(&domain.location.path, "layers"),
+        ),
+        None => (
+            project_config.layers.clone(),
+            project_config
+                .location
+                .as_ref()
+                .and_then(|location| finder.key(location, "layers")),
+        ),
+    };
+
+    let mut located_interfaces: Vec<(InterfaceConfig, Option<ConfigSource>)> = vec![];
+    if let Some(location) = &project_config.location {
+        located_interfaces.extend(finder.interfaces(location, project_config.interfaces.iter()));
+    }
+    for domain in &project_config.domains {
+        located_interfaces.extend(finder.interfaces(&domain.location.path, domain.interfaces()));
+    }
+    let interfaces = located_interfaces
+        .into_iter()
+        .filter(|(interface, _)| {
+            !CompiledInterfaces::build(std::iter::once(interface))
+                .get_interfaces(&module.path)
+                .is_empty()
+        })
+        .map(|(interface, source)| ExplainedInterface {
+            expose: interface.expose,
+            data_types: interface.data_types.to_string(),
+            source,
+        })
+        .collect();
+
+    let mut suppressions: Vec<Suppression> = vec![];
+    if module.unchecked {
+        suppressions.push(Suppression {
+            description: "The module is unchecked, so its imports are not checked".to_string(),
+            source: source.clone(),
+        });
+    }
+    if module.utility {
+        suppressions.push(Suppression {
+            description: "The module is a utility, so any module may depend on it".to_string(),
+            source: source.clone(),
+        });
+    }
+    if module.unrestricted_layer {
+        suppressions.push(Suppression {
+            description: "The module has an unrestricted layer, so it may be used from any layer"
+                .to_string(),
+            source: source.clone(),
+        });
+    }
+    if module.is_root() && project_config.root_module == RootModuleTreatment::Ignore {
+        suppressions.push(Suppression {
+            description: "The root module is ignored ('root_module = \"ignore\"')".to_string(),
+            source: project_config
+                .location
+                .as_ref()
+                .and_then(|location| finder.key(location, "root_module")),
+        });
+    }
+    for dependency in module.dependencies_iter().filter(|dep| dep.deprecated) {
+        suppressions.push(Suppression {
+            description: format!(
+                "The dependency on '{}' is deprecated, so its usages are warnings",
+                dependency.path
+            ),
+            source: source.clone(),
+        });
+    }
+    suppressions.extend(ignore_directives(
+        project_root,
+        &source_roots,
+        &module_tree,
+        &module.path,
+    )?);
+
+    Ok(ModuleExplanation {
+        domain: domain.map(|domain| domain.location.mod_path.clone()),
+        domain_source: domain.and_then(|domain| finder.source(&domain.location.path, None)),
+        module,
+        source,
+        layers,
+        layers_source,
+        interfaces,
+        suppressions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directive_description() {
+        let directive = IgnoreDirective {
+            modules: vec!["core.models".to_string()],
+            reason: "legacy".to_string(),
+            line_no: 3,
+            ignored_line_no: 4,
+        };
+        assert_eq!(
+            directive_description(&directive),
+            "'tach-ignore' comment for core.models (legacy)"
+        );
+    }
+
+    #[test]
+    fn test_is_key() {
+        assert!(is_key("layers = [\"ui\"]", "layers"));
+        assert!(is_key("layers=[]", "layers"));
+        assert!(!is_key("layers_extra = []", "layers"));
+        assert!(!is_key("# layers = []", "layers"));
+    }
+}
@@ -0,0 +1,509 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::check::CheckError;
+use crate::config::ProjectConfig;
+use crate::diagnostics::FileProcessor;
+use crate::exclusion::set_excluded_paths;
+use crate::external::parsing::parse_pyproject_toml;
+use crate::filesystem::{self as fs, ProjectFile};
+use crate::interrupt::check_interrupt;
+use crate::modules::cycles::{find_dependency_cycles, suggest_cycle_breaks};
+use crate::modules::{build_module_tree, ModuleTree};
+use crate::processors::InternalDependencyExtractor;
+use crate::python::parsing::count_public_symbols;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DependencyGraphNode {
+    pub path: String,
+    pub layer: Option<String>,
+    pub utility: bool,
+    // Entry points (as 'group:name') declared in pyproject.toml which refer to this module
+    pub entry_points: Vec<String>,
+    // Totals over the Python files which belong to this module
+    pub files: usize,
+    pub lines: usize,
+    pub public_symbols: usize,
+    pub imports: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DependencyGraphEdge {
+    pub source: String,
+    pub target: String,
+    // The number of imports and references from 'source' to 'target'
+    pub weight: usize,
+    // Whether 'target' appears in the 'depends_on' of 'source'
+    pub declared: bool,
+}
+
+/// A declared dependency which could be removed to break a circular dependency.
+#[derive(Debug, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct CycleBreak {
+    pub source: String,
+    pub target: String,
+    // The number of imports from 'source' to 'target' which would need to be removed
+    pub import_count: usize,
+}
+
+/// A group of modules which all depend on each other through their declared dependencies.
+#[derive(Debug, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct CircularDependency {
+    pub modules: Vec<String>,
+    // A minimal set of dependencies whose removal breaks every cycle, fewest imports first
+    pub suggested_breaks: Vec<CycleBreak>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyGraphNode>,
+    pub edges: Vec<DependencyGraphEdge>,
+}
+
+#[pymethods]
+impl DependencyGraph {
+    /// Returns the graph as a dict-of-dicts, suitable for 'networkx.from_dict_of_dicts'
+    /// with 'create_using=networkx.DiGraph'.
+    fn to_networkx_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let graph = PyDict::new_bound(py);
+        for node in &self.nodes {
+            graph.set_item(&node.path, PyDict::new_bound(py))?;
+        }
+        for edge in &self.edges {
+            let attributes = PyDict::new_bound(py);
+            attributes.set_item("weight", edge.weight)?;
+            attributes.set_item("declared", edge.declared)?;
+            match graph.get_item(&edge.source)? {
+                Some(adjacency) => adjacency.set_item(&edge.target, attributes)?,
+                None => {
+                    let adjacency = PyDict::new_bound(py);
+                    adjacency.set_item(&edge.target, attributes)?;
+                    graph.set_item(&edge.source, adjacency)?;
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Returns the attributes of each module, including its size totals,
+    /// suitable for 'networkx.set_node_attributes'.
+    fn node_attributes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let nodes = PyDict::new_bound(py);
+        for node in &self.nodes {
+            let attributes = PyDict::new_bound(py);
+            attributes.set_item("layer", &node.layer)?;
+            attributes.set_item("utility", node.utility)?;
+            attributes.set_item("files", node.files)?;
+            attributes.set_item("lines", node.lines)?;
+            attributes.set_item("public_symbols", node.public_symbols)?;
+            attributes.set_item("imports", node.imports)?;
+            nodes.set_item(&node.path, attributes)?;
+        }
+        Ok(nodes)
+    }
+
+    /// Returns a copy of the graph without the given modules, or any edges to or from them.
+    fn without_modules(&self, modules: Vec<String>) -> DependencyGraph {
+        let removed: HashSet<&str> = modules.iter().map(String::as_str).collect();
+        DependencyGraph {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|node| !removed.contains(node.path.as_str()))
+                .cloned()
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|edge| {
+                    !removed.contains(edge.source.as_str())
+                        && !removed.contains(edge.target.as_str())
+                })
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Modules which cannot be reached from any entry point by following dependency edges.
+    /// Returns an empty list when the project declares no entry points.
+    fn unreachable_modules(&self) -> Vec<String> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            adjacency
+                .entry(edge.source.as_str())
+                .or_default()
+                .push(edge.target.as_str());
+        }
+
+        let mut queue: VecDeque<&str> = self
+            .nodes
+            .iter()
+            .filter(|node| !node.entry_points.is_empty())
+            .map(|node| node.path.as_str())
+            .collect();
+        if queue.is_empty() {
+            return vec![];
+        }
+
+        let mut reachable: HashSet<&str> = queue.iter().copied().collect();
+        while let Some(module) = queue.pop_front() {
+            for target in adjacency.get(module).into_iter().flatten() {
+                if reachable.insert(target) {
+                    queue.push_back(target);
+                }
+            }
+        }
+
+        self.nodes
+            .iter()
+            .filter(|node| !reachable.contains(node.path.as_str()))
+            .map(|node| node.path.clone())
+            .collect()
+    }
+}
+
+/// Size totals for the files belonging to a module, accumulated while building the graph.
+#[derive(Debug, Default)]
+struct ModuleTotals {
+    files: usize,
+    lines: usize,
+    public_symbols: usize,
+    imports: usize,
+}
+
+impl ModuleTotals {
+    fn add(&mut self, other: &ModuleTotals) {
+        self.files += other.files;
+        self.lines += other.lines;
+        self.public_symbols += other.public_symbols;
+        self.imports += other.imports;
+    }
+}
+
+/// Entry points declared in pyproject.toml files beneath the project root,
+/// as (owning module, target module, 'group:name') for each entry point which refers to a module.
+fn resolve_entry_points(
+    project_root: &Path,
+    source_roots: &[PathBuf],
+    module_tree: &ModuleTree,
+) -> Vec<(Option<String>, String, String)> {
+    let module_for_path = |mod_path: &str| {
+        module_tree
+            .find_nearest(mod_path)
+            .and_then(|module| module.config.as_ref().map(|c| c.path.clone()))
+    };
+
+    fs::walk_pyprojects(project_root.to_string_lossy().as_ref())
+        .filter_map(|pyproject| parse_pyproject_toml(&pyproject).ok())
+        .flat_map(|project_info| {
+            // The owning package is the module containing the package's source
+            let owner = project_info
+                .source_paths
+                .iter()
+                .filter_map(|source_path| fs::file_to_module_path(source_roots, source_path).ok())
+                .find_map(|mod_path| module_for_path(&mod_path));
+            project_info
+                .entry_points
+                .into_iter()
+                .filter(|entry_point| fs::is_project_import(source_roots, &entry_point.module_path))
+                .filter_map(|entry_point| {
+                    module_for_path(&entry_point.module_path).map(|target| {
+                        (
+                            owner.clone(),
+                            target,
+                            format!("{}:{}", entry_point.group, entry_point.name),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The dependency of each package's owning module on every module which its entry points
+/// refer to, as (owning module, target module).
+pub fn entry_point_dependencies(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: &[String],
+) -> Result<Vec<(String, String)>, CheckError> {
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )?;
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )?;
+
+    Ok(
+        resolve_entry_points(project_root, &source_roots, &module_tree)
+            .into_iter()
+            .filter_map(|(owner, target, _)| {
+                owner
+                    .filter(|owner| owner != &target)
+                    .map(|owner| (owner, target))
+            })
+            .collect(),
+    )
+}
+
+pub fn build_dependency_graph(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<DependencyGraph, CheckError> {
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )?;
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )?;
+
+    let extractor = InternalDependencyExtractor::new(&source_roots, &module_tree, project_config);
+    let processed_files: Vec<(String, ModuleTotals, Vec<String>)> = source_roots
+        .par_iter()
+        .flat_map(|source_root| {
+            fs::walk_pyfiles(&source_root.display().to_string())
+                .par_bridge()
+                .filter_map(|file_path| {
+                    check_interrupt().ok()?;
+                    let project_file =
+                        ProjectFile::try_new(project_root, source_root, &file_path).ok()?;
+                    // Files which fail to parse are skipped, matching 'tach check'
+                    let file_module = extractor.process(project_file).ok()?;
+                    let source = file_module.module_config().path.clone();
+                    let totals = ModuleTotals {
+                        files: 1,
+                        lines: file_module.contents().lines().count(),
+                        public_symbols: count_public_symbols(file_module.contents())
+                            .unwrap_or_default(),
+                        imports: file_module.imports().count(),
+                    };
+                    let targets = file_module
+                        .dependencies
+                        .iter()
+                        .filter_map(|dependency| {
+                            module_tree
+                                .find_nearest(dependency.module_path())
+                                .and_then(|module| module.config.as_ref().map(|c| c.path.clone()))
+                        })
+                        .filter(|target| target != &source)
+                        .collect();
+                    Some((source, totals, targets))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    check_interrupt().map_err(|_| CheckError::Interrupt)?;
+
+    let mut weights: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut module_totals: HashMap<String, ModuleTotals> = HashMap::new();
+    for (source, totals, targets) in processed_files {
+        for target in targets {
+            *weights.entry((source.clone(), target)).or_default() += 1;
+        }
+        module_totals.entry(source).or_default().add(&totals);
+    }
+
+    let mut entry_points: HashMap<String, Vec<String>> = HashMap::new();
+    for (owner, target, entry_point) in
+        resolve_entry_points(project_root, &source_roots, &module_tree)
+    {
+        if let Some(owner) = owner.filter(|owner| owner != &target) {
+            *weights.entry((owner, target.clone())).or_default() += 1;
+        }
+        entry_points.entry(target).or_default().push(entry_point);
+    }
+
+    let declared_dependencies: HashMap<&str, Vec<&str>> = valid_modules
+        .iter()
+        .map(|module| {
+            (
+                module.path.as_str(),
+                module
+                    .dependencies_iter()
+                    .map(|dep| dep.path.as_str())
+                    .collect(),
+            )
+        })
+        .collect();
+
+    let mut nodes: Vec<DependencyGraphNode> = valid_modules
+        .iter()
+        .map(|module| {
+            let totals = module_totals.remove(&module.path).unwrap_or_default();
+            DependencyGraphNode {
+                path: module.path.clone(),
+                layer: module.layer.clone(),
+                utility: module.utility,
+                entry_points: entry_points.remove(&module.path).unwrap_or_default(),
+                files: totals.files,
+                lines: totals.lines,
+                public_symbols: totals.public_symbols,
+                imports: totals.imports,
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let edges = weights
+        .into_iter()
+        .map(|((source, target), weight)| DependencyGraphEdge {
+            declared: declared_dependencies
+                .get(source.as_str())
+                .is_some_and(|deps| deps.contains(&target.as_str())),
+            source,
+            target,
+            weight,
+        })
+        .collect();
+
+    Ok(DependencyGraph { nodes, edges })
+}
+
+/// Find every circular dependency between modules, with suggestions for how to break each one.
+/// Suggestions are ranked by the number of imports along each dependency, so the project is scanned
+/// for imports only when there are cycles.
+pub fn find_circular_dependencies(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<CircularDependency>, CheckError> {
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let cycles = find_dependency_cycles(&valid_modules);
+    if cycles.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let graph = build_dependency_graph(project_root, project_config, exclude_paths)?;
+    let mut import_counts: BTreeMap<(String, String), usize> = valid_modules
+        .iter()
+        .flat_map(|module| {
+            module
+                .dependencies_iter()
+                .map(|dependency| ((module.path.clone(), dependency.path.clone()), 0))
+        })
+        .collect();
+    for edge in graph.edges.into_iter().filter(|edge| edge.declared) {
+        import_counts.insert((edge.source, edge.target), edge.weight);
+    }
+
+    Ok(cycles
+        .into_iter()
+        .map(|modules| {
+            let suggested_breaks = suggest_cycle_breaks(&modules, &import_counts)
+                .into_iter()
+                .map(|(source, target, import_count)| CycleBreak {
+                    source,
+                    target,
+                    import_count,
+                })
+                .collect();
+            CircularDependency {
+                modules,
+                suggested_breaks,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::parsing::config::parse_project_config;
+
+    fn edge(source: &str, target: &str, weight: usize, declared: bool) -> DependencyGraphEdge {
+        DependencyGraphEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight,
+            declared,
+        }
+    }
+
+    #[test]
+    fn test_build_dependency_graph() {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for Python files
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["a", "b", "c"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(
+            root.join("a/service.py"),
+            "import b\nfrom b import api\nimport c\n",
+        )
+        .unwrap();
+        fs::write(root.join("b/api.py"), "import c\n").unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"a\"\ndepends_on = [\"b\"]\n",
+                "[[modules]]\npath = \"b\"\ndepends_on = []\n",
+                "[[modules]]\npath = \"c\"\ndepends_on = []\n",
+            ),
+        )
+        .unwrap();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+
+        let graph = build_dependency_graph(root, &project_config, vec![]).unwrap();
+
+        let nodes: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| (node.path.as_str(), node.files, node.lines, node.imports))
+            .collect();
+        assert_eq!(nodes, vec![("a", 2, 3, 3), ("b", 2, 1, 1), ("c", 1, 0, 0)]);
+        assert_eq!(
+            graph.edges,
+            vec![
+                edge("a", "b", 2, true),
+                edge("a", "c", 1, false),
+                edge("b", "c", 1, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::config::ProjectConfig;
+
+use super::graph::DependencyGraph;
+use super::report::{create_dependency_report_from_dependencies, ReportCreationError};
+use super::why::{explain_resolved_dependency, DependencyHop, ResolvedDependency, WhyError};
+
+/// Bumped whenever the artifact format changes, so stale artifacts are rejected
+/// instead of being read incorrectly.
+pub const GRAPH_ARTIFACT_VERSION: u32 = 2;
+
+#[derive(Error, Debug)]
+pub enum GraphArtifactError {
+    #[error(
+        "Graph artifact has version {0}, but this version of Tach reads version {expected}. Export the graph again.",
+        expected = GRAPH_ARTIFACT_VERSION
+    )]
+    UnsupportedVersion(u32),
+    #[error("Failed to read graph artifact: {0}")]
+    Deserialize(#[from] serde_json::Error),
+    #[error(transparent)]
+    Why(#[from] WhyError),
+    #[error(transparent)]
+    Report(#[from] ReportCreationError),
+}
+
+/// The fully-resolved dependency graph of a project, written to disk so that read-only
+/// analyses can run later without walking or parsing the project again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[pyclass(module = "tach.extension")]
+pub struct GraphArtifact {
+    version: u32,
+    // The version of Tach which exported the artifact
+    #[pyo3(get)]
+    tach_version: String,
+    #[pyo3(get)]
+    graph: DependencyGraph,
+    dependencies: Vec<ResolvedDependency>,
+    // The number of classes in each module, and how many of them are abstract
+    #[pyo3(get)]
+    classes: BTreeMap<String, (usize, usize)>,
+}
+
+#[pymethods]
+impl GraphArtifact {
+    #[new]
+    fn new(
+        graph: DependencyGraph,
+        dependencies: Vec<ResolvedDependency>,
+        classes: BTreeMap<String, (usize, usize)>,
+        tach_version: String,
+    ) -> Self {
+        Self {
+            version: GRAPH_ARTIFACT_VERSION,
+            tach_version,
+            graph,
+            dependencies,
+            classes,
+        }
+    }
+
+    pub fn serialize_json(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+
+    #[staticmethod]
+    pub fn deserialize_json(json: &str) -> Result<Self, GraphArtifactError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+        // Check the version first, since an older format may not deserialize at all
+        let Version { version } = serde_json::from_str(json)?;
+        if version != GRAPH_ARTIFACT_VERSION {
+            return Err(GraphArtifactError::UnsupportedVersion(version));
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Explain why 'source' depends on 'target', as in 'explain_dependency'.
+    pub fn explain_dependency(
+        &self,
+        project_config: &ProjectConfig,
+        source: &str,
+        target: &str,
+    ) -> Result<Vec<DependencyHop>, GraphArtifactError> {
+        Ok(explain_resolved_dependency(
+            project_config,
+            &self.dependencies,
+            source,
+            target,
+        )?)
+    }
+
+    /// Create a dependency report for 'path', as in 'create_dependency_report'.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (project_root, project_config, path, include_dependency_modules, include_usage_modules, skip_dependencies, skip_usages, raw))]
+    pub fn create_dependency_report(
+        &self,
+        project_root: PathBuf,
+        project_config: &ProjectConfig,
+        path: PathBuf,
+        include_dependency_modules: Option<Vec<String>>,
+        include_usage_modules: Option<Vec<String>>,
+        skip_dependencies: bool,
+        skip_usages: bool,
+        raw: bool,
+    ) -> Result<String, GraphArtifactError> {
+        Ok(create_dependency_report_from_dependencies(
+            &project_root,
+            project_config,
+            &path,
+            &self.dependencies,
+            include_dependency_modules,
+            include_usage_modules,
+            skip_dependencies,
+            skip_usages,
+            raw,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let artifact = GraphArtifact::new(
+            DependencyGraph::default(),
+            vec![],
+            BTreeMap::new(),
+            "0.0.0".to_string(),
+        );
+        let json = artifact.serialize_json();
+        assert!(GraphArtifact::deserialize_json(&json).is_ok());
+
+        let stale = json.replacen(
+            &format!("\"version\":{GRAPH_ARTIFACT_VERSION}"),
+            "\"version\":0",
+            1,
+        );
+        assert!(matches!(
+            GraphArtifact::deserialize_json(&stale),
+            Err(GraphArtifactError::UnsupportedVersion(0))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use pyo3::prelude::*;
@@ -13,6 +14,86 @@ pub struct PythonImport {
     pub line_number: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[pyclass(eq, eq_int, module = "tach.extension")]
+pub enum ImportCategory {
+    Project,
+    External,
+    Stdlib,
+    Unresolved,
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct CategorizedImport {
+    pub module_path: String,
+    pub line_number: usize,
+    pub category: ImportCategory,
+    pub ignored: bool,
+}
+
+/// Categorize a module path by where it resolves.
+pub fn categorize_module_path(
+    source_roots: &[PathBuf],
+    stdlib_modules: &HashSet<String>,
+    module_path: &str,
+) -> ImportCategory {
+    if filesystem::is_project_import(source_roots, module_path) {
+        return ImportCategory::Project;
+    }
+    let top_level_module_name = module_path.split('.').next().unwrap_or_default();
+    if stdlib_modules.contains(top_level_module_name) {
+        ImportCategory::Stdlib
+    } else if filesystem::module_to_file_path(source_roots, top_level_module_name, false).is_some()
+    {
+        // The top-level package is first-party, but the import does not resolve within it
+        ImportCategory::Unresolved
+    } else {
+        ImportCategory::External
+    }
+}
+
+/// Get every import in a file, categorized by where it resolves.
+/// Unlike the other helpers, imports ignored by a directive are included and marked as such.
+pub fn get_categorized_imports<P: AsRef<Path>>(
+    source_roots: &[PathBuf],
+    file_path: P,
+    stdlib_modules: &HashSet<String>,
+    ignore_type_checking_imports: bool,
+    include_string_imports: bool,
+) -> Result<Vec<CategorizedImport>> {
+    let file_contents = filesystem::read_file_content(file_path.as_ref())?;
+    let line_index = Locator::new(&file_contents).to_index().clone();
+    let normalized_imports = get_normalized_imports(
+        source_roots,
+        file_path.as_ref(),
+        &file_contents,
+        ignore_type_checking_imports,
+        include_string_imports,
+    )?;
+    let ignore_directives = get_ignore_directives(&file_contents);
+    Ok(normalized_imports
+        .into_iter()
+        .map(|import| {
+            let located_import = LocatedImport::new(
+                line_index.line_index(import.import_offset).get(),
+                line_index.line_index(import.alias_offset).get(),
+                import,
+            );
+            CategorizedImport {
+                category: categorize_module_path(
+                    source_roots,
+                    stdlib_modules,
+                    located_import.module_path(),
+                ),
+                ignored: ignore_directives.is_ignored(&located_import),
+                line_number: located_import.alias_line_number,
+                module_path: located_import.import.module_path,
+            }
+        })
+        .collect())
+}
+
 impl IntoPy<PyObject> for LocatedImport {
     fn into_py(self, py: Python<'_>) -> PyObject {
         PythonImport {
@@ -85,3 +166,65 @@ pub fn get_located_external_imports<P: AsRef<Path>>(
         })
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_get_categorized_imports() {
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path();
+        fs::create_dir_all(root.join("app")).unwrap();
+        fs::write(root.join("app/__init__.py"), "").unwrap();
+        fs::write(root.join("app/models.py"), "").unwrap();
+        fs::write(
+            root.join("app/views.py"),
+            concat!(
+                "import os\n",
+                "import json.decoder\n",
+                "import requests\n",
+                "from app import models\n",
+                "from app.missing import thing\n",
+                "import yaml  # tach-ignore\n",
+            ),
+        )
+        .unwrap();
+        let source_roots = vec![root.to_path_buf()];
+        let stdlib_modules = HashSet::from(["os".to_string(), "json".to_string()]);
+
+        let imports = get_categorized_imports(
+            &source_roots,
+            root.join("app/views.py"),
+            &stdlib_modules,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let imports: Vec<_> = imports
+            .iter()
+            .map(|import| {
+                (
+                    import.module_path.as_str(),
+                    import.line_number,
+                    import.category,
+                    import.ignored,
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("os", 1, ImportCategory::Stdlib, false),
+                ("json.decoder", 2, ImportCategory::Stdlib, false),
+                ("requests", 3, ImportCategory::External, false),
+                ("app.models", 4, ImportCategory::Project, false),
+                ("app.missing.thing", 5, ImportCategory::Unresolved, false),
+                ("yaml", 6, ImportCategory::External, true),
+            ]
+        );
+    }
+}
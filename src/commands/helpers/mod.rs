@@ -1,3 +1,7 @@
 pub mod import;
+pub mod module_tree;
 
-pub use import::{get_located_external_imports, get_located_project_imports};
+pub use import::{
+    get_categorized_imports, get_located_external_imports, get_located_project_imports,
+};
+pub use module_tree::PyModuleTree;
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+
+use crate::config::{ModuleConfig, ProjectConfig};
+use crate::filesystem;
+use crate::modules::{build_module_tree, error::ModuleTreeError, ModuleTree};
+
+/// A read-only handle to the module tree of a project,
+/// so that Python tooling can reuse tach's module resolution.
+#[pyclass(name = "ModuleTree", module = "tach.extension")]
+pub struct PyModuleTree {
+    tree: ModuleTree,
+    source_roots: Vec<PathBuf>,
+}
+
+impl PyModuleTree {
+    pub fn build(
+        project_root: &Path,
+        project_config: &ProjectConfig,
+    ) -> Result<Self, ModuleTreeError> {
+        let source_roots = project_config.prepend_roots(project_root);
+        let (valid_modules, _) = filesystem::validate_project_modules(
+            &source_roots,
+            project_config.all_modules().cloned().collect(),
+        );
+        let tree = build_module_tree(
+            &source_roots,
+            &valid_modules,
+            false,
+            project_config.root_module.clone(),
+        )?;
+        Ok(Self { tree, source_roots })
+    }
+}
+
+#[pymethods]
+impl PyModuleTree {
+    #[new]
+    fn new(project_root: PathBuf, project_config: &ProjectConfig) -> Result<Self, ModuleTreeError> {
+        Self::build(&project_root, project_config)
+    }
+
+    fn get(&self, module_path: &str) -> Option<ModuleConfig> {
+        self.tree
+            .get(module_path)
+            .and_then(|node| node.config.clone())
+    }
+
+    fn find_nearest(&self, module_path: &str) -> Option<ModuleConfig> {
+        self.tree
+            .find_nearest(module_path)
+            .and_then(|node| node.config.clone())
+    }
+
+    fn children(&self, module_path: &str) -> Vec<ModuleConfig> {
+        let mut children: Vec<ModuleConfig> = self
+            .tree
+            .child_modules(module_path)
+            .iter()
+            .filter_map(|node| node.config.clone())
+            .collect();
+        children.sort_by(|a, b| a.path.cmp(&b.path));
+        children
+    }
+
+    fn module_for_file(&self, file_path: PathBuf) -> Option<ModuleConfig> {
+        let module_path = filesystem::file_to_module_path(&self.source_roots, &file_path).ok()?;
+        self.find_nearest(&module_path)
+    }
+
+    fn module_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .tree
+            .iter()
+            .filter_map(|node| node.config.as_ref().map(|config| config.path.clone()))
+            .collect();
+        paths.sort();
+        paths
+    }
+}
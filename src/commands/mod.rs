@@ -1,6 +1,14 @@
 pub mod check;
+pub mod explain;
+pub mod graph;
+pub mod graph_artifact;
 pub mod helpers;
+pub mod new_domain;
+pub mod new_module;
+pub mod owners;
 pub mod report;
 pub mod server;
 pub mod sync;
 pub mod test;
+pub mod trace;
+pub mod why;
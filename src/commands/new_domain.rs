@@ -0,0 +1,119 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::config::domain::DOMAIN_CONFIG_FILE;
+use crate::config::ProjectConfig;
+
+#[derive(Error, Debug)]
+pub enum NewDomainError {
+    #[error("Domain config already exists at '{0}'")]
+    AlreadyExists(PathBuf),
+    #[error("No source roots are configured")]
+    NoSourceRoot,
+    #[error("Failed to write domain files: {0}")]
+    Io(#[from] io::Error),
+    #[error("Failed to serialize domain config: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+}
+
+pub(crate) fn create_file_if_missing(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, "")
+}
+
+/// The directory for the package at 'mod_path', preferring a source root
+/// which already contains its parent package.
+pub(crate) fn package_dir(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    mod_path: &str,
+) -> Option<PathBuf> {
+    let source_roots = project_config.prepend_roots(project_root);
+    let relative_dir: PathBuf = mod_path.split('.').collect();
+    source_roots
+        .iter()
+        .map(|root| root.join(&relative_dir))
+        .find(|dir| dir.parent().is_some_and(|parent| parent.is_dir()))
+        .or_else(|| source_roots.first().map(|root| root.join(&relative_dir)))
+}
+
+/// Scaffold a domain at 'domain_path' (e.g. 'billing.invoices') from the project's domain template,
+/// creating its directory, package files, and 'tach.domain.toml'.
+/// Returns the path of the new domain config.
+pub fn create_new_domain(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    domain_path: &str,
+) -> Result<PathBuf, NewDomainError> {
+    let domain_dir = package_dir(project_root, project_config, domain_path)
+        .ok_or(NewDomainError::NoSourceRoot)?;
+
+    let config_path = domain_dir.join(DOMAIN_CONFIG_FILE);
+    if config_path.exists() {
+        return Err(NewDomainError::AlreadyExists(config_path));
+    }
+    fs::create_dir_all(&domain_dir)?;
+
+    let template = &project_config.domain_template;
+    if template.package {
+        create_file_if_missing(&domain_dir.join("__init__.py"))?;
+    }
+    if let Some(public_module) = &template.public_module {
+        let module_file: PathBuf = public_module.split('.').collect();
+        create_file_if_missing(&domain_dir.join(module_file).with_extension("py"))?;
+    }
+
+    fs::write(&config_path, toml::to_string(&template.domain_config())?)?;
+    Ok(config_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DomainConfig, DomainTemplateConfig};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_new_domain() {
+        let project = TempDir::new().unwrap();
+        let project_config = ProjectConfig {
+            domain_template: DomainTemplateConfig {
+                public_module: Some("api".to_string()),
+                owner: Some("@billing-team".to_string()),
+                depends_on: vec!["core".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let config_path =
+            create_new_domain(project.path(), &project_config, "billing.invoices").unwrap();
+
+        let domain_dir = project.path().join("billing/invoices");
+        assert_eq!(config_path, domain_dir.join(DOMAIN_CONFIG_FILE));
+        assert!(domain_dir.join("__init__.py").exists());
+        assert!(domain_dir.join("api.py").exists());
+
+        let domain_config: DomainConfig =
+            toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(
+            domain_config,
+            project_config.domain_template.domain_config()
+        );
+        assert_eq!(domain_config.owner.as_deref(), Some("@billing-team"));
+        assert_eq!(domain_config.interfaces[0].expose, vec!["api.*"]);
+
+        assert!(matches!(
+            create_new_domain(project.path(), &project_config, "billing.invoices"),
+            Err(NewDomainError::AlreadyExists(_))
+        ));
+    }
+}
@@ -0,0 +1,79 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::commands::new_domain::{create_file_if_missing, package_dir};
+use crate::config::ProjectConfig;
+
+#[derive(Error, Debug)]
+pub enum NewModuleError {
+    #[error("Module '{0}' already exists")]
+    AlreadyExists(String),
+    #[error("No source roots are configured")]
+    NoSourceRoot,
+    #[error("Failed to write module files: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Scaffold the package for a module at 'module_path' (e.g. 'billing.invoices'),
+/// creating its directory and '__init__.py'. An existing package or module file is kept as is.
+/// Returns the path of the module's source file.
+pub fn create_new_module(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    module_path: &str,
+) -> Result<PathBuf, NewModuleError> {
+    if project_config
+        .all_modules()
+        .any(|module| module.path == module_path)
+    {
+        return Err(NewModuleError::AlreadyExists(module_path.to_string()));
+    }
+    let module_dir = package_dir(project_root, project_config, module_path)
+        .ok_or(NewModuleError::NoSourceRoot)?;
+
+    let module_file = module_dir.with_extension("py");
+    if module_file.is_file() {
+        return Ok(module_file);
+    }
+    let init_file = module_dir.join("__init__.py");
+    create_file_if_missing(&init_file)?;
+    Ok(init_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModuleConfig;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_new_module() {
+        let project = TempDir::new().unwrap();
+        let project_config = ProjectConfig {
+            modules: vec![ModuleConfig::new("core", false)],
+            ..Default::default()
+        };
+
+        let init_file =
+            create_new_module(project.path(), &project_config, "billing.invoices").unwrap();
+        assert_eq!(
+            init_file,
+            project.path().join("billing/invoices/__init__.py")
+        );
+        assert!(init_file.exists());
+
+        fs::write(project.path().join("billing/payments.py"), "").unwrap();
+        assert_eq!(
+            create_new_module(project.path(), &project_config, "billing.payments").unwrap(),
+            project.path().join("billing/payments.py")
+        );
+
+        assert!(matches!(
+            create_new_module(project.path(), &project_config, "core"),
+            Err(NewModuleError::AlreadyExists(_))
+        ));
+    }
+}
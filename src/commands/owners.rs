@@ -0,0 +1,34 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::codeowners::Codeowners;
+use crate::config::ProjectConfig;
+use crate::filesystem::module_to_file_path;
+
+/// The owner of each module which has one. Owners declared in config take precedence,
+/// and modules without a declared owner fall back to CODEOWNERS when 'use_codeowners' is set.
+pub fn get_module_owners(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+) -> BTreeMap<String, String> {
+    let source_roots = project_config.prepend_roots(project_root);
+    let codeowners = project_config
+        .use_codeowners
+        .then(|| Codeowners::find(project_root))
+        .flatten();
+
+    project_config
+        .all_modules()
+        .filter_map(|module| {
+            if let Some(owner) = &module.owner {
+                return Some((module.path.clone(), owner.clone()));
+            }
+            let codeowners = codeowners.as_ref()?;
+            let resolved_module = module_to_file_path(&source_roots, &module.path, false)?;
+            let relative_path = resolved_module.file_path.strip_prefix(project_root).ok()?;
+            codeowners
+                .owners(relative_path)
+                .map(|owners| (module.path.clone(), owners.join(" ")))
+        })
+        .collect()
+}
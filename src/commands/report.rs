@@ -2,6 +2,7 @@ use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use rayon::prelude::*;
 
@@ -12,19 +13,31 @@ use crate::colors::*;
 use crate::cli::create_clickable_link;
 use crate::config::root_module::RootModuleTreatment;
 use crate::config::ProjectConfig;
+use crate::diagnostics::{DiagnosticError, FileProcessor};
 use crate::filesystem::{
-    file_to_module_path, validate_project_modules, walk_pyfiles, FileSystemError,
+    file_to_module_path, validate_project_modules, walk_pyfiles, FileSystemError, ProjectFile,
 };
 use crate::interrupt::check_interrupt;
-use crate::modules::{build_module_tree, error::ModuleTreeError};
-use crate::processors::import::{ImportParseError, LocatedImport};
-
-use super::helpers::import::get_located_project_imports;
+use crate::modules::{build_module_tree, error::ModuleTreeError, ModuleNode, ModuleTree};
+use crate::processors::import::ImportParseError;
+use crate::processors::InternalDependencyExtractor;
+
+use super::why::{resolve_file_dependencies, ResolvedDependency};
+
+/// Whether a dependency comes from an import, or from a reference found by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DependencyKind {
+    Import,
+    // Describes the construct which makes the reference, such as 'django model relation'
+    Reference { description: String },
+}
 
 struct Dependency {
     file_path: PathBuf,
     absolute_path: PathBuf,
-    import: LocatedImport,
+    line_number: usize,
+    import_path: String,
+    kind: DependencyKind,
     source_module: String,
     target_module: String,
 }
@@ -49,14 +62,10 @@ pub type Result<T> = std::result::Result<T, ReportCreationError>;
 
 // less code than implementing/deriving all necessary traits for Ord
 fn compare_dependencies(left: &Dependency, right: &Dependency) -> Ordering {
-    let path_cmp = left.file_path.cmp(&right.file_path);
-    if path_cmp == Ordering::Equal {
-        return left
-            .import
-            .alias_line_number()
-            .cmp(&right.import.alias_line_number());
-    }
-    path_cmp
+    left.file_path
+        .cmp(&right.file_path)
+        .then_with(|| left.line_number.cmp(&right.line_number))
+        .then_with(|| left.import_path.cmp(&right.import_path))
 }
 
 struct DependencyReport {
@@ -80,15 +89,21 @@ impl DependencyReport {
         let clickable_link = create_clickable_link(
             &dependency.file_path,
             &dependency.absolute_path,
-            &dependency.import.alias_line_number(),
+            &dependency.line_number,
         );
+        let description = match &dependency.kind {
+            DependencyKind::Import => format!("Import '{}'", dependency.import_path),
+            DependencyKind::Reference { description } => {
+                format!("Reference '{}' ({})", dependency.import_path, description)
+            }
+        };
         format!(
-            "{green}{clickable_link}{end_color}: {cyan}Import '{import_mod_path}'{end_color}",
+            "{green}{clickable_link}{end_color}: {cyan}{description}{end_color}",
             green = BColors::OKGREEN,
             clickable_link = clickable_link,
             end_color = BColors::ENDC,
             cyan = BColors::OKCYAN,
-            import_mod_path = dependency.import.module_path()
+            description = description
         )
     }
 
@@ -188,6 +203,7 @@ impl DependencyReport {
         }
 
         if !self.warnings.is_empty() {
+            self.warnings.sort();
             result.push_str(&format!(
                 "[ Warnings ]\n\
                 {warning_color}{warnings}{end_color}",
@@ -208,44 +224,195 @@ fn is_module_prefix(prefix: &str, full_path: &str) -> bool {
     full_path.len() == prefix.len() || full_path[prefix.len()..].starts_with('.')
 }
 
-pub fn create_dependency_report(
-    project_root: &Path,
+fn build_report_module_tree(
+    source_roots: &[PathBuf],
     project_config: &ProjectConfig,
-    path: &PathBuf,
-    include_dependency_modules: Option<Vec<String>>,
-    include_usage_modules: Option<Vec<String>>,
-    skip_dependencies: bool,
-    skip_usages: bool,
-    raw: bool,
-) -> Result<String> {
-    if skip_dependencies && skip_usages {
-        return Err(ReportCreationError::NothingToReport);
-    }
-
-    let source_roots = project_config.prepend_roots(project_root);
+) -> Result<ModuleTree> {
     let (valid_modules, _) = validate_project_modules(
-        &source_roots,
+        source_roots,
         project_config.all_modules().cloned().collect(),
     );
 
     check_interrupt().map_err(|_| ReportCreationError::Interrupted)?;
 
-    let module_tree = build_module_tree(
-        &source_roots,
+    Ok(build_module_tree(
+        source_roots,
         &valid_modules,
         false,                      // skip circular dependency check in report
         RootModuleTreatment::Allow, // skip root module check in report
-    )?;
+    )?)
+}
 
+/// The module path of 'path', along with the module which contains it.
+fn find_target_module(
+    project_root: &Path,
+    source_roots: &[PathBuf],
+    module_tree: &ModuleTree,
+    path: &Path,
+) -> Result<(String, Arc<ModuleNode>)> {
     let absolute_path = project_root.join(path);
-    let module_path = file_to_module_path(&source_roots, &absolute_path)?;
+    let module_path = file_to_module_path(source_roots, &absolute_path)?;
     let target_module = module_tree.find_nearest(&module_path).ok_or_else(|| {
         ReportCreationError::ModuleTree(ModuleTreeError::ModuleNotFound(module_path.clone()))
     })?;
+    Ok((module_path, target_module))
+}
+
+/// Decides which resolved dependencies belong in the report for 'path'.
+/// Reports read from the sources and from a graph artifact are both built here, so they agree.
+struct ReportScope<'a> {
+    project_root: &'a Path,
+    source_roots: Vec<PathBuf>,
+    module_tree: ModuleTree,
+    module_path: String,
+    target_module: Arc<ModuleNode>,
+    include_dependency_modules: Option<Vec<String>>,
+    include_usage_modules: Option<Vec<String>>,
+    skip_dependencies: bool,
+    skip_usages: bool,
+}
+
+impl<'a> ReportScope<'a> {
+    fn new(
+        project_root: &'a Path,
+        project_config: &ProjectConfig,
+        path: &Path,
+        include_dependency_modules: Option<Vec<String>>,
+        include_usage_modules: Option<Vec<String>>,
+        skip_dependencies: bool,
+        skip_usages: bool,
+    ) -> Result<Self> {
+        if skip_dependencies && skip_usages {
+            return Err(ReportCreationError::NothingToReport);
+        }
+
+        let source_roots = project_config.prepend_roots(project_root);
+        let module_tree = build_report_module_tree(&source_roots, project_config)?;
+        let (module_path, target_module) =
+            find_target_module(project_root, &source_roots, &module_tree, path)?;
+        Ok(Self {
+            project_root,
+            source_roots,
+            module_tree,
+            module_path,
+            target_module,
+            include_dependency_modules,
+            include_usage_modules,
+            skip_dependencies,
+            skip_usages,
+        })
+    }
+
+    fn add_to_report<'d>(
+        &self,
+        report: &mut DependencyReport,
+        dependencies: impl IntoIterator<Item = &'d ResolvedDependency>,
+    ) {
+        for dependency in dependencies {
+            // Dependencies on a line with a matching ignore directive are skipped, as in 'tach check'
+            if dependency.ignored {
+                continue;
+            }
+            let is_in_target_path =
+                is_module_prefix(&self.module_path, &dependency.file_module_path);
+            if is_in_target_path && !self.skip_dependencies {
+                let Some(import_module) = self.module_tree.find_nearest(&dependency.import_path)
+                else {
+                    continue;
+                };
+                if import_module.full_path == self.target_module.full_path
+                    || !is_included(&self.include_dependency_modules, &import_module.full_path)
+                {
+                    continue;
+                }
+                report.dependencies.push(self.located(
+                    dependency,
+                    self.target_module.full_path.clone(),
+                    import_module.full_path.clone(),
+                ));
+            } else if !is_in_target_path && !self.skip_usages {
+                if !is_module_prefix(&self.module_path, &dependency.import_path) {
+                    continue;
+                }
+                let Some(file_module) = self.module_tree.find_nearest(&dependency.file_module_path)
+                else {
+                    continue;
+                };
+                if !is_included(&self.include_usage_modules, &file_module.full_path) {
+                    continue;
+                }
+                report.usages.push(self.located(
+                    dependency,
+                    file_module.full_path.clone(),
+                    self.target_module.full_path.clone(),
+                ));
+            }
+        }
+    }
+
+    fn located(
+        &self,
+        dependency: &ResolvedDependency,
+        source_module: String,
+        target_module: String,
+    ) -> Dependency {
+        let absolute_path = self.project_root.join(&dependency.file_path);
+        // Files are shown relative to their source root
+        let file_path = self
+            .source_roots
+            .iter()
+            .find_map(|root| absolute_path.strip_prefix(root).ok())
+            .unwrap_or(dependency.file_path.as_path())
+            .to_path_buf();
+        let kind = match &dependency.construct {
+            Some(construct) => DependencyKind::Reference {
+                description: construct.clone(),
+            },
+            None => DependencyKind::Import,
+        };
+        Dependency {
+            file_path,
+            absolute_path,
+            line_number: dependency.line_number,
+            import_path: dependency.import_path.clone(),
+            kind,
+            source_module,
+            target_module,
+        }
+    }
+}
 
+fn is_included(included_modules: &Option<Vec<String>>, module: &str) -> bool {
+    included_modules
+        .as_ref()
+        .is_none_or(|included_modules| included_modules.iter().any(|path| path == module))
+}
+
+pub fn create_dependency_report(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    path: &PathBuf,
+    include_dependency_modules: Option<Vec<String>>,
+    include_usage_modules: Option<Vec<String>>,
+    skip_dependencies: bool,
+    skip_usages: bool,
+    raw: bool,
+) -> Result<String> {
+    let scope = ReportScope::new(
+        project_root,
+        project_config,
+        path,
+        include_dependency_modules,
+        include_usage_modules,
+        skip_dependencies,
+        skip_usages,
+    )?;
+
+    let (source_roots, module_tree) = (&scope.source_roots, &scope.module_tree);
+    let extractor = InternalDependencyExtractor::new(source_roots, module_tree, project_config);
     let mut report = DependencyReport::new(path.display().to_string());
 
-    for source_root in &source_roots {
+    for source_root in source_roots {
         check_interrupt().map_err(|_| ReportCreationError::Interrupted)?;
 
         let source_root_results: Vec<_> = walk_pyfiles(&source_root.display().to_string())
@@ -255,110 +422,114 @@ pub fn create_dependency_report(
                     return None;
                 }
 
-                let absolute_pyfile = source_root.join(&pyfile);
-                let file_module_path = match file_to_module_path(&source_roots, &absolute_pyfile) {
-                    Ok(path) => path,
-                    Err(_) => return None,
-                };
-                let file_module = module_tree.find_nearest(&file_module_path);
-
-                match get_located_project_imports(
-                    &source_roots,
-                    &absolute_pyfile,
-                    project_config.ignore_type_checking_imports,
-                    project_config.include_string_imports,
-                ) {
-                    Ok(project_imports) => {
-                        let is_in_target_path = is_module_prefix(&module_path, &file_module_path);
-                        let mut dependencies = Vec::new();
-                        let mut usages = Vec::new();
-
-                        if is_in_target_path && !skip_dependencies {
-                            // Add dependencies
-                            dependencies.extend(
-                                project_imports
-                                    .iter()
-                                    .filter_map(|import| {
-                                        if let Some(import_module) =
-                                            module_tree.find_nearest(import.module_path())
-                                        {
-                                            if import_module == target_module {
-                                                return None;
-                                            }
-                                            include_dependency_modules.as_ref().map_or(
-                                                Some((import.clone(), import_module.clone())),
-                                                |included_modules| {
-                                                    if included_modules
-                                                        .contains(&import_module.full_path)
-                                                    {
-                                                        Some((
-                                                            import.clone(),
-                                                            import_module.clone(),
-                                                        ))
-                                                    } else {
-                                                        None
-                                                    }
-                                                },
-                                            )
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .map(|(import, import_module)| Dependency {
-                                        file_path: pyfile.clone(),
-                                        absolute_path: absolute_pyfile.clone(),
-                                        import,
-                                        source_module: target_module.full_path.clone(),
-                                        target_module: import_module.full_path.clone(),
-                                    }),
-                            );
-                        } else if !is_in_target_path && !skip_usages {
-                            // Add usages
-                            usages.extend(
-                                project_imports
-                                    .iter()
-                                    .filter(|import| {
-                                        if !is_module_prefix(&module_path, import.module_path()) {
-                                            return false;
-                                        }
-                                        file_module.as_ref().is_some_and(|m| {
-                                            include_usage_modules.as_ref().is_none_or(
-                                                |included_modules| {
-                                                    included_modules.contains(&m.full_path)
-                                                },
-                                            )
-                                        })
-                                    })
-                                    .map(|import| Dependency {
-                                        file_path: pyfile.clone(),
-                                        absolute_path: absolute_pyfile.clone(),
-                                        import: import.clone(),
-                                        source_module: file_module
-                                            .as_ref()
-                                            .map_or(String::new(), |m| m.full_path.clone()),
-                                        target_module: target_module.full_path.clone(),
-                                    }),
-                            );
-                        }
-
-                        Some((dependencies, usages, None))
-                    }
-                    Err(err) => Some((Vec::new(), Vec::new(), Some(err.to_string()))),
-                }
+                // Imports and plugin references come from a single parse of the file,
+                // and imports are shared with other commands through the import cache
+                let resolved_dependencies =
+                    ProjectFile::try_new(project_root, source_root, &pyfile)
+                        .map_err(DiagnosticError::from)
+                        .and_then(|project_file| extractor.process(project_file))
+                        .map(|file_module| {
+                            resolve_file_dependencies(source_roots, module_tree, &file_module)
+                        })
+                        .map_err(|err| err.to_string());
+                Some(resolved_dependencies)
             })
             .collect();
 
         check_interrupt().map_err(|_| ReportCreationError::Interrupted)?;
 
         // Combine results
-        for (dependencies, usages, warning) in source_root_results {
-            report.dependencies.extend(dependencies);
-            report.usages.extend(usages);
-            if let Some(warning) = warning {
-                report.warnings.push(warning);
+        for result in source_root_results {
+            match result {
+                Ok(dependencies) => scope.add_to_report(&mut report, &dependencies),
+                Err(warning) => report.warnings.push(warning),
             }
         }
     }
 
     Ok(report.render_to_string(skip_dependencies, skip_usages, raw))
 }
+
+/// Create the same report as 'create_dependency_report' from dependencies which were already
+/// resolved, such as those stored in a graph artifact, without reading any source files.
+#[allow(clippy::too_many_arguments)]
+pub fn create_dependency_report_from_dependencies(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    path: &PathBuf,
+    dependencies: &[ResolvedDependency],
+    include_dependency_modules: Option<Vec<String>>,
+    include_usage_modules: Option<Vec<String>>,
+    skip_dependencies: bool,
+    skip_usages: bool,
+    raw: bool,
+) -> Result<String> {
+    let scope = ReportScope::new(
+        project_root,
+        project_config,
+        path,
+        include_dependency_modules,
+        include_usage_modules,
+        skip_dependencies,
+        skip_usages,
+    )?;
+
+    let mut report = DependencyReport::new(path.display().to_string());
+    scope.add_to_report(&mut report, dependencies);
+    Ok(report.render_to_string(skip_dependencies, skip_usages, raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::parsing::config::parse_project_config;
+
+    #[test]
+    fn test_report_distinguishes_plugin_references() {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for Python files
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["a", "b"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(root.join("b/api.py"), "").unwrap();
+        fs::write(root.join("b/models.py"), "").unwrap();
+        fs::write(
+            root.join("a/service.py"),
+            "from b import api\n\ndef handler(value: \"b.models\") -> None: ...\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"a\"\ndepends_on = [\"b\"]\n",
+                "[[modules]]\npath = \"b\"\ndepends_on = []\n",
+                "[plugins.forward_refs]\n",
+            ),
+        )
+        .unwrap();
+        let (project_config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+
+        let report = create_dependency_report(
+            root,
+            &project_config,
+            &PathBuf::from("a"),
+            None,
+            None,
+            false,
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert!(report.contains("Import 'b.api'"));
+        assert!(report.contains("Reference 'b.models' (forward_refs forward reference)"));
+        assert!(!report.contains("Import 'b.models'"));
+    }
+}
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use crate::config;
 use crate::lsp::{error::ServerError, server::LSPServer};
+use crate::rpc::{error::RpcServerError, server::RpcServer};
 
 pub fn run_server(
     project_root: PathBuf,
@@ -10,3 +11,11 @@ pub fn run_server(
     let server = LSPServer::new(project_root, project_config);
     server.run()
 }
+
+pub fn run_rpc_server(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+) -> Result<(), RpcServerError> {
+    let server = RpcServer::new(project_root, project_config);
+    server.run()
+}
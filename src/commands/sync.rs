@@ -1,15 +1,18 @@
+use itertools::Itertools;
 use thiserror::Error;
 
 use pyo3::prelude::*;
 
 use crate::commands::check::{check_internal, CheckError};
+use crate::commands::graph::entry_point_dependencies;
 use crate::config::edit::{ConfigEditor, EditError};
 use crate::config::root_module::{RootModuleTreatment, ROOT_MODULE_SENTINEL_TAG};
-use crate::config::{DependencyConfig, ProjectConfig};
+use crate::config::{DependencyConfig, ModuleConfig, NewPackagePolicy, ProjectConfig};
 use crate::diagnostics::Diagnostic;
-use crate::filesystem::validate_module_path;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use crate::exclusion::set_excluded_paths;
+use crate::filesystem::{self as fs, validate_module_path};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 #[derive(Error, Debug)]
 pub enum SyncError {
@@ -23,27 +26,27 @@ pub enum SyncError {
     RootModuleViolation(String),
     #[error("Failed to apply edits to project configuration.\n{0}")]
     EditError(#[from] EditError),
+    #[error("{0}")]
+    NewPackagePolicy(String),
 }
 
-fn handle_added_dependency(
+/// Whether sync should add the dependency of 'module_path' on 'dependency',
+/// following the project's root module treatment.
+fn should_add_dependency(
     module_path: &str,
     dependency: &str,
-    project_config: &mut ProjectConfig,
-) -> Result<(), SyncError> {
+    project_config: &ProjectConfig,
+) -> Result<bool, SyncError> {
     let module_is_root = module_path == ROOT_MODULE_SENTINEL_TAG;
     let dependency_is_root = dependency == ROOT_MODULE_SENTINEL_TAG;
 
     if !module_is_root && !dependency_is_root {
-        project_config.add_dependency(module_path.to_string(), dependency.to_string())?;
-        return Ok(());
+        return Ok(true);
     }
 
     match project_config.root_module {
-        RootModuleTreatment::Ignore => Ok(()),
-        RootModuleTreatment::Allow => {
-            project_config.add_dependency(module_path.to_string(), dependency.to_string())?;
-            Ok(())
-        }
+        RootModuleTreatment::Ignore => Ok(false),
+        RootModuleTreatment::Allow => Ok(true),
         RootModuleTreatment::Forbid => Err(SyncError::RootModuleViolation(format!(
             "The root module is forbidden, but it was found that '{}' depends on '{}'.",
             module_path, dependency
@@ -52,8 +55,7 @@ fn handle_added_dependency(
             if dependency_is_root {
                 return Err(SyncError::RootModuleViolation(format!("No module may depend on the root module, but it was found that '{}' depends on the root module.", module_path)));
             }
-            project_config.add_dependency(module_path.to_string(), dependency.to_string())?;
-            Ok(())
+            Ok(true)
         }
     }
 }
@@ -73,18 +75,15 @@ fn detect_dependencies(diagnostics: &[Diagnostic]) -> HashMap<String, Vec<String
     dependencies
 }
 
-#[derive(Default, Clone)]
-#[pyclass(get_all, module = "tach.extension")]
-pub struct UnusedDependencies {
-    pub path: String,
-    pub dependencies: Vec<DependencyConfig>,
-}
-
-pub fn detect_unused_dependencies(
+/// Every cross-module dependency in the project, including those created by
+/// pyproject.toml entry points which refer to another module.
+fn detect_project_dependencies(
     project_root: PathBuf,
-    project_config: &mut ProjectConfig,
+    project_config: &ProjectConfig,
     exclude_paths: Vec<String>,
-) -> Result<Vec<UnusedDependencies>, SyncError> {
+) -> Result<HashMap<String, Vec<String>>, SyncError> {
+    let entry_point_dependencies =
+        entry_point_dependencies(&project_root, project_config, &exclude_paths)?;
     // This is a shortcut to finding all cross-module dependencies
     // TODO: dedicated function
     let cleared_project_config = project_config.with_dependencies_removed();
@@ -95,7 +94,27 @@ pub fn detect_unused_dependencies(
         false,
         exclude_paths,
     )?;
-    let detected_dependencies = detect_dependencies(&check_result);
+    let mut detected_dependencies = detect_dependencies(&check_result);
+    for (owner, target) in entry_point_dependencies {
+        detected_dependencies.entry(owner).or_default().push(target);
+    }
+    Ok(detected_dependencies)
+}
+
+#[derive(Default, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct UnusedDependencies {
+    pub path: String,
+    pub dependencies: Vec<DependencyConfig>,
+}
+
+pub fn detect_unused_dependencies(
+    project_root: PathBuf,
+    project_config: &mut ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<UnusedDependencies>, SyncError> {
+    let detected_dependencies =
+        detect_project_dependencies(project_root, project_config, exclude_paths)?;
 
     let mut unused_dependencies: Vec<UnusedDependencies> = vec![];
     for module_path in project_config.module_paths() {
@@ -120,6 +139,7 @@ pub fn detect_unused_dependencies(
         unused_dependencies.push(UnusedDependencies {
             path: module_path.to_string(),
             dependencies: dependencies_to_remove
+                .sorted()
                 .map(|dep| DependencyConfig::from_path(dep.to_string()))
                 .collect(),
         });
@@ -128,108 +148,397 @@ pub fn detect_unused_dependencies(
     Ok(unused_dependencies
         .into_iter()
         .filter(|dep| !dep.dependencies.is_empty())
+        .sorted_by(|a, b| a.path.cmp(&b.path))
         .collect())
 }
 
-fn sync_dependency_constraints(
+/// The dependencies which sync would add to and remove from a module.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ModuleSyncChanges {
+    pub path: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Every change which sync would make to the project configuration.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct SyncPlan {
+    // Whether the root module would be added to the configuration
+    pub create_root_module: bool,
+    // Sorted by module path, and only including modules with changes
+    pub modules: Vec<ModuleSyncChanges>,
+    // Modules which would be removed, because they no longer exist
+    pub deleted_modules: Vec<String>,
+    // Packages which would be registered as modules, following the new package policy
+    pub new_modules: Vec<String>,
+    // Packages outside every module which were left unregistered.
+    // These are only reported, so they do not make the plan non-empty.
+    pub unregistered_packages: Vec<String>,
+}
+
+#[pymethods]
+impl SyncPlan {
+    pub fn is_empty(&self) -> bool {
+        !self.create_root_module
+            && self.modules.is_empty()
+            && self.deleted_modules.is_empty()
+            && self.new_modules.is_empty()
+    }
+}
+
+/// A package which is not registered as a module.
+#[derive(Debug, PartialEq)]
+struct NewPackage {
+    path: String,
+    // The innermost module containing the package, if any
+    enclosing_module: Option<String>,
+}
+
+/// Find the outermost packages (directories with an '__init__.py') which are not modules
+/// and do not contain any module.
+fn discover_new_packages(source_roots: &[PathBuf], module_paths: &[String]) -> Vec<NewPackage> {
+    let package_paths: BTreeSet<String> = source_roots
+        .iter()
+        .flat_map(|source_root| fs::walk_pyfiles(&source_root.display().to_string()))
+        .filter(|file_path| {
+            file_path
+                .file_name()
+                .is_some_and(|name| name == "__init__.py")
+        })
+        .filter_map(|file_path| {
+            let package_dir = file_path.parent()?;
+            let components: Vec<String> = package_dir
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect();
+            // An '__init__.py' directly within a source root does not make a package
+            (!components.is_empty()).then(|| components.join("."))
+        })
+        .collect();
+
+    let is_within = |path: &str, ancestor: &str| {
+        path.strip_prefix(ancestor)
+            .is_some_and(|rest| rest.starts_with('.'))
+    };
+    let mut new_packages: Vec<NewPackage> = vec![];
+    // Sorted order visits each package before the packages within it
+    for package_path in package_paths {
+        let is_registered = module_paths.iter().any(|module_path| {
+            module_path == &package_path || is_within(module_path, &package_path)
+        });
+        let is_within_new_package = new_packages
+            .iter()
+            .any(|new_package| is_within(&package_path, &new_package.path));
+        if is_registered || is_within_new_package {
+            continue;
+        }
+        let enclosing_module = module_paths
+            .iter()
+            .filter(|module_path| is_within(&package_path, module_path))
+            .max_by_key(|module_path| module_path.len())
+            .cloned();
+        new_packages.push(NewPackage {
+            path: package_path,
+            enclosing_module,
+        });
+    }
+    new_packages
+}
+
+/// Split the packages outside every module into those which sync would register as modules,
+/// and those which it would leave unregistered.
+fn plan_new_packages(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: &[String],
+    policy: &NewPackagePolicy,
+) -> Result<(Vec<String>, Vec<String>), SyncError> {
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )
+    .map_err(CheckError::from)?;
+    let source_roots = project_config.prepend_roots(project_root);
+    let new_packages = discover_new_packages(&source_roots, &project_config.module_paths());
+
+    let (top_level, nested): (Vec<NewPackage>, Vec<NewPackage>) = new_packages
+        .into_iter()
+        .partition(|package| package.enclosing_module.is_none());
+    let top_level: Vec<String> = top_level.into_iter().map(|package| package.path).collect();
+    Ok(match policy {
+        NewPackagePolicy::TopLevel => (top_level, vec![]),
+        NewPackagePolicy::Nested => (
+            // Packages outside every module have nothing to nest under, so they are registered as-is
+            top_level
+                .into_iter()
+                .chain(nested.into_iter().map(|package| package.path))
+                .sorted()
+                .collect(),
+            vec![],
+        ),
+        NewPackagePolicy::Warn => (vec![], top_level),
+    })
+}
+
+fn plan_dependency_constraints(
     project_root: PathBuf,
-    project_config: &mut ProjectConfig,
+    project_config: &ProjectConfig,
     exclude_paths: Vec<String>,
     prune: bool,
-) -> Result<(), SyncError> {
-    // This is a shortcut to finding all cross-module dependencies
-    // TODO: dedicated function
-    let cleared_project_config = project_config.with_dependencies_removed();
-    let check_result = check_internal(
-        project_root,
-        &cleared_project_config,
-        true,
-        false,
-        exclude_paths,
+    new_package_policy: &NewPackagePolicy,
+) -> Result<SyncPlan, SyncError> {
+    let mut plan = SyncPlan::default();
+    (plan.new_modules, plan.unregistered_packages) = plan_new_packages(
+        &project_root,
+        project_config,
+        &exclude_paths,
+        new_package_policy,
     )?;
-    let detected_dependencies = detect_dependencies(&check_result);
+    // New modules are planned as though they were already registered,
+    // so that their dependencies are added along with them
+    let mut project_config = project_config.clone();
+    project_config.modules.extend(
+        plan.new_modules
+            .iter()
+            .map(|module_path| ModuleConfig::new(module_path, false)),
+    );
+    let project_config = &project_config;
 
+    let detected_dependencies =
+        detect_project_dependencies(project_root, project_config, exclude_paths)?;
+
+    let mut module_paths = project_config.module_paths();
     // Root module is a special case -- it may not be in module paths and still implicitly detect dependencies
     // If the root module is not in the module paths, but was detected, create it
-    if !project_config
-        .module_paths()
-        .contains(&ROOT_MODULE_SENTINEL_TAG.to_string())
+    if !module_paths.contains(&ROOT_MODULE_SENTINEL_TAG.to_string())
         && (detected_dependencies.contains_key(ROOT_MODULE_SENTINEL_TAG)
             || detected_dependencies
                 .values()
                 .any(|deps| deps.contains(&ROOT_MODULE_SENTINEL_TAG.to_string())))
     {
-        // This enqueues an edit to the TOML
-        project_config.create_module(ROOT_MODULE_SENTINEL_TAG.to_string())?;
-        // This adds the root module to the module paths immediately
-        project_config.add_root_module();
+        plan.create_root_module = true;
+        module_paths.push(ROOT_MODULE_SENTINEL_TAG.to_string());
     }
 
-    // Now diff with project config and apply edits
-    for module_path in project_config.module_paths() {
+    // Now diff with project config
+    for module_path in module_paths.iter().sorted() {
         let module_detected_dependencies =
             detected_dependencies
-                .get(&module_path)
+                .get(module_path)
                 .map_or(HashSet::new(), |deps| {
                     deps.iter()
                         .map(|dep| dep.to_string())
                         .collect::<HashSet<_>>()
                 });
         let module_current_dependencies = project_config
-            .dependencies_for_module(&module_path)
+            .dependencies_for_module(module_path)
             .map_or(HashSet::new(), |deps| {
                 deps.iter()
                     .map(|dep| dep.path.clone())
                     .collect::<HashSet<_>>()
             });
 
-        let dependencies_to_add =
-            module_detected_dependencies.difference(&module_current_dependencies);
-        for dep in dependencies_to_add {
-            // This handler will also handle root module treatment
-            handle_added_dependency(&module_path, dep, project_config)?;
+        // Sets have no stable order, so changes are listed in sorted order
+        let mut added = vec![];
+        for dep in module_detected_dependencies
+            .difference(&module_current_dependencies)
+            .sorted()
+        {
+            // This also handles root module treatment
+            if should_add_dependency(module_path, dep, project_config)? {
+                added.push(dep.to_string());
+            }
         }
 
-        if prune {
-            let dependencies_to_remove =
-                module_current_dependencies.difference(&module_detected_dependencies);
-            for dep in dependencies_to_remove {
-                project_config.remove_dependency(module_path.to_string(), dep.to_string())?;
-            }
+        let removed = if prune {
+            module_current_dependencies
+                .difference(&module_detected_dependencies)
+                .sorted()
+                .map(|dep| dep.to_string())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        if !added.is_empty() || !removed.is_empty() {
+            plan.modules.push(ModuleSyncChanges {
+                path: module_path.to_string(),
+                added,
+                removed,
+            });
         }
     }
 
     if prune {
-        project_config
-            .module_paths()
+        let source_roots = project_config.absolute_source_roots().unwrap();
+        plan.deleted_modules = module_paths
             .iter()
-            .for_each(|module_path| {
-                if !validate_module_path(
-                    &project_config.absolute_source_roots().unwrap(),
-                    module_path,
-                ) {
-                    // Not clear what to do if enqueueing deletion fails
-                    let _ = project_config.delete_module(module_path.to_string());
-                }
-            });
+            .filter(|module_path| !validate_module_path(&source_roots, module_path))
+            .sorted()
+            .cloned()
+            .collect();
+    }
+
+    Ok(plan)
+}
+
+fn apply_sync_plan(project_config: &mut ProjectConfig, plan: &SyncPlan) -> Result<(), SyncError> {
+    if plan.create_root_module {
+        // This enqueues an edit to the TOML
+        project_config.create_module(ROOT_MODULE_SENTINEL_TAG.to_string())?;
+        // This adds the root module to the module paths immediately
+        project_config.add_root_module();
+    }
+
+    for module_path in &plan.new_modules {
+        project_config.create_module(module_path.clone())?;
+    }
+
+    for module in &plan.modules {
+        for dep in &module.added {
+            project_config.add_dependency(module.path.clone(), dep.clone())?;
+        }
+        for dep in &module.removed {
+            project_config.remove_dependency(module.path.clone(), dep.clone())?;
+        }
+    }
+
+    for module_path in &plan.deleted_modules {
+        // Not clear what to do if enqueueing deletion fails
+        let _ = project_config.delete_module(module_path.clone());
     }
 
     Ok(())
 }
 
+/// Find the changes which 'sync_project' would make, without applying them.
+/// 'new_packages' overrides the project's policy for packages outside every module.
+pub fn plan_sync(
+    project_root: PathBuf,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+    add: bool,
+    new_packages: Option<String>,
+) -> Result<SyncPlan, SyncError> {
+    let new_package_policy = match new_packages {
+        Some(policy) => policy.parse().map_err(SyncError::NewPackagePolicy)?,
+        None => project_config.sync.new_packages.clone(),
+    };
+    plan_dependency_constraints(
+        project_root,
+        project_config,
+        exclude_paths,
+        !add,
+        &new_package_policy,
+    )
+}
+
 /// Update project configuration with auto-detected dependency constraints.
 /// If prune is set to False, it will create dependencies to resolve existing errors,
 /// but will not remove any constraints.
+/// Returns the applied plan, which lists any packages left unregistered.
 pub fn sync_project(
     project_root: PathBuf,
     mut project_config: ProjectConfig,
     exclude_paths: Vec<String>,
     add: bool,
-) -> Result<(), SyncError> {
-    // This may queue edits to the project config
-    sync_dependency_constraints(project_root, &mut project_config, exclude_paths, !add)?;
+    new_packages: Option<String>,
+) -> Result<SyncPlan, SyncError> {
+    let plan = plan_sync(
+        project_root,
+        &project_config,
+        exclude_paths,
+        add,
+        new_packages,
+    )?;
+
+    // This queues edits to the project config
+    apply_sync_plan(&mut project_config, &plan)?;
 
     project_config.apply_edits()?;
 
-    Ok(())
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_new_packages() {
+        let source_root = TempDir::new().unwrap();
+        for package in [
+            "billing",
+            "billing/api",
+            "core/models",
+            "payments",
+            "payments/gateways",
+        ] {
+            let package_dir = source_root.path().join(package);
+            fs::create_dir_all(&package_dir).unwrap();
+            fs::write(package_dir.join("__init__.py"), "").unwrap();
+        }
+
+        let new_packages = discover_new_packages(
+            &[source_root.path().to_path_buf()],
+            &["billing".to_string(), "core.models".to_string()],
+        );
+
+        assert_eq!(
+            new_packages,
+            vec![
+                NewPackage {
+                    path: "billing.api".to_string(),
+                    enclosing_module: Some("billing".to_string()),
+                },
+                NewPackage {
+                    path: "payments".to_string(),
+                    enclosing_module: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entry_point_dependency_is_used() {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for pyproject.toml
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["app", "plugins"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::write(root.join("plugins/extra.py"), "class Plugin: ...\n").unwrap();
+        // 'app' only refers to 'plugins' through the entry points of its package
+        fs::write(
+            root.join("app/pyproject.toml"),
+            "[project.entry-points.\"app.plugins\"]\nextra = \"plugins.extra:Plugin\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "[[modules]]\npath = \"app\"\ndepends_on = [\"plugins\"]\n",
+                "[[modules]]\npath = \"plugins\"\ndepends_on = []\n",
+            ),
+        )
+        .unwrap();
+        let (mut project_config, _) =
+            crate::parsing::config::parse_project_config(root.join("tach.toml")).unwrap();
+
+        let unused_dependencies =
+            detect_unused_dependencies(root.to_path_buf(), &mut project_config, vec![]).unwrap();
+
+        assert!(unused_dependencies.is_empty());
+    }
 }
@@ -185,6 +185,7 @@ pub fn get_affected_modules(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::SourceRootConfig;
     use crate::tests::test::fixtures::module_tree;
     use crate::tests::test::fixtures::modules;
     use rstest::rstest;
@@ -201,7 +202,7 @@ mod tests {
     ) {
         let project_root = env::temp_dir();
         let project_config = ProjectConfig {
-            source_roots: vec![PathBuf::from(source_root)],
+            source_roots: vec![SourceRootConfig::new(source_root)],
             ..Default::default()
         };
         let changed_files = changed_files
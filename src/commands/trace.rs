@@ -0,0 +1,350 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+use ruff_text_size::TextSize;
+use thiserror::Error;
+
+use crate::commands::check::CheckError;
+use crate::commands::helpers::import::{categorize_module_path, ImportCategory};
+use crate::config::ProjectConfig;
+use crate::exclusion::{is_path_excluded, set_excluded_paths};
+use crate::filesystem::{self as fs, ProjectFile};
+use crate::modules::{build_module_tree, ModuleTree};
+use crate::processors::file_module::FileModule;
+use crate::processors::ignore_directive::IgnoreDirective;
+use crate::processors::import::{
+    get_normalized_imports_from_ast, with_distribution_names, ImportParseError, LocatedImport,
+    NormalizedImport,
+};
+use crate::processors::InternalDependencyExtractor;
+use crate::python::{error::ParsingError, parsing::parse_python_source};
+
+#[derive(Error, Debug)]
+pub enum TraceError {
+    #[error("File '{0}' is not within a source root.")]
+    NotInSourceRoot(String),
+    #[error("File '{0}' does not belong to any module.")]
+    ModuleNotFound(String),
+    #[error(transparent)]
+    Check(#[from] CheckError),
+    #[error(transparent)]
+    Filesystem(#[from] fs::FileSystemError),
+    #[error(transparent)]
+    ImportParse(#[from] ImportParseError),
+    #[error(transparent)]
+    Parsing(#[from] ParsingError),
+}
+
+/// An import or reference found in a file, and how 'tach check' treats it.
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct TracedDependency {
+    pub line_number: usize,
+    pub module_path: String,
+    // 'import', 'string' for string imports, or the name of the plugin which found a reference
+    pub source: String,
+    pub category: ImportCategory,
+    // The module containing 'module_path', for project dependencies
+    pub module: Option<String>,
+    // The distributions which may provide 'module_path', for external dependencies
+    pub distributions: Vec<String>,
+    // Why 'tach check' ignores this dependency, if it does
+    pub ignored: Option<String>,
+}
+
+/// Everything the extraction pipeline finds in a single file.
+#[derive(Debug, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct FileTrace {
+    // Relative to the project root
+    pub file_path: PathBuf,
+    pub module_path: String,
+    // The module containing the file
+    pub module: Option<String>,
+    // Why no dependencies are extracted from the file, if they are not
+    pub skipped: Option<String>,
+    pub dependencies: Vec<TracedDependency>,
+}
+
+fn directive_reason(directive: &IgnoreDirective) -> String {
+    if directive.reason.is_empty() {
+        format!("'tach-ignore' on line {}", directive.line_no)
+    } else {
+        format!(
+            "'tach-ignore' on line {} ({})",
+            directive.line_no, directive.reason
+        )
+    }
+}
+
+struct Tracer<'a> {
+    source_roots: &'a [PathBuf],
+    project_config: &'a ProjectConfig,
+    module_tree: &'a ModuleTree,
+    extractor: &'a InternalDependencyExtractor<'a>,
+    module_mappings: &'a HashMap<String, Vec<String>>,
+    stdlib_modules: &'a HashSet<String>,
+}
+
+impl Tracer<'_> {
+    fn target_module(&self, module_path: &str) -> Option<String> {
+        let module_path = self
+            .extractor
+            .generated_code_owner(module_path)
+            .unwrap_or(module_path);
+        self.module_tree
+            .find_nearest(module_path)
+            .and_then(|module| module.config.as_ref().map(|config| config.path.clone()))
+    }
+
+    fn trace(
+        &self,
+        file_module: &FileModule,
+        source: &str,
+        module_path: &str,
+        line_number: usize,
+        ignored: Option<String>,
+    ) -> TracedDependency {
+        let category = categorize_module_path(self.source_roots, self.stdlib_modules, module_path);
+        let file_module_path = file_module.module.config.as_ref().map(|c| c.path.as_str());
+        let mut module = None;
+        let mut distributions = vec![];
+        let reason = match category {
+            ImportCategory::Project => {
+                module = self.target_module(module_path);
+                match module.as_deref() {
+                    None => Some("it is not within any module".to_string()),
+                    Some(target) if Some(target) == file_module_path => {
+                        Some("it is within the same module".to_string())
+                    }
+                    Some(_) => None,
+                }
+            }
+            ImportCategory::External => {
+                let import = NormalizedImport {
+                    module_path: module_path.to_string(),
+                    alias_path: None,
+                    import_offset: TextSize::default(),
+                    alias_offset: TextSize::default(),
+                    is_absolute: true,
+                    runs_at_import: true,
+                };
+                distributions =
+                    with_distribution_names(std::iter::once(&import), self.module_mappings)
+                        .pop()
+                        .map(|import| import.distribution_names)
+                        .unwrap_or_default();
+                distributions
+                    .iter()
+                    .any(|name| self.project_config.external.exclude.contains(name))
+                    .then(|| "it is excluded by 'external.exclude'".to_string())
+            }
+            ImportCategory::Unresolved => {
+                Some("it does not resolve to a file in the project".to_string())
+            }
+            ImportCategory::Stdlib => None,
+        };
+        TracedDependency {
+            line_number,
+            module_path: module_path.to_string(),
+            source: source.to_string(),
+            category,
+            module,
+            distributions,
+            ignored: ignored.or(reason),
+        }
+    }
+}
+
+/// Run the full extraction pipeline on 'file_path', and report everything it finds.
+/// Unlike 'tach check', ignored dependencies are included along with the reason they are ignored.
+pub fn trace_file(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+    file_path: &Path,
+    module_mappings: &HashMap<String, Vec<String>>,
+    stdlib_modules: &[String],
+) -> Result<FileTrace, TraceError> {
+    let stdlib_modules: HashSet<String> = stdlib_modules.iter().cloned().collect();
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let absolute_path = project_root.join(file_path);
+    let (source_root, relative_path) = source_roots
+        .iter()
+        .find_map(|root| {
+            absolute_path
+                .strip_prefix(root)
+                .ok()
+                .map(|relative| (root, relative))
+        })
+        .ok_or_else(|| TraceError::NotInSourceRoot(file_path.display().to_string()))?;
+
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )
+    .map_err(CheckError::from)?;
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )
+    .map_err(CheckError::from)?;
+
+    let module_path = fs::file_to_module_path(&source_roots, &absolute_path)?;
+    let module = module_tree
+        .find_nearest(&module_path)
+        .ok_or_else(|| TraceError::ModuleNotFound(file_path.display().to_string()))?;
+    let extractor = InternalDependencyExtractor::new(&source_roots, &module_tree, project_config);
+    let project_file = ProjectFile::try_new(project_root, source_root, relative_path)?;
+    let skipped = if is_path_excluded(&absolute_path) {
+        Some("the file is excluded")
+    } else {
+        extractor.skip_reason(&module, &project_file.contents)
+    };
+    let ignore_type_checking_imports =
+        project_config.ignore_type_checking_imports_in(project_root, source_root);
+    let include_string_imports =
+        project_config.include_string_imports_in(project_root, source_root);
+    let file_module = FileModule::new(project_file, module);
+    let file_ast = parse_python_source(file_module.contents())?;
+
+    // Imports are found with and without each setting, to tell which of them the setting affects
+    let imports =
+        get_normalized_imports_from_ast(&source_roots, &absolute_path, &file_ast, false, false)?;
+    let checked_offsets: HashSet<TextSize> = get_normalized_imports_from_ast(
+        &source_roots,
+        &absolute_path,
+        &file_ast,
+        ignore_type_checking_imports,
+        false,
+    )?
+    .iter()
+    .map(|import| import.alias_offset)
+    .collect();
+    let import_offsets: HashSet<TextSize> =
+        imports.iter().map(|import| import.alias_offset).collect();
+    let string_imports = if include_string_imports {
+        get_normalized_imports_from_ast(&source_roots, &absolute_path, &file_ast, true, true)?
+            .into_iter()
+            .filter(|import| !import_offsets.contains(&import.alias_offset))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let tracer = Tracer {
+        source_roots: &source_roots,
+        project_config,
+        module_tree: &module_tree,
+        extractor: &extractor,
+        module_mappings,
+        stdlib_modules: &stdlib_modules,
+    };
+    let skip_reason = || skipped.map(String::from);
+    let mut dependencies: Vec<TracedDependency> = vec![];
+    for (source, import) in imports
+        .into_iter()
+        .map(|import| ("import", import))
+        .chain(string_imports.into_iter().map(|import| ("string", import)))
+    {
+        let in_type_checking_block = !checked_offsets.contains(&import.alias_offset);
+        let located_import = LocatedImport::new(
+            file_module.line_number(import.import_offset),
+            file_module.line_number(import.alias_offset),
+            import,
+        );
+        let ignored = skip_reason()
+            .or_else(|| {
+                (source == "import" && in_type_checking_block).then(|| {
+                    "it is in a TYPE_CHECKING block ('ignore_type_checking_imports')".to_string()
+                })
+            })
+            .or_else(|| {
+                file_module
+                    .ignore_directives
+                    .is_ignored(&located_import)
+                    .then(|| {
+                        file_module
+                            .ignore_directives
+                            .get(&located_import.import_line_number())
+                            .map(directive_reason)
+                    })
+                    .flatten()
+            });
+        dependencies.push(tracer.trace(
+            &file_module,
+            source,
+            located_import.module_path(),
+            located_import.alias_line_number(),
+            ignored,
+        ));
+    }
+
+    for (plugin, reference) in extractor.plugin_references(&module_path, &file_ast) {
+        let line_number = file_module.line_number(reference.offset);
+        let ignored = skip_reason().or_else(|| {
+            file_module
+                .ignore_directives
+                .get(&line_number)
+                .filter(|directive| {
+                    directive.modules.is_empty()
+                        || directive
+                            .modules
+                            .iter()
+                            .any(|module| reference.module_path.ends_with(module))
+                })
+                .map(directive_reason)
+        });
+        dependencies.push(tracer.trace(
+            &file_module,
+            plugin,
+            &reference.module_path,
+            line_number,
+            ignored,
+        ));
+    }
+    dependencies.sort_by_key(|dependency| dependency.line_number);
+
+    Ok(FileTrace {
+        file_path: file_module.relative_file_path().to_path_buf(),
+        module: file_module.module.config.as_ref().map(|c| c.path.clone()),
+        module_path,
+        skipped: skipped.map(String::from),
+        dependencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directive_reason() {
+        let directive = IgnoreDirective {
+            modules: vec![],
+            reason: String::new(),
+            line_no: 3,
+            ignored_line_no: 4,
+        };
+        assert_eq!(directive_reason(&directive), "'tach-ignore' on line 3");
+
+        let directive = IgnoreDirective {
+            reason: "legacy".to_string(),
+            ..directive
+        };
+        assert_eq!(
+            directive_reason(&directive),
+            "'tach-ignore' on line 3 (legacy)"
+        );
+    }
+}
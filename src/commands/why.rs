@@ -0,0 +1,336 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::commands::check::CheckError;
+use crate::config::ProjectConfig;
+use crate::diagnostics::FileProcessor;
+use crate::exclusion::set_excluded_paths;
+use crate::filesystem::{self as fs, ProjectFile};
+use crate::interrupt::check_interrupt;
+use crate::modules::{build_module_tree, ModuleTree};
+use crate::processors::import::{LocatedImport, NormalizedImport};
+use crate::processors::{Dependency, FileModule, InternalDependencyExtractor};
+
+#[derive(Error, Debug)]
+pub enum WhyError {
+    #[error("Module '{0}' is not defined in the project config.")]
+    UnknownModule(String),
+    #[error(transparent)]
+    Check(#[from] CheckError),
+}
+
+/// An import (or reference) in 'file_path' which creates a dependency between two modules.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ImportLocation {
+    // Relative to the project root
+    pub file_path: PathBuf,
+    pub line_number: usize,
+    pub import_path: String,
+}
+
+/// One step in a chain of dependencies, along with every import which creates it.
+#[derive(Debug, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DependencyHop {
+    pub source: String,
+    pub target: String,
+    // Whether 'target' appears in the 'depends_on' of 'source'
+    pub declared: bool,
+    pub imports: Vec<ImportLocation>,
+}
+
+/// A project import (or plugin reference) in 'file_path', resolved to the modules at either end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ResolvedDependency {
+    // Relative to the project root
+    pub file_path: PathBuf,
+    // The module path of the file itself, such as 'billing.api.views'
+    pub file_module_path: String,
+    pub line_number: usize,
+    pub import_path: String,
+    // The modules which contain the file and the imported path, which may be the same module
+    pub source: String,
+    pub target: String,
+    // For references found by plugins, the plugin and the construct which makes the reference,
+    // such as 'django model relation'
+    pub construct: Option<String>,
+    // Whether a 'tach-ignore' directive on the line matches the dependency
+    pub ignored: bool,
+}
+
+type ModuleImports = BTreeMap<(String, String), Vec<ImportLocation>>;
+
+/// The dependencies of a processed file which resolve to a module.
+pub fn resolve_file_dependencies(
+    source_roots: &[PathBuf],
+    module_tree: &ModuleTree,
+    file_module: &FileModule,
+) -> Vec<ResolvedDependency> {
+    let source = file_module.module_config().path.clone();
+    let file_module_path =
+        fs::file_to_module_path(source_roots, file_module.file_path()).unwrap_or_default();
+    file_module
+        .dependencies
+        .iter()
+        .filter_map(|dependency| {
+            let target = module_tree
+                .find_nearest(dependency.module_path())
+                .and_then(|module| module.config.as_ref().map(|c| c.path.clone()))?;
+            let line_number = file_module.line_number(dependency.offset());
+            let (located_dependency, construct) = match dependency {
+                Dependency::Import(import) => (
+                    LocatedImport::new(
+                        file_module.line_number(import.import_offset),
+                        line_number,
+                        import.clone(),
+                    ),
+                    None,
+                ),
+                Dependency::Reference(plugin, reference) => (
+                    LocatedImport::new(
+                        line_number,
+                        line_number,
+                        NormalizedImport {
+                            module_path: reference.module_path.clone(),
+                            alias_path: None,
+                            import_offset: reference.offset,
+                            alias_offset: reference.offset,
+                            is_absolute: true,
+                            runs_at_import: false,
+                        },
+                    ),
+                    Some(format!("{} {}", plugin, reference.construct)),
+                ),
+            };
+            Some(ResolvedDependency {
+                file_path: file_module.relative_file_path().to_path_buf(),
+                file_module_path: file_module_path.clone(),
+                line_number,
+                import_path: dependency.module_path().to_string(),
+                source: source.clone(),
+                target,
+                construct,
+                ignored: file_module
+                    .ignore_directives
+                    .is_ignored(&located_dependency),
+            })
+        })
+        .collect()
+}
+
+/// Every import and reference in the project which resolves to a module, sorted by location.
+pub fn collect_resolved_dependencies(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<ResolvedDependency>, CheckError> {
+    let exclude_paths = exclude_paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let source_roots: Vec<PathBuf> = project_config.prepend_roots(project_root);
+    let (valid_modules, _) = fs::validate_project_modules(
+        &source_roots,
+        project_config.all_modules().cloned().collect(),
+    );
+    let module_tree = build_module_tree(
+        &source_roots,
+        &valid_modules,
+        false,
+        project_config.root_module.clone(),
+    )?;
+    let _excluded_paths = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &project_config.include,
+        project_config.use_regex_matching,
+    )?;
+
+    let extractor = InternalDependencyExtractor::new(&source_roots, &module_tree, project_config);
+    let mut dependencies: Vec<ResolvedDependency> = source_roots
+        .par_iter()
+        .flat_map(|source_root| {
+            fs::walk_pyfiles(&source_root.display().to_string())
+                .par_bridge()
+                .flat_map(|file_path| {
+                    if check_interrupt().is_err() {
+                        return vec![];
+                    }
+                    let Ok(project_file) =
+                        ProjectFile::try_new(project_root, source_root, &file_path)
+                    else {
+                        return vec![];
+                    };
+                    // Files which fail to parse are skipped, matching 'tach check'
+                    let Ok(file_module) = extractor.process(project_file) else {
+                        return vec![];
+                    };
+                    resolve_file_dependencies(&source_roots, &module_tree, &file_module)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    check_interrupt().map_err(|_| CheckError::Interrupt)?;
+
+    // Files are processed in parallel, so their dependencies arrive in no particular order
+    dependencies.sort_by(|a, b| {
+        (&a.file_path, a.line_number, &a.import_path).cmp(&(
+            &b.file_path,
+            b.line_number,
+            &b.import_path,
+        ))
+    });
+    Ok(dependencies)
+}
+
+/// The imports creating each dependency between two different modules.
+fn module_imports(dependencies: &[ResolvedDependency]) -> ModuleImports {
+    let mut module_imports = ModuleImports::new();
+    for dependency in dependencies {
+        if dependency.source == dependency.target {
+            continue;
+        }
+        module_imports
+            .entry((dependency.source.clone(), dependency.target.clone()))
+            .or_default()
+            .push(ImportLocation {
+                file_path: dependency.file_path.clone(),
+                line_number: dependency.line_number,
+                import_path: dependency.import_path.clone(),
+            });
+    }
+    for locations in module_imports.values_mut() {
+        locations.sort();
+    }
+    module_imports
+}
+
+/// The shortest chain of modules from 'source' to 'target' along the given edges,
+/// including both ends. Neighbours are visited in sorted order, so the chain is deterministic.
+fn shortest_path<'a>(
+    edges: impl Iterator<Item = (&'a str, &'a str)>,
+    source: &'a str,
+    target: &'a str,
+) -> Option<Vec<&'a str>> {
+    let mut adjacency: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (from, to) in edges {
+        adjacency.entry(from).or_default().push(to);
+    }
+
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut visited: HashSet<&str> = HashSet::from([source]);
+    let mut queue: VecDeque<&str> = VecDeque::from([source]);
+    while let Some(module) = queue.pop_front() {
+        if module == target {
+            let mut path = vec![target];
+            while let Some(&prev) = previous.get(path.last().unwrap()) {
+                path.push(prev);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in adjacency.get(module).into_iter().flatten() {
+            if visited.insert(next) {
+                previous.insert(next, module);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn validate_modules(
+    project_config: &ProjectConfig,
+    source: &str,
+    target: &str,
+) -> Result<(), WhyError> {
+    let module_paths = project_config.module_paths();
+    for module in [source, target] {
+        if !module_paths.iter().any(|path| path == module) {
+            return Err(WhyError::UnknownModule(module.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Explain why 'source' depends on 'target': the imports creating a direct dependency,
+/// or else the shortest chain of dependencies between them.
+/// Returns no hops when 'source' does not depend on 'target', even transitively.
+pub fn explain_dependency(
+    project_root: &Path,
+    project_config: &ProjectConfig,
+    exclude_paths: Vec<String>,
+    source: &str,
+    target: &str,
+) -> Result<Vec<DependencyHop>, WhyError> {
+    validate_modules(project_config, source, target)?;
+    let dependencies = collect_resolved_dependencies(project_root, project_config, exclude_paths)?;
+    explain_resolved_dependency(project_config, &dependencies, source, target)
+}
+
+/// Explain why 'source' depends on 'target' using dependencies which were already resolved,
+/// such as those stored in a graph artifact.
+pub fn explain_resolved_dependency(
+    project_config: &ProjectConfig,
+    dependencies: &[ResolvedDependency],
+    source: &str,
+    target: &str,
+) -> Result<Vec<DependencyHop>, WhyError> {
+    validate_modules(project_config, source, target)?;
+
+    let mut module_imports = module_imports(dependencies);
+    let edges = module_imports
+        .keys()
+        .map(|(from, to)| (from.as_str(), to.as_str()));
+    let path: Vec<String> = match shortest_path(edges, source, target) {
+        Some(path) => path.into_iter().map(String::from).collect(),
+        None => return Ok(vec![]),
+    };
+
+    Ok(path
+        .windows(2)
+        .map(|pair| {
+            let (source, target) = (pair[0].clone(), pair[1].clone());
+            DependencyHop {
+                declared: project_config
+                    .dependencies_for_module(&source)
+                    .is_some_and(|deps| deps.iter().any(|dep| dep.path == target)),
+                imports: module_imports
+                    .remove(&(source.clone(), target.clone()))
+                    .unwrap_or_default(),
+                source,
+                target,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_path() {
+        let edges = [
+            ("api", "services"),
+            ("api", "auth"),
+            ("auth", "db"),
+            ("services", "db"),
+            ("db", "utils"),
+        ];
+
+        assert_eq!(
+            shortest_path(edges.into_iter(), "api", "utils"),
+            Some(vec!["api", "auth", "db", "utils"])
+        );
+        assert_eq!(
+            shortest_path(edges.into_iter(), "api", "auth"),
+            Some(vec!["api", "auth"])
+        );
+        assert_eq!(shortest_path(edges.into_iter(), "db", "api"), None);
+    }
+}
@@ -0,0 +1,34 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Caps on how many diagnostics are printed, so that a first check of a large project stays readable.
+/// Diagnostics beyond a cap are summarized instead, but still count towards the exit code.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DiagnosticsConfig {
+    // The most diagnostics shown for a single file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_file: Option<usize>,
+    // The most diagnostics shown for a single pair of usage and definition modules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_per_module_pair: Option<usize>,
+}
+
+impl DiagnosticsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diagnostics_config() {
+        let config: DiagnosticsConfig = toml::from_str("max_per_file = 20").unwrap();
+        assert_eq!(config.max_per_file, Some(20));
+        assert_eq!(config.max_per_module_pair, None);
+    }
+}
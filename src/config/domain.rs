@@ -6,12 +6,16 @@ use serde::{Deserialize, Serialize};
 
 use crate::filesystem::file_to_module_path;
 
-use super::edit::{ConfigEdit, ConfigEditor, EditError};
+use super::edit::{
+    commit_writes, push_table, remove_module_table, rename_module_references, set_module_layer,
+    to_table, ConfigEdit, ConfigEditor, EditError, PendingWrite,
+};
 use super::interfaces::InterfaceConfig;
 use super::modules::{
     default_visibility, deserialize_modules, is_default_visibility, serialize_modules,
     DependencyConfig, ModuleConfig,
 };
+use super::span::ConfigSpan;
 use super::utils::*;
 use crate::parsing::error::ParsingError;
 
@@ -30,7 +34,18 @@ pub struct DomainRootConfig {
     #[serde(default, skip_serializing_if = "is_false")]
     pub utility: bool,
     #[serde(default, skip_serializing_if = "is_false")]
+    pub unrestricted_layer: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on_external: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "is_false")]
     pub unchecked: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor: Option<String>,
+    // Where the domain root is configured, recorded when the config is parsed
+    #[serde(skip)]
+    pub span: ConfigSpan,
 }
 
 impl DomainRootConfig {
@@ -55,9 +70,32 @@ pub struct DomainConfig {
     pub modules: Vec<ModuleConfig>,
     #[serde(default)]
     pub interfaces: Vec<InterfaceConfig>,
+    // Modules (relative to the domain) which may be used from outside the domain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public: Option<Vec<String>>,
+    // The owner of every module in this domain which does not declare its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    // Absolute module paths of the other domains which this domain may depend on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on_domains: Option<Vec<String>>,
+    // Layers for the modules within this domain, replacing the project layers between them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layers: Vec<String>,
 }
 
 impl DomainConfig {
+    /// The config for a discovered domain which has no 'tach.domain.toml' of its own.
+    pub fn implicit() -> Self {
+        Self {
+            root: Some(DomainRootConfig {
+                visibility: default_visibility(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
     pub fn with_dependencies_removed(&self) -> Self {
         Self {
             modules: self
@@ -82,49 +120,65 @@ impl DomainConfig {
                 self.root.as_ref().map(|root| root.resolve(&location)),
             ))
             .flatten()
+            .map(|module| ModuleConfig {
+                owner: module.owner.or_else(|| self.owner.clone()),
+                ..module
+            })
             .collect();
         let resolved_interfaces = self
             .interfaces
             .iter()
             .map(|interface| interface.resolve(&location))
             .collect();
+        // Only the domain root is public unless the domain declares otherwise
+        let resolved_public = match &self.public {
+            Some(public) => public
+                .iter()
+                .map(|mod_path| match mod_path.as_str() {
+                    DOMAIN_ROOT_SENTINEL => location.mod_path.clone(),
+                    _ => format!("{}.{}", location.mod_path, mod_path),
+                })
+                .collect(),
+            None => vec![location.mod_path.clone()],
+        };
         LocatedDomainConfig {
             config: self,
             location,
             resolved_modules,
             resolved_interfaces,
+            resolved_public,
             pending_edits: Default::default(),
         }
     }
 }
 
 pub const DOMAIN_ROOT_SENTINEL: &str = "<domain_root>";
+pub const DOMAIN_CONFIG_FILE: &str = "tach.domain.toml";
 
 trait Resolvable<T> {
     fn resolve(&self, location: &ConfigLocation) -> T;
 }
 
+/// Resolve a module path referenced from a domain config, such as a dependency or a successor.
+fn resolve_module_reference(path: &str, location: &ConfigLocation) -> String {
+    if let Some(absolute_path) = path.strip_prefix("//") {
+        // Absolute path does not need to be prefixed with the module path
+        absolute_path.to_string()
+    } else {
+        match path {
+            // Special case for the domain root sentinel, use the module path
+            DOMAIN_ROOT_SENTINEL => location.mod_path.clone(),
+            // Relative path needs to be prefixed with the module path
+            _ => format!("{}.{}", location.mod_path, path),
+        }
+    }
+}
+
 impl Resolvable<DependencyConfig> for DependencyConfig {
     fn resolve(&self, location: &ConfigLocation) -> DependencyConfig {
-        if self.path.starts_with("//") {
-            // Absolute path does not need to be prefixed with the module path
-            DependencyConfig {
-                path: self.path[2..].to_string(),
-                deprecated: self.deprecated,
-            }
-        } else {
-            match self.path.as_str() {
-                // Special case for the domain root sentinel, use the module path
-                DOMAIN_ROOT_SENTINEL => DependencyConfig {
-                    path: location.mod_path.clone(),
-                    deprecated: self.deprecated,
-                },
-                // Relative path needs to be prefixed with the module path
-                _ => DependencyConfig {
-                    path: format!("{}.{}", location.mod_path, self.path),
-                    deprecated: self.deprecated,
-                },
-            }
+        DependencyConfig {
+            path: resolve_module_reference(&self.path, location),
+            deprecated: self.deprecated,
         }
     }
 }
@@ -144,9 +198,18 @@ impl Resolvable<ModuleConfig> for DomainRootConfig {
             layer: self.layer.clone(),
             visibility: self.visibility.clone(),
             utility: self.utility,
+            unrestricted_layer: self.unrestricted_layer,
+            depends_on_external: self.depends_on_external.clone(),
             strict: false,
             unchecked: self.unchecked,
+            owner: None,
+            deprecated: self.deprecated,
+            successor: self
+                .successor
+                .as_ref()
+                .map(|successor| resolve_module_reference(successor, location)),
             group_id: None,
+            span: self.span.clone(),
         }
     }
 }
@@ -159,9 +222,18 @@ impl Resolvable<ModuleConfig> for ModuleConfig {
             layer: self.layer.clone(),
             visibility: self.visibility.clone(),
             utility: self.utility,
+            unrestricted_layer: self.unrestricted_layer,
+            depends_on_external: self.depends_on_external.clone(),
             strict: false,
             unchecked: self.unchecked,
+            owner: self.owner.clone(),
+            deprecated: self.deprecated,
+            successor: self
+                .successor
+                .as_ref()
+                .map(|successor| resolve_module_reference(successor, location)),
             group_id: None,
+            span: self.span.clone(),
         }
     }
 }
@@ -179,6 +251,7 @@ impl Resolvable<InterfaceConfig> for InterfaceConfig {
                 })
                 .collect(),
             data_types: self.data_types.clone(),
+            span: self.span.clone(),
         }
     }
 }
@@ -189,6 +262,7 @@ pub struct LocatedDomainConfig {
     pub location: ConfigLocation,
     resolved_modules: Vec<ModuleConfig>,
     resolved_interfaces: Vec<InterfaceConfig>,
+    resolved_public: Vec<String>,
     pending_edits: Vec<ConfigEdit>,
 }
 
@@ -207,6 +281,44 @@ impl LocatedDomainConfig {
             .with_location(self.location.clone())
     }
 
+    pub fn contains_module(&self, module_path: &str) -> bool {
+        module_path == self.location.mod_path
+            || module_path
+                .strip_prefix(&self.location.mod_path)
+                .is_some_and(|rest| rest.starts_with('.'))
+    }
+
+    /// Whether the module at 'module_path' is part of this domain's public API.
+    pub fn is_public_module(&self, module_path: &str) -> bool {
+        self.resolved_public
+            .iter()
+            .any(|public| public == module_path)
+    }
+
+    /// Whether this domain may depend on the domain rooted at 'domain_path'.
+    /// Domains which do not declare 'depends_on_domains' may depend on any domain.
+    pub fn allows_domain_dependency(&self, domain_path: &str) -> bool {
+        match &self.config.depends_on_domains {
+            Some(domains) => domains
+                .iter()
+                .any(|domain| domain.trim_start_matches("//") == domain_path),
+            None => true,
+        }
+    }
+
+    /// Whether this domain declares its own layers for the modules within it.
+    pub fn has_layers(&self) -> bool {
+        !self.config.layers.is_empty()
+    }
+
+    /// The layer of this domain as a whole, among the project layers.
+    pub fn root_layer(&self) -> Option<&String> {
+        self.config
+            .root
+            .as_ref()
+            .and_then(|root| root.layer.as_ref())
+    }
+
     pub fn normalize_module_path(&self, module_path: &str) -> String {
         if module_path == self.location.mod_path {
             DOMAIN_ROOT_SENTINEL.to_string()
@@ -220,6 +332,35 @@ impl LocatedDomainConfig {
             return format!("//{}", module_path);
         }
     }
+
+    /// Resolve the (old, new) references as they would appear in this domain's config
+    /// when renaming a module.
+    pub fn rename_references(
+        &self,
+        path: &str,
+        new_path: &str,
+    ) -> Result<(String, String), EditError> {
+        let old_reference = self.normalize_module_path(path);
+        let new_reference = self.normalize_module_path(new_path);
+        if old_reference == DOMAIN_ROOT_SENTINEL {
+            return Err(EditError::NotImplemented(
+                "Cannot rename the root module of a domain".to_string(),
+            ));
+        }
+        if !old_reference.starts_with("//") && new_reference.starts_with("//") {
+            return Err(EditError::NotImplemented(
+                "Cannot move modules between domains".to_string(),
+            ));
+        }
+        Ok((old_reference, new_reference))
+    }
+
+    /// Resolve a module reference as written in this domain's config to a full module path.
+    pub fn resolve_module_reference(&self, reference: &str) -> String {
+        DependencyConfig::from_path(reference)
+            .resolve(&self.location)
+            .path
+    }
 }
 
 impl ConfigEditor for LocatedDomainConfig {
@@ -230,7 +371,9 @@ impl ConfigEditor for LocatedDomainConfig {
             | ConfigEdit::MarkModuleAsUtility { path }
             | ConfigEdit::UnmarkModuleAsUtility { path }
             | ConfigEdit::AddDependency { path, .. }
-            | ConfigEdit::RemoveDependency { path, .. } => {
+            | ConfigEdit::RemoveDependency { path, .. }
+            | ConfigEdit::SetModuleLayer { path, .. }
+            | ConfigEdit::CreateInterface { path, .. } => {
                 if path.starts_with(&self.location.mod_path) {
                     // If this module path appears to belong to this domain, enqueue the edit
                     self.pending_edits.push(edit.clone());
@@ -239,19 +382,70 @@ impl ConfigEditor for LocatedDomainConfig {
                     Err(EditError::NotApplicable)
                 }
             }
-            ConfigEdit::AddSourceRoot { .. } | ConfigEdit::RemoveSourceRoot { .. } => {
-                Err(EditError::NotApplicable)
+            ConfigEdit::RenameModule { .. } => {
+                // Any domain may reference the renamed module
+                self.pending_edits.push(edit.clone());
+                Ok(())
+            }
+            ConfigEdit::DeleteDomain { path } if path == &self.location.mod_path => {
+                self.pending_edits.push(edit.clone());
+                Ok(())
             }
+            ConfigEdit::MoveModuleToDomain { module, domain } => {
+                // The target domain gains the module, and the domain declaring it loses it
+                if domain == &self.location.mod_path
+                    || self.modules().any(|m| m.path == module.path)
+                {
+                    self.pending_edits.push(edit.clone());
+                    Ok(())
+                } else {
+                    Err(EditError::NotApplicable)
+                }
+            }
+            ConfigEdit::AddSourceRoot { .. }
+            | ConfigEdit::RemoveSourceRoot { .. }
+            | ConfigEdit::CreateDomain { .. }
+            | ConfigEdit::DeleteDomain { .. }
+            | ConfigEdit::SetViolationBudget { .. } => Err(EditError::NotApplicable),
         }
     }
 
     fn apply_edits(&mut self) -> Result<(), EditError> {
+        commit_writes(self.pending_write()?.into_iter().collect())?;
+        self.pending_edits.clear();
+        Ok(())
+    }
+}
+
+impl LocatedDomainConfig {
+    pub(super) fn clear_pending_edits(&mut self) {
+        self.pending_edits.clear();
+    }
+
+    /// Computes the change to this domain's config file without touching the disk.
+    pub fn pending_write(&self) -> Result<Option<PendingWrite>, EditError> {
         if self.pending_edits.is_empty() {
-            return Ok(());
+            return Ok(None);
         }
 
-        let toml_str = std::fs::read_to_string(&self.location.path)
-            .map_err(|_| EditError::ConfigDoesNotExist)?;
+        if self
+            .pending_edits
+            .iter()
+            .any(|edit| matches!(edit, ConfigEdit::DeleteDomain { .. }))
+        {
+            // Deleting the domain supersedes any other edits to its config
+            return Ok(Some(PendingWrite::Remove {
+                path: self.location.path.clone(),
+            }));
+        }
+
+        let toml_str = if self.location.path.exists() {
+            std::fs::read_to_string(&self.location.path)
+                .map_err(|_| EditError::ConfigDoesNotExist)?
+        } else {
+            // Implicit domains are written to disk when they are first edited
+            toml::to_string(&self.config).map_err(|_| EditError::ParsingFailed)?
+        };
         let mut doc = toml_str
             .parse::<toml_edit::DocumentMut>()
             .map_err(|_| EditError::ParsingFailed)?;
@@ -276,7 +470,8 @@ impl ConfigEditor for LocatedDomainConfig {
                     module_table.insert("path", toml_edit::value(relative_path));
                     module_table.insert("depends_on", toml_edit::value(toml_edit::Array::new()));
 
-                    if let Some(root) = &self.config.root {
+                    // The root layer is a project layer, which doesn't apply within a layered domain
+                    if let Some(root) = self.config.root.as_ref().filter(|_| !self.has_layers()) {
                         if let Some(layer) = &root.layer {
                             module_table.insert("layer", toml_edit::value(layer));
                         }
@@ -300,35 +495,7 @@ impl ConfigEditor for LocatedDomainConfig {
                         .map(|p| p.trim_start_matches('.'))
                         .unwrap_or(path);
 
-                    if let toml_edit::Item::ArrayOfTables(modules) = &mut doc["modules"] {
-                        let mut module_index = None;
-                        for (i, table) in modules.iter_mut().enumerate() {
-                            if table
-                                .get("path")
-                                .map(|p| p.as_str() == Some(relative_path))
-                                .unwrap_or(false)
-                            {
-                                module_index = Some(i);
-                                break;
-                            } else if table
-                                .get("paths")
-                                .map(|p| p.as_array().is_some())
-                                .unwrap_or(false)
-                            {
-                                table["paths"]
-                                    .as_array_mut()
-                                    .unwrap()
-                                    .retain(|p| p.as_str().unwrap() != relative_path);
-                                if table["paths"].as_array().unwrap().is_empty() {
-                                    module_index = Some(i);
-                                }
-                                break;
-                            }
-                        }
-                        if let Some(index) = module_index {
-                            modules.remove(index);
-                        }
-                    }
+                    remove_module_table(&mut doc, relative_path);
                 }
                 ConfigEdit::MarkModuleAsUtility { path }
                 | ConfigEdit::UnmarkModuleAsUtility { path } => {
@@ -480,17 +647,91 @@ impl ConfigEditor for LocatedDomainConfig {
                         }
                     }
                 }
-                ConfigEdit::AddSourceRoot { .. } | ConfigEdit::RemoveSourceRoot { .. } => {
+                ConfigEdit::RenameModule { path, new_path } => {
+                    let (old_reference, new_reference) = self.rename_references(path, new_path)?;
+                    rename_module_references(&mut doc, &old_reference, &new_reference);
+                }
+                ConfigEdit::MoveModuleToDomain { module, domain } => {
+                    if domain != &self.location.mod_path {
+                        // This domain currently declares the module
+                        if module.path == self.location.mod_path {
+                            doc.remove("root");
+                        } else {
+                            remove_module_table(
+                                &mut doc,
+                                &self.normalize_module_path(&module.path),
+                            );
+                        }
+                        continue;
+                    }
+
+                    let depends_on = module.depends_on.as_ref().map(|deps| {
+                        deps.iter()
+                            .map(|dep| DependencyConfig {
+                                path: self.normalize_module_path(&dep.path),
+                                deprecated: dep.deprecated,
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                    let successor = module
+                        .successor
+                        .as_ref()
+                        .map(|successor| self.normalize_module_path(successor));
+                    if module.path == self.location.mod_path {
+                        let root = DomainRootConfig {
+                            depends_on,
+                            layer: module.layer.clone(),
+                            visibility: module.visibility.clone(),
+                            utility: module.utility,
+                            unrestricted_layer: module.unrestricted_layer,
+                            depends_on_external: module.depends_on_external.clone(),
+                            unchecked: module.unchecked,
+                            deprecated: module.deprecated,
+                            successor: successor.clone(),
+                            span: Default::default(),
+                        };
+                        doc.insert("root", toml_edit::Item::Table(to_table(&root)?));
+                    } else {
+                        let relative_module = ModuleConfig {
+                            path: self.normalize_module_path(&module.path),
+                            depends_on,
+                            successor,
+                            ..module.clone()
+                        };
+                        push_table(&mut doc, "modules", to_table(&relative_module)?)?;
+                    }
+                }
+                ConfigEdit::SetModuleLayer { path, layer } => {
+                    if path == &self.location.mod_path {
+                        if let Some(toml_edit::Item::Table(root)) = doc.get_mut("root") {
+                            root.insert("layer", toml_edit::value(layer));
+                        }
+                        continue;
+                    }
+                    set_module_layer(&mut doc, &self.normalize_module_path(path), layer)?;
+                }
+                ConfigEdit::CreateInterface { path, expose } => {
+                    let interface = InterfaceConfig {
+                        expose: expose.clone(),
+                        from_modules: vec![self.normalize_module_path(path)],
+                        ..Default::default()
+                    };
+                    push_table(&mut doc, "interfaces", to_table(&interface)?)?;
+                }
+                ConfigEdit::AddSourceRoot { .. }
+                | ConfigEdit::RemoveSourceRoot { .. }
+                | ConfigEdit::CreateDomain { .. }
+                | ConfigEdit::DeleteDomain { .. }
+                | ConfigEdit::SetViolationBudget { .. } => {
                     return Err(EditError::NotApplicable);
                 }
             }
         }
 
-        std::fs::write(&self.location.path, doc.to_string())
-            .map_err(|_| EditError::DiskWriteFailed)?;
-
-        self.pending_edits.clear();
-        Ok(())
+        Ok(Some(PendingWrite::Write {
+            path: self.location.path.clone(),
+            contents: doc.to_string(),
+        }))
     }
 }
 
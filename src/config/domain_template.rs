@@ -0,0 +1,75 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::domain::{DomainConfig, DomainRootConfig, DOMAIN_ROOT_SENTINEL};
+use super::interfaces::InterfaceConfig;
+use super::modules::{default_visibility, DependencyConfig};
+use super::utils::*;
+
+/// The template used by 'tach new-domain' to scaffold a domain.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DomainTemplateConfig {
+    // Whether to create an '__init__.py' in the domain directory
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub package: bool,
+    // A module (relative to the domain) which is created and exposed as the domain's public API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_module: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    // Absolute module paths which the domain root depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+impl Default for DomainTemplateConfig {
+    fn default() -> Self {
+        Self {
+            package: true,
+            public_module: None,
+            layer: None,
+            owner: None,
+            depends_on: vec![],
+        }
+    }
+}
+
+impl DomainTemplateConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The config for a new domain, declaring the domain root as its initial module.
+    pub fn domain_config(&self) -> DomainConfig {
+        let root = DomainRootConfig {
+            depends_on: Some(
+                self.depends_on
+                    .iter()
+                    .map(|path| DependencyConfig::from_path(format!("//{}", path)))
+                    .collect(),
+            ),
+            layer: self.layer.clone(),
+            visibility: default_visibility(),
+            ..Default::default()
+        };
+        let interfaces = self
+            .public_module
+            .iter()
+            .map(|public_module| InterfaceConfig {
+                expose: vec![format!("{}.*", public_module)],
+                from_modules: vec![DOMAIN_ROOT_SENTINEL.to_string()],
+                ..Default::default()
+            })
+            .collect();
+        DomainConfig {
+            root: Some(root),
+            interfaces,
+            owner: self.owner.clone(),
+            ..Default::default()
+        }
+    }
+}
@@ -1,17 +1,65 @@
+use std::io::Write;
 use std::path::PathBuf;
 
+use serde::Serialize;
 use thiserror::Error;
 
+use super::modules::ModuleConfig;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfigEdit {
-    CreateModule { path: String },
-    DeleteModule { path: String },
-    MarkModuleAsUtility { path: String },
-    UnmarkModuleAsUtility { path: String },
-    AddDependency { path: String, dependency: String },
-    RemoveDependency { path: String, dependency: String },
-    AddSourceRoot { filepath: PathBuf },
-    RemoveSourceRoot { filepath: PathBuf },
+    CreateModule {
+        path: String,
+    },
+    DeleteModule {
+        path: String,
+    },
+    MarkModuleAsUtility {
+        path: String,
+    },
+    UnmarkModuleAsUtility {
+        path: String,
+    },
+    AddDependency {
+        path: String,
+        dependency: String,
+    },
+    RemoveDependency {
+        path: String,
+        dependency: String,
+    },
+    RenameModule {
+        path: String,
+        new_path: String,
+    },
+    AddSourceRoot {
+        filepath: PathBuf,
+    },
+    RemoveSourceRoot {
+        filepath: PathBuf,
+    },
+    CreateDomain {
+        path: String,
+    },
+    DeleteDomain {
+        path: String,
+    },
+    MoveModuleToDomain {
+        module: ModuleConfig,
+        domain: String,
+    },
+    SetModuleLayer {
+        path: String,
+        layer: String,
+    },
+    CreateInterface {
+        path: String,
+        expose: Vec<String>,
+    },
+    SetViolationBudget {
+        code: String,
+        budget: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Error)]
@@ -22,6 +70,12 @@ pub enum EditError {
     ModuleNotFound,
     #[error("Module already exists")]
     ModuleAlreadyExists,
+    #[error("Module is not within the domain")]
+    ModuleOutsideDomain,
+    #[error("Domain not found")]
+    DomainNotFound,
+    #[error("Domain already exists")]
+    DomainAlreadyExists,
     #[error("Failed to parse config")]
     ParsingFailed,
     #[error("Failed to write to disk")]
@@ -36,3 +90,310 @@ pub trait ConfigEditor {
     fn enqueue_edit(&mut self, edit: &ConfigEdit) -> Result<(), EditError>;
     fn apply_edits(&mut self) -> Result<(), EditError>;
 }
+
+/// A change to a config file, computed before any config file is written.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingWrite {
+    Write { path: PathBuf, contents: String },
+    Remove { path: PathBuf },
+}
+
+/// Apply changes to several config files together. New contents are written to temporary files
+/// beside their targets, which are renamed into place only once every one of them is written.
+pub fn commit_writes(writes: Vec<PendingWrite>) -> Result<(), EditError> {
+    let mut staged = Vec::new();
+    for write in &writes {
+        if let PendingWrite::Write { path, contents } = write {
+            let directory = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(std::path::Path::new("."));
+            let mut file = tempfile::NamedTempFile::new_in(directory)
+                .map_err(|_| EditError::DiskWriteFailed)?;
+            file.write_all(contents.as_bytes())
+                .map_err(|_| EditError::DiskWriteFailed)?;
+            // Temporary files are only readable by their owner, unlike the files they replace
+            if let Ok(metadata) = std::fs::metadata(path) {
+                file.as_file()
+                    .set_permissions(metadata.permissions())
+                    .map_err(|_| EditError::DiskWriteFailed)?;
+            }
+            staged.push((file, path));
+        }
+    }
+    for (file, path) in staged {
+        file.persist(path).map_err(|_| EditError::DiskWriteFailed)?;
+    }
+    for write in writes {
+        if let PendingWrite::Remove { path } = write {
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|_| EditError::DiskWriteFailed)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Serialize a config value (e.g. a module) as a TOML table.
+pub fn to_table<T: Serialize>(value: &T) -> Result<toml_edit::Table, EditError> {
+    let doc = toml::to_string(value)
+        .map_err(|_| EditError::ParsingFailed)?
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|_| EditError::ParsingFailed)?;
+    // Copy into a new table so that it is positioned wherever it is inserted
+    let mut table = toml_edit::Table::new();
+    for (key, item) in doc.iter() {
+        table.insert(key, item.clone());
+    }
+    Ok(table)
+}
+
+/// Append a table to the array of tables at 'key' (e.g. '[[modules]]').
+pub fn push_table(
+    doc: &mut toml_edit::DocumentMut,
+    key: &str,
+    table: toml_edit::Table,
+) -> Result<(), EditError> {
+    match doc.get_mut(key) {
+        // If the key is a regular array (modules = []) or doesn't exist, convert it to array of tables
+        None | Some(toml_edit::Item::Value(toml_edit::Value::Array(_))) => {
+            let mut array = toml_edit::ArrayOfTables::new();
+            array.push(table);
+            doc[key] = toml_edit::Item::ArrayOfTables(array);
+        }
+        // If the key is already an array of tables, just push
+        Some(toml_edit::Item::ArrayOfTables(array)) => {
+            array.push(table);
+        }
+        _ => return Err(EditError::ParsingFailed),
+    }
+    Ok(())
+}
+
+/// Set the layer of the module entry for 'path' in a config document.
+pub fn set_module_layer(
+    doc: &mut toml_edit::DocumentMut,
+    path: &str,
+    layer: &str,
+) -> Result<(), EditError> {
+    if let Some(toml_edit::Item::ArrayOfTables(modules)) = doc.get_mut("modules") {
+        for table in modules.iter_mut() {
+            if table.get("path").and_then(|p| p.as_str()) == Some(path) {
+                table.insert("layer", toml_edit::value(layer));
+                return Ok(());
+            }
+            if table
+                .get("paths")
+                .and_then(|p| p.as_array())
+                .is_some_and(|paths| paths.iter().any(|p| p.as_str() == Some(path)))
+            {
+                return Err(EditError::NotImplemented(
+                    "Cannot set the layer of multi-path modules".to_string(),
+                ));
+            }
+        }
+    }
+    Err(EditError::ModuleNotFound)
+}
+
+/// Set the violation budget for a diagnostic code in the '[exit.budgets]' table of a config document.
+pub fn set_violation_budget(
+    doc: &mut toml_edit::DocumentMut,
+    code: &str,
+    budget: usize,
+) -> Result<(), EditError> {
+    let budgets = doc
+        .entry("exit")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or(EditError::ParsingFailed)?
+        .entry("budgets")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or(EditError::ParsingFailed)?;
+    budgets.insert(code, toml_edit::value(budget as i64));
+    Ok(())
+}
+
+/// Remove a module entry from a config document, including its entry in a multi-path module.
+pub fn remove_module_table(doc: &mut toml_edit::DocumentMut, path: &str) {
+    if let Some(toml_edit::Item::ArrayOfTables(modules)) = doc.get_mut("modules") {
+        let mut module_index = None;
+        for (i, table) in modules.iter_mut().enumerate() {
+            if table
+                .get("path")
+                .map(|p| p.as_str() == Some(path))
+                .unwrap_or(false)
+            {
+                module_index = Some(i);
+                break;
+            } else if table
+                .get("paths")
+                .map(|p| p.as_array().is_some())
+                .unwrap_or(false)
+            {
+                table["paths"]
+                    .as_array_mut()
+                    .unwrap()
+                    .retain(|p| p.as_str().unwrap() != path);
+                if table["paths"].as_array().unwrap().is_empty() {
+                    module_index = Some(i);
+                }
+                break;
+            }
+        }
+        if let Some(index) = module_index {
+            modules.remove(index);
+        }
+    }
+}
+
+fn rename_in_array(array: &mut toml_edit::Array, old_path: &str, new_path: &str) {
+    for item in array.iter_mut() {
+        if item.as_str() == Some(old_path) {
+            // Preserve surrounding whitespace and comments
+            let decor = item.decor().clone();
+            *item = new_path.into();
+            *item.decor_mut() = decor;
+        } else if let toml_edit::Value::InlineTable(table) = item {
+            if table.get("path").and_then(|p| p.as_str()) == Some(old_path) {
+                table.insert("path", new_path.into());
+            }
+        }
+    }
+}
+
+fn rename_successor(table: &mut toml_edit::Table, old_path: &str, new_path: &str) {
+    if table.get("successor").and_then(|s| s.as_str()) == Some(old_path) {
+        table.insert("successor", toml_edit::value(new_path));
+    }
+}
+
+/// Rewrite every reference to a module path within a config document,
+/// including module entries, dependencies, successors, and interface 'from' lists.
+pub fn rename_module_references(doc: &mut toml_edit::DocumentMut, old_path: &str, new_path: &str) {
+    if let Some(toml_edit::Item::ArrayOfTables(modules)) = doc.get_mut("modules") {
+        for table in modules.iter_mut() {
+            if table.get("path").and_then(|p| p.as_str()) == Some(old_path) {
+                table.insert("path", toml_edit::value(new_path));
+            }
+            if let Some(paths) = table.get_mut("paths").and_then(|p| p.as_array_mut()) {
+                rename_in_array(paths, old_path, new_path);
+            }
+            if let Some(depends_on) = table.get_mut("depends_on").and_then(|d| d.as_array_mut()) {
+                rename_in_array(depends_on, old_path, new_path);
+            }
+            rename_successor(table, old_path, new_path);
+        }
+    }
+
+    if let Some(toml_edit::Item::Table(root)) = doc.get_mut("root") {
+        if let Some(depends_on) = root.get_mut("depends_on").and_then(|d| d.as_array_mut()) {
+            rename_in_array(depends_on, old_path, new_path);
+        }
+        rename_successor(root, old_path, new_path);
+    }
+
+    if let Some(toml_edit::Item::ArrayOfTables(interfaces)) = doc.get_mut("interfaces") {
+        for table in interfaces.iter_mut() {
+            if let Some(from_modules) = table.get_mut("from").and_then(|f| f.as_array_mut()) {
+                rename_in_array(from_modules, old_path, new_path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_module_references() {
+        let mut doc = r#"
+[[modules]]
+path = "old"
+depends_on = ["other"]
+
+[[modules]]
+path = "other"
+depends_on = ["old", { path = "old", deprecated = true }]
+
+[[modules]]
+paths = ["old", "third"]
+
+[[modules]]
+path = "legacy"
+deprecated = true
+successor = "old"
+
+[[interfaces]]
+expose = ["api"]
+from = ["old"]
+"#
+        .parse::<toml_edit::DocumentMut>()
+        .unwrap();
+
+        rename_module_references(&mut doc, "old", "new");
+
+        let rendered = doc.to_string();
+        assert!(!rendered.contains("\"old\""));
+        assert_eq!(rendered.matches("\"new\"").count(), 6);
+    }
+
+    #[test]
+    fn test_set_module_layer() {
+        let mut doc = r#"
+[[modules]]
+path = "api"
+depends_on = []
+
+[[modules]]
+paths = ["core", "models"]
+"#
+        .parse::<toml_edit::DocumentMut>()
+        .unwrap();
+
+        set_module_layer(&mut doc, "api", "interface").unwrap();
+        assert_eq!(doc["modules"][0]["layer"].as_str(), Some("interface"));
+        assert!(matches!(
+            set_module_layer(&mut doc, "core", "domain"),
+            Err(EditError::NotImplemented(_))
+        ));
+        assert_eq!(
+            set_module_layer(&mut doc, "missing", "domain"),
+            Err(EditError::ModuleNotFound)
+        );
+    }
+
+    #[test]
+    fn test_set_violation_budget() {
+        let mut doc = r#"
+[exit]
+fail_on = "warning"
+
+[exit.budgets]
+undeclared-dependency = 12
+"#
+        .parse::<toml_edit::DocumentMut>()
+        .unwrap();
+
+        set_violation_budget(&mut doc, "undeclared-dependency", 9).unwrap();
+        set_violation_budget(&mut doc, "private-import", 0).unwrap();
+        assert_eq!(
+            doc["exit"]["budgets"]["undeclared-dependency"].as_integer(),
+            Some(9)
+        );
+        assert_eq!(
+            doc["exit"]["budgets"]["private-import"].as_integer(),
+            Some(0)
+        );
+        assert_eq!(doc["exit"]["fail_on"].as_str(), Some("warning"));
+
+        let mut doc = "".parse::<toml_edit::DocumentMut>().unwrap();
+        set_violation_budget(&mut doc, "layer-violation", 3).unwrap();
+        assert_eq!(
+            doc["exit"]["budgets"]["layer-violation"].as_integer(),
+            Some(3)
+        );
+    }
+}
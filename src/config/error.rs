@@ -4,4 +4,6 @@ use thiserror::Error;
 pub enum ConfigError {
     #[error("Config file does not exist")]
     ConfigDoesNotExist,
+    #[error("Failed to deserialize config: {0}")]
+    Deserialize(#[from] serde_json::Error),
 }
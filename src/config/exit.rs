@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::utils::*;
+
+/// The lowest severity of violation which causes a check to fail.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FailOn {
+    #[default]
+    Error,
+    Warning,
+    Never,
+}
+
+impl FailOn {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl IntoPy<PyObject> for FailOn {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            Self::Error => "error".to_object(py),
+            Self::Warning => "warning".to_object(py),
+            Self::Never => "never".to_object(py),
+        }
+    }
+}
+
+/// Controls the exit code of 'tach check' and 'tach check-external'.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ExitConfig {
+    #[serde(default, skip_serializing_if = "FailOn::is_default")]
+    pub fail_on: FailOn,
+    // Checks only fail when the number of failing violations exceeds this threshold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_violations: Option<usize>,
+    // Exit with a distinct code for each category of failure, combined as bit flags
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub category_exit_codes: bool,
+    // The number of failing violations allowed for each diagnostic code,
+    // which 'tach check' lowers as violations are fixed
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budgets: BTreeMap<String, usize>,
+    // A JSON file which stores the violation budgets instead of this config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_file: Option<String>,
+}
+
+impl ExitConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exit_config() {
+        let config: ExitConfig = toml::from_str(
+            r#"
+            fail_on = "warning"
+            max_violations = 10
+            category_exit_codes = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.fail_on, FailOn::Warning);
+        assert_eq!(config.max_violations, Some(10));
+        assert!(config.category_exit_codes);
+    }
+
+    #[test]
+    fn test_parse_violation_budgets() {
+        let config: ExitConfig = toml::from_str(
+            r#"
+            budget_file = "tach-budgets.json"
+
+            [budgets]
+            undeclared-dependency = 12
+            private-import = 0
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.budgets.get("undeclared-dependency"), Some(&12));
+        assert_eq!(config.budgets.get("private-import"), Some(&0));
+        assert_eq!(config.budget_file.as_deref(), Some("tach-budgets.json"));
+    }
+
+    #[test]
+    fn test_default_exit_config_is_not_serialized() {
+        assert!(ExitConfig::default().is_default());
+        assert_eq!(toml::to_string(&ExitConfig::default()).unwrap(), "");
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +10,13 @@ pub struct ExternalDependencyConfig {
     pub exclude: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rename: Vec<String>,
+    // Path to the virtualenv providing installed distributions, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub virtualenv: Option<String>,
+    // The most distinct external packages which modules in each layer may import together.
+    // Layers which are not listed are unlimited.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub layer_budgets: BTreeMap<String, usize>,
 }
 
 impl ExternalDependencyConfig {
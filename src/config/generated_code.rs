@@ -0,0 +1,49 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+fn default_max_lines() -> usize {
+    5
+}
+
+fn is_default_max_lines(max_lines: &usize) -> bool {
+    *max_lines == default_max_lines()
+}
+
+/// Detects generated files by a marker comment near the top of the file.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct GeneratedCodeConfig {
+    // Files containing any of these markers (e.g. '@generated') are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<String>,
+    // The number of lines at the start of each file which are searched for a marker
+    #[serde(
+        default = "default_max_lines",
+        skip_serializing_if = "is_default_max_lines"
+    )]
+    pub max_lines: usize,
+}
+
+impl Default for GeneratedCodeConfig {
+    fn default() -> Self {
+        Self {
+            markers: vec![],
+            max_lines: default_max_lines(),
+        }
+    }
+}
+
+impl GeneratedCodeConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn is_generated(&self, contents: &str) -> bool {
+        !self.markers.is_empty()
+            && contents
+                .lines()
+                .take(self.max_lines)
+                .any(|line| self.markers.iter().any(|marker| line.contains(marker)))
+    }
+}
@@ -0,0 +1,64 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+fn default_percentile() -> usize {
+    90
+}
+
+fn default_min_edges() -> usize {
+    3
+}
+
+/// Thresholds for flagging 'god modules', which have both a high fan-in and a high fan-out.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct GodModuleConfig {
+    // A module is flagged when its fan-in and fan-out are at or above these percentiles
+    #[serde(default = "default_percentile")]
+    pub fan_in_percentile: usize,
+    #[serde(default = "default_percentile")]
+    pub fan_out_percentile: usize,
+    // ... and also at or above these absolute thresholds, so small projects aren't flagged
+    #[serde(default = "default_min_edges")]
+    pub min_fan_in: usize,
+    #[serde(default = "default_min_edges")]
+    pub min_fan_out: usize,
+}
+
+impl Default for GodModuleConfig {
+    fn default() -> Self {
+        Self {
+            fan_in_percentile: default_percentile(),
+            fan_out_percentile: default_percentile(),
+            min_fan_in: default_min_edges(),
+            min_fan_out: default_min_edges(),
+        }
+    }
+}
+
+impl GodModuleConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_god_module_config() {
+        let config: GodModuleConfig = toml::from_str(
+            r#"
+            fan_in_percentile = 75
+            min_fan_out = 5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.fan_in_percentile, 75);
+        assert_eq!(config.fan_out_percentile, 90);
+        assert_eq!(config.min_fan_in, 3);
+        assert_eq!(config.min_fan_out, 5);
+    }
+}
@@ -3,6 +3,8 @@ use std::fmt::Display;
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::span::ConfigSpan;
+
 #[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum InterfaceDataTypes {
@@ -45,6 +47,9 @@ pub struct InterfaceConfig {
     pub from_modules: Vec<String>,
     #[serde(default, skip_serializing_if = "InterfaceDataTypes::is_default")]
     pub data_types: InterfaceDataTypes,
+    // Where the interface is defined, recorded when the config is parsed
+    #[serde(skip)]
+    pub span: ConfigSpan,
 }
 
 fn default_from_modules() -> Vec<String> {
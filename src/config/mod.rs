@@ -1,23 +1,43 @@
 pub mod cache;
+pub mod diagnostics;
 pub mod domain;
+pub mod domain_template;
 pub mod edit;
 pub mod error;
+pub mod exit;
 pub mod external;
+pub mod generated_code;
+pub mod god_modules;
 pub mod interfaces;
 pub mod modules;
+pub mod packages;
 pub mod plugins;
 pub mod project;
+pub mod python_version;
 pub mod root_module;
 pub mod rules;
+pub mod source_roots;
+pub mod span;
+pub mod sync;
 pub mod utils;
 
 pub use cache::{CacheBackend, CacheConfig};
+pub use diagnostics::DiagnosticsConfig;
 pub use domain::{ConfigLocation, DomainConfig, LocatedDomainConfig};
+pub use domain_template::DomainTemplateConfig;
 pub use edit::ConfigEdit;
 pub use error::ConfigError;
+pub use exit::{ExitConfig, FailOn};
 pub use external::ExternalDependencyConfig;
+pub use generated_code::GeneratedCodeConfig;
+pub use god_modules::GodModuleConfig;
 pub use interfaces::{InterfaceConfig, InterfaceDataTypes};
 pub use modules::{serialize_modules_json, DependencyConfig, ModuleConfig};
+pub use packages::PackageConfig;
 pub use plugins::PluginsConfig;
 pub use project::ProjectConfig;
+pub use python_version::PythonVersion;
 pub use rules::{RuleSetting, RulesConfig};
+pub use source_roots::SourceRootConfig;
+pub use span::ConfigSpan;
+pub use sync::{NewPackagePolicy, SyncConfig};
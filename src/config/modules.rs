@@ -1,6 +1,7 @@
 use crate::filesystem::module_path_is_included_in_paths;
 
 use super::root_module::ROOT_MODULE_SENTINEL_TAG;
+use super::span::ConfigSpan;
 use super::utils::*;
 use pyo3::prelude::*;
 use serde::ser::{Error, SerializeSeq, SerializeStruct};
@@ -138,6 +139,10 @@ pub struct ModuleConfig {
     pub visibility: Vec<String>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub utility: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub unrestricted_layer: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on_external: Option<Vec<String>>,
     // TODO: Remove this in a future version
     // This will be deserialized from old config,
     // but auto-migrated to interfaces internally.
@@ -146,10 +151,21 @@ pub struct ModuleConfig {
     pub strict: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub unchecked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    // A deprecated module may keep its existing consumers, but may not gain new ones
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    // The module which replaces a deprecated module
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor: Option<String>,
     // Hidden field to track grouping
     // Unfortunately marked as public due to test fixtures constructing struct literals
     #[serde(skip)]
     pub group_id: Option<usize>,
+    // Where the module is configured, recorded when the config is parsed
+    #[serde(skip)]
+    pub span: ConfigSpan,
 }
 
 impl Default for ModuleConfig {
@@ -160,9 +176,15 @@ impl Default for ModuleConfig {
             layer: Default::default(),
             visibility: default_visibility(),
             utility: Default::default(),
+            unrestricted_layer: Default::default(),
+            depends_on_external: Default::default(),
             strict: Default::default(),
             unchecked: Default::default(),
+            owner: Default::default(),
+            deprecated: Default::default(),
+            successor: Default::default(),
             group_id: Default::default(),
+            span: Default::default(),
         }
     }
 }
@@ -176,9 +198,15 @@ impl ModuleConfig {
             layer: Some(layer.to_string()),
             visibility: default_visibility(),
             utility: false,
+            unrestricted_layer: false,
+            depends_on_external: None,
             strict: false,
             unchecked: false,
+            owner: None,
+            deprecated: false,
+            successor: None,
             group_id: None,
+            span: Default::default(),
         }
     }
 
@@ -246,9 +274,15 @@ impl ModuleConfig {
             layer: None,
             visibility: default_visibility(),
             utility: false,
+            unrestricted_layer: false,
+            depends_on_external: None,
             strict,
             unchecked: false,
+            owner: None,
+            deprecated: false,
+            successor: None,
             group_id: None,
+            span: Default::default(),
         }
     }
 
@@ -283,7 +317,17 @@ struct BulkModule {
     #[serde(default, skip_serializing_if = "is_false")]
     utility: bool,
     #[serde(default, skip_serializing_if = "is_false")]
+    unrestricted_layer: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    depends_on_external: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "is_false")]
     unchecked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    successor: Option<String>,
 }
 
 impl TryFrom<&[&ModuleConfig]> for BulkModule {
@@ -301,7 +345,12 @@ impl TryFrom<&[&ModuleConfig]> for BulkModule {
             layer: first.layer.clone(),
             visibility: first.visibility.clone(),
             utility: first.utility,
+            unrestricted_layer: first.unrestricted_layer,
+            depends_on_external: first.depends_on_external.clone(),
             unchecked: first.unchecked,
+            owner: first.owner.clone(),
+            deprecated: first.deprecated,
+            successor: first.successor.clone(),
         };
 
         let mut unique_deps: HashSet<DependencyConfig> = HashSet::new();
@@ -329,6 +378,18 @@ impl TryFrom<&[&ModuleConfig]> for BulkModule {
                     module.path
                 ));
             }
+            if module.unrestricted_layer != first.unrestricted_layer {
+                return Err(format!(
+                    "Inconsistent unrestricted_layer setting in bulk module group for path {}",
+                    module.path
+                ));
+            }
+            if module.depends_on_external != first.depends_on_external {
+                return Err(format!(
+                    "Inconsistent depends_on_external in bulk module group for path {}",
+                    module.path
+                ));
+            }
             if module.strict != first.strict {
                 return Err(format!(
                     "Inconsistent strict setting in bulk module group for path {}",
@@ -341,6 +402,18 @@ impl TryFrom<&[&ModuleConfig]> for BulkModule {
                     module.path
                 ));
             }
+            if module.owner != first.owner {
+                return Err(format!(
+                    "Inconsistent owner in bulk module group for path {}",
+                    module.path
+                ));
+            }
+            if module.deprecated != first.deprecated || module.successor != first.successor {
+                return Err(format!(
+                    "Inconsistent deprecation in bulk module group for path {}",
+                    module.path
+                ));
+            }
         }
 
         if !unique_deps.is_empty() {
@@ -411,9 +484,15 @@ where
                     layer: bulk.layer.clone(),
                     visibility: bulk.visibility.clone(),
                     utility: bulk.utility,
+                    unrestricted_layer: bulk.unrestricted_layer,
+                    depends_on_external: bulk.depends_on_external.clone(),
                     strict: false,
                     unchecked: bulk.unchecked,
+                    owner: bulk.owner.clone(),
+                    deprecated: bulk.deprecated,
+                    successor: bulk.successor.clone(),
                     group_id: Some(i),
+                    span: Default::default(),
                 })
                 .collect(),
         })
@@ -0,0 +1,56 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A group of modules under a path prefix, treated as one unit for reporting,
+/// visualization, and coarse dependency rules. Module checks are unchanged.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct PackageConfig {
+    pub path: String,
+    // The other packages which modules in this package may depend on, if restricted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+}
+
+impl PackageConfig {
+    pub fn contains_module(&self, module_path: &str) -> bool {
+        module_path == self.path
+            || module_path
+                .strip_prefix(&self.path)
+                .is_some_and(|rest| rest.starts_with('.'))
+    }
+
+    pub fn allows_package_dependency(&self, package_path: &str) -> bool {
+        self.depends_on
+            .as_ref()
+            .is_none_or(|depends_on| depends_on.iter().any(|path| path == package_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_membership_and_rules() {
+        let package: PackageConfig = toml::from_str(
+            r#"
+            path = "billing"
+            depends_on = ["core"]
+            "#,
+        )
+        .unwrap();
+        assert!(package.contains_module("billing"));
+        assert!(package.contains_module("billing.invoices.pdf"));
+        assert!(!package.contains_module("billing_legacy"));
+        assert!(package.allows_package_dependency("core"));
+        assert!(!package.allows_package_dependency("auth"));
+
+        let unrestricted = PackageConfig {
+            path: "core".to_string(),
+            depends_on: None,
+        };
+        assert!(unrestricted.allows_package_dependency("auth"));
+    }
+}
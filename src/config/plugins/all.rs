@@ -1,13 +1,34 @@
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::airflow::AirflowConfig;
+use super::celery::CeleryConfig;
+use super::dependency_injector::DependencyInjectorConfig;
 use super::django::DjangoConfig;
+use super::forward_refs::ForwardRefsConfig;
+use super::protobuf::ProtobufConfig;
+use super::references::ReferencePatternConfig;
+use super::sqlalchemy::SqlAlchemyConfig;
 
 #[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
 #[pyclass(get_all, module = "tach.extension")]
 pub struct PluginsConfig {
     #[serde(default)]
     pub django: Option<DjangoConfig>,
+    #[serde(default)]
+    pub celery: Option<CeleryConfig>,
+    #[serde(default)]
+    pub sqlalchemy: Option<SqlAlchemyConfig>,
+    #[serde(default)]
+    pub forward_refs: Option<ForwardRefsConfig>,
+    #[serde(default)]
+    pub airflow: Option<AirflowConfig>,
+    #[serde(default)]
+    pub dependency_injector: Option<DependencyInjectorConfig>,
+    #[serde(default)]
+    pub protobuf: Option<ProtobufConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<ReferencePatternConfig>,
 }
 
 impl PluginsConfig {
@@ -1,4 +1,11 @@
+pub mod airflow;
 mod all;
+pub mod celery;
+pub mod dependency_injector;
 pub mod django;
+pub mod forward_refs;
+pub mod protobuf;
+pub mod references;
+pub mod sqlalchemy;
 
 pub use all::PluginsConfig;
@@ -0,0 +1,15 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maps packages of generated protobuf code ('*_pb2', '*_pb2_grpc') to the module which owns them.
+///
+/// Keys are dotted package paths containing generated code, and values are module paths.
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ProtobufConfig {
+    #[serde(default)]
+    pub mappings: BTreeMap<String, String>,
+}
@@ -0,0 +1,21 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A user-defined pattern for string references to project modules.
+///
+/// Either 'call' or 'regex' should be set. With 'call', the string argument at position
+/// 'argument' (or the 'keyword' argument) of matching calls is used as the module path.
+/// With 'regex', any string literal matching the pattern is used, or its first capture group.
+#[derive(Debug, Serialize, Default, Deserialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct ReferencePatternConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    #[serde(default)]
+    pub argument: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+}
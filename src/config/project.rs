@@ -1,26 +1,48 @@
 use pyo3::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 use std::iter;
 use std::path::{Path, PathBuf};
 
-use crate::filesystem::module_path_is_included_in_paths;
+use crate::filesystem::{
+    module_path_is_included_in_paths, normalize_path, normalize_separators, to_forward_slashes,
+};
 
 use super::cache::CacheConfig;
-use super::domain::LocatedDomainConfig;
-use super::edit::{ConfigEdit, ConfigEditor, EditError};
+use super::diagnostics::DiagnosticsConfig;
+use super::domain::{ConfigLocation, DomainConfig, LocatedDomainConfig, DOMAIN_CONFIG_FILE};
+use super::domain_template::DomainTemplateConfig;
+use super::edit::{
+    commit_writes, push_table, remove_module_table, rename_module_references, set_module_layer,
+    set_violation_budget, to_table, ConfigEdit, ConfigEditor, EditError, PendingWrite,
+};
 use super::error::ConfigError;
+use super::exit::ExitConfig;
 use super::external::ExternalDependencyConfig;
+use super::generated_code::GeneratedCodeConfig;
+use super::god_modules::GodModuleConfig;
 use super::interfaces::InterfaceConfig;
 use super::modules::{deserialize_modules, serialize_modules, DependencyConfig, ModuleConfig};
+use super::packages::PackageConfig;
 use super::plugins::PluginsConfig;
+use super::python_version::PythonVersion;
 use super::root_module::RootModuleTreatment;
 use super::rules::RulesConfig;
+use super::source_roots::{
+    default_source_roots, deserialize_source_roots, serialize_source_roots, SourceRootConfig,
+};
+use super::sync::SyncConfig;
 use super::utils::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 #[pyclass(module = "tach.extension")]
 pub struct ProjectConfig {
+    // The schema version of the config, which 'tach upgrade' sets to CONFIG_VERSION.
+    // Configs written before versioning have no version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[pyo3(get)]
+    pub version: Option<u32>,
     #[serde(
         default,
         deserialize_with = "deserialize_modules",
@@ -34,18 +56,43 @@ pub struct ProjectConfig {
     #[serde(default, skip_serializing_if = "is_empty")]
     #[pyo3(get)]
     pub layers: Vec<String>,
+    // Groups of modules under a path prefix, for reporting and coarse dependency rules
+    #[serde(default, skip_serializing_if = "is_empty")]
+    #[pyo3(get)]
+    pub packages: Vec<PackageConfig>,
     #[serde(default, skip_serializing_if = "CacheConfig::is_default")]
     #[pyo3(get)]
     pub cache: CacheConfig,
     #[serde(default, skip_serializing_if = "ExternalDependencyConfig::is_default")]
     #[pyo3(get)]
     pub external: ExternalDependencyConfig,
+    #[serde(default, skip_serializing_if = "GeneratedCodeConfig::is_default")]
+    #[pyo3(get)]
+    pub generated_code: GeneratedCodeConfig,
+    #[serde(default, skip_serializing_if = "ExitConfig::is_default")]
+    #[pyo3(get)]
+    pub exit: ExitConfig,
+    #[serde(default, skip_serializing_if = "GodModuleConfig::is_default")]
+    #[pyo3(get)]
+    pub god_modules: GodModuleConfig,
+    #[serde(default, skip_serializing_if = "SyncConfig::is_default")]
+    #[pyo3(get)]
+    pub sync: SyncConfig,
+    #[serde(default, skip_serializing_if = "DiagnosticsConfig::is_default")]
+    #[pyo3(get)]
+    pub diagnostics: DiagnosticsConfig,
     #[serde(default)]
     #[pyo3(get)]
     pub exclude: Vec<String>,
-    #[serde(default = "default_source_roots")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     #[pyo3(get)]
-    pub source_roots: Vec<PathBuf>,
+    pub include: Vec<String>,
+    #[serde(
+        default = "default_source_roots",
+        deserialize_with = "deserialize_source_roots",
+        serialize_with = "serialize_source_roots"
+    )]
+    pub source_roots: Vec<SourceRootConfig>,
     #[serde(default, skip_serializing_if = "is_false")]
     #[pyo3(get)]
     pub exact: bool,
@@ -64,15 +111,42 @@ pub struct ProjectConfig {
     #[serde(default, skip_serializing_if = "is_false")]
     #[pyo3(get)]
     pub use_regex_matching: bool,
+    // Require imports of another module to use a member exposed by an interface or '__all__'
+    #[serde(default, skip_serializing_if = "is_false")]
+    #[pyo3(get)]
+    pub strict_exports: bool,
     #[serde(default, skip_serializing_if = "RootModuleTreatment::is_default")]
     #[pyo3(get)]
     pub root_module: RootModuleTreatment,
+    // The oldest Python version the project supports, beyond which newer syntax is reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[pyo3(get)]
+    pub python_version: Option<PythonVersion>,
     #[serde(default, skip_serializing_if = "RulesConfig::is_default")]
     #[pyo3(get)]
     pub rules: RulesConfig,
     #[serde(default, skip_serializing_if = "PluginsConfig::is_default")]
     #[pyo3(get)]
     pub plugins: PluginsConfig,
+    #[serde(default, skip_serializing_if = "is_false")]
+    #[pyo3(get)]
+    pub use_codeowners: bool,
+    // A template (e.g. '{path}:{line}: {code} {message}') for each diagnostic in text output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[pyo3(get)]
+    pub output_format: Option<String>,
+    // Patterns (e.g. 'auto:src/domains/*') for directories which are implicitly domains
+    #[serde(
+        default,
+        rename = "domains",
+        deserialize_with = "deserialize_domain_patterns",
+        skip_serializing_if = "is_empty"
+    )]
+    #[pyo3(get)]
+    pub domain_patterns: Vec<String>,
+    #[serde(default, skip_serializing_if = "DomainTemplateConfig::is_default")]
+    #[pyo3(get)]
+    pub domain_template: DomainTemplateConfig,
     #[serde(skip)]
     pub domains: Vec<LocatedDomainConfig>,
     #[serde(skip)]
@@ -82,8 +156,63 @@ pub struct ProjectConfig {
     pub location: Option<PathBuf>,
 }
 
-pub fn default_source_roots() -> Vec<PathBuf> {
-    vec![PathBuf::from(".")]
+/// The complete state of a ProjectConfig, including fields which are not part of
+/// its public serialized form. Used to pass configs across process boundaries.
+#[derive(Serialize, Deserialize)]
+struct ProjectConfigState {
+    config: ProjectConfig,
+    location: Option<PathBuf>,
+    domains: Vec<(DomainConfig, ConfigLocation)>,
+}
+
+impl From<&ProjectConfig> for ProjectConfigState {
+    fn from(config: &ProjectConfig) -> Self {
+        Self {
+            config: config.clone(),
+            location: config.location.clone(),
+            domains: config
+                .domains
+                .iter()
+                .map(|domain| (domain.config.clone(), domain.location.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl From<ProjectConfigState> for ProjectConfig {
+    fn from(state: ProjectConfigState) -> Self {
+        let mut config = state.config;
+        config.location = state.location;
+        config.domains = state
+            .domains
+            .into_iter()
+            .map(|(domain, location)| domain.with_location(location))
+            .collect();
+        config
+    }
+}
+
+pub const AUTO_DOMAIN_PREFIX: &str = "auto:";
+
+/// The latest config schema version. Increment this when adding a migration
+/// for a key or shape which changes between releases.
+pub const CONFIG_VERSION: u32 = 1;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DomainPatterns {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+fn deserialize_domain_patterns<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match DomainPatterns::deserialize(deserializer)? {
+        DomainPatterns::Single(pattern) => vec![pattern],
+        DomainPatterns::Multiple(patterns) => patterns,
+    })
 }
 
 pub const DEFAULT_EXCLUDE_PATHS: [&str; 5] = [
@@ -101,6 +230,14 @@ pub fn default_excludes() -> Vec<String> {
         .collect()
 }
 
+// Source roots are written either as a path, or as an inline table with a 'path' key
+pub(crate) fn source_root_path(root: &toml_edit::Value) -> Option<&str> {
+    match root {
+        toml_edit::Value::InlineTable(table) => table.get("path").and_then(|path| path.as_str()),
+        root => root.as_str(),
+    }
+}
+
 impl Default for ProjectConfig {
     fn default() -> Self {
         Self {
@@ -109,19 +246,33 @@ impl Default for ProjectConfig {
             source_roots: default_source_roots(),
             ignore_type_checking_imports: true,
             // normal defaults
+            version: Default::default(),
+            include: Default::default(),
             modules: Default::default(),
             interfaces: Default::default(),
             layers: Default::default(),
+            packages: Default::default(),
             cache: Default::default(),
             external: Default::default(),
+            generated_code: Default::default(),
+            exit: Default::default(),
+            god_modules: Default::default(),
+            sync: Default::default(),
+            diagnostics: Default::default(),
             exact: Default::default(),
             disable_logging: Default::default(),
             include_string_imports: Default::default(),
             forbid_circular_dependencies: Default::default(),
             use_regex_matching: Default::default(),
+            strict_exports: Default::default(),
             root_module: Default::default(),
+            python_version: Default::default(),
             rules: Default::default(),
             plugins: Default::default(),
+            use_codeowners: Default::default(),
+            output_format: Default::default(),
+            domain_patterns: Default::default(),
+            domain_template: Default::default(),
             domains: Default::default(),
             pending_edits: Default::default(),
             location: Default::default(),
@@ -142,34 +293,50 @@ impl ProjectConfig {
             .as_ref()
             .map(|path| path.parent().unwrap())
             .ok_or(ConfigError::ConfigDoesNotExist)?;
-        Ok(self
-            .source_roots
-            .iter()
-            .map(|root| {
-                if root.display().to_string() == "." {
-                    project_root.to_path_buf()
-                } else {
-                    project_root.join(root)
-                }
-            })
-            .collect())
+        Ok(self.prepend_roots(project_root))
     }
 
     // TODO: use absolute_source_roots
     pub fn prepend_roots(&self, project_root: &Path) -> Vec<PathBuf> {
-        // don't prepend if root is "."
         self.source_roots
             .iter()
-            .map(|root| {
-                if root.display().to_string() == "." {
-                    project_root.to_path_buf()
-                } else {
-                    project_root.join(root)
-                }
-            })
+            .map(|root| root.absolute_path(project_root))
             .collect()
     }
 
+    /// The settings for the source root at 'source_root', an absolute path within 'project_root'.
+    pub fn source_root_config(
+        &self,
+        project_root: &Path,
+        source_root: &Path,
+    ) -> Option<&SourceRootConfig> {
+        self.source_roots.iter().find(|root| {
+            normalize_path(&root.absolute_path(project_root)) == normalize_path(source_root)
+        })
+    }
+
+    pub fn include_string_imports_in(&self, project_root: &Path, source_root: &Path) -> bool {
+        self.source_root_config(project_root, source_root)
+            .and_then(|root| root.include_string_imports)
+            .unwrap_or(self.include_string_imports)
+    }
+
+    pub fn ignore_type_checking_imports_in(&self, project_root: &Path, source_root: &Path) -> bool {
+        self.source_root_config(project_root, source_root)
+            .and_then(|root| root.ignore_type_checking_imports)
+            .unwrap_or(self.ignore_type_checking_imports)
+    }
+
+    pub fn python_version_in(
+        &self,
+        project_root: &Path,
+        source_root: &Path,
+    ) -> Option<PythonVersion> {
+        self.source_root_config(project_root, source_root)
+            .and_then(|root| root.python_version)
+            .or(self.python_version)
+    }
+
     pub fn with_dependencies_removed(&self) -> Self {
         Self {
             modules: self
@@ -186,6 +353,89 @@ impl ProjectConfig {
         }
     }
 
+    /// Locate the configuration entry for a module, returning the config file path
+    /// and the (1-indexed) line number of the entry when it was recorded during parsing.
+    pub fn module_config_location(&self, module_path: &str) -> Option<(PathBuf, Option<usize>)> {
+        if let Some(module) = self
+            .modules
+            .iter()
+            .find(|module| module.path == module_path)
+        {
+            let location = self.location.as_ref()?;
+            return Some((location.clone(), module.span.line_number()));
+        }
+
+        self.domains.iter().find_map(|domain| {
+            domain
+                .modules()
+                .find(|module| module.path == module_path)
+                .map(|module| (domain.location.path.clone(), module.span.line_number()))
+        })
+    }
+
+    /// A config file path relative to the project root, as used in diagnostics.
+    pub fn relative_config_path(&self, config_path: &Path) -> PathBuf {
+        self.location
+            .as_ref()
+            .and_then(|location| location.parent())
+            .and_then(|project_root| config_path.strip_prefix(project_root).ok())
+            .unwrap_or(config_path)
+            .to_path_buf()
+    }
+
+    /// Compute the contents of each config file after renaming a module,
+    /// without writing anything to disk. Only changed files are returned.
+    /// Files present in 'open_documents' are read from there rather than from disk.
+    pub fn preview_module_rename(
+        &self,
+        path: &str,
+        new_path: &str,
+        open_documents: &HashMap<PathBuf, String>,
+    ) -> Result<Vec<(PathBuf, String)>, EditError> {
+        let mut renames = Vec::new();
+        if let Some(location) = &self.location {
+            renames.push((location.clone(), path.to_string(), new_path.to_string()));
+        }
+        for domain in &self.domains {
+            let (old_reference, new_reference) = domain.rename_references(path, new_path)?;
+            renames.push((domain.location.path.clone(), old_reference, new_reference));
+        }
+
+        let mut changed_files = Vec::new();
+        for (config_path, old_reference, new_reference) in renames {
+            let toml_str = match open_documents.get(&config_path) {
+                Some(contents) => contents.clone(),
+                None => std::fs::read_to_string(&config_path)
+                    .map_err(|_| EditError::ConfigDoesNotExist)?,
+            };
+            let mut doc = toml_str
+                .parse::<toml_edit::DocumentMut>()
+                .map_err(|_| EditError::ParsingFailed)?;
+            rename_module_references(&mut doc, &old_reference, &new_reference);
+            let new_contents = doc.to_string();
+            if new_contents != toml_str {
+                changed_files.push((config_path, new_contents));
+            }
+        }
+        Ok(changed_files)
+    }
+
+    /// The innermost domain containing the given module path, if any.
+    pub fn domain_for_module(&self, module_path: &str) -> Option<&LocatedDomainConfig> {
+        self.domains
+            .iter()
+            .filter(|domain| domain.contains_module(module_path))
+            .max_by_key(|domain| domain.location.mod_path.len())
+    }
+
+    /// The innermost package containing the given module path, if any.
+    pub fn package_for_module(&self, module_path: &str) -> Option<&PackageConfig> {
+        self.packages
+            .iter()
+            .filter(|package| package.contains_module(module_path))
+            .max_by_key(|package| package.path.len())
+    }
+
     pub fn add_domain(&mut self, domain: LocatedDomainConfig) {
         self.domains.push(domain);
     }
@@ -222,7 +472,9 @@ impl ConfigEditor for ProjectConfig {
             | ConfigEdit::MarkModuleAsUtility { .. }
             | ConfigEdit::UnmarkModuleAsUtility { .. }
             | ConfigEdit::AddDependency { .. }
-            | ConfigEdit::RemoveDependency { .. } => {
+            | ConfigEdit::RemoveDependency { .. }
+            | ConfigEdit::SetModuleLayer { .. }
+            | ConfigEdit::CreateInterface { .. } => {
                 if !domain_results.iter().any(|r| r.is_ok()) {
                     // If no domain accepted the edit, enqueue the edit
                     self.pending_edits.push(edit.clone());
@@ -231,11 +483,30 @@ impl ConfigEditor for ProjectConfig {
                     Err(EditError::NotApplicable)
                 }
             }
-            ConfigEdit::AddSourceRoot { .. } | ConfigEdit::RemoveSourceRoot { .. } => {
-                // Source root edits are always applicable to project config
+            ConfigEdit::AddSourceRoot { .. }
+            | ConfigEdit::RemoveSourceRoot { .. }
+            | ConfigEdit::SetViolationBudget { .. }
+            | ConfigEdit::RenameModule { .. } => {
+                // Source root and exit edits are always applicable to project config,
+                // and renamed modules may be referenced from any config file
+                self.pending_edits.push(edit.clone());
+                Ok(())
+            }
+            ConfigEdit::CreateDomain { .. } | ConfigEdit::DeleteDomain { .. } => {
+                // New domain files are created by the project config,
+                // and the modules of a deleted domain are moved into it
                 self.pending_edits.push(edit.clone());
                 Ok(())
             }
+            ConfigEdit::MoveModuleToDomain { module, .. } => {
+                if self.modules.iter().any(|m| m.path == module.path) {
+                    // The module is declared here, and must be removed
+                    self.pending_edits.push(edit.clone());
+                    Ok(())
+                } else {
+                    Err(EditError::NotApplicable)
+                }
+            }
         };
 
         match result {
@@ -252,12 +523,28 @@ impl ConfigEditor for ProjectConfig {
     }
 
     fn apply_edits(&mut self) -> Result<(), EditError> {
+        // Every file is computed before any is written, so a failing edit leaves all of them intact
+        commit_writes(self.pending_writes()?)?;
+
         for domain in &mut self.domains {
-            domain.apply_edits()?;
+            domain.clear_pending_edits();
+        }
+        self.pending_edits.clear();
+        Ok(())
+    }
+}
+
+impl ProjectConfig {
+    /// Computes the change to every config file affected by the pending edits, across this
+    /// config and its domains, without touching the disk.
+    pub fn pending_writes(&self) -> Result<Vec<PendingWrite>, EditError> {
+        let mut writes = Vec::new();
+        for domain in &self.domains {
+            writes.extend(domain.pending_write()?);
         }
 
         if self.pending_edits.is_empty() {
-            return Ok(());
+            return Ok(writes);
         }
         let config_path = self
             .location
@@ -276,51 +563,10 @@ impl ConfigEditor for ProjectConfig {
                     let mut module_table = toml_edit::Table::new();
                     module_table.insert("path", toml_edit::value(path));
                     module_table.insert("depends_on", toml_edit::value(toml_edit::Array::new()));
-
-                    match doc.get_mut("modules") {
-                        // If modules is a regular array (modules = []) or doesn't exist, convert it to array of tables
-                        None | Some(toml_edit::Item::Value(toml_edit::Value::Array(_))) => {
-                            let mut array = toml_edit::ArrayOfTables::new();
-                            array.push(module_table);
-                            doc["modules"] = toml_edit::Item::ArrayOfTables(array);
-                        }
-                        // If modules is already an array of tables ([[modules]]), just push
-                        Some(toml_edit::Item::ArrayOfTables(array)) => {
-                            array.push(module_table);
-                        }
-                        _ => return Err(EditError::ParsingFailed),
-                    }
+                    push_table(&mut doc, "modules", module_table)?;
                 }
                 ConfigEdit::DeleteModule { path } => {
-                    if let toml_edit::Item::ArrayOfTables(modules) = &mut doc["modules"] {
-                        let mut module_index = None;
-                        for (i, table) in modules.iter_mut().enumerate() {
-                            if table
-                                .get("path")
-                                .map(|p| p.as_str() == Some(path))
-                                .unwrap_or(false)
-                            {
-                                module_index = Some(i);
-                                break;
-                            } else if table
-                                .get("paths")
-                                .map(|p| p.as_array().is_some())
-                                .unwrap_or(false)
-                            {
-                                table["paths"]
-                                    .as_array_mut()
-                                    .unwrap()
-                                    .retain(|p| p.as_str().unwrap() != path);
-                                if table["paths"].as_array().unwrap().is_empty() {
-                                    module_index = Some(i);
-                                }
-                                break;
-                            }
-                        }
-                        if let Some(index) = module_index {
-                            modules.remove(index);
-                        }
-                    }
+                    remove_module_table(&mut doc, path);
                 }
                 ConfigEdit::MarkModuleAsUtility { path }
                 | ConfigEdit::UnmarkModuleAsUtility { path } => {
@@ -423,14 +669,19 @@ impl ConfigEditor for ProjectConfig {
                         }
                     }
                 }
+                ConfigEdit::RenameModule { path, new_path } => {
+                    rename_module_references(&mut doc, path, new_path);
+                }
                 ConfigEdit::AddSourceRoot { filepath } => {
                     if let toml_edit::Item::Value(toml_edit::Value::Array(source_roots)) =
                         &mut doc["source_roots"]
                     {
+                        let filepath = to_forward_slashes(filepath);
                         if !source_roots.iter().any(|root| {
-                            root.as_str() == Some(filepath.as_os_str().to_str().unwrap())
+                            source_root_path(root).map(normalize_separators).as_ref()
+                                == Some(&filepath)
                         }) {
-                            source_roots.push(filepath.display().to_string());
+                            source_roots.push(filepath);
                         }
                     }
                 }
@@ -438,20 +689,72 @@ impl ConfigEditor for ProjectConfig {
                     if let toml_edit::Item::Value(toml_edit::Value::Array(source_roots)) =
                         &mut doc["source_roots"]
                     {
+                        let filepath = to_forward_slashes(filepath);
                         source_roots.retain(|root| {
-                            root.as_str()
-                                .map(|s| s != filepath.as_os_str().to_str().unwrap())
+                            source_root_path(root)
+                                .map(|s| normalize_separators(s) != filepath)
                                 .unwrap_or(true)
                         });
                     }
                 }
+                ConfigEdit::CreateDomain { path } => {
+                    let project_root = config_path.parent().ok_or(EditError::ConfigDoesNotExist)?;
+                    let domain_dir = self
+                        .prepend_roots(project_root)
+                        .into_iter()
+                        .map(|root| root.join(path.replace('.', std::path::MAIN_SEPARATOR_STR)))
+                        .find(|dir| dir.is_dir())
+                        .ok_or(EditError::ModuleNotFound)?;
+                    let domain_config_path = domain_dir.join(DOMAIN_CONFIG_FILE);
+                    if !domain_config_path.exists() {
+                        let contents = toml::to_string(&DomainConfig::default())
+                            .map_err(|_| EditError::ParsingFailed)?;
+                        writes.push(PendingWrite::Write {
+                            path: domain_config_path,
+                            contents,
+                        });
+                    }
+                }
+                ConfigEdit::DeleteDomain { path } => {
+                    // Keep the deleted domain's modules and interfaces, using absolute paths
+                    if let Some(domain) = self
+                        .domains
+                        .iter()
+                        .find(|domain| &domain.location.mod_path == path)
+                    {
+                        for module in domain.modules() {
+                            push_table(&mut doc, "modules", to_table(module)?)?;
+                        }
+                        for interface in domain.interfaces() {
+                            push_table(&mut doc, "interfaces", to_table(interface)?)?;
+                        }
+                    }
+                }
+                ConfigEdit::MoveModuleToDomain { module, .. } => {
+                    remove_module_table(&mut doc, &module.path);
+                }
+                ConfigEdit::SetModuleLayer { path, layer } => {
+                    set_module_layer(&mut doc, path, layer)?;
+                }
+                ConfigEdit::CreateInterface { path, expose } => {
+                    let interface = InterfaceConfig {
+                        expose: expose.clone(),
+                        from_modules: vec![path.clone()],
+                        ..Default::default()
+                    };
+                    push_table(&mut doc, "interfaces", to_table(&interface)?)?;
+                }
+                ConfigEdit::SetViolationBudget { code, budget } => {
+                    set_violation_budget(&mut doc, code, *budget)?;
+                }
             }
         }
 
-        std::fs::write(config_path, doc.to_string()).map_err(|_| EditError::DiskWriteFailed)?;
-
-        self.pending_edits.clear();
-        Ok(())
+        writes.push(PendingWrite::Write {
+            path: config_path.clone(),
+            contents: doc.to_string(),
+        });
+        Ok(writes)
     }
 }
 
@@ -466,10 +769,46 @@ impl ProjectConfig {
         format!("{:#?}", self)
     }
 
-    fn serialize_json(&self) -> String {
+    #[getter(source_roots)]
+    fn source_root_paths(&self) -> Vec<PathBuf> {
+        self.source_roots
+            .iter()
+            .map(|root| root.path.clone())
+            .collect()
+    }
+
+    #[getter]
+    fn source_root_configs(&self) -> Vec<SourceRootConfig> {
+        self.source_roots.clone()
+    }
+
+    /// The exclude patterns of every source root, relative to the project root.
+    pub fn source_root_exclude_paths(&self) -> Vec<String> {
+        self.source_roots
+            .iter()
+            .flat_map(|root| root.project_exclude_paths(self.use_regex_matching))
+            .collect()
+    }
+
+    pub fn serialize_json(&self) -> String {
         serde_json::to_string(&self).unwrap()
     }
 
+    #[staticmethod]
+    pub fn deserialize_json(json: &str) -> Result<Self, ConfigError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn __getstate__(&self) -> String {
+        serde_json::to_string(&ProjectConfigState::from(self)).unwrap()
+    }
+
+    pub fn __setstate__(&mut self, state: &str) -> Result<(), ConfigError> {
+        let state: ProjectConfigState = serde_json::from_str(state)?;
+        *self = state.into();
+        Ok(())
+    }
+
     #[pyo3(name = "all_modules")]
     fn all_modules_py(&self) -> Vec<ModuleConfig> {
         self.all_modules().cloned().collect()
@@ -515,6 +854,32 @@ impl ProjectConfig {
             .collect()
     }
 
+    pub fn domain_paths(&self) -> Vec<String> {
+        self.domains
+            .iter()
+            .map(|domain| domain.location.mod_path.clone())
+            .collect()
+    }
+
+    pub fn package_paths(&self) -> Vec<String> {
+        self.packages
+            .iter()
+            .map(|package| package.path.clone())
+            .collect()
+    }
+
+    /// The layers which the module at 'module_path' is ordered against, highest first.
+    /// Modules within a domain which declares its own layers use those layers.
+    pub fn layers_for_module(&self, module_path: &str) -> Vec<String> {
+        match self
+            .domain_for_module(module_path)
+            .filter(|domain| domain.has_layers())
+        {
+            Some(domain) => domain.config.layers.clone(),
+            None => self.layers.clone(),
+        }
+    }
+
     fn utility_paths(&self) -> Vec<String> {
         self.all_modules()
             .filter(|module| module.utility)
@@ -567,6 +932,28 @@ impl ProjectConfig {
         self.enqueue_edit(&ConfigEdit::RemoveDependency { path, dependency })
     }
 
+    pub fn set_module_layer(&mut self, path: String, layer: String) -> Result<(), EditError> {
+        self.enqueue_edit(&ConfigEdit::SetModuleLayer { path, layer })
+    }
+
+    pub fn create_interface(&mut self, path: String, expose: Vec<String>) -> Result<(), EditError> {
+        self.enqueue_edit(&ConfigEdit::CreateInterface { path, expose })
+    }
+
+    pub fn set_violation_budget(&mut self, code: String, budget: usize) -> Result<(), EditError> {
+        self.enqueue_edit(&ConfigEdit::SetViolationBudget { code, budget })
+    }
+
+    pub fn rename_module(&mut self, path: String, new_path: String) -> Result<(), EditError> {
+        if !self.all_modules().any(|module| module.path == path) {
+            return Err(EditError::ModuleNotFound);
+        }
+        if self.all_modules().any(|module| module.path == new_path) {
+            return Err(EditError::ModuleAlreadyExists);
+        }
+        self.enqueue_edit(&ConfigEdit::RenameModule { path, new_path })
+    }
+
     pub fn add_source_root(&mut self, filepath: PathBuf) -> Result<(), EditError> {
         self.enqueue_edit(&ConfigEdit::AddSourceRoot { filepath })
     }
@@ -575,7 +962,98 @@ impl ProjectConfig {
         self.enqueue_edit(&ConfigEdit::RemoveSourceRoot { filepath })
     }
 
+    pub fn create_domain(&mut self, path: String) -> Result<(), EditError> {
+        if self
+            .domains
+            .iter()
+            .any(|domain| domain.location.mod_path == path && domain.location.path.exists())
+        {
+            return Err(EditError::DomainAlreadyExists);
+        }
+        self.enqueue_edit(&ConfigEdit::CreateDomain { path })
+    }
+
+    pub fn delete_domain(&mut self, path: String) -> Result<(), EditError> {
+        let domain = self
+            .domains
+            .iter()
+            .find(|domain| domain.location.mod_path == path)
+            .ok_or(EditError::DomainNotFound)?;
+        if !domain.location.path.exists() {
+            // Domains discovered from a pattern have no config file to delete
+            return Err(EditError::ConfigDoesNotExist);
+        }
+        self.enqueue_edit(&ConfigEdit::DeleteDomain { path })
+    }
+
+    pub fn move_module_to_domain(&mut self, path: String, domain: String) -> Result<(), EditError> {
+        let module = self
+            .all_modules()
+            .find(|module| module.path == path)
+            .cloned()
+            .ok_or(EditError::ModuleNotFound)?;
+        let target = self
+            .domains
+            .iter()
+            .find(|located| located.location.mod_path == domain)
+            .ok_or(EditError::DomainNotFound)?;
+        if !target.contains_module(&path) {
+            return Err(EditError::ModuleOutsideDomain);
+        }
+        if target.modules().any(|module| module.path == path) {
+            return Err(EditError::ModuleAlreadyExists);
+        }
+        self.enqueue_edit(&ConfigEdit::MoveModuleToDomain { module, domain })
+    }
+
     pub fn save_edits(&mut self) -> Result<(), EditError> {
         self.apply_edits()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::parsing::config::parse_project_config;
+
+    #[test]
+    fn test_failed_rename_leaves_every_config_unchanged() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        let files = [
+            (
+                "tach.toml",
+                "[[modules]]\npath = \"top\"\ndepends_on = [\"b.inner\"]\n",
+            ),
+            (
+                "a/tach.domain.toml",
+                "[root]\ndepends_on = [\"//b.inner\"]\n",
+            ),
+            // Renaming 'b.inner' out of its domain cannot be expressed in this config
+            (
+                "b/tach.domain.toml",
+                "[[modules]]\npath = \"inner\"\ndepends_on = []\n",
+            ),
+        ];
+        for (path, contents) in files {
+            std::fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            std::fs::write(root.join(path), contents).unwrap();
+        }
+        for path in ["top.py", "a/__init__.py", "b/__init__.py", "b/inner.py"] {
+            std::fs::write(root.join(path), "").unwrap();
+        }
+
+        let (mut config, _) = parse_project_config(root.join("tach.toml")).unwrap();
+        assert_eq!(config.domains.len(), 2);
+        config
+            .rename_module("b.inner".to_string(), "renamed".to_string())
+            .unwrap();
+
+        assert!(config.save_edits().is_err());
+        for (path, contents) in files {
+            assert_eq!(std::fs::read_to_string(root.join(path)).unwrap(), contents);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A target Python version (e.g. '3.9'), written as a string in config files.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct PythonVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl PythonVersion {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl TryFrom<String> for PythonVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || {
+            format!(
+                "Invalid Python version '{}'. Expected a version like '3.12'.",
+                value
+            )
+        };
+        let (major, minor) = value.trim().split_once('.').ok_or_else(invalid)?;
+        Ok(Self {
+            major: major.parse().map_err(|_| invalid())?,
+            minor: minor.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl From<PythonVersion> for String {
+    fn from(version: PythonVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl Display for PythonVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl IntoPy<PyObject> for PythonVersion {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_string().to_object(py)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Config {
+        python_version: PythonVersion,
+    }
+
+    #[test]
+    fn test_parse_python_version() {
+        let config: Config = toml::from_str(r#"python_version = "3.12""#).unwrap();
+        assert_eq!(config.python_version, PythonVersion::new(3, 12));
+        assert!(PythonVersion::new(3, 9) < PythonVersion::new(3, 10));
+        assert_eq!(config.python_version.to_string(), "3.12");
+    }
+
+    #[test]
+    fn test_parse_invalid_python_version() {
+        assert!(toml::from_str::<Config>(r#"python_version = "3""#).is_err());
+        assert!(toml::from_str::<Config>(r#"python_version = "3.x""#).is_err());
+    }
+}
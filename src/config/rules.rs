@@ -64,6 +64,31 @@ pub struct RulesConfig {
         skip_serializing_if = "RuleSetting::is_error"
     )]
     pub unused_external_dependencies: RuleSetting,
+    #[serde(
+        default = "RuleSetting::off",
+        skip_serializing_if = "RuleSetting::is_off"
+    )]
+    pub domain_public_api: RuleSetting,
+    #[serde(
+        default = "RuleSetting::off",
+        skip_serializing_if = "RuleSetting::is_off"
+    )]
+    pub domain_isolation: RuleSetting,
+    #[serde(
+        default = "RuleSetting::warn",
+        skip_serializing_if = "RuleSetting::is_warn"
+    )]
+    pub parse_errors: RuleSetting,
+    #[serde(
+        default = "RuleSetting::error",
+        skip_serializing_if = "RuleSetting::is_error"
+    )]
+    pub utility_interfaces: RuleSetting,
+    #[serde(
+        default = "RuleSetting::off",
+        skip_serializing_if = "RuleSetting::is_off"
+    )]
+    pub file_cycles: RuleSetting,
 }
 
 impl Default for RulesConfig {
@@ -72,6 +97,11 @@ impl Default for RulesConfig {
             unused_ignore_directives: RuleSetting::warn(),
             require_ignore_directive_reasons: RuleSetting::off(),
             unused_external_dependencies: RuleSetting::error(),
+            domain_public_api: RuleSetting::off(),
+            domain_isolation: RuleSetting::off(),
+            parse_errors: RuleSetting::warn(),
+            utility_interfaces: RuleSetting::error(),
+            file_cycles: RuleSetting::off(),
         }
     }
 }
@@ -80,4 +110,19 @@ impl RulesConfig {
     pub fn is_default(&self) -> bool {
         *self == Self::default()
     }
+
+    /// The setting which controls diagnostics with the given code, if they are configurable.
+    pub fn setting_for_code(&self, code: &str) -> Option<&RuleSetting> {
+        match code {
+            "unused-ignore-directive" => Some(&self.unused_ignore_directives),
+            "missing-ignore-directive-reason" => Some(&self.require_ignore_directive_reasons),
+            "unused-external-dependency" => Some(&self.unused_external_dependencies),
+            "domain-private-dependency" => Some(&self.domain_public_api),
+            "domain-isolation-violation" => Some(&self.domain_isolation),
+            "parse-error" => Some(&self.parse_errors),
+            "utility-misuse" => Some(&self.utility_interfaces),
+            "file-import-cycle" => Some(&self.file_cycles),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,135 @@
+use pyo3::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::{Path, PathBuf};
+
+use super::python_version::PythonVersion;
+use crate::filesystem::{normalize_separators, to_forward_slashes};
+use crate::pattern::{GLOB_PATTERN_PREFIX, REGEX_PATTERN_PREFIX};
+
+/// A source root, along with settings which override the project settings for files within it.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct SourceRootConfig {
+    pub path: PathBuf,
+    // Exclude patterns relative to the source root, applied in addition to the project excludes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_string_imports: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_type_checking_imports: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python_version: Option<PythonVersion>,
+}
+
+impl SourceRootConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether this source root only sets its path, and so can be written as a plain string.
+    pub fn is_path_only(&self) -> bool {
+        *self == Self::new(self.path.clone())
+    }
+
+    pub fn absolute_path(&self, project_root: &Path) -> PathBuf {
+        // don't prepend if root is "."
+        if self.path == Path::new(".") {
+            project_root.to_path_buf()
+        } else {
+            project_root.join(&self.path)
+        }
+    }
+
+    /// The exclude patterns of this source root, relative to the project root.
+    pub fn project_exclude_paths(&self, use_regex_matching: bool) -> Vec<String> {
+        let root = to_forward_slashes(&self.path);
+        if root == "." {
+            return self.exclude.clone();
+        }
+        let prefix_regex = |pattern: &str| {
+            format!(
+                "{}{}/{}",
+                REGEX_PATTERN_PREFIX,
+                regex::escape(&root),
+                pattern.trim_start_matches('^')
+            )
+        };
+        let prefix_glob = |pattern: &str| {
+            format!(
+                "{}{}/{}",
+                GLOB_PATTERN_PREFIX,
+                glob::Pattern::escape(&root),
+                pattern
+            )
+        };
+        self.exclude
+            .iter()
+            .map(|pattern| {
+                if let Some(regex) = pattern.strip_prefix(REGEX_PATTERN_PREFIX) {
+                    prefix_regex(regex)
+                } else if let Some(glob) = pattern.strip_prefix(GLOB_PATTERN_PREFIX) {
+                    prefix_glob(glob)
+                } else if use_regex_matching {
+                    prefix_regex(pattern)
+                } else {
+                    prefix_glob(pattern)
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SourceRootPathOrConfig {
+    Path(PathBuf),
+    Config(SourceRootConfig),
+}
+
+pub fn default_source_roots() -> Vec<SourceRootConfig> {
+    vec![SourceRootConfig::new(".")]
+}
+
+pub fn serialize_source_roots<S>(
+    source_roots: &[SourceRootConfig],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    source_roots
+        .iter()
+        .map(|root| {
+            if root.is_path_only() {
+                SourceRootPathOrConfig::Path(root.path.clone())
+            } else {
+                SourceRootPathOrConfig::Config(root.clone())
+            }
+        })
+        .collect::<Vec<_>>()
+        .serialize(serializer)
+}
+
+pub fn deserialize_source_roots<'de, D>(deserializer: D) -> Result<Vec<SourceRootConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let source_roots: Vec<SourceRootPathOrConfig> = Vec::deserialize(deserializer)?;
+    Ok(source_roots
+        .into_iter()
+        .map(|root| match root {
+            SourceRootPathOrConfig::Path(path) => SourceRootConfig::new(path),
+            SourceRootPathOrConfig::Config(config) => config,
+        })
+        // Source roots written with backslashes behave the same on every platform
+        .map(|root| SourceRootConfig {
+            path: PathBuf::from(normalize_separators(&root.path.to_string_lossy())),
+            ..root
+        })
+        .collect())
+}
@@ -0,0 +1,62 @@
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+
+/// Where an entry (such as a module or an interface) is written in a config file.
+/// This is recorded when the config is parsed, so entries created in memory have no span.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSpan {
+    location: Option<(PathBuf, usize)>,
+}
+
+impl ConfigSpan {
+    /// The span of the entry at the given byte range within the contents of a config file.
+    pub fn new(file_path: &Path, content: &str, byte_range: Range<usize>) -> Self {
+        let line_number = content
+            .get(..byte_range.start)
+            .map(|preceding| preceding.matches('\n').count() + 1);
+        Self {
+            location: line_number.map(|line_number| (file_path.to_path_buf(), line_number)),
+        }
+    }
+
+    pub fn file_path(&self) -> Option<&Path> {
+        self.location
+            .as_ref()
+            .map(|(file_path, _)| file_path.as_path())
+    }
+
+    /// The (1-indexed) line number of the entry.
+    pub fn line_number(&self) -> Option<usize> {
+        self.location.as_ref().map(|(_, line_number)| *line_number)
+    }
+}
+
+// Spans describe where an entry was read from rather than what it configures,
+// so an entry is equal to the same entry written elsewhere (or not yet written at all)
+impl PartialEq for ConfigSpan {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl IntoPy<PyObject> for ConfigSpan {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.location.into_py(py)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_span_line_number() {
+        let content = "[[modules]]\npath = \"core\"\n";
+        let span = ConfigSpan::new(Path::new("tach.toml"), content, 19..25);
+        assert_eq!(span.file_path(), Some(Path::new("tach.toml")));
+        assert_eq!(span.line_number(), Some(2));
+        assert_eq!(ConfigSpan::default().line_number(), None);
+    }
+}
@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How 'tach sync' registers packages which do not belong to any module.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NewPackagePolicy {
+    // Register each package as a new module
+    TopLevel,
+    // Also register packages within existing modules, as modules nested under them
+    Nested,
+    // Leave packages unregistered, so they belong to the root module, and report them
+    #[default]
+    Warn,
+}
+
+impl NewPackagePolicy {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl FromStr for NewPackagePolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "top-level" => Ok(Self::TopLevel),
+            "nested" => Ok(Self::Nested),
+            "warn" => Ok(Self::Warn),
+            _ => Err(format!(
+                "Unknown policy '{}' for new packages. Expected 'top-level', 'nested' or 'warn'.",
+                value
+            )),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for NewPackagePolicy {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            Self::TopLevel => "top-level".to_object(py),
+            Self::Nested => "nested".to_object(py),
+            Self::Warn => "warn".to_object(py),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct SyncConfig {
+    #[serde(default, skip_serializing_if = "NewPackagePolicy::is_default")]
+    pub new_packages: NewPackagePolicy,
+}
+
+impl SyncConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sync_config() {
+        let config: SyncConfig = toml::from_str(r#"new_packages = "top-level""#).unwrap();
+        assert_eq!(config.new_packages, NewPackagePolicy::TopLevel);
+        assert!(toml::from_str::<SyncConfig>("").unwrap().is_default());
+        assert!("nested-ish".parse::<NewPackagePolicy>().is_err());
+    }
+}
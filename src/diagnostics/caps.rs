@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+
+use crate::config::DiagnosticsConfig;
+use crate::filesystem::to_forward_slashes;
+
+use super::diagnostics::Diagnostic;
+
+/// The diagnostics left out of a report by a cap, summarized as a single line.
+/// Either 'file_path' is set, or both modules are.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct OmittedDiagnostics {
+    pub file_path: Option<String>,
+    pub usage_module: Option<String>,
+    pub definition_module: Option<String>,
+    pub count: usize,
+}
+
+#[pymethods]
+impl OmittedDiagnostics {
+    pub fn message(&self) -> String {
+        match (&self.file_path, &self.usage_module, &self.definition_module) {
+            (Some(file_path), _, _) => format!("... and {} more in '{}'", self.count, file_path),
+            (None, Some(usage_module), Some(definition_module)) => format!(
+                "... and {} more from '{}' to '{}'",
+                self.count, usage_module, definition_module
+            ),
+            _ => format!("... and {} more", self.count),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct CappedDiagnostics {
+    pub diagnostics: Vec<Diagnostic>,
+    // Diagnostics over the per-file cap, followed by those over the per-module-pair cap
+    pub omitted: Vec<OmittedDiagnostics>,
+}
+
+fn is_over_cap<K: Ord>(shown: &BTreeMap<K, usize>, key: Option<&K>, cap: Option<usize>) -> bool {
+    match (key, cap) {
+        (Some(key), Some(cap)) => shown.get(key).copied().unwrap_or_default() >= cap,
+        _ => false,
+    }
+}
+
+/// Keep at most the configured number of diagnostics for each file and each pair of modules,
+/// in the order given. Global diagnostics are always kept.
+/// A diagnostic over both caps is counted against its file.
+pub fn cap_diagnostics(
+    diagnostics: Vec<Diagnostic>,
+    config: &DiagnosticsConfig,
+) -> CappedDiagnostics {
+    let mut shown_per_file: BTreeMap<String, usize> = BTreeMap::new();
+    let mut shown_per_module_pair: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut omitted_per_file: BTreeMap<String, usize> = BTreeMap::new();
+    let mut omitted_per_module_pair: BTreeMap<(String, String), usize> = BTreeMap::new();
+
+    let mut capped = CappedDiagnostics::default();
+    for diagnostic in diagnostics {
+        let file_path = diagnostic.file_path().map(|path| to_forward_slashes(path));
+        let module_pair = diagnostic
+            .usage_module()
+            .zip(diagnostic.definition_module())
+            .map(|(usage_module, definition_module)| {
+                (usage_module.to_string(), definition_module.to_string())
+            });
+
+        if is_over_cap(&shown_per_file, file_path.as_ref(), config.max_per_file) {
+            *omitted_per_file.entry(file_path.unwrap()).or_default() += 1;
+            continue;
+        }
+        if is_over_cap(
+            &shown_per_module_pair,
+            module_pair.as_ref(),
+            config.max_per_module_pair,
+        ) {
+            *omitted_per_module_pair
+                .entry(module_pair.unwrap())
+                .or_default() += 1;
+            continue;
+        }
+
+        if let Some(file_path) = file_path {
+            *shown_per_file.entry(file_path).or_default() += 1;
+        }
+        if let Some(module_pair) = module_pair {
+            *shown_per_module_pair.entry(module_pair).or_default() += 1;
+        }
+        capped.diagnostics.push(diagnostic);
+    }
+
+    capped.omitted = omitted_per_file
+        .into_iter()
+        .map(|(file_path, count)| OmittedDiagnostics {
+            file_path: Some(file_path),
+            count,
+            ..Default::default()
+        })
+        .chain(omitted_per_module_pair.into_iter().map(
+            |((usage_module, definition_module), count)| OmittedDiagnostics {
+                usage_module: Some(usage_module),
+                definition_module: Some(definition_module),
+                count,
+                ..Default::default()
+            },
+        ))
+        .collect();
+    capped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::diagnostics::{CodeDiagnostic, DiagnosticDetails};
+
+    fn undeclared_dependency(file_path: &str, usage_module: &str) -> Diagnostic {
+        Diagnostic::new_located_error(
+            PathBuf::from(file_path),
+            1,
+            DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                dependency: "core.api".to_string(),
+                usage_module: usage_module.to_string(),
+                definition_module: "core".to_string(),
+                suggestion: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn test_cap_diagnostics() {
+        let diagnostics = vec![
+            undeclared_dependency("a/x.py", "a"),
+            undeclared_dependency("a/x.py", "a"),
+            undeclared_dependency("a/x.py", "a"),
+            undeclared_dependency("a/y.py", "a"),
+            undeclared_dependency("b/x.py", "b"),
+        ];
+        let config = DiagnosticsConfig {
+            max_per_file: Some(2),
+            max_per_module_pair: Some(2),
+        };
+
+        let capped = cap_diagnostics(diagnostics, &config);
+
+        assert_eq!(capped.diagnostics.len(), 3);
+        assert_eq!(
+            capped.omitted,
+            vec![
+                OmittedDiagnostics {
+                    file_path: Some("a/x.py".to_string()),
+                    count: 1,
+                    ..Default::default()
+                },
+                OmittedDiagnostics {
+                    usage_module: Some("a".to_string()),
+                    definition_module: Some("core".to_string()),
+                    count: 1,
+                    ..Default::default()
+                },
+            ]
+        );
+        assert_eq!(capped.omitted[0].message(), "... and 1 more in 'a/x.py'");
+        assert_eq!(
+            capped.omitted[1].message(),
+            "... and 1 more from 'a' to 'core'"
+        );
+    }
+
+    #[test]
+    fn test_no_caps_keeps_everything() {
+        let diagnostics = vec![undeclared_dependency("a/x.py", "a"); 3];
+        let capped = cap_diagnostics(diagnostics, &DiagnosticsConfig::default());
+        assert_eq!(capped.diagnostics.len(), 3);
+        assert!(capped.omitted.is_empty());
+    }
+}
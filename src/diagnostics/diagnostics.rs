@@ -1,10 +1,13 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{collections::HashMap, fmt::Display, path::PathBuf};
 
 use pyo3::prelude::*;
 use serde::Serialize;
 use thiserror::Error;
 
 use crate::config::RuleSetting;
+use crate::filesystem::to_forward_slashes;
+
+use super::suggestion::{format_successor, format_suggestion};
 
 #[derive(Debug, Clone, Eq, PartialOrd, Ord, Serialize, PartialEq)]
 #[pyclass(eq, eq_int, module = "tach.extension")]
@@ -63,6 +66,30 @@ pub enum ConfigurationDiagnostic {
 
     #[error("Skipped '{file_path}' due to an unknown error.")]
     SkippedUnknownError { file_path: String },
+
+    #[error("Domain '{domain}' references '{module_path}', which is outside of the domain and not in 'depends_on_domains'.")]
+    DomainIsolationViolation { domain: String, module_path: String },
+}
+
+impl ConfigurationDiagnostic {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ConfigurationDiagnostic::ModuleNotFound { .. } => "module-not-found",
+            ConfigurationDiagnostic::ModuleConfigNotFound { .. } => "module-config-not-found",
+            ConfigurationDiagnostic::UnknownLayer { .. } => "unknown-layer",
+            ConfigurationDiagnostic::NoFirstPartyImportsFound() => "no-first-party-imports-found",
+            ConfigurationDiagnostic::NoChecksEnabled() => "no-checks-enabled",
+            ConfigurationDiagnostic::SkippedFileSyntaxError { .. } => "skipped-file-syntax-error",
+            ConfigurationDiagnostic::SkippedFileIoError { .. } => "skipped-file-io-error",
+            ConfigurationDiagnostic::SkippedPyProjectParsingError { .. } => {
+                "skipped-pyproject-parsing-error"
+            }
+            ConfigurationDiagnostic::SkippedUnknownError { .. } => "skipped-unknown-error",
+            ConfigurationDiagnostic::DomainIsolationViolation { .. } => {
+                "domain-isolation-violation"
+            }
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, Serialize, PartialEq)]
@@ -77,6 +104,13 @@ pub enum CodeDiagnostic {
         usage_module: String,
     },
 
+    #[error("The path '{dependency}' is not part of the public interface for utility module '{definition_module}'.")]
+    UtilityMisuse {
+        dependency: String,
+        definition_module: String,
+        usage_module: String,
+    },
+
     #[error("The dependency '{dependency}' (from module '{definition_module}') matches an interface but does not match the expected data type ('{expected_data_type}').")]
     InvalidDataTypeExport {
         dependency: String,
@@ -85,11 +119,13 @@ pub enum CodeDiagnostic {
         expected_data_type: String,
     },
 
-    #[error("Cannot use '{dependency}'. Module '{usage_module}' cannot depend on '{definition_module}'.")]
+    #[error("Cannot use '{dependency}'. Module '{usage_module}' cannot depend on '{definition_module}'.{}", format_suggestion(.suggestion))]
     UndeclaredDependency {
         dependency: String,
         usage_module: String,
         definition_module: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        suggestion: Option<String>,
     },
 
     #[error("Dependency '{dependency}' is deprecated. Module '{usage_module}' should not depend on '{definition_module}'.")]
@@ -99,6 +135,24 @@ pub enum CodeDiagnostic {
         definition_module: String,
     },
 
+    #[error("Module '{definition_module}' is deprecated, but '{usage_module}' still uses '{dependency}'.{}", format_successor(.successor))]
+    DeprecatedModuleUsage {
+        dependency: String,
+        usage_module: String,
+        definition_module: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        successor: Option<String>,
+    },
+
+    #[error("Cannot use '{dependency}'. Module '{definition_module}' is deprecated, so '{usage_module}' may not start depending on it.{}", format_successor(.successor))]
+    NewDeprecatedModuleDependency {
+        dependency: String,
+        usage_module: String,
+        definition_module: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        successor: Option<String>,
+    },
+
     #[error("Cannot use '{dependency}'. Layer '{usage_layer}' ('{usage_module}') is lower than layer '{definition_layer}' ('{definition_module}').")]
     LayerViolation {
         dependency: String,
@@ -108,6 +162,32 @@ pub enum CodeDiagnostic {
         definition_layer: String,
     },
 
+    #[error("Cannot use '{dependency}'. Module '{definition_module}' is not part of the public API of domain '{definition_domain}'.")]
+    DomainPrivateDependency {
+        dependency: String,
+        usage_module: String,
+        definition_module: String,
+        definition_domain: String,
+    },
+
+    #[error("Cannot use '{dependency}'. Domain '{usage_domain}' ('{usage_module}') cannot depend on domain '{definition_domain}' ('{definition_module}').")]
+    DomainViolation {
+        dependency: String,
+        usage_module: String,
+        usage_domain: String,
+        definition_module: String,
+        definition_domain: String,
+    },
+
+    #[error("Cannot use '{dependency}'. Package '{usage_package}' ('{usage_module}') cannot depend on package '{definition_package}' ('{definition_module}').")]
+    PackageViolation {
+        dependency: String,
+        usage_module: String,
+        usage_package: String,
+        definition_module: String,
+        definition_package: String,
+    },
+
     #[error("Dependency '{dependency}' is unnecessarily ignored by a directive.")]
     UnnecessarilyIgnoredDependency { dependency: String },
 
@@ -118,38 +198,137 @@ pub enum CodeDiagnostic {
     MissingIgnoreDirectiveReason(),
 
     #[error("Dependency '{dependency}' is not declared in the project.")]
-    UndeclaredExternalDependency { dependency: String },
+    UndeclaredExternalDependency {
+        dependency: String,
+        /// The module which owns the importing file, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        usage_module: Option<String>,
+    },
+
+    #[error("Module '{usage_module}' cannot depend on external package '{dependency}'. Add it to the module's 'depends_on_external'.")]
+    UndeclaredModuleExternalDependency {
+        dependency: String,
+        usage_module: String,
+    },
 
     #[error("External package '{package_module_name}' is not used.")]
     UnusedExternalDependency { package_module_name: String },
+
+    #[error("Layer '{layer}' may use at most {budget} external package(s), but uses {}: {}", .usages.len(), .usages.join(", "))]
+    LayerExternalBudgetExceeded {
+        layer: String,
+        budget: usize,
+        /// Each package used by the layer, followed by the modules which import it.
+        usages: Vec<String>,
+    },
+
+    #[error("Import cycle between files: {} -> {}. These imports run as soon as each file is imported, so importing any of these files can raise an ImportError.", .files.join(" -> "), .files[0])]
+    FileImportCycle {
+        /// Each file imports the next, and the last file imports the first.
+        files: Vec<String>,
+    },
+
+    #[error("Syntax error: {message}. Only imports before this line were checked.")]
+    ParseError { message: String },
+
+    #[error("{feature} require Python {minimum_version}, but the target 'python_version' is {python_version}.")]
+    UnsupportedSyntax {
+        feature: String,
+        minimum_version: String,
+        python_version: String,
+    },
 }
 
 impl CodeDiagnostic {
+    pub fn code(&self) -> &'static str {
+        match self {
+            CodeDiagnostic::PrivateDependency { .. } => "private-dependency",
+            CodeDiagnostic::UtilityMisuse { .. } => "utility-misuse",
+            CodeDiagnostic::InvalidDataTypeExport { .. } => "invalid-data-type-export",
+            CodeDiagnostic::UndeclaredDependency { .. } => "undeclared-dependency",
+            CodeDiagnostic::DeprecatedDependency { .. } => "deprecated-dependency",
+            CodeDiagnostic::DeprecatedModuleUsage { .. } => "deprecated-module-usage",
+            CodeDiagnostic::NewDeprecatedModuleDependency { .. } => {
+                "new-deprecated-module-dependency"
+            }
+            CodeDiagnostic::LayerViolation { .. } => "layer-violation",
+            CodeDiagnostic::DomainViolation { .. } => "domain-violation",
+            CodeDiagnostic::PackageViolation { .. } => "package-violation",
+            CodeDiagnostic::DomainPrivateDependency { .. } => "domain-private-dependency",
+            CodeDiagnostic::UnnecessarilyIgnoredDependency { .. } => {
+                "unnecessarily-ignored-dependency"
+            }
+            CodeDiagnostic::UnusedIgnoreDirective() => "unused-ignore-directive",
+            CodeDiagnostic::MissingIgnoreDirectiveReason() => "missing-ignore-directive-reason",
+            CodeDiagnostic::UndeclaredExternalDependency { .. } => "undeclared-external-dependency",
+            CodeDiagnostic::UndeclaredModuleExternalDependency { .. } => {
+                "undeclared-module-external-dependency"
+            }
+            CodeDiagnostic::UnusedExternalDependency { .. } => "unused-external-dependency",
+            CodeDiagnostic::LayerExternalBudgetExceeded { .. } => "layer-external-budget-exceeded",
+            CodeDiagnostic::FileImportCycle { .. } => "file-import-cycle",
+            CodeDiagnostic::ParseError { .. } => "parse-error",
+            CodeDiagnostic::UnsupportedSyntax { .. } => "unsupported-syntax",
+        }
+    }
+
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            CodeDiagnostic::UndeclaredDependency { suggestion, .. } => suggestion.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn dependency(&self) -> Option<&str> {
         match self {
             CodeDiagnostic::PrivateDependency { dependency, .. }
+            | CodeDiagnostic::UtilityMisuse { dependency, .. }
             | CodeDiagnostic::InvalidDataTypeExport { dependency, .. }
             | CodeDiagnostic::UndeclaredDependency { dependency, .. }
             | CodeDiagnostic::DeprecatedDependency { dependency, .. }
+            | CodeDiagnostic::DeprecatedModuleUsage { dependency, .. }
+            | CodeDiagnostic::NewDeprecatedModuleDependency { dependency, .. }
             | CodeDiagnostic::LayerViolation { dependency, .. }
+            | CodeDiagnostic::DomainViolation { dependency, .. }
+            | CodeDiagnostic::PackageViolation { dependency, .. }
+            | CodeDiagnostic::DomainPrivateDependency { dependency, .. }
             | CodeDiagnostic::UnnecessarilyIgnoredDependency { dependency, .. } => Some(dependency),
             CodeDiagnostic::UnusedIgnoreDirective() => None,
             CodeDiagnostic::MissingIgnoreDirectiveReason() => None,
-            CodeDiagnostic::UndeclaredExternalDependency { dependency, .. } => Some(dependency),
+            CodeDiagnostic::UndeclaredExternalDependency { dependency, .. }
+            | CodeDiagnostic::UndeclaredModuleExternalDependency { dependency, .. } => {
+                Some(dependency)
+            }
             CodeDiagnostic::UnusedExternalDependency {
                 package_module_name,
                 ..
             } => Some(package_module_name),
+            CodeDiagnostic::LayerExternalBudgetExceeded { .. } => None,
+            CodeDiagnostic::FileImportCycle { .. } => None,
+            CodeDiagnostic::ParseError { .. } => None,
+            CodeDiagnostic::UnsupportedSyntax { .. } => None,
         }
     }
 
     pub fn usage_module(&self) -> Option<&str> {
         match self {
             CodeDiagnostic::PrivateDependency { usage_module, .. }
+            | CodeDiagnostic::UtilityMisuse { usage_module, .. }
             | CodeDiagnostic::InvalidDataTypeExport { usage_module, .. }
             | CodeDiagnostic::UndeclaredDependency { usage_module, .. }
             | CodeDiagnostic::DeprecatedDependency { usage_module, .. }
-            | CodeDiagnostic::LayerViolation { usage_module, .. } => Some(usage_module),
+            | CodeDiagnostic::DeprecatedModuleUsage { usage_module, .. }
+            | CodeDiagnostic::NewDeprecatedModuleDependency { usage_module, .. }
+            | CodeDiagnostic::LayerViolation { usage_module, .. }
+            | CodeDiagnostic::DomainViolation { usage_module, .. }
+            | CodeDiagnostic::PackageViolation { usage_module, .. }
+            | CodeDiagnostic::DomainPrivateDependency { usage_module, .. }
+            | CodeDiagnostic::UndeclaredModuleExternalDependency { usage_module, .. } => {
+                Some(usage_module)
+            }
+            CodeDiagnostic::UndeclaredExternalDependency { usage_module, .. } => {
+                usage_module.as_deref()
+            }
             _ => None,
         }
     }
@@ -159,6 +338,9 @@ impl CodeDiagnostic {
             CodeDiagnostic::PrivateDependency {
                 definition_module, ..
             }
+            | CodeDiagnostic::UtilityMisuse {
+                definition_module, ..
+            }
             | CodeDiagnostic::InvalidDataTypeExport {
                 definition_module, ..
             }
@@ -168,8 +350,23 @@ impl CodeDiagnostic {
             | CodeDiagnostic::DeprecatedDependency {
                 definition_module, ..
             }
+            | CodeDiagnostic::DeprecatedModuleUsage {
+                definition_module, ..
+            }
+            | CodeDiagnostic::NewDeprecatedModuleDependency {
+                definition_module, ..
+            }
             | CodeDiagnostic::LayerViolation {
                 definition_module, ..
+            }
+            | CodeDiagnostic::DomainViolation {
+                definition_module, ..
+            }
+            | CodeDiagnostic::PackageViolation {
+                definition_module, ..
+            }
+            | CodeDiagnostic::DomainPrivateDependency {
+                definition_module, ..
             } => Some(definition_module),
             _ => None,
         }
@@ -183,6 +380,15 @@ pub enum DiagnosticDetails {
     Configuration(ConfigurationDiagnostic),
 }
 
+impl DiagnosticDetails {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DiagnosticDetails::Code(code) => code.code(),
+            DiagnosticDetails::Configuration(config) => config.code(),
+        }
+    }
+}
+
 impl Display for DiagnosticDetails {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -192,6 +398,34 @@ impl Display for DiagnosticDetails {
     }
 }
 
+/// The precise source location of a located diagnostic.
+/// Offsets are byte offsets into the file, and the column is 1-indexed.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct SourceSpan {
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct RelatedLocation {
+    pub file_path: PathBuf,
+    pub line_number: Option<usize>,
+    pub message: String,
+}
+
+impl RelatedLocation {
+    pub fn new(file_path: PathBuf, line_number: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            file_path,
+            line_number,
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[pyclass(module = "tach.extension")]
 pub enum Diagnostic {
@@ -204,6 +438,10 @@ pub enum Diagnostic {
         line_number: usize,
         severity: Severity,
         details: DiagnosticDetails,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        span: Option<SourceSpan>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        related: Vec<RelatedLocation>,
     },
 }
 
@@ -223,6 +461,8 @@ impl Diagnostic {
             details,
             file_path,
             line_number,
+            span: None,
+            related: vec![],
         }
     }
 
@@ -245,6 +485,8 @@ impl Diagnostic {
             line_number,
             severity: Severity::Error,
             details,
+            span: None,
+            related: vec![],
         }
     }
 
@@ -258,6 +500,8 @@ impl Diagnostic {
             line_number,
             severity: Severity::Warning,
             details,
+            span: None,
+            related: vec![],
         }
     }
 
@@ -275,6 +519,38 @@ impl Diagnostic {
         }
     }
 
+    pub fn with_span(mut self, source_span: SourceSpan) -> Self {
+        if let Self::Located { span, .. } = &mut self {
+            *span = Some(source_span);
+        }
+        self
+    }
+
+    pub fn span(&self) -> Option<&SourceSpan> {
+        match self {
+            Self::Global { .. } => None,
+            Self::Located { span, .. } => span.as_ref(),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.details().code()
+    }
+
+    pub fn with_related_location(mut self, location: RelatedLocation) -> Self {
+        if let Self::Located { related, .. } = &mut self {
+            related.push(location);
+        }
+        self
+    }
+
+    pub fn related_locations(&self) -> &[RelatedLocation] {
+        match self {
+            Self::Global { .. } => &[],
+            Self::Located { related, .. } => related,
+        }
+    }
+
     pub fn details(&self) -> &DiagnosticDetails {
         match self {
             Self::Global { details, .. } => details,
@@ -327,6 +603,160 @@ impl Diagnostic {
             _ => None,
         }
     }
+
+    pub fn suggestion(&self) -> Option<&str> {
+        match self.details() {
+            DiagnosticDetails::Code(details) => details.suggestion(),
+            _ => None,
+        }
+    }
+
+    /// The identifying fields of this violation, hashed together with its occurrence
+    /// index to form its fingerprint (see 'diagnostic_fingerprints').
+    ///
+    /// The fields cover the code, the modules and dependency involved, and the
+    /// file path (with forward slashes), but not the line number or span.
+    /// Messages are only included for configuration diagnostics, which have no other
+    /// identifying fields, so that rewording a code diagnostic keeps its fingerprint.
+    /// Layer budget diagnostics are identified by their layer instead,
+    /// and file import cycles by their files.
+    fn fingerprint_fields(&self) -> [String; 6] {
+        let file_path = self
+            .file_path()
+            .map(|path| to_forward_slashes(path))
+            .unwrap_or_default();
+        let message = match self.details() {
+            DiagnosticDetails::Code(CodeDiagnostic::LayerExternalBudgetExceeded {
+                layer, ..
+            }) => layer.clone(),
+            DiagnosticDetails::Code(CodeDiagnostic::FileImportCycle { files }) => {
+                files.join(" -> ")
+            }
+            DiagnosticDetails::Code(_) => String::new(),
+            DiagnosticDetails::Configuration(details) => details.to_string(),
+        };
+        [
+            self.code().to_string(),
+            self.usage_module().unwrap_or_default().to_string(),
+            self.definition_module().unwrap_or_default().to_string(),
+            self.dependency().unwrap_or_default().to_string(),
+            file_path,
+            message,
+        ]
+    }
+}
+
+/// A stable identifier for each violation, which survives line-number churn.
+///
+/// Violations with the same identifying fields (such as the same import repeated in a file)
+/// are told apart by their order within the file, so a violation keeps its fingerprint
+/// when unrelated edits shift its line, but each occurrence has its own fingerprint.
+/// Fingerprints are returned in the order of the given diagnostics.
+pub fn diagnostic_fingerprints(diagnostics: &[Diagnostic]) -> Vec<String> {
+    let mut order: Vec<usize> = (0..diagnostics.len()).collect();
+    order.sort_by_cached_key(|&index| diagnostics[index].sort_key());
+
+    let mut occurrences: HashMap<[String; 6], usize> = HashMap::new();
+    let mut fingerprints = vec![String::new(); diagnostics.len()];
+    for index in order {
+        let fields = diagnostics[index].fingerprint_fields();
+        let occurrence = occurrences.entry(fields.clone()).or_default();
+        let occurrence_index = occurrence.to_string();
+        let mut hashed: Vec<&str> = fields.iter().map(String::as_str).collect();
+        // The first occurrence is hashed without an index, matching earlier fingerprints
+        if *occurrence > 0 {
+            hashed.push(&occurrence_index);
+        }
+        *occurrence += 1;
+        fingerprints[index] = format!("{:016x}", fnv1a_hash(&hashed));
+    }
+    fingerprints
+}
+
+/// The canonical ordering of diagnostics: file path, line, offset, code, then message.
+type DiagnosticSortKey = (Option<String>, usize, usize, &'static str, String);
+
+impl Diagnostic {
+    // Paths are compared with forward slashes so that the order is the same on every platform.
+    // Global diagnostics have no path, so they come first.
+    pub(crate) fn sort_key(&self) -> DiagnosticSortKey {
+        (
+            self.file_path().map(|path| to_forward_slashes(path)),
+            self.line_number().unwrap_or_default(),
+            self.span()
+                .map(|span| span.start_offset)
+                .unwrap_or_default(),
+            self.code(),
+            self.message(),
+        )
+    }
+}
+
+/// Sort diagnostics into a stable order, independent of the order in which files were checked.
+pub fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_cached_key(Diagnostic::sort_key);
+}
+
+/// Remove duplicate diagnostics, such as those reported once for each re-export of a symbol.
+/// Diagnostics for the same usage of the same module are duplicates even when they name
+/// different symbols, as in 'from core import a, b'. The first is kept, gaining the
+/// related locations of its duplicates.
+/// The diagnostics must already be sorted.
+pub fn dedup_diagnostics(diagnostics: &mut Vec<Diagnostic>) {
+    let mut unique: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    for diagnostic in diagnostics.drain(..) {
+        // Once sorted, duplicate diagnostics are among the neighbours on the same line
+        let original = unique
+            .iter_mut()
+            .rev()
+            .take_while(|previous| {
+                previous.file_path() == diagnostic.file_path()
+                    && previous.line_number() == diagnostic.line_number()
+            })
+            .find(|previous| **previous == diagnostic || is_same_usage(previous, &diagnostic));
+        match original {
+            Some(Diagnostic::Located { related, .. }) => {
+                for location in diagnostic.related_locations() {
+                    if !related.contains(location) {
+                        related.push(location.clone());
+                    }
+                }
+            }
+            Some(Diagnostic::Global { .. }) => {}
+            None => unique.push(diagnostic),
+        }
+    }
+    *diagnostics = unique;
+}
+
+// Whether two diagnostics on the same line report the same usage of the same module.
+fn is_same_usage(previous: &Diagnostic, diagnostic: &Diagnostic) -> bool {
+    previous.definition_module().is_some()
+        && previous.code() == diagnostic.code()
+        && previous.severity() == diagnostic.severity()
+        && previous.usage_module() == diagnostic.usage_module()
+        && previous.definition_module() == diagnostic.definition_module()
+}
+
+// FNV-1a is used instead of the standard library hasher, whose output may change
+// between Rust releases. Each field is terminated so that field boundaries matter.
+fn fnv1a_hash(fields: &[&str]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    fields
+        .iter()
+        .flat_map(|field| field.bytes().chain(std::iter::once(0)))
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+/// The serialized form of a diagnostic, alongside its fingerprint.
+#[derive(Serialize)]
+struct FingerprintedDiagnostic<'a> {
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+    fingerprint: String,
 }
 
 #[pymethods]
@@ -344,7 +774,11 @@ impl Diagnostic {
             self.details(),
             DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency { .. })
                 | DiagnosticDetails::Code(CodeDiagnostic::DeprecatedDependency { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::DeprecatedModuleUsage { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::NewDeprecatedModuleDependency { .. })
                 | DiagnosticDetails::Code(CodeDiagnostic::LayerViolation { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::DomainViolation { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::PackageViolation { .. })
         )
     }
 
@@ -352,7 +786,9 @@ impl Diagnostic {
         matches!(
             self.details(),
             DiagnosticDetails::Code(CodeDiagnostic::PrivateDependency { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::UtilityMisuse { .. })
                 | DiagnosticDetails::Code(CodeDiagnostic::InvalidDataTypeExport { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::DomainPrivateDependency { .. })
         )
     }
 
@@ -360,6 +796,7 @@ impl Diagnostic {
         matches!(
             self.details(),
             DiagnosticDetails::Code(CodeDiagnostic::DeprecatedDependency { .. })
+                | DiagnosticDetails::Code(CodeDiagnostic::DeprecatedModuleUsage { .. })
         )
     }
 
@@ -376,6 +813,41 @@ impl Diagnostic {
         self.message()
     }
 
+    #[pyo3(name = "message")]
+    pub fn pymessage(&self) -> String {
+        self.message()
+    }
+
+    #[pyo3(name = "code")]
+    pub fn pycode(&self) -> &'static str {
+        self.code()
+    }
+
+    #[pyo3(name = "severity")]
+    pub fn pyseverity(&self) -> Severity {
+        self.severity()
+    }
+
+    #[pyo3(name = "usage_module")]
+    pub fn pyusage_module(&self) -> Option<String> {
+        self.usage_module().map(|s| s.to_string())
+    }
+
+    #[pyo3(name = "definition_module")]
+    pub fn pydefinition_module(&self) -> Option<String> {
+        self.definition_module().map(|s| s.to_string())
+    }
+
+    #[pyo3(name = "dependency")]
+    pub fn pydependency(&self) -> Option<String> {
+        self.dependency().map(|s| s.to_string())
+    }
+
+    #[pyo3(name = "span")]
+    pub fn pyspan(&self) -> Option<SourceSpan> {
+        self.span().copied()
+    }
+
     pub fn pyfile_path(&self) -> Option<String> {
         self.file_path()
             .map(|path| path.to_string_lossy().to_string())
@@ -384,13 +856,439 @@ impl Diagnostic {
     pub fn pyline_number(&self) -> Option<usize> {
         self.line_number()
     }
+
+    #[pyo3(name = "related_locations")]
+    pub fn pyrelated_locations(&self) -> Vec<RelatedLocation> {
+        self.related_locations().to_vec()
+    }
+
+    #[pyo3(name = "suggestion")]
+    pub fn pysuggestion(&self) -> Option<String> {
+        self.suggestion().map(|s| s.to_string())
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "diagnostic_fingerprints")]
+pub fn py_diagnostic_fingerprints(diagnostics: Vec<Diagnostic>) -> Vec<String> {
+    diagnostic_fingerprints(&diagnostics)
 }
 
 #[pyfunction(signature = (diagnostics, pretty_print = false))]
 pub fn serialize_diagnostics_json(diagnostics: Vec<Diagnostic>, pretty_print: bool) -> String {
+    let diagnostics: Vec<FingerprintedDiagnostic> = diagnostics
+        .iter()
+        .zip(diagnostic_fingerprints(&diagnostics))
+        .map(|(diagnostic, fingerprint)| FingerprintedDiagnostic {
+            diagnostic,
+            fingerprint,
+        })
+        .collect();
     if pretty_print {
         serde_json::to_string_pretty(&diagnostics).unwrap()
     } else {
         serde_json::to_string(&diagnostics).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undeclared_dependency(file_path: &str, line_number: usize) -> Diagnostic {
+        Diagnostic::new_located_error(
+            PathBuf::from(file_path),
+            line_number,
+            DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                dependency: "b.api".to_string(),
+                usage_module: "a".to_string(),
+                definition_module: "b".to_string(),
+                suggestion: None,
+            }),
+        )
+    }
+
+    fn fingerprint(diagnostic: Diagnostic) -> String {
+        diagnostic_fingerprints(&[diagnostic]).remove(0)
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_numbers() {
+        assert_eq!(
+            fingerprint(undeclared_dependency("a/x.py", 3)),
+            fingerprint(undeclared_dependency("a/x.py", 30))
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_location() {
+        assert_ne!(
+            fingerprint(undeclared_dependency("a/x.py", 3)),
+            fingerprint(undeclared_dependency("a/y.py", 3))
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_repeated_violations() {
+        let fingerprints = diagnostic_fingerprints(&[
+            undeclared_dependency("a/x.py", 3),
+            undeclared_dependency("a/x.py", 8),
+        ]);
+        assert_ne!(fingerprints[0], fingerprints[1]);
+    }
+
+    #[test]
+    fn test_fingerprint_survives_shifted_lines() {
+        let before = diagnostic_fingerprints(&[
+            undeclared_dependency("a/x.py", 3),
+            undeclared_dependency("a/x.py", 8),
+        ]);
+        // Lines added above both violations, with the diagnostics in a different order
+        let after = diagnostic_fingerprints(&[
+            undeclared_dependency("a/x.py", 12),
+            undeclared_dependency("a/x.py", 7),
+        ]);
+        assert_eq!(before, vec![after[1].clone(), after[0].clone()]);
+        // The first occurrence keeps the fingerprint of a lone violation
+        assert_eq!(before[0], fingerprint(undeclared_dependency("a/x.py", 30)));
+    }
+
+    #[test]
+    fn test_fingerprint_normalizes_separators() {
+        assert_eq!(
+            fingerprint(undeclared_dependency("a\\x.py", 3)),
+            fingerprint(undeclared_dependency("a/x.py", 3))
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // Fingerprints are tracked by external systems, so they must not change
+        assert_eq!(
+            fingerprint(undeclared_dependency("a/x.py", 3)),
+            "aba15381f6dbaff8"
+        );
+    }
+
+    #[test]
+    fn test_serialized_diagnostics_include_fingerprint() {
+        let diagnostic = undeclared_dependency("a/x.py", 3);
+        let json: serde_json::Value =
+            serde_json::from_str(&serialize_diagnostics_json(vec![diagnostic.clone()], false))
+                .unwrap();
+        assert_eq!(json[0]["fingerprint"], fingerprint(diagnostic));
+        assert_eq!(json[0]["Located"]["line_number"], 3);
+    }
+
+    #[test]
+    fn test_sort_diagnostics() {
+        let no_imports = Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+            ConfigurationDiagnostic::NoFirstPartyImportsFound(),
+        ));
+        let deprecated = Diagnostic::new_located_warning(
+            PathBuf::from("a/x.py"),
+            3,
+            DiagnosticDetails::Code(CodeDiagnostic::DeprecatedDependency {
+                dependency: "b.api".to_string(),
+                usage_module: "a".to_string(),
+                definition_module: "b".to_string(),
+            }),
+        );
+        let mut diagnostics = vec![
+            undeclared_dependency("b/x.py", 1),
+            undeclared_dependency("a/x.py", 3),
+            deprecated.clone(),
+            undeclared_dependency("a/x.py", 2),
+            no_imports.clone(),
+        ];
+
+        sort_diagnostics(&mut diagnostics);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                no_imports,
+                undeclared_dependency("a/x.py", 2),
+                deprecated,
+                undeclared_dependency("a/x.py", 3),
+                undeclared_dependency("b/x.py", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedup_diagnostics() {
+        let mut diagnostics = vec![
+            undeclared_dependency("a/x.py", 2),
+            undeclared_dependency("a/x.py", 3),
+            undeclared_dependency("a/x.py", 2),
+            undeclared_dependency("a/x.py", 2).with_related_location(RelatedLocation::new(
+                PathBuf::from("b/api.py"),
+                Some(1),
+                "re-exported here",
+            )),
+        ];
+
+        sort_diagnostics(&mut diagnostics);
+        dedup_diagnostics(&mut diagnostics);
+
+        // The re-exported duplicate is folded into the first diagnostic on its line
+        assert_eq!(
+            diagnostics,
+            vec![
+                undeclared_dependency("a/x.py", 2).with_related_location(RelatedLocation::new(
+                    PathBuf::from("b/api.py"),
+                    Some(1),
+                    "re-exported here",
+                )),
+                undeclared_dependency("a/x.py", 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedup_diagnostics_for_same_usage() {
+        let imported = |dependency: &str, offset: usize| {
+            Diagnostic::new_located_error(
+                PathBuf::from("a/x.py"),
+                1,
+                DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                    dependency: dependency.to_string(),
+                    usage_module: "a".to_string(),
+                    definition_module: "b".to_string(),
+                    suggestion: None,
+                }),
+            )
+            .with_span(SourceSpan {
+                start_offset: offset,
+                end_offset: offset + 1,
+                column: offset,
+            })
+        };
+        // 'from b import first, second'
+        let mut diagnostics = vec![imported("b.second", 21), imported("b.first", 14)];
+
+        sort_diagnostics(&mut diagnostics);
+        dedup_diagnostics(&mut diagnostics);
+
+        assert_eq!(diagnostics, vec![imported("b.first", 14)]);
+    }
+
+    #[test]
+    fn test_code_diagnostic_codes() {
+        let s = String::new;
+        let cases = [
+            (
+                CodeDiagnostic::PrivateDependency {
+                    dependency: s(),
+                    definition_module: s(),
+                    usage_module: s(),
+                },
+                "private-dependency",
+            ),
+            (
+                CodeDiagnostic::UtilityMisuse {
+                    dependency: s(),
+                    definition_module: s(),
+                    usage_module: s(),
+                },
+                "utility-misuse",
+            ),
+            (
+                CodeDiagnostic::InvalidDataTypeExport {
+                    dependency: s(),
+                    definition_module: s(),
+                    usage_module: s(),
+                    expected_data_type: s(),
+                },
+                "invalid-data-type-export",
+            ),
+            (
+                CodeDiagnostic::UndeclaredDependency {
+                    dependency: s(),
+                    usage_module: s(),
+                    definition_module: s(),
+                    suggestion: None,
+                },
+                "undeclared-dependency",
+            ),
+            (
+                CodeDiagnostic::DeprecatedDependency {
+                    dependency: s(),
+                    usage_module: s(),
+                    definition_module: s(),
+                },
+                "deprecated-dependency",
+            ),
+            (
+                CodeDiagnostic::DeprecatedModuleUsage {
+                    dependency: s(),
+                    usage_module: s(),
+                    definition_module: s(),
+                    successor: None,
+                },
+                "deprecated-module-usage",
+            ),
+            (
+                CodeDiagnostic::NewDeprecatedModuleDependency {
+                    dependency: s(),
+                    usage_module: s(),
+                    definition_module: s(),
+                    successor: None,
+                },
+                "new-deprecated-module-dependency",
+            ),
+            (
+                CodeDiagnostic::LayerViolation {
+                    dependency: s(),
+                    usage_module: s(),
+                    usage_layer: s(),
+                    definition_module: s(),
+                    definition_layer: s(),
+                },
+                "layer-violation",
+            ),
+            (
+                CodeDiagnostic::DomainPrivateDependency {
+                    dependency: s(),
+                    usage_module: s(),
+                    definition_module: s(),
+                    definition_domain: s(),
+                },
+                "domain-private-dependency",
+            ),
+            (
+                CodeDiagnostic::DomainViolation {
+                    dependency: s(),
+                    usage_module: s(),
+                    usage_domain: s(),
+                    definition_module: s(),
+                    definition_domain: s(),
+                },
+                "domain-violation",
+            ),
+            (
+                CodeDiagnostic::PackageViolation {
+                    dependency: s(),
+                    usage_module: s(),
+                    usage_package: s(),
+                    definition_module: s(),
+                    definition_package: s(),
+                },
+                "package-violation",
+            ),
+            (
+                CodeDiagnostic::UnnecessarilyIgnoredDependency { dependency: s() },
+                "unnecessarily-ignored-dependency",
+            ),
+            (
+                CodeDiagnostic::UnusedIgnoreDirective(),
+                "unused-ignore-directive",
+            ),
+            (
+                CodeDiagnostic::MissingIgnoreDirectiveReason(),
+                "missing-ignore-directive-reason",
+            ),
+            (
+                CodeDiagnostic::UndeclaredExternalDependency {
+                    dependency: s(),
+                    usage_module: None,
+                },
+                "undeclared-external-dependency",
+            ),
+            (
+                CodeDiagnostic::UndeclaredModuleExternalDependency {
+                    dependency: s(),
+                    usage_module: s(),
+                },
+                "undeclared-module-external-dependency",
+            ),
+            (
+                CodeDiagnostic::UnusedExternalDependency {
+                    package_module_name: s(),
+                },
+                "unused-external-dependency",
+            ),
+            (
+                CodeDiagnostic::LayerExternalBudgetExceeded {
+                    layer: s(),
+                    budget: 0,
+                    usages: vec![],
+                },
+                "layer-external-budget-exceeded",
+            ),
+            (
+                CodeDiagnostic::FileImportCycle { files: vec![s()] },
+                "file-import-cycle",
+            ),
+            (CodeDiagnostic::ParseError { message: s() }, "parse-error"),
+            (
+                CodeDiagnostic::UnsupportedSyntax {
+                    feature: s(),
+                    minimum_version: s(),
+                    python_version: s(),
+                },
+                "unsupported-syntax",
+            ),
+        ];
+        for (diagnostic, code) in cases {
+            let diagnostic = Diagnostic::new_global_error(DiagnosticDetails::Code(diagnostic));
+            assert_eq!(diagnostic.code(), code);
+        }
+    }
+
+    #[test]
+    fn test_configuration_diagnostic_codes() {
+        let s = String::new;
+        let cases = [
+            (
+                ConfigurationDiagnostic::ModuleNotFound { file_mod_path: s() },
+                "module-not-found",
+            ),
+            (
+                ConfigurationDiagnostic::ModuleConfigNotFound { module_path: s() },
+                "module-config-not-found",
+            ),
+            (
+                ConfigurationDiagnostic::UnknownLayer { layer: s() },
+                "unknown-layer",
+            ),
+            (
+                ConfigurationDiagnostic::NoFirstPartyImportsFound(),
+                "no-first-party-imports-found",
+            ),
+            (
+                ConfigurationDiagnostic::NoChecksEnabled(),
+                "no-checks-enabled",
+            ),
+            (
+                ConfigurationDiagnostic::SkippedFileSyntaxError { file_path: s() },
+                "skipped-file-syntax-error",
+            ),
+            (
+                ConfigurationDiagnostic::SkippedFileIoError { file_path: s() },
+                "skipped-file-io-error",
+            ),
+            (
+                ConfigurationDiagnostic::SkippedPyProjectParsingError { file_path: s() },
+                "skipped-pyproject-parsing-error",
+            ),
+            (
+                ConfigurationDiagnostic::SkippedUnknownError { file_path: s() },
+                "skipped-unknown-error",
+            ),
+            (
+                ConfigurationDiagnostic::DomainIsolationViolation {
+                    domain: s(),
+                    module_path: s(),
+                },
+                "domain-isolation-violation",
+            ),
+        ];
+        for (diagnostic, code) in cases {
+            let diagnostic =
+                Diagnostic::new_global_error(DiagnosticDetails::Configuration(diagnostic));
+            assert_eq!(diagnostic.code(), code);
+        }
+    }
+}
@@ -1,7 +1,11 @@
+pub mod caps;
 pub mod diagnostics;
 pub mod error;
 pub mod pipeline;
+pub mod sarif;
+pub mod suggestion;
 
+pub use caps::{cap_diagnostics, CappedDiagnostics, OmittedDiagnostics};
 pub use diagnostics::*;
 pub use error::DiagnosticError;
 pub use pipeline::{DiagnosticPipeline, FileChecker, FileProcessor, Result};
@@ -0,0 +1,343 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use crate::config::{ProjectConfig, RuleSetting};
+use crate::filesystem::to_forward_slashes;
+
+use super::diagnostics::{diagnostic_fingerprints, Diagnostic, RelatedLocation, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "tach";
+const TOOL_INFORMATION_URI: &str = "https://docs.gauge.sh";
+// Paths are relative to the project root, which consumers resolve against the checkout
+const SOURCE_ROOT_BASE_ID: &str = "%SRCROOT%";
+// SARIF consumers such as GitHub Code Scanning expect every result to have a location,
+// so diagnostics which do not point into a file are reported against the project configuration
+const PROJECT_CONFIG_FILE: &str = "tach.toml";
+// Versioned, so that a change to the fingerprint scheme does not match stale results
+const FINGERPRINT_KEY: &str = "tachFingerprint/v2";
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    tool: Tool<'a>,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    version: &'a str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: &'static str,
+    default_configuration: RuleConfiguration,
+}
+
+#[derive(Serialize)]
+struct RuleConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    rule_index: usize,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    partial_fingerprints: BTreeMap<&'static str, String>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    uri_base_id: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+}
+
+fn severity_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+fn setting_level(setting: &RuleSetting) -> &'static str {
+    match setting {
+        RuleSetting::Error => "error",
+        RuleSetting::Warn => "warning",
+        RuleSetting::Off => "none",
+    }
+}
+
+fn physical_location(
+    file_path: &Path,
+    line_number: Option<usize>,
+    column: Option<usize>,
+) -> PhysicalLocation {
+    PhysicalLocation {
+        artifact_location: ArtifactLocation {
+            uri: to_forward_slashes(file_path),
+            uri_base_id: SOURCE_ROOT_BASE_ID,
+        },
+        // SARIF lines start at 1
+        region: line_number
+            .filter(|line_number| *line_number > 0)
+            .map(|start_line| Region {
+                start_line,
+                start_column: column,
+            }),
+    }
+}
+
+fn diagnostic_location(diagnostic: &Diagnostic) -> Location {
+    let physical_location = match diagnostic.file_path() {
+        Some(file_path) => physical_location(
+            file_path,
+            diagnostic.line_number(),
+            diagnostic.span().map(|span| span.column),
+        ),
+        None => physical_location(Path::new(PROJECT_CONFIG_FILE), None, None),
+    };
+    Location {
+        physical_location,
+        message: None,
+    }
+}
+
+fn related_location(related: &RelatedLocation) -> Location {
+    Location {
+        physical_location: physical_location(&related.file_path, related.line_number, None),
+        message: Some(Message {
+            text: related.message.clone(),
+        }),
+    }
+}
+
+/// Serialize diagnostics as a SARIF 2.1.0 log with a single run.
+/// Each diagnostic code becomes a rule, whose default level follows the project's 'rules'
+/// where the code is configurable, and otherwise the most severe of its diagnostics.
+pub fn diagnostics_to_sarif(
+    diagnostics: &[Diagnostic],
+    project_config: &ProjectConfig,
+    tach_version: &str,
+) -> String {
+    let mut rule_levels: BTreeMap<&'static str, &'static str> = BTreeMap::new();
+    for diagnostic in diagnostics {
+        let code = diagnostic.code();
+        let level = match project_config.rules.setting_for_code(code) {
+            Some(setting) => setting_level(setting),
+            None if diagnostic.is_error() => "error",
+            None => rule_levels.get(code).copied().unwrap_or("warning"),
+        };
+        rule_levels.insert(code, level);
+    }
+    let rule_indices: BTreeMap<&str, usize> = rule_levels
+        .keys()
+        .enumerate()
+        .map(|(index, code)| (*code, index))
+        .collect();
+
+    let results = diagnostics
+        .iter()
+        .zip(diagnostic_fingerprints(diagnostics))
+        .map(|(diagnostic, fingerprint)| SarifResult {
+            rule_id: diagnostic.code(),
+            rule_index: rule_indices[diagnostic.code()],
+            level: severity_level(&diagnostic.severity()),
+            message: Message {
+                text: diagnostic.message(),
+            },
+            locations: vec![diagnostic_location(diagnostic)],
+            related_locations: diagnostic
+                .related_locations()
+                .iter()
+                .map(related_location)
+                .collect(),
+            partial_fingerprints: BTreeMap::from([(FINGERPRINT_KEY, fingerprint)]),
+        })
+        .collect();
+
+    let log = SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: TOOL_NAME,
+                    version: tach_version,
+                    information_uri: TOOL_INFORMATION_URI,
+                    rules: rule_levels
+                        .into_iter()
+                        .map(|(id, level)| Rule {
+                            id,
+                            default_configuration: RuleConfiguration { level },
+                        })
+                        .collect(),
+                },
+            },
+            results,
+        }],
+    };
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+#[pyfunction]
+#[pyo3(signature = (diagnostics, project_config, tach_version))]
+pub fn serialize_diagnostics_sarif(
+    diagnostics: Vec<Diagnostic>,
+    project_config: &ProjectConfig,
+    tach_version: &str,
+) -> String {
+    diagnostics_to_sarif(&diagnostics, project_config, tach_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::config::RulesConfig;
+    use crate::diagnostics::{
+        CodeDiagnostic, ConfigurationDiagnostic, DiagnosticDetails, SourceSpan,
+    };
+
+    #[test]
+    fn test_diagnostics_to_sarif() {
+        let undeclared = Diagnostic::new_located_error(
+            PathBuf::from("api/views.py"),
+            3,
+            DiagnosticDetails::Code(CodeDiagnostic::UndeclaredDependency {
+                dependency: "core.models".to_string(),
+                usage_module: "api".to_string(),
+                definition_module: "core".to_string(),
+                suggestion: None,
+            }),
+        )
+        .with_span(SourceSpan {
+            start_offset: 20,
+            end_offset: 31,
+            column: 6,
+        });
+        let parse_error = Diagnostic::new_located_warning(
+            PathBuf::from("api/broken.py"),
+            1,
+            DiagnosticDetails::Code(CodeDiagnostic::ParseError {
+                message: "unexpected indent".to_string(),
+            }),
+        );
+        let no_imports = Diagnostic::new_global_warning(DiagnosticDetails::Configuration(
+            ConfigurationDiagnostic::NoFirstPartyImportsFound(),
+        ));
+        let project_config = ProjectConfig {
+            rules: RulesConfig {
+                parse_errors: RuleSetting::Error,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let sarif: serde_json::Value = serde_json::from_str(&diagnostics_to_sarif(
+            &[undeclared.clone(), parse_error, no_imports],
+            &project_config,
+            "1.2.3",
+        ))
+        .unwrap();
+
+        let run = &sarif["runs"][0];
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["version"], "1.2.3");
+        let rules: Vec<(&str, &str)> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| {
+                (
+                    rule["id"].as_str().unwrap(),
+                    rule["defaultConfiguration"]["level"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        // The parse error rule follows the project's rules, not the severity of the diagnostic
+        assert_eq!(
+            rules,
+            vec![
+                ("no-first-party-imports-found", "warning"),
+                ("parse-error", "error"),
+                ("undeclared-dependency", "error"),
+            ]
+        );
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "undeclared-dependency");
+        assert_eq!(result["ruleIndex"], 2);
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "api/views.py");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["startColumn"], 6);
+        assert_eq!(
+            result["partialFingerprints"][FINGERPRINT_KEY],
+            diagnostic_fingerprints(&[undeclared])[0]
+        );
+
+        let global = &run["results"][2]["locations"][0]["physicalLocation"];
+        assert_eq!(global["artifactLocation"]["uri"], PROJECT_CONFIG_FILE);
+        assert!(global.get("region").is_none());
+    }
+}
@@ -0,0 +1,78 @@
+/// Maximum edit distance for two module paths to be considered a likely typo.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut current = vec![0; b_chars.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = (previous[j] + substitution_cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b_chars.len()]
+}
+
+/// Find the candidate closest to `target`, if any is within a small edit distance.
+/// Exact matches are not considered suggestions.
+pub fn closest_match<'a>(
+    target: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != target)
+        .map(|candidate| (levenshtein(target, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+pub fn format_suggestion(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(" {}", suggestion),
+        None => String::new(),
+    }
+}
+
+pub fn format_successor(successor: &Option<String>) -> String {
+    match successor {
+        Some(successor) => format!(" Use '{}' instead.", successor),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("core", "core", 0)]
+    #[case("core.utils", "core.util", 1)]
+    #[case("domain_one", "domain_two", 3)]
+    #[case("", "abc", 3)]
+    fn test_levenshtein(#[case] a: &str, #[case] b: &str, #[case] expected: usize) {
+        assert_eq!(levenshtein(a, b), expected);
+    }
+
+    #[rstest]
+    #[case("core.utils", vec!["core.util", "api"], Some("core.util"))]
+    #[case("core.utils", vec!["core.utils"], None)]
+    #[case("domain_one", vec!["domain_two", "api"], None)]
+    #[case("api.v1", vec!["api.v2", "api.v12"], Some("api.v2"))]
+    fn test_closest_match(
+        #[case] target: &str,
+        #[case] candidates: Vec<&str>,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(closest_match(target, candidates), expected);
+    }
+}
@@ -1,9 +1,13 @@
-use itertools::Itertools;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use thiserror::Error;
 
+use crate::filesystem::{normalize_path, to_forward_slashes};
 use crate::pattern::PatternMatcher;
 
 #[derive(Error, Debug)]
@@ -22,6 +26,18 @@ pub enum PathExclusionError {
         #[source]
         source: regex::Error,
     },
+    #[error("Failed to build glob pattern for included path:\n{include}\n{source}")]
+    IncludeGlobPatternError {
+        include: String,
+        #[source]
+        source: glob::PatternError,
+    },
+    #[error("Failed to build regex pattern for included path:\n{include}\n{source}")]
+    IncludeRegexPatternError {
+        include: String,
+        #[source]
+        source: regex::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, PathExclusionError>;
@@ -29,44 +45,141 @@ pub type Result<T> = std::result::Result<T, PathExclusionError>;
 pub struct PathExclusions {
     project_root: PathBuf,
     patterns: Vec<PatternMatcher>,
+    // When non-empty, only files matching one of these globs are walked
+    include_patterns: Vec<PatternMatcher>,
+}
+
+struct RegisteredExclusions {
+    // Identifies the guard which unregisters these exclusions
+    id: u64,
+    exclusions: PathExclusions,
 }
 
-static PATH_EXCLUSIONS_SINGLETON: Lazy<RwLock<Option<PathExclusions>>> =
-    Lazy::new(|| RwLock::new(None));
+static NEXT_REGISTRATION_ID: AtomicU64 = AtomicU64::new(0);
+
+// Exclusions are tracked per project root, so that projects can be analyzed concurrently
+// without overwriting each other's excluded paths. Each root holds its registrations in the
+// order they were made, and the most recent one applies.
+static PATH_EXCLUSIONS_BY_ROOT: Lazy<RwLock<HashMap<PathBuf, Vec<RegisteredExclusions>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Keeps one registration of excluded paths for a project root until it is dropped,
+/// so that long-running processes do not accumulate the exclusions of every run.
+#[must_use = "excluded paths are unregistered as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ExcludedPathsGuard {
+    project_root: PathBuf,
+    id: u64,
+}
+
+impl Drop for ExcludedPathsGuard {
+    fn drop(&mut self) {
+        let mut exclusions_by_root = PATH_EXCLUSIONS_BY_ROOT.write();
+        if let Some(registrations) = exclusions_by_root.get_mut(&self.project_root) {
+            registrations.retain(|registered| registered.id != self.id);
+            if registrations.is_empty() {
+                exclusions_by_root.remove(&self.project_root);
+            }
+        }
+    }
+}
 
+/// Register the excluded paths of 'project_root' until the returned guard is dropped.
+/// They take precedence over any registered before for the same root,
+/// which apply again once this guard is dropped.
 pub fn set_excluded_paths(
     project_root: &Path,
     exclude_paths: &[PathBuf],
+    include_paths: &[String],
     use_regex_matching: bool,
-) -> Result<()> {
-    let mut exclusions = PATH_EXCLUSIONS_SINGLETON.write();
-    *exclusions = Some(PathExclusions::try_from_with_mode(
-        project_root,
-        exclude_paths.into(),
-        use_regex_matching,
-    )?);
-    Ok(())
+) -> Result<ExcludedPathsGuard> {
+    let exclusions =
+        PathExclusions::try_from_with_mode(project_root, exclude_paths.into(), use_regex_matching)?
+            .with_include_paths(include_paths)?;
+    let project_root = normalize_path(project_root).into_owned();
+    let id = NEXT_REGISTRATION_ID.fetch_add(1, Ordering::Relaxed);
+    PATH_EXCLUSIONS_BY_ROOT
+        .write()
+        .entry(project_root.clone())
+        .or_default()
+        .push(RegisteredExclusions { id, exclusions });
+    Ok(ExcludedPathsGuard { project_root, id })
+}
+
+/// Excluded paths registered from Python, which stay registered until the 'with' block exits.
+#[pyclass(module = "tach.extension")]
+pub struct ExcludedPaths {
+    guard: Option<ExcludedPathsGuard>,
+}
+
+impl From<ExcludedPathsGuard> for ExcludedPaths {
+    fn from(guard: ExcludedPathsGuard) -> Self {
+        Self { guard: Some(guard) }
+    }
+}
+
+#[pymethods]
+impl ExcludedPaths {
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        self.guard = None;
+        false
+    }
 }
 
 impl PathExclusions {
     // Input MUST be an absolute path within the project root
-    fn is_path_excluded<P: AsRef<Path>>(&self, path: P) -> bool {
+    fn relative_path_with_forward_slashes<P: AsRef<Path>>(&self, path: P) -> String {
         // This is for portability across OS
-        // Exclude patterns in 'tach.toml' are universally written with forward slashes,
+        // Patterns in 'tach.toml' are universally written with forward slashes,
         // so we force our relative path to have forward slashes before checking for a match.
-        let path_with_forward_slashes: String = path
-            .as_ref()
-            .strip_prefix(&self.project_root)
-            .unwrap()
-            .components()
-            .map(|component| component.as_os_str().to_string_lossy())
-            .join("/");
+        to_forward_slashes(path.as_ref().strip_prefix(&self.project_root).unwrap())
+    }
 
+    fn is_path_excluded<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path_with_forward_slashes = self.relative_path_with_forward_slashes(path);
         self.patterns
             .iter()
             .any(|p| p.matches(&path_with_forward_slashes))
     }
 
+    fn is_path_included<P: AsRef<Path>>(&self, path: P) -> bool {
+        if self.include_patterns.is_empty() {
+            return true;
+        }
+        let path_with_forward_slashes = self.relative_path_with_forward_slashes(path);
+        self.include_patterns
+            .iter()
+            .any(|p| p.matches(&path_with_forward_slashes))
+    }
+
+    fn with_include_paths(mut self, include_paths: &[String]) -> Result<Self> {
+        for pattern in include_paths {
+            let include_pattern =
+                PatternMatcher::from_pattern(pattern, false).map_err(|e| match e {
+                    PathExclusionError::GlobPatternError { source, .. } => {
+                        PathExclusionError::IncludeGlobPatternError {
+                            include: pattern.to_string(),
+                            source,
+                        }
+                    }
+                    PathExclusionError::RegexPatternError { source, .. } => {
+                        PathExclusionError::IncludeRegexPatternError {
+                            include: pattern.to_string(),
+                            source,
+                        }
+                    }
+                    e => e,
+                })?;
+            self.include_patterns.push(include_pattern);
+        }
+        Ok(self)
+    }
+
     fn try_from_with_mode<P: AsRef<Path>>(
         project_root: P,
         from: Vec<PathBuf>,
@@ -75,22 +188,211 @@ impl PathExclusions {
         let mut patterns: Vec<PatternMatcher> = vec![];
         for pattern in from.iter() {
             let pattern_str = pattern.to_str().unwrap();
-            patterns.push(if use_regex_matching {
-                PatternMatcher::from_regex(pattern_str)?
-            } else {
-                PatternMatcher::from_glob(pattern_str)?
-            });
+            patterns.push(PatternMatcher::from_pattern(
+                pattern_str,
+                use_regex_matching,
+            )?);
         }
         Ok(Self {
-            project_root: project_root.as_ref().to_path_buf(),
+            project_root: normalize_path(project_root.as_ref()).into_owned(),
             patterns,
+            include_patterns: vec![],
         })
     }
 }
 
 pub fn is_path_excluded<P: AsRef<Path>>(path: P) -> bool {
-    PATH_EXCLUSIONS_SINGLETON
+    let path = normalize_path(path.as_ref());
+    // When project roots are nested, the innermost project's exclusions apply
+    PATH_EXCLUSIONS_BY_ROOT
+        .read()
+        .iter()
+        .filter(|(project_root, _)| path.starts_with(project_root))
+        .max_by_key(|(project_root, _)| project_root.components().count())
+        .and_then(|(_, registrations)| registrations.last())
+        .is_some_and(|registered| registered.exclusions.is_path_excluded(&path))
+}
+
+/// Whether the file at 'path' matches the 'include' patterns of its project, if there are any.
+pub fn is_path_included<P: AsRef<Path>>(path: P) -> bool {
+    let path = normalize_path(path.as_ref());
+    PATH_EXCLUSIONS_BY_ROOT
         .read()
-        .as_ref()
-        .is_some_and(|path_exclusions| path_exclusions.is_path_excluded(path))
+        .iter()
+        .filter(|(project_root, _)| path.starts_with(project_root))
+        .max_by_key(|(project_root, _)| project_root.components().count())
+        .and_then(|(_, registrations)| registrations.last())
+        .is_none_or(|registered| registered.exclusions.is_path_included(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusions_are_scoped_to_project_root() {
+        let _project_a = set_excluded_paths(
+            Path::new("/exclusion_test/project_a"),
+            &[PathBuf::from("**/generated")],
+            &[],
+            false,
+        )
+        .unwrap();
+        let _project_b = set_excluded_paths(
+            Path::new("/exclusion_test/project_b"),
+            &[PathBuf::from("**/vendored")],
+            &[],
+            false,
+        )
+        .unwrap();
+
+        assert!(is_path_excluded("/exclusion_test/project_a/src/generated"));
+        assert!(!is_path_excluded("/exclusion_test/project_a/src/vendored"));
+        assert!(is_path_excluded("/exclusion_test/project_b/src/vendored"));
+        assert!(!is_path_excluded("/exclusion_test/project_b/src/generated"));
+        assert!(!is_path_excluded("/exclusion_test/other/generated"));
+    }
+
+    #[test]
+    fn test_include_paths_restrict_walked_files() {
+        let _excluded_paths = set_excluded_paths(
+            Path::new("/inclusion_test/project"),
+            &[PathBuf::from("**/generated")],
+            &[
+                "services/billing/**".to_string(),
+                "scripts/*.py".to_string(),
+            ],
+            false,
+        )
+        .unwrap();
+
+        assert!(is_path_included(
+            "/inclusion_test/project/services/billing/api.py"
+        ));
+        assert!(is_path_included(
+            "/inclusion_test/project/scripts/deploy.py"
+        ));
+        assert!(!is_path_included(
+            "/inclusion_test/project/services/auth/api.py"
+        ));
+        assert!(is_path_excluded(
+            "/inclusion_test/project/services/billing/generated"
+        ));
+        // Projects without include patterns include every file
+        assert!(is_path_included("/inclusion_test/other/app.py"));
+    }
+
+    #[test]
+    fn test_mixed_pattern_syntax() {
+        let _excluded_paths = set_excluded_paths(
+            Path::new("/pattern_syntax_test/project"),
+            &[
+                PathBuf::from("re:^build/.*"),
+                PathBuf::from("glob:**/generated"),
+                PathBuf::from("vendored/.*"),
+            ],
+            &[],
+            true,
+        )
+        .unwrap();
+
+        assert!(is_path_excluded("/pattern_syntax_test/project/build/lib"));
+        assert!(is_path_excluded(
+            "/pattern_syntax_test/project/src/generated"
+        ));
+        // Unprefixed patterns follow 'use_regex_matching'
+        assert!(is_path_excluded(
+            "/pattern_syntax_test/project/vendored/lib"
+        ));
+    }
+
+    #[test]
+    fn test_glob_patterns_with_backslashes() {
+        let _excluded_paths = set_excluded_paths(
+            Path::new("/separator_test/project"),
+            &[PathBuf::from(r"src\generated\*")],
+            &[r"src\**".to_string()],
+            false,
+        )
+        .unwrap();
+
+        assert!(is_path_excluded(
+            "/separator_test/project/src/generated/api"
+        ));
+        assert!(!is_path_excluded("/separator_test/project/src/api"));
+        assert!(is_path_included("/separator_test/project/src/api.py"));
+        assert!(!is_path_included(
+            "/separator_test/project/scripts/deploy.py"
+        ));
+    }
+
+    #[test]
+    fn test_sequential_registrations_for_same_root() {
+        let root = Path::new("/guard_test/project");
+        let generated =
+            set_excluded_paths(root, &[PathBuf::from("**/generated")], &[], false).unwrap();
+        assert!(is_path_excluded("/guard_test/project/src/generated"));
+        drop(generated);
+
+        // A later run with different excludes sees only its own
+        let vendored =
+            set_excluded_paths(root, &[PathBuf::from("**/vendored")], &[], false).unwrap();
+        assert!(is_path_excluded("/guard_test/project/src/vendored"));
+        assert!(!is_path_excluded("/guard_test/project/src/generated"));
+        drop(vendored);
+
+        assert!(!is_path_excluded("/guard_test/project/src/vendored"));
+        assert!(!PATH_EXCLUSIONS_BY_ROOT.read().contains_key(root));
+    }
+
+    #[test]
+    fn test_nested_registrations_for_same_root() {
+        let root = Path::new("/nested_guard_test/project");
+        let outer = set_excluded_paths(root, &[PathBuf::from("**/generated")], &[], false).unwrap();
+        let inner = set_excluded_paths(root, &[PathBuf::from("**/vendored")], &[], false).unwrap();
+        assert!(is_path_excluded("/nested_guard_test/project/src/vendored"));
+        assert!(!is_path_excluded(
+            "/nested_guard_test/project/src/generated"
+        ));
+
+        // Dropping the inner registration restores the outer one, rather than keeping its own
+        drop(inner);
+        assert!(is_path_excluded("/nested_guard_test/project/src/generated"));
+        assert!(!is_path_excluded("/nested_guard_test/project/src/vendored"));
+
+        drop(outer);
+        assert!(!PATH_EXCLUSIONS_BY_ROOT.read().contains_key(root));
+    }
+
+    #[test]
+    fn test_concurrent_roots_are_independent() {
+        let barrier = std::sync::Barrier::new(2);
+        std::thread::scope(|scope| {
+            for (project, excluded, other) in [
+                ("a", "generated", "vendored"),
+                ("b", "vendored", "generated"),
+            ] {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    let root = PathBuf::from(format!("/concurrent_test/{project}"));
+                    let _excluded_paths = set_excluded_paths(
+                        &root,
+                        &[PathBuf::from(format!("**/{excluded}"))],
+                        &[],
+                        false,
+                    )
+                    .unwrap();
+                    // Both roots are registered at once while either checks its paths
+                    barrier.wait();
+                    let excludes_own = is_path_excluded(root.join("src").join(excluded));
+                    let excludes_other = is_path_excluded(root.join("src").join(other));
+                    barrier.wait();
+                    assert!(excludes_own);
+                    assert!(!excludes_other);
+                });
+            }
+        });
+        assert!(!is_path_excluded("/concurrent_test/a/src/generated"));
+        assert!(!is_path_excluded("/concurrent_test/b/src/vendored"));
+    }
 }
@@ -10,6 +10,15 @@ pub type Result<T> = std::result::Result<T, error::ParsingError>;
 pub struct ProjectInfo {
     pub dependencies: HashSet<String>,
     pub source_paths: Vec<PathBuf>,
+    pub entry_points: Vec<EntryPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryPoint {
+    pub group: String,
+    pub name: String,
+    // The dotted path of the referenced object (e.g. 'pkg.cli.main' for 'pkg.cli:main')
+    pub module_path: String,
 }
 
 pub fn parse_pyproject_toml(pyproject_path: &Path) -> Result<ProjectInfo> {
@@ -17,9 +26,11 @@ pub fn parse_pyproject_toml(pyproject_path: &Path) -> Result<ProjectInfo> {
     let toml_value: Value = toml::from_str(&content)?;
     let dependencies = extract_dependencies(&toml_value);
     let source_paths = extract_source_paths(&toml_value, pyproject_path.parent().unwrap());
+    let entry_points = extract_entry_points(&toml_value);
     Ok(ProjectInfo {
         dependencies,
         source_paths,
+        entry_points,
     })
 }
 
@@ -152,3 +163,236 @@ pub fn extract_source_paths(toml_value: &Value, project_root: &Path) -> Vec<Path
 
     source_paths
 }
+
+fn as_str_iter(value: Option<&Value>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+}
+
+/// The source roots declared by the package configuration in a pyproject.toml,
+/// from '[tool.setuptools.packages]', '[tool.poetry.packages]', or '[tool.hatch.build]'.
+/// Unlike 'extract_source_paths', these are the directories which packages are imported relative to,
+/// and nothing is returned for a pyproject.toml without any package declarations.
+pub fn extract_declared_source_roots(toml_value: &Value, project_root: &Path) -> Vec<PathBuf> {
+    let mut source_roots = Vec::new();
+    let tool = toml_value.get("tool");
+    let join = |dir: &str| {
+        if dir.is_empty() || dir == "." {
+            project_root.to_path_buf()
+        } else {
+            project_root.join(dir)
+        }
+    };
+
+    if let Some(setuptools) = tool.and_then(|t| t.get("setuptools")) {
+        match setuptools.get("packages") {
+            // Explicit package names are imported relative to the package directory, if any
+            Some(Value::Array(packages)) if !packages.is_empty() => {
+                let package_dir = setuptools
+                    .get("package-dir")
+                    .and_then(|dirs| dirs.get(""))
+                    .and_then(|dir| dir.as_str())
+                    .unwrap_or("");
+                source_roots.push(join(package_dir));
+            }
+            Some(Value::Table(packages)) => {
+                let find = packages.get("find");
+                let mut where_dirs = as_str_iter(find.and_then(|f| f.get("where"))).peekable();
+                if where_dirs.peek().is_none() {
+                    source_roots.push(project_root.to_path_buf());
+                }
+                source_roots.extend(where_dirs.map(join));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(packages) = tool
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("packages"))
+        .and_then(|p| p.as_array())
+    {
+        for package in packages {
+            if package.get("include").is_some() {
+                let from = package.get("from").and_then(|f| f.as_str()).unwrap_or("");
+                source_roots.push(join(from));
+            }
+        }
+    }
+
+    if let Some(build) = tool
+        .and_then(|t| t.get("hatch"))
+        .and_then(|h| h.get("build"))
+    {
+        let wheel = build.get("targets").and_then(|t| t.get("wheel"));
+        // Packages are paths to package directories (e.g. 'src/pkg')
+        for package in as_str_iter(build.get("packages"))
+            .chain(as_str_iter(wheel.and_then(|w| w.get("packages"))))
+        {
+            let package_path = project_root.join(package);
+            source_roots.push(
+                package_path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or(package_path),
+            );
+        }
+        // Sources are prefixes which are removed from paths in the built package
+        for sources in [build.get("sources"), wheel.and_then(|w| w.get("sources"))] {
+            match sources {
+                Some(Value::Array(_)) => source_roots.extend(as_str_iter(sources).map(join)),
+                Some(Value::Table(sources)) => {
+                    source_roots.extend(sources.keys().map(|dir| join(dir)))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    source_roots.sort();
+    source_roots.dedup();
+    source_roots
+}
+
+fn entry_point_module_path(object_reference: &str) -> String {
+    // Entry points are written as 'pkg.module:attr.nested [extra]'
+    object_reference
+        .split('[')
+        .next()
+        .unwrap_or(object_reference)
+        .trim()
+        .replace(':', ".")
+}
+
+fn extract_entry_point_group(entry_points: &mut Vec<EntryPoint>, group: &str, value: &Value) {
+    if let Some(table) = value.as_table() {
+        for (name, object_reference) in table {
+            // Poetry allows scripts to be tables (e.g. { reference = "pkg.cli:main", type = "console" })
+            let object_reference = object_reference
+                .as_str()
+                .or_else(|| object_reference.get("reference").and_then(|r| r.as_str()));
+            if let Some(object_reference) = object_reference {
+                entry_points.push(EntryPoint {
+                    group: group.to_string(),
+                    name: name.to_string(),
+                    module_path: entry_point_module_path(object_reference),
+                });
+            }
+        }
+    }
+}
+
+pub fn extract_entry_points(toml_value: &Value) -> Vec<EntryPoint> {
+    let mut entry_points = Vec::new();
+
+    if let Some(project) = toml_value.get("project") {
+        if let Some(scripts) = project.get("scripts") {
+            extract_entry_point_group(&mut entry_points, "console_scripts", scripts);
+        }
+        if let Some(gui_scripts) = project.get("gui-scripts") {
+            extract_entry_point_group(&mut entry_points, "gui_scripts", gui_scripts);
+        }
+        if let Some(groups) = project.get("entry-points").and_then(|e| e.as_table()) {
+            for (group, value) in groups {
+                extract_entry_point_group(&mut entry_points, group, value);
+            }
+        }
+    }
+
+    if let Some(poetry) = toml_value.get("tool").and_then(|t| t.get("poetry")) {
+        if let Some(scripts) = poetry.get("scripts") {
+            extract_entry_point_group(&mut entry_points, "console_scripts", scripts);
+        }
+        if let Some(groups) = poetry.get("plugins").and_then(|p| p.as_table()) {
+            for (group, value) in groups {
+                extract_entry_point_group(&mut entry_points, group, value);
+            }
+        }
+    }
+
+    entry_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_declared_source_roots() {
+        let project_root = Path::new("/project");
+        let declared_source_roots = |pyproject: &str| {
+            extract_declared_source_roots(&toml::from_str(pyproject).unwrap(), project_root)
+        };
+
+        assert_eq!(
+            declared_source_roots(
+                r#"
+[tool.setuptools.packages.find]
+where = ["src"]
+"#
+            ),
+            vec![project_root.join("src")]
+        );
+        assert_eq!(
+            declared_source_roots(
+                r#"
+[tool.setuptools]
+packages = ["pkg", "pkg.sub"]
+"#
+            ),
+            vec![project_root.to_path_buf()]
+        );
+        assert_eq!(
+            declared_source_roots(
+                r#"
+[tool.poetry]
+packages = [{ include = "pkg", from = "lib" }]
+"#
+            ),
+            vec![project_root.join("lib")]
+        );
+        assert_eq!(
+            declared_source_roots(
+                r#"
+[tool.hatch.build.targets.wheel]
+packages = ["src/pkg"]
+"#
+            ),
+            vec![project_root.join("src")]
+        );
+        assert!(declared_source_roots("[project]\nname = \"pkg\"").is_empty());
+    }
+
+    #[test]
+    fn test_extract_entry_points() {
+        let toml_value: Value = toml::from_str(
+            r#"
+[project.scripts]
+tool = "pkg.cli:main"
+
+[project.entry-points."pkg.plugins"]
+extra = "pkg.plugins.extra:Plugin.create [fast]"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            extract_entry_points(&toml_value),
+            vec![
+                EntryPoint {
+                    group: "console_scripts".to_string(),
+                    name: "tool".to_string(),
+                    module_path: "pkg.cli.main".to_string(),
+                },
+                EntryPoint {
+                    group: "pkg.plugins".to_string(),
+                    name: "extra".to_string(),
+                    module_path: "pkg.plugins.extra.Plugin.create".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -1,8 +1,9 @@
+use std::borrow::Cow;
 use std::fs;
 use std::io;
 use std::io::Read;
 use std::path::StripPrefixError;
-use std::path::{Path, PathBuf, MAIN_SEPARATOR, MAIN_SEPARATOR_STR};
+use std::path::{Component, Path, PathBuf, Prefix, MAIN_SEPARATOR, MAIN_SEPARATOR_STR};
 
 use cached::proc_macro::cached;
 use globset::Glob;
@@ -13,7 +14,8 @@ use walkdir::{DirEntry, WalkDir};
 
 use crate::config::root_module::ROOT_MODULE_SENTINEL_TAG;
 use crate::config::ModuleConfig;
-use crate::exclusion::is_path_excluded;
+use crate::exclusion::{is_path_excluded, is_path_included};
+use crate::python::encoding::decode_python_source;
 
 #[derive(Error, Debug)]
 pub enum FileSystemError {
@@ -31,18 +33,56 @@ pub fn relative_to<P: AsRef<Path>, R: AsRef<Path>>(path: P, root: R) -> Result<P
     Ok(diff_path.to_owned())
 }
 
+/// Strip the Windows verbatim prefixes added by `canonicalize` (`\\?\C:\` and `\\?\UNC\server\share`),
+/// so that paths compare equal no matter how they were resolved. Other paths are returned unchanged.
+pub fn normalize_path(path: &Path) -> Cow<'_, Path> {
+    let mut components = path.components();
+    let Some(Component::Prefix(prefix)) = components.next() else {
+        return Cow::Borrowed(path);
+    };
+    let normalized_prefix = match prefix.kind() {
+        Prefix::VerbatimDisk(disk) => format!("{}:", disk as char),
+        Prefix::VerbatimUNC(server, share) => format!(
+            r"\\{}\{}",
+            server.to_string_lossy(),
+            share.to_string_lossy()
+        ),
+        _ => return Cow::Borrowed(path),
+    };
+    let mut normalized = PathBuf::from(normalized_prefix);
+    normalized.extend(components);
+    Cow::Owned(normalized)
+}
+
+/// Render a relative path with forward slashes, which is how paths are written in 'tach.toml' on every platform.
+pub fn to_forward_slashes(path: &Path) -> String {
+    normalize_separators(
+        &path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .join("/"),
+    )
+}
+
+/// Replace backslash separators in a path from configuration with forward slashes.
+pub fn normalize_separators(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
 pub fn file_to_module_path(source_roots: &[PathBuf], file_path: &Path) -> Result<String> {
+    let file_path = normalize_path(file_path);
     // Find the matching source root
     let matching_root = source_roots
         .iter()
-        .find(|&root| file_path.starts_with(root))
+        .map(|root| normalize_path(root))
+        .find(|root| file_path.starts_with(root))
         .ok_or(FileSystemError::Other(format!(
             "No matching source root found for filepath: {:?}",
             file_path
         )))?;
 
     // Get the relative path from the matching root
-    let relative_path = file_path.strip_prefix(matching_root)?;
+    let relative_path = file_path.strip_prefix(&matching_root)?;
 
     // If the relative path is empty, return an error
     // indicating that the path cannot be a source root itself
@@ -232,11 +272,19 @@ pub fn is_project_import<P: AsRef<Path>>(source_roots: &[P], mod_path: &str) ->
     resolved_module.is_some_and(|module| !is_path_excluded(&module.file_path))
 }
 
+/// Read a file as UTF-8, decoding it according to its byte order mark or PEP 263
+/// encoding declaration if it has one.
 pub fn read_file_content<P: AsRef<Path>>(path: P) -> Result<String> {
     let mut file = fs::File::open(path.as_ref())?;
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-    Ok(content)
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    decode_python_source(bytes).map_err(|e| {
+        FileSystemError::Other(format!(
+            "Failed to read '{}': {}",
+            path.as_ref().display(),
+            e
+        ))
+    })
 }
 
 fn is_hidden(entry: &DirEntry) -> bool {
@@ -305,6 +353,7 @@ pub fn walk_pyfiles(root: &str) -> impl Iterator<Item = PathBuf> {
         .filter_entry(|e| !is_hidden(e) && !direntry_is_excluded(e) && is_pyfile_or_dir(e))
         .filter_map(|entry| entry.ok())
         .filter(|entry| entry.file_type().is_file()) // filter_entry would skip dirs if they were excluded earlier
+        .filter(|entry| is_path_included(entry.path()))
         .map(move |entry| {
             entry
                 .path()
@@ -321,6 +370,7 @@ pub fn walk_pyprojects(root: &str) -> impl Iterator<Item = PathBuf> {
         .filter_map(|entry| entry.ok())
         .filter(|entry| entry.file_type().is_file())
         .filter(|entry| entry.file_name() == "pyproject.toml")
+        .filter(|entry| is_path_included(entry.path()))
         .map(|entry| entry.into_path())
 }
 
@@ -413,4 +463,26 @@ mod tests {
             expected
         );
     }
+
+    #[rstest]
+    #[case("src/pkg", "src/pkg")]
+    #[case(r"src\pkg", "src/pkg")]
+    #[case("./src", "./src")]
+    #[case(".", ".")]
+    fn test_to_forward_slashes(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(to_forward_slashes(Path::new(path)), expected);
+    }
+
+    #[cfg(windows)]
+    #[rstest]
+    #[case(r"\\?\C:\project\src", r"C:\project\src")]
+    #[case(r"\\?\UNC\server\share\project", r"\\server\share\project")]
+    #[case(r"C:\project\src", r"C:\project\src")]
+    #[case(r"\\server\share\project", r"\\server\share\project")]
+    fn test_normalize_path(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(
+            normalize_path(Path::new(path)).as_ref(),
+            Path::new(expected)
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::config::{InterfaceConfig, InterfaceDataTypes};
+use crate::config::{ConfigSpan, InterfaceConfig, InterfaceDataTypes};
 use regex::Regex;
 
 #[derive(Debug, Clone)]
@@ -6,6 +6,7 @@ pub struct CompiledInterface {
     pub from_modules: Vec<Regex>,
     pub expose: Vec<Regex>,
     pub data_types: InterfaceDataTypes,
+    pub span: ConfigSpan,
 }
 
 impl CompiledInterface {
@@ -35,6 +36,7 @@ impl<'a> CompiledInterfaces {
             .into_iter()
             .map(|interface| CompiledInterface {
                 data_types: interface.data_types.clone(),
+                span: interface.span.clone(),
                 from_modules: interface
                     .from_modules
                     .iter()
@@ -1,7 +1,8 @@
 use super::compiled::{CompiledInterface, CompiledInterfaces};
 use super::error::InterfaceError;
 use crate::config::{InterfaceDataTypes, ModuleConfig};
-use crate::filesystem::module_to_file_path;
+use crate::filesystem::{module_to_file_path, read_file_content};
+use crate::python::error::ParsingError;
 use crate::python::parsing::parse_python_source;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -294,7 +295,8 @@ pub fn type_check_all_interface_members(
     for module_path in module_paths {
         let resolved_mod = module_to_file_path(source_roots, module_path, false).unwrap();
         // first get this working for only the module file itself
-        let python_source = std::fs::read_to_string(resolved_mod.file_path).unwrap();
+        let python_source =
+            read_file_content(&resolved_mod.file_path).map_err(ParsingError::from)?;
         let ast = match parse_python_source(&python_source) {
             Ok(Mod::Module(ast)) => ast,
             _ => panic!("Expected ast::Mod variant"),
@@ -334,6 +336,7 @@ mod tests {
             expose: vec![".*".to_string()],
             from_modules: vec!["my_module".to_string()],
             data_types: InterfaceDataTypes::Primitive,
+            ..Default::default()
         }
     }
 
@@ -79,6 +79,12 @@ pub fn get_interrupt_channel() -> Receiver<()> {
     INTERRUPT_NOTIFIER.create_channel()
 }
 
+/// Set (or clear) the interrupt signal directly, as the Ctrl-C handler would.
+#[cfg(test)]
+pub(crate) fn set_interrupt_signal(interrupted: bool) {
+    INTERRUPT_SIGNAL.store(interrupted, Ordering::SeqCst);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,7 @@
 pub mod cache;
 pub mod checks;
 pub mod cli;
+pub mod codeowners;
 pub mod colors;
 pub mod commands;
 pub mod config;
@@ -17,12 +18,17 @@ pub mod parsing;
 pub mod pattern;
 pub mod processors;
 pub mod python;
+pub mod rpc;
 pub mod tests;
 
-use commands::{check, report, server, sync, test};
-use diagnostics::serialize_diagnostics_json;
+use commands::{
+    check, explain, graph, graph_artifact, new_domain, new_module, owners, report, server, sync,
+    test, trace, why,
+};
+use diagnostics::sarif::serialize_diagnostics_sarif;
+use diagnostics::{py_diagnostic_fingerprints, serialize_diagnostics_json};
 use modularity::into_usage_errors;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 use pyo3::exceptions::{PyKeyboardInterrupt, PyOSError, PySyntaxError, PyValueError};
@@ -37,7 +43,7 @@ mod errors {
 impl From<processors::import::ImportParseError> for PyErr {
     fn from(err: processors::import::ImportParseError) -> Self {
         match err {
-            processors::import::ImportParseError::Parsing { file: _, source: _ } => {
+            processors::import::ImportParseError::Parsing { .. } => {
                 PySyntaxError::new_err(err.to_string())
             }
             _ => PyOSError::new_err(err.to_string()),
@@ -60,6 +66,12 @@ impl From<report::ReportCreationError> for PyErr {
     }
 }
 
+impl From<processors::django::apps::DjangoAppsError> for PyErr {
+    fn from(err: processors::django::apps::DjangoAppsError) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
 impl From<cache::CacheError> for PyErr {
     fn from(err: cache::CacheError) -> Self {
         PyValueError::new_err(err.to_string())
@@ -81,6 +93,12 @@ impl From<check::CheckError> for PyErr {
     }
 }
 
+impl From<modules::error::ModuleTreeError> for PyErr {
+    fn from(err: modules::error::ModuleTreeError) -> Self {
+        check::CheckError::from(err).into()
+    }
+}
+
 impl From<python::error::ParsingError> for PyErr {
     fn from(err: python::error::ParsingError) -> Self {
         match err {
@@ -100,8 +118,17 @@ impl From<parsing::error::ParsingError> for PyErr {
             parsing::error::ParsingError::Io(err) => PyOSError::new_err(err.to_string()),
             parsing::error::ParsingError::Filesystem(err) => PyOSError::new_err(err.to_string()),
             parsing::error::ParsingError::TomlParse(err) => PyValueError::new_err(err.to_string()),
+            parsing::error::ParsingError::TomlEdit(err) => PyValueError::new_err(err.to_string()),
+            parsing::error::ParsingError::UpgradeRequired(_)
+            | parsing::error::ParsingError::UnsupportedVersion(_) => {
+                PyValueError::new_err(err.to_string())
+            }
             parsing::error::ParsingError::MissingField(err) => PyValueError::new_err(err),
             parsing::error::ParsingError::ModulePath(err) => PyValueError::new_err(err),
+            parsing::error::ParsingError::DomainPattern(err) => PyValueError::new_err(err),
+            parsing::error::ParsingError::ReferencePattern(_) => {
+                PyValueError::new_err(err.to_string())
+            }
         }
     }
 }
@@ -113,6 +140,27 @@ impl From<sync::SyncError> for PyErr {
             sync::SyncError::CheckError(err) => err.into(),
             sync::SyncError::RootModuleViolation(err) => PyValueError::new_err(err.to_string()),
             sync::SyncError::EditError(err) => PyValueError::new_err(err.to_string()),
+            sync::SyncError::NewPackagePolicy(err) => PyValueError::new_err(err),
+        }
+    }
+}
+
+impl From<new_domain::NewDomainError> for PyErr {
+    fn from(err: new_domain::NewDomainError) -> Self {
+        match err {
+            new_domain::NewDomainError::AlreadyExists(_)
+            | new_domain::NewDomainError::NoSourceRoot => PyValueError::new_err(err.to_string()),
+            _ => PyOSError::new_err(err.to_string()),
+        }
+    }
+}
+
+impl From<new_module::NewModuleError> for PyErr {
+    fn from(err: new_module::NewModuleError) -> Self {
+        match err {
+            new_module::NewModuleError::AlreadyExists(_)
+            | new_module::NewModuleError::NoSourceRoot => PyValueError::new_err(err.to_string()),
+            new_module::NewModuleError::Io(_) => PyOSError::new_err(err.to_string()),
         }
     }
 }
@@ -126,6 +174,54 @@ impl From<lsp::error::ServerError> for PyErr {
     }
 }
 
+impl From<rpc::error::RpcServerError> for PyErr {
+    fn from(err: rpc::error::RpcServerError) -> Self {
+        PyOSError::new_err(err.to_string())
+    }
+}
+
+impl From<why::WhyError> for PyErr {
+    fn from(err: why::WhyError) -> Self {
+        match err {
+            why::WhyError::UnknownModule(_) => PyValueError::new_err(err.to_string()),
+            why::WhyError::Check(err) => err.into(),
+        }
+    }
+}
+
+impl From<graph_artifact::GraphArtifactError> for PyErr {
+    fn from(err: graph_artifact::GraphArtifactError) -> Self {
+        match err {
+            graph_artifact::GraphArtifactError::Why(err) => err.into(),
+            graph_artifact::GraphArtifactError::Report(err) => err.into(),
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+impl From<explain::ExplainError> for PyErr {
+    fn from(err: explain::ExplainError) -> Self {
+        match err {
+            explain::ExplainError::ModuleNotFound(_) => PyValueError::new_err(err.to_string()),
+            explain::ExplainError::Check(err) => err.into(),
+        }
+    }
+}
+
+impl From<trace::TraceError> for PyErr {
+    fn from(err: trace::TraceError) -> Self {
+        match err {
+            trace::TraceError::NotInSourceRoot(_) | trace::TraceError::ModuleNotFound(_) => {
+                PyValueError::new_err(err.to_string())
+            }
+            trace::TraceError::Check(err) => err.into(),
+            trace::TraceError::Filesystem(err) => PyOSError::new_err(err.to_string()),
+            trace::TraceError::ImportParse(err) => err.into(),
+            trace::TraceError::Parsing(err) => err.into(),
+        }
+    }
+}
+
 impl From<config::edit::EditError> for PyErr {
     fn from(err: config::edit::EditError) -> Self {
         PyValueError::new_err(err.to_string())
@@ -158,6 +254,17 @@ fn parse_project_config(
     parsing::config::parse_project_config(filepath)
 }
 
+/// Migrate deprecated keys in the project config at 'filepath', and set its version to the latest.
+/// Returns a description of each migration. With 'dry_run', the config is not written.
+#[pyfunction]
+#[pyo3(signature = (filepath, dry_run=false))]
+fn upgrade_project_config(
+    filepath: PathBuf,
+    dry_run: bool,
+) -> parsing::config::Result<Vec<String>> {
+    Ok(parsing::migrations::upgrade_project_config(&filepath, dry_run)?.migrations)
+}
+
 #[pyfunction]
 #[pyo3(signature = (config))]
 fn dump_project_config_to_toml(
@@ -203,43 +310,78 @@ fn get_external_imports(
     )
 }
 
-/// Set excluded paths globally.
+/// Get all imports from file_path, categorized as project, external, stdlib, or unresolved
+#[pyfunction]
+#[pyo3(signature = (source_roots, file_path, stdlib_modules, ignore_type_checking_imports=false, include_string_imports=false))]
+fn get_categorized_imports(
+    source_roots: Vec<String>,
+    file_path: String,
+    stdlib_modules: Vec<String>,
+    ignore_type_checking_imports: bool,
+    include_string_imports: bool,
+) -> processors::import::Result<Vec<commands::helpers::import::CategorizedImport>> {
+    let source_roots: Vec<PathBuf> = source_roots.iter().map(PathBuf::from).collect();
+    let file_path = PathBuf::from(file_path);
+    let stdlib_modules: HashSet<String> = stdlib_modules.into_iter().collect();
+    commands::helpers::import::get_categorized_imports(
+        &source_roots,
+        &file_path,
+        &stdlib_modules,
+        ignore_type_checking_imports,
+        include_string_imports,
+    )
+}
+
+/// Register excluded paths for the calls made within a 'with' block.
 /// This is called separately in order to set up a singleton instance holding regex/glob patterns,
 /// since they would be expensive to build for every call.
 #[pyfunction]
-#[pyo3(signature = (project_root, exclude_paths, use_regex_matching))]
+#[pyo3(signature = (project_root, exclude_paths, use_regex_matching, include_paths = vec![]))]
 fn set_excluded_paths(
     project_root: String,
     exclude_paths: Vec<String>,
     use_regex_matching: bool,
-) -> exclusion::Result<()> {
+    include_paths: Vec<String>,
+) -> exclusion::Result<exclusion::ExcludedPaths> {
     let project_root = PathBuf::from(project_root);
     let exclude_paths: Vec<PathBuf> = exclude_paths.iter().map(PathBuf::from).collect();
-    exclusion::set_excluded_paths(&project_root, &exclude_paths, use_regex_matching)
+    exclusion::set_excluded_paths(
+        &project_root,
+        &exclude_paths,
+        &include_paths,
+        use_regex_matching,
+    )
+    .map(exclusion::ExcludedPaths::from)
 }
 
 /// Validate external dependency imports against pyproject.toml dependencies
 #[pyfunction]
-#[pyo3(signature = (project_root, project_config, module_mappings, stdlib_modules))]
+#[pyo3(signature = (project_root, project_config, module_mappings, stdlib_modules, project_distributions = vec![]))]
 fn check_external_dependencies(
+    py: Python<'_>,
     project_root: String,
     project_config: config::ProjectConfig,
     module_mappings: HashMap<String, Vec<String>>,
     stdlib_modules: Vec<String>,
+    project_distributions: Vec<String>,
 ) -> check::check_external::Result<Vec<diagnostics::Diagnostic>> {
     let project_root = PathBuf::from(project_root);
-    check::check_external::check(
-        &project_root,
-        &project_config,
-        &module_mappings,
-        &stdlib_modules,
-    )
+    py.allow_threads(|| {
+        check::check_external::check(
+            &project_root,
+            &project_config,
+            &module_mappings,
+            &stdlib_modules,
+            &project_distributions,
+        )
+    })
 }
 
 /// Create a report of dependencies and usages of a given path
 #[pyfunction]
 #[pyo3(signature = (project_root, project_config, path, include_dependency_modules, include_usage_modules, skip_dependencies, skip_usages, raw))]
 fn create_dependency_report(
+    py: Python<'_>,
     project_root: String,
     project_config: &config::ProjectConfig,
     path: String,
@@ -251,16 +393,18 @@ fn create_dependency_report(
 ) -> report::Result<String> {
     let project_root = PathBuf::from(project_root);
     let file_path = PathBuf::from(path);
-    report::create_dependency_report(
-        &project_root,
-        project_config,
-        &file_path,
-        include_dependency_modules,
-        include_usage_modules,
-        skip_dependencies,
-        skip_usages,
-        raw,
-    )
+    py.allow_threads(|| {
+        report::create_dependency_report(
+            &project_root,
+            project_config,
+            &file_path,
+            include_dependency_modules,
+            include_usage_modules,
+            skip_dependencies,
+            skip_usages,
+            raw,
+        )
+    })
 }
 
 #[pyfunction]
@@ -307,50 +451,353 @@ fn update_computation_cache(
 }
 
 #[pyfunction]
-#[pyo3(name = "check", signature = (project_root, project_config, dependencies, interfaces, exclude_paths))]
+#[pyo3(signature = (project_root, project_config))]
+fn get_cache_stats(
+    project_root: String,
+    project_config: &config::ProjectConfig,
+) -> cache::Result<cache::CacheStats> {
+    cache::get_computation_cache_stats(project_root, &project_config.cache.backend)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root))]
+fn clear_cache(project_root: String) -> cache::Result<()> {
+    cache::clear_computation_cache(project_root)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, older_than_days))]
+fn prune_cache(project_root: String, older_than_days: u64) -> cache::Result<usize> {
+    cache::prune_computation_cache(project_root, older_than_days)
+}
+
+/// Run 'check'. If 'modules' or 'domains' are given, only the files within them are checked.
+#[pyfunction]
+#[pyo3(name = "check", signature = (project_root, project_config, dependencies, interfaces, exclude_paths, modules = vec![], domains = vec![]))]
+#[allow(clippy::too_many_arguments)]
 fn check_internal(
+    py: Python<'_>,
     project_root: PathBuf,
     project_config: &config::ProjectConfig,
     dependencies: bool,
     interfaces: bool,
     exclude_paths: Vec<String>,
+    modules: Vec<String>,
+    domains: Vec<String>,
 ) -> check::check_internal::Result<Vec<diagnostics::Diagnostic>> {
-    check::check_internal(
-        project_root,
-        project_config,
-        dependencies,
-        interfaces,
-        exclude_paths,
-    )
+    py.allow_threads(|| {
+        check::check_internal_with_callback(
+            project_root,
+            project_config,
+            dependencies,
+            interfaces,
+            exclude_paths,
+            &check::CheckScope::new(modules, domains),
+            None::<fn(usize)>,
+            |_, _| true,
+        )
+    })
+}
+
+/// Run 'check', and report each ignore directive along with the violations it suppressed.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths))]
+fn get_ignore_directive_usages(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: &config::ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> check::check_internal::Result<Vec<checks::ignore_directive::IgnoreDirectiveUsage>> {
+    py.allow_threads(|| check::check_ignore_directives(project_root, project_config, exclude_paths))
+}
+
+/// Leave out diagnostics beyond the project's per-file and per-module-pair caps, for display.
+#[pyfunction]
+#[pyo3(signature = (diagnostics, project_config))]
+pub fn cap_diagnostics(
+    diagnostics: Vec<diagnostics::Diagnostic>,
+    project_config: &config::ProjectConfig,
+) -> diagnostics::CappedDiagnostics {
+    diagnostics::cap_diagnostics(diagnostics, &project_config.diagnostics)
 }
 
 #[pyfunction]
+#[pyo3(signature = (project_root, diagnostics, code_frames = false))]
 pub fn format_diagnostics(
     project_root: PathBuf,
     diagnostics: Vec<diagnostics::Diagnostic>,
+    code_frames: bool,
 ) -> String {
-    check::format::DiagnosticFormatter::new(project_root).format_diagnostics(&diagnostics)
+    check::format::DiagnosticFormatter::new(project_root)
+        .with_code_frames(code_frames)
+        .format_diagnostics(&diagnostics)
 }
 
 #[pyfunction]
 #[pyo3(signature = (project_root, project_config, exclude_paths))]
 fn detect_unused_dependencies(
+    py: Python<'_>,
     project_root: PathBuf,
     project_config: &mut config::ProjectConfig,
     exclude_paths: Vec<String>,
 ) -> Result<Vec<sync::UnusedDependencies>, sync::SyncError> {
-    sync::detect_unused_dependencies(project_root, project_config, exclude_paths)
+    py.allow_threads(|| {
+        sync::detect_unused_dependencies(project_root, project_config, exclude_paths)
+    })
 }
 
 #[pyfunction]
-#[pyo3(signature = (project_root, project_config, exclude_paths, add))]
+#[pyo3(signature = (project_root, project_config, exclude_paths, add, new_packages = None))]
 pub fn sync_project(
+    py: Python<'_>,
     project_root: PathBuf,
     project_config: config::ProjectConfig,
     exclude_paths: Vec<String>,
     add: bool,
-) -> Result<(), sync::SyncError> {
-    sync::sync_project(project_root, project_config, exclude_paths, add)
+    new_packages: Option<String>,
+) -> Result<sync::SyncPlan, sync::SyncError> {
+    py.allow_threads(|| {
+        sync::sync_project(
+            project_root,
+            project_config,
+            exclude_paths,
+            add,
+            new_packages,
+        )
+    })
+}
+
+/// Find the changes which 'sync_project' would make to the project configuration, without applying them.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths, add = false, new_packages = None))]
+pub fn plan_sync(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: &config::ProjectConfig,
+    exclude_paths: Vec<String>,
+    add: bool,
+    new_packages: Option<String>,
+) -> Result<sync::SyncPlan, sync::SyncError> {
+    py.allow_threads(|| {
+        sync::plan_sync(
+            project_root,
+            project_config,
+            exclude_paths,
+            add,
+            new_packages,
+        )
+    })
+}
+
+/// Run 'check', calling 'callback(file_path, diagnostics)' as each file is checked.
+/// If the callback returns False, the check stops early.
+/// If given, 'total_callback(total)' is called with the number of files before checking starts.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, dependencies, interfaces, exclude_paths, callback, total_callback = None, modules = vec![], domains = vec![]))]
+#[allow(clippy::too_many_arguments)]
+fn check_streaming(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: &config::ProjectConfig,
+    dependencies: bool,
+    interfaces: bool,
+    exclude_paths: Vec<String>,
+    callback: PyObject,
+    total_callback: Option<PyObject>,
+    modules: Vec<String>,
+    domains: Vec<String>,
+) -> PyResult<Vec<diagnostics::Diagnostic>> {
+    let callback_error: std::sync::Mutex<Option<PyErr>> = std::sync::Mutex::new(None);
+    let result = py.allow_threads(|| {
+        check::check_internal_with_callback(
+            project_root,
+            project_config,
+            dependencies,
+            interfaces,
+            exclude_paths,
+            &check::CheckScope::new(modules, domains),
+            // Files are only counted up front when the total is requested
+            total_callback.as_ref().map(|total_callback| {
+                let callback_error = &callback_error;
+                move |total: usize| {
+                    Python::with_gil(|py| {
+                        if let Err(err) = total_callback.call1(py, (total,)) {
+                            callback_error.lock().unwrap().get_or_insert(err);
+                        }
+                    })
+                }
+            }),
+            |file_path, file_diagnostics| {
+                Python::with_gil(|py| {
+                    match callback.call1(
+                        py,
+                        (file_path.display().to_string(), file_diagnostics.to_vec()),
+                    ) {
+                        // Only an explicit False stops the check
+                        Ok(value) => !matches!(value.extract::<bool>(py), Ok(false)),
+                        Err(err) => {
+                            callback_error.lock().unwrap().get_or_insert(err);
+                            false
+                        }
+                    }
+                })
+            },
+        )
+    });
+    if let Some(err) = callback_error.into_inner().unwrap() {
+        return Err(err);
+    }
+    Ok(result?)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths))]
+fn get_dependency_graph(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<graph::DependencyGraph, check::CheckError> {
+    py.allow_threads(|| {
+        graph::build_dependency_graph(&project_root, &project_config, exclude_paths)
+    })
+}
+
+/// Every import and reference in the project which resolves to a module,
+/// for analyses which need file-level detail without parsing the project again.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths))]
+fn get_resolved_dependencies(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<why::ResolvedDependency>, check::CheckError> {
+    py.allow_threads(|| {
+        why::collect_resolved_dependencies(&project_root, &project_config, exclude_paths)
+    })
+}
+
+/// Explain why 'source' depends on 'target', as the chain of dependencies between them
+/// with the imports which create each one.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths, source, target))]
+fn explain_dependency(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+    source: String,
+    target: String,
+) -> Result<Vec<why::DependencyHop>, why::WhyError> {
+    py.allow_threads(|| {
+        why::explain_dependency(
+            &project_root,
+            &project_config,
+            exclude_paths,
+            &source,
+            &target,
+        )
+    })
+}
+
+/// Run the extraction pipeline on a single file, and report every dependency it finds
+/// along with how it is categorized and why it is ignored.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths, file_path, module_mappings, stdlib_modules))]
+fn trace_file(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+    file_path: PathBuf,
+    module_mappings: HashMap<String, Vec<String>>,
+    stdlib_modules: Vec<String>,
+) -> Result<trace::FileTrace, trace::TraceError> {
+    py.allow_threads(|| {
+        trace::trace_file(
+            &project_root,
+            &project_config,
+            exclude_paths,
+            &file_path,
+            &module_mappings,
+            &stdlib_modules,
+        )
+    })
+}
+
+/// Explain the effective configuration of the module containing a file, directory or module path,
+/// along with where each piece of it is declared.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths, path))]
+fn explain_module(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+    path: String,
+) -> Result<explain::ModuleExplanation, explain::ExplainError> {
+    py.allow_threads(|| {
+        explain::explain_module(&project_root, &project_config, exclude_paths, &path)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, exclude_paths))]
+fn get_circular_dependencies(
+    py: Python<'_>,
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    exclude_paths: Vec<String>,
+) -> Result<Vec<graph::CircularDependency>, check::CheckError> {
+    py.allow_threads(|| {
+        graph::find_circular_dependencies(&project_root, &project_config, exclude_paths)
+    })
+}
+
+/// Resolve each of the Django project's INSTALLED_APPS to its app label and module.
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config))]
+fn get_django_apps(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+) -> Result<Vec<processors::django::apps::DjangoApp>, processors::django::apps::DjangoAppsError> {
+    let django_config = project_config
+        .plugins
+        .django
+        .as_ref()
+        .ok_or(processors::django::apps::DjangoAppsError::NotConfigured)?;
+    let source_roots = project_config.prepend_roots(&project_root);
+    processors::django::apps::get_known_apps(&source_roots, django_config)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config))]
+fn get_module_owners(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+) -> BTreeMap<String, String> {
+    owners::get_module_owners(&project_root, &project_config)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, path))]
+fn create_new_domain(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    path: String,
+) -> Result<PathBuf, new_domain::NewDomainError> {
+    new_domain::create_new_domain(&project_root, &project_config, &path)
+}
+
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config, path))]
+fn create_new_module(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+    path: String,
+) -> Result<PathBuf, new_module::NewModuleError> {
+    new_module::create_new_module(&project_root, &project_config, &path)
 }
 
 #[pyfunction]
@@ -362,6 +809,15 @@ fn run_server(
     server::run_server(project_root, project_config)
 }
 
+#[pyfunction]
+#[pyo3(signature = (project_root, project_config))]
+fn run_rpc_server(
+    project_root: PathBuf,
+    project_config: config::ProjectConfig,
+) -> Result<(), rpc::error::RpcServerError> {
+    server::run_rpc_server(project_root, project_config)
+}
+
 #[pyfunction]
 #[pyo3(signature = (modules))]
 fn serialize_modules_json(modules: Vec<config::ModuleConfig>) -> String {
@@ -377,25 +833,77 @@ fn extension(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<config::RulesConfig>()?;
     m.add_class::<config::DependencyConfig>()?;
     m.add_class::<diagnostics::Diagnostic>()?;
+    m.add_class::<diagnostics::RelatedLocation>()?;
+    m.add_class::<diagnostics::SourceSpan>()?;
+    m.add_class::<diagnostics::Severity>()?;
+    m.add_class::<diagnostics::CappedDiagnostics>()?;
+    m.add_class::<diagnostics::OmittedDiagnostics>()?;
+    m.add_class::<checks::ignore_directive::SuppressedViolation>()?;
+    m.add_class::<checks::ignore_directive::IgnoreDirectiveUsage>()?;
     m.add_class::<test::TachPytestPluginHandler>()?;
     m.add_class::<modularity::UsageError>()?;
+    m.add_class::<graph::DependencyGraph>()?;
+    m.add_class::<commands::helpers::PyModuleTree>()?;
+    m.add_class::<cache::CacheStats>()?;
+    m.add_class::<sync::ModuleSyncChanges>()?;
+    m.add_class::<sync::SyncPlan>()?;
+    m.add_class::<commands::helpers::import::ImportCategory>()?;
+    m.add_class::<commands::helpers::import::CategorizedImport>()?;
+    m.add_class::<graph::DependencyGraphNode>()?;
+    m.add_class::<graph::DependencyGraphEdge>()?;
+    m.add_class::<graph::CircularDependency>()?;
+    m.add_class::<graph::CycleBreak>()?;
+    m.add_class::<why::ImportLocation>()?;
+    m.add_class::<why::DependencyHop>()?;
+    m.add_class::<why::ResolvedDependency>()?;
+    m.add_class::<graph_artifact::GraphArtifact>()?;
+    m.add_class::<trace::TracedDependency>()?;
+    m.add_class::<trace::FileTrace>()?;
+    m.add_class::<explain::ConfigSource>()?;
+    m.add_class::<explain::ExplainedInterface>()?;
+    m.add_class::<explain::Suppression>()?;
+    m.add_class::<explain::ModuleExplanation>()?;
+    m.add_class::<processors::django::apps::DjangoApp>()?;
+    m.add_class::<exclusion::ExcludedPaths>()?;
     m.add_function(wrap_pyfunction_bound!(parse_project_config, m)?)?;
     m.add_function(wrap_pyfunction_bound!(get_project_imports, m)?)?;
     m.add_function(wrap_pyfunction_bound!(get_external_imports, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_categorized_imports, m)?)?;
     m.add_function(wrap_pyfunction_bound!(set_excluded_paths, m)?)?;
     m.add_function(wrap_pyfunction_bound!(check_external_dependencies, m)?)?;
     m.add_function(wrap_pyfunction_bound!(create_dependency_report, m)?)?;
     m.add_function(wrap_pyfunction_bound!(create_computation_cache_key, m)?)?;
     m.add_function(wrap_pyfunction_bound!(check_computation_cache, m)?)?;
     m.add_function(wrap_pyfunction_bound!(update_computation_cache, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(clear_cache, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(prune_cache, m)?)?;
     m.add_function(wrap_pyfunction_bound!(dump_project_config_to_toml, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(upgrade_project_config, m)?)?;
     m.add_function(wrap_pyfunction_bound!(check_internal, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(check_streaming, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_ignore_directive_usages, m)?)?;
     m.add_function(wrap_pyfunction_bound!(format_diagnostics, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(cap_diagnostics, m)?)?;
     m.add_function(wrap_pyfunction_bound!(detect_unused_dependencies, m)?)?;
     m.add_function(wrap_pyfunction_bound!(sync_project, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(plan_sync, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_dependency_graph, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_circular_dependencies, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(explain_dependency, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_resolved_dependencies, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(trace_file, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(explain_module, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_module_owners, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(get_django_apps, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(create_new_domain, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(create_new_module, m)?)?;
     m.add_function(wrap_pyfunction_bound!(run_server, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(run_rpc_server, m)?)?;
     m.add_function(wrap_pyfunction_bound!(serialize_modules_json, m)?)?;
     m.add_function(wrap_pyfunction_bound!(serialize_diagnostics_json, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(py_diagnostic_fingerprints, m)?)?;
+    m.add_function(wrap_pyfunction_bound!(serialize_diagnostics_sarif, m)?)?;
     m.add_function(wrap_pyfunction_bound!(into_usage_errors, m)?)?;
     Ok(())
 }
@@ -1,10 +1,15 @@
 use lsp_types::notification::Notification;
 use lsp_types::request::Request;
 use lsp_types::{InitializeParams, Uri};
-use std::path::{PathBuf, MAIN_SEPARATOR_STR};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR_STR};
+use std::str::FromStr;
 use std::thread::JoinHandle;
 
-use lsp_server::{Connection, Message, Notification as NotificationMessage, RequestId};
+use lsp_server::{
+    Connection, ErrorCode, Message, Notification as NotificationMessage, RequestId, Response,
+};
 
 use crate::commands::check::check_internal;
 use crate::config;
@@ -18,6 +23,8 @@ use crossbeam_channel::select;
 pub struct LSPServer {
     project_root: PathBuf,
     project_config: config::ProjectConfig,
+    // The editor's contents of each open document, which may differ from disk
+    documents: RefCell<HashMap<Uri, String>>,
 }
 
 pub struct ServerHandle {
@@ -53,6 +60,88 @@ fn uri_to_path(uri: &Uri) -> PathBuf {
     }
 }
 
+fn path_to_uri(path: &Path) -> Option<Uri> {
+    let path = path.display().to_string().replace('\\', "/");
+    if path.starts_with('/') {
+        Uri::from_str(&format!("file://{}", path)).ok()
+    } else {
+        Uri::from_str(&format!("file:///{}", path)).ok()
+    }
+}
+
+/// The byte offset in 'line' of an LSP character offset, which counts UTF-16 code units.
+fn utf16_to_byte_offset(line: &str, character: usize) -> usize {
+    let mut utf16_offset = 0;
+    for (index, c) in line.char_indices() {
+        if utf16_offset >= character {
+            return index;
+        }
+        utf16_offset += c.len_utf16();
+    }
+    line.len()
+}
+
+/// The byte offset in 'contents' of an LSP position.
+fn position_to_byte_offset(contents: &str, position: lsp_types::Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match contents[line_start..].find('\n') {
+            Some(index) => line_start += index + 1,
+            None => return contents.len(),
+        }
+    }
+    let line = contents[line_start..]
+        .split('\n')
+        .next()
+        .unwrap_or_default();
+    line_start + utf16_to_byte_offset(line, position.character as usize)
+}
+
+/// Apply a change sent by the editor to the contents of an open document.
+fn apply_content_change(contents: &mut String, change: lsp_types::TextDocumentContentChangeEvent) {
+    match change.range {
+        Some(range) => {
+            let start = position_to_byte_offset(contents, range.start);
+            let end = position_to_byte_offset(contents, range.end).max(start);
+            contents.replace_range(start..end, &change.text);
+        }
+        None => *contents = change.text,
+    }
+}
+
+/// The LSP position just past the end of 'contents'.
+fn end_position(contents: &str) -> lsp_types::Position {
+    let last_line = contents.rsplit('\n').next().unwrap_or_default();
+    lsp_types::Position {
+        line: contents.matches('\n').count() as u32,
+        character: last_line.encode_utf16().count() as u32,
+    }
+}
+
+/// Find the quoted string in 'line' which contains the given LSP character offset.
+/// Both basic ("...") and literal ('...') TOML strings are recognized.
+fn quoted_string_at(line: &str, character: usize) -> Option<&str> {
+    let offset = utf16_to_byte_offset(line, character);
+    let mut start: Option<(char, usize)> = None;
+    for (index, c) in line.char_indices() {
+        if c != '"' && c != '\'' {
+            continue;
+        }
+        match start {
+            None => start = Some((c, index + 1)),
+            Some((quote, begin)) if quote == c => {
+                if begin <= offset && offset <= index {
+                    return Some(&line[begin..index]);
+                }
+                start = None;
+            }
+            // A quote of the other kind is part of the string
+            Some(_) => {}
+        }
+    }
+    None
+}
+
 impl From<Severity> for lsp_types::DiagnosticSeverity {
     fn from(severity: Severity) -> Self {
         match severity {
@@ -91,6 +180,7 @@ impl LSPServer {
         Self {
             project_root,
             project_config,
+            documents: Default::default(),
         }
     }
 
@@ -142,6 +232,7 @@ impl LSPServer {
                     },
                 },
             )),
+            rename_provider: Some(lsp_types::OneOf::Left(true)),
             text_document_sync: Some(lsp_types::TextDocumentSyncCapability::Options(
                 lsp_types::TextDocumentSyncOptions {
                     open_close: Some(true),
@@ -168,7 +259,11 @@ impl LSPServer {
             &self.project_config,
             true,
             true,
-            self.project_config.exclude.clone(),
+            [
+                self.project_config.exclude.clone(),
+                self.project_config.source_root_exclude_paths(),
+            ]
+            .concat(),
         )?;
         let diagnostics = check_result
             .into_iter()
@@ -188,6 +283,80 @@ impl LSPServer {
         })
     }
 
+    /// Resolve a module reference as written in the given config file to a full module path.
+    fn resolve_module_reference(&self, config_path: &Path, reference: &str) -> String {
+        self.project_config
+            .domains
+            .iter()
+            .find(|domain| domain.location.path == config_path)
+            .map(|domain| domain.resolve_module_reference(reference))
+            .unwrap_or_else(|| reference.to_string())
+    }
+
+    fn rename_module(
+        &self,
+        params: lsp_types::RenameParams,
+    ) -> Result<lsp_types::WorkspaceEdit, String> {
+        let position = params.text_document_position.position;
+        let uri = &params.text_document_position.text_document.uri;
+        let config_path = uri_to_path(uri);
+        // Unsaved changes in the editor take precedence over the files on disk
+        let open_documents: HashMap<PathBuf, String> = self
+            .documents
+            .borrow()
+            .iter()
+            .map(|(uri, contents)| (uri_to_path(uri), contents.clone()))
+            .collect();
+        let contents = match open_documents.get(&config_path) {
+            Some(contents) => contents.clone(),
+            None => std::fs::read_to_string(&config_path).map_err(|e| e.to_string())?,
+        };
+        let reference = contents
+            .lines()
+            .nth(position.line as usize)
+            .and_then(|line| quoted_string_at(line, position.character as usize))
+            .ok_or("No module path found at cursor")?;
+
+        let path = self.resolve_module_reference(&config_path, reference);
+        let new_path = self.resolve_module_reference(&config_path, &params.new_name);
+        if !self
+            .project_config
+            .all_modules()
+            .any(|module| module.path == path)
+        {
+            return Err(format!("'{}' is not a configured module", path));
+        }
+
+        let changed_files = self
+            .project_config
+            .preview_module_rename(&path, &new_path, &open_documents)
+            .map_err(|e| e.to_string())?;
+        let mut changes = HashMap::new();
+        for (file_path, new_contents) in changed_files {
+            let uri = path_to_uri(&file_path).ok_or("Failed to convert path to URI")?;
+            let old_contents = match open_documents.get(&file_path) {
+                Some(contents) => contents.clone(),
+                None => std::fs::read_to_string(&file_path).map_err(|e| e.to_string())?,
+            };
+            // Replace the entire document
+            let edit = lsp_types::TextEdit {
+                range: lsp_types::Range {
+                    start: lsp_types::Position {
+                        line: 0,
+                        character: 0,
+                    },
+                    end: end_position(&old_contents),
+                },
+                new_text: new_contents,
+            };
+            changes.insert(uri, vec![edit]);
+        }
+        Ok(lsp_types::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        })
+    }
+
     fn publish_diagnostics(
         &self,
         connection: &Connection,
@@ -230,6 +399,15 @@ impl LSPServer {
                                             let diagnostics = self.lint_for_diagnostics(data.text_document.uri.clone())?;
                                             self.publish_diagnostics(&connection, &diagnostics)?;
                                         }
+                                        lsp_types::request::Rename::METHOD => {
+                                            eprintln!("Received Rename request");
+                                            let (id, data): (RequestId, lsp_types::RenameParams) = req.extract(lsp_types::request::Rename::METHOD).unwrap();
+                                            let response = match self.rename_module(data) {
+                                                Ok(edit) => Response::new_ok(id, edit),
+                                                Err(message) => Response::new_err(id, ErrorCode::InvalidParams as i32, message),
+                                            };
+                                            connection.sender.send(Message::Response(response))?;
+                                        }
                                         _ => {
                                             eprintln!("[Ignored] Received request: {:?}", req.method);
                                         }
@@ -244,9 +422,19 @@ impl LSPServer {
                                         lsp_types::notification::DidOpenTextDocument::METHOD => {
                                             eprintln!("Received DidOpen notification");
                                             let data: lsp_types::DidOpenTextDocumentParams = notification.extract(lsp_types::notification::DidOpenTextDocument::METHOD).unwrap();
+                                            self.documents.borrow_mut().insert(data.text_document.uri.clone(), data.text_document.text);
                                             let diagnostics = self.lint_for_diagnostics(data.text_document.uri.clone())?;
                                             self.publish_diagnostics(&connection, &diagnostics)?;
                                         }
+                                        lsp_types::notification::DidChangeTextDocument::METHOD => {
+                                            eprintln!("Received DidChange notification");
+                                            let data: lsp_types::DidChangeTextDocumentParams = notification.extract(lsp_types::notification::DidChangeTextDocument::METHOD).unwrap();
+                                            if let Some(contents) = self.documents.borrow_mut().get_mut(&data.text_document.uri) {
+                                                for change in data.content_changes {
+                                                    apply_content_change(contents, change);
+                                                }
+                                            }
+                                        }
                                         lsp_types::notification::DidSaveTextDocument::METHOD => {
                                             eprintln!("Received DidSave notification");
                                             let data: lsp_types::DidSaveTextDocumentParams = notification.extract(lsp_types::notification::DidSaveTextDocument::METHOD).unwrap();
@@ -256,6 +444,7 @@ impl LSPServer {
                                         lsp_types::notification::DidCloseTextDocument::METHOD => {
                                             eprintln!("Received DidClose notification");
                                             let data: lsp_types::DidCloseTextDocumentParams = notification.extract(lsp_types::notification::DidCloseTextDocument::METHOD).unwrap();
+                                            self.documents.borrow_mut().remove(&data.text_document.uri);
                                             let diagnostics = lsp_types::PublishDiagnosticsParams {
                                                 uri: data.text_document.uri.clone(),
                                                 diagnostics: vec![],
@@ -286,3 +475,66 @@ impl LSPServer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoted_string_at_utf16_character() {
+        // '🦀' is one character, but two UTF-16 code units and four bytes
+        let line = "depends_on = [\"🦀\", \"billing\"]";
+        assert_eq!(quoted_string_at(line, 15), Some("🦀"));
+        assert_eq!(quoted_string_at(line, 27), Some("billing"));
+        assert_eq!(quoted_string_at(line, 29), None);
+    }
+
+    #[test]
+    fn test_quoted_string_at_literal_string() {
+        let line = "depends_on = ['core', \"it's.here\"]";
+        assert_eq!(quoted_string_at(line, 16), Some("core"));
+        assert_eq!(quoted_string_at(line, 26), Some("it's.here"));
+    }
+
+    #[test]
+    fn test_apply_content_change() {
+        let mut contents = "path = \"a\"\ndepends_on = [\"🦀\", \"b\"]\n".to_string();
+        let change = |start: (u32, u32), end: (u32, u32), text: &str| {
+            lsp_types::TextDocumentContentChangeEvent {
+                range: Some(lsp_types::Range::new(
+                    lsp_types::Position::new(start.0, start.1),
+                    lsp_types::Position::new(end.0, end.1),
+                )),
+                range_length: None,
+                text: text.to_string(),
+            }
+        };
+        // '🦀' spans UTF-16 characters 15 and 16 on the second line
+        apply_content_change(&mut contents, change((1, 21), (1, 22), "core"));
+        apply_content_change(&mut contents, change((0, 8), (0, 9), "app"));
+        assert_eq!(
+            contents,
+            "path = \"app\"\ndepends_on = [\"🦀\", \"core\"]\n"
+        );
+
+        apply_content_change(
+            &mut contents,
+            lsp_types::TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "replaced".to_string(),
+            },
+        );
+        assert_eq!(contents, "replaced");
+    }
+
+    #[test]
+    fn test_end_position() {
+        assert_eq!(end_position(""), lsp_types::Position::new(0, 0));
+        assert_eq!(end_position("a = 1\n"), lsp_types::Position::new(1, 0));
+        assert_eq!(
+            end_position("a = 1\nb = \"é🦀\""),
+            lsp_types::Position::new(1, 9)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, HashMap};
+
+use petgraph::algo::{is_cyclic_directed, kosaraju_scc};
+use petgraph::graphmap::DiGraphMap;
+
+use crate::config::ModuleConfig;
+
+/// Every group of modules whose declared dependencies form a cycle.
+/// These are the strongly connected components of the dependency graph with more than one module.
+/// Each group is sorted by module path, and the groups are sorted by their first module.
+pub fn find_dependency_cycles(modules: &[ModuleConfig]) -> Vec<Vec<String>> {
+    let mut graph = DiGraphMap::new();
+    for module in modules {
+        graph.add_node(module.path.as_str());
+    }
+    for module in modules {
+        for dependency in module.dependencies_iter() {
+            graph.add_edge(module.path.as_str(), dependency.path.as_str(), ());
+        }
+    }
+
+    let mut cycles: Vec<Vec<String>> = kosaraju_scc(&graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .map(|scc| {
+            let mut modules: Vec<String> = scc.into_iter().map(str::to_string).collect();
+            modules.sort();
+            modules
+        })
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+fn graph_from_edges<'a>(edges: &[(&'a str, &'a str)]) -> DiGraphMap<&'a str, ()> {
+    let mut graph = DiGraphMap::new();
+    for &(source, target) in edges {
+        graph.add_edge(source, target, ());
+    }
+    graph
+}
+
+/// Suggest a set of dependency edges to remove which breaks every cycle among 'modules'.
+///
+/// 'import_counts' holds the number of imports along each edge; declared dependencies without
+/// imports have a count of zero. Edges with the fewest imports are removed first, since they are
+/// the cheapest to cut. Afterwards, removed edges are restored where possible (most imports first),
+/// so that no suggested edge is redundant.
+///
+/// Returns the suggested edges with their import counts, ordered from fewest to most imports.
+pub fn suggest_cycle_breaks(
+    modules: &[String],
+    import_counts: &BTreeMap<(String, String), usize>,
+) -> Vec<(String, String, usize)> {
+    let mut candidates: Vec<(&str, &str, usize)> = import_counts
+        .iter()
+        .filter(|((source, target), _)| modules.contains(source) && modules.contains(target))
+        .map(|((source, target), count)| (source.as_str(), target.as_str(), *count))
+        .collect();
+    candidates.sort_by_key(|&(source, target, count)| (count, source, target));
+
+    let mut remaining: Vec<(&str, &str)> = candidates
+        .iter()
+        .map(|&(source, target, _)| (source, target))
+        .collect();
+    let mut removed: Vec<(&str, &str, usize)> = Vec::new();
+    loop {
+        let graph = graph_from_edges(&remaining);
+        if !is_cyclic_directed(&graph) {
+            break;
+        }
+        // An edge lies on a cycle when both of its ends are in the same component
+        let component: HashMap<&str, usize> = kosaraju_scc(&graph)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, scc)| scc.into_iter().map(move |module| (module, index)))
+            .collect();
+        let Some(&edge) = candidates.iter().find(|&&(source, target, _)| {
+            remaining.contains(&(source, target)) && component[source] == component[target]
+        }) else {
+            break;
+        };
+        remaining.retain(|&remaining_edge| remaining_edge != (edge.0, edge.1));
+        removed.push(edge);
+    }
+
+    removed.sort_by_key(|&(source, target, count)| (std::cmp::Reverse(count), source, target));
+    let mut suggested = Vec::new();
+    for (source, target, count) in removed {
+        remaining.push((source, target));
+        if is_cyclic_directed(&graph_from_edges(&remaining)) {
+            remaining.pop();
+            suggested.push((source.to_string(), target.to_string(), count));
+        }
+    }
+    suggested.sort_by(|a, b| (a.2, &a.0, &a.1).cmp(&(b.2, &b.0, &b.1)));
+    suggested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DependencyConfig;
+
+    fn module(path: &str, depends_on: &[&str]) -> ModuleConfig {
+        ModuleConfig {
+            path: path.to_string(),
+            depends_on: Some(
+                depends_on
+                    .iter()
+                    .map(|dependency| DependencyConfig::from_path(dependency.to_string()))
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn import_counts(edges: &[(&str, &str, usize)]) -> BTreeMap<(String, String), usize> {
+        edges
+            .iter()
+            .map(|&(source, target, count)| ((source.to_string(), target.to_string()), count))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_every_cycle() {
+        let modules = vec![
+            module("a", &["b"]),
+            module("b", &["a"]),
+            module("c", &["d"]),
+            module("d", &["e"]),
+            module("e", &["c", "a"]),
+            module("f", &["a"]),
+        ];
+        assert_eq!(
+            find_dependency_cycles(&modules),
+            vec![vec!["a", "b"], vec!["c", "d", "e"]]
+        );
+    }
+
+    #[test]
+    fn test_suggest_cheapest_break() {
+        let modules = ["a", "b", "c"].map(String::from).to_vec();
+        let counts = import_counts(&[("a", "b", 5), ("b", "c", 1), ("c", "a", 3)]);
+        assert_eq!(
+            suggest_cycle_breaks(&modules, &counts),
+            vec![("b".to_string(), "c".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_suggested_breaks_are_minimal() {
+        // Every pair of modules depends on each other, so several edges must be cut
+        let modules = ["a", "b", "c"].map(String::from).to_vec();
+        let counts = import_counts(&[
+            ("a", "b", 4),
+            ("b", "a", 1),
+            ("a", "c", 2),
+            ("c", "b", 6),
+            ("b", "c", 9),
+            ("c", "a", 8),
+        ]);
+        let breaks = suggest_cycle_breaks(&modules, &counts);
+
+        let mut remaining = counts.clone();
+        for (source, target, _) in &breaks {
+            remaining.remove(&(source.clone(), target.clone()));
+        }
+        let edges: Vec<(&str, &str)> = remaining
+            .keys()
+            .map(|(source, target)| (source.as_str(), target.as_str()))
+            .collect();
+        assert!(!is_cyclic_directed(&graph_from_edges(&edges)));
+        // Restoring any single suggested edge brings back a cycle
+        for (source, target, _) in &breaks {
+            let mut restored = edges.clone();
+            restored.push((source.as_str(), target.as_str()));
+            assert!(is_cyclic_directed(&graph_from_edges(&restored)));
+        }
+        assert!(breaks.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    }
+}
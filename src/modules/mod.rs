@@ -1,3 +1,4 @@
+pub mod cycles;
 pub mod error;
 pub mod parsing;
 pub mod tree;
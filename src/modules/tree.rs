@@ -147,6 +147,28 @@ impl ModuleTree {
         }
     }
 
+    /// The nearest modules beneath 'path', skipping intermediate nodes which are not modules.
+    pub fn child_modules(&self, path: &str) -> Vec<Arc<ModuleNode>> {
+        let mut node = Arc::clone(&self.root);
+        for part in split_module_path(path) {
+            match node.children.get(part) {
+                Some(child) => node = Arc::clone(child),
+                None => return vec![],
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut stack: VecDeque<Arc<ModuleNode>> = node.children.values().cloned().collect();
+        while let Some(child) = stack.pop_front() {
+            if child.is_end_of_path {
+                result.push(child);
+            } else {
+                stack.extend(child.children.values().cloned());
+            }
+        }
+        result
+    }
+
     pub fn iter(&self) -> ModuleTreeIterator {
         ModuleTreeIterator::new(self)
     }
@@ -236,6 +258,27 @@ mod tests {
         assert!(module_tree.get("domain_one").is_some());
     }
 
+    #[rstest]
+    fn test_child_modules(module_tree: ModuleTree) {
+        let children: HashSet<String> = module_tree
+            .child_modules(".")
+            .iter()
+            .map(|node| node.full_path.clone())
+            .collect();
+        assert_eq!(
+            children,
+            HashSet::from(["domain_one", "domain_two", "domain_three"].map(String::from))
+        );
+
+        let children: Vec<String> = module_tree
+            .child_modules("domain_one")
+            .iter()
+            .map(|node| node.full_path.clone())
+            .collect();
+        assert_eq!(children, ["domain_one.subdomain"]);
+        assert!(module_tree.child_modules("fakepath").is_empty());
+    }
+
     #[rstest]
     fn test_insert_empty_path(test_config: ModuleConfig) {
         let mut tree = ModuleTree::new();
@@ -1,5 +1,7 @@
 use std::{
     cmp::Ordering,
+    collections::HashMap,
+    ops::Range,
     path::{Path, PathBuf},
 };
 
@@ -8,14 +10,23 @@ use rayon::prelude::*;
 use crate::{
     colors::BColors,
     config::{
-        root_module::ROOT_MODULE_SENTINEL_TAG, ConfigLocation, DomainConfig, InterfaceConfig,
-        InterfaceDataTypes, LocatedDomainConfig, ProjectConfig,
+        domain::{DomainRootConfig, DOMAIN_CONFIG_FILE},
+        project::{AUTO_DOMAIN_PREFIX, CONFIG_VERSION},
+        root_module::ROOT_MODULE_SENTINEL_TAG,
+        ConfigLocation, ConfigSpan, DomainConfig, InterfaceConfig, InterfaceDataTypes,
+        LocatedDomainConfig, ModuleConfig, ProjectConfig, SourceRootConfig,
     },
-    filesystem::{read_file_content, walk_domain_config_files},
+    exclusion::set_excluded_paths,
+    external::parsing::extract_declared_source_roots,
+    filesystem::{
+        read_file_content, to_forward_slashes, walk_domain_config_files, walk_pyprojects,
+    },
+    processors::reference_pattern::get_reference_patterns,
     python::parsing::parse_interface_members,
 };
 
 use super::error;
+use super::migrations::needs_migration;
 
 pub type Result<T> = std::result::Result<T, error::ParsingError>;
 
@@ -39,7 +50,7 @@ pub fn dump_project_config_to_toml(
     }
 
     config.exclude.sort();
-    config.source_roots.sort();
+    config.source_roots.sort_by(|a, b| a.path.cmp(&b.path));
 
     toml::to_string(&config)
 }
@@ -60,7 +71,7 @@ fn migrate_strict_mode_to_interfaces(filepath: &Path, config: &mut ProjectConfig
     let abs_source_roots: Vec<PathBuf> = config
         .source_roots
         .iter()
-        .map(|r| filepath.parent().unwrap().join(r))
+        .map(|r| filepath.parent().unwrap().join(&r.path))
         .collect();
     for module in &mut config.modules {
         if module.strict {
@@ -70,6 +81,7 @@ fn migrate_strict_mode_to_interfaces(filepath: &Path, config: &mut ProjectConfig
                 expose: interface_members,
                 from_modules: vec![module.path.clone()],
                 data_types: InterfaceDataTypes::All,
+                ..Default::default()
             });
         }
     }
@@ -77,8 +89,8 @@ fn migrate_strict_mode_to_interfaces(filepath: &Path, config: &mut ProjectConfig
     true
 }
 
-const DEPRECATED_REGEX_EXCLUDE_PATHS: [&str; 2] = [".*__pycache__", ".*egg-info"];
-const REPLACEMENT_GLOB_EXCLUDE_PATHS: [&str; 2] = ["**/*__pycache__", "**/*egg-info"];
+pub(super) const DEPRECATED_REGEX_EXCLUDE_PATHS: [&str; 2] = [".*__pycache__", ".*egg-info"];
+pub(super) const REPLACEMENT_GLOB_EXCLUDE_PATHS: [&str; 2] = ["**/*__pycache__", "**/*egg-info"];
 const EXPECTED_EXCLUDE_PATHS: [&str; 5] = [
     "tests",
     "docs",
@@ -140,27 +152,212 @@ fn migrate_deprecated_regex_exclude(config: &mut ProjectConfig) -> bool {
     did_migrate
 }
 
+// Module and interface entries may be '[[modules]]' tables or inline tables in an array
+fn config_entries<'a>(doc: &'a toml_edit::Table, key: &str) -> Vec<&'a dyn toml_edit::TableLike> {
+    match doc.get(key) {
+        Some(toml_edit::Item::ArrayOfTables(tables)) => tables
+            .iter()
+            .map(|table| table as &dyn toml_edit::TableLike)
+            .collect(),
+        Some(item) => item
+            .as_array()
+            .into_iter()
+            .flat_map(|array| array.iter())
+            .filter_map(|value| value.as_inline_table())
+            .map(|table| table as &dyn toml_edit::TableLike)
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Record where each module and interface is configured within a config file.
+/// Modules are located by their 'path' (or their entry in 'paths'),
+/// and interfaces by their 'expose' list.
+fn record_config_spans(
+    filepath: &Path,
+    content: &str,
+    modules: &mut [ModuleConfig],
+    root: Option<&mut DomainRootConfig>,
+    interfaces: &mut [InterfaceConfig],
+) {
+    let Ok(doc) = toml_edit::ImDocument::parse(content) else {
+        return;
+    };
+    let doc = doc.as_table();
+
+    let mut module_spans: HashMap<&str, Range<usize>> = HashMap::new();
+    for entry in config_entries(doc, "modules") {
+        let paths = entry
+            .get("path")
+            .and_then(|item| item.as_value())
+            .into_iter()
+            .chain(
+                entry
+                    .get("paths")
+                    .and_then(|item| item.as_array())
+                    .into_iter()
+                    .flat_map(|paths| paths.iter()),
+            );
+        for path in paths {
+            if let (Some(module_path), Some(span)) = (path.as_str(), path.span()) {
+                module_spans.entry(module_path).or_insert(span);
+            }
+        }
+    }
+    for module in modules {
+        if let Some(span) = module_spans.get(module.path.as_str()) {
+            module.span = ConfigSpan::new(filepath, content, span.clone());
+        }
+    }
+
+    if let (Some(root), Some(span)) = (root, doc.get("root").and_then(|item| item.span())) {
+        root.span = ConfigSpan::new(filepath, content, span);
+    }
+
+    // Interfaces are deserialized in the order they are written
+    for (interface, entry) in interfaces.iter_mut().zip(config_entries(doc, "interfaces")) {
+        if let Some(span) = entry.get("expose").and_then(|item| item.span()) {
+            interface.span = ConfigSpan::new(filepath, content, span);
+        }
+    }
+}
+
 pub fn parse_domain_config<P: AsRef<Path>>(
     source_roots: &[PathBuf],
     filepath: P,
 ) -> Result<LocatedDomainConfig> {
     let content = read_file_content(filepath.as_ref())?;
-    let config: DomainConfig = toml::from_str(&content)?;
+    let mut config: DomainConfig = toml::from_str(&content)?;
+    record_config_spans(
+        filepath.as_ref(),
+        &content,
+        &mut config.modules,
+        config.root.as_mut(),
+        &mut config.interfaces,
+    );
     let location = ConfigLocation::new(source_roots, filepath.as_ref())?;
     Ok(config.with_location(location))
 }
 
+/// Find directories matching the project's domain patterns (e.g. 'auto:src/domains/*'),
+/// treating each one without its own 'tach.domain.toml' as a domain with an implicit config.
+fn discover_domains(
+    patterns: &[String],
+    project_root: &Path,
+    source_roots: &[PathBuf],
+) -> Result<Vec<LocatedDomainConfig>> {
+    let mut domain_dirs = Vec::new();
+    for pattern in patterns {
+        let glob_pattern = pattern.strip_prefix(AUTO_DOMAIN_PREFIX).ok_or_else(|| {
+            error::ParsingError::DomainPattern(format!(
+                "'{}' must start with '{}'",
+                pattern, AUTO_DOMAIN_PREFIX
+            ))
+        })?;
+        let paths = glob::glob(&project_root.join(glob_pattern).to_string_lossy())
+            .map_err(|e| error::ParsingError::DomainPattern(format!("'{}': {}", pattern, e)))?;
+        domain_dirs.extend(
+            paths
+                .filter_map(|path| path.ok())
+                .filter(|path| path.is_dir()),
+        );
+    }
+    domain_dirs.sort();
+    domain_dirs.dedup();
+
+    domain_dirs
+        .into_iter()
+        .map(|domain_dir| domain_dir.join(DOMAIN_CONFIG_FILE))
+        // Directories with their own domain config have already been loaded
+        .filter(|config_path| !config_path.exists())
+        .map(|config_path| {
+            let location = ConfigLocation::new(source_roots, &config_path)?;
+            Ok(DomainConfig::implicit().with_location(location))
+        })
+        .collect()
+}
+
+/// Derive source roots from the package declarations in each pyproject.toml within the project.
+/// Returns an empty list if no packages are declared.
+fn derive_source_roots(config: &ProjectConfig, project_root: &Path) -> Vec<SourceRootConfig> {
+    let exclude_paths: Vec<PathBuf> = config.exclude.iter().map(PathBuf::from).collect();
+    // Excluded paths (such as virtual environments) should not be searched for pyproject.toml files
+    let Ok(_excluded_paths) = set_excluded_paths(
+        project_root,
+        &exclude_paths,
+        &config.include,
+        config.use_regex_matching,
+    ) else {
+        return vec![];
+    };
+
+    let mut source_roots: Vec<PathBuf> = walk_pyprojects(&project_root.to_string_lossy())
+        .filter_map(|pyproject| {
+            let toml_value: toml::Value =
+                toml::from_str(&read_file_content(&pyproject).ok()?).ok()?;
+            Some(extract_declared_source_roots(
+                &toml_value,
+                pyproject.parent()?,
+            ))
+        })
+        .flatten()
+        .filter_map(|root| {
+            let relative_root = root.strip_prefix(project_root).ok()?;
+            Some(if relative_root.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                PathBuf::from(to_forward_slashes(relative_root))
+            })
+        })
+        .collect();
+    source_roots.sort();
+    source_roots.dedup();
+    source_roots
+        .into_iter()
+        .map(SourceRootConfig::new)
+        .collect()
+}
+
 pub fn parse_project_config<P: AsRef<Path>>(filepath: P) -> Result<(ProjectConfig, bool)> {
     let content = read_file_content(filepath.as_ref())?;
-    let mut config: ProjectConfig = toml::from_str(&content)?;
+    let root_dir = filepath.as_ref().parent().unwrap();
+    let mut config: ProjectConfig = toml::from_str(&content).map_err(|err| {
+        if needs_migration(&content, root_dir) {
+            error::ParsingError::UpgradeRequired(err)
+        } else {
+            error::ParsingError::TomlParse(err)
+        }
+    })?;
+    if let Some(version) = config.version.filter(|&version| version > CONFIG_VERSION) {
+        return Err(error::ParsingError::UnsupportedVersion(version.into()));
+    }
+    get_reference_patterns(&config.plugins.references)?;
+    record_config_spans(
+        filepath.as_ref(),
+        &content,
+        &mut config.modules,
+        None,
+        &mut config.interfaces,
+    );
     config.set_location(filepath.as_ref().to_path_buf());
     let did_migrate = migrate_strict_mode_to_interfaces(filepath.as_ref(), &mut config)
         || migrate_deprecated_regex_exclude(&mut config);
-    let root_dir = filepath.as_ref().parent().unwrap();
+    // Without explicit source roots, prefer those declared by the project's packages over '.'
+    if !toml::from_str::<toml::Table>(&content)?.contains_key("source_roots") {
+        let derived_source_roots = derive_source_roots(&config, root_dir);
+        if !derived_source_roots.is_empty() {
+            config.source_roots = derived_source_roots;
+        }
+    }
     let mut domain_configs = walk_domain_config_files(root_dir.as_os_str().to_str().unwrap())
         .par_bridge()
         .map(|filepath| parse_domain_config(&config.prepend_roots(root_dir), filepath))
         .collect::<Result<Vec<_>>>()?;
+    domain_configs.extend(discover_domains(
+        &config.domain_patterns,
+        root_dir,
+        &config.prepend_roots(root_dir),
+    )?);
     domain_configs.drain(..).for_each(|domain| {
         config.add_domain(domain);
     });
@@ -248,6 +445,86 @@ mod tests {
         assert!(config.forbid_circular_dependencies);
     }
 
+    #[rstest]
+    fn test_module_config_location(example_dir: PathBuf) {
+        let (config, _) = parse_project_config(example_dir.join("valid/tach.toml")).unwrap();
+
+        assert_eq!(
+            config.module_config_location("domain_two"),
+            Some((example_dir.join("valid/tach.toml"), Some(23)))
+        );
+        assert_eq!(config.module_config_location("unknown"), None);
+    }
+
+    #[test]
+    fn test_module_config_location_uses_entry_spans() {
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let config_path = project.path().join("tach.toml");
+        // 'core' is referenced before it is configured, and shares a prefix with 'core.models'
+        std::fs::write(
+            &config_path,
+            concat!(
+                "[[modules]]\n",
+                "path = \"app\"\n",
+                "depends_on = [\"core\", \"core.models\"]\n",
+                "\n",
+                "[[modules]]\n",
+                "path = \"core.models\"\n",
+                "depends_on = []\n",
+                "\n",
+                "[[modules]]\n",
+                "paths = [\n",
+                "    \"utils\",\n",
+                "    \"core\",\n",
+                "]\n",
+                "depends_on = []\n",
+                "\n",
+                "[[interfaces]]\n",
+                "expose = [\"api\"]\n",
+                "from = [\"core\"]\n",
+            ),
+        )
+        .unwrap();
+
+        let (config, _) = parse_project_config(&config_path).unwrap();
+
+        let line_number = |module_path: &str| {
+            config
+                .module_config_location(module_path)
+                .and_then(|(_, line_number)| line_number)
+        };
+        assert_eq!(line_number("app"), Some(2));
+        assert_eq!(line_number("core.models"), Some(6));
+        assert_eq!(line_number("utils"), Some(11));
+        assert_eq!(line_number("core"), Some(12));
+        assert_eq!(
+            config.interfaces[0].span.file_path(),
+            Some(config_path.as_path())
+        );
+        assert_eq!(config.interfaces[0].span.line_number(), Some(17));
+    }
+
+    #[rstest]
+    fn test_project_config_json_round_trip(example_dir: PathBuf) {
+        let (config, _) = parse_project_config(example_dir.join("valid/tach.toml")).unwrap();
+        let round_tripped = ProjectConfig::deserialize_json(&config.serialize_json()).unwrap();
+        assert_eq!(round_tripped.modules, config.modules);
+        assert_eq!(round_tripped.interfaces, config.interfaces);
+        assert!(ProjectConfig::deserialize_json("{\"unknown\": 1}").is_err());
+    }
+
+    #[rstest]
+    fn test_project_config_state_round_trip(example_dir: PathBuf) {
+        let (config, _) =
+            parse_project_config(example_dir.join("distributed_config/tach.toml")).unwrap();
+        let mut restored = ProjectConfig::default();
+        restored.__setstate__(&config.__getstate__()).unwrap();
+        assert_eq!(restored, config);
+    }
+
     #[rstest]
     fn test_parse_domain_config(example_dir: PathBuf) {
         let source_roots = vec![example_dir.join("distributed_config")];
@@ -267,6 +544,107 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_domain_for_module(example_dir: PathBuf) {
+        let (config, _) =
+            parse_project_config(example_dir.join("distributed_config/tach.toml")).unwrap();
+
+        let domain_path = |module_path: &str| {
+            config
+                .domain_for_module(module_path)
+                .map(|domain| domain.location.mod_path.as_str())
+        };
+        assert_eq!(
+            domain_path("project.module_one"),
+            Some("project.module_one")
+        );
+        assert_eq!(
+            domain_path("project.module_two.service"),
+            Some("project.module_two")
+        );
+        assert_eq!(domain_path("project.module_two_extra"), None);
+        assert_eq!(domain_path("project.top_level"), None);
+
+        // Domains without 'depends_on_domains' may depend on any domain
+        let domain = config.domain_for_module("project.module_one").unwrap();
+        assert!(domain.allows_domain_dependency("project.module_two"));
+    }
+
+    #[test]
+    fn test_domain_public_modules() {
+        let location = ConfigLocation {
+            path: PathBuf::from("billing/tach.domain.toml"),
+            mod_path: "billing".to_string(),
+        };
+        let implicit = DomainConfig::default().with_location(location.clone());
+        assert!(implicit.is_public_module("billing"));
+        assert!(!implicit.is_public_module("billing.internal"));
+
+        let config: DomainConfig = toml::from_str(r#"public = ["<domain_root>", "api"]"#).unwrap();
+        let domain = config.with_location(location);
+        assert!(domain.is_public_module("billing"));
+        assert!(domain.is_public_module("billing.api"));
+        assert!(!domain.is_public_module("billing.internal"));
+    }
+
+    #[test]
+    fn test_domain_layers() {
+        let location = ConfigLocation {
+            path: PathBuf::from("billing/tach.domain.toml"),
+            mod_path: "billing".to_string(),
+        };
+        let config: DomainConfig = toml::from_str(
+            r#"
+layers = ["api", "models"]
+
+[root]
+layer = "product"
+
+[[modules]]
+path = "api"
+layer = "api"
+"#,
+        )
+        .unwrap();
+        let domain = config.with_location(location);
+        assert!(domain.has_layers());
+        assert_eq!(domain.root_layer().map(String::as_str), Some("product"));
+        assert_eq!(
+            domain
+                .modules()
+                .find(|m| m.path == "billing.api")
+                .and_then(|m| m.layer.as_deref()),
+            Some("api")
+        );
+    }
+
+    #[rstest]
+    fn test_discover_domains(example_dir: PathBuf) {
+        let project_root = example_dir.join("valid");
+        let source_roots = vec![project_root.clone()];
+        let domains = discover_domains(
+            &["auto:domain_*".to_string(), "auto:domain_t*".to_string()],
+            &project_root,
+            &source_roots,
+        )
+        .unwrap();
+
+        let domain_paths: Vec<_> = domains
+            .iter()
+            .map(|domain| domain.location.mod_path.as_str())
+            .collect();
+        assert_eq!(domain_paths, ["domain_one", "domain_three", "domain_two"]);
+        assert_eq!(
+            domains[0]
+                .modules()
+                .map(|m| m.path.as_str())
+                .collect::<Vec<_>>(),
+            ["domain_one"]
+        );
+
+        assert!(discover_domains(&["domain_*".to_string()], &project_root, &source_roots).is_err());
+    }
+
     #[rstest]
     fn test_parse_nested_project_config(example_dir: PathBuf) {
         let result = parse_project_config(example_dir.join("distributed_config/tach.toml"));
@@ -315,4 +693,186 @@ mod tests {
             [].iter().collect::<HashSet<_>>()
         );
     }
+
+    #[test]
+    fn test_source_root_settings() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+include_string_imports = true
+source_roots = [
+    "app",
+    { path = "generated", exclude = ["**/migrations"], include_string_imports = false },
+]
+"#,
+        )
+        .unwrap();
+        let project_root = Path::new("/project");
+
+        assert!(config.source_roots[0].is_path_only());
+        assert!(config.include_string_imports_in(project_root, &project_root.join("app")));
+        assert!(!config.include_string_imports_in(project_root, &project_root.join("generated")));
+        assert_eq!(
+            config.source_root_exclude_paths(),
+            vec!["glob:generated/**/migrations".to_string()]
+        );
+
+        // Source roots without settings are written back as plain paths
+        let serialized: toml::Value = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(serialized["source_roots"][0].as_str(), Some("app"));
+        assert_eq!(
+            serialized["source_roots"][1]["path"].as_str(),
+            Some("generated")
+        );
+    }
+
+    #[test]
+    fn test_source_roots_with_backslashes() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+source_roots = ["libs\\core\\src", { path = "apps\\web", exclude = ["build\\**"] }]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.source_roots[0].path, PathBuf::from("libs/core/src"));
+        assert_eq!(config.source_roots[1].path, PathBuf::from("apps/web"));
+        assert_eq!(
+            config.source_root_exclude_paths(),
+            vec!["glob:apps/web/build\\**".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_generated_code_markers() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+[generated_code]
+markers = ["@generated", "Generated by the protocol buffer compiler"]
+max_lines = 2
+"#,
+        )
+        .unwrap();
+        let generated_code = &config.generated_code;
+
+        assert!(generated_code.is_generated("# @generated\nimport os\n"));
+        assert!(generated_code.is_generated(
+            "# -*- coding: utf-8 -*-\n# Generated by the protocol buffer compiler.  DO NOT EDIT!\n"
+        ));
+        // Markers past the first 'max_lines' lines are ignored
+        assert!(!generated_code.is_generated("import os\n\n# @generated\n"));
+        assert!(!ProjectConfig::default()
+            .generated_code
+            .is_generated("# @generated\n"));
+    }
+
+    #[test]
+    fn test_derive_source_roots_from_pyprojects() {
+        // Hidden directories (like the default '.tmp' prefix) are not searched
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let project_root = project.path();
+        std::fs::create_dir_all(project_root.join("services/billing/src/billing")).unwrap();
+        std::fs::create_dir_all(project_root.join("libs/utils/utils")).unwrap();
+        std::fs::write(project_root.join("tach.toml"), "modules = []\n").unwrap();
+        std::fs::write(
+            project_root.join("services/billing/pyproject.toml"),
+            "[tool.poetry]\npackages = [{ include = \"billing\", from = \"src\" }]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project_root.join("libs/utils/pyproject.toml"),
+            "[tool.setuptools]\npackages = [\"utils\"]\n",
+        )
+        .unwrap();
+
+        let (config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        assert_eq!(
+            config.source_roots,
+            vec![
+                SourceRootConfig::new("libs/utils"),
+                SourceRootConfig::new("services/billing/src"),
+            ]
+        );
+
+        // Explicit source roots are never replaced
+        std::fs::write(project_root.join("tach.toml"), "source_roots = [\".\"]\n").unwrap();
+        let (config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        assert_eq!(config.source_roots, vec![SourceRootConfig::new(".")]);
+    }
+
+    #[test]
+    fn test_invalid_reference_pattern() {
+        let project = tempfile::TempDir::new().unwrap();
+        let config_path = project.path().join("tach.toml");
+        std::fs::write(&config_path, "[[plugins.references]]\nregex = \"(\"\n").unwrap();
+        assert!(matches!(
+            parse_project_config(&config_path),
+            Err(error::ParsingError::ReferencePattern(_))
+        ));
+
+        std::fs::write(&config_path, "[[plugins.references]]\nargument = 1\n").unwrap();
+        assert!(matches!(
+            parse_project_config(&config_path),
+            Err(error::ParsingError::ReferencePattern(_))
+        ));
+    }
+
+    #[test]
+    fn test_domain_lifecycle_edits() {
+        let project = tempfile::TempDir::new().unwrap();
+        let project_root = project.path();
+        std::fs::create_dir_all(project_root.join("billing/api")).unwrap();
+        std::fs::write(
+            project_root.join("tach.toml"),
+            r#"
+source_roots = ["."]
+
+[[modules]]
+path = "billing.api"
+depends_on = ["core"]
+
+[[modules]]
+path = "core"
+depends_on = []
+"#,
+        )
+        .unwrap();
+        let domain_file = project_root.join("billing").join(DOMAIN_CONFIG_FILE);
+
+        let (mut config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        config.create_domain("billing".to_string()).unwrap();
+        config.save_edits().unwrap();
+        assert!(domain_file.exists());
+
+        let (mut config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        config
+            .move_module_to_domain("billing.api".to_string(), "billing".to_string())
+            .unwrap();
+        config.save_edits().unwrap();
+        let domain_config: DomainConfig =
+            toml::from_str(&std::fs::read_to_string(&domain_file).unwrap()).unwrap();
+        assert_eq!(domain_config.modules[0].path, "api");
+        assert_eq!(
+            domain_config.modules[0].depends_on,
+            Some(vec![DependencyConfig::from_path("//core")])
+        );
+
+        let (mut config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        assert_eq!(config.modules.len(), 1);
+        assert!(config
+            .dependencies_for_module("billing.api")
+            .is_some_and(|deps| deps == &[DependencyConfig::from_path("core")]));
+        config.delete_domain("billing".to_string()).unwrap();
+        config.save_edits().unwrap();
+        assert!(!domain_file.exists());
+
+        let (config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        assert!(config.domains.is_empty());
+        assert_eq!(
+            config.module_paths().into_iter().collect::<HashSet<_>>(),
+            HashSet::from(["billing.api".to_string(), "core".to_string()])
+        );
+    }
 }
@@ -1,7 +1,9 @@
 use std::io;
 use thiserror::Error;
 
+use crate::config::project::CONFIG_VERSION;
 use crate::filesystem::FileSystemError;
+use crate::processors::reference_pattern::ReferencePatternError;
 
 #[derive(Error, Debug)]
 pub enum ParsingError {
@@ -11,8 +13,18 @@ pub enum ParsingError {
     Filesystem(#[from] FileSystemError),
     #[error("TOML parsing error: {0}")]
     TomlParse(#[from] toml::de::Error),
+    #[error("TOML parsing error: {0}")]
+    TomlEdit(#[from] toml_edit::TomlError),
+    #[error("{0}\nThe config uses keys which are no longer supported. Run 'tach upgrade' to migrate it.")]
+    UpgradeRequired(toml::de::Error),
+    #[error("Config version {0} is newer than the latest version supported by this version of Tach ({max}). Upgrade Tach to use this config.", max = CONFIG_VERSION)]
+    UnsupportedVersion(i64),
     #[error("Missing field in TOML: {0}")]
     MissingField(String),
     #[error("Module path error: {0}")]
     ModulePath(String),
+    #[error("Invalid domain pattern: {0}")]
+    DomainPattern(String),
+    #[error("Invalid reference pattern: {0}")]
+    ReferencePattern(#[from] ReferencePatternError),
 }
@@ -0,0 +1,300 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::edit::{push_table, to_table};
+use crate::config::project::{source_root_path, CONFIG_VERSION};
+use crate::config::{InterfaceConfig, InterfaceDataTypes, ProjectConfig};
+use crate::filesystem::read_file_content;
+use crate::python::parsing::parse_interface_members;
+
+use super::config::{DEPRECATED_REGEX_EXCLUDE_PATHS, REPLACEMENT_GLOB_EXCLUDE_PATHS};
+use super::error::ParsingError;
+
+pub type Result<T> = std::result::Result<T, ParsingError>;
+
+/// A migration rewrites a deprecated key or shape in place, returning whether it changed anything.
+/// Migrations detect what they rewrite, so they can run on configs of any version.
+struct Migration {
+    description: &'static str,
+    apply: fn(&mut toml_edit::DocumentMut, &Path) -> bool,
+}
+
+const MIGRATIONS: [Migration; 4] = [
+    Migration {
+        description: "Replaced 'source_root' with 'source_roots'",
+        apply: migrate_source_root,
+    },
+    Migration {
+        description: "Removed the unsupported cache backend ('disk' is the only backend)",
+        apply: migrate_cache_backend,
+    },
+    Migration {
+        description: "Replaced the default regex exclude paths with glob patterns",
+        apply: migrate_regex_excludes,
+    },
+    Migration {
+        description: "Replaced 'strict' modules with interfaces",
+        apply: migrate_strict_modules,
+    },
+];
+
+fn migrate_source_root(doc: &mut toml_edit::DocumentMut, _project_root: &Path) -> bool {
+    let Some(source_root) = doc.remove("source_root") else {
+        return false;
+    };
+    // An explicit 'source_roots' takes precedence
+    if !doc.contains_key("source_roots") {
+        let mut source_roots = toml_edit::Array::new();
+        if let Some(path) = source_root.as_str() {
+            source_roots.push(path);
+        }
+        doc["source_roots"] = toml_edit::value(source_roots);
+    }
+    true
+}
+
+fn migrate_cache_backend(doc: &mut toml_edit::DocumentMut, _project_root: &Path) -> bool {
+    let Some(cache) = doc
+        .get_mut("cache")
+        .and_then(|cache| cache.as_table_like_mut())
+    else {
+        return false;
+    };
+    let unsupported = cache
+        .get("backend")
+        .and_then(|backend| backend.as_str())
+        .is_some_and(|backend| backend != "disk");
+    if unsupported {
+        cache.remove("backend");
+    }
+    unsupported
+}
+
+fn migrate_regex_excludes(doc: &mut toml_edit::DocumentMut, _project_root: &Path) -> bool {
+    if doc
+        .get("use_regex_matching")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    {
+        return false;
+    }
+    let Some(excludes) = doc
+        .get_mut("exclude")
+        .and_then(|exclude| exclude.as_array_mut())
+    else {
+        return false;
+    };
+
+    let mut did_migrate = false;
+    for exclude in excludes.iter_mut() {
+        let replacement = exclude.as_str().and_then(|path| {
+            DEPRECATED_REGEX_EXCLUDE_PATHS
+                .iter()
+                .position(|&deprecated| deprecated == path)
+                .map(|index| REPLACEMENT_GLOB_EXCLUDE_PATHS[index])
+        });
+        if let Some(replacement) = replacement {
+            let decor = exclude.decor().clone();
+            *exclude = replacement.into();
+            *exclude.decor_mut() = decor;
+            did_migrate = true;
+        }
+    }
+    did_migrate
+}
+
+fn migrate_strict_modules(doc: &mut toml_edit::DocumentMut, project_root: &Path) -> bool {
+    let source_roots: Vec<PathBuf> = match doc.get("source_roots").and_then(|r| r.as_array()) {
+        Some(roots) => roots
+            .iter()
+            .filter_map(source_root_path)
+            .map(|root| project_root.join(root))
+            .collect(),
+        None => vec![project_root.to_path_buf()],
+    };
+    let Some(toml_edit::Item::ArrayOfTables(modules)) = doc.get_mut("modules") else {
+        return false;
+    };
+
+    let mut did_migrate = false;
+    let mut interfaces: Vec<InterfaceConfig> = vec![];
+    for module in modules.iter_mut() {
+        let Some(strict) = module.remove("strict") else {
+            continue;
+        };
+        did_migrate = true;
+        if !strict.as_bool().unwrap_or(false) {
+            continue;
+        }
+        let paths: Vec<String> = match (module.get("path"), module.get("paths")) {
+            (Some(path), _) => path.as_str().map(String::from).into_iter().collect(),
+            (None, Some(paths)) => paths
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|path| path.as_str().map(String::from))
+                .collect(),
+            (None, None) => vec![],
+        };
+        for path in paths {
+            interfaces.push(InterfaceConfig {
+                expose: parse_interface_members(&source_roots, &path).unwrap_or_default(),
+                from_modules: vec![path],
+                data_types: InterfaceDataTypes::All,
+                ..Default::default()
+            });
+        }
+    }
+    for interface in interfaces {
+        if let Ok(table) = to_table(&interface) {
+            let _ = push_table(doc, "interfaces", table);
+        }
+    }
+    did_migrate
+}
+
+/// The result of upgrading a project config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigUpgrade {
+    pub content: String,
+    // Descriptions of each migration which changed the config
+    pub migrations: Vec<String>,
+}
+
+impl ConfigUpgrade {
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+}
+
+fn config_version(doc: &toml_edit::DocumentMut) -> Option<i64> {
+    doc.get("version").and_then(|version| version.as_integer())
+}
+
+/// Apply every migration to the contents of a 'tach.toml', and set its version to the latest.
+/// Formatting and comments are preserved wherever the config is not changed.
+pub fn upgrade_config_content(content: &str, project_root: &Path) -> Result<ConfigUpgrade> {
+    let mut doc = content.parse::<toml_edit::DocumentMut>()?;
+    if let Some(version) = config_version(&doc).filter(|&v| v > i64::from(CONFIG_VERSION)) {
+        return Err(ParsingError::UnsupportedVersion(version));
+    }
+
+    let mut migrations: Vec<String> = MIGRATIONS
+        .iter()
+        .filter(|migration| (migration.apply)(&mut doc, project_root))
+        .map(|migration| migration.description.to_string())
+        .collect();
+    if config_version(&doc) != Some(i64::from(CONFIG_VERSION)) {
+        doc["version"] = toml_edit::value(i64::from(CONFIG_VERSION));
+        migrations.push(format!("Set the config version to {}", CONFIG_VERSION));
+    }
+
+    let content = doc.to_string();
+    // The upgraded config must be valid for this version of Tach
+    toml::from_str::<ProjectConfig>(&content)?;
+    Ok(ConfigUpgrade {
+        content,
+        migrations,
+    })
+}
+
+/// Whether a config which failed to deserialize uses a deprecated key or shape.
+pub fn needs_migration(content: &str, project_root: &Path) -> bool {
+    content
+        .parse::<toml_edit::DocumentMut>()
+        .is_ok_and(|mut doc| {
+            MIGRATIONS
+                .iter()
+                .any(|migration| (migration.apply)(&mut doc, project_root))
+        })
+}
+
+/// Upgrade the project config at 'filepath', writing it back unless 'dry_run' is set.
+pub fn upgrade_project_config(filepath: &Path, dry_run: bool) -> Result<ConfigUpgrade> {
+    let content = read_file_content(filepath)?;
+    let project_root = filepath.parent().unwrap_or(Path::new("."));
+    let upgrade = upgrade_config_content(&content, project_root)?;
+    if !dry_run && !upgrade.is_empty() {
+        std::fs::write(filepath, &upgrade.content)?;
+    }
+    Ok(upgrade)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_deprecated_keys() {
+        let content = r#"# Project config
+source_root = "src"
+exclude = ["tests", ".*__pycache__"]
+
+[cache]
+backend = "remote"
+
+[[modules]]
+path = "core"
+depends_on = []
+"#;
+
+        let upgrade = upgrade_config_content(content, Path::new("/project")).unwrap();
+
+        assert_eq!(
+            upgrade.migrations,
+            vec![
+                "Replaced 'source_root' with 'source_roots'",
+                "Removed the unsupported cache backend ('disk' is the only backend)",
+                "Replaced the default regex exclude paths with glob patterns",
+                "Set the config version to 1",
+            ]
+        );
+        assert!(upgrade.content.starts_with("# Project config\n"));
+        let config: ProjectConfig = toml::from_str(&upgrade.content).unwrap();
+        assert_eq!(config.version, Some(CONFIG_VERSION));
+        assert_eq!(config.source_roots[0].path, PathBuf::from("src"));
+        assert_eq!(config.exclude, vec!["tests", "**/*__pycache__"]);
+    }
+
+    #[test]
+    fn test_upgrade_strict_modules() {
+        let content = r#"
+[[modules]]
+path = "core"
+depends_on = []
+strict = true
+"#;
+
+        let upgrade = upgrade_config_content(content, Path::new("/missing")).unwrap();
+
+        let config: ProjectConfig = toml::from_str(&upgrade.content).unwrap();
+        assert_eq!(config.interfaces.len(), 1);
+        assert_eq!(config.interfaces[0].from_modules, vec!["core"]);
+        assert!(!upgrade.content.contains("strict"));
+    }
+
+    #[test]
+    fn test_upgrade_is_idempotent() {
+        let upgrade = upgrade_config_content("source_root = \"src\"\n", Path::new(".")).unwrap();
+
+        let second = upgrade_config_content(&upgrade.content, Path::new(".")).unwrap();
+        assert!(second.is_empty());
+        assert_eq!(second.content, upgrade.content);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        assert!(matches!(
+            upgrade_config_content("version = 99\n", Path::new(".")),
+            Err(ParsingError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    fn test_needs_migration() {
+        assert!(needs_migration("source_root = \"src\"\n", Path::new(".")));
+        assert!(!needs_migration(
+            "source_roots = [\"src\"]\n",
+            Path::new(".")
+        ));
+    }
+}
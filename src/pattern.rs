@@ -2,6 +2,12 @@ use glob;
 use regex;
 
 use crate::exclusion::PathExclusionError;
+use crate::filesystem::normalize_separators;
+
+/// Patterns with this prefix are always interpreted as regex.
+pub const REGEX_PATTERN_PREFIX: &str = "re:";
+/// Patterns with this prefix are always interpreted as globs.
+pub const GLOB_PATTERN_PREFIX: &str = "glob:";
 
 pub enum PatternMatcher {
     Regex(regex::Regex),
@@ -16,6 +22,23 @@ impl PatternMatcher {
         }
     }
 
+    /// Build a matcher from a pattern with an optional 're:' or 'glob:' prefix.
+    /// Unprefixed patterns are interpreted as regex when 'use_regex_matching' is set, and as globs otherwise.
+    pub fn from_pattern(
+        pattern: &str,
+        use_regex_matching: bool,
+    ) -> Result<Self, PathExclusionError> {
+        if let Some(regex) = pattern.strip_prefix(REGEX_PATTERN_PREFIX) {
+            Self::from_regex(regex)
+        } else if let Some(glob) = pattern.strip_prefix(GLOB_PATTERN_PREFIX) {
+            Self::from_glob(glob)
+        } else if use_regex_matching {
+            Self::from_regex(pattern)
+        } else {
+            Self::from_glob(pattern)
+        }
+    }
+
     pub fn from_regex(pattern: &str) -> Result<Self, PathExclusionError> {
         let pattern_from_start = if pattern.starts_with('^') {
             pattern.to_string()
@@ -33,11 +56,16 @@ impl PatternMatcher {
     }
 
     pub fn from_glob(pattern: &str) -> Result<Self, PathExclusionError> {
-        Ok(PatternMatcher::Glob(glob::Pattern::new(pattern).map_err(
-            |e| PathExclusionError::GlobPatternError {
-                exclude: pattern.to_string(),
-                source: e,
-            },
-        )?))
+        // Paths are matched with forward slashes, so glob patterns written with backslashes
+        // (as is natural on Windows) are normalized. Regex patterns use backslashes as escapes.
+        let normalized_pattern = normalize_separators(pattern);
+        Ok(PatternMatcher::Glob(
+            glob::Pattern::new(&normalized_pattern).map_err(|e| {
+                PathExclusionError::GlobPatternError {
+                    exclude: pattern.to_string(),
+                    source: e,
+                }
+            })?,
+        ))
     }
 }
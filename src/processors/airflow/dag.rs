@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod};
+
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+
+/// Resolve a DAG folder (a filesystem path, relative to a source root or absolute)
+/// to the module path of the package or file it points to.
+fn dag_folder_module_path(source_roots: &[PathBuf], dag_folder: &str) -> Option<String> {
+    let path = Path::new(dag_folder);
+    if path.is_absolute() {
+        return filesystem::file_to_module_path(source_roots, path).ok();
+    }
+    source_roots
+        .iter()
+        .map(|root| root.join(path))
+        .find(|candidate| candidate.exists())
+        .and_then(|candidate| filesystem::file_to_module_path(source_roots, &candidate).ok())
+}
+
+struct AirflowVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl<'a> AirflowVisitor<'a> {
+    fn new(source_roots: &'a [PathBuf]) -> Self {
+        AirflowVisitor {
+            source_roots,
+            references: vec![],
+        }
+    }
+
+    fn add_callable_reference(&mut self, expr: &Expr) {
+        // Callables may be given as dotted paths (e.g. python_callable="pkg.tasks.extract")
+        if let Expr::StringLiteral(s) = expr {
+            self.references.push(SourceCodeReference::new(
+                s.value.to_string(),
+                s.range.start(),
+                "python_callable",
+            ));
+        }
+    }
+
+    fn add_dag_folder_reference(&mut self, expr: &Expr) {
+        if let Expr::StringLiteral(s) = expr {
+            if let Some(module_path) = dag_folder_module_path(self.source_roots, s.value.to_str()) {
+                self.references.push(SourceCodeReference::new(
+                    module_path,
+                    s.range.start(),
+                    "dag folder",
+                ));
+            }
+        }
+    }
+}
+
+impl Visitor<'_> for AirflowVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call_expr) = expr {
+            if let Some(keyword) = call_expr.arguments.find_keyword("python_callable") {
+                self.add_callable_reference(&keyword.value);
+            }
+
+            if let Some(keyword) = call_expr.arguments.find_keyword("dag_folder") {
+                self.add_dag_folder_reference(&keyword.value);
+            } else {
+                let func_name = match &*call_expr.func {
+                    Expr::Name(name) => Some(name.id.as_str()),
+                    Expr::Attribute(attr) => Some(attr.attr.as_str()),
+                    _ => None,
+                };
+                // The DAG folder is the first positional argument to DagBag
+                if func_name == Some("DagBag") {
+                    if let Some(first_arg) = call_expr.arguments.args.first() {
+                        self.add_dag_folder_reference(first_arg);
+                    }
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find modules referenced by Airflow, such as dotted 'python_callable' paths
+/// and DAG folders passed to 'DagBag'.
+pub fn get_airflow_references(
+    source_roots: &[PathBuf],
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = AirflowVisitor::new(source_roots);
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+    use crate::tests::fixtures::example_dir;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_airflow_references(example_dir: PathBuf) {
+        let source_root = example_dir.join("valid");
+        let file_ast = parse_python_source(&format!(
+            r#"
+PythonOperator(task_id="extract", python_callable="domain_two.other.extract")
+PythonOperator(task_id="load", python_callable=load)
+DagBag("domain_two")
+DagBag(dag_folder="domain_two/some_file.py", include_examples=False)
+DagBag(dag_folder="{}")
+DagBag(dag_folder="missing/dags")
+"#,
+            source_root.join("domain_one").display()
+        ))
+        .unwrap();
+
+        let references: Vec<String> = get_airflow_references(&[source_root], &file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect();
+        assert_eq!(
+            references,
+            [
+                "domain_two.other.extract",
+                "domain_two",
+                "domain_two.some_file",
+                "domain_one"
+            ]
+        );
+    }
+}
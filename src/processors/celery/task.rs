@@ -0,0 +1,189 @@
+use ruff_python_ast::visitor::{walk_expr, walk_stmt, Visitor};
+use ruff_python_ast::{Expr, Mod, Stmt};
+
+use crate::processors::reference::SourceCodeReference;
+
+const TASK_ROUTES_NAMES: [&str; 2] = ["task_routes", "CELERY_TASK_ROUTES"];
+
+struct CeleryTaskVisitor {
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl CeleryTaskVisitor {
+    fn new() -> Self {
+        CeleryTaskVisitor { references: vec![] }
+    }
+
+    fn add_string_reference(&mut self, expr: &Expr) {
+        if let Expr::StringLiteral(s) = expr {
+            self.references.push(SourceCodeReference::new(
+                s.value.to_string(),
+                s.range.start(),
+                "task name",
+            ));
+        }
+    }
+
+    fn add_task_routes(&mut self, expr: &Expr) {
+        if let Expr::Dict(dict) = expr {
+            for item in &dict.items {
+                if let Some(Expr::StringLiteral(s)) = &item.key {
+                    // Routes may use glob patterns (e.g. 'pkg.tasks.*'),
+                    // in which case the module path is everything before the wildcard
+                    let route = s.value.to_str();
+                    let module_path = route
+                        .split('*')
+                        .next()
+                        .unwrap_or_default()
+                        .trim_end_matches('.');
+                    if !module_path.is_empty() {
+                        self.references.push(SourceCodeReference::new(
+                            module_path.to_string(),
+                            s.range.start(),
+                            "task route",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn is_task_routes_target(target: &Expr) -> bool {
+    match target {
+        Expr::Name(name) => TASK_ROUTES_NAMES.contains(&name.id.as_str()),
+        // Match attribute assignment (app.conf.task_routes = ...)
+        Expr::Attribute(attr) => TASK_ROUTES_NAMES.contains(&attr.attr.as_str()),
+        _ => false,
+    }
+}
+
+impl Visitor<'_> for CeleryTaskVisitor {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Assign(assign) => {
+                if assign.targets.iter().any(is_task_routes_target) {
+                    self.add_task_routes(&assign.value);
+                }
+            }
+            Stmt::AnnAssign(assign) => {
+                if let Some(value) = &assign.value {
+                    if is_task_routes_target(&assign.target) {
+                        self.add_task_routes(value);
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call_expr) = expr {
+            let func_name = match &*call_expr.func {
+                // Match direct name (send_task, shared_task)
+                Expr::Name(name) => Some(name.id.as_str()),
+                // Match attribute access (app.send_task, app.task, etc.)
+                Expr::Attribute(attr) => Some(attr.attr.as_str()),
+                _ => None,
+            };
+
+            match func_name {
+                Some("send_task") => {
+                    // Task name is the first positional argument, or the 'name' keyword argument
+                    if let Some(first_arg) = call_expr.arguments.args.first() {
+                        self.add_string_reference(first_arg);
+                    } else if let Some(keyword) = call_expr.arguments.find_keyword("name") {
+                        self.add_string_reference(&keyword.value);
+                    }
+                }
+                Some("task") | Some("shared_task") => {
+                    if let Some(keyword) = call_expr.arguments.find_keyword("name") {
+                        self.add_string_reference(&keyword.value);
+                    }
+                }
+                _ => {}
+            }
+
+            // Match routes passed as configuration (app.conf.update(task_routes={...}))
+            for keyword in call_expr.arguments.keywords.iter() {
+                if keyword
+                    .arg
+                    .as_ref()
+                    .is_some_and(|arg| TASK_ROUTES_NAMES.contains(&arg.as_str()))
+                {
+                    self.add_task_routes(&keyword.value);
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find task names referenced by Celery, such as those passed to 'send_task',
+/// declared with 'app.task(name=...)', or configured in 'task_routes'.
+pub fn get_celery_task_references(file_ast: &Mod) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = CeleryTaskVisitor::new();
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+
+    fn reference_paths(source: &str) -> Vec<String> {
+        let file_ast = parse_python_source(source).unwrap();
+        get_celery_task_references(&file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect()
+    }
+
+    #[test]
+    fn test_send_task_references() {
+        assert_eq!(
+            reference_paths(
+                r#"
+app.send_task("pkg.tasks.do_thing", args=[1])
+send_task(name="pkg.other.do_thing")
+"#
+            ),
+            ["pkg.tasks.do_thing", "pkg.other.do_thing"]
+        );
+    }
+
+    #[test]
+    fn test_named_task_references() {
+        assert_eq!(
+            reference_paths(
+                r#"
+@app.task(name="pkg.tasks.named")
+def named(): ...
+
+@shared_task(name="pkg.tasks.shared")
+def shared(): ...
+"#
+            ),
+            ["pkg.tasks.named", "pkg.tasks.shared"]
+        );
+    }
+
+    #[test]
+    fn test_task_routes_references() {
+        assert_eq!(
+            reference_paths(
+                r#"
+task_routes = {"pkg.tasks.*": {"queue": "default"}}
+app.conf.update(task_routes={"pkg.other.do_thing": {"queue": "other"}})
+"#
+            ),
+            ["pkg.tasks", "pkg.other.do_thing"]
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use ruff_python_ast::Mod;
 use ruff_text_size::TextSize;
 
 use crate::config::plugins::django::DjangoConfig;
@@ -10,31 +11,53 @@ use crate::diagnostics::{FileProcessor, Result as DiagnosticResult};
 use crate::filesystem::{self, ProjectFile};
 use crate::modules::error::ModuleTreeError;
 use crate::modules::{ModuleNode, ModuleTree};
-use crate::python::parsing::parse_python_source;
+use crate::python::parsing::parse_python_source_partial;
+use crate::python::syntax::find_unsupported_syntax;
 
-use super::django::fkey::{get_foreign_key_references, get_known_apps};
+use super::airflow::dag::get_airflow_references;
+use super::celery::task::get_celery_task_references;
+use super::dependency_injector::wiring::get_wiring_references;
+use super::django::apps::{get_known_apps, DjangoApp};
+use super::django::fkey::{get_foreign_key_references, resolve_model_reference};
+use super::django::settings::{get_settings_references, is_settings_module};
+use super::django::urls::get_url_include_references;
 use super::file_module::FileModule;
-use super::import::{get_normalized_imports, get_normalized_imports_from_ast, NormalizedImport};
+use super::import::NormalizedImport;
+use super::import_cache::{ImportCache, ImportOptions};
+use super::protobuf::generated::ProtobufMappings;
 use super::reference::SourceCodeReference;
+use super::reference_pattern::{get_pattern_references, get_reference_patterns, ReferencePattern};
+use super::sqlalchemy::relationship::{
+    get_relationship_references, get_sqlalchemy_models, SqlAlchemyModels,
+};
+use super::typing::forward_ref::get_forward_references;
 
 #[derive(Debug)]
 pub enum Dependency {
     Import(NormalizedImport),
-    Reference(SourceCodeReference),
+    // A reference found by a plugin, along with the name of the plugin
+    Reference(&'static str, SourceCodeReference),
 }
 
 impl Dependency {
     pub fn module_path(&self) -> &str {
         match self {
             Dependency::Import(import) => &import.module_path,
-            Dependency::Reference(reference) => &reference.module_path,
+            Dependency::Reference(_, reference) => &reference.module_path,
+        }
+    }
+
+    pub fn module_path_mut(&mut self) -> &mut String {
+        match self {
+            Dependency::Import(import) => &mut import.module_path,
+            Dependency::Reference(_, reference) => &mut reference.module_path,
         }
     }
 
     pub fn offset(&self) -> TextSize {
         match self {
             Dependency::Import(import) => import.alias_offset,
-            Dependency::Reference(reference) => reference.offset,
+            Dependency::Reference(_, reference) => reference.offset,
         }
     }
 }
@@ -45,16 +68,10 @@ impl From<NormalizedImport> for Dependency {
     }
 }
 
-impl From<SourceCodeReference> for Dependency {
-    fn from(source_code_reference: SourceCodeReference) -> Self {
-        Dependency::Reference(source_code_reference)
-    }
-}
-
 #[derive(Debug)]
 pub struct DjangoMetadata<'a> {
     pub config: &'a DjangoConfig,
-    pub known_apps: Vec<String>,
+    pub known_apps: Vec<DjangoApp>,
 }
 
 impl<'a> DjangoMetadata<'a> {
@@ -73,6 +90,9 @@ pub struct InternalDependencyExtractor<'a> {
     source_roots: &'a [PathBuf],
     project_config: &'a ProjectConfig,
     django_metadata: Option<DjangoMetadata<'a>>,
+    sqlalchemy_models: Option<SqlAlchemyModels>,
+    protobuf_mappings: Option<ProtobufMappings>,
+    reference_patterns: Vec<ReferencePattern>,
 }
 
 impl<'a> InternalDependencyExtractor<'a> {
@@ -86,13 +106,147 @@ impl<'a> InternalDependencyExtractor<'a> {
             .django
             .as_ref()
             .map(|django_config| DjangoMetadata::new(source_roots, django_config));
+        let sqlalchemy_models = project_config
+            .plugins
+            .sqlalchemy
+            .as_ref()
+            .map(|_| get_sqlalchemy_models(source_roots));
+        let protobuf_mappings = project_config
+            .plugins
+            .protobuf
+            .as_ref()
+            .map(ProtobufMappings::new);
+
+        // Patterns are validated when the project config is parsed
+        let reference_patterns =
+            get_reference_patterns(&project_config.plugins.references).unwrap_or_default();
 
         Self {
             source_roots,
             module_tree,
             project_config,
             django_metadata,
+            sqlalchemy_models,
+            protobuf_mappings,
+            reference_patterns,
+        }
+    }
+
+    /// Why dependencies are not extracted from a file in 'module', if they are not.
+    pub fn skip_reason(&self, module: &ModuleNode, contents: &str) -> Option<&'static str> {
+        if module.is_unchecked() {
+            Some("the file belongs to an unchecked module")
+        } else if self.project_config.generated_code.is_generated(contents) {
+            Some("the file is marked as generated code")
+        } else if module.is_root() && self.project_config.root_module == RootModuleTreatment::Ignore
+        {
+            Some("the file belongs to the root module, which is ignored")
+        } else {
+            None
+        }
+    }
+
+    /// The module which owns generated code at 'module_path', if it is mapped to one.
+    pub fn generated_code_owner(&self, module_path: &str) -> Option<&str> {
+        self.protobuf_mappings
+            .as_ref()
+            .and_then(|mappings| mappings.owning_module(module_path))
+    }
+
+    /// Whether any enabled plugin finds references in addition to imports.
+    pub fn has_plugins(&self) -> bool {
+        let plugins = &self.project_config.plugins;
+        self.django_metadata.is_some()
+            || self.sqlalchemy_models.is_some()
+            || plugins.celery.is_some()
+            || plugins.airflow.is_some()
+            || plugins.dependency_injector.is_some()
+            || plugins.forward_refs.is_some()
+            || !self.reference_patterns.is_empty()
+    }
+
+    /// References found by the enabled plugins, each labelled with the plugin which found it.
+    pub fn plugin_references(
+        &self,
+        mod_path: &str,
+        file_ast: &Mod,
+    ) -> Vec<(&'static str, SourceCodeReference)> {
+        let mut references: Vec<(&'static str, SourceCodeReference)> = vec![];
+        let mut extend = |plugin: &'static str, found: Vec<SourceCodeReference>| {
+            references.extend(found.into_iter().map(|reference| (plugin, reference)));
+        };
+        let is_project_reference = |reference: &SourceCodeReference| {
+            filesystem::is_project_import(self.source_roots, &reference.module_path)
+        };
+
+        if let Some(django_metadata) = &self.django_metadata {
+            extend(
+                "django",
+                get_foreign_key_references(file_ast)
+                    .into_iter()
+                    .filter_map(|reference| {
+                        resolve_model_reference(&django_metadata.known_apps, reference)
+                    })
+                    .collect(),
+            );
+            extend(
+                "django",
+                get_url_include_references(self.source_roots, file_ast),
+            );
+            if is_settings_module(django_metadata.config, mod_path) {
+                extend(
+                    "django",
+                    get_settings_references(self.source_roots, file_ast),
+                );
+            }
+        }
+
+        if self.project_config.plugins.celery.is_some() {
+            let found = get_celery_task_references(file_ast);
+            extend(
+                "celery",
+                found.into_iter().filter(is_project_reference).collect(),
+            );
+        }
+
+        if self.project_config.plugins.airflow.is_some() {
+            let found = get_airflow_references(self.source_roots, file_ast);
+            extend(
+                "airflow",
+                found.into_iter().filter(is_project_reference).collect(),
+            );
+        }
+
+        if self.project_config.plugins.dependency_injector.is_some() {
+            let found = get_wiring_references(file_ast);
+            extend(
+                "dependency_injector",
+                found.into_iter().filter(is_project_reference).collect(),
+            );
         }
+
+        if let Some(sqlalchemy_models) = &self.sqlalchemy_models {
+            extend(
+                "sqlalchemy",
+                get_relationship_references(self.source_roots, sqlalchemy_models, file_ast),
+            );
+        }
+
+        if self.project_config.plugins.forward_refs.is_some() {
+            extend(
+                "forward_refs",
+                get_forward_references(self.source_roots, file_ast),
+            );
+        }
+
+        if !self.reference_patterns.is_empty() {
+            extend(
+                "references",
+                get_pattern_references(self.source_roots, &self.reference_patterns, file_ast),
+            );
+        }
+
+        references
     }
 }
 
@@ -104,31 +258,54 @@ impl<'a> FileProcessor<'a, ProjectFile<'a>> for InternalDependencyExtractor<'a>
         let module = self
             .module_tree
             .find_nearest(mod_path.as_ref())
-            .ok_or(ModuleTreeError::ModuleNotFound(mod_path))?;
+            .ok_or_else(|| ModuleTreeError::ModuleNotFound(mod_path.clone()))?;
 
-        if module.is_unchecked() {
-            return Ok(FileModule::new(file_path, module));
-        }
-
-        if module.is_root() && self.project_config.root_module == RootModuleTreatment::Ignore {
+        if self.skip_reason(&module, &file_path.contents).is_some() {
             return Ok(FileModule::new(file_path, module));
         }
 
+        let ignore_type_checking_imports = self
+            .project_config
+            .ignore_type_checking_imports_in(file_path.project_root, file_path.source_root);
+        let include_string_imports = self
+            .project_config
+            .include_string_imports_in(file_path.project_root, file_path.source_root);
+        let python_version = self
+            .project_config
+            .python_version_in(file_path.project_root, file_path.source_root);
         let mut file_module = FileModule::new(file_path, module);
         let mut dependencies: Vec<Dependency> = vec![];
-        let file_ast = parse_python_source(file_module.contents())?;
+        let options = ImportOptions::new(ignore_type_checking_imports, include_string_imports);
+        // The AST is only needed beyond imports for plugins and syntax checks,
+        // otherwise imports are shared with other commands without parsing the file again
+        let (file_imports, file_ast) = if python_version.is_some() || self.has_plugins() {
+            let (file_ast, syntax_error) = parse_python_source_partial(file_module.contents());
+            let file_imports = ImportCache::global().get_imports_from_ast(
+                self.source_roots,
+                file_module.file_path(),
+                file_module.contents(),
+                &file_ast,
+                syntax_error.as_ref(),
+                options,
+            )?;
+            (file_imports, Some(file_ast))
+        } else {
+            let file_imports = ImportCache::global().get_imports(
+                self.source_roots,
+                file_module.file_path(),
+                file_module.contents(),
+                options,
+            )?;
+            (file_imports, None)
+        };
+        file_module.set_syntax_error(file_imports.syntax_error.clone());
+        if let (Some(python_version), Some(file_ast)) = (python_version, &file_ast) {
+            file_module.unsupported_syntax = find_unsupported_syntax(file_ast, python_version);
+        }
 
-        let project_imports = get_normalized_imports_from_ast(
-            self.source_roots,
-            file_module.file_path(),
-            &file_ast,
-            self.project_config.ignore_type_checking_imports,
-            self.project_config.include_string_imports,
-        )?
-        .into_iter()
-        .filter_map(|import| {
+        let project_imports = file_imports.imports.iter().filter_map(|import| {
             if filesystem::is_project_import(self.source_roots, &import.module_path) {
-                Some(Dependency::Import(import))
+                Some(Dependency::Import(import.clone()))
             } else {
                 // Remove directives that match irrelevant imports
                 file_module
@@ -139,14 +316,21 @@ impl<'a> FileProcessor<'a, ProjectFile<'a>> for InternalDependencyExtractor<'a>
         });
         dependencies.extend(project_imports);
 
-        if self.django_metadata.is_some() {
+        if let Some(file_ast) = &file_ast {
             dependencies.extend(
-                get_foreign_key_references(&file_ast)
+                self.plugin_references(&mod_path, file_ast)
                     .into_iter()
-                    .map(Dependency::Reference),
+                    .map(|(plugin, reference)| Dependency::Reference(plugin, reference)),
             );
         }
 
+        // Generated code is attributed to the module which owns its proto package
+        for dependency in dependencies.iter_mut() {
+            if let Some(owning_module) = self.generated_code_owner(dependency.module_path()) {
+                *dependency.module_path_mut() = owning_module.to_string();
+            }
+        }
+
         file_module.extend_dependencies(dependencies);
         Ok(file_module)
     }
@@ -155,46 +339,70 @@ impl<'a> FileProcessor<'a, ProjectFile<'a>> for InternalDependencyExtractor<'a>
 #[derive(Debug)]
 pub struct ExternalDependencyExtractor<'a> {
     source_roots: &'a [PathBuf],
+    module_tree: &'a ModuleTree,
     project_config: &'a ProjectConfig,
 }
 
 impl<'a> ExternalDependencyExtractor<'a> {
-    pub fn new(source_roots: &'a [PathBuf], project_config: &'a ProjectConfig) -> Self {
+    pub fn new(
+        source_roots: &'a [PathBuf],
+        module_tree: &'a ModuleTree,
+        project_config: &'a ProjectConfig,
+    ) -> Self {
         Self {
             source_roots,
+            module_tree,
             project_config,
         }
     }
+
+    /// Unlike check, files outside of any module are still checked against their pyproject.toml.
+    fn owning_module(&self, file_path: &Path) -> Arc<ModuleNode> {
+        filesystem::file_to_module_path(self.source_roots, file_path)
+            .ok()
+            .and_then(|mod_path| self.module_tree.find_nearest(&mod_path))
+            .unwrap_or_else(|| Arc::new(ModuleNode::empty()))
+    }
 }
 
 impl<'a> FileProcessor<'a, ProjectFile<'a>> for ExternalDependencyExtractor<'a> {
     type ProcessedFile = FileModule<'a>;
 
     fn process(&self, file_path: ProjectFile<'a>) -> DiagnosticResult<Self::ProcessedFile> {
-        // NOTE: check-external does not currently make use of the module tree,
-        // but it is very likely to do so in the future.
-        let module = Arc::new(ModuleNode::empty());
+        let module = self.owning_module(file_path.as_ref());
+        if self
+            .project_config
+            .generated_code
+            .is_generated(&file_path.contents)
+        {
+            return Ok(FileModule::new(file_path, module));
+        }
+        let ignore_type_checking_imports = self
+            .project_config
+            .ignore_type_checking_imports_in(file_path.project_root, file_path.source_root);
         let mut file_module = FileModule::new(file_path, module);
-        let external_imports: Vec<Dependency> = get_normalized_imports(
+        let file_imports = ImportCache::global().get_imports(
             self.source_roots,
             file_module.file_path(),
             file_module.contents(),
-            self.project_config.ignore_type_checking_imports,
-            false,
-        )?
-        .into_iter()
-        .filter_map(|import| {
-            if !filesystem::is_project_import(self.source_roots, &import.module_path) {
-                Some(Dependency::Import(import))
-            } else {
-                // Remove directives that match irrelevant imports
-                file_module
-                    .ignore_directives
-                    .remove_matching_directives(file_module.line_number(import.import_offset));
-                None
-            }
-        })
-        .collect();
+            ImportOptions::new(ignore_type_checking_imports, false),
+        )?;
+        file_module.set_syntax_error(file_imports.syntax_error.clone());
+        let external_imports: Vec<Dependency> = file_imports
+            .imports
+            .iter()
+            .filter_map(|import| {
+                if !filesystem::is_project_import(self.source_roots, &import.module_path) {
+                    Some(Dependency::Import(import.clone()))
+                } else {
+                    // Remove directives that match irrelevant imports
+                    file_module
+                        .ignore_directives
+                        .remove_matching_directives(file_module.line_number(import.import_offset));
+                    None
+                }
+            })
+            .collect();
         file_module.extend_dependencies(external_imports);
         Ok(file_module)
     }
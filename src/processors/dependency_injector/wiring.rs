@@ -0,0 +1,147 @@
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod};
+
+use crate::processors::reference::SourceCodeReference;
+
+const WIRING_KEYWORDS: [&str; 2] = ["modules", "packages"];
+const MARKER_NAMES: [&str; 3] = ["Provide", "Provider", "Closing"];
+
+struct WiringVisitor {
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl WiringVisitor {
+    fn new() -> Self {
+        WiringVisitor { references: vec![] }
+    }
+
+    fn add_string_reference(&mut self, expr: &Expr) {
+        if let Expr::StringLiteral(s) = expr {
+            let module_path = s.value.to_str();
+            // Relative paths are resolved by dependency-injector against the container's package,
+            // and names without a dot refer to container providers rather than modules
+            if !module_path.starts_with('.') && module_path.contains('.') {
+                self.references.push(SourceCodeReference::new(
+                    module_path.to_string(),
+                    s.range.start(),
+                    "wiring",
+                ));
+            }
+        }
+    }
+
+    fn add_string_references(&mut self, expr: &Expr) {
+        match expr {
+            Expr::List(list) => list
+                .elts
+                .iter()
+                .for_each(|elt| self.add_string_reference(elt)),
+            Expr::Tuple(tuple) => tuple
+                .elts
+                .iter()
+                .for_each(|elt| self.add_string_reference(elt)),
+            _ => self.add_string_reference(expr),
+        }
+    }
+}
+
+fn name_of(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Name(name) => Some(name.id.as_str()),
+        Expr::Attribute(attr) => Some(attr.attr.as_str()),
+        _ => None,
+    }
+}
+
+impl Visitor<'_> for WiringVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Call(call_expr) => {
+                // Match container.wire(modules=[...]) and WiringConfiguration(modules=[...])
+                if matches!(
+                    name_of(&call_expr.func),
+                    Some("wire") | Some("WiringConfiguration")
+                ) {
+                    for keyword in call_expr.arguments.keywords.iter() {
+                        if keyword
+                            .arg
+                            .as_ref()
+                            .is_some_and(|arg| WIRING_KEYWORDS.contains(&arg.as_str()))
+                        {
+                            self.add_string_references(&keyword.value);
+                        }
+                    }
+                    // Modules may also be passed as the first positional argument
+                    if let Some(first_arg) = call_expr.arguments.args.first() {
+                        self.add_string_references(first_arg);
+                    }
+                }
+            }
+            Expr::Subscript(subscript) => {
+                // Match markers such as Provide["pkg.services.Service"]
+                if name_of(&subscript.value).is_some_and(|name| MARKER_NAMES.contains(&name)) {
+                    self.add_string_reference(&subscript.slice);
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find modules referenced by dependency-injector, such as those wired with
+/// 'container.wire(modules=[...])' or injected with 'Provide["..."]'.
+pub fn get_wiring_references(file_ast: &Mod) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = WiringVisitor::new();
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+
+    fn reference_paths(source: &str) -> Vec<String> {
+        let file_ast = parse_python_source(source).unwrap();
+        get_wiring_references(&file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect()
+    }
+
+    #[test]
+    fn test_wire_references() {
+        assert_eq!(
+            reference_paths(
+                r#"
+container.wire(modules=["pkg.api.views", ".handlers"], packages=("pkg.jobs",))
+
+class Container(containers.DeclarativeContainer):
+    wiring_config = containers.WiringConfiguration(modules=["pkg.api.routes"])
+"#
+            ),
+            ["pkg.api.views", "pkg.jobs", "pkg.api.routes"]
+        );
+    }
+
+    #[test]
+    fn test_marker_references() {
+        assert_eq!(
+            reference_paths(
+                r#"
+@inject
+def handler(service: Service = Provide["pkg.services.Service"], db = Provide["database"]):
+    ...
+
+mapping["pkg.not.a.marker"]
+"#
+            ),
+            ["pkg.services.Service"]
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use ruff_python_ast::statement_visitor::{walk_stmt, StatementVisitor};
+use ruff_python_ast::{Expr, Mod, Stmt, StmtClassDef};
+use thiserror::Error;
+
+use crate::config::plugins::django::DjangoConfig;
+use crate::filesystem;
+use crate::python::error::ParsingError;
+use crate::python::parsing::parse_python_source;
+
+#[derive(Error, Debug)]
+pub enum DjangoAppsError {
+    #[error("Failed to parse Django settings: {0}")]
+    Parsing(#[from] ParsingError),
+    #[error("Failed to read Django settings file: {0}")]
+    Filesystem(#[from] filesystem::FileSystemError),
+    #[error("Could not find Django settings file: {0}")]
+    SettingsFileNotFound(String),
+    #[error(
+        "The Django plugin is not configured. Set 'settings_module' under '[plugins.django]'."
+    )]
+    NotConfigured,
+}
+
+pub type Result<T> = std::result::Result<T, DjangoAppsError>;
+
+/// An entry in INSTALLED_APPS, resolved to its app label and the module which contains the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[pyclass(get_all, module = "tach.extension")]
+pub struct DjangoApp {
+    /// The entry as written in INSTALLED_APPS.
+    pub entry: String,
+    pub label: String,
+    pub module_path: String,
+    /// The dotted path of the AppConfig class which configures the app, if one was found.
+    pub app_config: Option<String>,
+    /// Whether the app's module is within the source roots.
+    pub in_project: bool,
+}
+
+struct InstalledAppsVisitor {
+    pub installed_apps: Vec<String>,
+}
+
+impl InstalledAppsVisitor {
+    fn add_apps(&mut self, expr: &Expr) {
+        let elements = match expr {
+            Expr::List(list) => list.elts.as_slice(),
+            Expr::Tuple(tuple) => tuple.elts.as_slice(),
+            _ => return,
+        };
+        for element in elements {
+            if let Expr::StringLiteral(s) = element {
+                self.installed_apps.push(s.value.to_string());
+            }
+        }
+    }
+}
+
+fn is_installed_apps(target: &Expr) -> bool {
+    matches!(target, Expr::Name(name) if name.id.as_str() == "INSTALLED_APPS")
+}
+
+impl StatementVisitor<'_> for InstalledAppsVisitor {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Assign(assign) if assign.targets.iter().any(is_installed_apps) => {
+                self.add_apps(&assign.value);
+            }
+            // Match apps which are added conditionally (INSTALLED_APPS += [...])
+            Stmt::AugAssign(assign) if is_installed_apps(&assign.target) => {
+                self.add_apps(&assign.value);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct AppConfigClass {
+    class_name: String,
+    name: Option<String>,
+    label: Option<String>,
+    default: Option<bool>,
+}
+
+fn is_app_config_base(base: &Expr) -> bool {
+    match base {
+        Expr::Name(name) => name.id.as_str().ends_with("AppConfig"),
+        Expr::Attribute(attr) => attr.attr.as_str().ends_with("AppConfig"),
+        _ => false,
+    }
+}
+
+/// Read the 'name', 'label' and 'default' attributes of an AppConfig subclass.
+/// Classes with a literal 'name' are also accepted, since they may extend a project-specific base.
+fn parse_app_config(class_def: &StmtClassDef) -> Option<AppConfigClass> {
+    let mut app_config = AppConfigClass {
+        class_name: class_def.name.to_string(),
+        ..Default::default()
+    };
+    for stmt in &class_def.body {
+        let (target, value) = match stmt {
+            Stmt::Assign(assign) if assign.targets.len() == 1 => {
+                (&assign.targets[0], assign.value.as_ref())
+            }
+            Stmt::AnnAssign(assign) => match &assign.value {
+                Some(value) => (assign.target.as_ref(), value.as_ref()),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let Expr::Name(target) = target else {
+            continue;
+        };
+        match (target.id.as_str(), value) {
+            ("name", Expr::StringLiteral(s)) => app_config.name = Some(s.value.to_string()),
+            ("label", Expr::StringLiteral(s)) => app_config.label = Some(s.value.to_string()),
+            ("default", Expr::BooleanLiteral(b)) => app_config.default = Some(b.value),
+            _ => {}
+        }
+    }
+    (class_def.bases().iter().any(is_app_config_base) || app_config.name.is_some())
+        .then_some(app_config)
+}
+
+fn read_app_configs(source_roots: &[PathBuf], mod_path: &str) -> Vec<AppConfigClass> {
+    let Some(resolved) = filesystem::module_to_file_path(source_roots, mod_path, false) else {
+        return vec![];
+    };
+    let Ok(source) = filesystem::read_file_content(&resolved.file_path) else {
+        return vec![];
+    };
+    let Ok(Mod::Module(module)) = parse_python_source(&source) else {
+        return vec![];
+    };
+    module
+        .body
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::ClassDef(class_def) => parse_app_config(class_def),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Like Django, use the only AppConfig in '<app>.apps', or the one marked 'default = True'.
+fn default_app_config(app_configs: Vec<AppConfigClass>) -> Option<AppConfigClass> {
+    let candidates: Vec<AppConfigClass> = app_configs
+        .into_iter()
+        .filter(|app_config| app_config.default != Some(false))
+        .collect();
+    if candidates.len() == 1 {
+        return candidates.into_iter().next();
+    }
+    candidates
+        .into_iter()
+        .find(|app_config| app_config.default == Some(true))
+}
+
+/// Without a readable AppConfig, an entry is either the app's module,
+/// or the path to an AppConfig class, conventionally found in '<app>.apps'.
+fn default_module_path(entry: &str, is_module: bool) -> String {
+    if is_module {
+        return entry.to_string();
+    }
+    match entry.rsplit_once('.') {
+        Some((module, class_name)) if class_name.starts_with(char::is_uppercase) => {
+            module.strip_suffix(".apps").unwrap_or(module).to_string()
+        }
+        _ => entry.to_string(),
+    }
+}
+
+fn resolve_installed_app(source_roots: &[PathBuf], entry: &str) -> DjangoApp {
+    let is_module = filesystem::module_to_file_path(source_roots, entry, false).is_some();
+    let (app_config_path, app_config) = if is_module {
+        let apps_module = format!("{}.apps", entry);
+        let app_config = default_app_config(read_app_configs(source_roots, &apps_module));
+        let app_config_path = app_config
+            .as_ref()
+            .map(|app_config| format!("{}.{}", apps_module, app_config.class_name));
+        (app_config_path, app_config)
+    } else if let Some((module, class_name)) = entry.rsplit_once('.') {
+        let app_config = read_app_configs(source_roots, module)
+            .into_iter()
+            .find(|app_config| app_config.class_name == class_name);
+        (app_config.as_ref().map(|_| entry.to_string()), app_config)
+    } else {
+        (None, None)
+    };
+
+    let module_path = app_config
+        .as_ref()
+        .and_then(|app_config| app_config.name.clone())
+        .unwrap_or_else(|| default_module_path(entry, is_module));
+    let label = app_config
+        .and_then(|app_config| app_config.label)
+        .unwrap_or_else(|| {
+            module_path
+                .rsplit('.')
+                .next()
+                .unwrap_or(&module_path)
+                .to_string()
+        });
+    let in_project = filesystem::module_to_file_path(source_roots, &module_path, false).is_some();
+
+    DjangoApp {
+        entry: entry.to_string(),
+        label,
+        module_path,
+        app_config: app_config_path,
+        in_project,
+    }
+}
+
+/// Resolve each entry in the settings module's INSTALLED_APPS to its app label and module.
+/// Apps outside the source roots are included, so that references to them can be recognized.
+pub fn get_known_apps(
+    source_roots: &[PathBuf],
+    django_config: &DjangoConfig,
+) -> Result<Vec<DjangoApp>> {
+    let settings_module_path = &django_config.settings_module;
+    let Some(settings_module) =
+        filesystem::module_to_file_path(source_roots, settings_module_path, false)
+    else {
+        return Err(DjangoAppsError::SettingsFileNotFound(
+            settings_module_path.to_string(),
+        ));
+    };
+    let settings_file = filesystem::read_file_content(settings_module.file_path)?;
+    let settings_ast = parse_python_source(&settings_file)?;
+    let mut visitor = InstalledAppsVisitor {
+        installed_apps: vec![],
+    };
+
+    match settings_ast {
+        Mod::Module(ref module) => {
+            visitor.visit_body(&module.body);
+        }
+        Mod::Expression(_) => return Err(ParsingError::InvalidSyntax.into()),
+    };
+
+    let mut known_apps: Vec<DjangoApp> = vec![];
+    for entry in visitor.installed_apps {
+        if !known_apps.iter().any(|app| app.entry == entry) {
+            known_apps.push(resolve_installed_app(source_roots, &entry));
+        }
+    }
+    Ok(known_apps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn parse_class(source: &str) -> Option<AppConfigClass> {
+        let Mod::Module(module) = parse_python_source(source).unwrap() else {
+            panic!("Expected a module");
+        };
+        match &module.body[0] {
+            Stmt::ClassDef(class_def) => parse_app_config(class_def),
+            _ => panic!("Expected a class definition"),
+        }
+    }
+
+    #[test]
+    fn test_parse_app_config() {
+        let app_config = parse_class(
+            "class BillingConfig(AppConfig):\n    name = 'project.billing'\n    label = 'invoices'\n    default = True\n",
+        );
+        assert_eq!(
+            app_config,
+            Some(AppConfigClass {
+                class_name: "BillingConfig".to_string(),
+                name: Some("project.billing".to_string()),
+                label: Some("invoices".to_string()),
+                default: Some(true),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_non_app_config() {
+        assert_eq!(
+            parse_class("class Invoice(models.Model):\n    pass\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_default_app_config() {
+        let config = |class_name: &str, default: Option<bool>| AppConfigClass {
+            class_name: class_name.to_string(),
+            default,
+            ..Default::default()
+        };
+        assert_eq!(
+            default_app_config(vec![config("Only", None)]).map(|c| c.class_name),
+            Some("Only".to_string())
+        );
+        assert_eq!(
+            default_app_config(vec![config("A", None), config("B", Some(true))])
+                .map(|c| c.class_name),
+            Some("B".to_string())
+        );
+        assert_eq!(
+            default_app_config(vec![config("A", None), config("B", None)]),
+            None
+        );
+        assert_eq!(default_app_config(vec![config("A", Some(false))]), None);
+    }
+
+    #[rstest]
+    #[case("project.billing", true, "project.billing")]
+    #[case("django.contrib.auth", false, "django.contrib.auth")]
+    #[case("django.contrib.auth.apps.AuthConfig", false, "django.contrib.auth")]
+    #[case("allauth.account.AccountConfig", false, "allauth.account")]
+    fn test_default_module_path(
+        #[case] entry: &str,
+        #[case] is_module: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(default_module_path(entry, is_module), expected);
+    }
+}
@@ -1,26 +1,9 @@
-use std::path::PathBuf;
-
 use ruff_python_ast::visitor::Visitor;
 use ruff_python_ast::Mod;
-use thiserror::Error;
 
-use crate::config::plugins::django::DjangoConfig;
-use crate::filesystem;
 use crate::processors::reference::SourceCodeReference;
-use crate::python::error::ParsingError;
-use crate::python::parsing::parse_python_source;
-
-#[derive(Error, Debug)]
-pub enum FKeyError {
-    #[error("Failed to parse Django foreign keys: {0}")]
-    Parsing(#[from] ParsingError),
-    #[error("Failed to read Django settings file: {0}")]
-    Filesystem(#[from] filesystem::FileSystemError),
-    #[error("Could not find Django settings file: {0}")]
-    SettingsFileNotFound(String),
-}
 
-pub type Result<T> = std::result::Result<T, FKeyError>;
+use super::apps::DjangoApp;
 
 struct FKeyVisitor {
     pub fkeys: Vec<SourceCodeReference>,
@@ -70,86 +53,84 @@ impl Visitor<'_> for FKeyVisitor {
             };
 
             if let Some((model_path, string_start)) = target_model {
-                self.fkeys
-                    .push(SourceCodeReference::new(model_path, string_start));
+                self.fkeys.push(SourceCodeReference::new(
+                    model_path,
+                    string_start,
+                    "model relation",
+                ));
             }
         }
     }
 }
 
-struct InstalledAppVisitor {
-    pub installed_apps: Vec<String>,
-}
-
-impl InstalledAppVisitor {
-    fn new() -> Self {
-        InstalledAppVisitor {
-            installed_apps: vec![],
-        }
-    }
-}
+pub fn get_foreign_key_references(file_ast: &Mod) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = FKeyVisitor::new();
 
-impl Visitor<'_> for InstalledAppVisitor {
-    fn visit_stmt(&mut self, stmt: &ruff_python_ast::Stmt) {
-        if let ruff_python_ast::Stmt::Assign(ref assign) = stmt {
-            if assign.targets.len() == 1 {
-                let target = &assign.targets[0];
-                if let ruff_python_ast::Expr::Name(name) = target {
-                    if name.id() == "INSTALLED_APPS" {
-                        if let ruff_python_ast::Expr::List(list) = assign.value.as_ref() {
-                            for item in &list.elts {
-                                if let ruff_python_ast::Expr::StringLiteral(string) = item {
-                                    self.installed_apps.push(string.value.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
     }
-}
 
-fn filter_installed_apps(source_roots: &[PathBuf], installed_apps: Vec<String>) -> Vec<String> {
-    installed_apps
-        .into_iter()
-        .filter(|app| filesystem::module_to_file_path(source_roots, app, false).is_some())
-        .collect()
+    visitor.fkeys
 }
 
-pub fn get_known_apps(
-    source_roots: &[PathBuf],
-    django_config: &DjangoConfig,
-) -> Result<Vec<String>> {
-    let settings_module_path = &django_config.settings_module;
-    let settings_module =
-        filesystem::module_to_file_path(source_roots, settings_module_path, false);
-    if let Some(settings_module) = settings_module {
-        let settings_file = filesystem::read_file_content(settings_module.file_path)?;
-        let settings_ast = parse_python_source(&settings_file)?;
-        let mut visitor = InstalledAppVisitor::new();
-
-        match settings_ast {
-            Mod::Module(ref module) => {
-                visitor.visit_body(&module.body);
-            }
-            Mod::Expression(_) => return Err(FKeyError::Parsing(ParsingError::InvalidSyntax)),
-        };
-
-        Ok(filter_installed_apps(source_roots, visitor.installed_apps))
-    } else {
-        Err(FKeyError::SettingsFileNotFound(
-            settings_module_path.to_string(),
-        ))
+/// Resolve a lazy model reference ('app_label.ModelName') against the installed apps,
+/// so that it refers to the module of the app with that label.
+/// References to apps outside the project are dropped, and unknown labels are kept as written.
+pub fn resolve_model_reference(
+    known_apps: &[DjangoApp],
+    mut reference: SourceCodeReference,
+) -> Option<SourceCodeReference> {
+    let Some((app_label, model_name)) = reference.module_path.split_once('.') else {
+        return Some(reference);
+    };
+    let Some(app) = known_apps.iter().find(|app| app.label == app_label) else {
+        return Some(reference);
+    };
+    if !app.in_project {
+        return None;
     }
+    reference.module_path = format!("{}.{}", app.module_path, model_name);
+    Some(reference)
 }
 
-pub fn get_foreign_key_references(file_ast: &Mod) -> impl IntoIterator<Item = SourceCodeReference> {
-    let mut visitor = FKeyVisitor::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruff_text_size::TextSize;
+
+    fn app(label: &str, module_path: &str, in_project: bool) -> DjangoApp {
+        DjangoApp {
+            entry: module_path.to_string(),
+            label: label.to_string(),
+            module_path: module_path.to_string(),
+            app_config: None,
+            in_project,
+        }
+    }
 
-    if let Mod::Module(module) = file_ast {
-        visitor.visit_body(&module.body);
+    fn resolve(known_apps: &[DjangoApp], model: &str) -> Option<String> {
+        resolve_model_reference(
+            known_apps,
+            SourceCodeReference::new(model.to_string(), TextSize::default(), "model relation"),
+        )
+        .map(|reference| reference.module_path)
     }
 
-    visitor.fkeys
+    #[test]
+    fn test_resolve_model_reference() {
+        let known_apps = vec![
+            app("invoices", "project.billing", true),
+            app("auth", "django.contrib.auth", false),
+        ];
+        assert_eq!(
+            resolve(&known_apps, "invoices.Invoice"),
+            Some("project.billing.Invoice".to_string())
+        );
+        assert_eq!(resolve(&known_apps, "auth.User"), None);
+        assert_eq!(
+            resolve(&known_apps, "users.User"),
+            Some("users.User".to_string())
+        );
+        assert_eq!(resolve(&known_apps, "Invoice"), Some("Invoice".to_string()));
+    }
 }
@@ -1 +1,4 @@
+pub mod apps;
 pub mod fkey;
+pub mod settings;
+pub mod urls;
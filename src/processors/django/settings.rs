@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use ruff_python_ast::statement_visitor::{walk_stmt, StatementVisitor};
+use ruff_python_ast::{Expr, Mod, Stmt};
+
+use crate::config::plugins::django::DjangoConfig;
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+
+// Settings whose values are dotted paths to project modules or members
+const DOTTED_PATH_SETTINGS: [&str; 4] = [
+    "ROOT_URLCONF",
+    "MIDDLEWARE",
+    "MIDDLEWARE_CLASSES",
+    "INSTALLED_APPS",
+];
+
+struct SettingsVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl SettingsVisitor<'_> {
+    fn add_references(&mut self, expr: &Expr) {
+        let elements = match expr {
+            Expr::List(list) => list.elts.as_slice(),
+            Expr::Tuple(tuple) => tuple.elts.as_slice(),
+            _ => std::slice::from_ref(expr),
+        };
+        for element in elements {
+            if let Expr::StringLiteral(s) = element {
+                let module_path = s.value.to_str();
+                if filesystem::is_project_import(self.source_roots, module_path) {
+                    self.references.push(SourceCodeReference::new(
+                        module_path.to_string(),
+                        s.range.start(),
+                        "setting",
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn is_dotted_path_setting(target: &Expr) -> bool {
+    matches!(target, Expr::Name(name) if DOTTED_PATH_SETTINGS.contains(&name.id.as_str()))
+}
+
+impl StatementVisitor<'_> for SettingsVisitor<'_> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Assign(assign) if assign.targets.iter().any(is_dotted_path_setting) => {
+                self.add_references(&assign.value);
+            }
+            // Match settings which are extended (INSTALLED_APPS += [...])
+            Stmt::AugAssign(assign) if is_dotted_path_setting(&assign.target) => {
+                self.add_references(&assign.value);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+}
+
+/// Whether the given module is the configured settings module, or part of a settings package.
+pub fn is_settings_module(django_config: &DjangoConfig, mod_path: &str) -> bool {
+    let settings_module = &django_config.settings_module;
+    !settings_module.is_empty()
+        && (mod_path == settings_module
+            || mod_path
+                .strip_prefix(settings_module.as_str())
+                .is_some_and(|rest| rest.starts_with('.')))
+}
+
+/// Find project modules referenced by dotted paths in Django settings,
+/// such as 'ROOT_URLCONF', 'MIDDLEWARE', and 'INSTALLED_APPS'.
+pub fn get_settings_references(
+    source_roots: &[PathBuf],
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = SettingsVisitor {
+        source_roots,
+        references: vec![],
+    };
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("project.settings", true)]
+    #[case("project.settings.base", true)]
+    #[case("project.settings_extra", false)]
+    #[case("project", false)]
+    fn test_is_settings_module(#[case] mod_path: &str, #[case] expected: bool) {
+        let django_config = DjangoConfig {
+            settings_module: "project.settings".to_string(),
+        };
+        assert_eq!(is_settings_module(&django_config, mod_path), expected);
+    }
+
+    #[test]
+    fn test_get_settings_references() {
+        let project = tempfile::tempdir().unwrap();
+        fs::create_dir_all(project.path().join("app")).unwrap();
+        for file in ["__init__.py", "urls.py", "middleware.py"] {
+            fs::write(project.path().join("app").join(file), "").unwrap();
+        }
+        let source = concat!(
+            "DEBUG = True\n",
+            "ROOT_URLCONF = \"app.urls\"\n",
+            "INSTALLED_APPS = [\"django.contrib.auth\", \"app\"]\n",
+            "MIDDLEWARE = (\"app.middleware.AuthMiddleware\",)\n",
+            "LOGIN_URL = \"app.urls\"\n",
+            "if DEBUG:\n",
+            "    MIDDLEWARE += (\"app.middleware.DebugMiddleware\",)\n",
+        );
+        let file_ast = parse_python_source(source).unwrap();
+
+        let references: Vec<_> =
+            get_settings_references(&[project.path().to_path_buf()], &file_ast)
+                .into_iter()
+                .map(|r| (r.module_path, usize::from(r.offset), r.construct))
+                .collect();
+
+        let expected: Vec<_> = [
+            ("app.urls", "\"app.urls\""),
+            ("app", "\"app\""),
+            (
+                "app.middleware.AuthMiddleware",
+                "\"app.middleware.AuthMiddleware",
+            ),
+            (
+                "app.middleware.DebugMiddleware",
+                "\"app.middleware.DebugMiddleware",
+            ),
+        ]
+        .into_iter()
+        .map(|(module_path, literal)| {
+            (
+                module_path.to_string(),
+                source.find(literal).unwrap(),
+                "setting",
+            )
+        })
+        .collect();
+        assert_eq!(references, expected);
+    }
+}
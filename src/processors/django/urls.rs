@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod};
+
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+
+struct UrlIncludeVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl Visitor<'_> for UrlIncludeVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call_expr) = expr {
+            let is_include = match &*call_expr.func {
+                // Match direct name (include)
+                Expr::Name(name) => name.id.as_str() == "include",
+                // Match attribute access (urls.include, django.urls.include, etc.)
+                Expr::Attribute(attr) => attr.attr.as_str() == "include",
+                _ => false,
+            };
+
+            if is_include {
+                // The URLconf may be passed directly, or as the first element of a tuple
+                let urlconf = match call_expr.arguments.args.first() {
+                    Some(Expr::Tuple(tuple)) => tuple.elts.first(),
+                    arg => arg,
+                };
+                if let Some(Expr::StringLiteral(s)) = urlconf {
+                    let module_path = s.value.to_str();
+                    if filesystem::is_project_import(self.source_roots, module_path) {
+                        self.references.push(SourceCodeReference::new(
+                            module_path.to_string(),
+                            s.range.start(),
+                            "include()",
+                        ));
+                    }
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find URLconf modules referenced with 'include("app.urls")'.
+pub fn get_url_include_references(
+    source_roots: &[PathBuf],
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = UrlIncludeVisitor {
+        source_roots,
+        references: vec![],
+    };
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+
+    #[test]
+    fn test_get_url_include_references() {
+        let project = tempfile::tempdir().unwrap();
+        fs::create_dir_all(project.path().join("app")).unwrap();
+        for file in ["__init__.py", "urls.py"] {
+            fs::write(project.path().join("app").join(file), "").unwrap();
+        }
+        let source = concat!(
+            "from django import urls\n",
+            "from django.urls import include, path\n",
+            "\n",
+            "urlpatterns = [\n",
+            "    path(\"app/\", include(\"app.urls\")),\n",
+            "    path(\"api/\", urls.include((\"app.urls\", \"app\"), namespace=\"api\")),\n",
+            "    path(\"admin/\", include(\"django.contrib.admin.urls\")),\n",
+            "]\n",
+        );
+        let file_ast = parse_python_source(source).unwrap();
+
+        let references: Vec<_> =
+            get_url_include_references(&[project.path().to_path_buf()], &file_ast)
+                .into_iter()
+                .map(|r| (r.module_path, usize::from(r.offset), r.construct))
+                .collect();
+
+        assert_eq!(
+            references,
+            vec![
+                (
+                    "app.urls".to_string(),
+                    source.find("\"app.urls\"").unwrap(),
+                    "include()"
+                ),
+                (
+                    "app.urls".to_string(),
+                    source.rfind("\"app.urls\"").unwrap(),
+                    "include()"
+                ),
+            ]
+        );
+    }
+}
@@ -3,7 +3,10 @@ use ruff_source_file::LineIndex;
 use ruff_text_size::TextSize;
 use std::{path::Path, sync::Arc};
 
+use crate::diagnostics::SourceSpan;
 use crate::filesystem::ProjectFile;
+use crate::python::parsing::SyntaxError;
+use crate::python::syntax::UnsupportedSyntax;
 use crate::{config::ModuleConfig, modules::ModuleNode};
 
 use super::dependency::Dependency;
@@ -17,6 +20,10 @@ pub struct FileModule<'a> {
     pub module: Arc<ModuleNode>,
     pub ignore_directives: IgnoreDirectives,
     pub dependencies: Vec<Dependency>,
+    // Dependencies are only extracted from the statements before a syntax error
+    pub syntax_error: Option<SyntaxError>,
+    // Syntax which is newer than the configured 'python_version'
+    pub unsupported_syntax: Vec<UnsupportedSyntax>,
     line_index: LineIndex,
 }
 
@@ -28,6 +35,8 @@ impl<'a> FileModule<'a> {
             file,
             module,
             dependencies: vec![],
+            syntax_error: None,
+            unsupported_syntax: vec![],
         }
     }
 
@@ -43,6 +52,32 @@ impl<'a> FileModule<'a> {
         self.line_index.line_index(offset).get()
     }
 
+    pub fn span(&self, offset: TextSize) -> SourceSpan {
+        let contents = self.contents();
+        let start_offset = usize::from(offset).min(contents.len());
+        let line_start = contents[..start_offset].rfind('\n').map_or(0, |i| i + 1);
+        // Extend the span over the dotted name at this offset
+        let name_length: usize = contents[start_offset..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+            .map(char::len_utf8)
+            .sum();
+        SourceSpan {
+            start_offset,
+            end_offset: start_offset + name_length,
+            column: contents[line_start..start_offset].chars().count() + 1,
+        }
+    }
+
+    /// Record a syntax error, after which no dependencies were extracted
+    pub fn set_syntax_error(&mut self, syntax_error: Option<SyntaxError>) {
+        if let Some(syntax_error) = &syntax_error {
+            let line_number = self.line_number(syntax_error.offset);
+            self.ignore_directives.remove_directives_from(line_number);
+        }
+        self.syntax_error = syntax_error;
+    }
+
     pub fn module_config(&self) -> &ModuleConfig {
         self.module.config.as_ref().unwrap()
     }
@@ -67,7 +102,7 @@ impl<'a> FileModule<'a> {
 
     pub fn references(&self) -> impl Iterator<Item = &SourceCodeReference> {
         self.dependencies.iter().filter_map(|dependency| {
-            if let Dependency::Reference(reference) = dependency {
+            if let Dependency::Reference(_, reference) = dependency {
                 Some(reference)
             } else {
                 None
@@ -81,3 +116,55 @@ impl<'a> AsRef<FileModule<'a>> for FileModule<'a> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn file_module(contents: &str) -> FileModule<'static> {
+        let root = Path::new("");
+        FileModule::new(
+            ProjectFile {
+                project_root: root,
+                source_root: root,
+                file_path: PathBuf::from("app/views.py"),
+                relative_file_path: PathBuf::from("app/views.py"),
+                contents: contents.to_string(),
+            },
+            Arc::new(ModuleNode::implicit_root()),
+        )
+    }
+
+    #[test]
+    fn test_span_on_multibyte_line() {
+        // 'é' and '→' take two and three bytes, but count as a single column each
+        let contents = "x = 1\nname = 'é→'; import billing.api\n";
+        let file_module = file_module(contents);
+        let offset = contents.find("billing").unwrap();
+
+        let span = file_module.span(TextSize::try_from(offset).unwrap());
+
+        assert_eq!(
+            file_module.line_number(TextSize::try_from(offset).unwrap()),
+            2
+        );
+        assert_eq!(span.start_offset, offset);
+        assert_eq!(&contents[span.start_offset..span.end_offset], "billing.api");
+        assert_eq!(span.column, 21);
+    }
+
+    #[test]
+    fn test_span_over_multibyte_name() {
+        let contents = "from données import café\n";
+        let file_module = file_module(contents);
+        let offset = contents.find("données").unwrap();
+
+        let span = file_module.span(TextSize::try_from(offset).unwrap());
+
+        assert_eq!(&contents[span.start_offset..span.end_offset], "données");
+        assert_eq!(span.end_offset - span.start_offset, "données".len());
+        assert_eq!(span.column, 6);
+    }
+}
@@ -108,6 +108,14 @@ impl IgnoreDirectives {
         self.redundant_directives
             .retain(|directive| directive.line_no != import_line_no);
     }
+
+    /// Remove directives for lines at or after 'line_no', such as lines which were not parsed
+    pub fn remove_directives_from(&mut self, line_no: usize) {
+        self.directives
+            .retain(|ignored_line_no, _directive| *ignored_line_no < line_no);
+        self.redundant_directives
+            .retain(|directive| directive.ignored_line_no < line_no);
+    }
 }
 
 impl Extend<IgnoreDirectives> for IgnoreDirectives {
@@ -224,4 +232,16 @@ mod tests {
         let directives = get_ignore_directives(content);
         assert!(directives.is_empty());
     }
+
+    #[test]
+    fn test_remove_directives_from() {
+        let content = "from foo import bar  # tach-ignore\n# tach-ignore\nfrom baz import qux";
+        let mut directives = get_ignore_directives(content);
+        assert_eq!(directives.len(), 2);
+
+        directives.remove_directives_from(2);
+        assert_eq!(directives.len(), 1);
+        assert!(directives.get(&1).is_some());
+        assert!(directives.get(&3).is_none());
+    }
 }
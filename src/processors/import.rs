@@ -10,16 +10,13 @@ use thiserror::Error;
 
 use crate::external::parsing::normalize_package_name;
 use crate::filesystem;
-use crate::python::{error::ParsingError, parsing::parse_python_source};
+
+use super::import_cache::{ImportCache, ImportOptions};
 
 #[derive(Error, Debug)]
 pub enum ImportParseError {
-    #[error("Failed to parse project imports.\nFile: {file}\nFailure: {source}")]
-    Parsing {
-        file: String,
-        #[source]
-        source: ParsingError,
-    },
+    #[error("Failed to parse project imports.\nFile: {file}\nFailure: {message}")]
+    Parsing { file: String, message: String },
     #[error("Failed to parse project imports.\n{0}")]
     Filesystem(#[from] filesystem::FileSystemError),
 }
@@ -34,6 +31,7 @@ pub struct NormalizedImport {
     pub import_offset: TextSize,    // Source location of the import statement
     pub alias_offset: TextSize,     // Source location of the alias
     pub is_absolute: bool,          // Whether the import is absolute
+    pub runs_at_import: bool,       // Whether the import runs as soon as its file is imported
 }
 
 impl NormalizedImport {
@@ -84,6 +82,10 @@ impl LocatedImport {
     pub fn is_absolute(&self) -> bool {
         self.import.is_absolute
     }
+
+    pub fn runs_at_import(&self) -> bool {
+        self.import.runs_at_import
+    }
 }
 
 pub struct AllImports;
@@ -96,6 +98,9 @@ pub struct ExternalImportWithDistributionNames<'a> {
     pub import: &'a NormalizedImport,
 }
 
+/// The suffix of PEP 561 stub-only packages, such as 'foo-stubs' for 'foo'.
+const STUB_PACKAGE_SUFFIX: &str = "-stubs";
+
 pub fn with_distribution_names<'a, I>(
     imports: I,
     module_mappings: &HashMap<String, Vec<String>>,
@@ -106,7 +111,14 @@ where
     imports
         .map(|import| {
             let top_level_module_name = import.top_level_module_name().to_string();
-            let default_distribution_names = vec![top_level_module_name.clone()];
+            // Without a known mapping, the module may be provided by a PEP 561 stub-only package
+            let default_distribution_names = vec![
+                top_level_module_name.clone(),
+                normalize_package_name(&format!(
+                    "{}{}",
+                    top_level_module_name, STUB_PACKAGE_SUFFIX
+                )),
+            ];
             let distribution_names: Vec<String> = module_mappings
                 .get(&top_level_module_name)
                 .map(|dist_names| {
@@ -129,6 +141,8 @@ pub struct ImportVisitor {
     file_mod_path: Option<String>,
     is_package: bool,
     ignore_type_checking_imports: bool,
+    // Depth of function bodies and TYPE_CHECKING blocks around the current statement
+    deferred_depth: usize,
     pub normalized_imports: Vec<NormalizedImport>,
 }
 
@@ -142,6 +156,7 @@ impl ImportVisitor {
             file_mod_path,
             is_package,
             ignore_type_checking_imports,
+            deferred_depth: 0,
             normalized_imports: Default::default(),
         }
     }
@@ -159,6 +174,7 @@ impl ImportVisitor {
                 alias_offset: alias.range.start(),
                 import_offset: import_statement.range.start(),
                 is_absolute: true,
+                runs_at_import: self.deferred_depth == 0,
             };
             normalized_imports.push(import);
         }
@@ -229,6 +245,7 @@ impl ImportVisitor {
                 alias_offset: name.range.start(),
                 import_offset: import_statement.range.start(),
                 is_absolute: false,
+                runs_at_import: self.deferred_depth == 0,
             };
 
             normalized_imports.push(import);
@@ -237,7 +254,7 @@ impl ImportVisitor {
         normalized_imports
     }
 
-    fn should_ignore_if_statement(&mut self, node: &StmtIf) -> bool {
+    fn is_type_checking_block(&self, node: &StmtIf) -> bool {
         let id = match node.test.as_ref() {
             Expr::Name(ref name) => Some(name.id.as_str()),
             // This will match a single-level attribute access in cases like:
@@ -245,7 +262,13 @@ impl ImportVisitor {
             Expr::Attribute(ref attribute) => Some(attribute.attr.as_str()),
             _ => None,
         };
-        id.unwrap_or_default() == "TYPE_CHECKING" && self.ignore_type_checking_imports
+        id.unwrap_or_default() == "TYPE_CHECKING"
+    }
+
+    fn visit_deferred_body(&mut self, body: &[Stmt]) {
+        self.deferred_depth += 1;
+        self.visit_body(body);
+        self.deferred_depth -= 1;
     }
 
     fn visit_stmt_import(&mut self, node: &StmtImport) {
@@ -264,11 +287,16 @@ impl StatementVisitor<'_> for ImportVisitor {
         match stmt {
             Stmt::Import(statement) => self.visit_stmt_import(statement),
             Stmt::ImportFrom(statement) => self.visit_stmt_import_from(statement),
-            Stmt::If(statement) => {
-                if !self.should_ignore_if_statement(statement) {
-                    walk_stmt(self, stmt)
+            Stmt::If(statement) if self.is_type_checking_block(statement) => {
+                if !self.ignore_type_checking_imports {
+                    // Only the 'else' branches of a TYPE_CHECKING block run at runtime
+                    self.visit_deferred_body(&statement.body);
+                    for clause in &statement.elif_else_clauses {
+                        self.visit_body(&clause.body);
+                    }
                 }
             }
+            Stmt::FunctionDef(statement) => self.visit_deferred_body(&statement.body),
             _ => walk_stmt(self, stmt),
         }
     }
@@ -304,6 +332,7 @@ impl Visitor<'_> for StringImportVisitor<'_> {
                 alias_offset: string_literal.range.start(),
                 import_offset: string_literal.range.start(),
                 is_absolute: true,
+                runs_at_import: false,
             });
         }
     }
@@ -350,6 +379,8 @@ pub fn get_normalized_imports_from_ast<P: AsRef<Path>>(
     }
 }
 
+/// Get the imports of a file, failing if it contains a syntax error.
+/// Results are shared with every other command through the [`ImportCache`].
 pub fn get_normalized_imports<P: AsRef<Path>>(
     source_roots: &[PathBuf],
     file_path: P,
@@ -357,15 +388,17 @@ pub fn get_normalized_imports<P: AsRef<Path>>(
     ignore_type_checking_imports: bool,
     include_string_imports: bool,
 ) -> Result<Vec<NormalizedImport>> {
-    let file_ast = parse_python_source(file_contents).map_err(|err| ImportParseError::Parsing {
-        file: file_path.as_ref().to_string_lossy().to_string(),
-        source: err,
-    })?;
-    get_normalized_imports_from_ast(
+    let file_imports = ImportCache::global().get_imports(
         source_roots,
-        file_path,
-        &file_ast,
-        ignore_type_checking_imports,
-        include_string_imports,
-    )
+        file_path.as_ref(),
+        file_contents,
+        ImportOptions::new(ignore_type_checking_imports, include_string_imports),
+    )?;
+    if let Some(syntax_error) = &file_imports.syntax_error {
+        return Err(ImportParseError::Parsing {
+            file: file_path.as_ref().to_string_lossy().to_string(),
+            message: syntax_error.message.clone(),
+        });
+    }
+    Ok(file_imports.imports.clone())
 }
@@ -0,0 +1,302 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use ruff_python_ast::Mod;
+
+use crate::python::parsing::{parse_python_source_partial, SyntaxError};
+
+use super::import::{get_normalized_imports_from_ast, NormalizedImport, Result};
+
+/// The settings which change which imports are extracted from a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImportOptions {
+    pub ignore_type_checking_imports: bool,
+    pub include_string_imports: bool,
+}
+
+impl ImportOptions {
+    pub fn new(ignore_type_checking_imports: bool, include_string_imports: bool) -> Self {
+        Self {
+            ignore_type_checking_imports,
+            include_string_imports,
+        }
+    }
+}
+
+/// The normalized imports of a file, along with the first syntax error found while parsing it.
+/// When there is a syntax error, only imports before it are included.
+#[derive(Debug)]
+pub struct FileImports {
+    pub imports: Vec<NormalizedImport>,
+    pub syntax_error: Option<SyntaxError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CacheKey {
+    source_roots_hash: u64,
+    options: ImportOptions,
+}
+
+#[derive(Debug)]
+struct CachedFile {
+    content_hash: u64,
+    entries: HashMap<CacheKey, Arc<FileImports>>,
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// The most files kept at once, so that a long-running server does not grow without bound
+const MAX_CACHED_FILES: usize = 50_000;
+
+/// Normalized imports memoized by file path and content hash, shared by every command
+/// which runs in this process, so that a file is parsed once per invocation or server session.
+/// Only the latest contents of each file are kept, so edits replace entries instead of adding them.
+/// String imports are never memoized, since they depend on which files exist rather than on contents.
+#[derive(Debug, Default)]
+pub struct ImportCache {
+    files: DashMap<PathBuf, CachedFile>,
+}
+
+static IMPORT_CACHE: Lazy<ImportCache> = Lazy::new(ImportCache::default);
+
+impl ImportCache {
+    pub fn global() -> &'static ImportCache {
+        &IMPORT_CACHE
+    }
+
+    fn get(&self, file_path: &Path, content_hash: u64, key: &CacheKey) -> Option<Arc<FileImports>> {
+        let cached_file = self.files.get(file_path)?;
+        if cached_file.content_hash != content_hash {
+            return None;
+        }
+        cached_file.entries.get(key).cloned()
+    }
+
+    fn insert(
+        &self,
+        file_path: &Path,
+        content_hash: u64,
+        key: CacheKey,
+        file_imports: Arc<FileImports>,
+    ) {
+        if !self.files.contains_key(file_path) && self.files.len() >= MAX_CACHED_FILES {
+            self.evict_any();
+        }
+        let mut cached_file = self
+            .files
+            .entry(file_path.to_path_buf())
+            .or_insert_with(|| CachedFile {
+                content_hash,
+                entries: HashMap::new(),
+            });
+        if cached_file.content_hash != content_hash {
+            cached_file.content_hash = content_hash;
+            cached_file.entries.clear();
+        }
+        cached_file.entries.insert(key, file_imports);
+    }
+
+    fn evict_any(&self) {
+        // The key is cloned so that no shard lock is held while removing
+        let evicted = self.files.iter().next().map(|entry| entry.key().clone());
+        if let Some(file_path) = evicted {
+            self.files.remove(&file_path);
+        }
+    }
+
+    /// Get the imports of a file, extracting them with 'extract' on a cache miss.
+    fn get_or_extract(
+        &self,
+        source_roots: &[PathBuf],
+        file_path: &Path,
+        contents: &str,
+        options: ImportOptions,
+        extract: impl FnOnce() -> Result<FileImports>,
+    ) -> Result<Arc<FileImports>> {
+        if options.include_string_imports {
+            return Ok(Arc::new(extract()?));
+        }
+        let content_hash = hash_of(contents);
+        let key = CacheKey {
+            source_roots_hash: hash_of(source_roots),
+            options,
+        };
+        if let Some(file_imports) = self.get(file_path, content_hash, &key) {
+            return Ok(file_imports);
+        }
+        let file_imports = Arc::new(extract()?);
+        self.insert(file_path, content_hash, key, Arc::clone(&file_imports));
+        Ok(file_imports)
+    }
+
+    /// Get the imports of a file, parsing it on a cache miss.
+    pub fn get_imports(
+        &self,
+        source_roots: &[PathBuf],
+        file_path: &Path,
+        contents: &str,
+        options: ImportOptions,
+    ) -> Result<Arc<FileImports>> {
+        self.get_or_extract(source_roots, file_path, contents, options, || {
+            let (file_ast, syntax_error) = parse_python_source_partial(contents);
+            Ok(FileImports {
+                imports: get_normalized_imports_from_ast(
+                    source_roots,
+                    file_path,
+                    &file_ast,
+                    options.ignore_type_checking_imports,
+                    options.include_string_imports,
+                )?,
+                syntax_error,
+            })
+        })
+    }
+
+    /// Get the imports of a file which the caller has already parsed for other reasons.
+    pub fn get_imports_from_ast(
+        &self,
+        source_roots: &[PathBuf],
+        file_path: &Path,
+        contents: &str,
+        file_ast: &Mod,
+        syntax_error: Option<&SyntaxError>,
+        options: ImportOptions,
+    ) -> Result<Arc<FileImports>> {
+        self.get_or_extract(source_roots, file_path, contents, options, || {
+            Ok(FileImports {
+                imports: get_normalized_imports_from_ast(
+                    source_roots,
+                    file_path,
+                    file_ast,
+                    options.ignore_type_checking_imports,
+                    options.include_string_imports,
+                )?,
+                syntax_error: syntax_error.cloned(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: ImportOptions = ImportOptions {
+        ignore_type_checking_imports: false,
+        include_string_imports: false,
+    };
+
+    #[test]
+    fn test_imports_are_memoized_by_content() {
+        let cache = ImportCache::default();
+        let file_path = Path::new("module.py");
+        let first = cache
+            .get_imports(&[], file_path, "import os\n", OPTIONS)
+            .unwrap();
+        let second = cache
+            .get_imports(&[], file_path, "import os\n", OPTIONS)
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let changed = cache
+            .get_imports(&[], file_path, "import os\nimport sys\n", OPTIONS)
+            .unwrap();
+        assert_eq!(changed.imports.len(), 2);
+        assert_eq!(cache.files.get(file_path).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_imports_are_memoized_by_options() {
+        let cache = ImportCache::default();
+        let file_path = Path::new("module.py");
+        let contents = "from typing import TYPE_CHECKING\nif TYPE_CHECKING:\n    import os\n";
+        let all = cache
+            .get_imports(&[], file_path, contents, OPTIONS)
+            .unwrap();
+        let runtime = cache
+            .get_imports(&[], file_path, contents, ImportOptions::new(true, false))
+            .unwrap();
+        assert_eq!(all.imports.len(), 2);
+        assert_eq!(runtime.imports.len(), 1);
+    }
+
+    #[test]
+    fn test_deferred_imports_do_not_run_at_import() {
+        let cache = ImportCache::default();
+        let contents = "import os\nfrom typing import TYPE_CHECKING\nif TYPE_CHECKING:\n    import json\nelse:\n    import sys\ndef f():\n    import re\nclass A:\n    import abc\n";
+        let file_imports = cache
+            .get_imports(&[], Path::new("module.py"), contents, OPTIONS)
+            .unwrap();
+        let runs_at_import: Vec<(&str, bool)> = file_imports
+            .imports
+            .iter()
+            .map(|import| (import.module_path.as_str(), import.runs_at_import))
+            .collect();
+        assert_eq!(
+            runs_at_import,
+            vec![
+                ("os", true),
+                ("typing.TYPE_CHECKING", true),
+                ("json", false),
+                ("sys", true),
+                ("re", false),
+                ("abc", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_imports_are_not_memoized() {
+        let cache = ImportCache::default();
+        let file_path = Path::new("module.py");
+        let options = ImportOptions::new(false, true);
+        let first = cache
+            .get_imports(&[], file_path, "import os\n", options)
+            .unwrap();
+        let second = cache
+            .get_imports(&[], file_path, "import os\n", options)
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert!(cache.files.is_empty());
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let cache = ImportCache::default();
+        for index in 0..MAX_CACHED_FILES + 1 {
+            let file_path = PathBuf::from(format!("module_{}.py", index));
+            cache.insert(
+                &file_path,
+                0,
+                CacheKey {
+                    source_roots_hash: 0,
+                    options: OPTIONS,
+                },
+                Arc::new(FileImports {
+                    imports: vec![],
+                    syntax_error: None,
+                }),
+            );
+        }
+        assert_eq!(cache.files.len(), MAX_CACHED_FILES);
+    }
+
+    #[test]
+    fn test_syntax_error_is_cached() {
+        let cache = ImportCache::default();
+        let file_imports = cache
+            .get_imports(&[], Path::new("module.py"), "import os\ndef (:\n", OPTIONS)
+            .unwrap();
+        assert_eq!(file_imports.imports.len(), 1);
+        assert!(file_imports.syntax_error.is_some());
+    }
+}
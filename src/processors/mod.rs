@@ -1,9 +1,18 @@
+pub mod airflow;
+pub mod celery;
 pub mod dependency;
+pub mod dependency_injector;
 pub mod django;
 pub mod file_module;
 pub mod ignore_directive;
 pub mod import;
+pub mod import_cache;
+pub mod protobuf;
 pub mod reference;
+pub mod reference_pattern;
+pub mod sqlalchemy;
+pub mod typing;
 
 pub use dependency::{Dependency, ExternalDependencyExtractor, InternalDependencyExtractor};
 pub use file_module::FileModule;
+pub use import_cache::{ImportCache, ImportOptions};
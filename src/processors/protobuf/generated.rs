@@ -0,0 +1,74 @@
+use crate::config::plugins::protobuf::ProtobufConfig;
+
+const GENERATED_MODULE_SUFFIXES: [&str; 2] = ["_pb2", "_pb2_grpc"];
+
+fn is_generated_module(segment: &str) -> bool {
+    GENERATED_MODULE_SUFFIXES
+        .iter()
+        .any(|suffix| segment.ends_with(suffix))
+}
+
+fn is_within_package(module_path: &str, package: &str) -> bool {
+    module_path == package
+        || module_path
+            .strip_prefix(package)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Attributes paths within generated protobuf modules to the module which owns their package.
+#[derive(Debug)]
+pub struct ProtobufMappings {
+    // Ordered from the most to least specific package
+    mappings: Vec<(String, String)>,
+}
+
+impl ProtobufMappings {
+    pub fn new(config: &ProtobufConfig) -> Self {
+        let mut mappings: Vec<(String, String)> = config
+            .mappings
+            .iter()
+            .map(|(package, module)| (package.clone(), module.clone()))
+            .collect();
+        mappings.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        Self { mappings }
+    }
+
+    pub fn owning_module(&self, module_path: &str) -> Option<&str> {
+        if !module_path.split('.').any(is_generated_module) {
+            return None;
+        }
+        self.mappings
+            .iter()
+            .find(|(package, _)| is_within_package(module_path, package))
+            .map(|(_, module)| module.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("gen.billing.invoice_pb2", Some("billing"))]
+    #[case("gen.billing.invoice_pb2_grpc.InvoiceServicer", Some("billing"))]
+    #[case("gen.billing.v2.invoice_pb2.Invoice", Some("billing.v2"))]
+    #[case("gen.users.user_pb2.User", Some("users"))]
+    #[case("gen.billing.helpers", None)]
+    #[case("gen.billingx.invoice_pb2", None)]
+    #[case("other.invoice_pb2", None)]
+    fn test_owning_module(#[case] module_path: &str, #[case] expected: Option<&str>) {
+        let config = ProtobufConfig {
+            mappings: [
+                ("gen.billing", "billing"),
+                ("gen.billing.v2", "billing.v2"),
+                ("gen.users", "users"),
+            ]
+            .into_iter()
+            .map(|(package, module)| (package.to_string(), module.to_string()))
+            .collect(),
+        };
+        let mappings = ProtobufMappings::new(&config);
+        assert_eq!(mappings.owning_module(module_path), expected);
+    }
+}
@@ -4,13 +4,16 @@ use ruff_text_size::TextSize;
 pub struct SourceCodeReference {
     pub module_path: String,
     pub offset: TextSize,
+    /// The construct which makes the reference, e.g. 'include()' for a Django URLconf.
+    pub construct: &'static str,
 }
 
 impl SourceCodeReference {
-    pub fn new(module_path: String, offset: TextSize) -> Self {
+    pub fn new(module_path: String, offset: TextSize, construct: &'static str) -> Self {
         Self {
             module_path,
             offset,
+            construct,
         }
     }
 }
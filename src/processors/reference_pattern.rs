@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use regex::Regex;
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod, StringLiteral};
+use ruff_text_size::TextSize;
+use thiserror::Error;
+
+use crate::config::plugins::references::ReferencePatternConfig;
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+
+#[derive(Error, Debug)]
+pub enum ReferencePatternError {
+    #[error("Reference pattern must set exactly one of 'call' or 'regex'.")]
+    InvalidPattern,
+    #[error("Failed to build regex for reference pattern:\n{pattern}\n{source}")]
+    Regex {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+}
+
+#[derive(Debug)]
+pub enum ReferencePattern {
+    Call {
+        name: String,
+        argument: usize,
+        keyword: Option<String>,
+    },
+    Regex(Regex),
+}
+
+impl TryFrom<&ReferencePatternConfig> for ReferencePattern {
+    type Error = ReferencePatternError;
+
+    fn try_from(config: &ReferencePatternConfig) -> Result<Self, Self::Error> {
+        match (&config.call, &config.regex) {
+            (Some(name), None) => Ok(ReferencePattern::Call {
+                name: name.clone(),
+                argument: config.argument,
+                keyword: config.keyword.clone(),
+            }),
+            (None, Some(pattern)) => {
+                // Patterns must match the entire string literal
+                let anchored = format!("^(?:{})$", pattern);
+                Regex::new(&anchored)
+                    .map(ReferencePattern::Regex)
+                    .map_err(|source| ReferencePatternError::Regex {
+                        pattern: pattern.clone(),
+                        source,
+                    })
+            }
+            _ => Err(ReferencePatternError::InvalidPattern),
+        }
+    }
+}
+
+fn dotted_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Name(name) => Some(name.id.to_string()),
+        Expr::Attribute(attr) => {
+            dotted_name(&attr.value).map(|value| format!("{}.{}", value, attr.attr))
+        }
+        _ => None,
+    }
+}
+
+fn call_matches(pattern_name: &str, call_name: &str) -> bool {
+    // Match either the full dotted name (app.register) or its trailing segments (register)
+    call_name == pattern_name
+        || call_name
+            .strip_suffix(pattern_name)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+struct ReferencePatternVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    patterns: &'a [ReferencePattern],
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl ReferencePatternVisitor<'_> {
+    fn add_reference(&mut self, module_path: &str, offset: TextSize) {
+        if filesystem::is_project_import(self.source_roots, module_path) {
+            self.references.push(SourceCodeReference::new(
+                module_path.to_string(),
+                offset,
+                "reference pattern",
+            ));
+        }
+    }
+}
+
+impl Visitor<'_> for ReferencePatternVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call_expr) = expr {
+            if let Some(call_name) = dotted_name(&call_expr.func) {
+                for pattern in self.patterns {
+                    let ReferencePattern::Call {
+                        name,
+                        argument,
+                        keyword,
+                    } = pattern
+                    else {
+                        continue;
+                    };
+                    if !call_matches(name, &call_name) {
+                        continue;
+                    }
+                    let value = call_expr.arguments.args.get(*argument).or_else(|| {
+                        keyword
+                            .as_ref()
+                            .and_then(|keyword| call_expr.arguments.find_keyword(keyword))
+                            .map(|keyword| &keyword.value)
+                    });
+                    if let Some(Expr::StringLiteral(s)) = value {
+                        self.add_reference(s.value.to_str(), s.range.start());
+                    }
+                }
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn visit_string_literal(&mut self, string_literal: &StringLiteral) {
+        for pattern in self.patterns {
+            let ReferencePattern::Regex(regex) = pattern else {
+                continue;
+            };
+            if let Some(captures) = regex.captures(&string_literal.value) {
+                let module_path = captures.get(1).or_else(|| captures.get(0));
+                if let Some(module_path) = module_path {
+                    self.add_reference(module_path.as_str(), string_literal.range.start());
+                }
+            }
+        }
+    }
+}
+
+pub fn get_reference_patterns(
+    configs: &[ReferencePatternConfig],
+) -> Result<Vec<ReferencePattern>, ReferencePatternError> {
+    configs.iter().map(ReferencePattern::try_from).collect()
+}
+
+/// Find string references to project modules which match the user-defined reference patterns.
+pub fn get_pattern_references(
+    source_roots: &[PathBuf],
+    patterns: &[ReferencePattern],
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = ReferencePatternVisitor {
+        source_roots,
+        patterns,
+        references: vec![],
+    };
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+    use crate::tests::fixtures::example_dir;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_pattern_references(example_dir: PathBuf) {
+        let source_roots = vec![example_dir.join("valid")];
+        let patterns = get_reference_patterns(&[
+            ReferencePatternConfig {
+                call: Some("registry.register".to_string()),
+                argument: 1,
+                keyword: Some("target".to_string()),
+                ..Default::default()
+            },
+            ReferencePatternConfig {
+                regex: Some(r"handler:(.+)".to_string()),
+                ..Default::default()
+            },
+        ])
+        .unwrap();
+        let file_ast = parse_python_source(
+            r#"
+app.registry.register("name", "domain_two.other")
+registry.register(target="domain_two.some_file")
+other.register("name", "domain_two.other")
+HANDLER = "handler:domain_two.other"
+"#,
+        )
+        .unwrap();
+
+        let references: Vec<String> = get_pattern_references(&source_roots, &patterns, &file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect();
+        assert_eq!(
+            references,
+            [
+                "domain_two.other",
+                "domain_two.some_file",
+                "domain_two.other"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(get_reference_patterns(&[ReferencePatternConfig::default()]).is_err());
+    }
+}
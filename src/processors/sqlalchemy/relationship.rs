@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use ruff_python_ast::statement_visitor::{walk_stmt, StatementVisitor};
+use ruff_python_ast::visitor::{walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod, Stmt};
+
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+use crate::python::parsing::parse_python_source;
+
+/// Project-wide index of SQLAlchemy models and tables, used to resolve string references
+/// which name a model class or table rather than a module path.
+#[derive(Debug, Default)]
+pub struct SqlAlchemyModels {
+    // Names which are defined more than once map to None, since they cannot be resolved
+    tables: HashMap<String, Option<String>>,
+    classes: HashMap<String, Option<String>>,
+}
+
+fn insert_unique(map: &mut HashMap<String, Option<String>>, name: String, path: String) {
+    map.entry(name)
+        .and_modify(|existing| {
+            if existing.as_ref() != Some(&path) {
+                *existing = None;
+            }
+        })
+        .or_insert(Some(path));
+}
+
+impl SqlAlchemyModels {
+    pub fn table(&self, table_name: &str) -> Option<&str> {
+        self.tables.get(table_name)?.as_deref()
+    }
+
+    pub fn class(&self, class_name: &str) -> Option<&str> {
+        self.classes.get(class_name)?.as_deref()
+    }
+}
+
+struct ModelDefinitionVisitor<'a> {
+    file_mod_path: &'a str,
+    models: &'a mut SqlAlchemyModels,
+}
+
+fn string_value(expr: &Expr) -> Option<String> {
+    if let Expr::StringLiteral(s) = expr {
+        Some(s.value.to_string())
+    } else {
+        None
+    }
+}
+
+fn call_name(expr: &Expr) -> Option<&str> {
+    if let Expr::Call(call_expr) = expr {
+        match &*call_expr.func {
+            Expr::Name(name) => Some(name.id.as_str()),
+            Expr::Attribute(attr) => Some(attr.attr.as_str()),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+impl StatementVisitor<'_> for ModelDefinitionVisitor<'_> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::ClassDef(class_def) => {
+                let table_name = class_def.body.iter().find_map(|stmt| match stmt {
+                    Stmt::Assign(assign) => match assign.targets.as_slice() {
+                        [Expr::Name(name)] if name.id.as_str() == "__tablename__" => {
+                            string_value(&assign.value)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                });
+                if let Some(table_name) = table_name {
+                    let class_path = format!("{}.{}", self.file_mod_path, class_def.name);
+                    insert_unique(&mut self.models.tables, table_name, class_path.clone());
+                    insert_unique(
+                        &mut self.models.classes,
+                        class_def.name.to_string(),
+                        class_path,
+                    );
+                }
+            }
+            // Match tables defined directly (users = Table("users", metadata, ...))
+            Stmt::Assign(assign) if call_name(&assign.value) == Some("Table") => {
+                if let ([Expr::Name(name)], Expr::Call(call_expr)) =
+                    (assign.targets.as_slice(), assign.value.as_ref())
+                {
+                    if let Some(table_name) =
+                        call_expr.arguments.args.first().and_then(string_value)
+                    {
+                        insert_unique(
+                            &mut self.models.tables,
+                            table_name,
+                            format!("{}.{}", self.file_mod_path, name.id),
+                        );
+                    }
+                }
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+}
+
+pub fn get_sqlalchemy_models(source_roots: &[PathBuf]) -> SqlAlchemyModels {
+    let mut models = SqlAlchemyModels::default();
+    for source_root in source_roots {
+        for file_path in filesystem::walk_pyfiles(&source_root.display().to_string()) {
+            let file_path = source_root.join(file_path);
+            let Ok(contents) = filesystem::read_file_content(&file_path) else {
+                continue;
+            };
+            // Avoid parsing files which cannot define any models
+            if !contents.contains("__tablename__") && !contents.contains("Table(") {
+                continue;
+            }
+            let (Ok(file_mod_path), Ok(Mod::Module(module))) = (
+                filesystem::file_to_module_path(source_roots, &file_path),
+                parse_python_source(&contents),
+            ) else {
+                continue;
+            };
+            let mut visitor = ModelDefinitionVisitor {
+                file_mod_path: &file_mod_path,
+                models: &mut models,
+            };
+            visitor.visit_body(&module.body);
+        }
+    }
+    models
+}
+
+struct RelationshipVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    models: &'a SqlAlchemyModels,
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl RelationshipVisitor<'_> {
+    fn add_model_reference(&mut self, expr: &Expr) {
+        if let Expr::StringLiteral(s) = expr {
+            let model = s.value.to_str();
+            let module_path = if filesystem::is_project_import(self.source_roots, model) {
+                Some(model)
+            } else {
+                self.models.class(model)
+            };
+            if let Some(module_path) = module_path {
+                self.references.push(SourceCodeReference::new(
+                    module_path.to_string(),
+                    s.range.start(),
+                    "relationship()",
+                ));
+            }
+        }
+    }
+
+    fn add_table_reference(&mut self, expr: &Expr, is_column: bool) {
+        if let Expr::StringLiteral(s) = expr {
+            // Column targets are written as '[schema.]table.column'
+            let mut parts = s.value.to_str().rsplit('.');
+            if is_column {
+                parts.next();
+            }
+            if let Some(module_path) = parts.next().and_then(|table| self.models.table(table)) {
+                self.references.push(SourceCodeReference::new(
+                    module_path.to_string(),
+                    s.range.start(),
+                    "foreign key",
+                ));
+            }
+        }
+    }
+}
+
+impl Visitor<'_> for RelationshipVisitor<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Call(call_expr) = expr {
+            match call_name(expr) {
+                Some("relationship") => {
+                    // Target model is the first positional argument, or the 'argument' keyword argument
+                    if let Some(first_arg) = call_expr.arguments.args.first() {
+                        self.add_model_reference(first_arg);
+                    } else if let Some(keyword) = call_expr.arguments.find_keyword("argument") {
+                        self.add_model_reference(&keyword.value);
+                    }
+                    if let Some(keyword) = call_expr.arguments.find_keyword("secondary") {
+                        self.add_table_reference(&keyword.value, false);
+                    }
+                }
+                Some("ForeignKey") => {
+                    if let Some(first_arg) = call_expr.arguments.args.first() {
+                        self.add_table_reference(first_arg, true);
+                    } else if let Some(keyword) = call_expr.arguments.find_keyword("column") {
+                        self.add_table_reference(&keyword.value, true);
+                    }
+                }
+                _ => {}
+            }
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find SQLAlchemy models and tables referenced by string in 'relationship' and 'ForeignKey'.
+pub fn get_relationship_references(
+    source_roots: &[PathBuf],
+    models: &SqlAlchemyModels,
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = RelationshipVisitor {
+        source_roots,
+        models,
+        references: vec![],
+    };
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference_paths(models: &SqlAlchemyModels, source: &str) -> Vec<String> {
+        let file_ast = parse_python_source(source).unwrap();
+        get_relationship_references(&[], models, &file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect()
+    }
+
+    #[test]
+    fn test_relationship_references() {
+        let mut models = SqlAlchemyModels::default();
+        let module = parse_python_source(
+            r#"
+class User(Base):
+    __tablename__ = "users"
+
+association = Table("user_groups", metadata)
+"#,
+        )
+        .unwrap();
+        if let Mod::Module(module) = module {
+            ModelDefinitionVisitor {
+                file_mod_path: "app.models",
+                models: &mut models,
+            }
+            .visit_body(&module.body);
+        }
+
+        assert_eq!(
+            reference_paths(
+                &models,
+                r#"
+class Group(Base):
+    owner_id = Column(ForeignKey("users.id"))
+    owner = relationship("User", secondary="user_groups")
+    other = relationship("Unknown")
+"#
+            ),
+            [
+                "app.models.User",
+                "app.models.User",
+                "app.models.association"
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use ruff_python_ast::visitor::{walk_annotation, walk_expr, Visitor};
+use ruff_python_ast::{Expr, Mod};
+use ruff_text_size::TextSize;
+
+use crate::filesystem;
+use crate::processors::reference::SourceCodeReference;
+
+static DOTTED_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z_]\w*(?:\.[A-Za-z_]\w*)+").unwrap());
+
+struct ForwardRefVisitor<'a> {
+    source_roots: &'a [PathBuf],
+    in_annotation: bool,
+    pub references: Vec<SourceCodeReference>,
+}
+
+impl ForwardRefVisitor<'_> {
+    fn add_references(&mut self, forward_ref: &str, offset: TextSize) {
+        // A forward reference is itself an expression (e.g. 'Optional[pkg.mod.Type]'),
+        // so every dotted name within it may refer to a project module
+        for dotted_name in DOTTED_NAME_REGEX.find_iter(forward_ref) {
+            if filesystem::is_project_import(self.source_roots, dotted_name.as_str()) {
+                self.references.push(SourceCodeReference::new(
+                    dotted_name.as_str().to_string(),
+                    offset,
+                    "forward reference",
+                ));
+            }
+        }
+    }
+}
+
+impl Visitor<'_> for ForwardRefVisitor<'_> {
+    fn visit_annotation(&mut self, expr: &Expr) {
+        let was_in_annotation = self.in_annotation;
+        self.in_annotation = true;
+        walk_annotation(self, expr);
+        self.in_annotation = was_in_annotation;
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::StringLiteral(s) if self.in_annotation => {
+                self.add_references(s.value.to_str(), s.range.start());
+            }
+            Expr::Call(call_expr) => {
+                let is_forward_ref = match &*call_expr.func {
+                    // Match direct name (ForwardRef)
+                    Expr::Name(name) => name.id.as_str() == "ForwardRef",
+                    // Match attribute access (typing.ForwardRef)
+                    Expr::Attribute(attr) => attr.attr.as_str() == "ForwardRef",
+                    _ => false,
+                };
+                if is_forward_ref {
+                    if let Some(Expr::StringLiteral(s)) = call_expr.arguments.args.first() {
+                        self.add_references(s.value.to_str(), s.range.start());
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Find project modules named in string annotations and 'ForwardRef' calls.
+pub fn get_forward_references(
+    source_roots: &[PathBuf],
+    file_ast: &Mod,
+) -> impl IntoIterator<Item = SourceCodeReference> {
+    let mut visitor = ForwardRefVisitor {
+        source_roots,
+        in_annotation: false,
+        references: vec![],
+    };
+
+    if let Mod::Module(module) = file_ast {
+        visitor.visit_body(&module.body);
+    }
+
+    visitor.references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+    use crate::tests::fixtures::example_dir;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_forward_references(example_dir: PathBuf) {
+        let source_roots = vec![example_dir.join("valid")];
+        let file_ast = parse_python_source(
+            r#"
+def handler(value: "domain_two.other") -> "Optional[domain_two.some_file]": ...
+
+class Model:
+    field: "list[unknown.Type]"
+    other = ForwardRef("domain_two.other")
+    not_an_annotation = "domain_two.other"
+"#,
+        )
+        .unwrap();
+
+        let references: Vec<String> = get_forward_references(&source_roots, &file_ast)
+            .into_iter()
+            .map(|reference| reference.module_path)
+            .collect();
+        assert_eq!(
+            references,
+            [
+                "domain_two.other",
+                "domain_two.some_file",
+                "domain_two.other"
+            ]
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod forward_ref;
@@ -0,0 +1,161 @@
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+// PEP 263: https://peps.python.org/pep-0263/
+static CODING_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[ \t\x0C]*#.*?coding[:=][ \t]*([-\w.]+)").unwrap());
+
+// The characters of cp1252 (Windows-1252) which differ from latin-1, for bytes 0x80 to 0x9F.
+// Undefined bytes decode to the control character with the same value, as in latin-1.
+const CP1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{8D}', '\u{017D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{9D}', '\u{017E}', '\u{0178}',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SourceEncoding {
+    Utf8,
+    Latin1,
+    Cp1252,
+}
+
+impl SourceEncoding {
+    fn from_name(name: &str) -> Option<Self> {
+        // Python ignores case, and treats '_' and '-' alike
+        let name = name.to_lowercase().replace('_', "-");
+        match name.as_str() {
+            "utf-8" | "utf8" | "utf-8-sig" | "u8" | "ascii" | "us-ascii" => Some(Self::Utf8),
+            "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" | "iso-latin-1" | "l1" | "cp819" => {
+                Some(Self::Latin1)
+            }
+            "cp1252" | "windows-1252" => Some(Self::Cp1252),
+            _ => None,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Self::Utf8 => std::str::from_utf8(bytes).ok().map(str::to_string),
+            Self::Latin1 => Some(bytes.iter().map(|&byte| char::from(byte)).collect()),
+            Self::Cp1252 => Some(
+                bytes
+                    .iter()
+                    .map(|&byte| match byte {
+                        0x80..=0x9F => CP1252_HIGH[usize::from(byte - 0x80)],
+                        _ => char::from(byte),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The encoding declared by a PEP 263 comment on the first or second line of the source.
+fn declared_encoding(bytes: &[u8]) -> Option<&str> {
+    let mut lines = bytes.split(|&byte| byte == b'\n');
+    let first_line = lines.next()?;
+    let candidates = if first_line.trim_ascii_start().starts_with(b"#") {
+        // The declaration may only be on the second line if the first is a comment
+        vec![first_line, lines.next().unwrap_or_default()]
+    } else if first_line.trim_ascii().is_empty() {
+        vec![lines.next().unwrap_or_default()]
+    } else {
+        vec![first_line]
+    };
+    candidates.into_iter().find_map(|line| {
+        CODING_REGEX
+            .captures(line)
+            .and_then(|captures| std::str::from_utf8(captures.get(1)?.as_bytes()).ok())
+    })
+}
+
+/// Decode the bytes of a Python source file to UTF-8, as the Python interpreter would.
+/// A UTF-8 byte order mark is removed, and otherwise a PEP 263 encoding declaration is respected.
+/// Sources declaring an encoding which is not supported here are read as strict UTF-8,
+/// which covers the common case of ASCII sources with a declaration.
+pub fn decode_python_source(bytes: Vec<u8>) -> Result<String, String> {
+    if let Some(content) = bytes.strip_prefix(UTF8_BOM) {
+        return String::from_utf8(content.to_vec())
+            .map_err(|e| format!("Source with a UTF-8 byte order mark is not UTF-8: {}", e));
+    }
+    let Some(name) = declared_encoding(&bytes) else {
+        return String::from_utf8(bytes)
+            .map_err(|e| format!("Source is not UTF-8 and has no encoding declaration: {}", e));
+    };
+    let Some(encoding) = SourceEncoding::from_name(name) else {
+        return String::from_utf8(bytes).map_err(|_| {
+            format!(
+                "Unsupported source encoding '{}', and source is not UTF-8.",
+                name
+            )
+        });
+    };
+    encoding
+        .decode(&bytes)
+        .ok_or_else(|| format!("Source is not valid '{}'.", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_utf8() {
+        let source = "import os\nname = 'café'\n";
+        assert_eq!(
+            decode_python_source(source.as_bytes().to_vec()).unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    fn test_decode_bom() {
+        let mut bytes = UTF8_BOM.to_vec();
+        bytes.extend_from_slice(b"import os\n");
+        assert_eq!(decode_python_source(bytes).unwrap(), "import os\n");
+    }
+
+    #[test]
+    fn test_decode_latin1() {
+        let bytes = b"# -*- coding: latin-1 -*-\nname = 'caf\xE9'\n".to_vec();
+        assert_eq!(
+            decode_python_source(bytes).unwrap(),
+            "# -*- coding: latin-1 -*-\nname = 'café'\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_declaration_on_second_line() {
+        let bytes =
+            b"#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\nx = '\x80'\n".to_vec();
+        assert!(decode_python_source(bytes).unwrap().ends_with("x = '€'\n"));
+    }
+
+    #[test]
+    fn test_declaration_after_code_is_ignored() {
+        let bytes = b"import os\n# coding: latin-1\nname = 'caf\xE9'\n".to_vec();
+        assert!(decode_python_source(bytes).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_encoding_with_ascii_source() {
+        let source = "# coding: iso-8859-15\nimport os\n";
+        assert_eq!(
+            decode_python_source(source.as_bytes().to_vec()).unwrap(),
+            source
+        );
+    }
+
+    #[test]
+    fn test_unsupported_encoding_with_non_utf8_source() {
+        let bytes = b"# coding: shift_jis\nname = '\x83J'\n".to_vec();
+        assert_eq!(
+            decode_python_source(bytes).unwrap_err(),
+            "Unsupported source encoding 'shift_jis', and source is not UTF-8."
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod encoding;
 pub mod error;
 pub mod parsing;
+pub mod syntax;
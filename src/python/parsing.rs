@@ -4,10 +4,11 @@ use ruff_python_ast::{
     statement_visitor::{walk_stmt, StatementVisitor},
     Expr, Mod, Stmt,
 };
-use ruff_python_parser::{parse, Mode};
+use ruff_python_parser::{parse, parse_unchecked, Mode};
+use ruff_text_size::{Ranged, TextSize};
 
 use super::error::ParsingError;
-use crate::filesystem::module_to_file_path;
+use crate::filesystem::{module_to_file_path, read_file_content};
 
 pub type Result<T> = std::result::Result<T, ParsingError>;
 
@@ -16,6 +17,31 @@ pub fn parse_python_source(python_source: &str) -> Result<Mod> {
     Ok(parse(python_source, Mode::Module)?.syntax().to_owned())
 }
 
+/// The first syntax error found while parsing a Python source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub message: String,
+    pub offset: TextSize,
+}
+
+/// Parse a Python source file, recovering from syntax errors.
+/// When the source is invalid, the AST only contains the top-level statements
+/// which end before the first syntax error.
+pub fn parse_python_source_partial(python_source: &str) -> (Mod, Option<SyntaxError>) {
+    let parsed = parse_unchecked(python_source, Mode::Module);
+    let syntax_error = parsed.errors().first().map(|error| SyntaxError {
+        message: error.error.to_string(),
+        offset: error.location.start(),
+    });
+    let mut ast = parsed.into_syntax();
+    if let (Some(syntax_error), Mod::Module(module)) = (&syntax_error, &mut ast) {
+        module
+            .body
+            .retain(|stmt| stmt.range().end() <= syntax_error.offset);
+    }
+    (ast, syntax_error)
+}
+
 struct InterfaceVisitor {
     members: Vec<String>,
 }
@@ -42,9 +68,37 @@ impl StatementVisitor<'_> for InterfaceVisitor {
     }
 }
 
+/// Count the public (not underscore-prefixed) functions, classes and variables defined
+/// at the top level of a Python source file.
+pub fn count_public_symbols(python_source: &str) -> Result<usize> {
+    let ast = match parse_python_source(python_source)? {
+        Mod::Module(ast) => ast,
+        Mod::Expression(_) => panic!("Expected ast::Mod variant"),
+    };
+    let is_public = |name: &str| !name.starts_with('_');
+    let is_public_target =
+        |target: &Expr| matches!(target, Expr::Name(name) if is_public(name.id.as_str()));
+
+    Ok(ast
+        .body
+        .iter()
+        .map(|stmt| match stmt {
+            Stmt::FunctionDef(node) => usize::from(is_public(node.name.as_str())),
+            Stmt::ClassDef(node) => usize::from(is_public(node.name.as_str())),
+            Stmt::Assign(node) => node
+                .targets
+                .iter()
+                .filter(|target| is_public_target(target))
+                .count(),
+            Stmt::AnnAssign(node) => usize::from(is_public_target(&node.target)),
+            _ => 0,
+        })
+        .sum())
+}
+
 pub fn parse_interface_members(source_roots: &[PathBuf], path: &str) -> Result<Vec<String>> {
     if let Some(resolved_mod) = module_to_file_path(source_roots, path, false) {
-        let python_source = std::fs::read_to_string(resolved_mod.file_path)?;
+        let python_source = read_file_content(resolved_mod.file_path)?;
         let ast = match parse_python_source(&python_source)? {
             Mod::Module(ast) => ast,
             Mod::Expression(_) => panic!("Expected ast::Mod variant"),
@@ -60,3 +114,51 @@ pub fn parse_interface_members(source_roots: &[PathBuf], path: &str) -> Result<V
         Ok(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_public_symbols() {
+        let source = r#"
+import os
+from typing import Any
+
+__all__ = ["get_user"]
+VERSION = "1.0"
+_cache: dict = {}
+timeout: int = 5
+
+def get_user(): ...
+def _helper(): ...
+
+class User:
+    def name(self): ...
+
+class _Private: ...
+"#;
+        assert_eq!(count_public_symbols(source).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_parse_python_source_partial() {
+        let source = "import os\nfrom a import b\n\ndef broken(:\n    pass\n\nimport sys\n";
+        let (ast, syntax_error) = parse_python_source_partial(source);
+        let module = match ast {
+            Mod::Module(module) => module,
+            Mod::Expression(_) => panic!("Expected ast::Mod variant"),
+        };
+        assert_eq!(module.body.len(), 2);
+        // The error is on the 'def' line
+        let prefix = &source[..usize::from(syntax_error.unwrap().offset)];
+        assert_eq!(prefix.matches('\n').count(), 3);
+    }
+
+    #[test]
+    fn test_parse_python_source_partial_valid() {
+        let (ast, syntax_error) = parse_python_source_partial("import os\nimport sys\n");
+        assert!(syntax_error.is_none());
+        assert!(matches!(ast, Mod::Module(module) if module.body.len() == 2));
+    }
+}
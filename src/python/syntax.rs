@@ -0,0 +1,171 @@
+use ruff_python_ast::visitor::{self, Visitor};
+use ruff_python_ast::{Expr, Mod, Parameters, Stmt};
+use ruff_text_size::{Ranged, TextSize};
+
+use crate::config::PythonVersion;
+
+/// A use of syntax which is newer than the target Python version
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedSyntax {
+    pub feature: &'static str,
+    pub minimum_version: PythonVersion,
+    pub offset: TextSize,
+}
+
+struct SyntaxVersionVisitor {
+    python_version: PythonVersion,
+    unsupported: Vec<UnsupportedSyntax>,
+}
+
+impl SyntaxVersionVisitor {
+    fn require(&mut self, feature: &'static str, minimum_version: PythonVersion, offset: TextSize) {
+        if self.python_version < minimum_version {
+            self.unsupported.push(UnsupportedSyntax {
+                feature,
+                minimum_version,
+                offset,
+            });
+        }
+    }
+
+    fn visit_signature(&mut self, parameters: &Parameters) {
+        if let Some(parameter) = parameters.posonlyargs.first() {
+            self.require(
+                "Positional-only parameters",
+                PythonVersion::new(3, 8),
+                parameter.start(),
+            );
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for SyntaxVersionVisitor {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Match(_) => {
+                self.require("Match statements", PythonVersion::new(3, 10), stmt.start())
+            }
+            Stmt::TypeAlias(_) => self.require(
+                "Type alias statements",
+                PythonVersion::new(3, 12),
+                stmt.start(),
+            ),
+            Stmt::Try(node) if node.is_star => {
+                self.require("'except*' clauses", PythonVersion::new(3, 11), stmt.start())
+            }
+            Stmt::FunctionDef(node) => {
+                if let Some(type_params) = &node.type_params {
+                    self.require(
+                        "Type parameter lists",
+                        PythonVersion::new(3, 12),
+                        type_params.start(),
+                    );
+                }
+                self.visit_signature(&node.parameters);
+            }
+            Stmt::ClassDef(node) => {
+                if let Some(type_params) = &node.type_params {
+                    self.require(
+                        "Type parameter lists",
+                        PythonVersion::new(3, 12),
+                        type_params.start(),
+                    );
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Named(_) => self.require(
+                "Assignment expressions",
+                PythonVersion::new(3, 8),
+                expr.start(),
+            ),
+            Expr::Lambda(node) => {
+                if let Some(parameters) = &node.parameters {
+                    self.visit_signature(parameters);
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+/// Find syntax in a parsed file which requires a newer Python version than 'python_version'.
+/// The parser always accepts the newest syntax, so that the rest of the file is still checked.
+pub fn find_unsupported_syntax(ast: &Mod, python_version: PythonVersion) -> Vec<UnsupportedSyntax> {
+    let mut visitor = SyntaxVersionVisitor {
+        python_version,
+        unsupported: Vec::new(),
+    };
+    if let Mod::Module(module) = ast {
+        visitor.visit_body(&module.body);
+    }
+    visitor.unsupported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python::parsing::parse_python_source;
+
+    fn features(source: &str, python_version: PythonVersion) -> Vec<&'static str> {
+        let ast = parse_python_source(source).unwrap();
+        find_unsupported_syntax(&ast, python_version)
+            .into_iter()
+            .map(|unsupported| unsupported.feature)
+            .collect()
+    }
+
+    const SOURCE: &str = r#"
+type Alias = list[int]
+
+def first[T](items: list[T], /) -> T:
+    if (n := len(items)) > 0:
+        return items[0]
+
+match command:
+    case "go":
+        pass
+
+try:
+    pass
+except* ValueError:
+    pass
+"#;
+
+    #[test]
+    fn test_find_unsupported_syntax() {
+        assert_eq!(
+            features(SOURCE, PythonVersion::new(3, 7)),
+            vec![
+                "Type alias statements",
+                "Type parameter lists",
+                "Positional-only parameters",
+                "Assignment expressions",
+                "Match statements",
+                "'except*' clauses",
+            ]
+        );
+        assert_eq!(
+            features(SOURCE, PythonVersion::new(3, 10)),
+            vec![
+                "Type alias statements",
+                "Type parameter lists",
+                "'except*' clauses",
+            ]
+        );
+        assert!(features(SOURCE, PythonVersion::new(3, 12)).is_empty());
+    }
+
+    #[test]
+    fn test_soft_keywords_as_names() {
+        // 'match' and 'type' are only keywords in their statements
+        let source = "match = 1\ntype = str\nprint(match, type)\n";
+        assert!(features(source, PythonVersion::new(3, 7)).is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+use std::io;
+use thiserror::Error;
+
+use crate::commands::check::CheckError;
+use crate::filesystem::FileSystemError;
+use crate::modules::error::ModuleTreeError;
+
+#[derive(Error, Debug)]
+pub enum RpcServerError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Filesystem error: {0}")]
+    Filesystem(#[from] FileSystemError),
+    #[error("Failed to serialize response: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("Failed to lint files: {0}")]
+    Lint(#[from] CheckError),
+    #[error("Module tree error: {0}")]
+    ModuleTree(#[from] ModuleTreeError),
+}
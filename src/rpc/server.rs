@@ -0,0 +1,489 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Component, PathBuf};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+
+use crate::commands::check::{check_internal_with_callback, CheckScope};
+use crate::config::ProjectConfig;
+use crate::filesystem::{file_to_module_path, validate_project_modules};
+use crate::interrupt::check_interrupt;
+use crate::modules::{build_module_tree, ModuleTree};
+
+use super::error::RpcServerError;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+// Distinguishes an explicit 'null' id (Some(Value::Null)) from a missing one (None)
+fn deserialize_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    // Requests without an id are notifications, which are never answered
+    #[serde(default, deserialize_with = "deserialize_id")]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcErrorObject>,
+}
+
+impl RpcResponse {
+    fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn failure(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(RpcErrorObject {
+                code,
+                message: message.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckFilesParams {
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PathParams {
+    path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModuleParams {
+    module: String,
+}
+
+/// A line-delimited JSON-RPC 2.0 server over stdio.
+///
+/// Each request and response is a single JSON object on its own line.
+/// Notifications (requests without an 'id') are handled, but never answered.
+/// Supported methods: 'check_files', 'get_module_for_path', 'get_dependencies', 'shutdown'.
+pub struct RpcServer {
+    project_root: PathBuf,
+    project_config: ProjectConfig,
+}
+
+impl RpcServer {
+    pub fn new(project_root: PathBuf, project_config: ProjectConfig) -> Self {
+        Self {
+            project_root,
+            project_config,
+        }
+    }
+
+    pub fn run(&self) -> Result<(), RpcServerError> {
+        eprintln!(
+            "Starting JSON-RPC server @ project root: {}",
+            self.project_root.display()
+        );
+        self.serve(io::stdin().lock(), io::stdout())?;
+        eprintln!("JSON-RPC server shutting down");
+        Ok(())
+    }
+
+    /// Answer each request read from 'input' on 'output', until a 'shutdown' request or the end of input.
+    pub fn serve(&self, input: impl BufRead, mut output: impl Write) -> Result<(), RpcServerError> {
+        for line in input.lines() {
+            if check_interrupt().is_err() {
+                break;
+            }
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, should_exit) = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => {
+                    // The id of an invalid request is still echoed back when it can be read
+                    let id = message.get("id").cloned().unwrap_or(Value::Null);
+                    match serde_json::from_value::<RpcRequest>(message) {
+                        Ok(request) => {
+                            let should_exit = request.method == "shutdown";
+                            (self.handle_request(request), should_exit)
+                        }
+                        Err(e) => (
+                            Some(RpcResponse::failure(id, INVALID_REQUEST, e.to_string())),
+                            false,
+                        ),
+                    }
+                }
+                Err(e) => (
+                    Some(RpcResponse::failure(
+                        Value::Null,
+                        PARSE_ERROR,
+                        e.to_string(),
+                    )),
+                    false,
+                ),
+            };
+
+            if let Some(response) = response {
+                writeln!(output, "{}", serde_json::to_string(&response)?)?;
+                output.flush()?;
+            }
+
+            if should_exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: RpcRequest) -> Option<RpcResponse> {
+        let result = match request.method.as_str() {
+            "check_files" => parse_params(request.params).map(|params| self.check_files(params)),
+            "get_module_for_path" => {
+                parse_params(request.params).map(|params| self.get_module_for_path(params))
+            }
+            "get_dependencies" => {
+                parse_params(request.params).map(|params| self.get_dependencies(params))
+            }
+            "shutdown" => Ok(Ok(Value::Null)),
+            method => {
+                let message = format!("Unknown method: '{}'", method);
+                return request
+                    .id
+                    .map(|id| RpcResponse::failure(id, METHOD_NOT_FOUND, message));
+            }
+        };
+
+        let id = request.id?;
+        Some(match result {
+            Ok(Ok(value)) => RpcResponse::success(id, value),
+            Ok(Err(e)) => RpcResponse::failure(id, INTERNAL_ERROR, e.to_string()),
+            Err(e) => RpcResponse::failure(id, INVALID_PARAMS, e.to_string()),
+        })
+    }
+
+    /// Resolve a requested path against the project root. '.' and '..' are resolved lexically,
+    /// so that 'pkg/../pkg/x.py' names the same file as 'pkg/x.py' even if it does not exist.
+    fn absolute_path(&self, path: PathBuf) -> PathBuf {
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.project_root.join(path)
+        };
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                _ => normalized.push(component),
+            }
+        }
+        normalized
+    }
+
+    fn module_tree(&self) -> Result<ModuleTree, RpcServerError> {
+        let source_roots = self.project_config.prepend_roots(&self.project_root);
+        let (valid_modules, _) = validate_project_modules(
+            &source_roots,
+            self.project_config.all_modules().cloned().collect(),
+        );
+        Ok(build_module_tree(
+            &source_roots,
+            &valid_modules,
+            false,
+            self.project_config.root_module.clone(),
+        )?)
+    }
+
+    fn check_files(&self, params: CheckFilesParams) -> Result<Value, RpcServerError> {
+        // An empty scope would check the whole project, but no files were requested
+        if params.paths.is_empty() {
+            return Ok(json!([]));
+        }
+        let paths: Vec<PathBuf> = params
+            .paths
+            .into_iter()
+            .map(|path| self.absolute_path(path))
+            .collect();
+        // Only the requested files are checked, with the same exclusions as 'tach check'
+        let diagnostics: Vec<_> = check_internal_with_callback(
+            self.project_root.clone(),
+            &self.project_config,
+            true,
+            true,
+            [
+                self.project_config.exclude.clone(),
+                self.project_config.source_root_exclude_paths(),
+            ]
+            .concat(),
+            &CheckScope::default().with_files(paths),
+            None::<fn(usize)>,
+            |_, _| true,
+        )?
+        .into_iter()
+        .filter(|diagnostic| diagnostic.file_path().is_some())
+        .collect();
+        Ok(serde_json::to_value(diagnostics)?)
+    }
+
+    fn get_module_for_path(&self, params: PathParams) -> Result<Value, RpcServerError> {
+        let source_roots = self.project_config.prepend_roots(&self.project_root);
+        let file_mod_path = file_to_module_path(&source_roots, &self.absolute_path(params.path))?;
+        let module_tree = self.module_tree()?;
+        Ok(match module_tree.find_nearest(&file_mod_path) {
+            Some(module) => json!({
+                "module": module.full_path,
+                "file_module_path": file_mod_path,
+                "layer": module.config.as_ref().and_then(|config| config.layer.clone()),
+            }),
+            None => Value::Null,
+        })
+    }
+
+    fn get_dependencies(&self, params: ModuleParams) -> Result<Value, RpcServerError> {
+        Ok(
+            match self.project_config.dependencies_for_module(&params.module) {
+                Some(dependencies) => Value::Array(
+                    dependencies
+                        .iter()
+                        .map(|dep| json!({"path": dep.path, "deprecated": dep.deprecated}))
+                        .collect(),
+                ),
+                None => Value::Null,
+            },
+        )
+    }
+}
+
+fn parse_params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, serde_json::Error> {
+    serde_json::from_value(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+    use crate::parsing::config::parse_project_config;
+
+    fn create_project() -> tempfile::TempDir {
+        // Hidden directories (like the default '.tmp' prefix) are not searched for Python files
+        let project = tempfile::Builder::new()
+            .prefix("project")
+            .tempdir()
+            .unwrap();
+        let root = project.path();
+        for module in ["a", "b", "c"] {
+            fs::create_dir_all(root.join(module)).unwrap();
+            fs::write(root.join(module).join("__init__.py"), "").unwrap();
+        }
+        fs::create_dir_all(root.join("a/generated")).unwrap();
+        fs::write(root.join("a/service.py"), "import b\nimport c\n").unwrap();
+        fs::write(root.join("a/other.py"), "import c\n").unwrap();
+        fs::write(root.join("a/generated/client.py"), "import c\n").unwrap();
+        fs::write(
+            root.join("tach.toml"),
+            concat!(
+                "exclude = [\"**/generated\"]\n",
+                "[[modules]]\npath = \"a\"\ndepends_on = [\"b\"]\n",
+                "[[modules]]\npath = \"b\"\ndepends_on = []\n",
+                "[[modules]]\npath = \"c\"\ndepends_on = []\n",
+            ),
+        )
+        .unwrap();
+        project
+    }
+
+    fn serve(project_root: &Path, requests: &[&str]) -> Vec<Value> {
+        let (project_config, _) = parse_project_config(project_root.join("tach.toml")).unwrap();
+        let server = RpcServer::new(project_root.to_path_buf(), project_config);
+        let mut output = Vec::new();
+        server
+            .serve(requests.join("\n").as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_check_files() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "check_files", "params": {"paths": ["a/service.py"]}}"#,
+            ],
+        );
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], 1);
+        let diagnostics = responses[0]["result"].as_array().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = diagnostics[0].to_string();
+        assert!(diagnostic.contains("a/service.py"));
+        assert!(!diagnostic.contains("a/other.py"));
+    }
+
+    #[test]
+    fn test_check_files_respects_exclusions() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "check_files", "params": {"paths": ["a/service.py", "a/other.py", "a/generated/client.py"]}}"#,
+            ],
+        );
+        let diagnostics = responses[0]["result"].to_string();
+        assert!(diagnostics.contains("a/service.py"));
+        assert!(diagnostics.contains("a/other.py"));
+        assert!(!diagnostics.contains("a/generated"));
+    }
+
+    #[test]
+    fn test_check_files_without_paths() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[r#"{"jsonrpc": "2.0", "id": 1, "method": "check_files", "params": {"paths": []}}"#],
+        );
+        assert_eq!(responses[0]["result"], json!([]));
+    }
+
+    #[test]
+    fn test_check_files_normalizes_paths() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "check_files", "params": {"paths": ["./a/service.py"]}}"#,
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "check_files", "params": {"paths": ["a/../a/service.py"]}}"#,
+            ],
+        );
+        for response in &responses {
+            let diagnostics = response["result"].as_array().unwrap();
+            assert_eq!(diagnostics.len(), 1);
+            assert!(diagnostics[0].to_string().contains("a/service.py"));
+        }
+    }
+
+    #[test]
+    fn test_get_module_for_path() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "get_module_for_path", "params": {"path": "a/service.py"}}"#,
+            ],
+        );
+        assert_eq!(responses[0]["result"]["module"], "a");
+        assert_eq!(responses[0]["result"]["file_module_path"], "a.service");
+    }
+
+    #[test]
+    fn test_get_dependencies() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": "deps", "method": "get_dependencies", "params": {"module": "a"}}"#,
+                r#"{"jsonrpc": "2.0", "id": "missing", "method": "get_dependencies", "params": {"module": "d"}}"#,
+            ],
+        );
+        assert_eq!(responses[0]["id"], "deps");
+        assert_eq!(
+            responses[0]["result"],
+            json!([{"path": "b", "deprecated": false}])
+        );
+        assert_eq!(responses[1]["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_errors() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                "not json",
+                r#"{"jsonrpc": "2.0", "id": 1}"#,
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "unknown"}"#,
+                r#"{"jsonrpc": "2.0", "id": 3, "method": "get_dependencies", "params": {}}"#,
+            ],
+        );
+        let errors: Vec<(&Value, i64)> = responses
+            .iter()
+            .map(|response| (&response["id"], response["error"]["code"].as_i64().unwrap()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (&Value::Null, PARSE_ERROR),
+                (&json!(1), INVALID_REQUEST),
+                (&json!(2), METHOD_NOT_FOUND),
+                (&json!(3), INVALID_PARAMS),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notifications_are_not_answered() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "method": "get_dependencies", "params": {"module": "a"}}"#,
+                r#"{"jsonrpc": "2.0", "method": "unknown"}"#,
+                r#"{"jsonrpc": "2.0", "id": null, "method": "get_dependencies", "params": {"module": "a"}}"#,
+            ],
+        );
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["id"], Value::Null);
+    }
+
+    #[test]
+    fn test_shutdown() {
+        let project = create_project();
+        let responses = serve(
+            project.path(),
+            &[
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "shutdown"}"#,
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "get_dependencies", "params": {"module": "a"}}"#,
+            ],
+        );
+        assert_eq!(
+            responses,
+            vec![json!({"jsonrpc": "2.0", "id": 1, "result": null})]
+        );
+    }
+}